@@ -0,0 +1,399 @@
+# English messages, the reference catalog every other language is checked against
+
+## Shared
+
+close = Close
+cancel = Cancel
+rename = Rename
+delete = Delete
+import = Import
+tab-body = Body
+tab-headers = Headers
+remove = Remove
+stop = Stop
+inspect-token = Inspect Token
+check-connectivity = Check Connectivity
+
+## Welcome screen
+
+welcome-title = Welcome to Beam
+welcome-subtitle = Collections group your requests. How would you like to start?
+welcome-sample = Sample collection
+welcome-sample-description = A few httpbin.org requests showing bodies, auth and status codes.
+welcome-import-description = Bring in a Postman, OpenAPI or Beam export from a URL.
+welcome-blank = Start blank
+welcome-blank-description = An empty collection to add your own requests to.
+
+## Settings
+
+settings-title = Settings
+settings-theme = Theme
+settings-theme-system = System default
+settings-open-themes-folder = Open Themes Folder
+settings-themes-hint = Themes are TOML files in ~/.beam/themes/ with a [colors] table of background, text, primary, success, danger and warning hex colors. Changes to them apply right away.
+settings-theme-missing = The selected theme is missing, the system default is used until it's back.
+settings-language = Language
+settings-language-system = System language
+settings-accessibility = Accessibility
+settings-large-targets = Larger click targets and higher contrast
+settings-tray = Tray Icon
+settings-show-tray = Show an icon in the system tray / menu bar
+settings-tray-hint = Pin requests from their menu in the sidebar to send them from the icon's menu, the outcome shows as a notification.
+settings-tray-unsupported = This build of Beam has no tray icon.
+settings-usage = Usage Statistics
+settings-usage-stats = Show usage statistics
+settings-usage-hint = Most used requests and latency per host, computed on this computer from the run history of your requests. Nothing is sent anywhere.
+settings-open-usage = Open Usage Statistics
+settings-json = JSON Bodies
+settings-json-on-save = Format the JSON body on save
+settings-json-on-send = Format the JSON body before sending
+settings-json-off = Leave as is
+settings-json-pretty = Pretty-print
+settings-json-minify = Minify
+settings-json-block-malformed = Don't send a JSON body that isn't valid JSON
+settings-json-hint = Bodies that aren't valid JSON, such as ones with a variable standing for a value, are never reformatted. The check before sending runs once variables are resolved.
+
+## Audit log
+
+audit-title = Workspace Changes
+audit-empty = No changes yet. Creating, renaming and deleting collections, requests and environments is recorded here.
+audit-older = { $count } older changes are in the audit file
+audit-create-collection = Created collection "{ $name }"
+audit-create-request = Created request "{ $name }"
+audit-create-environment = Created environment "{ $name }"
+audit-rename-collection = Renamed collection "{ $previous }" to "{ $name }"
+audit-rename-request = Renamed request "{ $previous }" to "{ $name }"
+audit-rename-environment = Renamed environment "{ $previous }" to "{ $name }"
+audit-delete-collection = Deleted collection "{ $name }"
+audit-delete-request = Deleted request "{ $name }"
+audit-delete-environment = Deleted environment "{ $name }"
+audit-in-collection = { $summary } in { $collection }
+
+## Collection statistics
+
+stats-title = Statistics of { $name }
+stats-empty = None of these requests has been sent yet, statistics show once they have.
+stats-runs = Runs
+stats-success-rate = Success rate
+stats-average-latency = Average latency
+stats-failures = Failures
+stats-slowest = Slowest Endpoints
+stats-slowest-hint = Average latency of each request, with the slowest run
+stats-runs-by-day = Runs, Last { $days } Days
+stats-runs-by-day-hint = Failed runs (no response, 4xx or 5xx) in red
+usage-title = Usage Statistics
+usage-requests-used = Requests used
+usage-most-used = Most Used Requests
+usage-hosts = Latency per Host
+usage-hint = From the last 20 runs kept with each request, on this computer only.
+
+## Collections sidebar
+
+collections-add-request = Add Request
+collections-add-folder = Add Folder
+collections-import-url = Import from URL
+collections-remote-sync = Remote Sync
+collections-run = Run collection
+collections-export-ci-bundle = Export CI Bundle…
+collections-statistics = Statistics
+collections-cancel-request = Cancel Request
+collections-send-request = Send Request
+collections-copy-curl = Copy as cURL
+collections-copy-curl-secrets = Copy as cURL with Secrets
+collections-export-request = Export Request…
+collections-copy-link = Copy Link
+collections-pin-tray = Pin to Tray
+collections-unpin-tray = Unpin from Tray
+collections-monitor-passing = Monitor passing
+collections-monitor-failing = Monitor failing: { $reason }
+collections-monitor-pending = Monitor not checked yet
+collections-duplicate = Duplicate
+collections-sort-by = Sort by
+collections-sending = Sending
+
+## Environments
+
+environments-title = Environments
+environments-new = New Environment
+environments-active = Active
+environments-hint = Define variables that can be used across your requests
+environments-variables = Variables
+environments-show-values = Show Values
+environments-key = Key
+environments-value = Value
+environments-type = Type
+environments-add-variable = Add Variable
+environments-using-variables = Using Variables
+environments-using-variables-hint = Reference variables in your requests using the syntax: {{variable_name}}
+environments-example = Example: {{base_url}}/api/users
+environments-settings = Environment Settings
+environments-description = Description
+environments-delete = Delete Environment
+environments-none-selected = No environment selected
+environments-none-selected-hint = Select an environment from the sidebar or create a new one
+environments-default-auth = Default Auth
+environments-default-auth-hint = Used by requests whose auth type is Inherit from Environment
+environments-add-to = Add to
+environments-secret = Secret: masked in logs and exports, click to show
+environments-mark-secret = Mark as secret
+environments-description-placeholder = Environment description
+environments-token = Token
+environments-token-placeholder = Bearer token or {{variable}}
+environments-key-name = Key name
+environments-api-key = API key
+environments-api-key-placeholder = API key or {{variable}}
+environments-username = Username
+environments-password = Password
+
+## Request tabs
+
+request-tab-params = Params
+request-tab-auth = Auth
+request-tab-script = Script
+request-tab-assertions = Assertions
+request-tab-settings = Settings
+request-tab-history = History
+
+## Response tabs
+
+response-tab-caching = Caching
+response-tab-request = Request
+response-tab-certificate = Certificate
+response-tab-records = Records
+response-tab-chart = Chart
+response-tab-history = History
+response-tab-tests = Tests { $passed }/{ $total }
+response-tab-examples = Examples
+response-tab-examples-count = Examples { $count }
+
+## Dialogs
+
+modal-send-anyway = Send Anyway
+modal-request-too-large = Request Too Large
+connectivity-check-again = Check Again
+conflict-reload = Reload
+conflict-merge = Merge
+conflict-overwrite = Overwrite
+conflict-title = File Changed on Disk
+new-request-create = Create
+new-request-title = New Request
+import-downloading = Downloading...
+import-url-hint = Link to a Postman collection or environment, an OpenAPI document (JSON) or a Beam export.
+modal-request-too-large-description = This request is { $total } bytes ({ $headers } bytes of headers, { $body } bytes of body), which exceeds the upload limit of { $limit } bytes.
+connectivity-checking = Checking { $target }...
+connectivity-title = Connectivity to { $target }
+conflict-description = "{ $name }" was changed outside Beam, possibly by another Beam window. Reload the version on disk, overwrite it with your changes, or merge both (your edited fields win).
+recovery-title = Beam Closed Unexpectedly
+recovery-description = Beam ran into an error and had to close: { $message }. A report was written to { $path }.
+recovery-restore-hint = Your latest edits to "{ $name }" were kept. Restore them?
+recovery-restore = Restore
+recovery-discard = Discard
+import-file-title = Import { $name }
+import-file-question = Import { $summary }?
+import-file-error = Beam can't import this file: { $error }
+new-request-name = Name
+new-request-name-placeholder = Defaults to the last URL segment
+new-request-request = Request
+new-request-folder = Folder
+rename-folder = Rename Folder
+rename-folder-hint = Enter a new name for the folder:
+rename-request = Rename Request
+rename-request-hint = Enter a new name for the request:
+rename-hint = Enter a new name:
+rename-placeholder = Enter new name...
+connectivity-dns = DNS resolution
+connectivity-tcp = TCP connection
+connectivity-tls = TLS handshake
+connectivity-not-run = Not run
+
+## Accessibility
+
+a11y-show-sidebar = Show sidebar
+a11y-hide-sidebar = Hide sidebar
+a11y-tools = Tools
+a11y-webhook-inbox = Webhook inbox
+a11y-audit-log = Audit log
+a11y-settings = Settings
+a11y-maximize = Maximize
+a11y-restore = Restore
+a11y-response-below = Move response below
+a11y-response-right = Move response to the right
+a11y-send = Send request
+a11y-cancel = Cancel request
+a11y-method = Change method
+a11y-format-body = Format body
+a11y-remove-queued = Remove from queue
+
+## Drafts
+
+draft-unsaved = Unsaved changes, the saved request is kept until you save
+draft-save = Save
+draft-revert = Revert to Saved
+
+## History
+
+history-loading = Loading versions…
+history-empty = No versions yet. One is kept each time the request is saved.
+history-count = { $count } saved versions, the latest first
+history-latest = Latest
+history-restore = Restore
+history-first = First saved version
+history-no-changes = Same fields as the version before
+
+## Offline queue
+
+queue-retry-all = Retry all
+queue-retry = Retry
+
+## Request editor
+
+request-environment = Environment:
+request-encode-query = Encode Query Values
+request-decode-url = Decode URL
+request-no-body = No body
+request-body-changed = Changed since the request was last sent, the response is for the earlier body
+request-body-too-large-to-compare = The body is too large to compare
+request-path-variables = Path Variables
+request-query-parameters = Query Parameters
+request-auth-type = Authentication Type
+request-auth-none = No authentication required
+request-auth-inherit = Uses the default auth of the active environment, set in the environment editor.
+request-auth-bearer = Bearer Token
+request-auth-basic = Basic Authentication
+request-auth-username = Username
+request-auth-password = Password
+request-auth-api-key = API Key Authentication
+request-auth-add-to = Add to
+request-auth-api-key-value = API Key
+request-auth-hmac = HMAC Signature
+request-auth-algorithm = Algorithm
+request-auth-secret-key = Secret Key
+request-auth-string-to-sign = String to Sign
+request-auth-string-to-sign-hint = Placeholders: {method} {path} {host} {date} {timestamp} {content_type} {body_hash}, \n for newline
+request-auth-header-name = Header Name
+request-auth-header-value = Header Value
+request-auth-hawk = Hawk Authentication
+request-auth-hawk-id = Hawk ID
+request-auth-hawk-key = Hawk Key
+request-auth-hawk-ext = Extra Data (ext)
+request-token-refresh = Token Refresh
+request-token-refresh-hint = Runs another request first when the token is missing or expired, and stores the token it returns.
+request-computed-headers = Computed Headers
+request-no-computed-headers = No headers will be added
+request-assert-status = Status
+request-assert-add-header = Add header check
+request-assert-schema = JSON Schema
+request-snapshot = Snapshot
+request-snapshot-update = Update from Last Response
+request-snapshot-record = Record Last Response
+request-snapshot-ignored = Ignored Paths
+request-snapshot-add-ignored = Add ignored path
+request-contract = OpenAPI Contract
+request-contract-detach = Detach
+request-max-upload = Max Upload Size (KB)
+request-max-download = Max Download Size (KB)
+request-size-limit-hint = Leave empty to disable the limit.
+request-latency-budget = Latency Budget (ms)
+request-latency-budget-hint = Responses slower than this are flagged in the response panel and run history.
+request-security-headers-hint = Grades HSTS, CSP, X-Content-Type-Options and other security headers of responses, with explanations.
+request-monitor-interval = Check Every (seconds)
+request-monitor-status = Expected Status
+request-monitor-hint = Sends the request on an interval while Beam runs, its status shows in the sidebar and a notification tells when it starts failing. Without an expected status the request's assertions decide, or any status below 400 passes.
+request-pagination = Pagination
+request-unix-socket = Unix Socket
+request-unix-socket-hint = When set, the request is sent over this socket instead of TCP. The socket is set per request and not inherited from its collection, use a {{variable}} to share one between requests.
+request-query-encoding-hint = By default characters such as spaces, | or a lone % in the query are percent-encoded before sending.
+request-connection = Connection
+request-accept-encoding = Accept-Encoding
+request-accept-encoding-hint = Uncheck all encodings to stop sending Accept-Encoding.
+request-paste-fill = Fill In Request
+request-paste-keep = Keep as URL
+
+## Response panel
+
+response-docs = Docs
+response-send-conditional = Send Conditional
+response-cancel-retry = Cancel retry
+response-offline = The network appears to be unavailable.
+response-queue-retry = Queue for retry
+response-too-large = The response is larger than the download limit.
+response-download-anyway = Download anyway
+response-more-pages = More pages are available.
+response-next-page = Next page
+response-all-pages = Fetch all pages
+response-no-headers = No headers available
+response-empty = No response yet
+response-empty-hint = Send a request to see the response here
+response-binary = Binary Response
+response-binary-preview = Preview (first 100 bytes as hex):
+response-no-records = The body has no records
+response-no-tests = No tests ran, add assertions or pm.test calls to the request
+response-no-certificate = No certificate, the request was not sent over HTTPS
+response-certificate-valid-until = Valid until
+response-none = None
+response-caching-how = How It Is Cached
+response-caching-notes = Notes
+response-no-request = No request details available
+response-not-sent = This request has not been sent yet
+response-save-example = Save Response as Example
+response-examples-hint = Save responses to keep examples of what this request returns.
+response-mock = Mock
+response-mock-latency = ms latency,
+response-mock-error-rate = % answered with
+
+## Collection runner
+
+runner-environment = Environment
+runner-iteration-data = Iteration data
+runner-mode = Mode
+runner-concurrency = Concurrency
+runner-delay = Delay
+runner-delay-unit = ms between requests
+runner-retries = Retries
+runner-edit-steps = Edit steps
+runner-loop-back = Loop back to
+runner-loop-times = more times
+runner-jump-when = When
+runner-jump-to = go to
+runner-chained = chains variables
+runner-chained-hint = It sets a variable another request uses, or uses one set by another request. Its outcome depends on the order the requests finish in.
+
+## Remote sync
+
+sync-backend = Backend
+sync-syncing = Syncing...
+sync-now = Sync Now
+sync-title = Remote Sync
+sync-hint = Share collections and environments through a WebDAV server or an S3-compatible bucket.
+sync-secrets-warning = The password and secret key are kept in plain text in ~/.beam/sync.toml, readable only by you. Keep that folder out of shared drives and version control.
+
+## Notifications
+
+toast-details = Click for details
+
+## Tools
+
+tools-now = Now
+tools-waiting = Waiting for a reply...
+tools-copy-output = Copy Output
+tools-title = Tools
+tools-jwt-header = Header
+tools-jwt-payload = Payload
+tools-jwt-unverified = The signature is not verified.
+
+## Webhook inbox
+
+webhook-listening = Listening on
+webhook-copy-url = Copy URL
+webhook-port = Port
+webhook-start = Start Listening
+webhook-hint = Every request is answered with 200 and shown here.
+webhook-clear = Clear
+webhook-copy-body = Copy Body
+webhook-title = Webhook Inbox
+
+## Tray icon
+
+tray-show = Show Beam
+tray-empty = Pin requests from their menu in the sidebar
+tray-send = Send { $name }
@@ -0,0 +1,399 @@
+# Mensajes en español
+
+## Shared
+
+close = Cerrar
+cancel = Cancelar
+rename = Renombrar
+delete = Eliminar
+import = Importar
+tab-body = Cuerpo
+tab-headers = Cabeceras
+remove = Quitar
+stop = Detener
+inspect-token = Inspeccionar token
+check-connectivity = Comprobar conectividad
+
+## Welcome screen
+
+welcome-title = Bienvenido a Beam
+welcome-subtitle = Las colecciones agrupan tus peticiones. ¿Cómo quieres empezar?
+welcome-sample = Colección de ejemplo
+welcome-sample-description = Algunas peticiones a httpbin.org que muestran cuerpos, autenticación y códigos de estado.
+welcome-import-description = Trae una exportación de Postman, OpenAPI o Beam desde una URL.
+welcome-blank = Empezar en blanco
+welcome-blank-description = Una colección vacía a la que añadir tus propias peticiones.
+
+## Settings
+
+settings-title = Ajustes
+settings-theme = Tema
+settings-theme-system = Predeterminado del sistema
+settings-open-themes-folder = Abrir carpeta de temas
+settings-themes-hint = Los temas son archivos TOML en ~/.beam/themes/ con una tabla [colors] de colores hexadecimales background, text, primary, success, danger y warning. Sus cambios se aplican al instante.
+settings-theme-missing = Falta el tema seleccionado; se usa el predeterminado del sistema hasta que vuelva.
+settings-language = Idioma
+settings-language-system = Idioma del sistema
+settings-accessibility = Accesibilidad
+settings-large-targets = Áreas de clic más grandes y mayor contraste
+settings-tray = Icono de bandeja
+settings-show-tray = Mostrar un icono en la bandeja del sistema / barra de menús
+settings-tray-hint = Fija peticiones desde su menú en la barra lateral para enviarlas desde el menú del icono, el resultado se muestra como notificación.
+settings-tray-unsupported = Esta versión de Beam no tiene icono de bandeja.
+settings-usage = Estadísticas de uso
+settings-usage-stats = Mostrar estadísticas de uso
+settings-usage-hint = Peticiones más usadas y latencia por host, calculadas en este equipo a partir del historial de ejecuciones de tus peticiones. No se envía nada a ningún sitio.
+settings-open-usage = Abrir estadísticas de uso
+settings-json = Cuerpos JSON
+settings-json-on-save = Formatear el cuerpo JSON al guardar
+settings-json-on-send = Formatear el cuerpo JSON antes de enviar
+settings-json-off = Dejar como está
+settings-json-pretty = Indentar
+settings-json-minify = Minificar
+settings-json-block-malformed = No enviar un cuerpo JSON que no sea JSON válido
+settings-json-hint = Los cuerpos que no son JSON válido, como los que tienen una variable en lugar de un valor, nunca se reformatean. La comprobación antes de enviar se hace una vez resueltas las variables.
+
+## Audit log
+
+audit-title = Cambios del espacio de trabajo
+audit-empty = Aún no hay cambios. Aquí se registra la creación, el cambio de nombre y la eliminación de colecciones, peticiones y entornos.
+audit-older = Hay { $count } cambios anteriores en el archivo de auditoría
+audit-create-collection = Colección «{ $name }» creada
+audit-create-request = Petición «{ $name }» creada
+audit-create-environment = Entorno «{ $name }» creado
+audit-rename-collection = Colección «{ $previous }» renombrada a «{ $name }»
+audit-rename-request = Petición «{ $previous }» renombrada a «{ $name }»
+audit-rename-environment = Entorno «{ $previous }» renombrado a «{ $name }»
+audit-delete-collection = Colección «{ $name }» eliminada
+audit-delete-request = Petición «{ $name }» eliminada
+audit-delete-environment = Entorno «{ $name }» eliminado
+audit-in-collection = { $summary } en { $collection }
+
+## Collection statistics
+
+stats-title = Estadísticas de { $name }
+stats-empty = Ninguna de estas peticiones se ha enviado todavía; las estadísticas aparecerán cuando se envíen.
+stats-runs = Ejecuciones
+stats-success-rate = Tasa de éxito
+stats-average-latency = Latencia media
+stats-failures = Fallos
+stats-slowest = Endpoints más lentos
+stats-slowest-hint = Latencia media de cada petición, con la ejecución más lenta
+stats-runs-by-day = Ejecuciones, últimos { $days } días
+stats-runs-by-day-hint = Ejecuciones fallidas (sin respuesta, 4xx o 5xx) en rojo
+usage-title = Estadísticas de uso
+usage-requests-used = Peticiones usadas
+usage-most-used = Peticiones más usadas
+usage-hosts = Latencia por host
+usage-hint = A partir de las últimas 20 ejecuciones guardadas con cada petición, solo en este equipo.
+
+## Collections sidebar
+
+collections-add-request = Añadir petición
+collections-add-folder = Añadir carpeta
+collections-import-url = Importar desde URL
+collections-remote-sync = Sincronización remota
+collections-run = Ejecutar colección
+collections-export-ci-bundle = Exportar paquete de CI…
+collections-statistics = Estadísticas
+collections-cancel-request = Cancelar petición
+collections-send-request = Enviar petición
+collections-copy-curl = Copiar como cURL
+collections-copy-curl-secrets = Copiar como cURL con secretos
+collections-export-request = Exportar petición…
+collections-copy-link = Copiar enlace
+collections-pin-tray = Fijar en la bandeja
+collections-unpin-tray = Quitar de la bandeja
+collections-monitor-passing = Monitor correcto
+collections-monitor-failing = Monitor fallando: { $reason }
+collections-monitor-pending = Monitor aún sin comprobar
+collections-duplicate = Duplicar
+collections-sort-by = Ordenar por
+collections-sending = Enviando
+
+## Environments
+
+environments-title = Entornos
+environments-new = Nuevo entorno
+environments-active = Activo
+environments-hint = Define variables que puedes usar en todas tus peticiones
+environments-variables = Variables
+environments-show-values = Mostrar valores
+environments-key = Clave
+environments-value = Valor
+environments-type = Tipo
+environments-add-variable = Añadir variable
+environments-using-variables = Uso de variables
+environments-using-variables-hint = Haz referencia a las variables en tus peticiones con la sintaxis: {{variable_name}}
+environments-example = Ejemplo: {{base_url}}/api/users
+environments-settings = Ajustes del entorno
+environments-description = Descripción
+environments-delete = Eliminar entorno
+environments-none-selected = Ningún entorno seleccionado
+environments-none-selected-hint = Selecciona un entorno en la barra lateral o crea uno nuevo
+environments-default-auth = Autenticación predeterminada
+environments-default-auth-hint = La usan las peticiones cuyo tipo de autenticación es «Heredar del entorno»
+environments-add-to = Añadir a
+environments-secret = Secreto: oculto en registros y exportaciones, haz clic para mostrarlo
+environments-mark-secret = Marcar como secreto
+environments-description-placeholder = Descripción del entorno
+environments-token = Token
+environments-token-placeholder = Token bearer o {{variable}}
+environments-key-name = Nombre de la clave
+environments-api-key = Clave de API
+environments-api-key-placeholder = Clave de API o {{variable}}
+environments-username = Usuario
+environments-password = Contraseña
+
+## Request tabs
+
+request-tab-params = Parámetros
+request-tab-auth = Autenticación
+request-tab-script = Script
+request-tab-assertions = Aserciones
+request-tab-settings = Ajustes
+request-tab-history = Historial
+
+## Response tabs
+
+response-tab-caching = Caché
+response-tab-request = Petición
+response-tab-certificate = Certificado
+response-tab-records = Registros
+response-tab-chart = Gráfico
+response-tab-history = Historial
+response-tab-tests = Pruebas { $passed }/{ $total }
+response-tab-examples = Ejemplos
+response-tab-examples-count = Ejemplos { $count }
+
+## Dialogs
+
+modal-send-anyway = Enviar de todos modos
+modal-request-too-large = Petición demasiado grande
+connectivity-check-again = Comprobar de nuevo
+conflict-reload = Recargar
+conflict-merge = Combinar
+conflict-overwrite = Sobrescribir
+conflict-title = El archivo cambió en el disco
+new-request-create = Crear
+new-request-title = Nueva petición
+import-downloading = Descargando...
+import-url-hint = Enlace a una colección o entorno de Postman, un documento OpenAPI (JSON) o una exportación de Beam.
+modal-request-too-large-description = Esta petición ocupa { $total } bytes ({ $headers } bytes de cabeceras, { $body } bytes de cuerpo), lo que supera el límite de subida de { $limit } bytes.
+connectivity-checking = Comprobando { $target }...
+connectivity-title = Conectividad con { $target }
+conflict-description = «{ $name }» se modificó fuera de Beam, quizá desde otra ventana de Beam. Recarga la versión del disco, sobrescríbela con tus cambios o combina ambas (tus campos editados prevalecen).
+recovery-title = Beam se cerró inesperadamente
+recovery-description = Beam encontró un error y tuvo que cerrarse: { $message }. Se guardó un informe en { $path }.
+recovery-restore-hint = Se conservaron tus últimos cambios en «{ $name }». ¿Quieres restaurarlos?
+recovery-restore = Restaurar
+recovery-discard = Descartar
+import-file-title = Importar { $name }
+import-file-question = ¿Importar { $summary }?
+import-file-error = Beam no puede importar este archivo: { $error }
+new-request-name = Nombre
+new-request-name-placeholder = Por defecto, el último segmento de la URL
+new-request-request = Petición
+new-request-folder = Carpeta
+rename-folder = Renombrar carpeta
+rename-folder-hint = Escribe un nombre nuevo para la carpeta:
+rename-request = Renombrar petición
+rename-request-hint = Escribe un nombre nuevo para la petición:
+rename-hint = Escribe un nombre nuevo:
+rename-placeholder = Nombre nuevo...
+connectivity-dns = Resolución DNS
+connectivity-tcp = Conexión TCP
+connectivity-tls = Negociación TLS
+connectivity-not-run = Sin ejecutar
+
+## Accessibility
+
+a11y-show-sidebar = Mostrar barra lateral
+a11y-hide-sidebar = Ocultar barra lateral
+a11y-tools = Herramientas
+a11y-webhook-inbox = Bandeja de webhooks
+a11y-audit-log = Registro de auditoría
+a11y-settings = Ajustes
+a11y-maximize = Maximizar
+a11y-restore = Restaurar
+a11y-response-below = Mover la respuesta abajo
+a11y-response-right = Mover la respuesta a la derecha
+a11y-send = Enviar petición
+a11y-cancel = Cancelar petición
+a11y-method = Cambiar método
+a11y-format-body = Formatear cuerpo
+a11y-remove-queued = Quitar de la cola
+
+## Drafts
+
+draft-unsaved = Cambios sin guardar; la petición guardada se conserva hasta que guardes
+draft-save = Guardar
+draft-revert = Volver a lo guardado
+
+## History
+
+history-loading = Cargando versiones…
+history-empty = Aún no hay versiones. Se guarda una cada vez que se guarda la petición.
+history-count = { $count } versiones guardadas, la más reciente primero
+history-latest = Más reciente
+history-restore = Restaurar
+history-first = Primera versión guardada
+history-no-changes = Mismos campos que la versión anterior
+
+## Offline queue
+
+queue-retry-all = Reintentar todas
+queue-retry = Reintentar
+
+## Request editor
+
+request-environment = Entorno:
+request-encode-query = Codificar valores de la consulta
+request-decode-url = Decodificar URL
+request-no-body = Sin cuerpo
+request-body-changed = Cambió desde el último envío; la respuesta corresponde al cuerpo anterior
+request-body-too-large-to-compare = El cuerpo es demasiado grande para compararlo
+request-path-variables = Variables de ruta
+request-query-parameters = Parámetros de consulta
+request-auth-type = Tipo de autenticación
+request-auth-none = No se requiere autenticación
+request-auth-inherit = Usa la autenticación por defecto del entorno activo, definida en el editor de entornos.
+request-auth-bearer = Token Bearer
+request-auth-basic = Autenticación básica
+request-auth-username = Usuario
+request-auth-password = Contraseña
+request-auth-api-key = Autenticación con clave de API
+request-auth-add-to = Añadir a
+request-auth-api-key-value = Clave de API
+request-auth-hmac = Firma HMAC
+request-auth-algorithm = Algoritmo
+request-auth-secret-key = Clave secreta
+request-auth-string-to-sign = Cadena a firmar
+request-auth-string-to-sign-hint = Marcadores: {method} {path} {host} {date} {timestamp} {content_type} {body_hash}, \n para un salto de línea
+request-auth-header-name = Nombre de la cabecera
+request-auth-header-value = Valor de la cabecera
+request-auth-hawk = Autenticación Hawk
+request-auth-hawk-id = ID de Hawk
+request-auth-hawk-key = Clave de Hawk
+request-auth-hawk-ext = Datos extra (ext)
+request-token-refresh = Renovación del token
+request-token-refresh-hint = Envía antes otra petición cuando falta el token o ha caducado, y guarda el token que devuelve.
+request-computed-headers = Cabeceras calculadas
+request-no-computed-headers = No se añadirá ninguna cabecera
+request-assert-status = Estado
+request-assert-add-header = Añadir comprobación de cabecera
+request-assert-schema = Esquema JSON
+request-snapshot = Instantánea
+request-snapshot-update = Actualizar con la última respuesta
+request-snapshot-record = Grabar la última respuesta
+request-snapshot-ignored = Rutas ignoradas
+request-snapshot-add-ignored = Añadir ruta ignorada
+request-contract = Contrato OpenAPI
+request-contract-detach = Desvincular
+request-max-upload = Tamaño máximo de subida (KB)
+request-max-download = Tamaño máximo de descarga (KB)
+request-size-limit-hint = Déjalo vacío para desactivar el límite.
+request-latency-budget = Presupuesto de latencia (ms)
+request-latency-budget-hint = Las respuestas más lentas se señalan en el panel de respuesta y en el historial de ejecuciones.
+request-security-headers-hint = Califica HSTS, CSP, X-Content-Type-Options y otras cabeceras de seguridad de las respuestas, con explicaciones.
+request-monitor-interval = Comprobar cada (segundos)
+request-monitor-status = Estado esperado
+request-monitor-hint = Envía la petición a intervalos mientras Beam está abierto; su estado se muestra en la barra lateral y una notificación avisa cuando empieza a fallar. Sin un estado esperado deciden las aserciones de la petición, o pasa cualquier estado por debajo de 400.
+request-pagination = Paginación
+request-unix-socket = Socket Unix
+request-unix-socket-hint = Si se indica, la petición se envía por este socket en lugar de TCP. El socket se indica por petición y no se hereda de su colección, usa una {{variable}} para compartirlo entre peticiones.
+request-query-encoding-hint = Por defecto, caracteres como los espacios, | o un % suelto en la consulta se codifican con porcentajes antes de enviar.
+request-connection = Conexión
+request-accept-encoding = Accept-Encoding
+request-accept-encoding-hint = Desmarca todas las codificaciones para dejar de enviar Accept-Encoding.
+request-paste-fill = Rellenar la petición
+request-paste-keep = Dejar como URL
+
+## Response panel
+
+response-docs = Documentación
+response-send-conditional = Enviar condicional
+response-cancel-retry = Cancelar reintento
+response-offline = Parece que la red no está disponible.
+response-queue-retry = Poner en cola para reintentar
+response-too-large = La respuesta supera el límite de descarga.
+response-download-anyway = Descargar de todos modos
+response-more-pages = Hay más páginas disponibles.
+response-next-page = Página siguiente
+response-all-pages = Obtener todas las páginas
+response-no-headers = No hay cabeceras
+response-empty = Aún no hay respuesta
+response-empty-hint = Envía una petición para ver aquí la respuesta
+response-binary = Respuesta binaria
+response-binary-preview = Vista previa (primeros 100 bytes en hexadecimal):
+response-no-records = El cuerpo no tiene registros
+response-no-tests = No se ejecutó ninguna prueba; añade aserciones o llamadas a pm.test a la petición
+response-no-certificate = Sin certificado: la petición no se envió por HTTPS
+response-certificate-valid-until = Válido hasta
+response-none = Ninguno
+response-caching-how = Cómo se almacena en caché
+response-caching-notes = Notas
+response-no-request = No hay detalles de la petición
+response-not-sent = Esta petición aún no se ha enviado
+response-save-example = Guardar la respuesta como ejemplo
+response-examples-hint = Guarda respuestas para conservar ejemplos de lo que devuelve esta petición.
+response-mock = Simulación
+response-mock-latency = ms de latencia,
+response-mock-error-rate = % respondidas con
+
+## Collection runner
+
+runner-environment = Entorno
+runner-iteration-data = Datos de iteración
+runner-mode = Modo
+runner-concurrency = Concurrencia
+runner-delay = Espera
+runner-delay-unit = ms entre peticiones
+runner-retries = Reintentos
+runner-edit-steps = Editar pasos
+runner-loop-back = Volver a
+runner-loop-times = veces más
+runner-jump-when = Cuando
+runner-jump-to = ir a
+runner-chained = encadena variables
+runner-chained-hint = Define una variable que usa otra petición, o usa una definida por otra petición. Su resultado depende del orden en que terminen las peticiones.
+
+## Remote sync
+
+sync-backend = Servicio
+sync-syncing = Sincronizando...
+sync-now = Sincronizar ahora
+sync-title = Sincronización remota
+sync-hint = Comparte colecciones y entornos a través de un servidor WebDAV o un bucket compatible con S3.
+sync-secrets-warning = La contraseña y la clave secreta se guardan en texto plano en ~/.beam/sync.toml, que solo tú puedes leer. Mantén esa carpeta fuera de unidades compartidas y del control de versiones.
+
+## Notifications
+
+toast-details = Haz clic para ver los detalles
+
+## Tools
+
+tools-now = Ahora
+tools-waiting = Esperando respuesta...
+tools-copy-output = Copiar resultado
+tools-title = Herramientas
+tools-jwt-header = Cabecera
+tools-jwt-payload = Contenido
+tools-jwt-unverified = La firma no se verifica.
+
+## Webhook inbox
+
+webhook-listening = Escuchando en
+webhook-copy-url = Copiar URL
+webhook-port = Puerto
+webhook-start = Empezar a escuchar
+webhook-hint = Cada petición se responde con 200 y se muestra aquí.
+webhook-clear = Vaciar
+webhook-copy-body = Copiar cuerpo
+webhook-title = Bandeja de webhooks
+
+## Tray icon
+
+tray-show = Mostrar Beam
+tray-empty = Fija peticiones desde su menú en la barra lateral
+tray-send = Enviar { $name }
@@ -0,0 +1,454 @@
+use crate::snapshot;
+use crate::types::{ResponseAssertions, ResponseData, TestResult};
+use regex::Regex;
+use serde_json::Value;
+
+/// Schema violations reported per response, the rest are summarized as a count
+const MAX_SCHEMA_ERRORS: usize = 10;
+
+/// Checks a response against the expectations attached to its request
+pub fn evaluate(assertions: &ResponseAssertions, response: &ResponseData) -> Vec<TestResult> {
+    let mut results = Vec::new();
+
+    let expected_status = assertions.status.trim();
+    if !expected_status.is_empty() {
+        let name = format!("Status is {}", expected_status);
+        results.push(if status_matches(expected_status, response.status) {
+            TestResult::pass(name)
+        } else {
+            TestResult::fail(name, format!("Got {}", response.status))
+        });
+    }
+
+    for (header, expected) in &assertions.headers {
+        let header = header.trim();
+        if header.is_empty() {
+            continue;
+        }
+
+        let expected = expected.trim();
+        let actual = response
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(header))
+            .map(|(_, value)| value.as_str());
+
+        let result = match actual {
+            None => TestResult::fail(
+                format!("Header {} is present", header),
+                "The response has no such header".to_string(),
+            ),
+            Some(_) if expected.is_empty() || expected == "*" => {
+                TestResult::pass(format!("Header {} is present", header))
+            }
+            Some(actual) if actual == expected => {
+                TestResult::pass(format!("Header {} is {}", header, expected))
+            }
+            Some(actual) => TestResult::fail(
+                format!("Header {} is {}", header, expected),
+                format!("Got {}", actual),
+            ),
+        };
+        results.push(result);
+    }
+
+    if !assertions.json_schema.trim().is_empty() {
+        results.push(schema_result(&assertions.json_schema, &response.body));
+    }
+
+    if let Some(snapshot) = &assertions.snapshot {
+        results.push(snapshot::check(snapshot, response, &assertions.ignore_paths));
+    }
+
+    results
+}
+
+fn schema_result(schema: &str, body: &str) -> TestResult {
+    const NAME: &str = "Body matches JSON schema";
+
+    let schema = match serde_json::from_str::<Value>(schema) {
+        Ok(schema) => schema,
+        Err(e) => return TestResult::fail(NAME, format!("The schema is not valid JSON: {}", e)),
+    };
+    let body = match serde_json::from_str::<Value>(body) {
+        Ok(body) => body,
+        Err(e) => return TestResult::fail(NAME, format!("The body is not valid JSON: {}", e)),
+    };
+
+    let errors = validate_schema(&schema, &schema, &body);
+    if errors.is_empty() {
+        TestResult::pass(NAME)
+    } else {
+        TestResult::fail(NAME, summarize_errors(errors))
+    }
+}
+
+/// Joins schema violations into one message, listing at most `MAX_SCHEMA_ERRORS`
+pub fn summarize_errors(errors: Vec<String>) -> String {
+    let total = errors.len();
+    let mut lines: Vec<String> = errors.into_iter().take(MAX_SCHEMA_ERRORS).collect();
+    if total > MAX_SCHEMA_ERRORS {
+        lines.push(format!("...and {} more", total - MAX_SCHEMA_ERRORS));
+    }
+    lines.join("\n")
+}
+
+/// `expected` is an exact code such as `201` or a class such as `2xx`
+fn status_matches(expected: &str, status: u16) -> bool {
+    let expected = expected.to_ascii_lowercase();
+    match expected.strip_suffix("xx") {
+        Some(class) => class
+            .parse::<u16>()
+            .is_ok_and(|class| status / 100 == class),
+        None => expected.parse::<u16>().is_ok_and(|code| code == status),
+    }
+}
+
+/// Validates `value` against a JSON Schema, returning one message per violation.
+///
+/// Covers the keywords API responses commonly rely on: `type`, `enum`, `const`,
+/// `properties`, `required`, `additionalProperties`, `items`, length and range limits,
+/// `pattern`, `allOf`/`anyOf`/`oneOf`, OpenAPI's `nullable`, and local `$ref`s, which are
+/// resolved against `root`.
+pub fn validate_schema(root: &Value, schema: &Value, value: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(root, schema, value, "$", &mut errors, 0);
+    errors
+}
+
+/// Deepest `$ref` chain followed, guards against self-referencing schemas
+const MAX_REF_DEPTH: usize = 32;
+
+fn validate_at(
+    root: &Value,
+    schema: &Value,
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<String>,
+    depth: usize,
+) {
+    let Some(schema) = schema.as_object() else {
+        // `true` accepts anything, `false` nothing
+        if schema == &Value::Bool(false) {
+            errors.push(format!("{}: no value is allowed here", path));
+        }
+        return;
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        if depth >= MAX_REF_DEPTH {
+            errors.push(format!("{}: {} nests too deeply", path, reference));
+            return;
+        }
+        match resolve_ref(root, reference) {
+            Some(target) => validate_at(root, target, value, path, errors, depth + 1),
+            None => errors.push(format!("{}: cannot resolve {}", path, reference)),
+        }
+        return;
+    }
+
+    if value.is_null() && schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        return;
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        if !types.is_empty() && !types.iter().any(|name| has_type(value, name)) {
+            errors.push(format!(
+                "{}: expected {}, got {}",
+                path,
+                types.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum")
+        && !options.contains(value)
+    {
+        errors.push(format!(
+            "{}: {} is not one of the allowed values",
+            path, value
+        ));
+    }
+
+    if let Some(constant) = schema.get("const")
+        && constant != value
+    {
+        errors.push(format!("{}: expected {}, got {}", path, constant, value));
+    }
+
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(name) {
+                        errors.push(format!("{}: missing required property {}", path, name));
+                    }
+                }
+            }
+
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (name, property) in object {
+                let property_path = format!("{}.{}", path, name);
+                match properties.and_then(|properties| properties.get(name)) {
+                    Some(property_schema) => validate_at(
+                        root,
+                        property_schema,
+                        property,
+                        &property_path,
+                        errors,
+                        depth,
+                    ),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{}: property is not allowed", property_path))
+                        }
+                        Some(additional @ Value::Object(_)) => {
+                            validate_at(root, additional, property, &property_path, errors, depth)
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            check_limit(schema, "minItems", items.len(), path, errors, |len, min| {
+                len >= min
+            });
+            check_limit(schema, "maxItems", items.len(), path, errors, |len, max| {
+                len <= max
+            });
+
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    let item_path = format!("{}[{}]", path, index);
+                    validate_at(root, item_schema, item, &item_path, errors, depth);
+                }
+            }
+        }
+        Value::String(text) => {
+            let len = text.chars().count();
+            check_limit(schema, "minLength", len, path, errors, |len, min| {
+                len >= min
+            });
+            check_limit(schema, "maxLength", len, path, errors, |len, max| {
+                len <= max
+            });
+
+            if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                match Regex::new(pattern) {
+                    Ok(regex) if !regex.is_match(text) => {
+                        errors.push(format!("{}: does not match {}", path, pattern))
+                    }
+                    Ok(_) => {}
+                    Err(_) => errors.push(format!("{}: invalid pattern {}", path, pattern)),
+                }
+            }
+        }
+        Value::Number(number) => {
+            if let Some(number) = number.as_f64() {
+                check_range(schema, number, path, errors);
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(Value::Array(all)) = schema.get("allOf") {
+        for sub_schema in all {
+            validate_at(root, sub_schema, value, path, errors, depth);
+        }
+    }
+
+    let matching = |options: &Vec<Value>| {
+        options
+            .iter()
+            .filter(|option| {
+                let mut option_errors = Vec::new();
+                validate_at(root, option, value, path, &mut option_errors, depth);
+                option_errors.is_empty()
+            })
+            .count()
+    };
+
+    if let Some(Value::Array(any)) = schema.get("anyOf")
+        && matching(any) == 0
+    {
+        errors.push(format!("{}: matches none of the anyOf schemas", path));
+    }
+
+    if let Some(Value::Array(one)) = schema.get("oneOf") {
+        let count = matching(one);
+        if count != 1 {
+            errors.push(format!(
+                "{}: matches {} of the oneOf schemas, expected exactly 1",
+                path, count
+            ));
+        }
+    }
+}
+
+fn check_limit(
+    schema: &serde_json::Map<String, Value>,
+    keyword: &str,
+    actual: usize,
+    path: &str,
+    errors: &mut Vec<String>,
+    within: impl Fn(usize, usize) -> bool,
+) {
+    if let Some(limit) = schema.get(keyword).and_then(Value::as_u64)
+        && !within(actual, limit as usize)
+    {
+        errors.push(format!(
+            "{}: {} is {}, got {}",
+            path, keyword, limit, actual
+        ));
+    }
+}
+
+fn check_range(
+    schema: &serde_json::Map<String, Value>,
+    number: f64,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let limit = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+
+    if let Some(minimum) = limit("minimum")
+        && number < minimum
+    {
+        errors.push(format!(
+            "{}: {} is below the minimum {}",
+            path, number, minimum
+        ));
+    }
+    if let Some(maximum) = limit("maximum")
+        && number > maximum
+    {
+        errors.push(format!(
+            "{}: {} is above the maximum {}",
+            path, number, maximum
+        ));
+    }
+    if let Some(minimum) = limit("exclusiveMinimum")
+        && number <= minimum
+    {
+        errors.push(format!(
+            "{}: {} must be greater than {}",
+            path, number, minimum
+        ));
+    }
+    if let Some(maximum) = limit("exclusiveMaximum")
+        && number >= maximum
+    {
+        errors.push(format!(
+            "{}: {} must be less than {}",
+            path, number, maximum
+        ));
+    }
+}
+
+/// Follows a local reference such as `#/definitions/User` or `#/components/schemas/User`
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    let pointer = reference.strip_prefix('#')?;
+    root.pointer(pointer)
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value.as_f64().is_some_and(|number| number.fract() == 0.0),
+        "number" => value.is_number(),
+        name => type_name(value) == name,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn response(status: u16, body: &str) -> ResponseData {
+        ResponseData {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+            content_type: "application/json".to_string(),
+            size: body.len(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn status_and_header_matchers() {
+        let assertions = ResponseAssertions {
+            status: "2xx".to_string(),
+            headers: vec![
+                ("content-type".to_string(), "application/json".to_string()),
+                ("X-Request-Id".to_string(), "*".to_string()),
+            ],
+            json_schema: String::new(),
+            snapshot: None,
+            ignore_paths: Vec::new(),
+        };
+
+        let results = evaluate(&assertions, &response(201, "{}"));
+
+        let passed: Vec<bool> = results.iter().map(|result| result.passed).collect();
+        assert_eq!(passed, vec![true, true, false]);
+    }
+
+    #[test]
+    fn schema_reports_each_violation_with_its_path() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id", "name"],
+            "properties": {
+                "id": { "type": "integer", "minimum": 1 },
+                "tags": { "type": "array", "items": { "$ref": "#/definitions/tag" } }
+            },
+            "definitions": { "tag": { "type": "string", "maxLength": 3 } }
+        });
+
+        let errors = validate_schema(
+            &schema,
+            &schema,
+            &json!({ "id": 0, "tags": ["ok", "long"] }),
+        );
+
+        assert_eq!(
+            errors,
+            vec![
+                "$: missing required property name",
+                "$.id: 0 is below the minimum 1",
+                "$.tags[1]: maxLength is 3, got 4",
+            ]
+        );
+    }
+
+    #[test]
+    fn nullable_and_one_of() {
+        let schema = json!({
+            "oneOf": [{ "type": "string" }, { "type": "integer", "nullable": true }]
+        });
+
+        assert!(validate_schema(&schema, &schema, &json!(null)).is_empty());
+        assert!(validate_schema(&schema, &schema, &json!("a")).is_empty());
+        assert_eq!(validate_schema(&schema, &schema, &json!(1.5)).len(), 1);
+    }
+}
@@ -0,0 +1,220 @@
+use crate::redact;
+use crate::share;
+use crate::types::{
+    Environment, EnvironmentAuth, EnvironmentVariable, RequestCollection, RequestConfig,
+    RunConfiguration,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Extension of a CI bundle
+pub const EXTENSION: &str = "beambundle";
+
+/// Version of the bundle format, bundles of a later version are refused
+pub const VERSION: u32 = 1;
+
+/// A collection with its run configuration and an environment, packed into one file for a
+/// pipeline to run. Secret variables are left out, each is read from an OS environment
+/// variable instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CiBundle {
+    pub version: u32,
+    pub collection: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<BundledEnvironment>,
+    #[serde(default, skip_serializing_if = "RunConfiguration::is_empty")]
+    pub run: RunConfiguration,
+    #[serde(default)]
+    pub requests: Vec<RequestConfig>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BundledEnvironment {
+    pub name: String,
+    #[serde(default)]
+    pub variables: BTreeMap<String, BundledVariable>,
+    #[serde(default)]
+    pub auth: EnvironmentAuth,
+}
+
+/// A variable of a bundled environment: its value, or for a secret the name of the OS
+/// environment variable holding it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundledVariable {
+    Value(String),
+    FromEnv(String),
+}
+
+/// The OS environment variable a secret is read from, e.g. `BEAM_API_TOKEN` for `api-token`
+pub fn env_var_name(variable: &str) -> String {
+    let name: String = variable
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("BEAM_{}", name)
+}
+
+/// The bundle of `collection` and `environment`. Disabled variables are left out, and the
+/// credentials written into the requests or the environment's auth are masked as in a
+/// shared request.
+pub fn export(
+    collection: &RequestCollection,
+    environment: Option<&Environment>,
+) -> Result<String, String> {
+    let bundle = CiBundle {
+        version: VERSION,
+        collection: collection.name.clone(),
+        environment: environment.map(|environment| BundledEnvironment {
+            name: environment.name.clone(),
+            variables: environment
+                .variables
+                .iter()
+                .filter(|(_, variable)| variable.enabled)
+                .map(|(name, variable)| {
+                    let bundled = if variable.secret {
+                        BundledVariable::FromEnv(env_var_name(name))
+                    } else {
+                        BundledVariable::Value(variable.value.clone())
+                    };
+                    (name.clone(), bundled)
+                })
+                .collect(),
+            auth: environment.auth.clone(),
+        }),
+        run: collection.run_configuration.clone(),
+        requests: collection.requests.iter().map(share::portable).collect(),
+    };
+
+    let mut secrets: Vec<String> = collection
+        .requests
+        .iter()
+        .flat_map(|request| redact::secrets(request, environment))
+        .collect();
+    secrets.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    secrets.dedup();
+
+    let content = toml::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+    Ok(redact::mask(&content, &secrets))
+}
+
+/// Reads a bundle
+pub fn parse(content: &str) -> Result<CiBundle, String> {
+    let bundle: CiBundle =
+        toml::from_str(content).map_err(|e| format!("Invalid CI bundle: {}", e))?;
+    if bundle.version > VERSION {
+        return Err(format!(
+            "The CI bundle is version {}, this Beam reads up to version {}",
+            bundle.version, VERSION
+        ));
+    }
+    Ok(bundle)
+}
+
+impl CiBundle {
+    /// The environment of the bundle with its secrets read through `lookup`, usually
+    /// `std::env::var`. Fails listing the OS environment variables that aren't set.
+    pub fn environment(
+        &self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Environment, String> {
+        let Some(bundled) = &self.environment else {
+            return Ok(Environment::new(String::new()));
+        };
+
+        let mut environment = Environment::new(bundled.name.clone());
+        environment.auth = bundled.auth.clone();
+        let mut missing = Vec::new();
+        for (name, variable) in &bundled.variables {
+            let (value, secret) = match variable {
+                BundledVariable::Value(value) => (value.clone(), false),
+                BundledVariable::FromEnv(key) => match lookup(key) {
+                    Some(value) => (value, true),
+                    None => {
+                        missing.push(key.clone());
+                        continue;
+                    }
+                },
+            };
+            let mut variable = EnvironmentVariable::new(value);
+            variable.secret = secret;
+            environment.variables.insert(name.clone(), variable);
+        }
+
+        if missing.is_empty() {
+            Ok(environment)
+        } else {
+            Err(format!("Set {} to run the bundle", missing.join(", ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RunStep;
+
+    #[test]
+    fn bundles_a_collection_without_its_secrets() {
+        let request = RequestConfig {
+            id: "r1".to_string(),
+            name: "Me".to_string(),
+            url: "{{base_url}}/me".to_string(),
+            bearer_token: "hard-coded-token".to_string(),
+            ..RequestConfig::default()
+        };
+        let collection = RequestCollection {
+            id: "c1".to_string(),
+            name: "Users".to_string(),
+            folder_name: "0000".to_string(),
+            requests: vec![request],
+            expanded: true,
+            color: None,
+            icon: None,
+            sort: Default::default(),
+            run_configuration: RunConfiguration {
+                steps: vec![RunStep::new("r1".to_string())],
+                retries: 2,
+                ..RunConfiguration::default()
+            },
+        };
+        let mut environment = Environment::new("Staging".to_string());
+        environment.add_variable("base_url".to_string(), "https://staging.test".to_string());
+        environment.add_variable("api-token".to_string(), "s3cr3t-value".to_string());
+        environment.variables.get_mut("api-token").unwrap().secret = true;
+
+        let content = export(&collection, Some(&environment)).unwrap();
+        assert!(!content.contains("s3cr3t-value"));
+        assert!(!content.contains("hard-coded-token"));
+        assert!(content.contains("BEAM_API_TOKEN"));
+
+        let bundle = parse(&content).unwrap();
+        assert_eq!(bundle.collection, "Users");
+        assert_eq!(bundle.run.retries, 2);
+        assert_eq!(bundle.requests[0].url, "{{base_url}}/me");
+        assert_eq!(
+            bundle.environment(|_| None).unwrap_err(),
+            "Set BEAM_API_TOKEN to run the bundle"
+        );
+        let environment = bundle
+            .environment(|key| (key == "BEAM_API_TOKEN").then(|| "from-ci".to_string()))
+            .unwrap();
+        assert_eq!(
+            environment.get_variable("api-token"),
+            Some(&"from-ci".to_string())
+        );
+        assert_eq!(
+            environment.get_variable("base_url"),
+            Some(&"https://staging.test".to_string())
+        );
+
+        assert!(parse(&content.replace("version = 1", "version = 2")).is_err());
+    }
+}
@@ -0,0 +1,395 @@
+use crate::types::ResponseData;
+use chrono::{DateTime, Utc};
+
+/// Headers that decide how a response is cached, in the order they are listed
+const CACHING_HEADERS: [&str; 9] = [
+    "cache-control",
+    "expires",
+    "age",
+    "date",
+    "vary",
+    "etag",
+    "last-modified",
+    "pragma",
+    "set-cookie",
+];
+
+/// Statuses caches may store without explicit freshness (RFC 9110, section 15.1)
+const HEURISTICALLY_CACHEABLE: [u16; 12] =
+    [200, 203, 204, 206, 300, 301, 308, 404, 405, 410, 414, 501];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    pub severity: Severity,
+    pub text: String,
+}
+
+/// How browsers and shared caches (CDNs, proxies) would treat a response
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachingReport {
+    /// The caching headers of the response, as received
+    pub headers: Vec<(String, String)>,
+    pub private_cache: String,
+    pub shared_cache: String,
+    pub notes: Vec<Note>,
+}
+
+/// A `Cache-Control` directive, its name lowercased
+#[derive(Debug, Clone, PartialEq)]
+struct Directive {
+    name: String,
+    value: Option<String>,
+}
+
+fn directives(header: &str) -> Vec<Directive> {
+    let mut directives = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in header.chars().chain([',']) {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            ',' if !quoted => {
+                let item = std::mem::take(&mut current);
+                let item = item.trim();
+                if item.is_empty() {
+                    continue;
+                }
+                let (name, value) = match item.split_once('=') {
+                    Some((name, value)) => (name, Some(value.trim().trim_matches('"').to_string())),
+                    None => (item, None),
+                };
+                directives.push(Directive {
+                    name: name.trim().to_ascii_lowercase(),
+                    value,
+                });
+            }
+            c => current.push(c),
+        }
+    }
+    directives
+}
+
+/// Seconds as the largest two units, e.g. `1d 2h` or `5m 30s`
+pub fn format_lifetime(seconds: u64) -> String {
+    let units = [(86_400, "d"), (3_600, "h"), (60, "m"), (1, "s")];
+    let mut parts = Vec::new();
+    let mut rest = seconds;
+    for (size, unit) in units {
+        if rest >= size || (parts.is_empty() && size == 1) {
+            parts.push(format!("{}{}", rest / size, unit));
+            rest %= size;
+        }
+        if parts.len() == 2 || (!parts.is_empty() && rest == 0) {
+            break;
+        }
+    }
+    parts.join(" ")
+}
+
+/// Explains the caching headers of `response` in plain language
+pub fn analyze(response: &ResponseData) -> CachingReport {
+    let header = |name: &str| {
+        response
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+    };
+    let date = |name: &str| {
+        header(name)
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+            .map(|value| value.with_timezone(&Utc))
+    };
+
+    let mut headers = Vec::new();
+    for name in CACHING_HEADERS {
+        for (key, value) in &response.headers {
+            if key.eq_ignore_ascii_case(name) {
+                headers.push((key.clone(), value.clone()));
+            }
+        }
+    }
+
+    let cache_control = directives(
+        &response
+            .headers
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case("cache-control"))
+            .map(|(_, value)| value.as_str())
+            .collect::<Vec<_>>()
+            .join(","),
+    );
+    let has = |name: &str| cache_control.iter().any(|directive| directive.name == name);
+    let seconds = |name: &str| {
+        cache_control
+            .iter()
+            .find(|directive| directive.name == name)
+            .and_then(|directive| directive.value.as_deref()?.parse::<u64>().ok())
+    };
+
+    let mut notes = Vec::new();
+    let mut note = |severity: Severity, text: String| notes.push(Note { severity, text });
+
+    let age = header("age")
+        .and_then(|age| age.parse::<u64>().ok())
+        .unwrap_or(0);
+    let expires = header("expires").map(|value| {
+        // An invalid Expires, such as 0, means already expired
+        match (DateTime::parse_from_rfc2822(value), date("date")) {
+            (Ok(expires), Some(sent)) => {
+                (expires.with_timezone(&Utc) - sent).num_seconds().max(0) as u64
+            }
+            (Ok(expires), None) => (expires.with_timezone(&Utc) - Utc::now())
+                .num_seconds()
+                .max(0) as u64,
+            (Err(_), _) => 0,
+        }
+    });
+    let heuristic = match (date("last-modified"), date("date")) {
+        (Some(modified), Some(sent)) if sent > modified => {
+            Some((sent - modified).num_seconds() as u64 / 10)
+        }
+        _ => None,
+    };
+    let cacheable_status = HEURISTICALLY_CACHEABLE.contains(&response.status);
+
+    // Freshness for one kind of cache: s-maxage only applies to shared ones
+    let describe = |shared: bool| -> String {
+        if has("no-store") {
+            return "Not stored: no-store forbids keeping any copy.".to_string();
+        }
+        if shared && has("private") {
+            return "Not stored: the response is private to one user.".to_string();
+        }
+        if shared
+            && response.raw_request.lines().any(|line| {
+                line.split_once(':')
+                    .is_some_and(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+            })
+            && !has("public")
+            && !has("s-maxage")
+            && !has("must-revalidate")
+        {
+            return "Not stored: the request was authenticated, which needs public, s-maxage or must-revalidate to be shared.".to_string();
+        }
+        if header("vary").is_some_and(|vary| vary.split(',').any(|name| name.trim() == "*")) {
+            return "Not reused: Vary: * makes every request unique.".to_string();
+        }
+
+        let lifetime = (if shared { seconds("s-maxage") } else { None })
+            .or_else(|| seconds("max-age"))
+            .or(expires);
+        if has("no-cache") {
+            return "Stored, but checked with the server before every reuse (no-cache)."
+                .to_string();
+        }
+        match lifetime {
+            Some(lifetime) if lifetime > age => format!(
+                "Served without contacting the server for {}{}, then revalidated.",
+                format_lifetime(lifetime - age),
+                if age > 0 {
+                    format!(" more (already {} old)", format_lifetime(age))
+                } else {
+                    String::new()
+                }
+            ),
+            Some(_) => {
+                "Stored, but already stale: checked with the server before reuse.".to_string()
+            }
+            None if !cacheable_status => format!(
+                "Not stored: a {} response needs explicit freshness to be cached.",
+                response.status
+            ),
+            None => match heuristic {
+                Some(heuristic) => format!(
+                    "No explicit freshness, caches may guess: about {} (a tenth of the time since Last-Modified).",
+                    format_lifetime(heuristic)
+                ),
+                None => {
+                    "No explicit freshness: caches may store it but pick how long on their own."
+                        .to_string()
+                }
+            },
+        }
+    };
+    let private_cache = describe(false);
+    let shared_cache = describe(true);
+
+    if cache_control.is_empty() && header("expires").is_none() {
+        if header("pragma").is_some_and(|pragma| pragma.to_ascii_lowercase().contains("no-cache")) {
+            note(
+                Severity::Warning,
+                "Only Pragma: no-cache is set, a legacy request header most caches ignore on responses. Use Cache-Control.".to_string(),
+            );
+        } else {
+            note(
+                Severity::Warning,
+                "There is no Cache-Control or Expires, how long the response is kept is left to each cache.".to_string(),
+            );
+        }
+    }
+    if has("must-revalidate") || has("proxy-revalidate") {
+        note(
+            Severity::Info,
+            "Once stale, the response must not be served without checking with the server, even when it is unreachable.".to_string(),
+        );
+    }
+    if has("immutable") {
+        note(
+            Severity::Info,
+            "immutable: browsers won't revalidate it on reload while it is fresh.".to_string(),
+        );
+    }
+    if let Some(window) = seconds("stale-while-revalidate") {
+        note(
+            Severity::Info,
+            format!(
+                "Once stale it can still be served for {} while a fresh copy is fetched in the background.",
+                format_lifetime(window)
+            ),
+        );
+    }
+    if let Some(window) = seconds("stale-if-error") {
+        note(
+            Severity::Info,
+            format!(
+                "If the server errors, a stale copy can be served for up to {}.",
+                format_lifetime(window)
+            ),
+        );
+    }
+    if has("no-store") && (has("max-age") || has("public")) {
+        note(
+            Severity::Warning,
+            "no-store overrides the other directives, they have no effect.".to_string(),
+        );
+    }
+    if let Some(vary) = header("vary") {
+        let names: Vec<&str> = vary
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect();
+        if !names.contains(&"*") {
+            note(
+                Severity::Info,
+                format!("A copy is kept for each value of {}.", names.join(", ")),
+            );
+        }
+        if names.iter().any(|name| {
+            name.eq_ignore_ascii_case("user-agent") || name.eq_ignore_ascii_case("cookie")
+        }) {
+            note(
+                Severity::Warning,
+                "Varying on User-Agent or Cookie splits the cache into many copies and lowers the hit rate.".to_string(),
+            );
+        }
+    }
+    if header("set-cookie").is_some() && !has("private") && !has("no-store") {
+        note(
+            Severity::Warning,
+            "The response sets a cookie but isn't private, a shared cache could hand that cookie to other users.".to_string(),
+        );
+    }
+    if header("etag").is_some() || header("last-modified").is_some() {
+        note(
+            Severity::Info,
+            "Validators are present, stale copies can be revalidated with a cheap 304 instead of a full download.".to_string(),
+        );
+    } else if !has("no-store") {
+        note(
+            Severity::Info,
+            "There is no ETag or Last-Modified, stale copies have to be downloaded again in full."
+                .to_string(),
+        );
+    }
+
+    CachingReport {
+        headers,
+        private_cache,
+        shared_cache,
+        notes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(headers: &[(&str, &str)]) -> ResponseData {
+        ResponseData {
+            status: 200,
+            status_text: "OK".to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reads_directives_with_quoted_values() {
+        assert_eq!(
+            directives(r#"no-cache="Set-Cookie, Set-Cookie2", MAX-AGE=60"#),
+            vec![
+                Directive {
+                    name: "no-cache".to_string(),
+                    value: Some("Set-Cookie, Set-Cookie2".to_string()),
+                },
+                Directive {
+                    name: "max-age".to_string(),
+                    value: Some("60".to_string()),
+                },
+            ]
+        );
+        assert_eq!(format_lifetime(90_061), "1d 1h");
+        assert_eq!(format_lifetime(330), "5m 30s");
+        assert_eq!(format_lifetime(0), "0s");
+    }
+
+    #[test]
+    fn tells_browsers_and_shared_caches_apart() {
+        let report = analyze(&response(&[
+            ("Cache-Control", "private, max-age=600"),
+            ("Age", "100"),
+            ("Set-Cookie", "session=1"),
+        ]));
+        assert_eq!(
+            report.private_cache,
+            "Served without contacting the server for 8m 20s more (already 1m 40s old), then revalidated."
+        );
+        assert!(report.shared_cache.contains("private to one user"));
+        assert!(
+            report
+                .notes
+                .iter()
+                .all(|note| !note.text.contains("sets a cookie"))
+        );
+
+        let report = analyze(&response(&[
+            ("Cache-Control", "max-age=60, s-maxage=3600"),
+            ("Vary", "Accept-Encoding, User-Agent"),
+        ]));
+        assert!(report.shared_cache.contains("for 1h,"));
+        assert!(report.private_cache.contains("for 1m,"));
+        assert!(
+            report
+                .notes
+                .iter()
+                .any(|note| note.severity == Severity::Warning && note.text.contains("User-Agent"))
+        );
+
+        let report = analyze(&response(&[("Cache-Control", "no-store, max-age=60")]));
+        assert!(report.private_cache.starts_with("Not stored"));
+    }
+}
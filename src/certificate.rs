@@ -0,0 +1,55 @@
+use crate::types::CertificateInfo;
+use chrono::DateTime;
+use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
+
+/// Extracts the fields worth showing from a DER encoded certificate
+pub fn parse_certificate(der: &[u8]) -> Option<CertificateInfo> {
+    let (_, cert) = X509Certificate::from_der(der)
+        .inspect_err(|e| log::warn!("Failed to parse server certificate: {}", e))
+        .ok()?;
+
+    let subject_alt_names = match cert.subject_alternative_name() {
+        Ok(Some(extension)) => extension
+            .value
+            .general_names
+            .iter()
+            .filter_map(general_name_to_string)
+            .collect(),
+        _ => vec![],
+    };
+
+    Some(CertificateInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        subject_alt_names,
+        serial: cert.raw_serial_as_string(),
+        not_before: format_timestamp(cert.validity().not_before.timestamp()),
+        not_after: format_timestamp(cert.validity().not_after.timestamp()),
+    })
+}
+
+fn general_name_to_string(name: &GeneralName) -> Option<String> {
+    match name {
+        GeneralName::DNSName(dns) => Some(format!("DNS:{}", dns)),
+        GeneralName::RFC822Name(email) => Some(format!("email:{}", email)),
+        GeneralName::URI(uri) => Some(format!("URI:{}", uri)),
+        GeneralName::IPAddress(bytes) => match bytes.len() {
+            4 => {
+                let octets: [u8; 4] = (*bytes).try_into().ok()?;
+                Some(format!("IP:{}", std::net::Ipv4Addr::from(octets)))
+            }
+            16 => {
+                let octets: [u8; 16] = (*bytes).try_into().ok()?;
+                Some(format!("IP:{}", std::net::Ipv6Addr::from(octets)))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_default()
+}
@@ -0,0 +1,99 @@
+use crate::json_path;
+use serde_json::Value;
+
+/// Points past this many aren't charted
+pub const MAX_POINTS: usize = 5000;
+
+/// A value to chart, with the timestamp or name it was paired with
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataPoint {
+    pub label: Option<String>,
+    pub value: f64,
+}
+
+/// Numbers `path` points to in a JSON body. The path can select an array or several
+/// values, items being numbers, numeric strings or `[label, value]` pairs such as the
+/// `[timestamp, "1.5"]` series of Prometheus. Nulls are skipped as gaps.
+pub fn data_points(body: &str, path: &str) -> Result<Vec<DataPoint>, String> {
+    let document: Value =
+        serde_json::from_str(body).map_err(|e| format!("The body isn't JSON: {}", e))?;
+    let selected = json_path::select(&document, path)?;
+
+    let items: Vec<&Value> = match selected.as_slice() {
+        [Value::Array(items)] => items.iter().collect(),
+        _ => selected,
+    };
+
+    let mut points = Vec::new();
+    for (index, item) in items.into_iter().enumerate().take(MAX_POINTS) {
+        let point = match item {
+            Value::Null => continue,
+            Value::Array(pair) if pair.len() == 2 => number(&pair[1]).map(|value| DataPoint {
+                label: Some(label(&pair[0])),
+                value,
+            }),
+            item => number(item).map(|value| DataPoint { label: None, value }),
+        };
+        match point {
+            Some(point) => points.push(point),
+            None => return Err(format!("Item {} isn't a number: {}", index, item)),
+        }
+    }
+
+    if points.is_empty() {
+        return Err(format!("No numbers at '{}'", path.trim()));
+    }
+    Ok(points)
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse::<f64>().ok().filter(|n| n.is_finite()),
+        _ => None,
+    }
+}
+
+fn label(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_numbers_strings_and_pairs() {
+        let body = r#"{
+            "counts": [3, null, "4.5", 7],
+            "series": [{"v": 1}, {"v": 2}],
+            "values": [[1700000000, "0.25"], [1700000060, "0.5"]],
+            "names": ["a", "b"]
+        }"#;
+
+        let values = |path: &str| {
+            data_points(body, path)
+                .unwrap()
+                .iter()
+                .map(|point| point.value)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(values("$.counts"), vec![3.0, 4.5, 7.0]);
+        assert_eq!(values("$.series[*].v"), vec![1.0, 2.0]);
+        assert_eq!(values("values"), vec![0.25, 0.5]);
+        assert_eq!(
+            data_points(body, "values").unwrap()[1].label.as_deref(),
+            Some("1700000060")
+        );
+
+        assert_eq!(
+            data_points(body, "$.names").unwrap_err(),
+            "Item 0 isn't a number: \"a\""
+        );
+        assert!(data_points(body, "$.missing").is_err());
+        assert!(data_points("<html>", "$").is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::types::{CacheValidators, RequestConfig, ResponseData};
+
+fn header<'a>(response: &'a ResponseData, name: &str) -> Option<&'a str> {
+    response
+        .headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+/// Keeps the validators of `response`. A full response replaces them, a 304 only refreshes
+/// the ones it repeats, other statuses leave them as they were.
+pub fn remember_validators(validators: &mut CacheValidators, response: &ResponseData) {
+    let etag = header(response, "etag").map(str::to_string);
+    let last_modified = header(response, "last-modified").map(str::to_string);
+
+    match response.status {
+        200..=299 => {
+            validators.etag = etag;
+            validators.last_modified = last_modified;
+        }
+        304 => {
+            if etag.is_some() {
+                validators.etag = etag;
+            }
+            if last_modified.is_some() {
+                validators.last_modified = last_modified;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `config` with `If-None-Match` and `If-Modified-Since` set from its validators, in place
+/// of any it already had. `None` when no response gave validators yet.
+pub fn conditional_request(config: &RequestConfig) -> Option<RequestConfig> {
+    let validators = &config.cache_validators;
+    if validators.is_empty() {
+        return None;
+    }
+
+    let mut request = config.clone();
+    request.headers.retain(|(name, _)| {
+        !name.eq_ignore_ascii_case("if-none-match")
+            && !name.eq_ignore_ascii_case("if-modified-since")
+    });
+    if let Some(etag) = &validators.etag {
+        request
+            .headers
+            .push(("If-None-Match".to_string(), etag.clone()));
+    }
+    if let Some(last_modified) = &validators.last_modified {
+        request
+            .headers
+            .push(("If-Modified-Since".to_string(), last_modified.clone()));
+    }
+    Some(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, headers: &[(&str, &str)]) -> ResponseData {
+        ResponseData {
+            status,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn remembers_validators_across_not_modified_responses() {
+        let mut validators = CacheValidators::default();
+        remember_validators(
+            &mut validators,
+            &response(
+                200,
+                &[
+                    ("ETag", "\"v1\""),
+                    ("Last-Modified", "Tue, 14 Nov 2023 22:13:20 GMT"),
+                ],
+            ),
+        );
+        remember_validators(&mut validators, &response(304, &[("etag", "\"v2\"")]));
+        remember_validators(&mut validators, &response(500, &[]));
+
+        assert_eq!(validators.etag.as_deref(), Some("\"v2\""));
+        assert_eq!(
+            validators.last_modified.as_deref(),
+            Some("Tue, 14 Nov 2023 22:13:20 GMT")
+        );
+
+        remember_validators(&mut validators, &response(200, &[]));
+        assert!(validators.is_empty());
+    }
+
+    #[test]
+    fn replaces_existing_conditional_headers() {
+        let mut config = RequestConfig {
+            headers: vec![
+                ("Accept".to_string(), "application/json".to_string()),
+                ("if-none-match".to_string(), "\"old\"".to_string()),
+            ],
+            ..RequestConfig::default()
+        };
+        assert!(conditional_request(&config).is_none());
+
+        config.cache_validators.etag = Some("\"v1\"".to_string());
+        let request = conditional_request(&config).unwrap();
+        assert_eq!(
+            request.headers,
+            vec![
+                ("Accept".to_string(), "application/json".to_string()),
+                ("If-None-Match".to_string(), "\"v1\"".to_string()),
+            ]
+        );
+    }
+}
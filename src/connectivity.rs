@@ -0,0 +1,210 @@
+use crate::http::error_chain;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+/// Time allowed to each step of the check
+const STEP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Host and port a request connects to, and whether it uses TLS
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+}
+
+impl Target {
+    pub fn from_url(url: &str) -> Result<Target, String> {
+        let url = reqwest::Url::parse(url.trim()).map_err(|e| format!("Invalid URL: {}", e))?;
+        let tls = match url.scheme() {
+            "https" | "wss" => true,
+            "http" | "ws" => false,
+            scheme => return Err(format!("'{}' URLs can't be checked", scheme)),
+        };
+        let host = url
+            .host_str()
+            .ok_or_else(|| "The URL has no host".to_string())?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let port = url.port().unwrap_or(if tls { 443 } else { 80 });
+
+        Ok(Target { host, port, tls })
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// Outcome of one step, with what it found or why it failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckStep {
+    pub result: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+/// DNS, TCP and TLS results for a host. Steps after a failed one aren't run and stay `None`,
+/// as does TLS for plain HTTP.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectivityReport {
+    pub target: Target,
+    pub dns: CheckStep,
+    pub tcp: Option<CheckStep>,
+    pub tls: Option<CheckStep>,
+}
+
+impl ConnectivityReport {
+    fn failed(step: &Option<CheckStep>) -> bool {
+        step.as_ref().is_some_and(|step| step.result.is_err())
+    }
+
+    /// One-line reading of the results
+    pub fn summary(&self) -> &'static str {
+        if self.dns.result.is_err() {
+            "The host name doesn't resolve: check the URL, VPN or DNS settings."
+        } else if Self::failed(&self.tcp) {
+            "The host can't be reached on this port: it's down, firewalled or the port is wrong."
+        } else if Self::failed(&self.tls) {
+            "The TLS handshake fails: check the certificate, or the scheme if the port is plain HTTP."
+        } else {
+            "The network path works, errors come from the API itself."
+        }
+    }
+}
+
+/// Resolves the host, connects to the port and, for HTTPS, completes a TLS handshake.
+/// Blocking, except for the TLS step run by reqwest.
+pub async fn check(target: Target, accept_invalid_certs: bool) -> ConnectivityReport {
+    let network_target = target.clone();
+    let (dns, tcp) = tokio::task::spawn_blocking(move || resolve_and_connect(&network_target))
+        .await
+        .unwrap_or_else(|e| {
+            let step = CheckStep {
+                result: Err(e.to_string()),
+                elapsed: Duration::ZERO,
+            };
+            (step, None)
+        });
+
+    let tls = match &tcp {
+        Some(CheckStep { result: Ok(_), .. }) if target.tls => {
+            Some(handshake(&target, accept_invalid_certs).await)
+        }
+        _ => None,
+    };
+
+    ConnectivityReport {
+        target,
+        dns,
+        tcp,
+        tls,
+    }
+}
+
+fn resolve_and_connect(target: &Target) -> (CheckStep, Option<CheckStep>) {
+    let start = Instant::now();
+    let addresses: Result<Vec<SocketAddr>, String> = (target.host.as_str(), target.port)
+        .to_socket_addrs()
+        .map(|addresses| addresses.collect())
+        .map_err(|e| e.to_string());
+    let dns = CheckStep {
+        result: addresses.as_ref().map_or_else(
+            |e| Err(e.clone()),
+            |addresses| {
+                let ips: Vec<String> = addresses.iter().map(|a| a.ip().to_string()).collect();
+                Ok(ips.join(", "))
+            },
+        ),
+        elapsed: start.elapsed(),
+    };
+    let Ok(addresses) = addresses else {
+        return (dns, None);
+    };
+
+    let start = Instant::now();
+    let mut errors = Vec::new();
+    for address in &addresses {
+        match TcpStream::connect_timeout(address, STEP_TIMEOUT) {
+            Ok(_) => {
+                let tcp = CheckStep {
+                    result: Ok(format!("Connected to {}", address)),
+                    elapsed: start.elapsed(),
+                };
+                return (dns, Some(tcp));
+            }
+            Err(e) => errors.push(format!("{}: {}", address, e)),
+        }
+    }
+
+    let tcp = CheckStep {
+        result: Err(if errors.is_empty() {
+            "No address to connect to".to_string()
+        } else {
+            errors.join("\n")
+        }),
+        elapsed: start.elapsed(),
+    };
+    (dns, Some(tcp))
+}
+
+/// Opens an HTTPS connection to the host's root. Any HTTP answer means the handshake went
+/// through, whatever its status.
+async fn handshake(target: &Target, accept_invalid_certs: bool) -> CheckStep {
+    let start = Instant::now();
+    let client = reqwest::Client::builder()
+        .timeout(STEP_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .danger_accept_invalid_certs(accept_invalid_certs)
+        .build();
+
+    let result = match client {
+        Ok(client) => client
+            .head(format!("https://{}/", target))
+            .send()
+            .await
+            .map(|response| {
+                format!(
+                    "Handshake succeeded, the server answered {}",
+                    response.status()
+                )
+            })
+            .map_err(|e| error_chain(&e)),
+        Err(e) => Err(error_chain(&e)),
+    };
+
+    CheckStep {
+        result,
+        elapsed: start.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_host_port_and_scheme() {
+        assert_eq!(
+            Target::from_url("https://api.example.com/v1?x=1").unwrap(),
+            Target {
+                host: "api.example.com".to_string(),
+                port: 443,
+                tls: true
+            }
+        );
+
+        let target = Target::from_url("http://[::1]:8080/health").unwrap();
+        assert_eq!((target.port, target.tls), (8080, false));
+        assert_eq!(target.to_string(), "[::1]:8080");
+
+        assert!(Target::from_url("ftp://example.com").is_err());
+        assert!(Target::from_url("{{baseUrl}}/users").is_err());
+    }
+}
@@ -1,5 +1,19 @@
 pub const URL_INPUT_ID: &str = "url-input";
 pub const REQUEST_BODY_EDITOR_ID: &str = "request-body-editor";
 pub const REQUEST_BODY_SCROLLABLE_ID: &str = "request-body-scrollable";
+pub const POST_SCRIPT_EDITOR_ID: &str = "post-script-editor";
 pub const RESPONSE_BODY_EDITOR_ID: &str = "response-body-editor";
 pub const RESPONSE_BODY_SCROLLABLE_ID: &str = "response-body-scrollable";
+
+// Default size guards for request/response payloads (in bytes, 0 disables the limit)
+pub const DEFAULT_MAX_UPLOAD_SIZE: u64 = 10 * 1024 * 1024;
+pub const DEFAULT_MAX_DOWNLOAD_SIZE: u64 = 50 * 1024 * 1024;
+
+// How deep environment variables may reference other variables
+pub const MAX_VARIABLE_DEPTH: usize = 10;
+
+// Number of sends remembered per request for the sidebar sparkline
+pub const MAX_RUN_HISTORY: usize = 20;
+
+// Text responses above this size only render their head until expanded
+pub const RESPONSE_PREVIEW_SIZE: usize = 256 * 1024;
@@ -0,0 +1,272 @@
+use crate::assertions::{summarize_errors, validate_schema};
+use crate::types::{ApiContract, ResponseData, TestResult};
+use serde_json::{Map, Value, json};
+
+/// How many `$ref`s deep schemas are inlined, recursive schemas accept anything below this
+const MAX_INLINE_DEPTH: usize = 8;
+
+/// Captures what an OpenAPI operation documents about its responses, so a request imported
+/// from it can be checked without keeping the whole document around. Swagger 2 responses
+/// are converted to the OpenAPI 3 shape, `produces` becoming the media types.
+pub fn contract_for_operation(
+    document: &Value,
+    path: &str,
+    method: &str,
+    operation: &Value,
+) -> Option<ApiContract> {
+    let responses = operation.get("responses")?.as_object()?;
+
+    let produces = operation
+        .get("produces")
+        .or_else(|| document.get("produces"))
+        .and_then(Value::as_array)
+        .map(|types| types.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+        .filter(|types| !types.is_empty())
+        .unwrap_or_else(|| vec!["application/json"]);
+
+    let mut normalized = Map::new();
+    for (status, response) in responses {
+        let response = inline_refs(document, response, 0);
+        let response = match response.get("schema") {
+            // Swagger 2
+            Some(schema) if response.get("content").is_none() => {
+                let content: Map<String, Value> = produces
+                    .iter()
+                    .map(|media_type| (media_type.to_string(), json!({ "schema": schema })))
+                    .collect();
+                json!({ "content": content })
+            }
+            _ => response,
+        };
+        normalized.insert(status.clone(), response);
+    }
+
+    Some(ApiContract {
+        operation: format!("{} {}", method.to_uppercase(), path),
+        responses: Value::Object(normalized).to_string(),
+    })
+}
+
+/// Replaces local `$ref`s with what they point to
+fn inline_refs(document: &Value, value: &Value, depth: usize) -> Value {
+    match value {
+        Value::Object(object) => {
+            if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+                let target = reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| document.pointer(pointer));
+                return match target {
+                    Some(target) if depth < MAX_INLINE_DEPTH => {
+                        inline_refs(document, target, depth + 1)
+                    }
+                    _ => json!({}),
+                };
+            }
+
+            Value::Object(
+                object
+                    .iter()
+                    .map(|(key, value)| (key.clone(), inline_refs(document, value, depth)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| inline_refs(document, item, depth))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+/// Checks the status, content type and body of a response against the operation's spec
+pub fn check(contract: &ApiContract, response: &ResponseData) -> Vec<TestResult> {
+    let responses = match serde_json::from_str::<Value>(&contract.responses) {
+        Ok(Value::Object(responses)) => responses,
+        _ => {
+            return vec![TestResult::fail(
+                "Contract",
+                format!("The stored spec for {} is unreadable", contract.operation),
+            )];
+        }
+    };
+
+    let status = response.status.to_string();
+    let class = format!("{}XX", response.status / 100);
+    let documented = responses
+        .iter()
+        .find(|(code, _)| *code == &status)
+        .or_else(|| {
+            responses
+                .iter()
+                .find(|(code, _)| code.eq_ignore_ascii_case(&class))
+        })
+        .or_else(|| responses.iter().find(|(code, _)| *code == "default"));
+
+    let status_name = format!("Contract: status {} is documented", response.status);
+    let Some((_, definition)) = documented else {
+        let codes: Vec<&str> = responses.keys().map(String::as_str).collect();
+        return vec![TestResult::fail(
+            status_name,
+            format!(
+                "{} documents {}",
+                contract.operation,
+                if codes.is_empty() {
+                    "no responses".to_string()
+                } else {
+                    codes.join(", ")
+                }
+            ),
+        )];
+    };
+    let mut results = vec![TestResult::pass(status_name)];
+
+    let Some(content) = definition
+        .get("content")
+        .and_then(Value::as_object)
+        .filter(|content| !content.is_empty())
+    else {
+        return results;
+    };
+
+    let media_type = response
+        .content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    let matched = content
+        .iter()
+        .find(|(documented, _)| media_type_matches(documented, &media_type));
+
+    let type_name = "Contract: content type is documented";
+    let Some((_, media)) = matched else {
+        let types: Vec<&str> = content.keys().map(String::as_str).collect();
+        results.push(TestResult::fail(
+            type_name,
+            format!(
+                "Got {}, expected {}",
+                if media_type.is_empty() {
+                    "no content type"
+                } else {
+                    &media_type
+                },
+                types.join(" or ")
+            ),
+        ));
+        return results;
+    };
+    results.push(TestResult::pass(type_name));
+
+    if let Some(schema) = media.get("schema")
+        && media_type.contains("json")
+    {
+        let body_name = "Contract: body matches schema";
+        results.push(match serde_json::from_str::<Value>(&response.body) {
+            Ok(body) => {
+                let errors = validate_schema(schema, schema, &body);
+                if errors.is_empty() {
+                    TestResult::pass(body_name)
+                } else {
+                    TestResult::fail(body_name, summarize_errors(errors))
+                }
+            }
+            Err(e) => TestResult::fail(body_name, format!("The body is not valid JSON: {}", e)),
+        });
+    }
+
+    results
+}
+
+/// `documented` may be a range such as `application/*` or `*/*`
+fn media_type_matches(documented: &str, actual: &str) -> bool {
+    let documented = documented.to_lowercase();
+    match documented.split_once('/') {
+        Some(("*", "*")) => true,
+        Some((kind, "*")) => actual.split('/').next() == Some(kind),
+        _ => documented == actual,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, content_type: &str, body: &str) -> ResponseData {
+        ResponseData {
+            status,
+            body: body.to_string(),
+            content_type: content_type.to_string(),
+            size: body.len(),
+            ..Default::default()
+        }
+    }
+
+    fn pet_contract() -> ApiContract {
+        let document = json!({
+            "openapi": "3.0.0",
+            "paths": {},
+            "components": { "schemas": { "Pet": {
+                "type": "object",
+                "required": ["id"],
+                "properties": { "id": { "type": "integer" }, "tag": { "type": "string", "nullable": true } }
+            } } }
+        });
+        let operation = json!({ "responses": {
+            "200": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } } },
+            "4XX": { "description": "Client error" }
+        } });
+
+        contract_for_operation(&document, "/pets/{id}", "get", &operation).unwrap()
+    }
+
+    #[test]
+    fn flags_undocumented_status_and_schema_mismatch() {
+        let contract = pet_contract();
+        assert_eq!(contract.operation, "GET /pets/{id}");
+
+        let ok = check(
+            &contract,
+            &response(
+                200,
+                "application/json; charset=utf-8",
+                r#"{"id": 1, "tag": null}"#,
+            ),
+        );
+        assert!(ok.iter().all(|result| result.passed));
+
+        let wrong_body = check(
+            &contract,
+            &response(200, "application/json", r#"{"id": "1"}"#),
+        );
+        assert_eq!(
+            wrong_body.last().unwrap().error_message.as_deref(),
+            Some("$.id: expected integer, got string")
+        );
+
+        assert!(
+            check(&contract, &response(404, "text/plain", ""))
+                .iter()
+                .all(|r| r.passed)
+        );
+        assert!(!check(&contract, &response(500, "text/plain", ""))[0].passed);
+        assert!(!check(&contract, &response(200, "text/html", "<p>"))[1].passed);
+    }
+
+    #[test]
+    fn swagger_responses_use_produces() {
+        let document = json!({ "swagger": "2.0", "produces": ["application/xml"] });
+        let operation = json!({ "responses": { "200": { "schema": { "type": "string" } } } });
+
+        let contract = contract_for_operation(&document, "/", "get", &operation).unwrap();
+
+        assert!(
+            check(&contract, &response(200, "application/xml", "<a/>"))
+                .iter()
+                .all(|r| r.passed)
+        );
+        assert!(!check(&contract, &response(200, "application/json", "1"))[1].passed);
+    }
+}
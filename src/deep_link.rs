@@ -0,0 +1,106 @@
+use reqwest::Url;
+
+/// URL scheme Beam registers with the OS
+pub const SCHEME: &str = "beam";
+
+/// What a `beam://` link asks the app to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLink {
+    /// `beam://open?collection=Users&request=List%20users`. Collections and requests are
+    /// matched by id or name, without a request the collection is only expanded.
+    Open {
+        collection: String,
+        request: Option<String>,
+    },
+    /// `beam://import?url=https://example.com/collection.json`, confirmed before importing
+    Import { url: String },
+}
+
+impl DeepLink {
+    pub fn parse(link: &str) -> Result<DeepLink, String> {
+        let url = Url::parse(link.trim()).map_err(|e| format!("Invalid link: {}", e))?;
+        if url.scheme() != SCHEME {
+            return Err(format!("Not a {}:// link", SCHEME));
+        }
+
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        // `beam://open` holds the action in its host, `beam:open` in its path
+        let action = url.host_str().unwrap_or_default().to_string() + url.path().trim_matches('/');
+
+        match action.as_str() {
+            "open" => Ok(DeepLink::Open {
+                collection: param("collection").ok_or("The link names no collection")?,
+                request: param("request"),
+            }),
+            "import" => Ok(DeepLink::Import {
+                url: param("url").ok_or("The link has no URL to import")?,
+            }),
+            other => Err(format!("Unknown link action \"{}\"", other)),
+        }
+    }
+
+    /// The link that opens a request, by name so it reads well in a wiki
+    pub fn open_request(collection: &str, request: &str) -> String {
+        format!(
+            "{}://open?collection={}&request={}",
+            SCHEME,
+            urlencoding::encode(collection),
+            urlencoding::encode(request)
+        )
+    }
+}
+
+/// The `beam://` link among the command line arguments, as passed by the OS when a link is
+/// opened
+pub fn from_args(args: impl IntoIterator<Item = String>) -> Option<String> {
+    let prefix = format!("{}://", SCHEME);
+    args.into_iter()
+        .find(|arg| arg.to_ascii_lowercase().starts_with(&prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_open_and_import_links() {
+        let link = DeepLink::open_request("Users", "List users & roles");
+        assert_eq!(
+            link,
+            "beam://open?collection=Users&request=List%20users%20%26%20roles"
+        );
+        assert_eq!(
+            DeepLink::parse(&link),
+            Ok(DeepLink::Open {
+                collection: "Users".to_string(),
+                request: Some("List users & roles".to_string()),
+            })
+        );
+        assert_eq!(
+            DeepLink::parse("beam://open?collection=c1"),
+            Ok(DeepLink::Open {
+                collection: "c1".to_string(),
+                request: None,
+            })
+        );
+        assert_eq!(
+            DeepLink::parse("beam:import?url=https%3A%2F%2Fexample.com%2Fapi.json"),
+            Ok(DeepLink::Import {
+                url: "https://example.com/api.json".to_string(),
+            })
+        );
+        assert!(DeepLink::parse("beam://open").is_err());
+        assert!(DeepLink::parse("https://open?collection=Users").is_err());
+
+        let args = ["beam".to_string(), "BEAM://open?collection=c1".to_string()];
+        assert_eq!(
+            from_args(args),
+            Some("BEAM://open?collection=c1".to_string())
+        );
+    }
+}
@@ -0,0 +1,153 @@
+/// A header sent more than once: typed in several rows, or typed in and also added by the
+/// auth settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Duplicate {
+    /// Name as typed in its first row
+    pub name: String,
+    /// Rows setting it, in order
+    pub rows: Vec<usize>,
+    /// The auth settings add it too
+    pub auth: bool,
+}
+
+impl Duplicate {
+    pub fn summary(&self) -> String {
+        match (self.rows.len(), self.auth) {
+            (1, _) => format!("{} is also set by the auth settings", self.name),
+            (times, false) => format!("{} is set {} times", self.name, times),
+            (times, true) => format!(
+                "{} is set {} times and by the auth settings",
+                self.name, times
+            ),
+        }
+    }
+}
+
+/// Ways to send a duplicated header once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fix {
+    /// Joins the values into the first row
+    Merge,
+    /// Removes all rows but the first
+    KeepFirst,
+    /// Removes the rows, leaving the header to the auth settings
+    UseAuth,
+}
+
+/// Rows that are sent, an empty name or value leaves a row out
+fn sent_rows<'a>(headers: &'a [(String, String)]) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+    headers
+        .iter()
+        .enumerate()
+        .filter(|(_, (key, value))| !key.trim().is_empty() && !value.is_empty())
+        .map(|(index, (key, _))| (index, key.trim()))
+}
+
+/// Headers sent more than once, names compared ignoring case. `auth_headers` are the ones the
+/// auth settings add.
+pub fn find(headers: &[(String, String)], auth_headers: &[(String, String)]) -> Vec<Duplicate> {
+    let mut duplicates: Vec<Duplicate> = Vec::new();
+    for (index, name) in sent_rows(headers) {
+        match duplicates
+            .iter_mut()
+            .find(|duplicate| duplicate.name.eq_ignore_ascii_case(name))
+        {
+            Some(duplicate) => duplicate.rows.push(index),
+            None => duplicates.push(Duplicate {
+                name: name.to_string(),
+                rows: vec![index],
+                auth: false,
+            }),
+        }
+    }
+
+    for duplicate in &mut duplicates {
+        duplicate.auth = auth_headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case(&duplicate.name));
+    }
+    duplicates.retain(|duplicate| duplicate.rows.len() > 1 || duplicate.auth);
+    duplicates
+}
+
+/// Applies `fix` to the rows of the header `name`
+pub fn fix(headers: &mut Vec<(String, String)>, name: &str, fix: Fix) {
+    let rows: Vec<usize> = sent_rows(headers)
+        .filter(|(_, key)| key.eq_ignore_ascii_case(name))
+        .map(|(index, _)| index)
+        .collect();
+    let Some(&first) = rows.first() else {
+        return;
+    };
+
+    let removed = match fix {
+        Fix::Merge => {
+            // Cookies are joined as in a single Cookie header, other lists with commas
+            let separator = if name.eq_ignore_ascii_case("cookie") {
+                "; "
+            } else {
+                ", "
+            };
+            let merged = rows
+                .iter()
+                .map(|&index| headers[index].1.as_str())
+                .collect::<Vec<_>>()
+                .join(separator);
+            headers[first].1 = merged;
+            &rows[1..]
+        }
+        Fix::KeepFirst => &rows[1..],
+        Fix::UseAuth => &rows[..],
+    };
+    for &index in removed.iter().rev() {
+        headers.remove(index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(rows: &[(&str, &str)]) -> Vec<(String, String)> {
+        rows.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn finds_and_fixes_duplicate_headers() {
+        let rows = headers(&[
+            ("Accept", "application/json"),
+            ("Authorization", "Bearer typed"),
+            ("accept", "text/plain"),
+            ("X-Empty", ""),
+            ("X-Empty", ""),
+            ("", ""),
+        ]);
+        let auth = headers(&[("Authorization", "Bearer from-auth")]);
+
+        let duplicates = find(&rows, &auth);
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates[0].rows, vec![0, 2]);
+        assert_eq!(duplicates[0].summary(), "Accept is set 2 times");
+        assert_eq!(
+            duplicates[1].summary(),
+            "Authorization is also set by the auth settings"
+        );
+
+        let mut merged = rows.clone();
+        fix(&mut merged, "Accept", Fix::Merge);
+        assert_eq!(merged[0].1, "application/json, text/plain");
+        assert_eq!(merged.len(), 5);
+
+        let mut kept = rows.clone();
+        fix(&mut kept, "Accept", Fix::KeepFirst);
+        assert_eq!(kept[0].1, "application/json");
+        assert_eq!(find(&kept, &[]), vec![]);
+
+        let mut auth_only = rows;
+        fix(&mut auth_only, "Authorization", Fix::UseAuth);
+        assert!(auth_only.iter().all(|(key, _)| key != "Authorization"));
+        assert_eq!(auth_only.last(), Some(&(String::new(), String::new())));
+    }
+}
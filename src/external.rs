@@ -0,0 +1,127 @@
+use crate::types::{ResponseData, new_id};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// How the response can be opened outside Beam, if at all
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenTarget {
+    Browser,
+    DefaultApp,
+}
+
+impl OpenTarget {
+    pub fn for_response(response: &ResponseData) -> Option<OpenTarget> {
+        if response.is_binary {
+            response.body_file.as_ref().map(|_| OpenTarget::DefaultApp)
+        } else if response.content_type.contains("html") {
+            Some(OpenTarget::Browser)
+        } else if response.body.is_empty() {
+            None
+        } else {
+            Some(OpenTarget::DefaultApp)
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OpenTarget::Browser => "Open in Browser",
+            OpenTarget::DefaultApp => "Open Externally",
+        }
+    }
+}
+
+/// File extension matching a Content-Type, so the OS picks a suitable application
+pub fn extension_for_content_type(content_type: &str) -> &'static str {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+
+    match mime.as_str() {
+        "text/html" | "application/xhtml+xml" => "html",
+        "application/pdf" => "pdf",
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "application/zip" => "zip",
+        "text/csv" => "csv",
+        "text/css" => "css",
+        "application/javascript" | "text/javascript" => "js",
+        mime if mime.contains("json") => "json",
+        mime if mime.contains("xml") => "xml",
+        mime if mime.starts_with("text/") => "txt",
+        _ => "bin",
+    }
+}
+
+/// Directory for response bodies handed to other applications
+fn output_dir() -> PathBuf {
+    std::env::temp_dir().join("beam")
+}
+
+fn write_output(content: &[u8], content_type: &str) -> Result<PathBuf, String> {
+    let dir = output_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let path = dir.join(format!(
+        "response-{}.{}",
+        new_id(),
+        extension_for_content_type(content_type)
+    ));
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+
+    Ok(path)
+}
+
+/// Keeps the raw bytes of a binary response on disk so it can be opened later
+pub fn save_binary_body(bytes: &[u8], content_type: &str) -> Option<PathBuf> {
+    write_output(bytes, content_type)
+        .inspect_err(|e| log::warn!("Failed to save binary response: {}", e))
+        .ok()
+}
+
+/// Opens the response body with the OS default handler for its content type
+pub fn open_response(response: &ResponseData) -> Result<(), String> {
+    let path = match &response.body_file {
+        Some(path) if response.is_binary && path.exists() => path.clone(),
+        _ if response.is_binary => {
+            return Err(
+                "The binary body is no longer available, send the request again".to_string(),
+            );
+        }
+        _ => write_output(response.body.as_bytes(), &response.content_type)?,
+    };
+
+    open_path(&path)
+}
+
+pub fn open_path(path: &Path) -> Result<(), String> {
+    open_target(path.as_os_str())
+}
+
+pub fn open_url(url: &str) -> Result<(), String> {
+    open_target(url.as_ref())
+}
+
+fn open_target(target: &std::ffi::OsStr) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+
+    command
+        .arg(target)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open {}: {}", target.to_string_lossy(), e))
+}
@@ -0,0 +1,129 @@
+use fake::Fake;
+use fake::faker::address::en::{CityName, CountryName, StateName, StreetName, ZipCode};
+use fake::faker::company::en::{CatchPhrase, CompanyName};
+use fake::faker::internet::en::{IPv4, SafeEmail, UserAgent, Username};
+use fake::faker::job::en::Title as JobTitle;
+use fake::faker::lorem::en::{Paragraph, Sentence, Word, Words};
+use fake::faker::name::en::{FirstName, LastName, Name};
+use fake::faker::phone_number::en::PhoneNumber;
+use crate::types::new_id;
+use rand::Rng;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Names of the dynamic placeholders, used as `{{$name}}` in requests and as
+/// `beam.faker.name()` in scripts
+pub const PLACEHOLDERS: &[&str] = &[
+    "randomFullName",
+    "randomFirstName",
+    "randomLastName",
+    "randomEmail",
+    "randomUserName",
+    "randomPhoneNumber",
+    "randomCity",
+    "randomCountry",
+    "randomState",
+    "randomStreetAddress",
+    "randomZipCode",
+    "randomCompanyName",
+    "randomCatchPhrase",
+    "randomJobTitle",
+    "randomIP",
+    "randomUserAgent",
+    "randomUUID",
+    "randomInt",
+    "randomBoolean",
+    "randomWord",
+    "randomLoremSentence",
+    "randomLoremParagraph",
+    "timestamp",
+    "isoTimestamp",
+    "lorem",
+];
+
+/// Most words `{{$lorem(count)}}` generates
+const MAX_LOREM_WORDS: usize = 1000;
+
+/// Generates a value for a dynamic placeholder. `argument` is the text between the
+/// parentheses of `{{$name(argument)}}`, if any. Returns None for unknown names.
+pub fn generate(name: &str, argument: Option<&str>) -> Option<String> {
+    let mut rng = rand::rng();
+
+    let value = match name {
+        "randomFullName" => Name().fake(),
+        "randomFirstName" => FirstName().fake(),
+        "randomLastName" => LastName().fake(),
+        "randomEmail" => SafeEmail().fake(),
+        "randomUserName" => Username().fake(),
+        "randomPhoneNumber" => PhoneNumber().fake(),
+        "randomCity" => CityName().fake(),
+        "randomCountry" => CountryName().fake(),
+        "randomState" => StateName().fake(),
+        "randomStreetAddress" => format!(
+            "{} {}",
+            rng.random_range(1..9999),
+            StreetName().fake::<String>()
+        ),
+        "randomZipCode" => ZipCode().fake(),
+        "randomCompanyName" => CompanyName().fake(),
+        "randomCatchPhrase" => CatchPhrase().fake(),
+        "randomJobTitle" => JobTitle().fake(),
+        "randomIP" => IPv4().fake(),
+        "randomUserAgent" => UserAgent().fake(),
+        "randomUUID" => new_id(),
+        "randomInt" => {
+            let (min, max) = argument.and_then(parse_range).unwrap_or((0, 1000));
+            rng.random_range(min..=max).to_string()
+        }
+        "randomBoolean" => rng.random_bool(0.5).to_string(),
+        "randomWord" => Word().fake(),
+        "randomLoremSentence" => Sentence(4..12).fake(),
+        "randomLoremParagraph" => Paragraph(3..6).fake(),
+        "timestamp" => chrono::Utc::now().timestamp().to_string(),
+        "isoTimestamp" => chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        "lorem" => {
+            let count = argument
+                .and_then(|arg| arg.trim().parse::<usize>().ok())
+                .unwrap_or(10)
+                .clamp(1, MAX_LOREM_WORDS);
+            // fake takes an exclusive range, so this asks for exactly `count` words
+            Words(count..count + 1).fake::<Vec<String>>().join(" ")
+        }
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// A `{{...}}` reference, capturing what is inside it
+static REFERENCE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{\{([^}]+)\}\}").unwrap());
+
+/// Replaces every `{{$name}}` / `{{$name(argument)}}` in `input` with generated data,
+/// leaving unknown placeholders untouched
+pub fn resolve_placeholders(input: &str) -> String {
+    if !input.contains("{{$") {
+        return input.to_string();
+    }
+
+    REFERENCE
+        .replace_all(input, |captures: &regex::Captures| {
+            resolve_placeholder(captures[1].trim()).unwrap_or_else(|| captures[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Resolves the inside of a `{{...}}` reference if it is a dynamic placeholder
+pub fn resolve_placeholder(reference: &str) -> Option<String> {
+    let reference = reference.strip_prefix('$')?;
+
+    match reference.split_once('(') {
+        Some((name, rest)) => generate(name.trim(), Some(rest.strip_suffix(')')?)),
+        None => generate(reference, None),
+    }
+}
+
+fn parse_range(argument: &str) -> Option<(i64, i64)> {
+    let (min, max) = argument.split_once(',')?;
+    let (min, max) = (min.trim().parse().ok()?, max.trim().parse().ok()?);
+    if min <= max { Some((min, max)) } else { None }
+}
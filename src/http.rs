@@ -1,7 +1,429 @@
-use crate::types::{HttpMethod, RequestConfig, ResponseData, AuthType};
+use crate::certificate;
+use crate::external;
+use crate::ndjson;
+use crate::signing;
+use crate::url_encoding::encode_unsafe_query_chars;
+use crate::types::{ApiKeyLocation, HttpMethod, RequestConfig, ResponseData, AuthType};
+use std::sync::{Arc, LazyLock, Mutex};
 use std::time::Instant;
 use base64::{Engine as _, engine::general_purpose};
 use log::{info};
+use std::io::Read;
+use encoding_rs::Encoding;
+use regex::Regex;
+
+/// Why a request did not produce a response
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum RequestError {
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Could not resolve host {host}: {detail}")]
+    Dns { host: String, detail: String },
+
+    #[error("Connection refused by {host}: {detail}")]
+    ConnectionRefused { host: String, detail: String },
+
+    #[error("Network unavailable: {0}")]
+    NetworkUnavailable(String),
+
+    #[error("TLS error: {0}")]
+    Tls(String),
+
+    #[error("Request timed out: {0}")]
+    Timeout(String),
+
+    #[error("Too many redirects: {0}")]
+    TooManyRedirects(String),
+
+    #[error("Response exceeds download limit: at least {size} bytes, limit is {limit} bytes")]
+    ResponseTooLarge { size: u64, limit: u64 },
+
+    #[error("Failed to read response: {0}")]
+    InvalidResponse(String),
+
+    #[error("Request failed: {0}")]
+    Other(String),
+}
+
+impl RequestError {
+    /// Short label shown in place of the status text
+    pub fn label(&self) -> &'static str {
+        match self {
+            RequestError::InvalidRequest(_) => "Invalid Request",
+            RequestError::Dns { .. } => "DNS Failure",
+            RequestError::ConnectionRefused { .. } => "Connection Refused",
+            RequestError::NetworkUnavailable(_) => "Network Unavailable",
+            RequestError::Tls(_) => "TLS Error",
+            RequestError::Timeout(_) => "Timeout",
+            RequestError::TooManyRedirects(_) => "Too Many Redirects",
+            RequestError::ResponseTooLarge { .. } => "Response Too Large",
+            RequestError::InvalidResponse(_) => "Invalid Response",
+            RequestError::Other(_) => "Error",
+        }
+    }
+
+    /// What the user can try next to get past the error
+    pub fn hint(&self) -> &'static str {
+        match self {
+            RequestError::InvalidRequest(_) => "Check the URL, method and body of the request.",
+            RequestError::Dns { .. } => {
+                "Check the host name for typos, that the environment variable in the URL is set, and that you are on the right network or VPN."
+            }
+            RequestError::ConnectionRefused { .. } => {
+                "Nothing is listening on that port. Make sure the server is running and the port in the URL is correct."
+            }
+            RequestError::NetworkUnavailable(_) => {
+                "Check your internet connection. You can queue the request to retry once the network is back."
+            }
+            RequestError::Tls(_) => {
+                "The secure connection failed. Check that the certificate is valid for this host and not expired, or try http:// for local servers."
+            }
+            RequestError::Timeout(_) => {
+                "The server took too long to answer. It may be overloaded, or a firewall may be dropping the connection."
+            }
+            RequestError::TooManyRedirects(_) => {
+                "The server keeps redirecting. Look for a redirect loop, often caused by missing cookies or an http/https mismatch."
+            }
+            RequestError::ResponseTooLarge { .. } => {
+                "Raise the download limit in the request settings, or download it anyway."
+            }
+            RequestError::InvalidResponse(_) => {
+                "The server sent a response Beam could not read. Try disabling decompression for this request."
+            }
+            RequestError::Other(_) => "Check the request details and try again.",
+        }
+    }
+
+    /// True when the server could not be reached, so the request may succeed later
+    pub fn is_network_unavailable(&self) -> bool {
+        matches!(
+            self,
+            RequestError::Dns { .. }
+                | RequestError::ConnectionRefused { .. }
+                | RequestError::NetworkUnavailable(_)
+                | RequestError::Timeout(_)
+        )
+    }
+
+    /// Sorts a reqwest failure into the category with the most useful hint
+    fn from_reqwest(error: reqwest::Error, url: &str) -> RequestError {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let detail = error_chain(&error);
+        let lowercase = detail.to_lowercase();
+
+        if error.is_redirect() {
+            RequestError::TooManyRedirects(detail)
+        } else if error.is_timeout() {
+            RequestError::Timeout(detail)
+        } else if lowercase.contains("dns error") || lowercase.contains("failed to lookup address") {
+            RequestError::Dns { host, detail }
+        } else if lowercase.contains("certificate")
+            || lowercase.contains("tls")
+            || lowercase.contains("ssl")
+            || lowercase.contains("handshake")
+        {
+            RequestError::Tls(detail)
+        } else if has_io_error_kind(&error, std::io::ErrorKind::ConnectionRefused) {
+            RequestError::ConnectionRefused { host, detail }
+        } else if error.is_connect() {
+            RequestError::NetworkUnavailable(detail)
+        } else if error.is_body() || error.is_decode() {
+            RequestError::InvalidResponse(detail)
+        } else {
+            RequestError::Other(detail)
+        }
+    }
+}
+
+/// Joins an error with its sources, reqwest keeps the interesting part at the bottom
+pub(crate) fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+fn has_io_error_kind(error: &dyn std::error::Error, kind: std::io::ErrorKind) -> bool {
+    let mut source = error.source();
+    while let Some(cause) = source {
+        if cause
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|io| io.kind() == kind)
+        {
+            return true;
+        }
+        source = cause.source();
+    }
+    false
+}
+
+/// Computes the size in bytes of the request as it goes over the wire,
+/// returned as (headers, body). Headers include the request line, auth and
+/// the implicit Host, Content-Type and Content-Length headers.
+pub fn request_payload_size(config: &RequestConfig) -> (usize, usize) {
+    let has_body = matches!(
+        config.method,
+        HttpMethod::POST | HttpMethod::PUT | HttpMethod::PATCH
+    ) && !config.body.is_empty();
+    let body_size = if has_body { config.body.len() } else { 0 };
+
+    let without_scheme = config
+        .url
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .unwrap_or(&config.url);
+    let (host, path) = match without_scheme.find('/') {
+        Some(index) => without_scheme.split_at(index),
+        None => (without_scheme, "/"),
+    };
+
+    let query: Vec<String> = config
+        .params
+        .iter()
+        .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+        .chain(computed_auth_query(config).iter())
+        .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+        .collect();
+    let query_size = if query.is_empty() {
+        0
+    } else {
+        query.join("&").len() + 1
+    };
+
+    let header_line = |key: &str, value: &str| key.len() + value.len() + 4; // ": " and CRLF
+
+    // Request line: METHOD SP path SP HTTP/1.1 CRLF
+    let mut headers_size =
+        config.method.to_string().len() + path.len() + query_size + " HTTP/1.1\r\n".len() + 1;
+    headers_size += header_line("Host", host);
+
+    for (key, value) in &config.headers {
+        if !key.is_empty() && !value.is_empty() {
+            headers_size += header_line(key, value);
+        }
+    }
+
+    if let Some(accept_encoding) = accept_encoding_header(config) {
+        headers_size += header_line("Accept-Encoding", &accept_encoding);
+    }
+
+    for (name, value) in computed_auth_headers(config) {
+        headers_size += header_line(&name, &value);
+    }
+
+    if has_body {
+        if !config.headers.iter().any(|(k, _)| k.to_lowercase() == "content-type") {
+            headers_size += header_line("Content-Type", &config.content_type);
+        }
+        headers_size += header_line("Content-Length", &body_size.to_string());
+    }
+
+    // Blank line terminating the header section
+    headers_size += 2;
+
+    (headers_size, body_size)
+}
+
+/// Renders the request as it is written on the wire: request line, headers and body.
+fn format_raw_request(request: &reqwest::Request) -> String {
+    let url = request.url();
+    let mut target = url.path().to_string();
+    if let Some(query) = url.query() {
+        target.push('?');
+        target.push_str(query);
+    }
+
+    let mut raw = format!("{} {} HTTP/1.1\r\n", request.method(), target);
+
+    if let Some(host) = url.host_str() {
+        match url.port() {
+            Some(port) => raw.push_str(&format!("host: {}:{}\r\n", host, port)),
+            None => raw.push_str(&format!("host: {}\r\n", host)),
+        }
+    }
+
+    for (name, value) in request.headers() {
+        raw.push_str(&format!(
+            "{}: {}\r\n",
+            name,
+            String::from_utf8_lossy(value.as_bytes())
+        ));
+    }
+
+    let body = request.body().and_then(|body| body.as_bytes());
+    if let Some(bytes) = body
+        && !request.headers().contains_key(reqwest::header::CONTENT_LENGTH)
+    {
+        raw.push_str(&format!("content-length: {}\r\n", bytes.len()));
+    }
+
+    raw.push_str("\r\n");
+
+    if let Some(bytes) = body {
+        raw.push_str(&String::from_utf8_lossy(bytes));
+    }
+
+    raw
+}
+
+/// Headers added by the selected auth type, in the order they are sent
+pub fn computed_auth_headers(config: &RequestConfig) -> Vec<(String, String)> {
+    match config.auth_type {
+        AuthType::None | AuthType::InheritFromEnvironment => vec![],
+        AuthType::Bearer => {
+            if config.bearer_token.is_empty() {
+                return vec![];
+            }
+            vec![("Authorization".to_string(), format!("Bearer {}", config.bearer_token))]
+        }
+        AuthType::Basic => {
+            if config.basic_username.is_empty() {
+                return vec![];
+            }
+            let credentials = if config.basic_password.is_empty() {
+                config.basic_username.clone()
+            } else {
+                format!("{}:{}", config.basic_username, config.basic_password)
+            };
+            let encoded = general_purpose::STANDARD.encode(credentials);
+            vec![("Authorization".to_string(), format!("Basic {}", encoded))]
+        }
+        AuthType::ApiKey => {
+            if config.api_key.is_empty()
+                || config.api_key_header.is_empty()
+                || config.api_key_location != ApiKeyLocation::Header
+            {
+                return vec![];
+            }
+            vec![(config.api_key_header.clone(), config.api_key.clone())]
+        }
+        AuthType::Hmac => signing::hmac_headers(config),
+        AuthType::Hawk => signing::hawk_header(config).into_iter().collect(),
+    }
+}
+
+/// Query parameters added by the selected auth type
+pub fn computed_auth_query(config: &RequestConfig) -> Vec<(String, String)> {
+    match config.auth_type {
+        AuthType::ApiKey
+            if config.api_key_location == ApiKeyLocation::Query
+                && !config.api_key.is_empty()
+                && !config.api_key_header.is_empty() =>
+        {
+            vec![(config.api_key_header.clone(), config.api_key.clone())]
+        }
+        _ => vec![],
+    }
+}
+
+/// Value for the `Accept-Encoding` header, or None if it should not be sent
+fn accept_encoding_header(config: &RequestConfig) -> Option<String> {
+    let user_defined = config
+        .headers
+        .iter()
+        .any(|(k, v)| k.eq_ignore_ascii_case("accept-encoding") && !v.is_empty());
+
+    if user_defined || config.accept_encoding.is_empty() {
+        return None;
+    }
+
+    Some(
+        config
+            .accept_encoding
+            .iter()
+            .map(|encoding| encoding.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// Decodes a body according to its `Content-Encoding`, applying codings in reverse order.
+/// Each decoded coding is read up to `limit` bytes, 0 for no limit, so a small compressed
+/// body can't expand past the download limit.
+fn decode_body(
+    bytes: Vec<u8>,
+    content_encoding: &str,
+    limit: u64,
+) -> Result<Vec<u8>, RequestError> {
+    let failed = |e: std::io::Error| {
+        RequestError::InvalidResponse(format!("Failed to decompress response: {}", e))
+    };
+    // One byte past the limit tells a body that exceeds it from one that fills it
+    let cap = if limit > 0 { limit.saturating_add(1) } else { u64::MAX };
+    let mut decoded = bytes;
+
+    for encoding in content_encoding.rsplit(',').map(str::trim) {
+        let input = decoded.as_slice();
+        let decoder: Box<dyn Read + '_> = match encoding {
+            "gzip" | "x-gzip" => Box::new(flate2::read::GzDecoder::new(input)),
+            "deflate" => Box::new(flate2::read::ZlibDecoder::new(input)),
+            "br" => Box::new(brotli::Decompressor::new(input, 4096)),
+            "zstd" => Box::new(zstd::stream::read::Decoder::new(input).map_err(failed)?),
+            "identity" | "" => continue,
+            other => {
+                return Err(RequestError::InvalidResponse(format!(
+                    "Failed to decompress response: Unsupported content encoding: {}",
+                    other
+                )));
+            }
+        };
+        let mut output = Vec::new();
+        decoder.take(cap).read_to_end(&mut output).map_err(failed)?;
+        if limit > 0 && output.len() as u64 > limit {
+            return Err(RequestError::ResponseTooLarge {
+                size: output.len() as u64,
+                limit,
+            });
+        }
+        decoded = output;
+    }
+
+    Ok(decoded)
+}
+
+/// Decodes a text body to UTF-8. The encoding comes from a byte order mark, the
+/// Content-Type charset, or an HTML/XML declaration near the start of the body, in that
+/// order. Undeclared bodies that are not valid UTF-8 are read as Windows-1252.
+fn decode_text_body(bytes: &[u8], content_type: &str) -> String {
+    let declared = content_type
+        .split(';')
+        .filter_map(|param| param.trim().split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+        .and_then(|(_, value)| Encoding::for_label(value.trim().trim_matches('"').as_bytes()))
+        .or_else(|| sniff_declared_charset(bytes));
+
+    let encoding = match declared {
+        Some(encoding) => encoding,
+        None if std::str::from_utf8(bytes).is_ok() => encoding_rs::UTF_8,
+        None => encoding_rs::WINDOWS_1252,
+    };
+
+    // decode() prefers a BOM over the given encoding
+    let (text, _, _) = encoding.decode(bytes);
+    text.into_owned()
+}
+
+static DECLARED_CHARSET: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:<meta[^>]*charset|<\?xml[^>]*encoding)\s*=\s*["']?([a-z0-9_:.-]+)"#).unwrap()
+});
+
+/// Looks for `<meta charset=...>`, `<meta http-equiv ... charset=...>` or
+/// `<?xml ... encoding=...?>` in the first kilobyte of the body
+fn sniff_declared_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(1024)]).to_lowercase();
+
+    DECLARED_CHARSET
+        .captures(&head)
+        .and_then(|captures| Encoding::for_label(captures[1].as_bytes()))
+}
 
 fn is_binary_content_type(content_type: &str) -> bool {
     let content_type_lower = content_type.to_lowercase();
@@ -31,21 +453,85 @@ fn is_binary_content_type(content_type: &str) -> bool {
     true
 }
 
-pub async fn send_request(config: RequestConfig) -> Result<ResponseData, String> {
+fn build_client(config: &RequestConfig) -> Result<reqwest::Client, RequestError> {
+    let mut builder = reqwest::Client::builder();
+
+    let socket_path = config.unix_socket.trim();
+    if !socket_path.is_empty() {
+        #[cfg(unix)]
+        {
+            builder = builder.unix_socket(std::path::PathBuf::from(socket_path));
+        }
+
+        #[cfg(not(unix))]
+        {
+            return Err(RequestError::InvalidRequest(
+                "Unix domain sockets are not supported on this platform".to_string(),
+            ));
+        }
+    }
+
+    let overrides = &config.connection;
+    if overrides.disable_redirects {
+        builder = builder.redirect(reqwest::redirect::Policy::none());
+    }
+    if overrides.disable_proxy {
+        builder = builder.no_proxy();
+    }
+    if overrides.accept_invalid_certs {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    builder
+        .tls_info(true)
+        .build()
+        .map_err(|e| RequestError::Other(format!("Failed to create HTTP client: {}", e)))
+}
+
+/// The URL as it is sent: unsafe query characters are percent-encoded unless the request
+/// asks to send the query as typed
+fn request_url(config: &RequestConfig) -> String {
+    if config.send_query_as_typed {
+        config.url.clone()
+    } else {
+        encode_unsafe_query_chars(&config.url)
+    }
+}
+
+/// Lines of an NDJSON response received so far, filled while the body streams in
+pub type StreamedBody = Arc<Mutex<Vec<u8>>>;
+
+pub async fn send_request(config: RequestConfig) -> Result<ResponseData, RequestError> {
+    send_request_streaming(config, None).await
+}
+
+/// Sends the request, copying the body of NDJSON responses into `streamed` as it arrives
+pub async fn send_request_streaming(
+    config: RequestConfig,
+    streamed: Option<StreamedBody>,
+) -> Result<ResponseData, RequestError> {
     let start_time = Instant::now();
 
     // Validate URL
     if config.url.trim().is_empty() {
-        return Err("URL cannot be empty".to_string());
+        return Err(RequestError::InvalidRequest("URL cannot be empty".to_string()));
     }
 
     // Basic URL validation
     if !config.url.starts_with("http://") && !config.url.starts_with("https://") {
-        return Err("URL must start with http:// or https://".to_string());
+        return Err(RequestError::InvalidRequest(
+            "URL must start with http:// or https://".to_string(),
+        ));
     }
 
+    // Signing and the request itself must see the same URL
+    let config = RequestConfig {
+        url: request_url(&config),
+        ..config
+    };
+
     // TODO: reuse the client
-    let client = reqwest::Client::new();
+    let client = build_client(&config)?;
 
     // Build the request
     // TODO: use client.request directly instead of match clause
@@ -57,45 +543,27 @@ pub async fn send_request(config: RequestConfig) -> Result<ResponseData, String>
         HttpMethod::PATCH => client.patch(&config.url),
         HttpMethod::HEAD => client.head(&config.url),
         HttpMethod::OPTIONS => {
-            return Err("OPTIONS method not supported yet".to_string());
+            return Err(RequestError::InvalidRequest(
+                "OPTIONS method not supported yet".to_string(),
+            ));
         }
     };
 
-    // Add headers
+    // Add headers, in the order of the Headers tab
     for (key, value) in &config.headers {
         if !key.is_empty() && !value.is_empty() {
             request_builder = request_builder.header(key, value);
         }
     }
 
+    // Advertise supported compression unless the user set the header explicitly
+    if let Some(accept_encoding) = accept_encoding_header(&config) {
+        request_builder = request_builder.header("Accept-Encoding", accept_encoding);
+    }
+
     // Add authentication
-    match config.auth_type {
-        AuthType::None => {
-            // No authentication needed
-        }
-        AuthType::Bearer => {
-            if !config.bearer_token.is_empty() {
-                let auth_header = format!("Bearer {}", config.bearer_token);
-                request_builder = request_builder.header("Authorization", auth_header);
-            }
-        }
-        AuthType::Basic => {
-            if !config.basic_username.is_empty() {
-                let credentials = if config.basic_password.is_empty() {
-                    config.basic_username.clone()
-                } else {
-                    format!("{}:{}", config.basic_username, config.basic_password)
-                };
-                let encoded = general_purpose::STANDARD.encode(credentials);
-                let auth_header = format!("Basic {}", encoded);
-                request_builder = request_builder.header("Authorization", auth_header);
-            }
-        }
-        AuthType::ApiKey => {
-            if !config.api_key.is_empty() && !config.api_key_header.is_empty() {
-                request_builder = request_builder.header(&config.api_key_header, &config.api_key);
-            }
-        }
+    for (name, value) in computed_auth_headers(&config) {
+        request_builder = request_builder.header(name, value);
     }
 
     // Add query parameters
@@ -105,6 +573,10 @@ pub async fn send_request(config: RequestConfig) -> Result<ResponseData, String>
             query_params.push((key, value));
         }
     }
+    let auth_query = computed_auth_query(&config);
+    for (key, value) in &auth_query {
+        query_params.push((key, value));
+    }
     if !query_params.is_empty() {
         request_builder = request_builder.query(&query_params);
     }
@@ -121,9 +593,14 @@ pub async fn send_request(config: RequestConfig) -> Result<ResponseData, String>
         }
     }
 
+    let request = request_builder
+        .build()
+        .map_err(|e| RequestError::InvalidRequest(format!("Failed to build request: {}", e)))?;
+    let raw_request = format_raw_request(&request);
+
     info!("DEBUG: sending request - {:?}", Instant::now());
     // Send the request
-    match request_builder.send().await {
+    match client.execute(request).await {
         Ok(response) => {
             info!("DEBUG: done sending request - {:?}", Instant::now());
             let status = response.status().as_u16();
@@ -137,6 +614,15 @@ pub async fn send_request(config: RequestConfig) -> Result<ResponseData, String>
                 }
             }
 
+            // The TLS backend only hands over the leaf certificate of the chain
+            let certificates = response
+                .extensions()
+                .get::<reqwest::tls::TlsInfo>()
+                .and_then(|info| info.peer_certificate())
+                .and_then(certificate::parse_certificate)
+                .into_iter()
+                .collect();
+
             // Get content type
             let content_type = response
                 .headers()
@@ -145,38 +631,90 @@ pub async fn send_request(config: RequestConfig) -> Result<ResponseData, String>
                 .unwrap_or("application/octet-stream")
                 .to_string();
 
-            // Determine if response is binary
-            let is_binary = is_binary_content_type(&content_type);
+            let content_encoding = response
+                .headers()
+                .get("content-encoding")
+                .and_then(|ce| ce.to_str().ok())
+                .map(|ce| ce.trim().to_lowercase())
+                .filter(|ce| !ce.is_empty() && ce != "identity");
 
-            // Get response body
-            let (body, actual_size) = if is_binary {
-                // For binary content, get the raw bytes and create a summary
-                match response.bytes().await {
-                    Ok(bytes) => {
-                        let size = bytes.len();
-                        let summary = format!(
-                            "[Binary data: {} bytes]\nContent-Type: {}\nFirst 100 bytes (hex): {}",
-                            size,
-                            content_type,
-                            bytes.iter()
-                                .take(100)
-                                .map(|b| format!("{:02x}", b))
-                                .collect::<Vec<_>>()
-                                .join(" ")
-                        );
-                        (summary, size)
+            // Reject responses that announce a size above the download limit
+            let max_download_size = config.max_download_size;
+            if max_download_size > 0
+                && let Some(length) = response.content_length()
+                && length > max_download_size
+            {
+                return Err(RequestError::ResponseTooLarge {
+                    size: length,
+                    limit: max_download_size,
+                });
+            }
+
+            // Records of uncompressed NDJSON bodies can be shown before the body ends
+            let streamed = streamed
+                .filter(|_| content_encoding.is_none() && ndjson::is_ndjson(&content_type));
+
+            // Read the body in chunks so we can stop as soon as the limit is exceeded
+            let mut response = response;
+            let mut bytes = Vec::new();
+            loop {
+                match response.chunk().await {
+                    Ok(Some(chunk)) => {
+                        bytes.extend_from_slice(&chunk);
+                        if let Some(streamed) = &streamed
+                            && let Ok(mut streamed) = streamed.lock()
+                        {
+                            streamed.extend_from_slice(&chunk);
+                        }
+                        if max_download_size > 0 && bytes.len() as u64 > max_download_size {
+                            return Err(RequestError::ResponseTooLarge {
+                                size: bytes.len() as u64,
+                                limit: max_download_size,
+                            });
+                        }
                     }
-                    Err(e) => return Err(format!("Failed to read binary response: {}", e)),
+                    Ok(None) => break,
+                    Err(e) => return Err(RequestError::InvalidResponse(error_chain(&e))),
                 }
-            } else {
-                // For text content, try to convert to string
-                match response.text().await {
-                    Ok(text) => {
-                        let size = text.len();
-                        (text, size)
-                    }
-                    Err(e) => return Err(format!("Failed to read text response: {}", e)),
+            }
+
+            // Decompress the body, keeping track of the size on the wire
+            let (bytes, compressed_size) = match &content_encoding {
+                Some(encoding) if config.decompress_response => {
+                    let transferred = bytes.len();
+                    let decoded = decode_body(bytes, encoding, max_download_size)?;
+                    (decoded, Some(transferred))
                 }
+                _ => (bytes, None),
+            };
+
+            // Determine if response is binary, compressed bodies we did not decode are opaque
+            let is_binary = is_binary_content_type(&content_type)
+                || (content_encoding.is_some() && compressed_size.is_none());
+
+            // Get response body
+            let actual_size = bytes.len();
+            let body = if is_binary {
+                // For binary content, create a summary of the raw bytes
+                format!(
+                    "[Binary data: {} bytes]\nContent-Type: {}\nFirst 100 bytes (hex): {}",
+                    actual_size,
+                    content_type,
+                    bytes.iter()
+                        .take(100)
+                        .map(|b| format!("{:02x}", b))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                )
+            } else {
+                decode_text_body(&bytes, &content_type)
+            };
+
+            // Keep binary bodies on disk so they can be opened with another application
+            let body_file = if is_binary {
+                external::save_binary_body(&bytes, &content_type)
+            } else {
+                None
             };
 
             let elapsed = start_time.elapsed();
@@ -189,10 +727,15 @@ pub async fn send_request(config: RequestConfig) -> Result<ResponseData, String>
                 content_type,
                 is_binary,
                 size: actual_size,
+                compressed_size,
                 time: elapsed.as_millis() as u64,
+                raw_request,
+                body_file,
+                certificates,
+                test_results: vec![],
             })
         }
-        Err(e) => Err(format!("Request failed: {}", e)),
+        Err(e) => Err(RequestError::from_reqwest(e, &config.url)),
     }
 }
 
@@ -215,8 +758,8 @@ pub fn generate_curl_command(config: &RequestConfig) -> String {
 
     // Add authentication
     match config.auth_type {
-        AuthType::None => {
-            // No authentication needed
+        AuthType::None | AuthType::InheritFromEnvironment => {
+            // No authentication needed, or no environment to inherit it from
         }
         AuthType::Bearer => {
             if !config.bearer_token.is_empty() {
@@ -235,10 +778,10 @@ pub fn generate_curl_command(config: &RequestConfig) -> String {
                 }
             }
         }
-        AuthType::ApiKey => {
-            if !config.api_key.is_empty() && !config.api_key_header.is_empty() {
+        AuthType::ApiKey | AuthType::Hmac | AuthType::Hawk => {
+            for (name, value) in computed_auth_headers(config) {
                 curl_parts.push("-H".to_string());
-                curl_parts.push(format!("'{}: {}'", config.api_key_header, config.api_key));
+                curl_parts.push(format!("'{}: {}'", name, value));
             }
         }
     }
@@ -252,10 +795,11 @@ pub fn generate_curl_command(config: &RequestConfig) -> String {
     }
 
     // Build URL with query parameters
-    let mut url = config.url.clone();
+    let mut url = request_url(config);
     let query_params: Vec<String> = config.params
         .iter()
         .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+        .chain(computed_auth_query(config).iter())
         .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
         .collect();
 
@@ -268,6 +812,22 @@ pub fn generate_curl_command(config: &RequestConfig) -> String {
         url.push_str(&query_params.join("&"));
     }
 
+    if config.decompress_response && accept_encoding_header(config).is_some() {
+        curl_parts.push("--compressed".to_string());
+    }
+
+    if config.connection.disable_proxy {
+        curl_parts.push("--noproxy '*'".to_string());
+    }
+    if config.connection.accept_invalid_certs {
+        curl_parts.push("-k".to_string());
+    }
+
+    if !config.unix_socket.trim().is_empty() {
+        curl_parts.push("--unix-socket".to_string());
+        curl_parts.push(format!("'{}'", config.unix_socket.trim()));
+    }
+
     curl_parts.push(format!("'{}'", url));
 
     curl_parts.join(" ")
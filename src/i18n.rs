@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Languages the interface is translated to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// The ISO 639-1 code, as in `LANG=es_ES.UTF-8`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    /// The language of the system's locale, English when it isn't translated
+    pub fn from_system() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| {
+                let code = locale.split(['_', '.', '-']).next().unwrap_or_default();
+                Language::ALL
+                    .into_iter()
+                    .find(|language| language.code().eq_ignore_ascii_case(code))
+            })
+            .unwrap_or_default()
+    }
+
+    fn catalog(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../assets/locales/en.ftl"),
+            Language::Spanish => include_str!("../assets/locales/es.ftl"),
+        }
+    }
+}
+
+impl std::fmt::Display for Language {
+    /// Each language is named in itself, so it can be found whatever the current one is
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+static MESSAGES: LazyLock<HashMap<Language, HashMap<String, String>>> = LazyLock::new(|| {
+    Language::ALL
+        .into_iter()
+        .map(|language| (language, parse(language.catalog())))
+        .collect()
+});
+
+pub fn set_language(language: Language) {
+    let index = Language::ALL
+        .iter()
+        .position(|l| *l == language)
+        .unwrap_or(0);
+    CURRENT.store(index as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::ALL
+        .get(CURRENT.load(Ordering::Relaxed) as usize)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Reads a catalog in a subset of the Fluent syntax: `id = value` messages whose indented
+/// lines continue the value, `#` comments and `{ $name }` placeables.
+fn parse(catalog: &str) -> HashMap<String, String> {
+    let mut messages = HashMap::new();
+    let mut current: Option<(String, String)> = None;
+
+    for line in catalog.lines() {
+        if line.starts_with([' ', '\t']) && !line.trim().is_empty() {
+            if let Some((_, value)) = &mut current {
+                if !value.is_empty() {
+                    value.push('\n');
+                }
+                value.push_str(line.trim());
+            }
+            continue;
+        }
+
+        if let Some((id, value)) = current.take() {
+            messages.insert(id, value);
+        }
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((id, value)) = line.split_once('=') {
+            current = Some((id.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    if let Some((id, value)) = current {
+        messages.insert(id, value);
+    }
+    messages
+}
+
+/// The message `id` in the current language, falling back to English
+pub fn tr(id: &str) -> String {
+    let message = |language: Language| MESSAGES.get(&language).and_then(|m| m.get(id));
+    message(language())
+        .or_else(|| message(Language::English))
+        .cloned()
+        // A missing message shows its id rather than nothing, so it's easy to spot
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// The message `id` with its `{ $name }` placeables filled from `args`
+pub fn tr_args(id: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let mut message = tr(id);
+    for (name, value) in args {
+        message = message.replace(&format!("{{ ${} }}", name), &value.to_string());
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_fluent_messages() {
+        let messages = parse(
+            "# Welcome screen\nwelcome-title = Welcome to Beam\nwelcome-hint =\n    Collections group\n    your requests.\n\nrun-count = { $count } runs\n",
+        );
+
+        assert_eq!(messages["welcome-title"], "Welcome to Beam");
+        assert_eq!(
+            messages["welcome-hint"],
+            "Collections group\nyour requests."
+        );
+        assert_eq!(messages["run-count"], "{ $count } runs");
+        assert_eq!(messages.len(), 3);
+    }
+
+    #[test]
+    fn translations_cover_every_english_message() {
+        let english = &MESSAGES[&Language::English];
+        for language in Language::ALL {
+            let messages = &MESSAGES[&language];
+            let mut missing: Vec<&String> = english
+                .keys()
+                .filter(|id| !messages.contains_key(*id))
+                .collect();
+            missing.sort();
+            assert!(missing.is_empty(), "{:?} misses {:?}", language, missing);
+
+            for (id, message) in messages {
+                assert!(
+                    english.contains_key(id),
+                    "{:?} has unknown {}",
+                    language,
+                    id
+                );
+                // Placeables must match or arguments would go missing
+                let placeables = |text: &str| {
+                    let mut names: Vec<String> = text
+                        .split("{ $")
+                        .skip(1)
+                        .filter_map(|rest| rest.split_once(" }").map(|(name, _)| name.to_string()))
+                        .collect();
+                    names.sort();
+                    names
+                };
+                assert_eq!(placeables(message), placeables(&english[id]), "{}", id);
+            }
+        }
+    }
+}
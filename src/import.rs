@@ -0,0 +1,686 @@
+use crate::contract::contract_for_operation;
+use crate::share;
+use crate::storage::PersistentEnvironments;
+use crate::types::{
+    ApiKeyLocation, AuthType, BodyFormat, CollectionSort, Environment, FieldDoc, HttpMethod,
+    RequestCollection, RequestConfig, RunConfiguration, ValueType, new_id,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Something that can be imported from a shared export
+#[derive(Debug, Clone)]
+pub enum Imported {
+    Collection(RequestCollection),
+    Environments(Vec<Environment>),
+    Request(Box<RequestConfig>),
+}
+
+impl Imported {
+    /// Short description for confirmation dialogs
+    pub fn summary(&self) -> String {
+        match self {
+            Imported::Collection(collection) => format!(
+                "Collection \"{}\" with {} requests",
+                collection.name,
+                collection.requests.len()
+            ),
+            Imported::Environments(environments) => {
+                let names: Vec<&str> = environments.iter().map(|env| env.name.as_str()).collect();
+                format!("Environments: {}", names.join(", "))
+            }
+            Imported::Request(request) => {
+                format!("Request \"{}\" ({} {})", request.name, request.method, request.url)
+            }
+        }
+    }
+}
+
+/// A collection exported from Beam as a single TOML file
+#[derive(Deserialize)]
+struct BeamCollectionExport {
+    name: String,
+    requests: Vec<RequestConfig>,
+}
+
+/// Downloads an export from `url` and parses it with [`parse`]
+pub async fn fetch(url: String) -> Result<Imported, String> {
+    let response = reqwest::get(url.trim())
+        .await
+        .map_err(|e| format!("Failed to download: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to download: HTTP {}", response.status()));
+    }
+
+    let content = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    parse(&content)
+}
+
+/// Detects the export format and converts it. Supported are Postman collections (v2.x)
+/// and environments, OpenAPI 3 / Swagger 2 documents in JSON or YAML, and Beam collection,
+/// environment and single request TOML exports.
+pub fn parse(content: &str) -> Result<Imported, String> {
+    if let Ok(json) = serde_json::from_str::<Value>(content) {
+        if json.get("openapi").is_some() || json.get("swagger").is_some() {
+            return Ok(Imported::Collection(openapi_collection(&json)));
+        }
+        if json.get("item").is_some() {
+            return Ok(Imported::Collection(postman_collection(&json)));
+        }
+        if json.get("values").is_some() {
+            return Ok(Imported::Environments(vec![postman_environment(&json)]));
+        }
+        return Err("Unrecognized JSON export".to_string());
+    }
+
+    if let Ok(environments) = toml::from_str::<PersistentEnvironments>(content) {
+        return Ok(Imported::Environments(environments.environments));
+    }
+
+    if let Ok(request) = share::parse(content) {
+        return Ok(Imported::Request(Box::new(request)));
+    }
+
+    match toml::from_str::<BeamCollectionExport>(content) {
+        Ok(export) => {
+            let requests = export
+                .requests
+                .into_iter()
+                .map(|mut request| {
+                    request.id = new_id();
+                    request.path = Default::default();
+                    request
+                })
+                .collect();
+            Ok(Imported::Collection(new_collection(export.name, requests)))
+        }
+        Err(_)
+            if content.trim_start().starts_with("openapi:")
+                || content.trim_start().starts_with("swagger:") =>
+        {
+            openapi_yaml(content)
+        }
+        Err(_) => Err("Unrecognized export format".to_string()),
+    }
+}
+
+/// Imports a file dropped onto the window, picking the importer from its extension:
+/// `.har` archives, `.env` files, single request `.toml` and `.beamreq` files, and anything
+/// [`parse`] understands.
+pub fn parse_file(path: &Path) -> Result<Imported, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "har" => har_collection(&content, stem),
+        "env" => Ok(Imported::Environments(vec![dotenv_environment(&content, stem)])),
+        share::EXTENSION => {
+            share::parse(&content).map(|request| Imported::Request(Box::new(request)))
+        }
+        "yaml" | "yml" => openapi_yaml(&content),
+        "toml" => match toml::from_str::<RequestConfig>(&content) {
+            Ok(mut request) => {
+                request.id = new_id();
+                request.path = Default::default();
+                Ok(Imported::Request(Box::new(request)))
+            }
+            Err(_) => parse(&content),
+        },
+        _ => parse(&content),
+    }
+}
+
+/// An OpenAPI 3 / Swagger 2 document in YAML, converted like its JSON version
+fn openapi_yaml(content: &str) -> Result<Imported, String> {
+    let yaml: serde_yaml::Value =
+        serde_yaml::from_str(content).map_err(|e| format!("Invalid YAML: {}", e))?;
+    // Going through the YAML value turns keys like `200:` into the strings JSON expects
+    let json =
+        serde_json::to_value(yaml).map_err(|e| format!("Unsupported YAML document: {}", e))?;
+    if json.get("openapi").is_none() && json.get("swagger").is_none() {
+        return Err("Only OpenAPI documents can be imported from YAML".to_string());
+    }
+    Ok(Imported::Collection(openapi_collection(&json)))
+}
+
+/// One request per entry of an HTTP Archive
+fn har_collection(content: &str, name: String) -> Result<Imported, String> {
+    let json: Value =
+        serde_json::from_str(content).map_err(|e| format!("Invalid HAR file: {}", e))?;
+    let entries = json
+        .pointer("/log/entries")
+        .and_then(Value::as_array)
+        .ok_or("Invalid HAR file: no log entries")?;
+
+    let requests = entries
+        .iter()
+        .filter_map(|entry| entry.get("request"))
+        .map(|request| {
+            let url = str_field(request, "url").to_string();
+            let method = parse_method(str_field(request, "method"));
+            let name = reqwest::Url::parse(&url)
+                .map(|parsed| format!("{} {}", method, parsed.path()))
+                .unwrap_or_else(|_| format!("{} {}", method, url));
+
+            let mut config = new_request(name, method, url);
+            config.headers = request
+                .get("headers")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(|header| {
+                    (
+                        str_field(header, "name").to_string(),
+                        str_field(header, "value").to_string(),
+                    )
+                })
+                // Pseudo-headers from HTTP/2 captures can't be sent as regular headers
+                .filter(|(name, _)| !name.starts_with(':'))
+                .collect();
+
+            if let Some(post_data) = request.get("postData") {
+                config.body = str_field(post_data, "text").to_string();
+                config.content_type = str_field(post_data, "mimeType").to_string();
+                config.body_format = if config.content_type.contains("json") {
+                    BodyFormat::Json
+                } else if config.content_type.contains("xml") {
+                    BodyFormat::Xml
+                } else {
+                    BodyFormat::Text
+                };
+            }
+
+            config
+        })
+        .collect();
+
+    Ok(Imported::Collection(new_collection(name, requests)))
+}
+
+/// `KEY=VALUE` lines; `#` comments, `export` prefixes and surrounding quotes are ignored
+fn dotenv_environment(content: &str, name: String) -> Environment {
+    let mut environment = Environment::new(name);
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(value);
+            environment.add_variable(key.trim().to_string(), value.to_string());
+        }
+    }
+
+    environment
+}
+
+fn new_collection(name: String, mut requests: Vec<RequestConfig>) -> RequestCollection {
+    // Requests are stored by name, so duplicates would overwrite each other
+    let mut seen = std::collections::HashSet::new();
+    for request in &mut requests {
+        let mut name = request.name.clone();
+        let mut counter = 2;
+        while !seen.insert(name.clone()) {
+            name = format!("{} ({})", request.name, counter);
+            counter += 1;
+        }
+        request.name = name;
+    }
+
+    RequestCollection {
+        id: new_id(),
+        name,
+        folder_name: String::new(),
+        requests,
+        expanded: true,
+        color: None,
+        icon: None,
+        sort: CollectionSort::default(),
+        run_configuration: RunConfiguration::default(),
+    }
+}
+
+fn new_request(name: String, method: HttpMethod, url: String) -> RequestConfig {
+    RequestConfig {
+        name,
+        method,
+        url,
+        ..Default::default()
+    }
+}
+
+fn parse_method(method: &str) -> HttpMethod {
+    HttpMethod::parse(method).unwrap_or(HttpMethod::GET)
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+/// Postman key/value lists (`[{ "key": .., "value": .., "disabled": .. }]`)
+fn key_values(value: Option<&Value>) -> Vec<(String, String)> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|entry| {
+            !entry
+                .get("disabled")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+        })
+        .map(|entry| {
+            let value = match entry.get("value") {
+                Some(Value::String(value)) => value.clone(),
+                Some(Value::Null) | None => String::new(),
+                Some(other) => other.to_string(),
+            };
+            (str_field(entry, "key").to_string(), value)
+        })
+        .collect()
+}
+
+fn postman_collection(json: &Value) -> RequestCollection {
+    let name = json
+        .get("info")
+        .map(|info| str_field(info, "name"))
+        .filter(|name| !name.is_empty())
+        .unwrap_or("Imported Collection")
+        .to_string();
+
+    let mut requests = Vec::new();
+    let tests = postman_tests(json, &[]);
+    collect_postman_items(json.get("item"), "", &tests, &mut requests);
+
+    new_collection(name, requests)
+}
+
+/// Flattens Postman folders; requests inside folders are named `Folder / Request`. The test
+/// scripts of the collection and folders run before the request's own, as in Postman.
+fn collect_postman_items(
+    items: Option<&Value>,
+    prefix: &str,
+    tests: &[String],
+    requests: &mut Vec<RequestConfig>,
+) {
+    for item in items.and_then(Value::as_array).into_iter().flatten() {
+        let name = format!("{}{}", prefix, str_field(item, "name"));
+        let tests = postman_tests(item, tests);
+
+        if item.get("item").is_some() {
+            collect_postman_items(item.get("item"), &format!("{} / ", name), &tests, requests);
+        } else if let Some(request) = item.get("request") {
+            let mut config = postman_request(name, request);
+            if !tests.is_empty() {
+                config.post_request_script = Some(tests.join("\n\n"));
+            }
+            requests.push(config);
+        }
+    }
+}
+
+/// `inherited` followed by the test script of a Postman item, its `exec` being a list of
+/// lines or a single string. Pre-request scripts have no counterpart in Beam, so they're
+/// left out.
+fn postman_tests(item: &Value, inherited: &[String]) -> Vec<String> {
+    let mut tests = inherited.to_vec();
+    let events = item.get("event").and_then(Value::as_array).into_iter().flatten();
+    for event in events.filter(|event| str_field(event, "listen") == "test") {
+        let script = match event.pointer("/script/exec") {
+            Some(Value::Array(lines)) => lines
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Some(Value::String(script)) => script.clone(),
+            _ => continue,
+        };
+        if !script.trim().is_empty() {
+            tests.push(script);
+        }
+    }
+    tests
+}
+
+fn postman_request(name: String, request: &Value) -> RequestConfig {
+    // A request may be just its URL
+    if let Some(url) = request.as_str() {
+        return new_request(name, HttpMethod::GET, url.to_string());
+    }
+
+    let url = match request.get("url") {
+        Some(Value::String(url)) => url.clone(),
+        Some(url) => str_field(url, "raw").to_string(),
+        None => String::new(),
+    };
+
+    let mut config = new_request(name, parse_method(str_field(request, "method")), url);
+    config.headers = key_values(request.get("header"));
+
+    if let Some(body) = request.get("body") {
+        match str_field(body, "mode") {
+            "raw" => {
+                config.body = str_field(body, "raw").to_string();
+                let language = body
+                    .pointer("/options/raw/language")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                config.body_format = match language {
+                    "json" => BodyFormat::Json,
+                    "xml" => BodyFormat::Xml,
+                    _ => BodyFormat::Text,
+                };
+            }
+            "graphql" => {
+                if let Some(graphql) = body.get("graphql") {
+                    config.body = str_field(graphql, "query").to_string();
+                }
+                config.body_format = BodyFormat::GraphQL;
+            }
+            "urlencoded" => {
+                config.body = key_values(body.get("urlencoded"))
+                    .iter()
+                    .map(|(key, value)| {
+                        format!(
+                            "{}={}",
+                            urlencoding::encode(key),
+                            urlencoding::encode(value)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("&");
+                config.content_type = "application/x-www-form-urlencoded".to_string();
+                config.body_format = BodyFormat::Text;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(auth) = request.get("auth") {
+        apply_postman_auth(&mut config, auth);
+    }
+
+    config
+}
+
+fn apply_postman_auth(config: &mut RequestConfig, auth: &Value) {
+    let kind = str_field(auth, "type");
+    let params: Vec<(String, String)> = key_values(auth.get(kind));
+    let param = |key: &str| {
+        params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+            .unwrap_or_default()
+    };
+
+    match kind {
+        "bearer" => {
+            config.auth_type = AuthType::Bearer;
+            config.bearer_token = param("token");
+        }
+        "basic" => {
+            config.auth_type = AuthType::Basic;
+            config.basic_username = param("username");
+            config.basic_password = param("password");
+        }
+        "apikey" => {
+            config.auth_type = AuthType::ApiKey;
+            config.api_key_header = param("key");
+            config.api_key = param("value");
+            config.api_key_location = if param("in") == "query" {
+                ApiKeyLocation::Query
+            } else {
+                ApiKeyLocation::Header
+            };
+        }
+        _ => {}
+    }
+}
+
+fn postman_environment(json: &Value) -> Environment {
+    let name = match str_field(json, "name") {
+        "" => "Imported Environment".to_string(),
+        name => name.to_string(),
+    };
+
+    let mut environment = Environment::new(name);
+    for entry in json
+        .get("values")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let key = str_field(entry, "key");
+        if key.is_empty() {
+            continue;
+        }
+        environment.add_variable(key.to_string(), str_field(entry, "value").to_string());
+        environment.set_variable_enabled(
+            key,
+            entry
+                .get("enabled")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+        );
+    }
+
+    environment
+}
+
+const OPENAPI_METHODS: &[&str] = &["get", "post", "put", "delete", "patch", "head", "options"];
+
+/// One request per operation. Path parameters become `{{name}}` variables and the
+/// server URL is taken from `servers` (OpenAPI 3) or `host`/`basePath` (Swagger 2).
+fn openapi_collection(json: &Value) -> RequestCollection {
+    let name = json
+        .get("info")
+        .map(|info| str_field(info, "title"))
+        .filter(|title| !title.is_empty())
+        .unwrap_or("Imported API")
+        .to_string();
+
+    let base_url = match json.pointer("/servers/0/url").and_then(Value::as_str) {
+        Some(url) => url.to_string(),
+        None if json.get("host").is_some() => {
+            let scheme = json
+                .pointer("/schemes/0")
+                .and_then(Value::as_str)
+                .unwrap_or("https");
+            format!(
+                "{}://{}{}",
+                scheme,
+                str_field(json, "host"),
+                str_field(json, "basePath")
+            )
+        }
+        None => "{{baseUrl}}".to_string(),
+    };
+    let base_url = base_url.trim_end_matches('/');
+
+    let mut requests = Vec::new();
+    for (path, operations) in json
+        .get("paths")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        for (method, operation) in operations.as_object().into_iter().flatten() {
+            if !OPENAPI_METHODS.contains(&method.as_str()) {
+                continue;
+            }
+
+            let name = [
+                str_field(operation, "summary"),
+                str_field(operation, "operationId"),
+            ]
+            .into_iter()
+            .find(|name| !name.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+            let url = format!("{}{}", base_url, path.replace('{', "{{").replace('}', "}}"));
+
+            let mut request = new_request(name, parse_method(method), url);
+            request.contract = contract_for_operation(json, path, method, operation);
+            apply_openapi_parameters(json, operations, operation, &mut request);
+            if let Some(example) = operation
+                .pointer("/requestBody/content/application~1json/example")
+                .and_then(|example| serde_json::to_string_pretty(example).ok())
+            {
+                request.body = example;
+                request.body_format = BodyFormat::Json;
+            }
+
+            requests.push(request);
+        }
+    }
+
+    new_collection(name, requests)
+}
+
+/// Adds the query and header parameters of an operation, documented with their description
+/// and type. Path-level parameters apply to every operation of the path.
+fn apply_openapi_parameters(
+    document: &Value,
+    path_item: &Value,
+    operation: &Value,
+    request: &mut RequestConfig,
+) {
+    let parameters = [path_item, operation]
+        .into_iter()
+        .filter_map(|item| item.get("parameters").and_then(Value::as_array))
+        .flatten()
+        .filter_map(|parameter| match parameter.get("$ref").and_then(Value::as_str) {
+            Some(reference) => reference
+                .strip_prefix('#')
+                .and_then(|pointer| document.pointer(pointer)),
+            None => Some(parameter),
+        });
+
+    for parameter in parameters {
+        let name = str_field(parameter, "name").to_string();
+        if name.is_empty() {
+            continue;
+        }
+
+        // OpenAPI 3 nests the type in a schema, Swagger 2 has it on the parameter
+        let schema = parameter.get("schema").unwrap_or(parameter);
+        let value_type = match (str_field(schema, "type"), str_field(schema, "format")) {
+            ("integer" | "number", _) => ValueType::Number,
+            ("boolean", _) => ValueType::Bool,
+            ("string", "date" | "date-time") => ValueType::Date,
+            _ => ValueType::String,
+        };
+        let doc = FieldDoc {
+            description: str_field(parameter, "description").to_string(),
+            value_type,
+        };
+        let value = match parameter.get("example").or_else(|| schema.get("example")) {
+            Some(Value::String(example)) => example.clone(),
+            Some(example) => example.to_string(),
+            None => String::new(),
+        };
+
+        let (fields, docs) = match str_field(parameter, "in") {
+            "query" => (&mut request.params, &mut request.param_docs),
+            "header" => (&mut request.headers, &mut request.header_docs),
+            _ => continue,
+        };
+        if fields.iter().any(|(key, _)| *key == name) {
+            continue;
+        }
+        if !doc.is_empty() {
+            docs.insert(name.clone(), doc);
+        }
+        fields.push((name, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_postman_tests_with_those_of_their_folders() {
+        let content = r#"{
+            "info": { "name": "Users" },
+            "event": [{ "listen": "test", "script": { "exec": ["pm.test('up', function () {", "});"] } }],
+            "item": [{
+                "name": "Admin",
+                "event": [
+                    { "listen": "prerequest", "script": { "exec": "pm.variables.set('a', 1);" } },
+                    { "listen": "test", "script": { "exec": "pm.response.to.be.ok;" } }
+                ],
+                "item": [{ "name": "List", "request": { "method": "GET", "url": "{{host}}/users" } }]
+            }]
+        }"#;
+        let Ok(Imported::Collection(collection)) = parse(content) else {
+            panic!("expected a collection");
+        };
+        assert_eq!(collection.requests[0].name, "Admin / List");
+        assert_eq!(
+            collection.requests[0].post_request_script.as_deref(),
+            Some("pm.test('up', function () {\n});\n\npm.response.to.be.ok;")
+        );
+    }
+    #[test]
+    fn imports_openapi_yaml_like_its_json_version() {
+        let content = r#"openapi: 3.0.0
+info:
+  title: Pets
+servers:
+  - url: https://pets.example.com/v1/
+paths:
+  /pets/{id}:
+    get:
+      summary: Show a pet
+      parameters:
+        - name: verbose
+          in: query
+          schema:
+            type: boolean
+      responses:
+        200:
+          description: The pet
+"#;
+        let Ok(Imported::Collection(collection)) = parse(content) else {
+            panic!("expected a collection");
+        };
+        assert_eq!(collection.name, "Pets");
+        assert_eq!(collection.requests.len(), 1);
+        assert_eq!(collection.requests[0].name, "Show a pet");
+        assert_eq!(
+            collection.requests[0].url,
+            "https://pets.example.com/v1/pets/{{id}}"
+        );
+
+        let path = std::env::temp_dir().join(format!("beam-import-{}.yaml", new_id()));
+        fs::write(&path, content).unwrap();
+        let imported = parse_file(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(imported, Ok(Imported::Collection(_))));
+        assert!(openapi_yaml("name: not an api").is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::deep_link;
+use iced::Subscription;
+use iced::futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use iced::futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Starts every connection and its reply, so a stale port file pointing at another program
+/// isn't mistaken for a running Beam
+const HANDSHAKE: &str = "beam-instance/2";
+
+/// How long a launch waits on the running instance before starting on its own
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+/// What a launch asks of the app: the running instance is handed these by later launches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum LaunchRequest {
+    /// Bring the window to the front
+    Activate,
+    /// Follow a beam:// link
+    OpenLink { link: String },
+    /// Import a file, as if it was dropped onto the window
+    OpenFile { path: PathBuf },
+}
+
+/// Whether this process runs the app or handed its launch to the one already running
+#[derive(Debug)]
+pub enum Instance {
+    Primary(Launches),
+    Forwarded,
+}
+
+/// Requests handed over by later launches, delivered to the running app by `subscription`
+#[derive(Debug, Clone)]
+pub struct Launches(Arc<Mutex<Option<UnboundedReceiver<LaunchRequest>>>>);
+
+impl Launches {
+    /// The requests as later launches hand them over. The receiver is taken by the first
+    /// subscription, which iced keeps running for as long as the app asks for it.
+    pub fn subscription(&self) -> Subscription<LaunchRequest> {
+        Subscription::run_with(self.clone(), |launches| {
+            let receiver = launches
+                .0
+                .lock()
+                .ok()
+                .and_then(|mut receiver| receiver.take());
+            stream::iter(receiver).flatten()
+        })
+    }
+}
+
+// There's one receiver per process, so every `Launches` is the same subscription
+impl Hash for Launches {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        "launches".hash(state);
+    }
+}
+
+/// The requests of a launch from its command line arguments: beam:// links and files to
+/// import. Relative paths are made absolute, as the running instance has another working
+/// directory.
+pub fn from_args(args: impl IntoIterator<Item = String>) -> Vec<LaunchRequest> {
+    let args: Vec<String> = args.into_iter().collect();
+    let mut requests = vec![LaunchRequest::Activate];
+    if let Some(link) = deep_link::from_args(args.clone()) {
+        requests.push(LaunchRequest::OpenLink { link });
+    }
+    requests.extend(
+        args.iter()
+            .map(PathBuf::from)
+            .filter(|path| path.is_file())
+            .map(|path| LaunchRequest::OpenFile {
+                path: std::path::absolute(&path).unwrap_or(path),
+            }),
+    );
+    requests
+}
+
+/// Hands `requests` to the running instance if there is one. Otherwise this process becomes
+/// the running instance and listens for later launches on a loopback port. The port and a
+/// token made for this run are written to `instance.port` in `base_path`, readable by the
+/// user only, and a launch has to send the token to be heard.
+pub fn claim(base_path: &Path, requests: &[LaunchRequest]) -> Instance {
+    let port_file = base_path.join("instance.port");
+    match forward(&port_file, requests) {
+        Ok(()) => return Instance::Forwarded,
+        Err(e) => log::debug!("No running instance to hand the launch to: {}", e),
+    }
+
+    let (sender, receiver) = mpsc::unbounded();
+    if let Err(e) = listen(&port_file, sender) {
+        // Still usable, later launches just open windows of their own
+        log::warn!("Failed to listen for later launches: {}", e);
+    }
+    Instance::Primary(Launches(Arc::new(Mutex::new(Some(receiver)))))
+}
+
+fn forward(port_file: &Path, requests: &[LaunchRequest]) -> std::io::Result<()> {
+    let contents = fs::read_to_string(port_file)?;
+    let mut lines = contents.lines();
+    let port: u16 = lines
+        .next()
+        .and_then(|port| port.trim().parse().ok())
+        .ok_or_else(|| std::io::Error::other("invalid port file"))?;
+    let token = lines
+        .next()
+        .ok_or_else(|| std::io::Error::other("invalid port file"))?
+        .trim();
+    let mut stream =
+        TcpStream::connect_timeout(&SocketAddr::from((Ipv4Addr::LOCALHOST, port)), TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+
+    let mut message = format!("{} {}\n", HANDSHAKE, token);
+    for request in requests {
+        message.push_str(&serde_json::to_string(request).map_err(std::io::Error::other)?);
+        message.push('\n');
+    }
+    stream.write_all(message.as_bytes())?;
+    stream.shutdown(std::net::Shutdown::Write)?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    if reply.trim() == HANDSHAKE {
+        Ok(())
+    } else {
+        Err(std::io::Error::other("not a Beam instance"))
+    }
+}
+
+fn listen(port_file: &Path, sender: UnboundedSender<LaunchRequest>) -> std::io::Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let token: String = rand::random::<[u8; 16]>()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    write_private(
+        port_file,
+        &format!("{}\n{}\n", listener.local_addr()?.port(), token),
+    )?;
+
+    let greeting = format!("{} {}", HANDSHAKE, token);
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if let Err(e) = receive(stream, &greeting, &sender) {
+                log::warn!("Failed to read a launch handed over: {}", e);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Replaces the file with one only the user can read, as it holds the token
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // A file left by an earlier run keeps its permissions when written over
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
+fn receive(
+    stream: TcpStream,
+    greeting: &str,
+    sender: &UnboundedSender<LaunchRequest>,
+) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut lines = BufReader::new(stream.try_clone()?).lines();
+    if lines.next().transpose()?.as_deref() != Some(greeting) {
+        return Ok(());
+    }
+
+    for request in lines
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+    {
+        // The app is closing when nothing receives them anymore
+        let _ = sender.unbounded_send(request);
+    }
+    (&stream).write_all(format!("{}\n", HANDSHAKE).as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hands_a_launch_to_the_running_instance() {
+        let dir = std::env::temp_dir().join(format!("beam-instance-{}", std::process::id()));
+        let requests = vec![
+            LaunchRequest::Activate,
+            LaunchRequest::OpenLink {
+                link: "beam://open?collection=Users".to_string(),
+            },
+        ];
+
+        let Instance::Primary(launches) = claim(&dir, &requests) else {
+            panic!("no instance was running");
+        };
+        assert!(matches!(claim(&dir, &requests), Instance::Forwarded));
+        let mut receiver = launches.0.lock().unwrap().take().unwrap();
+        for request in &requests {
+            assert_eq!(receiver.try_recv().ok().as_ref(), Some(request));
+        }
+
+        // Without the token the launch isn't heard
+        let port_file = dir.join("instance.port");
+        let contents = fs::read_to_string(&port_file).unwrap();
+        let port = contents.lines().next().unwrap();
+        fs::write(&port_file, format!("{}\nguessed\n", port)).unwrap();
+        assert!(forward(&port_file, &requests).is_err());
+        assert!(receiver.try_recv().is_err());
+
+        // A port file left by a crashed instance points nowhere
+        fs::write(&port_file, "1\ntoken\n").unwrap();
+        assert!(forward(&port_file, &requests).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,148 @@
+/// Rewrites a JSON5 body as strict JSON: comments are dropped, trailing commas removed,
+/// single-quoted strings and unquoted keys double-quoted. Everything else is kept as
+/// written, so strict JSON and `{{variable}}` references come out unchanged. Other JSON5
+/// extensions, such as hexadecimal numbers, are left for the server to refuse.
+pub fn to_json(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut output = String::with_capacity(input.len());
+    // Byte offset in `output` of the last comma, while only blanks and comments follow it
+    let mut last_comma: Option<usize> = None;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let next = chars.get(index + 1).copied();
+        match c {
+            '"' | '\'' => {
+                index = push_string(&chars, index, &mut output);
+                last_comma = None;
+            }
+            '/' if next == Some('/') => {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+            }
+            '/' if next == Some('*') => {
+                index += 2;
+                while index < chars.len()
+                    && !(chars[index] == '*' && chars.get(index + 1) == Some(&'/'))
+                {
+                    index += 1;
+                }
+                index += 2;
+            }
+            ',' => {
+                last_comma = Some(output.len());
+                output.push(c);
+                index += 1;
+            }
+            '}' | ']' => {
+                if let Some(comma) = last_comma.take() {
+                    output.remove(comma);
+                }
+                output.push(c);
+                index += 1;
+            }
+            c if c.is_whitespace() => {
+                output.push(c);
+                index += 1;
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let start = index;
+                while index < chars.len()
+                    && (chars[index].is_alphanumeric()
+                        || chars[index] == '_'
+                        || chars[index] == '$')
+                {
+                    index += 1;
+                }
+                let name: String = chars[start..index].iter().collect();
+                let is_key = chars[index..]
+                    .iter()
+                    .find(|c| !c.is_whitespace())
+                    .is_some_and(|c| *c == ':');
+                if is_key {
+                    output.push('"');
+                    output.push_str(&name);
+                    output.push('"');
+                } else {
+                    output.push_str(&name);
+                }
+                last_comma = None;
+            }
+            _ => {
+                output.push(c);
+                index += 1;
+                last_comma = None;
+            }
+        }
+    }
+
+    output
+}
+
+/// Copies the string starting at `start` as a double-quoted one, returning the index after it
+fn push_string(chars: &[char], start: usize, output: &mut String) -> usize {
+    let quote = chars[start];
+    output.push('"');
+    let mut index = start + 1;
+    while index < chars.len() {
+        let c = chars[index];
+        match c {
+            '\\' => match chars.get(index + 1) {
+                Some('\'') => {
+                    output.push('\'');
+                    index += 2;
+                }
+                // A line continuation
+                Some('\n') => index += 2,
+                Some(&escaped) => {
+                    output.push('\\');
+                    output.push(escaped);
+                    index += 2;
+                }
+                None => index += 1,
+            },
+            c if c == quote => {
+                output.push('"');
+                return index + 1;
+            }
+            '"' => {
+                output.push_str("\\\"");
+                index += 1;
+            }
+            c => {
+                output.push(c);
+                index += 1;
+            }
+        }
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_json5_as_json() {
+        let body = r#"{
+  // Who to create
+  name: 'O\'Brien "Bob"',
+  url: "https://example.com/a//b", /* not a comment in a string */
+  id: {{user_id}},
+  tags: ['a', 'b',],
+}"#;
+        let json = to_json(body);
+        let value: serde_json::Value =
+            serde_json::from_str(&json.replace("{{user_id}}", "7")).unwrap();
+        assert_eq!(value["name"], "O'Brien \"Bob\"");
+        assert_eq!(value["url"], "https://example.com/a//b");
+        assert_eq!(value["id"], 7);
+        assert_eq!(value["tags"], serde_json::json!(["a", "b"]));
+        assert!(json.contains("{{user_id}}"));
+
+        let strict = "{\n  \"a\": [1, true, null],\n  \"b\": \"x, }\"\n}";
+        assert_eq!(to_json(strict), strict);
+    }
+}
@@ -0,0 +1,258 @@
+use serde_json::Value;
+
+/// A step of a JSONPath
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// `.name` or `['name']`
+    Key(String),
+    /// `[0]`
+    Index(usize),
+    /// `.*` or `[*]`, every member or item
+    Wildcard,
+    /// `..name`, the member at any depth
+    Descendant(String),
+}
+
+/// Parses the JSONPath subset Beam supports, such as `$.items[*].id`, `$..updatedAt` or
+/// `data.token`, the leading `$` being optional
+pub fn parse(path: &str) -> Result<Vec<Segment>, String> {
+    let invalid = |reason: &str| format!("Invalid path '{}': {}", path, reason);
+
+    let mut rest = path.trim();
+    rest = rest.strip_prefix('$').unwrap_or(rest);
+    let mut segments = Vec::new();
+    let mut first = true;
+
+    while !rest.is_empty() {
+        let name_end = |s: &str| s.find(['.', '[']).unwrap_or(s.len());
+
+        if let Some(after) = rest.strip_prefix("..") {
+            let end = name_end(after);
+            let name = &after[..end];
+            if name.is_empty() || name == "*" {
+                return Err(invalid("'..' needs a member name"));
+            }
+            segments.push(Segment::Descendant(name.to_string()));
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(|| invalid("missing ']'"))?;
+            let inside = after[..end].trim();
+            let segment = if inside == "*" {
+                Segment::Wildcard
+            } else if let Ok(index) = inside.parse::<usize>() {
+                Segment::Index(index)
+            } else if inside.len() >= 2
+                && ((inside.starts_with('\'') && inside.ends_with('\''))
+                    || (inside.starts_with('"') && inside.ends_with('"')))
+            {
+                Segment::Key(inside[1..inside.len() - 1].to_string())
+            } else {
+                return Err(invalid(&format!(
+                    "'[{}]' is not an index, * or a quoted name",
+                    inside
+                )));
+            };
+            segments.push(segment);
+            rest = &after[end + 1..];
+        } else {
+            let after = match rest.strip_prefix('.') {
+                Some(after) => after,
+                None if first => rest,
+                None => return Err(invalid("expected '.' or '['")),
+            };
+            let end = name_end(after);
+            let name = &after[..end];
+            if name.is_empty() {
+                return Err(invalid("empty member name"));
+            }
+            segments.push(if name == "*" {
+                Segment::Wildcard
+            } else {
+                Segment::Key(name.to_string())
+            });
+            rest = &after[end..];
+        }
+        first = false;
+    }
+
+    Ok(segments)
+}
+
+/// Values `path` points to in `value`, in document order
+pub fn select<'a>(value: &'a Value, path: &str) -> Result<Vec<&'a Value>, String> {
+    let mut current = vec![value];
+    for segment in parse(path)? {
+        let mut next = Vec::new();
+        for value in current {
+            match (&segment, value) {
+                (Segment::Key(key), Value::Object(map)) => next.extend(map.get(key)),
+                (Segment::Index(index), Value::Array(items)) => next.extend(items.get(*index)),
+                (Segment::Wildcard, Value::Object(map)) => next.extend(map.values()),
+                (Segment::Wildcard, Value::Array(items)) => next.extend(items.iter()),
+                (Segment::Descendant(key), value) => collect_descendants(value, key, &mut next),
+                _ => {}
+            }
+        }
+        current = next;
+    }
+    Ok(current)
+}
+
+fn collect_descendants<'a>(value: &'a Value, key: &str, out: &mut Vec<&'a Value>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(found) = map.get(key) {
+                out.push(found);
+            }
+            for child in map.values() {
+                collect_descendants(child, key, out);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_descendants(item, key, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Removes what `path` points to from `value`. Removed array items shift the ones after.
+pub fn remove(value: &mut Value, path: &str) -> Result<(), String> {
+    let segments = parse(path)?;
+    if segments.is_empty() {
+        return Err(format!(
+            "Invalid path '{}': the whole document can't be removed",
+            path
+        ));
+    }
+    remove_at(value, &segments);
+    Ok(())
+}
+
+fn remove_at(value: &mut Value, segments: &[Segment]) {
+    let Some((segment, rest)) = segments.split_first() else {
+        return;
+    };
+
+    if rest.is_empty() {
+        match (segment, value) {
+            (Segment::Key(key), Value::Object(map)) => {
+                map.remove(key);
+            }
+            (Segment::Index(index), Value::Array(items)) if *index < items.len() => {
+                items.remove(*index);
+            }
+            (Segment::Wildcard, Value::Object(map)) => map.clear(),
+            (Segment::Wildcard, Value::Array(items)) => items.clear(),
+            (Segment::Descendant(key), value) => remove_descendants(value, key),
+            _ => {}
+        }
+        return;
+    }
+
+    match (segment, value) {
+        (Segment::Key(key), Value::Object(map)) => {
+            if let Some(child) = map.get_mut(key) {
+                remove_at(child, rest);
+            }
+        }
+        (Segment::Index(index), Value::Array(items)) => {
+            if let Some(child) = items.get_mut(*index) {
+                remove_at(child, rest);
+            }
+        }
+        (Segment::Wildcard, Value::Object(map)) => {
+            map.values_mut().for_each(|child| remove_at(child, rest))
+        }
+        (Segment::Wildcard, Value::Array(items)) => {
+            items.iter_mut().for_each(|child| remove_at(child, rest))
+        }
+        (Segment::Descendant(key), value) => descend_mut(value, key, rest),
+        _ => {}
+    }
+}
+
+fn remove_descendants(value: &mut Value, key: &str) {
+    match value {
+        Value::Object(map) => {
+            map.remove(key);
+            map.values_mut()
+                .for_each(|child| remove_descendants(child, key));
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| remove_descendants(item, key)),
+        _ => {}
+    }
+}
+
+/// Applies `rest` to every member named `key`, at any depth
+fn descend_mut(value: &mut Value, key: &str, rest: &[Segment]) {
+    match value {
+        Value::Object(map) => {
+            for (name, child) in map.iter_mut() {
+                if name == key {
+                    remove_at(child, rest);
+                }
+                descend_mut(child, key, rest);
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| descend_mut(item, key, rest)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_dots_brackets_and_descendants() {
+        assert_eq!(
+            parse("$.items[*].tags[0]").unwrap(),
+            vec![
+                Segment::Key("items".to_string()),
+                Segment::Wildcard,
+                Segment::Key("tags".to_string()),
+                Segment::Index(0),
+            ]
+        );
+        assert_eq!(
+            parse("data['a.b']..id").unwrap(),
+            vec![
+                Segment::Key("data".to_string()),
+                Segment::Key("a.b".to_string()),
+                Segment::Descendant("id".to_string()),
+            ]
+        );
+        assert_eq!(parse("$").unwrap(), vec![]);
+        assert!(parse("$.items[").is_err());
+        assert!(parse("$.a..").is_err());
+    }
+
+    #[test]
+    fn selects_and_removes_values() {
+        let mut value = json!({
+            "id": 1,
+            "updatedAt": "2024-01-31",
+            "items": [
+                {"id": 10, "meta": {"updatedAt": "x"}},
+                {"id": 11}
+            ]
+        });
+
+        assert_eq!(
+            select(&value, "$.items[*].id").unwrap(),
+            vec![&json!(10), &json!(11)]
+        );
+        assert_eq!(select(&value, "$..updatedAt").unwrap().len(), 2);
+
+        remove(&mut value, "$..updatedAt").unwrap();
+        remove(&mut value, "items[*].id").unwrap();
+        assert_eq!(value, json!({"id": 1, "items": [{"meta": {}}, {}]}));
+    }
+}
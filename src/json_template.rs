@@ -0,0 +1,101 @@
+use crate::types::VariableType;
+
+/// Fills the `{{variable}}` references of a JSON body. `lookup` gives the value and type of
+/// a variable, None leaves its reference in place. A reference that is a whole string, as in
+/// `"limit": "{{page_size}}"`, is written unquoted when its variable is a number, bool or
+/// JSON variable with a value of that type, so the body can stay valid JSON while edited.
+/// Inside a string, values are escaped; outside of one they're written as they are.
+pub fn resolve(body: &str, lookup: impl Fn(&str) -> Option<(String, VariableType)>) -> String {
+    let bytes = body.as_bytes();
+    let mut output = String::with_capacity(body.len());
+    // Start of the string the scan is in, and whether the next character is escaped
+    let mut string_start: Option<usize> = None;
+    let mut escaped = false;
+    let mut copied = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if escaped {
+            escaped = false;
+            index += 1;
+            continue;
+        }
+        match bytes[index] {
+            b'\\' if string_start.is_some() => escaped = true,
+            b'"' => {
+                string_start = match string_start {
+                    Some(_) => None,
+                    None => Some(index),
+                }
+            }
+            b'{' if body[index..].starts_with("{{") => {
+                let Some(length) = body[index + 2..].find("}}") else {
+                    break;
+                };
+                let end = index + 2 + length + 2;
+                let Some((value, value_type)) = lookup(body[index + 2..end - 2].trim()) else {
+                    index = end;
+                    continue;
+                };
+
+                output.push_str(&body[copied..index]);
+                let whole_string =
+                    string_start == Some(index.wrapping_sub(1)) && bytes.get(end) == Some(&b'"');
+                if whole_string && value_type.is_literal(&value) {
+                    // Drop the quotes around the reference
+                    output.pop();
+                    output.push_str(value.trim());
+                    string_start = None;
+                    copied = end + 1;
+                    index = end + 1;
+                    continue;
+                }
+                if string_start.is_some() {
+                    let quoted = serde_json::to_string(&value).unwrap_or_default();
+                    output.push_str(&quoted[1..quoted.len() - 1]);
+                } else {
+                    output.push_str(&value);
+                }
+                copied = end;
+                index = end;
+                continue;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    output.push_str(&body[copied..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_typed_variables_into_json() {
+        let lookup = |name: &str| {
+            let (value, value_type) = match name {
+                "page_size" => ("20", VariableType::Number),
+                "active" => ("true", VariableType::Bool),
+                "filter" => ("{\"role\": \"admin\"}", VariableType::Json),
+                "name" => ("Ada \"The Countess\"", VariableType::String),
+                "typo" => ("twenty", VariableType::Number),
+                _ => return None,
+            };
+            Some((value.to_string(), value_type))
+        };
+
+        let body = r#"{"limit": "{{page_size}}", "offset": {{page_size}}, "active": "{{ active }}", "filter": "{{filter}}", "greeting": "Hi {{name}}", "id": "{{page_size}}-{{missing}}", "count": "{{typo}}"}"#;
+        let json = resolve(body, lookup);
+        assert_eq!(
+            json,
+            r#"{"limit": 20, "offset": 20, "active": true, "filter": {"role": "admin"}, "greeting": "Hi Ada \"The Countess\"", "id": "20-{{missing}}", "count": "twenty"}"#
+        );
+        assert!(serde_json::from_str::<serde_json::Value>(&json).is_ok());
+
+        let strict = r#"{"a": "{\"b\": \"{{page_size}}\"}"}"#;
+        assert_eq!(resolve(strict, lookup), r#"{"a": "{\"b\": \"20\"}"}"#);
+    }
+}
@@ -3,5 +3,49 @@ pub mod ui;
 pub mod storage;
 pub mod history;
 pub mod icons;
+pub mod i18n;
 pub mod types;
+pub mod signing;
+pub mod faker;
+pub mod external;
+pub mod certificate;
+pub mod assertions;
+pub mod snapshot;
+pub mod json_path;
+pub mod chart;
+pub mod ndjson;
+pub mod pagination;
+pub mod rate_limit;
+pub mod conditional;
+pub mod caching;
+pub mod security_headers;
+pub mod redact;
+pub mod stats;
+pub mod contract;
+pub mod import;
+pub mod paste;
+pub mod path_variables;
+pub mod url_encoding;
+pub mod theme;
+pub mod tools;
+pub mod token_refresh;
+pub mod mock;
+pub mod raw_socket;
+pub mod connectivity;
+pub mod webhook;
+pub mod sync;
 pub mod constant;
+pub mod undo;
+pub mod share;
+pub mod deep_link;
+pub mod instance;
+pub mod tray;
+pub mod monitor;
+pub mod search;
+pub mod recovery;
+pub mod runner;
+pub mod bundle;
+pub mod duplicate_headers;
+pub mod json5;
+pub mod json_template;
+pub mod text_diff;
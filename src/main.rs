@@ -1,40 +1,284 @@
 mod script;
 use std::path::PathBuf;
 
+use beam::types::ApiKeyLocation;
 use beam::types::AuthType;
 use beam::types::BodyFormat;
+use beam::types::CacheValidators;
+use beam::types::ContentEncoding;
 use beam::types::Environment;
+use beam::types::HawkAuth;
+use beam::types::HmacAuth;
 use beam::types::HttpMethod;
+use beam::types::JsonFormatting;
+use beam::types::Pagination;
+use beam::types::QueuedRequest;
 use beam::types::RenameTarget;
 use beam::types::RequestCollection;
 use beam::types::RequestConfig;
+use beam::types::RequestTab;
+use beam::types::ResponseAssertions;
 use beam::types::ResponseData;
-
+use beam::types::ResponsePosition;
+use beam::types::TestResult;
+use beam::types::WindowLayout;
+use beam::types::find_collection;
+use beam::types::find_collection_mut;
+use beam::types::find_request;
+use beam::types::find_request_mut;
+use beam::types::new_id;
+use beam::types::request_position;
+
+use beam::assertions;
+use beam::bundle;
+use beam::conditional;
+use beam::connectivity::{self, ConnectivityReport, Target};
+use beam::constant::{
+    DEFAULT_MAX_DOWNLOAD_SIZE, DEFAULT_MAX_UPLOAD_SIZE, MAX_VARIABLE_DEPTH, RESPONSE_PREVIEW_SIZE,
+};
+use beam::contract;
+use beam::deep_link::DeepLink;
+use beam::faker;
 use beam::http::*;
+use beam::i18n::{self, Language, tr, tr_args};
+use beam::import::{self, Imported};
+use beam::instance::{self, Instance, LaunchRequest, Launches};
+use beam::json_template;
+use beam::json5;
+use beam::monitor::{self, MonitorStatus};
+use beam::pagination::{self, FetchedPages};
+use beam::path_variables::substitute_path_variables;
+use beam::rate_limit;
+use beam::recovery::{self, Recovery};
+use beam::redact;
+use beam::runner::{Run, RunResult, parse_iteration_data};
+use beam::security_headers;
+use beam::share;
+use beam::stats::{CollectionStats, UsageStats, collection_stats, usage_stats};
 use beam::storage;
 use beam::storage::StorageManager;
+use beam::storage::audit_log::{AuditEntry, AuditLog, AuditOperation, AuditSubject};
+use beam::storage::versions::VersionStore;
+use beam::sync::SyncReport;
+use beam::theme::{self, Themes};
+use beam::token_refresh;
+use beam::tray::{self, PinnedRequest, Tray, TrayEvent};
 use beam::ui::CollectionPanel;
 use beam::ui::EnvironmentPanel;
+use beam::ui::QueuePanel;
 use beam::ui::RequestPanel;
+use beam::ui::RequestPreview;
 use beam::ui::ResponsePanel;
+use beam::ui::RunnerDialog;
+use beam::ui::SyncPanel;
+use beam::ui::ToolsPanel;
+use beam::ui::WebhookPanel;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use beam::ui::accessibility;
+use beam::ui::audit_log;
 use beam::ui::collections;
 use beam::ui::environment;
+use beam::ui::floating_element::{AnchorPosition, FloatingElement};
+use beam::ui::queue;
 use beam::ui::request;
 use beam::ui::response;
+use beam::ui::runner;
+use beam::ui::settings::{self, LanguageChoice, ThemeChoice};
+use beam::ui::stats;
+use beam::ui::sync;
+use beam::ui::toast::{self, Toasts};
+use beam::ui::tools;
+use beam::ui::webhook;
+use beam::ui::welcome;
+use beam::ui::{IconName, icon};
+use beam::undo::{UndoStack, WorkspaceCommand};
 
 use iced::color;
 use iced::widget::pane_grid::{self, Axis, PaneGrid};
 use iced::widget::{
-    button, column, container, mouse_area, operation, row, space, stack, text, text_editor,
-    text_input,
+    button, column, container, mouse_area, operation, pick_list, row, space, stack, text,
+    text_editor, text_input, tooltip,
 };
 use iced::{Color, Element, Fill, Size, Task, Theme, Vector};
-use log::{error, info};
+use log::{error, info, warn};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
+/// Failures of spawned background tasks, as (title, detail), waiting to be shown as toasts
+static BACKGROUND_ERRORS: std::sync::Mutex<Vec<(String, String)>> =
+    std::sync::Mutex::new(Vec::new());
+
+/// Shows `content` in a card of the given width over `base`, which is dimmed behind it
+fn modal_overlay<'a>(
+    base: Element<'a, Message>,
+    content: impl Into<Element<'a, Message>>,
+    width: impl Into<iced::Length>,
+) -> Element<'a, Message> {
+    stack![
+        base,
+        container(container(content).width(width))
+            .center_x(Fill)
+            .center_y(Fill)
+            .width(Fill)
+            .height(Fill)
+            .style(|_theme| container::Style {
+                background: Some(Color::from_rgba(0.25, 0.25, 0.25, 0.7).into()),
+                ..Default::default()
+            })
+    ]
+    .into()
+}
+
+/// White card with a border and a drop shadow, for the content of a modal
+fn modal_card_style(_theme: &Theme) -> container::Style {
+    container::Style {
+        background: Some(iced::Background::Color(Color::WHITE)),
+        border: iced::Border {
+            color: Color::from_rgb(0.7, 0.7, 0.7),
+            width: 1.0,
+            radius: 8.0.into(),
+        },
+        shadow: iced::Shadow {
+            color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+            offset: Vector::new(0.0, 4.0),
+            blur_radius: 10.0,
+        },
+        snap: true,
+        ..Default::default()
+    }
+}
+
+/// Lays out the sidebar, request and response panes, returning the sidebar and
+/// request/response splits
+fn build_panes(
+    layout: &WindowLayout,
+) -> (
+    pane_grid::State<PaneContent>,
+    pane_grid::Split,
+    pane_grid::Split,
+) {
+    let (mut panes, collections_pane) = pane_grid::State::new(PaneContent::Collections);
+
+    // Split vertically to create request config pane (middle panel)
+    let (request_pane, first_split) = panes
+        .split(Axis::Vertical, collections_pane, PaneContent::RequestConfig)
+        .unwrap();
+
+    // Split again to put the response pane beside or below the request pane
+    let response_axis = match layout.response_position {
+        ResponsePosition::Right => Axis::Vertical,
+        ResponsePosition::Bottom => Axis::Horizontal,
+    };
+    let (_, second_split) = panes
+        .split(response_axis, request_pane, PaneContent::Response)
+        .unwrap();
+
+    // Restore the layout ratios
+    panes.resize(
+        first_split,
+        if layout.sidebar_collapsed {
+            collapsed_sidebar_ratio(layout.width)
+        } else {
+            layout.sidebar_ratio
+        },
+    );
+    panes.resize(second_split, layout.request_ratio);
+
+    (panes, first_split, second_split)
+}
+
+/// Sidebar ratio that leaves room for just the expand button
+fn collapsed_sidebar_ratio(window_width: f32) -> f32 {
+    (36.0 / window_width.max(1.0)).clamp(0.01, 0.2)
+}
+
+/// Small icon button floating over a pane to collapse or maximize it, `label` naming it
+fn pane_control_button<'a>(name: IconName, message: Message, label: &str) -> Element<'a, Message> {
+    let control = button(
+        icon(name)
+            .size(accessibility::icon_size(14.0))
+            .color(accessibility::contrast(Color::from_rgb(0.55, 0.55, 0.55))),
+    )
+    .on_press(message)
+    .padding(accessibility::padding(4.0))
+    .style(|_theme, status| button::Style {
+        background: match status {
+            button::Status::Hovered => Some(Color::from_rgb(0.9, 0.9, 0.9).into()),
+            _ => None,
+        },
+        border: iced::Border {
+            radius: 4.0.into(),
+            ..Default::default()
+        },
+        ..button::Style::default()
+    });
+
+    accessibility::labeled(control, tr(label), tooltip::Position::Top)
+}
+
+fn maximize_button<'a>(pane: PaneContent, is_maximized: bool) -> Element<'a, Message> {
+    pane_control_button(
+        if is_maximized {
+            IconName::Minimize
+        } else {
+            IconName::Maximize
+        },
+        Message::ToggleMaximized(pane),
+        if is_maximized {
+            "a11y-restore"
+        } else {
+            "a11y-maximize"
+        },
+    )
+}
+
+fn report_background_error(title: &str, detail: String) {
+    error!("{}: {}", title, detail);
+    if let Ok(mut errors) = BACKGROUND_ERRORS.lock() {
+        errors.push((title.to_string(), detail));
+    }
+}
+
+/// Replaces requests with the edits left in their drafts, so they survive a restart. Returns
+/// the ids of the requests that had one.
+fn apply_drafts(
+    storage_manager: &storage::StorageManager,
+    collections: &mut [RequestCollection],
+) -> HashSet<String> {
+    let mut drafts = HashSet::new();
+    for request in collections
+        .iter_mut()
+        .flat_map(|collection| collection.requests.iter_mut())
+    {
+        match storage_manager.storage().load_draft(&request.path) {
+            Ok(Some(mut draft)) => {
+                draft.id = request.id.clone();
+                drafts.insert(draft.id.clone());
+                *request = draft;
+            }
+            Ok(None) => {}
+            Err(e) => error!("Failed to load draft of {:?}: {}", request.path, e),
+        }
+    }
+    drafts
+}
+
+/// Writes the collection's metadata without blocking the UI
+fn save_collection_in_background(
+    storage: Option<storage::StorageManager>,
+    collection: RequestCollection,
+) {
+    tokio::spawn(async move {
+        if let Some(storage_manager) = storage
+            && let Err(e) = storage_manager.storage().save_collection(&collection)
+        {
+            report_background_error("Failed to save collection", e.to_string());
+        }
+    });
+}
+
 #[derive(Debug, Clone)]
 pub enum PaneContent {
     Collections,
@@ -48,19 +292,101 @@ pub enum Message {
     ResponsePanel(response::Message),
     CollectionPanel(collections::Message),
     EnvironmentPanel(environment::Message),
+    QueuePanel(queue::Message),
 
     PaneResized(pane_grid::ResizeEvent),
+    WindowResized(Size),
+    WindowMoved(iced::Point),
+    SaveWindowLayout,
+    ToggleSidebar,
+    ToggleMaximized(PaneContent),
+    ToggleResponsePosition,
     TimerTick,
 
     ModifiersChanged(iced::keyboard::Modifiers),
     KeyPressed(iced::keyboard::Key),
-    RequestCompleted(Result<ResponseData, String>),
-    PostScriptCompleted(crate::script::ScriptExecutionResult),
+    RequestCompleted(String, Result<ResponseData, RequestError>), // (request id, result)
+    // The token request of a request waiting to be sent completed
+    TokenRefreshCompleted(
+        Box<RequestConfig>,
+        Instant,
+        Result<ResponseData, RequestError>,
+    ),
+    Toast(toast::Message),
+    ToastTick,
+    // Theme files, environments.toml and the window layout, checked on one timer
+    PeriodicChecks,
+    PagesFetched(String, Result<FetchedPages, String>), // (request id, merged pages)
+    PostScriptCompleted(String, crate::script::ScriptExecutionResult),
+
+    // Offline queue
+    RetryQueuedRequests,
+    QueuedRequestCompleted(u64, Result<ResponseData, RequestError>),
+    // Sends the monitored requests whose interval is over
+    CheckMonitors,
+    MonitorCompleted(String, Result<ResponseData, RequestError>), // (request_id, result)
+
+    ConfirmOversizedSend,
+    CancelOversizedSend,
 
     HideRenameModal,
     RenameInputChanged(String),
     ConfirmRename,
 
+    // Import from URL
+    HideImportModal,
+    ImportUrlChanged(String),
+    ConfirmImport,
+    ImportFetched(Result<Imported, String>),
+
+    // First-run welcome screen
+    Welcome(welcome::Message),
+    SampleCollectionCreated(Result<(), String>),
+
+    // Files dropped onto the window
+    FileDropped(PathBuf),
+    ConfirmDroppedImport,
+    CancelDroppedImport,
+
+    // A link or file handed over by a later launch of the app
+    Launched(LaunchRequest),
+    // Entries picked in the tray menu
+    CheckTray,
+
+    // Remote sync
+    SyncPanel(sync::Message),
+    SyncFinished(Result<SyncReport, String>),
+
+    // Converters modal
+    ToggleTools,
+    ToolsPanel(tools::Message),
+
+    // Webhook inbox modal
+    ToggleWebhookInbox,
+    WebhookPanel(webhook::Message),
+
+    // Collection statistics modal
+    CollectionStats(stats::Message),
+    UsageStats(stats::Message),
+    Runner(runner::Message),
+    RunnerRequestCompleted(u64, usize, RunResult), // (run_id, entry, result)
+
+    // Settings modal and the themes it picks from, reloaded as their files change
+    ToggleSettings,
+    Settings(settings::Message),
+    ReloadThemes,
+    ThemesLoaded(Themes),
+
+    // Audit log of workspace changes
+    ToggleAuditLog,
+    AuditLogLoaded(Result<Vec<AuditEntry>, String>),
+    AuditLog(audit_log::Message),
+
+    // Connectivity diagnostics for the current request's host
+    CheckConnectivity,
+    ConnectivityChecked(ConnectivityReport),
+    CloseConnectivity,
+
     // Storage operations
     // #[allow(dead_code)]
     // SaveCollection(usize),
@@ -73,37 +399,205 @@ pub enum Message {
     LoadConfigFiles,
     #[allow(dead_code)]
     CollectionsSaved(Result<(), String>),
-    CollectionsLoaded(Result<Vec<RequestCollection>, String>),
+    /// The collections, with the edits of their drafts, and the ids of the requests that have one
+    CollectionsLoaded(Result<(Vec<RequestCollection>, HashSet<String>), String>),
+    CollectionRenamed {
+        collection_id: String,
+        new_name: String,
+        result: Result<PathBuf, String>, // directory holding the requests
+        undoable: bool,                  // false when the rename is itself an undo
+    },
+    CollectionRestored(Result<(), String>),
     EnvironmentsSaved(Result<(), String>),
     EnvironmentsLoadedComplete(crate::storage::PersistentEnvironments),
+    // environments.toml edited by another program, reloaded as it changes
+    CheckEnvironmentsOnDisk,
+    EnvironmentsChangedOnDisk(Result<crate::storage::PersistentEnvironments, String>),
     #[allow(dead_code)]
     SaveInitialData,
-    UpdateLastOpenedRequest(usize, usize), // (collection_index, request_index) - deferred state update
-    LoadLastOpenedRequest(Result<Option<(usize, usize)>, String>),
+    UpdateLastOpenedRequest(String), // request id - deferred state update
+    LoadLastOpenedRequest(Result<Option<String>, String>),
     SaveRequestDebounced {
-        collection_index: usize,
-        request_index: usize,
+        request_id: String,
     },
     RequestSaved(Result<(), String>),
 
+    // Draft of the open request
+    SaveCurrentRequest,
+    RevertToSaved,
+
+    // Save conflicts with changes made outside Beam
+    SaveConflict(RequestConfig),
+    ConflictReload,
+    ConflictReloaded(Result<RequestConfig, String>),
+    ConflictOverwrite,
+    ConflictOverwritten(Result<(), String>),
+    ConflictMerge,
+    ConflictMerged(Result<RequestConfig, String>),
+    DismissConflict,
+
+    // Edits saved by the panic hook of a crashed run
+    RestoreRecovery,
+    DismissRecovery,
+
     DoNothing,
 }
 
+/// A send waiting for its response
+#[derive(Debug)]
+pub struct InFlightRequest {
+    pub config: RequestConfig, // As sent, with variables resolved
+    pub started_at: Instant,
+    pub handle: iced::task::Handle,
+    pub streamed: StreamedBody,
+    pub retries: u32, // Automatic resends after a rate limit so far
+}
+
+/// A write to a request's files, made in order by the save debouncer
+#[derive(Debug)]
+pub enum PendingWrite {
+    Draft(RequestConfig),       // Edits, kept in the draft until saved
+    Save(RequestConfig),        // Writes the request's file and drops the draft
+    Revert(std::path::PathBuf), // Drops the draft of the request at this path
+}
+
+/// Writes requests and their drafts off the UI thread. An autosave refused because the
+/// file changed outside Beam is handed back to the window as `Message::SaveConflict`.
+#[derive(Debug, Clone)]
+pub struct RequestWriter {
+    storage: storage::StorageManager,
+    conflicts: iced::futures::channel::mpsc::UnboundedSender<RequestConfig>,
+}
+
+impl RequestWriter {
+    /// Writes the request's file, true once it's on disk
+    fn save_request(&self, request_config: RequestConfig) -> bool {
+        info!("===request auto saved (debounced)");
+
+        match self.storage.storage().save_request_by_path(&request_config) {
+            Ok(()) => {
+                if let Err(e) = VersionStore::new(&storage::StorageConfig::default().base_path)
+                    .record(&request_config)
+                {
+                    report_background_error("Failed to record request version", e.to_string());
+                }
+                true
+            }
+            Err(storage::StorageError::Conflict(path)) => {
+                warn!("Not saving {}: changed outside Beam", path);
+                // The window is gone when the receiver is, nothing is left to ask
+                let _ = self.conflicts.unbounded_send(request_config);
+                false
+            }
+            Err(e) => {
+                report_background_error("Failed to save request", e.to_string());
+                false
+            }
+        }
+    }
+
+    fn save_draft(&self, request_config: RequestConfig) {
+        if let Err(e) = self.storage.storage().save_draft(&request_config) {
+            report_background_error("Failed to save draft", e.to_string());
+        }
+    }
+
+    /// Saves the request to its file, its draft being dropped once that's done
+    fn commit_request(&self, request_config: RequestConfig) {
+        let path = request_config.path.clone();
+        if self.save_request(request_config) {
+            self.discard_draft(&path);
+        }
+    }
+
+    fn discard_draft(&self, path: &std::path::Path) {
+        if let Err(e) = self.storage.storage().delete_draft(path) {
+            report_background_error("Failed to delete draft", e.to_string());
+        }
+    }
+
+    /// Saves a request after it was run. With unsaved edits, the run goes into the draft and
+    /// into the saved file's history, which otherwise stays as it was saved.
+    fn save_run(&self, request_config: RequestConfig, has_draft: bool) {
+        if !has_draft {
+            self.save_request(request_config);
+            return;
+        }
+
+        self.save_draft(request_config.clone());
+        match self
+            .storage
+            .storage()
+            .load_request_by_path(&request_config.path)
+        {
+            Ok(mut saved) => {
+                saved.run_history = request_config.run_history;
+                saved.last_response = request_config.last_response;
+                saved.cache_validators = request_config.cache_validators;
+                self.save_request(saved);
+            }
+            Err(e) => report_background_error("Failed to save run", e.to_string()),
+        }
+    }
+}
+
+/// A rate limited request waiting out its Retry-After before being sent again
+#[derive(Debug, Clone)]
+pub struct PendingRetry {
+    pub config: RequestConfig, // As sent, with variables resolved
+    pub due: Instant,
+    pub attempt: u32,
+}
+
 #[derive(Debug)]
 pub struct BeamApp {
     pub panes: pane_grid::State<PaneContent>,
     pub collections: Vec<RequestCollection>,
     pub current_request: RequestConfig,
-    pub is_loading: bool,
     pub current_elapsed_time: u64,
     pub request_body_content: text_editor::Content,
     pub post_script_content: text_editor::Content,
+    pub assertion_schema_content: text_editor::Content,
     pub response_body_content: text_editor::Content,
     pub collection_panel: CollectionPanel,
     pub environment_panel: EnvironmentPanel,
     pub response_panel: ResponsePanel,
     pub request_panel: RequestPanel,
-    pub request_start_time: Option<Instant>,
+
+    // Offline request queue
+    pub queue_panel: QueuePanel,
+    pub offline_queue: Vec<QueuedRequest>,
+    pub next_queue_id: u64,
+    pub queue_offer: Option<RequestConfig>, // Failed request that can be queued
+
+    // Size limit guards
+    pub pending_oversized_send: Option<(RequestConfig, Instant)>, // Awaiting confirmation
+    pub download_limit_offer: Option<RequestConfig>, // Request whose response was too large
+
+    // Sends that have not completed yet, by request id
+    pub in_flight: HashMap<String, InFlightRequest>,
+
+    // Last page of paginated responses with more to fetch: the request sent and its response
+    pub next_pages: HashMap<String, (RequestConfig, ResponseData)>,
+
+    // Rate limited requests to send again, by request id
+    pub pending_retries: HashMap<String, PendingRetry>,
+
+    // When refreshed tokens were stored, by (environment name, variable), for TTL expiry
+    pub token_fetched_at: HashMap<(String, String), i64>,
+
+    // Full text of a response body too large to render at once
+    pub full_response_body: Option<String>,
+
+    // Notifications for saves, imports and background failures
+    pub toasts: Toasts,
+
+    // Requests whose autosave was blocked because their file changed outside Beam, the
+    // first one shown in the conflict dialog
+    pub save_conflicts: Vec<RequestConfig>,
+
+    // Host being checked for connectivity, with the report once it's in
+    pub connectivity: Option<(Target, Option<ConnectivityReport>)>,
 
     // Environment management
     pub environments: Vec<Environment>,
@@ -112,16 +606,89 @@ pub struct BeamApp {
     pub method_menu_open: bool,
 
     // Last opened request tracking
-    pub last_opened_request: Option<(usize, usize)>, // (collection_index, request_index)
+    pub last_opened_request: Option<String>, // request id
 
-    // Debounce channel for request saving
-    pub debounce_tx: Option<mpsc::Sender<RequestConfig>>,
+    // Workspace storage, shared by the background tasks so a file changed outside Beam is
+    // noticed against what this window last read or wrote
+    pub storage: Option<storage::StorageManager>,
+
+    // Debounce channel for request saving, and what writes the requests
+    pub debounce_tx: Option<mpsc::Sender<PendingWrite>>,
+    pub writer: Option<RequestWriter>,
+    // Requests with edits in their draft that aren't saved to their file
+    pub drafts: std::collections::HashSet<String>,
+
+    // Icon in the system tray / menu bar while it's turned on
+    pub tray: Option<Tray>,
+    // Requests sent from the tray, whose outcome is notified
+    pub tray_sends: std::collections::HashSet<String>,
+
+    // Outcome of the latest check of each monitored request, by request id
+    pub monitor_status: HashMap<String, MonitorStatus>,
+    // When each monitored request was last sent, and those still being sent
+    pub monitor_checked: HashMap<String, Instant>,
+    pub monitor_sends: std::collections::HashSet<String>,
 
     // Rename modal state
     pub show_rename_modal: bool,
     pub rename_input: String,
     pub rename_target: Option<RenameTarget>, // What is being renamed
 
+    // Import from URL modal state
+    pub show_import_modal: bool,
+    pub import_url: String,
+    pub import_status: Option<Result<String, String>>, // Outcome of the last import
+    pub is_importing: bool,
+
+    // Dropped files awaiting confirmation: file name and what it would import
+    pub pending_drops: Vec<(String, Result<Imported, String>)>,
+
+    // beam:// link the app was launched with, followed once the collections are loaded
+    pub pending_link: Option<String>,
+    // Links and files handed over by later launches of the app
+    pub launches: Launches,
+
+    // What the last run saved when it crashed, offered once the collections are loaded
+    pub pending_recovery: Option<Recovery>,
+    pub crash_recovery: Option<Recovery>,
+
+    // Shown instead of the panes while there are no collections
+    pub show_welcome: bool,
+
+    // Remote sync modal state
+    pub show_sync_modal: bool,
+    pub sync_panel: SyncPanel,
+
+    // Converters modal state
+    pub show_tools_modal: bool,
+    pub tools_panel: ToolsPanel,
+
+    // Local listener capturing webhooks
+    pub show_webhook_modal: bool,
+    pub webhook_panel: WebhookPanel,
+
+    // Statistics of the collection they were opened for
+    pub collection_stats: Option<CollectionStats>,
+    // Local usage statistics across all collections, once opted in
+    pub usage_stats: Option<UsageStats>,
+    // Collection runner, `next_run_id` tells the results of a run from those of an earlier one
+    pub runner: Option<RunnerDialog>,
+    pub next_run_id: u64,
+
+    // Settings modal state and the themes found in the themes folder
+    pub show_settings_modal: bool,
+    pub themes: Themes,
+
+    // Workspace changes, loaded when the audit log is opened
+    pub audit_entries: Option<Vec<AuditEntry>>,
+    // Names of the environments renamed since the environment popup opened, recorded once
+    // it closes rather than on every keystroke
+    pub renamed_environments: HashMap<usize, String>,
+    // Why environments.toml couldn't be reloaded after an outside change, reported once
+    pub environments_reload_error: Option<String>,
+    // Structural changes to the workspace, reversed with Cmd+Z
+    pub undo_stack: UndoStack,
+
     // Storage
     #[allow(dead_code)]
     pub storage_manager: Option<StorageManager>,
@@ -130,14 +697,14 @@ pub struct BeamApp {
     pub send_button_hovered: bool,
     pub cancel_button_hovered: bool,
 
-    // Flag to track recent undo operations
-    pub just_performed_undo: bool,
-
-    // Flag to track when Cmd+Z is being processed to prevent visual flicker
-    pub processing_cmd_z: bool,
-
     // Track keyboard modifiers
     pub modifiers: iced::keyboard::Modifiers,
+
+    // Window and pane layout, saved when it differs from what is on disk
+    pub sidebar_split: pane_grid::Split,
+    pub request_split: pane_grid::Split,
+    pub layout: WindowLayout,
+    pub saved_layout: WindowLayout,
 }
 
 pub fn main() -> iced::Result {
@@ -145,10 +712,52 @@ pub fn main() -> iced::Result {
         .format_timestamp_millis()
         .init();
 
+    // A second launch, e.g. from a link or a file opened, goes to the window already open
+    let launch = instance::from_args(std::env::args().skip(1));
+    let launches = match instance::claim(&storage::StorageConfig::default().base_path, &launch) {
+        Instance::Primary(launches) => launches,
+        Instance::Forwarded => {
+            info!("Handed the launch to the running instance");
+            return Ok(());
+        }
+    };
+
+    // Keep the request being edited should Beam panic, and offer what the last crash kept
+    let base_path = storage::StorageConfig::default().base_path;
+    recovery::install(base_path.clone());
+    let recovered = recovery::take(&base_path);
+
+    // One storage for the whole run, so it knows which files were changed outside Beam
+    let storage = storage::StorageManager::with_default_config()
+        .inspect_err(|e| error!("Failed to open the workspace: {}", e))
+        .ok();
+    let layout = storage
+        .as_ref()
+        .and_then(|storage_manager| {
+            storage_manager
+                .storage()
+                .load_window_layout()
+                .inspect_err(|e| warn!("Failed to load window layout: {}", e))
+                .ok()
+                .flatten()
+        })
+        .unwrap_or_default();
+    let position = match (layout.x, layout.y) {
+        (Some(x), Some(y)) => iced::window::Position::Specific(iced::Point::new(x, y)),
+        _ => iced::window::Position::default(),
+    };
+    let size = Size::new(layout.width, layout.height);
+
     iced::application(
-        || {
+        move || {
             (
-                BeamApp::default(),
+                BeamApp::new(
+                    storage.clone(),
+                    layout.clone(),
+                    launch.clone(),
+                    launches.clone(),
+                    recovered.clone(),
+                ),
                 Task::perform(async { Message::LoadConfigFiles }, |msg| msg),
             )
         },
@@ -156,39 +765,52 @@ pub fn main() -> iced::Result {
         BeamApp::view,
     )
     .title(|_: &BeamApp| "Beam".to_string())
-    // .theme(BeamApp::theme)
+    .theme(BeamApp::theme)
     .subscription(BeamApp::subscription)
-    .window_size(Size::new(1200.0, 800.0))
+    .window_size(size)
+    .position(position)
     .run()
 }
 
-impl Default for BeamApp {
-    fn default() -> Self {
-        let (mut panes, collections_pane) = pane_grid::State::new(PaneContent::Collections);
-
-        // Split vertically to create request config pane (middle panel)
-        let (request_pane, first_split) = panes
-            .split(Axis::Vertical, collections_pane, PaneContent::RequestConfig)
-            .unwrap();
-
-        // Split vertically again to create response pane (right panel)
-        let (_, second_split) = panes
-            .split(Axis::Vertical, request_pane, PaneContent::Response)
-            .unwrap();
-
-        // Set three-panel horizontal layout ratios
-        // Collections: 25%, Request Config: 40%, Response: 35%
-        panes.resize(first_split, 0.25);
-        panes.resize(second_split, 0.466); // 40/(40+35) = 0.533
+impl BeamApp {
+    fn new(
+        storage: Option<storage::StorageManager>,
+        layout: WindowLayout,
+        launch: Vec<LaunchRequest>,
+        launches: Launches,
+        pending_recovery: Option<Recovery>,
+    ) -> Self {
+        i18n::set_language(layout.language.unwrap_or_else(Language::from_system));
+        accessibility::set_large_targets(layout.large_targets);
+        let (panes, first_split, second_split) = build_panes(&layout);
+
+        let request_panel = RequestPanel {
+            selected_tab: layout.request_tab.clone(),
+            request_tabs: layout.request_tabs.clone(),
+            ..RequestPanel::default()
+        };
+        let mut response_panel = ResponsePanel::new();
+        response_panel.selected_tab = layout.response_tab;
 
         let collections = vec![];
 
+        // Links wait for the collections, files are confirmed like dropped ones
+        let mut pending_link = None;
+        let mut pending_drops = Vec::new();
+        for request in launch {
+            match request {
+                LaunchRequest::OpenLink { link } => pending_link = Some(link),
+                LaunchRequest::OpenFile { path } => pending_drops.push(dropped_file(&path)),
+                LaunchRequest::Activate => {}
+            }
+        }
+
         Self {
             panes,
             collections,
-            is_loading: false,
             current_elapsed_time: 0,
             current_request: RequestConfig {
+                id: new_id(),
                 name: String::new(),
                 path: std::path::PathBuf::new(),
                 method: HttpMethod::GET,
@@ -207,20 +829,60 @@ impl Default for BeamApp {
                 basic_password: String::new(),
                 api_key: String::new(),
                 api_key_header: "X-API-Key".to_string(),
-                collection_index: 0,
-                request_index: 0,
+                api_key_location: ApiKeyLocation::Header,
                 metadata: None,
+                unix_socket: String::new(),
+                max_upload_size: DEFAULT_MAX_UPLOAD_SIZE,
+                max_download_size: DEFAULT_MAX_DOWNLOAD_SIZE,
+                latency_budget: 0,
+                retry_after_rate_limit: false,
+                audit_security_headers: false,
+                accept_encoding: ContentEncoding::ALL.to_vec(),
+                decompress_response: true,
+                send_query_as_typed: false,
+                connection: Default::default(),
+                hmac_auth: HmacAuth::default(),
+                hawk_auth: HawkAuth::default(),
+                pagination: Pagination::default(),
+                cache_validators: CacheValidators::default(),
+                run_history: Vec::new(),
+                examples: Vec::new(),
                 post_request_script: None,
+                path_variables: Vec::new(),
+                param_docs: Default::default(),
+                header_docs: Default::default(),
+                assertions: ResponseAssertions::default(),
+                token_refresh: Default::default(),
+                contract: None,
+                monitor: Default::default(),
                 last_response: None,
             },
             request_body_content: text_editor::Content::new(),
             response_body_content: text_editor::Content::new(),
             post_script_content: text_editor::Content::new(),
-            response_panel: ResponsePanel::new(),
-            request_panel: RequestPanel::default(),
+            assertion_schema_content: text_editor::Content::new(),
+            response_panel,
+            request_panel,
             collection_panel: CollectionPanel::new(),
             environment_panel: EnvironmentPanel::new(),
-            request_start_time: None,
+
+            // Offline request queue
+            queue_panel: QueuePanel::new(),
+            offline_queue: vec![],
+            next_queue_id: 0,
+            queue_offer: None,
+
+            // Size limit guards
+            pending_oversized_send: None,
+            save_conflicts: Vec::new(),
+            connectivity: None,
+            download_limit_offer: None,
+            in_flight: HashMap::new(),
+            next_pages: HashMap::new(),
+            pending_retries: HashMap::new(),
+            token_fetched_at: HashMap::new(),
+            full_response_body: None,
+            toasts: Toasts::new(),
 
             // Initialize with empty environments
             environments: vec![],
@@ -232,11 +894,50 @@ impl Default for BeamApp {
             last_opened_request: None,
 
             // Debounce channel will be initialized later
+            storage,
             debounce_tx: None,
+            writer: None,
+            drafts: std::collections::HashSet::new(),
+            tray: None,
+            tray_sends: std::collections::HashSet::new(),
+            monitor_status: HashMap::new(),
+            monitor_checked: HashMap::new(),
+            monitor_sends: std::collections::HashSet::new(),
 
             // Rename modal state
             show_rename_modal: false,
             rename_input: String::new(),
+            show_import_modal: false,
+            import_url: String::new(),
+            import_status: None,
+            is_importing: false,
+            pending_drops,
+            pending_link,
+            launches,
+            pending_recovery,
+            crash_recovery: None,
+            show_welcome: false,
+            show_sync_modal: false,
+            sync_panel: SyncPanel::new(beam::sync::load_config(
+                &storage::StorageConfig::default().base_path,
+            )),
+            show_tools_modal: false,
+            tools_panel: ToolsPanel::default(),
+            show_webhook_modal: false,
+            webhook_panel: WebhookPanel::default(),
+            collection_stats: None,
+            usage_stats: None,
+            runner: None,
+            next_run_id: 0,
+            show_settings_modal: false,
+            themes: Themes {
+                themes: vec![theme::built_in()],
+                errors: Vec::new(),
+            },
+            audit_entries: None,
+            renamed_environments: HashMap::new(),
+            environments_reload_error: None,
+            undo_stack: UndoStack::default(),
             rename_target: None,
 
             // Storage will be initialized asynchronously
@@ -247,15 +948,24 @@ impl Default for BeamApp {
             cancel_button_hovered: false,
 
             // Initialize undo tracking
-            just_performed_undo: false,
-            processing_cmd_z: false,
             modifiers: iced::keyboard::Modifiers::default(),
+
+            sidebar_split: first_split,
+            request_split: second_split,
+            saved_layout: layout.clone(),
+            layout,
         }
     }
 }
 
 impl BeamApp {
     fn update(&mut self, message: Message) -> Task<Message> {
+        let task = self.handle_message(message);
+        self.request_panel.refresh_body_diff(&self.current_request);
+        task
+    }
+
+    fn handle_message(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::ModifiersChanged(modifiers) => {
                 self.modifiers = modifiers;
@@ -267,16 +977,13 @@ impl BeamApp {
                     &self.current_request,
                     &self.environments,
                     &mut self.request_body_content,
+                    &mut self.post_script_content,
                 ) {
-                    request::Action::SendRequest(request_start_time) => {
-                        let resolved_config =
-                            self.resolve_request_config_variables(&self.current_request);
-
-                        self.handle_send_request(resolved_config, request_start_time)
-                    }
+                    request::Action::SendRequest(request_start_time) => self
+                        .send_with_token_refresh(self.current_request.clone(), request_start_time),
                     request::Action::CancelRequest() => {
-                        // TODO: cancel request
-                        self.is_loading = false;
+                        let request_id = self.current_request.id.clone();
+                        self.cancel_request(&request_id);
                         Task::none()
                     }
                     request::Action::Run(task) => return task.map(Message::RequestPanel),
@@ -293,8 +1000,9 @@ impl BeamApp {
                         let environments = self.environments.clone();
                         let active_env_name = environments[index].name.clone();
 
+                        let storage = self.storage();
                         tokio::spawn(async move {
-                            match storage::StorageManager::with_default_config() {
+                            match storage {
                                 Ok(storage_manager) => {
                                     if let Err(e) =
                                         storage_manager.storage().save_environments_with_active(
@@ -315,52 +1023,25 @@ impl BeamApp {
                         self.request_body_content.perform(action);
                         self.current_request.body = self.request_body_content.text();
 
-                        if let Some(collection) = self
-                            .collections
-                            .get_mut(self.current_request.collection_index)
+                        if let Some(request) =
+                            find_request_mut(&mut self.collections, &self.current_request.id)
                         {
-                            if let Some(request) = collection
-                                .requests
-                                .get_mut(self.current_request.request_index)
-                            {
-                                *request = self.current_request.clone();
-                            }
+                            *request = self.current_request.clone();
                         }
 
-                        let request_to_persist = self.current_request.clone();
-
                         // TODO: only save if edit, not movement
-                        if let Some(tx) = &self.debounce_tx {
-                            if let Err(_) = tx.try_send(request_to_persist) {
-                                info!("Debounce channel is full or closed");
-                            }
-                        }
+                        self.queue_draft(self.current_request.clone());
 
                         Task::none()
                     }
-                    request::Action::EditRequestPostRequestScript(action) => {
-                        self.post_script_content.perform(action);
-                        let mut request = self.current_request.clone();
-                        request.post_request_script = Some(self.post_script_content.text());
-
-                        if let Some(collection) = self
-                            .collections
-                            .get_mut(self.current_request.collection_index)
-                        {
-                            if let Some(request) = collection
-                                .requests
-                                .get_mut(self.current_request.request_index)
-                            {
-                                *request = self.current_request.clone();
-                            }
-                        }
-
-                        let request_to_persist = self.current_request.clone();
-
-                        if let Some(tx) = &self.debounce_tx {
-                            if let Err(_) = tx.try_send(request_to_persist) {
-                                info!("Debounce channel is full or closed");
-                            }
+                    request::Action::EditAssertionSchema(action) => {
+                        let is_edit = action.is_edit();
+                        self.assertion_schema_content.perform(action);
+
+                        if is_edit {
+                            let mut request = self.current_request.clone();
+                            request.assertions.json_schema = self.assertion_schema_content.text();
+                            self.update_request_state(request);
                         }
 
                         Task::none()
@@ -380,40 +1061,45 @@ impl BeamApp {
                         self.perform_search(!is_previous, Some(focus_id))
                     }
                     request::Action::FormatRequestBody(formatted_body) => {
-                        let select_all_action = text_editor::Action::SelectAll;
-                        self.request_body_content.perform(select_all_action);
-
-                        let paste_action = text_editor::Action::Edit(text_editor::Edit::Paste(
-                            std::sync::Arc::new(formatted_body),
-                        ));
-                        self.request_body_content.perform(paste_action);
-                        self.current_request.body = self.request_body_content.text();
+                        self.replace_request_body(formatted_body);
+                        Task::none()
+                    }
+                    request::Action::OpenEnvironmentPopup => {
+                        self.show_environment_popup = true;
 
-                        if let Some(collection) = self
-                            .collections
-                            .get_mut(self.current_request.collection_index)
+                        Task::none()
+                    }
+                    request::Action::InspectToken(token) => {
+                        self.tools_panel.inspect_jwt(token);
+                        self.show_tools_modal = true;
+                        Task::none()
+                    }
+                    request::Action::CheckConnectivity => self.update(Message::CheckConnectivity),
+                    request::Action::LoadVersions => self.load_versions(),
+                    request::Action::RestoreVersion(hash) => {
+                        match VersionStore::new(&storage::StorageConfig::default().base_path)
+                            .restore(&self.current_request, &hash)
                         {
-                            if let Some(request) = collection
-                                .requests
-                                .get_mut(self.current_request.request_index)
-                            {
-                                *request = self.current_request.clone();
-                            }
-                        }
-
-                        // Save the updated request
-                        let request_to_persist = self.current_request.clone();
-                        if let Some(tx) = &self.debounce_tx {
-                            if let Err(_) = tx.try_send(request_to_persist) {
-                                info!("Debounce channel is full or closed");
+                            // Restored as unsaved edits, so it can be reviewed and reverted
+                            Ok(request) => {
+                                self.apply_loaded_request(request.clone());
+                                self.queue_draft(request);
+                                self.toasts.success("Restored the version, save to keep it");
                             }
+                            Err(e) => self
+                                .toasts
+                                .error("Could not restore the version", e.to_string()),
                         }
 
                         Task::none()
                     }
-                    request::Action::OpenEnvironmentPopup => {
-                        self.show_environment_popup = true;
-
+                    request::Action::OpenHeaderDocs(name) => {
+                        if let Some(url) = request::header_docs_url(&name)
+                            && let Err(e) = beam::external::open_url(&url)
+                        {
+                            error!("{}", e);
+                            self.toasts.error("Could not open the documentation", e);
+                        }
                         Task::none()
                     }
                     request::Action::None => Task::none(),
@@ -430,13 +1116,7 @@ impl BeamApp {
                         Task::none()
                     }
                     response::Action::FormatResponseBody(formatted_body) => {
-                        let select_all_action = text_editor::Action::SelectAll;
-                        self.response_body_content.perform(select_all_action);
-
-                        let paste_action = text_editor::Action::Edit(text_editor::Edit::Paste(
-                            std::sync::Arc::new(formatted_body),
-                        ));
-                        self.response_body_content.perform(paste_action);
+                        self.set_response_body(formatted_body);
 
                         Task::none()
                     }
@@ -453,475 +1133,1371 @@ impl BeamApp {
                     response::Action::Focus(id) => iced::widget::operation::focus(id)
                         .map(|_: ()| Message::ResponsePanel(response::Message::DoNothing)),
                     response::Action::Run(task) => task.map(Message::ResponsePanel),
-                    response::Action::None => Task::none(),
-                }
-            }
-            Message::CollectionPanel(view_message) => {
-                match self
-                    .collection_panel
-                    .update(view_message, &self.collections)
-                {
-                    collections::Action::ToggleCollection(collection_index) => {
-                        if let Some(collection) = self.collections.get_mut(collection_index) {
-                            collection.expanded = !collection.expanded;
-
-                            let col = collection.clone();
-
-                            tokio::spawn(async move {
-                                match storage::StorageManager::with_default_config() {
-                                    Ok(storage_manager) => {
-                                        match storage_manager.storage().save_collection(&col) {
-                                            Ok(_) => Ok(()),
-                                            Err(e) => Err(e.to_string()),
-                                        }
-                                    }
-                                    Err(e) => Err(e.to_string()),
-                                }
+                    response::Action::QueueRequest => {
+                        if let Some(request) = self.queue_offer.take() {
+                            let last_error = self
+                                .current_request
+                                .last_response
+                                .as_ref()
+                                .and_then(|response| response.body.lines().next())
+                                .unwrap_or_default()
+                                .to_string();
+
+                            self.offline_queue.push(QueuedRequest {
+                                id: self.next_queue_id,
+                                request,
+                                attempts: 1,
+                                last_error,
+                                in_flight: false,
                             });
+                            self.next_queue_id += 1;
+                            self.toasts.info(
+                                "Queued, the request will be retried when the network is back",
+                            );
                         }
 
                         Task::none()
                     }
-                    collections::Action::SelectRequestConfig(collection_index, request_index) => {
-                        if self.last_opened_request == Some((collection_index, request_index)) {
-                            return Task::none();
+                    response::Action::DownloadAnyway => match self.download_limit_offer.take() {
+                        Some(mut request) => {
+                            request.max_download_size = 0;
+                            self.dispatch_request(request, Instant::now())
                         }
-
-                        if let Some(collection) = self.collections.get(collection_index) {
-                            if let Some(request_config) = collection.requests.get(request_index) {
-                                self.current_request = request_config.clone();
-                                self.request_panel.reset_undo_histories(
-                                    &self.current_request.url,
-                                    &self.current_request.body,
-                                );
-
-                                Self::update_editor_content(
-                                    &mut self.request_body_content,
-                                    self.current_request.body.to_string(),
-                                );
-
-                                Self::update_editor_content(
-                                    &mut self.post_script_content,
-                                    self.current_request
-                                        .post_request_script
-                                        .as_deref()
-                                        .unwrap_or("")
-                                        .to_string(),
-                                );
-
-                                if let Some(resp) = &self.current_request.last_response {
-                                    let formatted_resp = Self::format_response_content(
-                                        resp.body.as_str(),
-                                        self.current_request.body_format,
-                                    );
-
-                                    Self::update_editor_content(
-                                        &mut self.response_body_content,
-                                        formatted_resp,
-                                    );
-                                }
-
-                                // Update the last opened request state and save to storage
-                                self.last_opened_request = Some((collection_index, request_index));
-                                // Save the last opened request asynchronously without blocking the UI
-                                tokio::spawn(async move {
-                                    if let Ok(storage_manager) =
-                                        storage::StorageManager::with_default_config()
-                                    {
-                                        if let Err(e) =
-                                            storage_manager.storage().save_last_opened_request(
-                                                collection_index,
-                                                request_index,
-                                            )
-                                        {
-                                            error!("Failed to save last opened request: {}", e);
-                                        }
-                                    }
-                                });
+                        None => Task::none(),
+                    },
+                    response::Action::FetchNextPage => {
+                        match self
+                            .next_pages
+                            .get(&self.current_request.id)
+                            .and_then(|(config, response)| pagination::next_page(config, response))
+                        {
+                            Some(config) => self.dispatch_request(config, Instant::now()),
+                            None => Task::none(),
+                        }
+                    }
+                    response::Action::FetchAllPages => {
+                        match self.next_pages.remove(&self.current_request.id) {
+                            Some((config, response)) => {
+                                let request_id = config.id.clone();
+                                self.toasts.info("Fetching the remaining pages");
+                                Task::perform(
+                                    pagination::fetch_all(config, response),
+                                    move |result| Message::PagesFetched(request_id, result),
+                                )
                             }
+                            None => Task::none(),
                         }
-
+                    }
+                    response::Action::SendConditional => {
+                        match conditional::conditional_request(&self.current_request) {
+                            Some(request) => self.send_with_token_refresh(request, Instant::now()),
+                            None => Task::none(),
+                        }
+                    }
+                    response::Action::CancelRetry => {
+                        self.pending_retries.remove(&self.current_request.id);
                         Task::none()
                     }
-                    collections::Action::SaveRequestToCollection(request_config) => {
-                        if let Some(collection) =
-                            self.collections.get_mut(request_config.collection_index)
+                    response::Action::OpenExternally => {
+                        if let Some(response) = &self.current_request.last_response
+                            && let Err(e) = beam::external::open_response(response)
                         {
-                            let mut new_req = request_config.clone();
-                            // Get the new request path using the storage manager
-                            if let Ok(storage_manager) =
-                                storage::StorageManager::with_default_config()
-                            {
-                                let new_request_path = storage_manager
-                                    .storage()
-                                    .get_new_request_path_from_collection(collection);
-
-                                new_req.path = PathBuf::from(new_request_path);
-                            } else {
-                                error!("failed to get storage manager");
-                                return Task::none();
-                            };
-
-                            self.last_opened_request = Some((
-                                request_config.collection_index,
-                                request_config.request_index,
-                            ));
-
-                            collection.requests.push(new_req.clone());
-
-                            self.current_request = new_req.clone();
-
-                            tokio::spawn(async move {
-                                Self::save_request(new_req);
-                            });
+                            error!("{}", e);
+                            self.toasts.error("Could not open the response", e);
                         }
-
                         Task::none()
                     }
-                    collections::Action::SaveNewCollection(new_collection) => {
-                        self.collections.push(new_collection.clone());
-
-                        tokio::spawn(async move {
-                            if let Ok(storage_manager) =
-                                storage::StorageManager::with_default_config()
-                            {
-                                if let Err(e) =
-                                    storage_manager.storage().save_collection(&new_collection)
-                                {
-                                    error!("Failed to save collection: {}", e);
-                                }
-                            }
-                        });
-
+                    response::Action::OpenStatusDocs(status) => {
+                        if let Err(e) = beam::external::open_url(&response::status_docs_url(status))
+                        {
+                            error!("{}", e);
+                            self.toasts.error("Could not open the documentation", e);
+                        }
                         Task::none()
                     }
-                    collections::Action::SendRequest(
-                        collection_index,
-                        request_index,
-                        request_start_time,
-                    ) => {
-                        if let Some(collection) = self.collections.get(collection_index) {
-                            if let Some(request) = collection.requests.get(request_index) {
-                                let resolved_config =
-                                    self.resolve_request_config_variables(request);
-                                return self
-                                    .handle_send_request(resolved_config, request_start_time);
-                            }
+                    response::Action::ShowFullResponse => {
+                        if let Some(body) = self.full_response_body.take() {
+                            Self::update_editor_content(&mut self.response_body_content, body);
                         }
-
                         Task::none()
                     }
-                    collections::Action::DuplicateRequest(collection_index, request_index) => {
-                        if let Some(collection) = self.collections.get_mut(collection_index) {
-                            if let Some(request) = collection.requests.get(request_index) {
-                                let mut new_request = request.clone();
-
-                                new_request.name = format!("{} (Copy)", new_request.name);
-                                new_request.collection_index = collection_index;
-                                new_request.request_index = collection.requests.len();
-
-                                let mut path = PathBuf::new();
-                                let curr_request_path = PathBuf::from(&request.path);
-                                if let Some(parent) = curr_request_path.as_path().parent() {
-                                    path.push(parent);
-                                }
-
-                                let mut max_number = 0;
-                                if let Some(last_request) = collection.requests.last() {
-                                    if let Some(filename_str) =
-                                        last_request.path.file_stem().and_then(|s| s.to_str())
-                                    {
-                                        if let Ok(number) = filename_str.parse::<u32>() {
-                                            max_number = number;
-                                        }
-                                    }
-                                }
-                                path.push(format!("{:04}.toml", max_number + 1));
-                                new_request.path = path.clone();
-                                let request_to_persist = new_request.clone();
-                                collection.requests.push(new_request);
-
-                                tokio::spawn(async move {
-                                    Self::save_request(request_to_persist);
-                                });
-                            }
-                        }
-
+                    response::Action::InspectToken(token) => {
+                        self.tools_panel.inspect_jwt(token);
+                        self.show_tools_modal = true;
                         Task::none()
                     }
-                    collections::Action::DeleteRequest(collection_index, request_index) => {
-                        if let Some(collection) = self.collections.get_mut(collection_index) {
-                            if request_index < collection.requests.len() {
-                                if let Some(request) = collection.requests.get(request_index) {
-                                    let request_path = request.path.clone();
-                                    collection.requests.remove(request_index);
-
-                                    // Use the storage method to delete the file
-                                    tokio::spawn(async move {
-                                        if let Ok(storage_manager) =
-                                            StorageManager::with_default_config()
-                                        {
-                                            let storage = storage_manager.storage();
-                                            if let Err(e) =
-                                                storage.delete_request_by_path(&request_path)
-                                            {
-                                                error!("Failed to delete request file: {}", e);
-                                            }
-                                        }
-                                    });
-
-                                    Task::none()
-                                } else {
-                                    Task::none()
-                                }
-                            } else {
-                                Task::none()
-                            }
-                        } else {
-                            Task::none()
+                    response::Action::CheckConnectivity => self.update(Message::CheckConnectivity),
+                    response::Action::UpdateRunNote(index, note) => {
+                        let mut request = self.current_request.clone();
+                        if let Some(run) = request.run_history.get_mut(index) {
+                            run.note = note;
+                            self.update_request_state(request);
                         }
+
+                        Task::none()
                     }
-                    collections::Action::RenameRequest(collection_index, request_index) => {
-                        // Show the rename modal with the current request name
-                        if let Some(collection) = self.collections.get(collection_index) {
-                            if let Some(request) = collection.requests.get(request_index) {
-                                self.show_rename_modal = true;
-                                self.rename_input = request.name.clone();
-                                self.rename_target =
-                                    Some(RenameTarget::Request(collection_index, request_index));
-                            }
+                    response::Action::SaveExample => {
+                        let mut request = self.current_request.clone();
+                        if let Some(response) = &self.current_request.last_response {
+                            self.response_panel.selected_example = request.add_example(response);
+                            self.update_request_state(request);
+                            self.toasts.success("Saved the response as an example");
                         }
 
                         Task::none()
                     }
-                    collections::Action::RenameCollection(collection_index) => {
-                        // Show the rename modal for the folder
-                        if let Some(collection) = self.collections.get(collection_index) {
-                            self.show_rename_modal = true;
-                            self.rename_input = collection.name.clone();
-                            self.rename_target = Some(RenameTarget::Folder(collection_index));
+                    response::Action::RenameExample(index, name) => {
+                        let mut request = self.current_request.clone();
+                        if let Some(example) = request.examples.get_mut(index) {
+                            example.name = name;
+                            self.update_request_state(request);
                         }
 
                         Task::none()
                     }
-                    collections::Action::DeleteCollection(collection_index) => {
-                        if collection_index >= self.collections.len() {
-                            return Task::none();
+                    response::Action::UpdateExampleMock(index, mock) => {
+                        let mut request = self.current_request.clone();
+                        if let Some(example) = request.examples.get_mut(index) {
+                            example.mock = mock;
+                            self.update_request_state(request);
                         }
 
-                        if let Some(collection) = self.collections.get(collection_index) {
-                            if let Ok(storage_manager) =
-                                storage::StorageManager::with_default_config()
-                            {
-                                storage_manager
-                                    .storage()
-                                    .delete_collection_by_folder_name(&collection.folder_name);
-                            }
+                        Task::none()
+                    }
+                    response::Action::DeleteExample(index) => {
+                        let mut request = self.current_request.clone();
+                        if index < request.examples.len() {
+                            request.examples.remove(index);
+                            self.update_request_state(request);
                         }
 
-                        self.collections.remove(collection_index);
-
                         Task::none()
                     }
-                    collections::Action::None => Task::none(),
+                    response::Action::None => Task::none(),
                 }
             }
-            Message::PaneResized(event) => {
-                self.panes.resize(event.split, event.ratio);
+            Message::ConfirmOversizedSend => match self.pending_oversized_send.take() {
+                Some((config, request_start_time)) => {
+                    self.dispatch_request(config, request_start_time)
+                }
+                None => Task::none(),
+            },
+            Message::CancelOversizedSend => {
+                self.pending_oversized_send = None;
+                Task::none()
+            }
+            Message::SaveCurrentRequest => {
+                self.format_json_body(self.layout.json_body.on_save);
+                if self.drafts.remove(&self.current_request.id) {
+                    self.write_in_order(PendingWrite::Save(self.current_request.clone()));
+                }
+                Task::none()
+            }
+            Message::RevertToSaved => {
+                if !self.drafts.remove(&self.current_request.id) {
+                    return Task::none();
+                }
+                self.write_in_order(PendingWrite::Revert(self.current_request.path.clone()));
+
+                match self.storage().and_then(|manager| {
+                    manager
+                        .storage()
+                        .load_request_by_path(&self.current_request.path)
+                }) {
+                    Ok(mut request) => {
+                        request.id = self.current_request.id.clone();
+                        self.apply_loaded_request(request);
+                    }
+                    Err(e) => {
+                        error!("Failed to revert request: {}", e);
+                        self.toasts.error("Failed to revert request", e.to_string());
+                    }
+                }
+                Task::none()
+            }
+            Message::SaveConflict(request) => {
+                self.save_conflicts
+                    .retain(|conflict| conflict.id != request.id);
+                self.save_conflicts.push(request);
                 Task::none()
             }
-            Message::RequestCompleted(result) => {
-                self.is_loading = false;
-                self.request_start_time = None;
+            Message::ConflictReload => {
+                if self.save_conflicts.is_empty() {
+                    return Task::none();
+                }
+                let conflict = self.save_conflicts.remove(0);
+                let storage = self.storage();
+                Task::perform(
+                    async move {
+                        let mut request = storage
+                            .and_then(|manager| {
+                                manager.storage().load_request_by_path(&conflict.path)
+                            })
+                            .map_err(|e| e.to_string())?;
+                        request.id = conflict.id;
+                        Ok(request)
+                    },
+                    Message::ConflictReloaded,
+                )
+            }
+            Message::ConflictReloaded(result) => {
                 match result {
-                    Ok(response) => {
-                        let formatted_body = Self::format_response_content(
-                            &response.body,
-                            self.current_request.body_format,
-                        );
-
-                        Self::update_editor_content(
-                            &mut self.response_body_content,
-                            formatted_body,
-                        );
-
-                        // Update the request in the collections as well
-                        if let Some(collection) = self
-                            .collections
-                            .get_mut(self.current_request.collection_index)
-                        {
-                            if let Some(request) = collection
-                                .requests
-                                .get_mut(self.current_request.request_index)
-                            {
-                                request.last_response = Some(response.clone());
-                                self.current_request = request.clone();
-
-                                let request_to_persist = request.clone();
-
-                                tokio::spawn(async move {
-                                    Self::save_request(request_to_persist);
-                                });
-                            }
-                        }
+                    Ok(request) => self.apply_loaded_request(request),
+                    Err(e) => {
+                        error!("Failed to reload request: {}", e);
+                        self.toasts.error("Failed to reload request", e);
+                    }
+                }
+                Task::none()
+            }
+            Message::ConflictOverwrite => {
+                if self.save_conflicts.is_empty() {
+                    return Task::none();
+                }
+                let conflict = self.save_conflicts.remove(0);
+                let request = self.latest_request(conflict);
+                let storage = self.storage();
+                Task::perform(
+                    async move {
+                        storage
+                            .and_then(|manager| {
+                                manager.storage().overwrite_request_by_path(&request)
+                            })
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::ConflictOverwritten,
+                )
+            }
+            Message::ConflictOverwritten(result) => {
+                if let Err(e) = result {
+                    error!("Failed to overwrite request: {}", e);
+                    self.toasts.error("Failed to overwrite request", e);
+                }
+                Task::none()
+            }
+            Message::ConflictMerge => {
+                if self.save_conflicts.is_empty() {
+                    return Task::none();
+                }
+                let conflict = self.save_conflicts.remove(0);
+                let request = self.latest_request(conflict);
+                let storage = self.storage();
+                Task::perform(
+                    async move {
+                        storage
+                            .and_then(|manager| {
+                                let merged = manager.storage().merge_request_by_path(&request)?;
+                                manager.storage().overwrite_request_by_path(&merged)?;
+                                Ok(merged)
+                            })
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::ConflictMerged,
+                )
+            }
+            Message::ConflictMerged(result) => {
+                match result {
+                    Ok(merged) => self.apply_loaded_request(merged),
+                    Err(e) => {
+                        error!("Failed to merge request: {}", e);
+                        self.toasts.error("Failed to merge request", e);
+                    }
+                }
+                Task::none()
+            }
+            Message::DismissConflict => {
+                if !self.save_conflicts.is_empty() {
+                    self.save_conflicts.remove(0);
+                }
+                Task::none()
+            }
+            Message::RestoreRecovery => {
+                let Some(request) = self.recoverable_request().cloned() else {
+                    self.crash_recovery = None;
+                    return Task::none();
+                };
+                self.crash_recovery = None;
 
-                        // Execute post-request script if available
-                        if let Some(script) = &self.current_request.post_request_script {
-                            if !script.trim().is_empty() {
-                                let script_clone = script.clone();
-                                let request_config = self.current_request.clone();
-                                let active_env = self
-                                    .active_environment
-                                    .and_then(|idx| self.environments.get(idx))
-                                    .cloned()
-                                    .unwrap_or_else(|| Environment::new("Default".to_string()));
+                let Some((collection_index, _)) = request_position(&self.collections, &request.id)
+                else {
+                    return Task::none();
+                };
 
-                                return Task::perform(
-                                    async move {
-                                        crate::script::execute_post_request_script(
-                                            &script_clone,
-                                            request_config,
-                                            response,
-                                            &active_env,
-                                        )
-                                    },
-                                    Message::PostScriptCompleted,
-                                );
-                            }
-                        }
+                self.apply_loaded_request(request.clone());
+                self.queue_draft(request.clone());
+                self.collections[collection_index].expanded = true;
+                self.toasts.success("Restored your unsaved edits");
+                self.update(Message::CollectionPanel(
+                    collections::Message::RequestSelected(request.id),
+                ))
+            }
+            Message::DismissRecovery => {
+                self.crash_recovery = None;
+                Task::none()
+            }
+            Message::QueuePanel(queue_message) => match self.queue_panel.update(queue_message) {
+                queue::Action::Retry(id) => self.retry_queued_request(id),
+                queue::Action::RetryAll => self.update(Message::RetryQueuedRequests),
+                queue::Action::Remove(id) => {
+                    self.offline_queue.retain(|queued| queued.id != id);
+                    Task::none()
+                }
+                queue::Action::None => Task::none(),
+            },
+            Message::RetryQueuedRequests => {
+                let ids: Vec<u64> = self
+                    .offline_queue
+                    .iter()
+                    .filter(|queued| !queued.in_flight)
+                    .map(|queued| queued.id)
+                    .collect();
+
+                Task::batch(ids.into_iter().map(|id| self.retry_queued_request(id)))
+            }
+            Message::CheckMonitors => {
+                let monitored: Vec<RequestConfig> = self
+                    .collections
+                    .iter()
+                    .flat_map(|collection| &collection.requests)
+                    .filter(|request| request.monitor.enabled)
+                    .cloned()
+                    .collect();
+
+                // Forget the monitors turned off or deleted
+                let ids: Vec<&str> = monitored
+                    .iter()
+                    .map(|request| request.id.as_str())
+                    .collect();
+                self.monitor_status
+                    .retain(|id, _| ids.contains(&id.as_str()));
+                self.monitor_checked
+                    .retain(|id, _| ids.contains(&id.as_str()));
+
+                let now = Instant::now();
+                let mut tasks = Vec::new();
+                for request in monitored {
+                    if self.monitor_sends.contains(&request.id)
+                        || !monitor::is_due(
+                            &request.monitor,
+                            self.monitor_checked.get(&request.id).copied(),
+                            now,
+                        )
+                    {
+                        continue;
                     }
-                    Err(error) => {
-                        let error_response = ResponseData {
-                            status: 0,
-                            status_text: "Error".to_string(),
-                            headers: vec![],
-                            body: error.clone(),
-                            content_type: "text/plain".to_string(),
-                            is_binary: false,
-                            size: 0,
-                            time: 0,
-                        };
 
-                        // Store the error response in the current request
-                        self.current_request.last_response = Some(error_response.clone());
-                        Self::update_editor_content(
-                            &mut self.response_body_content,
-                            error.to_string(),
-                        );
+                    self.monitor_checked.insert(request.id.clone(), now);
+                    self.monitor_sends.insert(request.id.clone());
+                    let request_id = request.id.clone();
+                    let config = self.resolve_request_config_variables(&request);
+                    tasks.push(Task::perform(send_request(config), move |result| {
+                        Message::MonitorCompleted(request_id, result)
+                    }));
+                }
+                Task::batch(tasks)
+            }
+            Message::MonitorCompleted(request_id, result) => {
+                self.monitor_sends.remove(&request_id);
+                let Some(request) = find_request(&self.collections, &request_id) else {
+                    return Task::none();
+                };
+                if !request.monitor.enabled {
+                    return Task::none();
+                }
 
-                        if let Some(collection) = self
-                            .collections
-                            .get_mut(self.current_request.collection_index)
-                        {
-                            if let Some(request) = collection
-                                .requests
-                                .get_mut(self.current_request.request_index)
-                            {
-                                request.last_response = Some(error_response);
-                                let request_to_save = request.clone();
+                let response = match result {
+                    Ok(response) => response,
+                    Err(error) => Self::error_response(&error),
+                };
+                let status = monitor::check(&request.monitor, &request.assertions, &response);
 
-                                tokio::spawn(async move {
-                                    Self::save_request(request_to_save);
-                                });
-                            }
-                        }
-                    }
+                if monitor::started_failing(self.monitor_status.get(&request_id), &status)
+                    && let MonitorStatus::Failing(reason) = &status
+                {
+                    warn!("Monitor of '{}' started failing: {}", request.name, reason);
+                    tray::notify(&format!("{} is failing", request.name), reason);
+                    self.toasts
+                        .error(format!("{} is failing", request.name), reason.clone());
                 }
-
+                self.monitor_status.insert(request_id, status);
                 Task::none()
             }
-            Message::PostScriptCompleted(script_result) => {
-                info!("Post-request script completed: {:?}", script_result);
+            Message::QueuedRequestCompleted(id, result) => {
+                let Some(position) = self.offline_queue.iter().position(|q| q.id == id) else {
+                    return Task::none();
+                };
 
-                // Apply environment variable changes
-                if let Some(active_env_idx) = self.active_environment {
-                    if let Some(active_env) = self.environments.get_mut(active_env_idx) {
-                        for (key, value) in script_result.environment_changes {
-                            if let Some(var) = active_env.variables.get_mut(&key) {
-                                var.value = value;
-                            } else {
-                                active_env.add_variable(key, value);
-                            }
-                        }
-                    }
+                // Still offline, keep it queued for the next attempt
+                if let Err(error) = &result
+                    && error.is_network_unavailable()
+                {
+                    let queued = &mut self.offline_queue[position];
+                    queued.in_flight = false;
+                    queued.attempts += 1;
+                    queued.last_error = error.to_string();
+                    return Task::none();
                 }
 
-                // TODO: Display script execution results in UI
-                // For now, just log the results
-                for test_result in &script_result.test_results {
-                    info!(
-                        "Test '{}': {}",
-                        test_result.name,
-                        if test_result.passed {
-                            "PASSED"
-                        } else {
-                            "FAILED"
-                        }
-                    );
-                }
+                let queued = self.offline_queue.remove(position);
+                let response = match result {
+                    Ok(response) => response,
+                    Err(error) => Self::error_response(&error),
+                };
 
-                for console_msg in &script_result.console_output {
-                    info!("Console: {}", console_msg);
+                info!(
+                    "Queued request '{}' completed after {} attempt(s)",
+                    queued.request.name,
+                    queued.attempts + 1
+                );
+
+                // File the result into the request's response history
+                if let Some(request) = find_request_mut(&mut self.collections, &queued.request.id) {
+                    request.record_response(response.clone());
+                    let request_to_save = request.clone();
+                    let has_draft = self.drafts.contains(&request_to_save.id);
+
+                    if let Some(writer) = self.writer.clone() {
+                        tokio::spawn(async move {
+                            writer.save_run(request_to_save, has_draft);
+                        });
+                    }
+
+                    if self.current_request.id == queued.request.id {
+                        let formatted_body = Self::format_response_content(
+                            &response.body,
+                            self.current_request.body_format,
+                        );
+                        self.current_request.record_response(response);
+                        self.set_response_body(formatted_body);
+                    }
                 }
 
                 Task::none()
             }
-            Message::EnvironmentPanel(env_message) => {
-                match self.environment_panel.update(env_message) {
-                    environment::Action::AddEnvironment => {
-                        let new_env = Environment::new(format!(
-                            "Environment {}",
-                            self.environments.len() + 1
-                        ));
-                        self.environments.push(new_env);
-                        self.active_environment = Some(self.environments.len() - 1);
+            Message::CollectionPanel(view_message) => {
+                let in_flight: Vec<&str> = self.in_flight.keys().map(String::as_str).collect();
+                let pinned: Vec<&str> = self
+                    .layout
+                    .pinned_requests
+                    .iter()
+                    .map(String::as_str)
+                    .collect();
+                match self.collection_panel.update(
+                    view_message,
+                    &self.collections,
+                    &in_flight,
+                    &pinned,
+                ) {
+                    collections::Action::ToggleCollection(collection_id) => {
+                        if let Some(collection) =
+                            find_collection_mut(&mut self.collections, &collection_id)
+                        {
+                            collection.expanded = !collection.expanded;
 
-                        let environments = self.environments.clone();
-                        Task::perform(
-                            async move {
-                                match storage::StorageManager::with_default_config() {
+                            let col = collection.clone();
+
+                            let storage = self.storage();
+                            tokio::spawn(async move {
+                                match storage {
                                     Ok(storage_manager) => {
-                                        match storage_manager
-                                            .storage()
-                                            .save_environments(&environments)
-                                        {
+                                        match storage_manager.storage().save_collection(&col) {
                                             Ok(_) => Ok(()),
                                             Err(e) => Err(e.to_string()),
                                         }
                                     }
                                     Err(e) => Err(e.to_string()),
                                 }
-                            },
-                            Message::EnvironmentsSaved,
-                        )
+                            });
+                        }
+
+                        Task::none()
                     }
-                    environment::Action::DeleteEnvironment(index) => {
-                        if index < self.environments.len() && self.environments.len() > 1 {
-                            self.environments.remove(index);
-                            if let Some(active) = self.active_environment {
-                                if active == index {
-                                    self.active_environment = Some(0);
-                                } else if active > index {
-                                    self.active_environment = Some(active - 1);
-                                }
+                    collections::Action::SelectRequestConfig(request_id) => {
+                        let mut scroll = Task::none();
+                        if let Some(request_config) = find_request(&self.collections, &request_id) {
+                            if self.last_opened_request.as_deref() == Some(&request_config.id) {
+                                return Task::none();
                             }
 
-                            let environments = self.environments.clone();
-                            Task::perform(
-                                async move {
-                                    match storage::StorageManager::with_default_config() {
-                                        Ok(storage_manager) => {
-                                            match storage_manager
-                                                .storage()
-                                                .save_environments(&environments)
-                                            {
-                                                Ok(_) => Ok(()),
-                                                Err(e) => Err(e.to_string()),
-                                            }
-                                        }
-                                        Err(e) => Err(e.to_string()),
-                                    }
-                                },
+                            scroll = self
+                                .request_panel
+                                .switch_request(&self.current_request.id, &request_config.id)
+                                .map(Message::RequestPanel);
+                            self.current_request = request_config.clone();
+                            self.request_panel
+                                .reset_undo_histories(&self.current_request);
+
+                            Self::update_editor_content(
+                                &mut self.request_body_content,
+                                self.current_request.body.to_string(),
+                            );
+
+                            Self::update_editor_content(
+                                &mut self.post_script_content,
+                                self.current_request
+                                    .post_request_script
+                                    .as_deref()
+                                    .unwrap_or("")
+                                    .to_string(),
+                            );
+
+                            Self::update_editor_content(
+                                &mut self.assertion_schema_content,
+                                self.current_request.assertions.json_schema.clone(),
+                            );
+
+                            if let Some(resp) = &self.current_request.last_response {
+                                let formatted_resp = Self::format_response_content(
+                                    resp.body.as_str(),
+                                    self.current_request.body_format,
+                                );
+
+                                self.set_response_body(formatted_resp);
+                            }
+
+                            // Update the last opened request state and save to storage
+                            let request_id = self.current_request.id.clone();
+                            self.last_opened_request = Some(request_id.clone());
+                            let storage = self.storage();
+                            // Save the last opened request asynchronously without blocking the UI
+                            tokio::spawn(async move {
+                                if let Ok(storage_manager) = storage {
+                                    if let Err(e) = storage_manager
+                                        .storage()
+                                        .save_last_opened_request(&request_id)
+                                    {
+                                        error!("Failed to save last opened request: {}", e);
+                                    }
+                                }
+                            });
+                        }
+
+                        Task::batch([scroll, self.load_versions()])
+                    }
+                    collections::Action::CancelRequest(request_id) => {
+                        self.cancel_request(&request_id);
+                        Task::none()
+                    }
+                    collections::Action::SaveRequestToCollection(collection_id, request_config) => {
+                        let name = request_config.name.clone();
+                        self.add_request_to_collection(&collection_id, request_config);
+                        self.toasts.success(format!("Saved \"{}\"", name));
+                        Task::none()
+                    }
+                    collections::Action::SaveNewCollection(new_collection) => {
+                        self.collections.push(new_collection.clone());
+                        self.record_change(AuditEntry::new(
+                            AuditOperation::Create,
+                            AuditSubject::Collection,
+                            &new_collection.name,
+                        ));
+
+                        let storage = self.storage();
+                        tokio::spawn(async move {
+                            if let Ok(storage_manager) = storage {
+                                if let Err(e) =
+                                    storage_manager.storage().save_collection(&new_collection)
+                                {
+                                    report_background_error(
+                                        "Failed to save collection",
+                                        e.to_string(),
+                                    );
+                                }
+                            }
+                        });
+
+                        Task::none()
+                    }
+                    collections::Action::SendRequest(request_id, request_start_time) => {
+                        if let Some(request) = find_request(&self.collections, &request_id) {
+                            return self
+                                .send_with_token_refresh(request.clone(), request_start_time);
+                        }
+
+                        Task::none()
+                    }
+                    collections::Action::DuplicateRequest(request_id) => {
+                        let Some((collection_index, request_index)) =
+                            request_position(&self.collections, &request_id)
+                        else {
+                            return Task::none();
+                        };
+                        let collection = &mut self.collections[collection_index];
+                        let request = &collection.requests[request_index];
+                        let mut new_request = request.clone();
+
+                        new_request.id = new_id();
+                        new_request.name = format!("{} (Copy)", new_request.name);
+
+                        let mut path = PathBuf::new();
+                        let curr_request_path = PathBuf::from(&request.path);
+                        if let Some(parent) = curr_request_path.as_path().parent() {
+                            path.push(parent);
+                        }
+
+                        let mut max_number = 0;
+                        if let Some(last_request) = collection.requests.last() {
+                            if let Some(filename_str) =
+                                last_request.path.file_stem().and_then(|s| s.to_str())
+                            {
+                                if let Ok(number) = filename_str.parse::<u32>() {
+                                    max_number = number;
+                                }
+                            }
+                        }
+                        path.push(format!("{:04}.toml", max_number + 1));
+                        new_request.path = path.clone();
+                        let request_to_persist = new_request.clone();
+                        let change = AuditEntry::new(
+                            AuditOperation::Create,
+                            AuditSubject::Request,
+                            &new_request.name,
+                        )
+                        .in_collection(&collection.name);
+                        collection.requests.push(new_request);
+                        self.record_change(change);
+
+                        if let Some(writer) = self.writer.clone() {
+                            tokio::spawn(async move {
+                                writer.save_request(request_to_persist);
+                            });
+                        }
+
+                        Task::none()
+                    }
+                    collections::Action::DeleteRequest(request_id) => {
+                        let Some((collection_index, request_index)) =
+                            request_position(&self.collections, &request_id)
+                        else {
+                            return Task::none();
+                        };
+                        let collection = &mut self.collections[collection_index];
+                        let request = collection.requests.remove(request_index);
+                        let request_path = request.path.clone();
+                        let change = AuditEntry::new(
+                            AuditOperation::Delete,
+                            AuditSubject::Request,
+                            &request.name,
+                        )
+                        .in_collection(&collection.name);
+                        let collection_id = collection.id.clone();
+                        self.drafts.remove(&request.id);
+                        self.record_change(change);
+                        self.undo_stack.push(WorkspaceCommand::DeleteRequest {
+                            collection_id,
+                            position: request_index,
+                            request: Box::new(request),
+                        });
+
+                        let storage = self.storage();
+                        // Use the storage method to delete the file
+                        tokio::spawn(async move {
+                            if let Ok(storage_manager) = storage {
+                                let storage = storage_manager.storage();
+                                if let Err(e) = storage.delete_request_by_path(&request_path) {
+                                    error!("Failed to delete request file: {}", e);
+                                }
+                            }
+                        });
+
+                        Task::none()
+                    }
+                    collections::Action::RenameRequest(request_id) => {
+                        // Show the rename modal with the current request name
+                        if let Some(request) = find_request(&self.collections, &request_id) {
+                            self.show_rename_modal = true;
+                            self.rename_input = request.name.clone();
+                            self.rename_target = Some(RenameTarget::Request(request_id));
+                        }
+
+                        Task::none()
+                    }
+                    collections::Action::RenameCollection(collection_id) => {
+                        // Show the rename modal for the folder
+                        if let Some(collection) = find_collection(&self.collections, &collection_id)
+                        {
+                            self.show_rename_modal = true;
+                            self.rename_input = collection.name.clone();
+                            self.rename_target = Some(RenameTarget::Folder(collection_id));
+                        }
+
+                        Task::none()
+                    }
+                    collections::Action::SetCollectionAppearance {
+                        collection_id,
+                        color,
+                        icon,
+                    } => {
+                        if let Some(collection) =
+                            find_collection_mut(&mut self.collections, &collection_id)
+                        {
+                            collection.color = color;
+                            collection.icon = icon;
+                            save_collection_in_background(self.storage.clone(), collection.clone());
+                        }
+
+                        Task::none()
+                    }
+                    collections::Action::SetCollectionSort(collection_id, sort) => {
+                        if let Some(collection) =
+                            find_collection_mut(&mut self.collections, &collection_id)
+                        {
+                            collection.sort = sort;
+                            save_collection_in_background(self.storage.clone(), collection.clone());
+                        }
+
+                        Task::none()
+                    }
+                    collections::Action::ImportFromUrl => {
+                        self.show_import_modal = true;
+                        self.import_status = None;
+                        Task::none()
+                    }
+                    collections::Action::Focus(id) => operation::focus(id),
+                    collections::Action::OpenRemoteSync => {
+                        self.show_sync_modal = true;
+                        Task::none()
+                    }
+                    collections::Action::CopyRequestAsCurl(request_id, reveal_secrets) => {
+                        let Some(request) = find_request(&self.collections, &request_id) else {
+                            return Task::none();
+                        };
+
+                        let curl_command = if reveal_secrets {
+                            generate_curl_command(request)
+                        } else {
+                            redact::curl_command(request, &self.secrets_of(request))
+                        };
+                        self.toasts.success(if reveal_secrets {
+                            "Copied as cURL, secrets included"
+                        } else {
+                            "Copied as cURL, secrets masked"
+                        });
+                        iced::clipboard::write(curl_command)
+                    }
+                    collections::Action::ExportRequest(request_id) => {
+                        let Some(request) = find_request(&self.collections, &request_id) else {
+                            return Task::none();
+                        };
+
+                        match share::export(request, &self.secrets_of(request)).and_then(
+                            |content| {
+                                share::write_export(&share::export_dir(), &request.name, &content)
+                            },
+                        ) {
+                            Ok(path) => self
+                                .toasts
+                                .info(format!("Exported to {}, secrets masked", path.display())),
+                            Err(e) => self.toasts.error("Could not export the request", e),
+                        }
+                        Task::none()
+                    }
+                    collections::Action::CopyLink(request_id) => {
+                        let Some((collection_index, request_index)) =
+                            request_position(&self.collections, &request_id)
+                        else {
+                            return Task::none();
+                        };
+                        let collection = &self.collections[collection_index];
+                        let request = &collection.requests[request_index];
+
+                        self.toasts.success("Copied the link to the request");
+                        iced::clipboard::write(DeepLink::open_request(
+                            &collection.name,
+                            &request.name,
+                        ))
+                    }
+                    collections::Action::TogglePinned(request_id) => {
+                        let Some(request) = find_request(&self.collections, &request_id) else {
+                            return Task::none();
+                        };
+
+                        let pinned = &mut self.layout.pinned_requests;
+                        if let Some(position) = pinned.iter().position(|id| *id == request.id) {
+                            pinned.remove(position);
+                        } else {
+                            pinned.push(request.id.clone());
+                            if !self.layout.show_tray {
+                                self.toasts
+                                    .info("Pinned, turn on the tray icon in Settings to send it");
+                            }
+                        }
+                        self.sync_tray();
+                        Task::none()
+                    }
+                    collections::Action::RunCollection(collection_id) => {
+                        if let Some(collection) = find_collection(&self.collections, &collection_id)
+                        {
+                            let environment = self
+                                .active_environment
+                                .and_then(|index| self.environments.get(index))
+                                .map(|environment| environment.name.clone());
+                            self.runner = Some(RunnerDialog::new(collection, environment));
+                        }
+                        Task::none()
+                    }
+                    collections::Action::ExportCiBundle(collection_id) => {
+                        let Some(collection) = find_collection(&self.collections, &collection_id)
+                        else {
+                            return Task::none();
+                        };
+                        let environment = self
+                            .active_environment
+                            .and_then(|index| self.environments.get(index));
+
+                        match bundle::export(collection, environment).and_then(|content| {
+                            share::write_file(
+                                &share::export_dir(),
+                                &collection.name,
+                                bundle::EXTENSION,
+                                &content,
+                            )
+                        }) {
+                            Ok(path) => self.toasts.info(format!(
+                                "Exported the CI bundle to {}, secret variables are read from BEAM_* environment variables",
+                                path.display()
+                            )),
+                            Err(e) => self.toasts.error("Could not export the CI bundle", e),
+                        }
+                        Task::none()
+                    }
+                    collections::Action::ShowCollectionStats(collection_id) => {
+                        if let Some(collection) = find_collection(&self.collections, &collection_id)
+                        {
+                            self.collection_stats = Some(collection_stats(
+                                collection,
+                                chrono::Local::now().date_naive(),
+                            ));
+                        }
+                        Task::none()
+                    }
+                    collections::Action::DeleteCollection(collection_id) => {
+                        let Some(collection_index) = self
+                            .collections
+                            .iter()
+                            .position(|collection| collection.id == collection_id)
+                        else {
+                            return Task::none();
+                        };
+
+                        if let Some(collection) = self.collections.get(collection_index) {
+                            if let Ok(storage_manager) = self.storage() {
+                                storage_manager
+                                    .storage()
+                                    .delete_collection_by_folder_name(&collection.folder_name);
+                            }
+                            self.record_change(AuditEntry::new(
+                                AuditOperation::Delete,
+                                AuditSubject::Collection,
+                                &collection.name,
+                            ));
+                        }
+
+                        let collection = self.collections.remove(collection_index);
+                        self.show_welcome = self.collections.is_empty();
+                        self.undo_stack.push(WorkspaceCommand::DeleteCollection {
+                            position: collection_index,
+                            collection,
+                        });
+
+                        Task::none()
+                    }
+                    collections::Action::None => Task::none(),
+                }
+            }
+            Message::PaneResized(event) => {
+                // The collapsed sidebar keeps its width until it is expanded again
+                if event.split == self.sidebar_split && self.layout.sidebar_collapsed {
+                    return Task::none();
+                }
+
+                self.panes.resize(event.split, event.ratio);
+                if event.split == self.sidebar_split {
+                    self.layout.sidebar_ratio = event.ratio;
+                } else if event.split == self.request_split {
+                    self.layout.request_ratio = event.ratio;
+                }
+                Task::none()
+            }
+            Message::WindowResized(size) => {
+                self.layout.width = size.width;
+                self.layout.height = size.height;
+                if self.layout.sidebar_collapsed {
+                    self.panes
+                        .resize(self.sidebar_split, collapsed_sidebar_ratio(size.width));
+                }
+                Task::none()
+            }
+            Message::ToggleSidebar => {
+                self.layout.sidebar_collapsed = !self.layout.sidebar_collapsed;
+                let ratio = if self.layout.sidebar_collapsed {
+                    collapsed_sidebar_ratio(self.layout.width)
+                } else {
+                    self.layout.sidebar_ratio
+                };
+                self.panes.restore();
+                self.panes.resize(self.sidebar_split, ratio);
+                Task::none()
+            }
+            Message::ToggleResponsePosition => {
+                self.layout.response_position = match self.layout.response_position {
+                    ResponsePosition::Right => ResponsePosition::Bottom,
+                    ResponsePosition::Bottom => ResponsePosition::Right,
+                };
+
+                let (panes, sidebar_split, request_split) = build_panes(&self.layout);
+                self.panes = panes;
+                self.sidebar_split = sidebar_split;
+                self.request_split = request_split;
+                Task::none()
+            }
+            Message::ToggleMaximized(content) => {
+                let pane = self.panes.iter().find_map(|(pane, pane_content)| {
+                    (std::mem::discriminant(pane_content) == std::mem::discriminant(&content))
+                        .then_some(*pane)
+                });
+
+                if let Some(pane) = pane {
+                    if self.panes.maximized() == Some(pane) {
+                        self.panes.restore();
+                    } else {
+                        self.panes.maximize(pane);
+                    }
+                }
+                Task::none()
+            }
+            Message::WindowMoved(position) => {
+                self.layout.x = Some(position.x);
+                self.layout.y = Some(position.y);
+                Task::none()
+            }
+            Message::SaveWindowLayout => {
+                self.layout.request_tab = self.request_panel.selected_tab.clone();
+                // The open request's tab is only in the panel's map once another is opened
+                self.layout.request_tabs = self.request_panel.request_tabs.clone();
+                if !self.current_request.id.is_empty() {
+                    self.layout.request_tabs.insert(
+                        self.current_request.id.clone(),
+                        self.request_panel.selected_tab.clone(),
+                    );
+                }
+                self.layout.response_tab = self.response_panel.selected_tab;
+                if self.layout == self.saved_layout {
+                    return Task::none();
+                }
+
+                self.saved_layout = self.layout.clone();
+                let layout = self.layout.clone();
+                let storage = self.storage();
+                tokio::spawn(async move {
+                    match storage {
+                        Ok(storage_manager) => {
+                            if let Err(e) = storage_manager.storage().save_window_layout(&layout) {
+                                error!("Failed to save window layout: {}", e);
+                            }
+                        }
+                        Err(e) => error!("Failed to create storage manager: {}", e),
+                    }
+                });
+                Task::none()
+            }
+            Message::Toast(toast_message) => {
+                self.toasts.update(toast_message);
+                Task::none()
+            }
+            Message::PeriodicChecks => {
+                let mut tasks = vec![
+                    self.update(Message::ReloadThemes),
+                    self.update(Message::CheckEnvironmentsOnDisk),
+                    self.update(Message::SaveWindowLayout),
+                ];
+                // With toasts up they're expired on a timer of their own
+                if self.toasts.is_empty() {
+                    tasks.push(self.update(Message::ToastTick));
+                }
+                Task::batch(tasks)
+            }
+            Message::ToastTick => {
+                if let Ok(mut errors) = BACKGROUND_ERRORS.lock() {
+                    for (title, detail) in errors.drain(..) {
+                        self.toasts.error(title, detail);
+                    }
+                }
+                self.toasts.tick(Instant::now());
+                Task::none()
+            }
+            Message::RequestCompleted(request_id, result) => {
+                // Dropped when the send was cancelled or replaced by a newer one
+                let Some(sent) = self.in_flight.remove(&request_id) else {
+                    return Task::none();
+                };
+                if self
+                    .queue_offer
+                    .as_ref()
+                    .is_some_and(|request| request.id == request_id)
+                {
+                    self.queue_offer = None;
+                }
+                if self
+                    .download_limit_offer
+                    .as_ref()
+                    .is_some_and(|request| request.id == request_id)
+                {
+                    self.download_limit_offer = None;
+                }
+
+                let response = match &result {
+                    Ok(response) => {
+                        let mut response = response.clone();
+                        response.test_results =
+                            assertions::evaluate(&sent.config.assertions, &response);
+                        if let Some(api_contract) = &sent.config.contract {
+                            response
+                                .test_results
+                                .extend(contract::check(api_contract, &response));
+                        }
+                        self.report_failed_tests("assertions", &response.test_results);
+                        response
+                    }
+                    Err(error) => {
+                        if error.is_network_unavailable() {
+                            self.queue_offer = Some(sent.config.clone());
+                        } else if matches!(error, RequestError::ResponseTooLarge { .. }) {
+                            self.download_limit_offer = Some(sent.config.clone());
+                        }
+                        Self::error_response(error)
+                    }
+                };
+
+                if self.tray_sends.remove(&request_id) {
+                    let status = tray::summary(&response);
+                    tray::notify(&sent.config.name, &status);
+                    if let Some(tray) = &mut self.tray {
+                        tray.set_status(&format!("{}: {}", sent.config.name, status));
+                    }
+                }
+
+                if let Ok(response) = &result
+                    && sent.config.retry_after_rate_limit
+                    && sent.retries < rate_limit::MAX_RETRIES
+                    && let Some(delay) = rate_limit::retry_delay(response, chrono::Utc::now())
+                {
+                    info!(
+                        "{} was rate limited, retrying in {}s",
+                        request_id,
+                        delay.as_secs()
+                    );
+                    self.pending_retries.insert(
+                        request_id.clone(),
+                        PendingRetry {
+                            config: sent.config.clone(),
+                            due: Instant::now() + delay,
+                            attempt: sent.retries + 1,
+                        },
+                    );
+                }
+
+                match &result {
+                    Ok(response) if pagination::next_page(&sent.config, response).is_some() => {
+                        self.next_pages
+                            .insert(request_id.clone(), (sent.config.clone(), response.clone()));
+                    }
+                    _ => {
+                        self.next_pages.remove(&request_id);
+                    }
+                }
+
+                // File the response under the request that was sent, which may no longer
+                // be the one on screen
+                let mut request = match find_request_mut(&mut self.collections, &request_id) {
+                    Some(request) => {
+                        request.record_response(response.clone());
+                        let request_to_persist = request.clone();
+                        let updated = request.clone();
+                        let has_draft = self.drafts.contains(&request_id);
+
+                        if let Some(writer) = self.writer.clone() {
+                            tokio::spawn(async move {
+                                writer.save_run(request_to_persist, has_draft);
+                            });
+                        }
+                        Some(updated)
+                    }
+                    None => None,
+                };
+
+                if self.current_request.id == request_id {
+                    match request.take() {
+                        Some(updated) => self.current_request = updated,
+                        None => self.current_request.record_response(response.clone()),
+                    }
+
+                    let body = match &result {
+                        Ok(response) => Self::format_response_content(
+                            &response.body,
+                            self.current_request.body_format,
+                        ),
+                        Err(_) => response.body.clone(),
+                    };
+                    self.set_response_body(body);
+                    request = Some(self.current_request.clone());
+                }
+
+                // Execute post-request script if available
+                if let Ok(response) = result
+                    && let Some(request_config) = request
+                    && let Some(script) = request_config.post_request_script.clone()
+                    && !script.trim().is_empty()
+                {
+                    let active_env = self
+                        .active_environment
+                        .and_then(|idx| self.environments.get(idx))
+                        .cloned()
+                        .unwrap_or_else(|| Environment::new("Default".to_string()));
+
+                    return Task::perform(
+                        async move {
+                            crate::script::execute_post_request_script(
+                                &script,
+                                request_config,
+                                response,
+                                &active_env,
+                            )
+                        },
+                        move |script_result| {
+                            Message::PostScriptCompleted(request_id, script_result)
+                        },
+                    );
+                }
+
+                Task::none()
+            }
+            Message::PagesFetched(request_id, result) => {
+                match result {
+                    Ok(fetched) => {
+                        let mut summary =
+                            format!("{} items from {} pages", fetched.items, fetched.pages);
+                        if fetched.incomplete {
+                            summary
+                                .push_str(&format!(", stopped at {} pages", pagination::MAX_PAGES));
+                        }
+                        if self.current_request.id == request_id {
+                            self.set_response_body(fetched.body);
+                        }
+                        self.toasts.success(summary);
+                    }
+                    Err(e) => {
+                        error!("Failed to fetch the pages: {}", e);
+                        self.toasts.error("Could not fetch all pages", e);
+                    }
+                }
+
+                Task::none()
+            }
+            Message::TokenRefreshCompleted(request, request_start_time, result) => {
+                let refresh = &request.token_refresh;
+                let token = match result {
+                    Ok(response) if (200..300).contains(&response.status) => {
+                        token_refresh::extract_token(&response.body, &refresh.token_path)
+                    }
+                    Ok(response) => Err(format!(
+                        "The token request returned {} {}",
+                        response.status, response.status_text
+                    )),
+                    Err(e) => Err(e.to_string()),
+                };
+
+                // The request is sent either way, a failed refresh shows in its response too
+                let mut save_task = Task::none();
+                match token {
+                    Ok(token) => {
+                        if let Some(environment) = self
+                            .active_environment
+                            .and_then(|index| self.environments.get_mut(index))
+                        {
+                            let variable = refresh.variable.trim().to_string();
+                            match environment.variables.get_mut(&variable) {
+                                Some(existing) => existing.value = token,
+                                None => environment.add_variable(variable.clone(), token),
+                            }
+                            self.token_fetched_at.insert(
+                                (environment.name.clone(), variable),
+                                chrono::Utc::now().timestamp(),
+                            );
+
+                            let environments = self.environments.clone();
+                            let storage = self.storage();
+                            save_task = Task::perform(
+                                async move {
+                                    match storage {
+                                        Ok(storage_manager) => storage_manager
+                                            .storage()
+                                            .save_environments(&environments)
+                                            .map_err(|e| e.to_string()),
+                                        Err(e) => Err(e.to_string()),
+                                    }
+                                },
+                                Message::EnvironmentsSaved,
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        error!("Failed to refresh the token: {}", e);
+                        self.toasts.error("Failed to refresh the token", e);
+                    }
+                }
+
+                let resolved_config = self.resolve_request_config_variables(&request);
+                Task::batch([
+                    save_task,
+                    self.handle_send_request(resolved_config, request_start_time),
+                ])
+            }
+            Message::PostScriptCompleted(request_id, script_result) => {
+                info!(
+                    "Post-request script completed: {}, {} environment change(s), {} test(s)",
+                    if script_result.success {
+                        "succeeded"
+                    } else {
+                        "failed"
+                    },
+                    script_result.environment_changes.len(),
+                    script_result.test_results.len()
+                );
+
+                // Apply environment variable changes
+                if let Some(active_env_idx) = self.active_environment {
+                    if let Some(active_env) = self.environments.get_mut(active_env_idx) {
+                        for (key, value) in script_result.environment_changes {
+                            if let Some(var) = active_env.variables.get_mut(&key) {
+                                var.value = value;
+                            } else {
+                                active_env.add_variable(key, value);
+                            }
+                        }
+                    }
+                }
+
+                for test_result in &script_result.test_results {
+                    info!(
+                        "Test '{}': {}",
+                        test_result.name,
+                        if test_result.passed {
+                            "PASSED"
+                        } else {
+                            "FAILED"
+                        }
+                    );
+                }
+
+                for console_msg in &script_result.console_output {
+                    info!("Console: {}", console_msg);
+                }
+
+                if !script_result.success {
+                    self.toasts.error(
+                        "Post-request script failed",
+                        script_result.error_message.unwrap_or_default(),
+                    );
+                } else {
+                    self.report_failed_tests("tests", &script_result.test_results);
+                }
+
+                // Show the script's tests next to the assertions in the Tests tab
+                if !script_result.test_results.is_empty()
+                    && let Some(request) = find_request_mut(&mut self.collections, &request_id)
+                    && let Some(response) = request.last_response.as_mut()
+                {
+                    response
+                        .test_results
+                        .extend(script_result.test_results.iter().cloned());
+                    let request_to_persist = request.clone();
+
+                    if self.current_request.id == request_id {
+                        self.current_request.last_response = request.last_response.clone();
+                    }
+
+                    let has_draft = self.drafts.contains(&request_id);
+
+                    if let Some(writer) = self.writer.clone() {
+                        tokio::spawn(async move {
+                            writer.save_run(request_to_persist, has_draft);
+                        });
+                    }
+                }
+
+                Task::none()
+            }
+            Message::EnvironmentPanel(env_message) => {
+                match self.environment_panel.update(env_message) {
+                    environment::Action::AddEnvironment => {
+                        let new_env = Environment::new(format!(
+                            "Environment {}",
+                            self.environments.len() + 1
+                        ));
+                        self.record_change(AuditEntry::new(
+                            AuditOperation::Create,
+                            AuditSubject::Environment,
+                            &new_env.name,
+                        ));
+                        self.environments.push(new_env);
+                        self.active_environment = Some(self.environments.len() - 1);
+
+                        let environments = self.environments.clone();
+                        let storage = self.storage();
+                        Task::perform(
+                            async move {
+                                match storage {
+                                    Ok(storage_manager) => {
+                                        match storage_manager
+                                            .storage()
+                                            .save_environments(&environments)
+                                        {
+                                            Ok(_) => Ok(()),
+                                            Err(e) => Err(e.to_string()),
+                                        }
+                                    }
+                                    Err(e) => Err(e.to_string()),
+                                }
+                            },
+                            Message::EnvironmentsSaved,
+                        )
+                    }
+                    environment::Action::DeleteEnvironment(index) => {
+                        if index < self.environments.len() && self.environments.len() > 1 {
+                            // Renames are keyed by position, which the removal shifts
+                            self.record_environment_renames();
+                            let removed = self.environments.remove(index);
+                            self.record_change(AuditEntry::new(
+                                AuditOperation::Delete,
+                                AuditSubject::Environment,
+                                &removed.name,
+                            ));
+                            if let Some(active) = self.active_environment {
+                                if active == index {
+                                    self.active_environment = Some(0);
+                                } else if active > index {
+                                    self.active_environment = Some(active - 1);
+                                }
+                            }
+
+                            let environments = self.environments.clone();
+                            let storage = self.storage();
+                            Task::perform(
+                                async move {
+                                    match storage {
+                                        Ok(storage_manager) => {
+                                            match storage_manager
+                                                .storage()
+                                                .save_environments(&environments)
+                                            {
+                                                Ok(_) => Ok(()),
+                                                Err(e) => Err(e.to_string()),
+                                            }
+                                        }
+                                        Err(e) => Err(e.to_string()),
+                                    }
+                                },
                                 Message::EnvironmentsSaved,
                             )
                         } else {
@@ -930,12 +2506,16 @@ impl BeamApp {
                     }
                     environment::Action::EnvironmentNameChanged(env_index, name) => {
                         if let Some(env) = self.environments.get_mut(env_index) {
+                            self.renamed_environments
+                                .entry(env_index)
+                                .or_insert_with(|| env.name.clone());
                             env.name = name;
 
                             let environments = self.environments.clone();
+                            let storage = self.storage();
                             Task::perform(
                                 async move {
-                                    match storage::StorageManager::with_default_config() {
+                                    match storage {
                                         Ok(storage_manager) => {
                                             match storage_manager
                                                 .storage()
@@ -963,9 +2543,10 @@ impl BeamApp {
                             };
 
                             let environments = self.environments.clone();
+                            let storage = self.storage();
                             Task::perform(
                                 async move {
-                                    match storage::StorageManager::with_default_config() {
+                                    match storage {
                                         Ok(storage_manager) => {
                                             match storage_manager
                                                 .storage()
@@ -984,6 +2565,28 @@ impl BeamApp {
                             Task::none()
                         }
                     }
+                    environment::Action::EnvironmentAuthChanged(env_index, auth) => {
+                        if let Some(env) = self.environments.get_mut(env_index) {
+                            env.auth = auth;
+
+                            let environments = self.environments.clone();
+                            let storage = self.storage();
+                            Task::perform(
+                                async move {
+                                    match storage {
+                                        Ok(storage_manager) => storage_manager
+                                            .storage()
+                                            .save_environments(&environments)
+                                            .map_err(|e| e.to_string()),
+                                        Err(e) => Err(e.to_string()),
+                                    }
+                                },
+                                Message::EnvironmentsSaved,
+                            )
+                        } else {
+                            Task::none()
+                        }
+                    }
                     environment::Action::VariableKeyChanged(env_index, old_key, new_key) => {
                         if let Some(env) = self.environments.get_mut(env_index) {
                             if let Some(value) = env.variables.remove(&old_key) {
@@ -991,9 +2594,10 @@ impl BeamApp {
                             }
 
                             let environments = self.environments.clone();
+                            let storage = self.storage();
                             Task::perform(
                                 async move {
-                                    match storage::StorageManager::with_default_config() {
+                                    match storage {
                                         Ok(storage_manager) => {
                                             match storage_manager
                                                 .storage()
@@ -1019,9 +2623,10 @@ impl BeamApp {
                             }
 
                             let environments = self.environments.clone();
+                            let storage = self.storage();
                             Task::perform(
                                 async move {
-                                    match storage::StorageManager::with_default_config() {
+                                    match storage {
                                         Ok(storage_manager) => {
                                             match storage_manager
                                                 .storage()
@@ -1046,9 +2651,10 @@ impl BeamApp {
                             env.add_variable(format!("variable_{}", var_count + 1), String::new());
 
                             let environments = self.environments.clone();
+                            let storage = self.storage();
                             Task::perform(
                                 async move {
-                                    match storage::StorageManager::with_default_config() {
+                                    match storage {
                                         Ok(storage_manager) => {
                                             match storage_manager
                                                 .storage()
@@ -1069,12 +2675,19 @@ impl BeamApp {
                     }
                     environment::Action::RemoveVariable(env_index, key) => {
                         if let Some(env) = self.environments.get_mut(env_index) {
-                            env.variables.remove(&key);
+                            if let Some(variable) = env.variables.remove(&key) {
+                                self.undo_stack.push(WorkspaceCommand::DeleteVariable {
+                                    environment: env.name.clone(),
+                                    key,
+                                    variable,
+                                });
+                            }
 
                             let environments = self.environments.clone();
+                            let storage = self.storage();
                             Task::perform(
                                 async move {
-                                    match storage::StorageManager::with_default_config() {
+                                    match storage {
                                         Ok(storage_manager) => {
                                             match storage_manager
                                                 .storage()
@@ -1100,9 +2713,39 @@ impl BeamApp {
                             }
 
                             let environments = self.environments.clone();
+                            let storage = self.storage();
+                            Task::perform(
+                                async move {
+                                    match storage {
+                                        Ok(storage_manager) => {
+                                            match storage_manager
+                                                .storage()
+                                                .save_environments(&environments)
+                                            {
+                                                Ok(_) => Ok(()),
+                                                Err(e) => Err(e.to_string()),
+                                            }
+                                        }
+                                        Err(e) => Err(e.to_string()),
+                                    }
+                                },
+                                Message::EnvironmentsSaved,
+                            )
+                        } else {
+                            Task::none()
+                        }
+                    }
+                    environment::Action::ToggleSecret(env_index, key) => {
+                        if let Some(env) = self.environments.get_mut(env_index) {
+                            if let Some(var) = env.variables.get_mut(&key) {
+                                var.secret = !var.secret;
+                            }
+
+                            let environments = self.environments.clone();
+                            let storage = self.storage();
                             Task::perform(
                                 async move {
-                                    match storage::StorageManager::with_default_config() {
+                                    match storage {
                                         Ok(storage_manager) => {
                                             match storage_manager
                                                 .storage()
@@ -1121,8 +2764,30 @@ impl BeamApp {
                             Task::none()
                         }
                     }
+                    environment::Action::VariableTypeChanged(env_index, key, value_type) => {
+                        if let Some(var) = self
+                            .environments
+                            .get_mut(env_index)
+                            .and_then(|env| env.variables.get_mut(&key))
+                        {
+                            var.value_type = value_type;
+                        }
+                        let environments = self.environments.clone();
+                        let storage = self.storage();
+                        Task::perform(
+                            async move {
+                                storage
+                                    .and_then(|storage_manager| {
+                                        storage_manager.storage().save_environments(&environments)
+                                    })
+                                    .map_err(|e| e.to_string())
+                            },
+                            Message::EnvironmentsSaved,
+                        )
+                    }
                     environment::Action::ClosePopup => {
                         self.show_environment_popup = false;
+                        self.record_environment_renames();
                         Task::none()
                     }
                     environment::Action::EnvironmentSelected(index) => {
@@ -1136,65 +2801,234 @@ impl BeamApp {
                 iced::keyboard::Key::Named(iced::keyboard::key::Named::Escape) => {
                     if self.show_environment_popup {
                         self.show_environment_popup = false;
+                        self.record_environment_renames();
+                        Task::none()
+                    } else if self.pending_oversized_send.is_some() {
+                        self.pending_oversized_send = None;
+                        Task::none()
+                    } else if !self.save_conflicts.is_empty() {
+                        self.save_conflicts.remove(0);
+                        Task::none()
+                    } else if self.crash_recovery.is_some() {
+                        self.crash_recovery = None;
+                        Task::none()
+                    } else if !self.pending_drops.is_empty() {
+                        self.update(Message::CancelDroppedImport)
+                    } else if self.collection_panel.new_request.is_some() {
+                        self.update(Message::CollectionPanel(
+                            collections::Message::CancelNewRequest,
+                        ))
+                    } else if self.show_sync_modal {
+                        self.show_sync_modal = false;
+                        Task::none()
+                    } else if self.show_tools_modal {
+                        self.show_tools_modal = false;
+                        Task::none()
+                    } else if self.show_webhook_modal {
+                        self.show_webhook_modal = false;
+                        Task::none()
+                    } else if self.collection_stats.is_some() {
+                        self.collection_stats = None;
                         Task::none()
+                    } else if self.usage_stats.is_some() {
+                        self.usage_stats = None;
+                        Task::none()
+                    } else if self.runner.is_some() {
+                        self.runner = None;
+                        Task::none()
+                    } else if self.show_settings_modal {
+                        self.show_settings_modal = false;
+                        Task::none()
+                    } else if self.audit_entries.is_some() {
+                        self.audit_entries = None;
+                        Task::none()
+                    } else if self.connectivity.is_some() {
+                        self.connectivity = None;
+                        Task::none()
+                    } else if self.show_import_modal {
+                        self.update(Message::HideImportModal)
                     } else if self.show_rename_modal {
                         self.show_rename_modal = false;
                         self.rename_input.clear();
                         self.rename_target = None;
                         Task::none()
+                    } else if self.collection_panel.has_keyboard_focus() {
+                        self.update(Message::CollectionPanel(collections::Message::Navigate(
+                            collections::Navigation::Back,
+                        )))
                     } else if self.request_panel.show_search {
                         self.update(Message::RequestPanel(request::Message::CloseSearch))
                     } else if self.response_panel.show_search {
                         self.update(Message::ResponsePanel(response::Message::CloseSearch))
+                    } else if self.panes.maximized().is_some() {
+                        self.panes.restore();
+                        Task::none()
                     } else {
                         Task::none()
                     }
                 }
+                iced::keyboard::Key::Character(c) if self.modifiers.command() => {
+                    match c.to_lowercase().as_str() {
+                        "b" if !self.modifiers.shift() => self.update(Message::ToggleSidebar),
+                        "e" if self.modifiers.shift() => {
+                            self.update(Message::ToggleMaximized(PaneContent::RequestConfig))
+                        }
+                        "r" if self.modifiers.shift() => {
+                            self.update(Message::ToggleMaximized(PaneContent::Response))
+                        }
+                        "t" if self.modifiers.shift() => self.update(Message::ToggleTools),
+                        // Reached only when no input took the key for its own text undo
+                        "z" if !self.modifiers.shift() => self.undo(),
+                        "s" if !self.modifiers.shift() => self.update(Message::SaveCurrentRequest),
+                        _ => Task::none(),
+                    }
+                }
+                // Tab and Shift+Tab go through the inputs, leaving the sidebar
+                iced::keyboard::Key::Named(iced::keyboard::key::Named::Tab) => {
+                    let blur =
+                        self.update(Message::CollectionPanel(collections::Message::BlurSidebar));
+                    if self.modifiers.shift() {
+                        blur.chain(operation::focus_previous())
+                    } else {
+                        blur.chain(operation::focus_next())
+                    }
+                }
+                // F6 moves the keyboard between the sidebar and the request
+                iced::keyboard::Key::Named(iced::keyboard::key::Named::F6) => {
+                    if self.collection_panel.has_keyboard_focus() {
+                        self.update(Message::CollectionPanel(collections::Message::BlurSidebar))
+                            .chain(operation::focus_next())
+                    } else {
+                        let show_sidebar = if self.layout.sidebar_collapsed {
+                            self.update(Message::ToggleSidebar)
+                        } else {
+                            Task::none()
+                        };
+                        // An input keeping focus would swallow the arrow keys
+                        let focus_sidebar = self
+                            .update(Message::CollectionPanel(collections::Message::FocusSidebar))
+                            .chain(iced::advanced::widget::operate(
+                                iced::advanced::widget::operation::focusable::unfocus(),
+                            ));
+                        Task::batch([show_sidebar, focus_sidebar])
+                    }
+                }
+                iced::keyboard::Key::Named(named) if self.collection_panel.has_keyboard_focus() => {
+                    use iced::keyboard::key::Named;
+
+                    let navigation = match named {
+                        Named::ArrowUp => collections::Navigation::Up,
+                        Named::ArrowDown => collections::Navigation::Down,
+                        Named::ArrowLeft => collections::Navigation::Collapse,
+                        Named::ArrowRight => collections::Navigation::Expand,
+                        Named::Enter | Named::Space => collections::Navigation::Activate,
+                        Named::ContextMenu => collections::Navigation::OpenMenu,
+                        Named::F10 if self.modifiers.shift() => collections::Navigation::OpenMenu,
+                        _ => return Task::none(),
+                    };
+                    self.update(Message::CollectionPanel(collections::Message::Navigate(
+                        navigation,
+                    )))
+                }
                 _ => Task::none(),
             },
             Message::TimerTick => {
-                if let Some(start_time) = self.request_start_time {
-                    self.current_elapsed_time = start_time.elapsed().as_millis() as u64;
+                if let Some(sent) = self.in_flight.get(&self.current_request.id) {
+                    self.current_elapsed_time = sent.started_at.elapsed().as_millis() as u64;
                 }
+                self.response_panel.follow_stream(
+                    self.in_flight
+                        .get(&self.current_request.id)
+                        .map(|sent| &sent.streamed),
+                );
 
-                if self.is_loading {
+                if self.is_loading() {
                     self.response_panel.update_spinner();
                 }
 
-                Task::none()
+                let now = Instant::now();
+                let due: Vec<String> = self
+                    .pending_retries
+                    .iter()
+                    .filter(|(_, retry)| retry.due <= now)
+                    .map(|(id, _)| id.clone())
+                    .collect();
+                let mut retries = Vec::new();
+                for request_id in due {
+                    if let Some(retry) = self.pending_retries.remove(&request_id) {
+                        retries.push(self.dispatch_request(retry.config, now));
+                        if let Some(sent) = self.in_flight.get_mut(&request_id) {
+                            sent.retries = retry.attempt;
+                        }
+                    }
+                }
+                // A run waiting out its delay sends its next request once the delay is up
+                if self.is_run_waiting() {
+                    retries.push(self.start_run_requests());
+                }
+
+                Task::batch(retries)
             }
             // Storage operations
             Message::LoadConfigFiles => {
                 // Initialize the debouncer for request update
-                self.initialize_debouncer();
+                let conflicts = self.initialize_debouncer();
 
                 Task::batch([
+                    conflicts,
                     Task::perform(async { Message::LoadCollections }, |msg| msg),
                     Task::perform(async { Message::LoadEnvironments }, |msg| msg),
+                    Task::perform(async { Message::ReloadThemes }, |msg| msg),
                 ])
             }
-            Message::LoadCollections => Task::perform(
-                async {
-                    match storage::StorageManager::with_default_config() {
-                        Ok(storage_manager) => match storage_manager.storage().load_collections() {
-                            Ok(collections) => Ok(collections),
-                            Err(e) => Err(e.to_string()),
-                        },
-                        Err(e) => Err(e.to_string()),
-                    }
-                },
-                Message::CollectionsLoaded,
-            ),
+            Message::LoadCollections => {
+                let storage = self.storage();
+                Task::perform(
+                    async move {
+                        match storage {
+                            Ok(storage_manager) => {
+                                match storage_manager.storage().load_collections() {
+                                    Ok(mut collections) => {
+                                        let drafts =
+                                            apply_drafts(&storage_manager, &mut collections);
+                                        Ok((collections, drafts))
+                                    }
+                                    Err(e) => Err(e.to_string()),
+                                }
+                            }
+                            Err(e) => Err(e.to_string()),
+                        }
+                    },
+                    Message::CollectionsLoaded,
+                )
+            }
             Message::CollectionsLoaded(result) => {
                 match result {
-                    Ok(collections) => {
-                        if !collections.is_empty() {
+                    Ok((collections, drafts)) => {
+                        self.show_welcome = collections.is_empty();
+                        let loaded = !collections.is_empty();
+                        if loaded {
                             self.collections = collections;
+                            self.drafts = drafts;
+                        }
+                        self.sync_tray();
 
+                        // Offered now that the request it belongs to can be found
+                        if let Some(recovery) = self.pending_recovery.take() {
+                            self.crash_recovery = Some(recovery);
+                        }
+
+                        // A link goes to its request instead of the last opened one
+                        if let Some(link) = self.pending_link.take() {
+                            return self.open_link(&link);
+                        }
+
+                        if loaded {
+                            let storage = self.storage();
                             // Load lsast opened request after collections are loaded
                             return Task::perform(
-                                async {
-                                    match storage::StorageManager::with_default_config() {
+                                async move {
+                                    match storage {
                                         Ok(storage_manager) => {
                                             match storage_manager
                                                 .storage()
@@ -1219,6 +3053,73 @@ impl BeamApp {
                     }
                 }
             }
+            Message::CollectionRenamed {
+                collection_id,
+                new_name,
+                result,
+                undoable,
+            } => {
+                let directory = match result {
+                    Ok(directory) => directory,
+                    Err(e) => {
+                        self.toasts.error("Failed to rename collection", e);
+                        return Task::none();
+                    }
+                };
+
+                let mut change = None;
+                if let Some(collection) = self
+                    .collections
+                    .iter_mut()
+                    .find(|collection| collection.id == collection_id)
+                {
+                    change = Some(
+                        AuditEntry::new(
+                            AuditOperation::Rename,
+                            AuditSubject::Collection,
+                            &new_name,
+                        )
+                        .renamed_from(&collection.name),
+                    );
+                    if undoable {
+                        self.undo_stack.push(WorkspaceCommand::RenameCollection {
+                            collection_id: collection_id.clone(),
+                            from: collection.name.clone(),
+                            to: new_name.clone(),
+                        });
+                    }
+                    collection.name = new_name;
+                    if let Some(folder_name) = directory.file_name() {
+                        collection.folder_name = folder_name.to_string_lossy().into_owned();
+                    }
+                    for request in &mut collection.requests {
+                        if let Some(file_name) = request.path.file_name() {
+                            request.path = directory.join(file_name);
+                        }
+                    }
+                }
+
+                if let Some(change) = change {
+                    self.record_change(change);
+                }
+
+                // Keep the open request pointing at the same file
+                if let Some(request) =
+                    find_request_mut(&mut self.collections, &self.current_request.id)
+                {
+                    self.current_request.path = request.path.clone();
+                }
+
+                Task::none()
+            }
+            Message::CollectionRestored(result) => match result {
+                // Read back so the requests point at the files they were written to
+                Ok(()) => self.update(Message::LoadCollections),
+                Err(e) => {
+                    self.toasts.error("Failed to restore collection", e);
+                    Task::none()
+                }
+            },
             Message::CollectionsSaved(result) => {
                 match result {
                     Ok(_) => {
@@ -1226,38 +3127,43 @@ impl BeamApp {
                     }
                     Err(e) => {
                         error!("Failed to save collection: {}", e);
+                        self.toasts.error("Failed to save collection", e);
                     }
                 }
                 Task::none()
             }
-            Message::LoadEnvironments => Task::perform(
-                async {
-                    match storage::StorageManager::with_default_config() {
-                        Ok(storage_manager) => {
-                            match storage_manager.storage().load_environments() {
-                                Ok(persistent_envs) => persistent_envs,
-                                Err(e) => {
-                                    error!("Failed to load environments: {}", e);
-                                    crate::storage::PersistentEnvironments {
-                                        environments: Vec::new(),
-                                        active_environment: None,
-                                        metadata: crate::storage::EnvironmentsMetadata::default(),
+            Message::LoadEnvironments => {
+                let storage = self.storage();
+                Task::perform(
+                    async move {
+                        match storage {
+                            Ok(storage_manager) => {
+                                match storage_manager.storage().load_environments() {
+                                    Ok(persistent_envs) => persistent_envs,
+                                    Err(e) => {
+                                        error!("Failed to load environments: {}", e);
+                                        crate::storage::PersistentEnvironments {
+                                            environments: Vec::new(),
+                                            active_environment: None,
+                                            metadata: crate::storage::EnvironmentsMetadata::default(
+                                            ),
+                                        }
                                     }
                                 }
                             }
-                        }
-                        Err(e) => {
-                            error!("Failed to create storage manager: {}", e);
-                            crate::storage::PersistentEnvironments {
-                                environments: Vec::new(),
-                                active_environment: None,
-                                metadata: crate::storage::EnvironmentsMetadata::default(),
+                            Err(e) => {
+                                error!("Failed to create storage manager: {}", e);
+                                crate::storage::PersistentEnvironments {
+                                    environments: Vec::new(),
+                                    active_environment: None,
+                                    metadata: crate::storage::EnvironmentsMetadata::default(),
+                                }
                             }
                         }
-                    }
-                },
-                Message::EnvironmentsLoadedComplete,
-            ),
+                    },
+                    Message::EnvironmentsLoadedComplete,
+                )
+            }
             Message::EnvironmentsLoadedComplete(persistent_envs) => {
                 // Update environments
                 self.environments = persistent_envs.environments;
@@ -1275,20 +3181,67 @@ impl BeamApp {
 
                 Task::none()
             }
-            Message::LoadActiveEnvironment => Task::perform(
-                async {
-                    match storage::StorageManager::with_default_config() {
-                        Ok(storage_manager) => {
-                            match storage_manager.storage().load_active_environment() {
-                                Ok(active_env) => Ok(active_env),
-                                Err(e) => Err(e.to_string()),
+            Message::CheckEnvironmentsOnDisk => {
+                // Edits in the environment popup win, the file is checked again once it closes
+                if self.show_environment_popup {
+                    return Task::none();
+                }
+                let storage = self.storage();
+                Task::perform(
+                    async move {
+                        storage
+                            .and_then(|storage_manager| {
+                                storage_manager.storage().reload_environments()
+                            })
+                            .map_err(|e| e.to_string())
+                            .transpose()
+                    },
+                    |result| match result {
+                        Some(result) => Message::EnvironmentsChangedOnDisk(result),
+                        None => Message::DoNothing,
+                    },
+                )
+            }
+            Message::EnvironmentsChangedOnDisk(Ok(persistent_envs)) => {
+                let active_name = self
+                    .active_environment
+                    .and_then(|index| self.environments.get(index))
+                    .map(|env| env.name.clone())
+                    .or(persistent_envs.active_environment);
+                self.environments = persistent_envs.environments;
+                self.active_environment = active_name
+                    .and_then(|name| self.environments.iter().position(|env| env.name == name));
+                self.environments_reload_error = None;
+                self.toasts
+                    .success("Reloaded the environments changed on disk");
+                Task::none()
+            }
+            Message::EnvironmentsChangedOnDisk(Err(e)) => {
+                if self.environments_reload_error.as_ref() != Some(&e) {
+                    error!("Failed to reload environments: {}", e);
+                    self.toasts
+                        .error("Could not reload the environments", e.clone());
+                    self.environments_reload_error = Some(e);
+                }
+                Task::none()
+            }
+            Message::LoadActiveEnvironment => {
+                let storage = self.storage();
+                Task::perform(
+                    async move {
+                        match storage {
+                            Ok(storage_manager) => {
+                                match storage_manager.storage().load_active_environment() {
+                                    Ok(active_env) => Ok(active_env),
+                                    Err(e) => Err(e.to_string()),
+                                }
                             }
+                            Err(e) => Err(e.to_string()),
                         }
-                        Err(e) => Err(e.to_string()),
-                    }
-                },
-                Message::ActiveEnvironmentLoaded,
-            ),
+                    },
+                    Message::ActiveEnvironmentLoaded,
+                )
+            }
             Message::ActiveEnvironmentLoaded(result) => {
                 match result {
                     Ok(Some(active_env_name)) => {
@@ -1320,9 +3273,10 @@ impl BeamApp {
                 let collections = self.collections.clone();
                 let environments = self.environments.clone();
 
+                let storage = self.storage();
                 Task::perform(
                     async move {
-                        match storage::StorageManager::with_default_config() {
+                        match storage {
                             Ok(storage_manager) => {
                                 let storage = storage_manager.storage();
 
@@ -1371,9 +3325,10 @@ impl BeamApp {
             }
             Message::SaveEnvironments => {
                 let environments = self.environments.clone();
+                let storage = self.storage();
                 Task::perform(
                     async move {
-                        match storage::StorageManager::with_default_config() {
+                        match storage {
                             Ok(storage_manager) => {
                                 match storage_manager.storage().save_environments(&environments) {
                                     Ok(_) => Ok(()),
@@ -1393,19 +3348,21 @@ impl BeamApp {
                     }
                     Err(e) => {
                         error!("Failed to save environments: {}", e);
+                        self.toasts.error("Failed to save environments", e);
                     }
                 }
                 Task::none()
             }
-            Message::UpdateLastOpenedRequest(collection_index, request_index) => {
+            Message::UpdateLastOpenedRequest(request_id) => {
                 // Update the last opened request state and save to storage
-                self.last_opened_request = Some((collection_index, request_index));
+                self.last_opened_request = Some(request_id.clone());
+                let storage = self.storage();
                 // Save the last opened request asynchronously without blocking the UI
                 tokio::spawn(async move {
-                    if let Ok(storage_manager) = storage::StorageManager::with_default_config() {
+                    if let Ok(storage_manager) = storage {
                         if let Err(e) = storage_manager
                             .storage()
-                            .save_last_opened_request(collection_index, request_index)
+                            .save_last_opened_request(&request_id)
                         {
                             error!("Failed to save last opened request: {}", e);
                         }
@@ -1416,18 +3373,24 @@ impl BeamApp {
             }
             Message::LoadLastOpenedRequest(result) => {
                 match result {
-                    Ok(Some((collection_index, request_index))) => {
-                        if let Some(collection) = self.collections.get_mut(collection_index) {
+                    Ok(Some(request_id)) => {
+                        let mut scroll = Task::none();
+                        let position = request_position(&self.collections, &request_id);
+
+                        if let Some((collection_index, request_index)) = position {
+                            let collection = &mut self.collections[collection_index];
                             collection.expanded = true;
 
                             if let Some(request_config) = collection.requests.get(request_index) {
-                                self.last_opened_request = Some((collection_index, request_index));
+                                self.last_opened_request = Some(request_id);
 
+                                scroll = self
+                                    .request_panel
+                                    .switch_request(&self.current_request.id, &request_config.id)
+                                    .map(Message::RequestPanel);
                                 self.current_request = request_config.clone();
-                                self.request_panel.reset_undo_histories(
-                                    &self.current_request.url,
-                                    &self.current_request.body,
-                                );
+                                self.request_panel
+                                    .reset_undo_histories(&self.current_request);
 
                                 Self::update_editor_content(
                                     &mut self.request_body_content,
@@ -1443,6 +3406,11 @@ impl BeamApp {
                                         .to_string(),
                                 );
 
+                                Self::update_editor_content(
+                                    &mut self.assertion_schema_content,
+                                    self.current_request.assertions.json_schema.clone(),
+                                );
+
                                 if let Some(resp) = &self.current_request.last_response {
                                     // TODO: move the response body content update in a new message
                                     // so it doesn't block the UI loading
@@ -1451,17 +3419,14 @@ impl BeamApp {
                                         self.current_request.body_format,
                                     );
 
-                                    Self::update_editor_content(
-                                        &mut self.response_body_content,
-                                        formatted_resp.to_string(),
-                                    );
+                                    self.set_response_body(formatted_resp.to_string());
                                 }
                             }
                         } else {
                             error!("===no collections");
                         }
 
-                        Task::none()
+                        Task::batch([scroll, self.load_versions()])
                     }
                     Ok(None) => {
                         // No last opened request found
@@ -1517,15 +3482,9 @@ impl BeamApp {
             //     Task::none()
             // }
             // Auto-save message handlers
-            Message::SaveRequestDebounced {
-                collection_index,
-                request_index,
-            } => {
-                info!(
-                    "=== SaveRequestDebounced - collection_index: {}, request_index: {}",
-                    collection_index, request_index
-                );
-                let _key = (collection_index, request_index);
+            Message::SaveRequestDebounced { request_id } => {
+                info!("=== SaveRequestDebounced - request_id: {}", request_id);
+                let _key = request_id;
 
                 // Check if this save is still valid (no newer changes)
                 // if let Some(last_change_time) = self.debounce_timers.get(&key) {
@@ -1588,17 +3547,559 @@ impl BeamApp {
                 // }
                 Task::none()
             }
-            Message::RequestSaved(result) => {
-                match result {
-                    Ok(_) => {
-                        info!("Request auto-saved successfully");
+            Message::RequestSaved(result) => {
+                match result {
+                    Ok(_) => {
+                        info!("Request auto-saved successfully");
+                    }
+                    Err(e) => {
+                        error!("Failed to auto-save request: {}", e);
+                    }
+                }
+                Task::none()
+            }
+            Message::HideImportModal => {
+                self.show_import_modal = false;
+                self.import_status = None;
+                Task::none()
+            }
+            Message::ImportUrlChanged(url) => {
+                self.import_url = url;
+                Task::none()
+            }
+            Message::ConfirmImport => {
+                let url = self.import_url.trim().to_string();
+                if url.is_empty() || self.is_importing {
+                    return Task::none();
+                }
+
+                self.is_importing = true;
+                self.import_status = None;
+                Task::perform(import::fetch(url), Message::ImportFetched)
+            }
+            Message::ImportFetched(result) => {
+                self.is_importing = false;
+
+                match result {
+                    Ok(Imported::Collection(mut collection)) => {
+                        let base_name = collection.name.clone();
+                        let mut counter = 2;
+                        while self.collections.iter().any(|c| c.name == collection.name) {
+                            collection.name = format!("{} ({})", base_name, counter);
+                            counter += 1;
+                        }
+
+                        self.set_import_status(Ok(format!(
+                            "Imported {} requests into \"{}\"",
+                            collection.requests.len(),
+                            collection.name
+                        )));
+                        self.record_change(AuditEntry::new(
+                            AuditOperation::Create,
+                            AuditSubject::Collection,
+                            &collection.name,
+                        ));
+                        self.import_url.clear();
+
+                        let storage = self.storage();
+                        Task::perform(
+                            async move {
+                                match storage {
+                                    Ok(storage_manager) => storage_manager
+                                        .storage()
+                                        .save_collection_with_requests(&collection)
+                                        .map_err(|e| e.to_string()),
+                                    Err(e) => Err(e.to_string()),
+                                }
+                            },
+                            |result| match result {
+                                // Reload so the imported requests pick up their file paths
+                                Ok(()) => Message::LoadCollections,
+                                Err(e) => Message::CollectionsSaved(Err(e)),
+                            },
+                        )
+                    }
+                    Ok(Imported::Request(request)) => {
+                        if self.collections.is_empty() {
+                            self.set_import_status(Err(
+                                "Create a collection before importing requests".into(),
+                            ));
+                            return Task::none();
+                        }
+
+                        // Into the collection of the open request
+                        let collection_index =
+                            request_position(&self.collections, &self.current_request.id)
+                                .map_or(0, |(collection_index, _)| collection_index);
+                        let collection_id = self.collections[collection_index].id.clone();
+
+                        let mut status = format!(
+                            "Imported \"{}\" into \"{}\"",
+                            request.name, self.collections[collection_index].name
+                        );
+                        let environment = self
+                            .active_environment
+                            .and_then(|index| self.environments.get(index));
+                        let missing: Vec<String> = share::referenced_variables(&request)
+                            .into_iter()
+                            .filter(|name| {
+                                environment.is_none_or(|env| env.get_variable(name).is_none())
+                            })
+                            .collect();
+                        if !missing.is_empty() {
+                            status.push_str(&format!(", it uses {}", missing.join(", ")));
+                        }
+                        self.set_import_status(Ok(status));
+                        self.add_request_to_collection(&collection_id, *request);
+                        Task::none()
+                    }
+                    Ok(Imported::Environments(environments)) => {
+                        let count = environments.len();
+                        for mut environment in environments {
+                            let base_name = environment.name.clone();
+                            let mut counter = 2;
+                            while self.environments.iter().any(|e| e.name == environment.name) {
+                                environment.name = format!("{} ({})", base_name, counter);
+                                counter += 1;
+                            }
+                            self.record_change(AuditEntry::new(
+                                AuditOperation::Create,
+                                AuditSubject::Environment,
+                                &environment.name,
+                            ));
+                            self.environments.push(environment);
+                        }
+                        if self.active_environment.is_none() && !self.environments.is_empty() {
+                            self.active_environment = Some(0);
+                        }
+
+                        self.set_import_status(Ok(format!("Imported {} environments", count)));
+                        self.import_url.clear();
+
+                        let environments = self.environments.clone();
+                        let storage = self.storage();
+                        Task::perform(
+                            async move {
+                                match storage {
+                                    Ok(storage_manager) => storage_manager
+                                        .storage()
+                                        .save_environments(&environments)
+                                        .map_err(|e| e.to_string()),
+                                    Err(e) => Err(e.to_string()),
+                                }
+                            },
+                            Message::EnvironmentsSaved,
+                        )
+                    }
+                    Err(e) => {
+                        error!("Failed to import from URL: {}", e);
+                        self.set_import_status(Err(e));
+                        Task::none()
+                    }
+                }
+            }
+            Message::FileDropped(path) => {
+                self.pending_drops.push(dropped_file(&path));
+                Task::none()
+            }
+            Message::CheckTray => {
+                let events = self.tray.as_ref().map(Tray::events).unwrap_or_default();
+                let mut tasks = Vec::new();
+                for event in events {
+                    match event {
+                        TrayEvent::ShowWindow => tasks.push(bring_to_front()),
+                        TrayEvent::Send(request_id) => {
+                            let Some(request) =
+                                find_request(&self.collections, &request_id).cloned()
+                            else {
+                                continue;
+                            };
+                            self.tray_sends.insert(request_id);
+                            tasks.push(self.send_with_token_refresh(request, Instant::now()));
+                        }
+                    }
+                }
+                Task::batch(tasks)
+            }
+            Message::Launched(request) => match request {
+                LaunchRequest::Activate => bring_to_front(),
+                LaunchRequest::OpenLink { link } => self.open_link(&link),
+                LaunchRequest::OpenFile { path } => {
+                    self.pending_drops.push(dropped_file(&path));
+                    Task::none()
+                }
+            },
+            Message::ConfirmDroppedImport => {
+                if self.pending_drops.is_empty() {
+                    return Task::none();
+                }
+
+                match self.pending_drops.remove(0) {
+                    (_, Ok(imported)) => self.update(Message::ImportFetched(Ok(imported))),
+                    (_, Err(_)) => Task::none(),
+                }
+            }
+            Message::CancelDroppedImport => {
+                if !self.pending_drops.is_empty() {
+                    let _ = self.pending_drops.remove(0);
+                }
+                Task::none()
+            }
+            Message::Welcome(welcome_message) => match welcome_message {
+                welcome::Message::CreateSampleCollection => {
+                    let storage = self.storage();
+                    Task::perform(
+                        async move {
+                            match storage {
+                                Ok(storage_manager) => storage_manager
+                                    .storage()
+                                    .initialize_default_collections()
+                                    .map_err(|e| e.to_string()),
+                                Err(e) => Err(e.to_string()),
+                            }
+                        },
+                        Message::SampleCollectionCreated,
+                    )
+                }
+                welcome::Message::ImportExisting => {
+                    self.show_import_modal = true;
+                    self.import_status = None;
+                    Task::none()
+                }
+                welcome::Message::StartBlank => {
+                    self.show_welcome = false;
+                    self.update(Message::CollectionPanel(collections::Message::AddFolder))
+                }
+            },
+            Message::SampleCollectionCreated(result) => match result {
+                Ok(()) => Task::perform(async { Message::LoadCollections }, |msg| msg),
+                Err(e) => {
+                    error!("Failed to create sample collection: {}", e);
+                    self.toasts.error("Failed to create sample collection", e);
+                    Task::none()
+                }
+            },
+            Message::SyncPanel(sync_message) => match self.sync_panel.update(sync_message) {
+                sync::Action::Sync => {
+                    let config = self.sync_panel.config.clone();
+                    let resolutions = self.sync_panel.resolutions.clone();
+                    let base_path = storage::StorageConfig::default().base_path;
+                    if let Err(e) = beam::sync::save_config(&base_path, &config) {
+                        error!("Failed to save sync settings: {}", e);
+                        self.toasts
+                            .error("Failed to save sync settings", e.to_string());
+                    }
+
+                    Task::perform(
+                        beam::sync::sync(base_path, config, resolutions),
+                        Message::SyncFinished,
+                    )
+                }
+                sync::Action::Close => {
+                    self.show_sync_modal = false;
+                    Task::none()
+                }
+                sync::Action::None => Task::none(),
+            },
+            Message::SyncFinished(result) => {
+                let pulled = result.as_ref().is_ok_and(|report| report.pulled > 0);
+                if let Err(e) = &result {
+                    error!("Remote sync failed: {}", e);
+                    self.toasts.error("Remote sync failed", e.clone());
+                }
+                self.sync_panel.finish(result);
+
+                if pulled {
+                    Task::batch([
+                        Task::perform(async { Message::LoadCollections }, |msg| msg),
+                        Task::perform(async { Message::LoadEnvironments }, |msg| msg),
+                    ])
+                } else {
+                    Task::none()
+                }
+            }
+            Message::ToggleTools => {
+                self.show_tools_modal = !self.show_tools_modal;
+                Task::none()
+            }
+            Message::ToggleWebhookInbox => {
+                self.show_webhook_modal = !self.show_webhook_modal;
+                Task::none()
+            }
+            Message::WebhookPanel(webhook_message) => {
+                match self.webhook_panel.update(webhook_message) {
+                    webhook::Action::CopyToClipboard(output) => {
+                        self.toasts.success("Copied to clipboard");
+                        iced::clipboard::write(output)
+                    }
+                    webhook::Action::Close => {
+                        self.show_webhook_modal = false;
+                        Task::none()
+                    }
+                    webhook::Action::None => Task::none(),
+                }
+            }
+            Message::ToggleSettings => {
+                self.show_settings_modal = !self.show_settings_modal;
+                Task::none()
+            }
+            Message::Settings(settings_message) => match settings_message {
+                settings::Message::ThemeSelected(ThemeChoice::System) => {
+                    self.layout.theme = None;
+                    Task::none()
+                }
+                settings::Message::ThemeSelected(ThemeChoice::Named(name)) => {
+                    self.layout.theme = Some(name);
+                    Task::none()
+                }
+                settings::Message::LanguageSelected(choice) => {
+                    self.layout.language = match choice {
+                        LanguageChoice::System => None,
+                        LanguageChoice::Fixed(language) => Some(language),
+                    };
+                    i18n::set_language(self.layout.language.unwrap_or_else(Language::from_system));
+                    Task::none()
+                }
+                settings::Message::UsageStatsToggled(enabled) => {
+                    self.layout.usage_stats = enabled;
+                    Task::none()
+                }
+                settings::Message::JsonOnSaveSelected(formatting) => {
+                    self.layout.json_body.on_save = formatting;
+                    Task::none()
+                }
+                settings::Message::JsonOnSendSelected(formatting) => {
+                    self.layout.json_body.on_send = formatting;
+                    Task::none()
+                }
+                settings::Message::BlockMalformedJsonToggled(enabled) => {
+                    self.layout.json_body.block_malformed = enabled;
+                    Task::none()
+                }
+                settings::Message::OpenUsageStats => {
+                    // Hosts as the active environment resolves them
+                    self.usage_stats = Some(usage_stats(&self.collections, |request| {
+                        beam::stats::host(&self.resolve_variables(&request.url))
+                    }));
+                    Task::none()
+                }
+                settings::Message::TrayToggled(enabled) => {
+                    self.layout.show_tray = enabled;
+                    self.sync_tray();
+                    Task::none()
+                }
+                settings::Message::LargeTargetsToggled(enabled) => {
+                    self.layout.large_targets = enabled;
+                    accessibility::set_large_targets(enabled);
+                    Task::none()
+                }
+                settings::Message::OpenThemesFolder => {
+                    let dir = theme::themes_dir();
+                    if let Err(e) = std::fs::create_dir_all(&dir)
+                        .map_err(|e| e.to_string())
+                        .and_then(|_| beam::external::open_path(&dir))
+                    {
+                        self.toasts.error("Failed to open the themes folder", e);
+                    }
+                    Task::none()
+                }
+                settings::Message::Close => {
+                    self.show_settings_modal = false;
+                    Task::none()
+                }
+            },
+            Message::ReloadThemes => Task::perform(
+                async { theme::load(&theme::themes_dir()) },
+                Message::ThemesLoaded,
+            ),
+            Message::ThemesLoaded(themes) => {
+                if themes != self.themes {
+                    for error in themes
+                        .errors
+                        .iter()
+                        .filter(|error| !self.themes.errors.contains(error))
+                    {
+                        self.toasts.error("Failed to load theme", error.clone());
+                    }
+                    self.themes = themes;
+                }
+                Task::none()
+            }
+            Message::ToggleAuditLog => {
+                if self.audit_entries.take().is_some() {
+                    return Task::none();
+                }
+
+                Task::perform(
+                    async {
+                        AuditLog::new(&storage::StorageConfig::default().base_path)
+                            .load()
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::AuditLogLoaded,
+                )
+            }
+            Message::AuditLogLoaded(result) => {
+                match result {
+                    Ok(entries) => self.audit_entries = Some(entries),
+                    Err(e) => self.toasts.error("Failed to load the audit log", e),
+                }
+                Task::none()
+            }
+            Message::AuditLog(audit_log::Message::Close) => {
+                self.audit_entries = None;
+                Task::none()
+            }
+            Message::CollectionStats(stats::Message::Close) => {
+                self.collection_stats = None;
+                Task::none()
+            }
+            Message::UsageStats(stats::Message::Close) => {
+                self.usage_stats = None;
+                Task::none()
+            }
+            Message::Runner(runner_message) => {
+                let Some(dialog) = self.runner.as_mut() else {
+                    return Task::none();
+                };
+                match dialog.update(runner_message) {
+                    runner::Action::Start => {
+                        let Some(collection) =
+                            find_collection(&self.collections, &dialog.collection_id)
+                        else {
+                            return Task::none();
+                        };
+                        // The environment picked for the run, rather than the active one
+                        let environment = dialog
+                            .environment
+                            .as_ref()
+                            .and_then(|name| {
+                                self.environments
+                                    .iter()
+                                    .find(|environment| environment.name == *name)
+                            })
+                            .cloned()
+                            .unwrap_or_else(|| Environment::new(String::new()));
+                        let path = dialog.data_path.trim();
+                        let data = if path.is_empty() {
+                            Vec::new()
+                        } else {
+                            match std::fs::read_to_string(path)
+                                .map_err(|e| e.to_string())
+                                .and_then(|content| parse_iteration_data(&content))
+                            {
+                                Ok(data) => data,
+                                Err(e) => {
+                                    self.toasts.error("Could not read the iteration data", e);
+                                    return Task::none();
+                                }
+                            }
+                        };
+
+                        self.next_run_id += 1;
+                        dialog.run_id = self.next_run_id;
+                        dialog.run = Some(Run::new(
+                            collection.name.clone(),
+                            &collection.requests,
+                            &dialog.configuration(),
+                            environment,
+                            data,
+                            dialog.mode,
+                            dialog.concurrency(),
+                        ));
+                        self.start_run_requests()
+                    }
+                    runner::Action::Stop => {
+                        if let Some(run) = dialog.run.as_mut() {
+                            run.stop();
+                        }
+                        Task::none()
+                    }
+                    runner::Action::SaveConfiguration(configuration) => {
+                        if let Some(collection) =
+                            find_collection_mut(&mut self.collections, &dialog.collection_id)
+                        {
+                            collection.run_configuration = configuration;
+                            save_collection_in_background(self.storage.clone(), collection.clone());
+                        }
+                        Task::none()
+                    }
+                    runner::Action::Close => {
+                        self.runner = None;
+                        Task::none()
+                    }
+                    runner::Action::None => Task::none(),
+                }
+            }
+            Message::RunnerRequestCompleted(run_id, entry, result) => {
+                // Dropped when the dialog was closed or another run started
+                let Some(run) = self
+                    .runner
+                    .as_mut()
+                    .filter(|dialog| dialog.run_id == run_id)
+                    .and_then(|dialog| dialog.run.as_mut())
+                else {
+                    return Task::none();
+                };
+
+                run.complete(entry, result);
+                if run.is_finished() {
+                    info!(
+                        "Run of '{}' with environment '{}' finished: {} sent, {} failed",
+                        run.collection,
+                        run.environment.name,
+                        run.completed(),
+                        run.failed()
+                    );
+                }
+                self.start_run_requests()
+            }
+            Message::CheckConnectivity => {
+                let request = self.resolve_request_config_variables(&self.current_request);
+                if !request.unix_socket.trim().is_empty() {
+                    self.toasts
+                        .info("This request goes over a Unix socket, not the network");
+                    return Task::none();
+                }
+                match Target::from_url(&request.url) {
+                    Ok(target) => {
+                        self.connectivity = Some((target.clone(), None));
+                        Task::perform(
+                            connectivity::check(target, request.connection.accept_invalid_certs),
+                            Message::ConnectivityChecked,
+                        )
                     }
                     Err(e) => {
-                        error!("Failed to auto-save request: {}", e);
+                        self.toasts.error("Can't check connectivity", e);
+                        Task::none()
                     }
                 }
+            }
+            Message::ConnectivityChecked(report) => {
+                // Ignore the report of a check that was closed or replaced meanwhile
+                if let Some((target, pending)) = &mut self.connectivity
+                    && *target == report.target
+                {
+                    *pending = Some(report);
+                }
+                Task::none()
+            }
+            Message::CloseConnectivity => {
+                self.connectivity = None;
                 Task::none()
             }
+            Message::ToolsPanel(tools_message) => match self.tools_panel.update(tools_message) {
+                tools::Action::CopyToClipboard(output) => {
+                    self.toasts.success("Copied to clipboard");
+                    iced::clipboard::write(output)
+                }
+                tools::Action::Run(task) => task.map(Message::ToolsPanel),
+                tools::Action::Close => {
+                    self.show_tools_modal = false;
+                    Task::none()
+                }
+                tools::Action::None => Task::none(),
+            },
             Message::HideRenameModal => {
                 self.show_rename_modal = false;
                 self.rename_input.clear();
@@ -1620,21 +4121,22 @@ impl BeamApp {
                         return Task::none();
                     }
                     match rename_target {
-                        RenameTarget::Request(collection_index, request_index) => {
-                            let collection_index = *collection_index;
-                            let request_index = *request_index;
+                        RenameTarget::Request(request_id) => {
+                            let Some((collection_index, request_index)) =
+                                request_position(&self.collections, request_id)
+                            else {
+                                return Task::none();
+                            };
 
                             // Check for duplicate names in the same collection
-                            if let Some(collection) = self.collections.get(collection_index) {
-                                if collection
-                                    .requests
-                                    .iter()
-                                    .enumerate()
-                                    .any(|(i, req)| i != request_index && req.name == new_name)
-                                {
-                                    // TODO: Show error message for duplicate name
-                                    return Task::none();
-                                }
+                            if self.collections[collection_index]
+                                .requests
+                                .iter()
+                                .enumerate()
+                                .any(|(i, req)| i != request_index && req.name == new_name)
+                            {
+                                // TODO: Show error message for duplicate name
+                                return Task::none();
                             }
 
                             // Update the request name
@@ -1642,6 +4144,13 @@ impl BeamApp {
                                 if let Some(request) = collection.requests.get_mut(request_index) {
                                     let old_name = request.name.clone();
                                     request.name = new_name.clone();
+                                    let change = AuditEntry::new(
+                                        AuditOperation::Rename,
+                                        AuditSubject::Request,
+                                        &new_name,
+                                    )
+                                    .renamed_from(&old_name)
+                                    .in_collection(&collection.name);
 
                                     // Hide the modal
                                     self.show_rename_modal = false;
@@ -1650,11 +4159,17 @@ impl BeamApp {
 
                                     // Save the collection and rename the file (non-blocking)
                                     let collection_name = collection.name.clone();
+                                    let request_id = request.id.clone();
+                                    self.record_change(change);
+                                    self.undo_stack.push(WorkspaceCommand::RenameRequest {
+                                        request_id,
+                                        from: old_name.clone(),
+                                        to: new_name.clone(),
+                                    });
 
+                                    let storage = self.storage();
                                     tokio::spawn(async move {
-                                        if let Ok(storage_manager) =
-                                            storage::StorageManager::with_default_config()
-                                        {
+                                        if let Ok(storage_manager) = storage {
                                             let storage = storage_manager.storage();
                                             if let Err(e) = storage.rename_request(
                                                 &collection_name,
@@ -1670,45 +4185,50 @@ impl BeamApp {
                                 }
                             }
                         }
-                        RenameTarget::Folder(collection_index) => {
-                            let collection_index = *collection_index;
-
+                        RenameTarget::Folder(collection_id) => {
                             // Check for duplicate folder names
                             if self
                                 .collections
                                 .iter()
-                                .enumerate()
-                                .any(|(i, col)| i != collection_index && col.name == new_name)
+                                .any(|col| col.id != *collection_id && col.name == new_name)
                             {
                                 // TODO: Show error message for duplicate name
                                 return Task::none();
                             }
 
-                            // Update the folder name
-                            if let Some(collection) = self.collections.get_mut(collection_index) {
+                            // The new name is applied once it is on disk, so saves in the
+                            // meantime still find the collection under its old name
+                            if let Some(collection) =
+                                find_collection(&self.collections, collection_id)
+                            {
                                 let old_name = collection.name.clone();
-                                collection.name = new_name.clone();
+                                let collection_id = collection.id.clone();
 
                                 // Hide the modal
                                 self.show_rename_modal = false;
                                 self.rename_input.clear();
                                 self.rename_target = None;
 
-                                // Rename the collection folder (non-blocking)
-                                tokio::spawn(async move {
-                                    if let Ok(storage_manager) =
-                                        storage::StorageManager::with_default_config()
-                                    {
-                                        let storage = storage_manager.storage();
-                                        if let Err(e) =
-                                            storage.rename_collection(&old_name, &new_name)
-                                        {
-                                            eprintln!("Failed to rename collection folder: {}", e);
+                                let storage = self.storage();
+                                return Task::perform(
+                                    async move {
+                                        let result = storage
+                                            .and_then(|storage_manager| {
+                                                storage_manager
+                                                    .storage()
+                                                    .rename_collection(&old_name, &new_name)
+                                            })
+                                            .map_err(|e| e.to_string());
+
+                                        Message::CollectionRenamed {
+                                            collection_id,
+                                            new_name,
+                                            result,
+                                            undoable: true,
                                         }
-                                    }
-                                });
-
-                                return Task::none();
+                                    },
+                                    |msg| msg,
+                                );
                             }
                         }
                     }
@@ -1722,8 +4242,11 @@ impl BeamApp {
     }
 
     fn perform_search(&mut self, next: bool, focus_id: Option<iced::widget::Id>) -> Task<Message> {
-        let query = &self.request_panel.search_query;
-        if query.is_empty() {
+        let Ok(pattern) = self.request_panel.search_pattern() else {
+            // The find bar tells why the regular expression is invalid
+            return Task::done(Message::RequestPanel(request::Message::SearchNotFound));
+        };
+        if pattern.is_empty() {
             if let Some(id) = focus_id {
                 return operation::focus(id)
                     .map(|_: ()| Message::RequestPanel(request::Message::DoNothing));
@@ -1742,79 +4265,20 @@ impl BeamApp {
 
         let current_idx = Self::position_to_byte_index(content, current_pos);
 
-        let search_start_idx = if next {
-            // Start search from current cursor position + char length to find the "next" match
-            if let Some(c) = text.get(current_idx..).and_then(|s| s.chars().next()) {
-                current_idx + c.len_utf8()
-            } else {
-                current_idx
-            }
-        } else {
-            // For previous, search from beginning up to current cursor position
-            current_idx
-        };
-
-        let search_range = if next {
-            search_start_idx..text.len()
+        let found = if next {
+            // Start after the cursor to find the "next" match
+            let from = text
+                .get(current_idx..)
+                .and_then(|s| s.chars().next())
+                .map_or(current_idx, |c| current_idx + c.len_utf8());
+            pattern.next(&text, from)
         } else {
-            0..search_start_idx
+            pattern.previous(&text, current_idx)
         };
 
-        let mut found_match = false;
-        let mut match_start_byte = 0;
-        let mut match_end_byte = 0;
-
-        if next {
-            // Search forward
-            if let Some(start) = text[search_range].find(query) {
-                match_start_byte = search_start_idx + start;
-                match_end_byte = match_start_byte + query.len();
-                found_match = true;
-            } else {
-                // Wrap around: search from beginning
-                if let Some(start) = text[0..search_start_idx].find(query) {
-                    match_start_byte = start;
-                    match_end_byte = match_start_byte + query.len();
-                    found_match = true;
-                }
-            }
-        } else {
-            // Search backward
-            if let Some(start) = text[search_range.clone()].rfind(query) {
-                match_start_byte = search_range.start + start;
-                match_end_byte = match_start_byte + query.len();
-
-                if match_end_byte == current_idx {
-                    // If we found the match ending exactly at our cursor,
-                    // we want to find the one before it.
-                    if let Some(prev_start) = text[0..match_start_byte].rfind(query) {
-                        match_start_byte = prev_start;
-                        match_end_byte = match_start_byte + query.len();
-                        found_match = true;
-                    } else {
-                        // If no previous match, try wrapping around to the end
-                        if let Some(last_start) = text[search_start_idx..text.len()].rfind(query) {
-                            match_start_byte = search_start_idx + last_start;
-                            match_end_byte = match_start_byte + query.len();
-                            found_match = true;
-                        }
-                    }
-                } else {
-                    found_match = true;
-                }
-            } else {
-                // Wrap around: search from end
-                if let Some(start) = text[search_start_idx..text.len()].rfind(query) {
-                    match_start_byte = search_start_idx + start;
-                    match_end_byte = match_start_byte + query.len();
-                    found_match = true;
-                }
-            }
-        }
-
-        if found_match {
-            let start_pos = Self::byte_index_to_position(content, match_start_byte);
-            let end_pos = Self::byte_index_to_position(content, match_end_byte);
+        if let Some(found) = found {
+            let start_pos = Self::byte_index_to_position(content, found.start);
+            let end_pos = Self::byte_index_to_position(content, found.end);
 
             // Do NOT modify the user's cursor or selection!
             // The highlight will be driven independently by `SearchFound` -> `search_selection`.
@@ -1855,8 +4319,11 @@ impl BeamApp {
         next: bool,
         focus_id: Option<iced::widget::Id>,
     ) -> Task<Message> {
-        let query = &self.response_panel.search_query;
-        if query.is_empty() {
+        let Ok(pattern) = self.response_panel.search_pattern() else {
+            // The find bar tells why the regular expression is invalid
+            return Task::done(Message::ResponsePanel(response::Message::SearchNotFound));
+        };
+        if pattern.is_empty() {
             if let Some(id) = focus_id {
                 return operation::focus(id)
                     .map(|_: ()| Message::ResponsePanel(response::Message::DoNothing));
@@ -1875,70 +4342,20 @@ impl BeamApp {
 
         let current_idx = Self::position_to_byte_index(content, current_pos);
 
-        let search_start_idx = if next {
-            if let Some(c) = text.get(current_idx..).and_then(|s| s.chars().next()) {
-                current_idx + c.len_utf8()
-            } else {
-                current_idx
-            }
-        } else {
-            current_idx
-        };
-
-        let search_range = if next {
-            search_start_idx..text.len()
+        let found = if next {
+            // Start after the cursor to find the "next" match
+            let from = text
+                .get(current_idx..)
+                .and_then(|s| s.chars().next())
+                .map_or(current_idx, |c| current_idx + c.len_utf8());
+            pattern.next(&text, from)
         } else {
-            0..search_start_idx
+            pattern.previous(&text, current_idx)
         };
 
-        let mut found_match = false;
-        let mut match_start_byte = 0;
-        let mut match_end_byte = 0;
-
-        if next {
-            if let Some(start) = text[search_range].find(query) {
-                match_start_byte = search_start_idx + start;
-                match_end_byte = match_start_byte + query.len();
-                found_match = true;
-            } else {
-                if let Some(start) = text[0..search_start_idx].find(query) {
-                    match_start_byte = start;
-                    match_end_byte = match_start_byte + query.len();
-                    found_match = true;
-                }
-            }
-        } else {
-            if let Some(start) = text[search_range.clone()].rfind(query) {
-                match_start_byte = search_range.start + start;
-                match_end_byte = match_start_byte + query.len();
-
-                if match_end_byte == current_idx {
-                    if let Some(prev_start) = text[0..match_start_byte].rfind(query) {
-                        match_start_byte = prev_start;
-                        match_end_byte = match_start_byte + query.len();
-                        found_match = true;
-                    } else {
-                        if let Some(last_start) = text[search_start_idx..text.len()].rfind(query) {
-                            match_start_byte = search_start_idx + last_start;
-                            match_end_byte = match_start_byte + query.len();
-                            found_match = true;
-                        }
-                    }
-                } else {
-                    found_match = true;
-                }
-            } else {
-                if let Some(start) = text[search_start_idx..text.len()].rfind(query) {
-                    match_start_byte = search_start_idx + start;
-                    match_end_byte = match_start_byte + query.len();
-                    found_match = true;
-                }
-            }
-        }
-
-        if found_match {
-            let start_pos = Self::byte_index_to_position(content, match_start_byte);
-            let end_pos = Self::byte_index_to_position(content, match_end_byte);
+        if let Some(found) = found {
+            let start_pos = Self::byte_index_to_position(content, found.start);
+            let end_pos = Self::byte_index_to_position(content, found.end);
 
             // Do NOT modify the user's cursor or selection!
             // The highlight will be driven independently by `SearchFound` -> `search_selection`.
@@ -2032,36 +4449,20 @@ impl BeamApp {
     }
 
     fn view(&self) -> Element<'_, Message> {
-        info!("=== Rendering main view ===");
-        let pane_grid = PaneGrid::new(&self.panes, |_id, pane, _is_maximized| {
-            let content = match pane {
-                PaneContent::Collections => self.collections_view(),
-                PaneContent::RequestConfig => self.request_config_view(),
-                PaneContent::Response => self.response_view(),
-            };
+        if self.toasts.is_empty() {
+            self.content_view()
+        } else {
+            stack![self.content_view(), self.toasts.view().map(Message::Toast)].into()
+        }
+    }
 
-            container(content)
-                .width(Fill)
-                .height(Fill)
-                .padding(0)
-                .into()
-        })
-        .on_resize(10, Message::PaneResized)
-        .spacing(1)
-        .style(|_theme| pane_grid::Style {
-            hovered_region: pane_grid::Highlight {
-                background: iced::Background::Color(iced::Color::TRANSPARENT),
-                border: iced::Border::default(),
-            },
-            hovered_split: pane_grid::Line {
-                color: iced::Color::from_rgb(0.9, 0.9, 0.9), // Gray
-                width: 0.0,
-            },
-            picked_split: pane_grid::Line {
-                color: iced::Color::from_rgb(0.9, 0.9, 0.9), // Gray
-                width: 0.0,
-            },
-        });
+    fn content_view(&self) -> Element<'_, Message> {
+        info!("=== Rendering main view ===");
+        let pane_grid: Element<'_, Message> = if self.show_welcome {
+            welcome::view().map(Message::Welcome)
+        } else {
+            self.pane_grid_view()
+        };
 
         // Wrap the main content in a custom overlay if any popup is shown
         if self.show_environment_popup {
@@ -2094,24 +4495,104 @@ impl BeamApp {
                 .on_scroll(|_| Message::DoNothing)
             ]
             .into()
+        } else if let Some((config, _)) = &self.pending_oversized_send {
+            modal_overlay(pane_grid, self.oversized_send_modal_view(config), 420)
+        } else if let Some(recovery) = &self.crash_recovery {
+            modal_overlay(pane_grid, self.crash_recovery_modal_view(recovery), 460)
+        } else if let Some(conflict) = self.save_conflicts.first() {
+            modal_overlay(pane_grid, self.save_conflict_modal_view(conflict), 460)
+        } else if let Some((name, imported)) = self.pending_drops.first() {
+            modal_overlay(
+                pane_grid,
+                self.dropped_import_modal_view(name, imported),
+                460,
+            )
+        } else if let Some(draft) = &self.collection_panel.new_request {
+            modal_overlay(pane_grid, self.new_request_modal_view(draft), 480)
+        } else if self.show_sync_modal {
+            modal_overlay(
+                pane_grid,
+                self.sync_panel.view().map(Message::SyncPanel),
+                520,
+            )
+        } else if self.show_tools_modal {
+            modal_overlay(
+                pane_grid,
+                self.tools_panel.view().map(Message::ToolsPanel),
+                560,
+            )
+        } else if self.show_webhook_modal {
+            modal_overlay(
+                pane_grid,
+                container(self.webhook_panel.view().map(Message::WebhookPanel)).height(600),
+                820,
+            )
+        } else if let Some(collection_stats) = &self.collection_stats {
+            modal_overlay(
+                pane_grid,
+                container(stats::view(collection_stats).map(Message::CollectionStats)).height(600),
+                760,
+            )
+        } else if let Some(dialog) = &self.runner
+            && let Some(collection) = find_collection(&self.collections, &dialog.collection_id)
+        {
+            modal_overlay(
+                pane_grid,
+                container(
+                    dialog
+                        .view(collection, &self.environments)
+                        .map(Message::Runner),
+                )
+                .height(600),
+                760,
+            )
+        } else if let Some(usage) = &self.usage_stats {
+            modal_overlay(
+                pane_grid,
+                container(stats::usage_view(usage).map(Message::UsageStats)).height(600),
+                760,
+            )
+        } else if self.show_settings_modal {
+            modal_overlay(
+                pane_grid,
+                container(
+                    settings::view(
+                        &self.themes,
+                        self.layout.theme.as_deref(),
+                        self.layout.language,
+                        self.layout.large_targets,
+                        self.layout.show_tray,
+                        self.layout.usage_stats,
+                        self.layout.json_body,
+                    )
+                    .map(Message::Settings),
+                )
+                .height(460),
+                560,
+            )
+        } else if let Some(audit_entries) = &self.audit_entries {
+            modal_overlay(
+                pane_grid,
+                container(audit_log::view(audit_entries).map(Message::AuditLog)).height(600),
+                760,
+            )
+        } else if let Some((target, report)) = &self.connectivity {
+            modal_overlay(
+                pane_grid,
+                self.connectivity_modal_view(target, report.as_ref()),
+                520,
+            )
+        } else if self.show_import_modal {
+            modal_overlay(pane_grid, self.import_modal_view(), 460)
         } else if self.show_rename_modal {
             // Create a custom overlay for the rename modal
-            stack![
+            modal_overlay(
                 pane_grid,
-                // Semi-transparent backdrop with centered modal
-                container(container(self.rename_modal_view()).width(400).height(200))
-                    .center_x(Fill)
-                    .center_y(Fill)
-                    .width(Fill)
-                    .height(Fill)
-                    .style(|_theme| container::Style {
-                        background: Some(Color::from_rgba(0.25, 0.25, 0.25, 0.7).into()),
-                        ..Default::default()
-                    })
-            ]
-            .into()
+                container(self.rename_modal_view()).height(200),
+                400,
+            )
         } else {
-            pane_grid.into()
+            pane_grid
         }
     }
 
@@ -2122,6 +4603,23 @@ impl BeamApp {
         )));
     }
 
+    /// Fills the response editor, rendering only the head of very large bodies
+    /// until the user asks for the rest
+    fn set_response_body(&mut self, body: String) {
+        if body.len() <= RESPONSE_PREVIEW_SIZE {
+            self.full_response_body = None;
+            Self::update_editor_content(&mut self.response_body_content, body);
+            return;
+        }
+
+        let mut end = RESPONSE_PREVIEW_SIZE;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        Self::update_editor_content(&mut self.response_body_content, body[..end].to_string());
+        self.full_response_body = Some(body);
+    }
+
     fn format_response_content(body: &str, body_format: BodyFormat) -> String {
         const MAX_JSON_FORMAT_SIZE: usize = 1000 * 1024; // 1MB
 
@@ -2146,200 +4644,993 @@ impl BeamApp {
 
     /// Resolves variables in the format {{variable_name}} using the active environment
     fn resolve_variables(&self, input: &str) -> String {
-        if let Some(active_env_index) = self.active_environment {
-            if let Some(active_env) = self.environments.get(active_env_index) {
-                let mut result = input.to_string();
-
-                // Use regex to find all {{variable_name}} patterns
-                use regex::Regex;
-                let re = Regex::new(r"\{\{([^}]+)\}\}").unwrap();
-
-                // Replace each variable with its value from the active environment
-                for captures in re.captures_iter(input) {
-                    if let Some(var_name) = captures.get(1) {
-                        let var_name = var_name.as_str().trim();
-                        if let Some(var_value) = active_env.get_variable(var_name) {
-                            let pattern = format!("{{{{{}}}}}", var_name);
-                            result = result.replace(&pattern, var_value);
-                        }
-                    }
-                }
-
-                return result;
-            }
+        if let Some(active_env_index) = self.active_environment
+            && let Some(active_env) = self.environments.get(active_env_index)
+        {
+            return resolve_environment_variables(active_env, input, &mut Vec::new());
         }
 
-        // If no active environment or variable not found, return original string
-        input.to_string()
+        // Without an active environment only dynamic placeholders can be resolved
+        faker::resolve_placeholders(input)
     }
 
     /// Resolves all variables in a RequestConfig and returns a new resolved config
     fn resolve_request_config_variables(&self, config: &RequestConfig) -> RequestConfig {
+        Self::resolve_request_config_in(
+            config,
+            self.active_environment
+                .and_then(|index| self.environments.get(index)),
+        )
+    }
+
+    /// Resolves all variables in a RequestConfig with `environment` rather than the active
+    /// one, as a collection run does
+    fn resolve_request_config_in(
+        config: &RequestConfig,
+        environment: Option<&Environment>,
+    ) -> RequestConfig {
         let mut resolved_config = config.clone();
+        let resolve = |input: &str| match environment {
+            Some(environment) => resolve_environment_variables(environment, input, &mut Vec::new()),
+            None => faker::resolve_placeholders(input),
+        };
+
+        if resolved_config.auth_type == AuthType::InheritFromEnvironment
+            && let Some(environment) = environment
+        {
+            environment.auth.apply_to(&mut resolved_config);
+        }
 
         info!("resolve variables");
+        // Path variables first, their values may use environment variables too
+        for (_, value) in &mut resolved_config.path_variables {
+            *value = resolve(value);
+        }
+        resolved_config.url =
+            substitute_path_variables(&resolved_config.url, &resolved_config.path_variables);
+
         // Resolve variables in URL
-        resolved_config.url = self.resolve_variables(&resolved_config.url);
-        info!("DEBUG: Resolved URL: {}", resolved_config.url);
+        resolved_config.url = resolve(&resolved_config.url);
+        info!(
+            "DEBUG: Resolved URL: {}",
+            redact::mask(
+                &resolved_config.url,
+                &redact::secrets(&resolved_config, environment)
+            )
+        );
 
         // Resolve variables in headers
         for (key, value) in &mut resolved_config.headers {
-            *key = self.resolve_variables(key);
-            *value = self.resolve_variables(value);
+            *key = resolve(key);
+            *value = resolve(value);
         }
         info!("DEBUG: Resolved Headers");
 
-        // Resolve variables in params
-        for (key, value) in &mut resolved_config.params {
-            *key = self.resolve_variables(key);
-            *value = self.resolve_variables(value);
+        // Resolve variables in params
+        for (key, value) in &mut resolved_config.params {
+            *key = resolve(key);
+            *value = resolve(value);
+        }
+        info!("DEBUG: Resolved Params");
+
+        // Resolve variables in body, a JSON5 one goes out as strict JSON with its typed
+        // variables written unquoted
+        resolved_config.body = if resolved_config.body_format == BodyFormat::Json {
+            json_template::resolve(&json5::to_json(&resolved_config.body), |name| {
+                let reference = format!("{{{{{}}}}}", name);
+                let value = resolve(&reference);
+                let value_type = environment
+                    .and_then(|environment| environment.variables.get(name))
+                    .map(|variable| variable.value_type)
+                    .unwrap_or_default();
+                (value != reference).then_some((value, value_type))
+            })
+        } else {
+            resolve(&resolved_config.body)
+        };
+        info!("DEBUG: Resolved Body");
+
+        // Resolve variables in authentication fields
+        resolved_config.bearer_token = resolve(&resolved_config.bearer_token);
+        resolved_config.basic_username = resolve(&resolved_config.basic_username);
+        resolved_config.basic_password = resolve(&resolved_config.basic_password);
+        resolved_config.api_key = resolve(&resolved_config.api_key);
+        resolved_config.api_key_header = resolve(&resolved_config.api_key_header);
+        resolved_config.hmac_auth.key = resolve(&resolved_config.hmac_auth.key);
+        resolved_config.hmac_auth.string_to_sign =
+            resolve(&resolved_config.hmac_auth.string_to_sign);
+        resolved_config.hmac_auth.header_name = resolve(&resolved_config.hmac_auth.header_name);
+        resolved_config.hmac_auth.header_value = resolve(&resolved_config.hmac_auth.header_value);
+        resolved_config.hawk_auth.id = resolve(&resolved_config.hawk_auth.id);
+        resolved_config.hawk_auth.key = resolve(&resolved_config.hawk_auth.key);
+        resolved_config.hawk_auth.ext = resolve(&resolved_config.hawk_auth.ext);
+
+        // Resolve variables in transport settings
+        resolved_config.unix_socket = resolve(&resolved_config.unix_socket);
+
+        resolved_config
+    }
+
+    /// Whether the collection run is waiting for the delay before its next request
+    fn is_run_waiting(&self) -> bool {
+        self.runner
+            .as_ref()
+            .and_then(|dialog| dialog.run.as_ref())
+            .is_some_and(|run| run.is_waiting())
+    }
+
+    /// Sends the requests of the collection run that are due, as many as its mode allows
+    fn start_run_requests(&mut self) -> Task<Message> {
+        let Some(dialog) = self.runner.as_mut() else {
+            return Task::none();
+        };
+        let run_id = dialog.run_id;
+        let Some(run) = dialog.run.as_mut() else {
+            return Task::none();
+        };
+
+        let started = run.start_next();
+        let tasks: Vec<Task<Message>> = started
+            .into_iter()
+            .filter_map(|entry| {
+                let request = run.requests.get(run.entries.get(entry)?.request)?.clone();
+                let environment = run.environment_for(entry);
+                let config = Self::resolve_request_config_in(&request, Some(&environment));
+                Some(Task::perform(
+                    run_request(request, config, environment),
+                    move |result| Message::RunnerRequestCompleted(run_id, entry, result),
+                ))
+            })
+            .collect();
+        Task::batch(tasks)
+    }
+
+    /// Values to mask when `request` is logged or exported, secret variables of the active
+    /// environment included
+    fn secrets_of(&self, request: &RequestConfig) -> Vec<String> {
+        redact::secrets(
+            request,
+            self.active_environment
+                .and_then(|index| self.environments.get(index)),
+        )
+    }
+
+    /// Sends `request`, first running its token request when the token it uses is missing
+    /// or expired
+    fn send_with_token_refresh(
+        &mut self,
+        mut request: RequestConfig,
+        request_start_time: Instant,
+    ) -> Task<Message> {
+        if let Err(e) = self.prepare_body_for_send(&mut request) {
+            self.toasts.error("The JSON body is not valid JSON", e);
+            return Task::none();
+        }
+        self.request_panel
+            .sent_bodies
+            .insert(request.id.clone(), request.body.clone());
+        let refresh = &request.token_refresh;
+        if refresh.is_configured()
+            && refresh.request_id != request.id
+            && let Some(token_request) = find_request(&self.collections, &refresh.request_id)
+        {
+            match self
+                .active_environment
+                .and_then(|index| self.environments.get(index))
+            {
+                Some(environment) => {
+                    let variable = refresh.variable.trim();
+                    let token = environment
+                        .get_variable(variable)
+                        .map(String::as_str)
+                        .unwrap_or_default();
+                    let fetched_at = self
+                        .token_fetched_at
+                        .get(&(environment.name.clone(), variable.to_string()))
+                        .copied();
+
+                    if token_refresh::needs_refresh(
+                        token,
+                        fetched_at,
+                        refresh.ttl_seconds,
+                        chrono::Utc::now().timestamp(),
+                    ) {
+                        info!("Refreshing {} with {}", variable, token_request.name);
+                        let token_config = self.resolve_request_config_variables(token_request);
+                        return Task::perform(send_request(token_config), move |result| {
+                            Message::TokenRefreshCompleted(
+                                Box::new(request),
+                                request_start_time,
+                                result,
+                            )
+                        });
+                    }
+                }
+                None => self
+                    .toasts
+                    .info("Select an environment to store refreshed tokens"),
+            }
+        }
+
+        let resolved_config = self.resolve_request_config_variables(&request);
+        self.handle_send_request(resolved_config, request_start_time)
+    }
+
+    /// Handles sending a request with the provided resolved config
+    fn handle_send_request(
+        &mut self,
+        config: RequestConfig,
+        request_start_time: Instant,
+    ) -> Task<Message> {
+        // Ask for confirmation before sending a payload above the upload limit
+        let (headers_size, body_size) = request_payload_size(&config);
+        if config.max_upload_size > 0 && (headers_size + body_size) as u64 > config.max_upload_size
+        {
+            self.pending_oversized_send = Some((config, request_start_time));
+            return Task::none();
+        }
+
+        self.dispatch_request(config, request_start_time)
+    }
+
+    /// Sends the request without checking it against the upload limit
+    fn dispatch_request(
+        &mut self,
+        config: RequestConfig,
+        request_start_time: Instant,
+    ) -> Task<Message> {
+        if self
+            .queue_offer
+            .as_ref()
+            .is_some_and(|request| request.id == config.id)
+        {
+            self.queue_offer = None;
+        }
+        if self
+            .download_limit_offer
+            .as_ref()
+            .is_some_and(|request| request.id == config.id)
+        {
+            self.download_limit_offer = None;
+        }
+
+        if self.last_opened_request.is_some() {
+            self.last_opened_request = Some(config.id.clone());
+        }
+
+        // A send replaces the automatic retry of an earlier one
+        self.pending_retries.remove(&config.id);
+
+        let request_id = config.id.clone();
+        let streamed = StreamedBody::default();
+        let (task, handle) = Task::perform(
+            send_request_streaming(config.clone(), Some(streamed.clone())),
+            {
+                let request_id = request_id.clone();
+                move |result| Message::RequestCompleted(request_id, result)
+            },
+        )
+        .abortable();
+
+        let sent = InFlightRequest {
+            config,
+            started_at: request_start_time,
+            handle,
+            streamed,
+            retries: 0,
+        };
+        if let Some(previous) = self.in_flight.insert(request_id, sent) {
+            previous.handle.abort();
         }
-        info!("DEBUG: Resolved Params");
 
-        // Resolve variables in body
-        resolved_config.body = self.resolve_variables(&resolved_config.body);
-        info!("DEBUG: Resolved Body");
+        task
+    }
 
-        // Resolve variables in authentication fields
-        resolved_config.bearer_token = self.resolve_variables(&resolved_config.bearer_token);
-        resolved_config.basic_username = self.resolve_variables(&resolved_config.basic_username);
-        resolved_config.basic_password = self.resolve_variables(&resolved_config.basic_password);
-        resolved_config.api_key = self.resolve_variables(&resolved_config.api_key);
-        resolved_config.api_key_header = self.resolve_variables(&resolved_config.api_key_header);
+    /// Whether the request on screen is waiting for its response
+    fn is_loading(&self) -> bool {
+        self.in_flight.contains_key(&self.current_request.id)
+    }
 
-        resolved_config
+    /// Aborts an in-flight send, its response is dropped
+    fn cancel_request(&mut self, request_id: &str) {
+        if let Some(sent) = self.in_flight.remove(request_id) {
+            sent.handle.abort();
+            info!("Cancelled request {}", request_id);
+        }
     }
 
-    /// Handles sending a request with the provided resolved config
-    fn handle_send_request(
-        &mut self,
-        config: RequestConfig,
-        request_start_time: Instant,
-    ) -> Task<Message> {
-        self.is_loading = true;
-        self.request_start_time = Some(request_start_time);
+    fn retry_queued_request(&mut self, id: u64) -> Task<Message> {
+        match self.offline_queue.iter_mut().find(|queued| queued.id == id) {
+            Some(queued) if !queued.in_flight => {
+                queued.in_flight = true;
+                let config = queued.request.clone();
 
-        if let Some((collection_index, request_index)) = self.last_opened_request {
-            if collection_index != config.collection_index || request_index != config.request_index
-            {
-                self.last_opened_request = Some((config.collection_index, config.request_index));
+                Task::perform(send_request(config), move |result| {
+                    Message::QueuedRequestCompleted(id, result)
+                })
             }
+            _ => Task::none(),
+        }
+    }
+
+    fn error_response(error: &RequestError) -> ResponseData {
+        ResponseData {
+            status: 0,
+            status_text: error.label().to_string(),
+            headers: vec![],
+            body: format!("{}\n\nHint: {}", error, error.hint()),
+            content_type: "text/plain".to_string(),
+            is_binary: false,
+            size: 0,
+            compressed_size: None,
+            time: 0,
+            raw_request: String::new(),
+            body_file: None,
+            certificates: vec![],
+            test_results: vec![],
         }
+    }
+    fn pane_grid_view(&self) -> Element<'_, Message> {
+        PaneGrid::new(&self.panes, |_id, pane, is_maximized| {
+            let content: Element<'_, Message> = match pane {
+                PaneContent::Collections if self.layout.sidebar_collapsed => {
+                    container(pane_control_button(
+                        IconName::PanelLeftOpen,
+                        Message::ToggleSidebar,
+                        "a11y-show-sidebar",
+                    ))
+                    .center_x(Fill)
+                    .padding([8, 0])
+                    .into()
+                }
+                PaneContent::Collections => FloatingElement::new(
+                    self.collections_view(),
+                    row![
+                        pane_control_button(IconName::Wrench, Message::ToggleTools, "a11y-tools"),
+                        pane_control_button(
+                            IconName::Inbox,
+                            Message::ToggleWebhookInbox,
+                            "a11y-webhook-inbox",
+                        ),
+                        pane_control_button(
+                            IconName::History,
+                            Message::ToggleAuditLog,
+                            "a11y-audit-log",
+                        ),
+                        pane_control_button(
+                            IconName::Settings,
+                            Message::ToggleSettings,
+                            "a11y-settings",
+                        ),
+                        pane_control_button(
+                            IconName::PanelLeftClose,
+                            Message::ToggleSidebar,
+                            "a11y-hide-sidebar",
+                        ),
+                    ]
+                    .spacing(2),
+                )
+                .position(AnchorPosition::BottomRight)
+                .offset(Vector::new(6.0, 6.0))
+                .into(),
+                PaneContent::RequestConfig => FloatingElement::new(
+                    self.request_config_view(),
+                    maximize_button(PaneContent::RequestConfig, is_maximized),
+                )
+                .position(AnchorPosition::BottomRight)
+                .offset(Vector::new(6.0, 6.0))
+                .into(),
+                PaneContent::Response => FloatingElement::new(
+                    self.response_view(),
+                    row![
+                        pane_control_button(
+                            match self.layout.response_position {
+                                ResponsePosition::Right => IconName::PanelBottom,
+                                ResponsePosition::Bottom => IconName::PanelRight,
+                            },
+                            Message::ToggleResponsePosition,
+                            match self.layout.response_position {
+                                ResponsePosition::Right => "a11y-response-below",
+                                ResponsePosition::Bottom => "a11y-response-right",
+                            },
+                        ),
+                        maximize_button(PaneContent::Response, is_maximized),
+                    ]
+                    .spacing(2),
+                )
+                .position(AnchorPosition::BottomRight)
+                .offset(Vector::new(6.0, 6.0))
+                .into(),
+            };
 
-        Task::perform(send_request(config), Message::RequestCompleted)
+            container(content)
+                .width(Fill)
+                .height(Fill)
+                .padding(0)
+                .into()
+        })
+        .on_resize(10, Message::PaneResized)
+        .spacing(1)
+        .style(|_theme| pane_grid::Style {
+            hovered_region: pane_grid::Highlight {
+                background: iced::Background::Color(iced::Color::TRANSPARENT),
+                border: iced::Border::default(),
+            },
+            hovered_split: pane_grid::Line {
+                color: iced::Color::from_rgb(0.9, 0.9, 0.9), // Gray
+                width: 0.0,
+            },
+            picked_split: pane_grid::Line {
+                color: iced::Color::from_rgb(0.9, 0.9, 0.9), // Gray
+                width: 0.0,
+            },
+        })
+        .into()
     }
+
     fn collections_view(&self) -> Element<'_, Message> {
         // collections_panel(&self.collections, self.last_opened_request)
-        self.collection_panel
-            .view(&self.collections, self.last_opened_request)
-            .map(Message::CollectionPanel)
+        let in_flight: Vec<&str> = self.in_flight.keys().map(String::as_str).collect();
+        let drafts: Vec<&str> = self.drafts.iter().map(String::as_str).collect();
+        let pinned: Vec<&str> = self
+            .layout
+            .pinned_requests
+            .iter()
+            .map(String::as_str)
+            .collect();
+        let collections = self
+            .collection_panel
+            .view(
+                &self.collections,
+                self.last_opened_request.as_deref(),
+                &in_flight,
+                &drafts,
+                &pinned,
+                &self.monitor_status,
+            )
+            .map(Message::CollectionPanel);
+
+        if self.offline_queue.is_empty() {
+            collections
+        } else {
+            column![
+                container(collections).height(Fill),
+                self.queue_panel
+                    .view(&self.offline_queue)
+                    .map(Message::QueuePanel)
+            ]
+            .into()
+        }
     }
 
     fn request_config_view(&self) -> Element<'_, Message> {
-        self.request_panel
+        let request_panel = self
+            .request_panel
             .view(
                 &self.current_request,
                 &self.request_body_content,
                 &self.post_script_content,
-                self.is_loading,
+                &self.assertion_schema_content,
+                self.is_loading(),
                 &self.environments,
                 self.active_environment,
+                &self.collections,
+                RequestPreview::new(&self.resolve_request_config_variables(&self.current_request)),
             )
-            .map(Message::RequestPanel)
+            .map(Message::RequestPanel);
+
+        if !self.drafts.contains(&self.current_request.id) {
+            return request_panel;
+        }
+
+        // The edits are in the draft until saved, the request's file keeps the saved version
+        let draft_bar = container(
+            row![
+                text(tr("draft-unsaved"))
+                    .size(12)
+                    .color(Color::from_rgb(0.6, 0.35, 0.0)),
+                space().width(Fill),
+                button(text(tr("draft-revert")).size(12))
+                    .on_press(Message::RevertToSaved)
+                    .padding([3, 8])
+                    .style(button::text),
+                button(text(tr("draft-save")).size(12))
+                    .on_press(Message::SaveCurrentRequest)
+                    .padding([3, 8])
+                    .style(button::primary),
+            ]
+            .spacing(6)
+            .align_y(iced::Alignment::Center),
+        )
+        .padding([4, 10])
+        .style(|_theme| container::Style {
+            background: Some(Color::from_rgb(1.0, 0.97, 0.88).into()),
+            ..container::Style::default()
+        });
+
+        column![draft_bar, request_panel].into()
     }
 
     fn response_view(&self) -> Element<'_, Message> {
         self.response_panel
             .view(
                 &self.current_request.last_response,
+                &self.current_request.run_history,
+                &self.current_request.examples,
+                &self.current_request.cache_validators,
+                self.secrets_of(&self.current_request),
+                self.current_request
+                    .last_response
+                    .as_ref()
+                    .filter(|_| self.current_request.audit_security_headers)
+                    .map(|response| security_headers::audit(response, &self.current_request.url)),
                 &self.response_body_content,
-                self.is_loading,
+                self.is_loading(),
                 self.current_elapsed_time,
+                self.current_request.latency_budget,
+                self.queue_offer
+                    .as_ref()
+                    .is_some_and(|request| request.id == self.current_request.id),
+                self.download_limit_offer
+                    .as_ref()
+                    .is_some_and(|request| request.id == self.current_request.id),
+                self.next_pages.contains_key(&self.current_request.id),
+                self.pending_retries
+                    .get(&self.current_request.id)
+                    .map(|retry| {
+                        (
+                            retry
+                                .due
+                                .saturating_duration_since(Instant::now())
+                                .as_secs_f64()
+                                .ceil() as u64,
+                            retry.attempt,
+                        )
+                    }),
+                self.full_response_body.as_ref().map(String::len),
             )
             .map(Message::ResponsePanel)
     }
 
+    fn oversized_send_modal_view<'a>(&self, config: &RequestConfig) -> Element<'a, Message> {
+        let (headers_size, body_size) = request_payload_size(config);
+
+        let description = tr_args(
+            "modal-request-too-large-description",
+            &[
+                ("total", &(headers_size + body_size)),
+                ("headers", &headers_size),
+                ("body", &body_size),
+                ("limit", &config.max_upload_size),
+            ],
+        );
+
+        let buttons = container(
+            row![
+                button(text(tr("cancel")).size(16))
+                    .on_press(Message::CancelOversizedSend)
+                    .padding(10)
+                    .style(modal_secondary_button_style),
+                space().width(10),
+                button(text(tr("modal-send-anyway")).size(16))
+                    .on_press(Message::ConfirmOversizedSend)
+                    .padding(10)
+                    .style(modal_primary_button_style),
+            ]
+            .align_y(iced::Alignment::Center),
+        )
+        .width(Fill)
+        .align_x(iced::Alignment::End);
+
+        container(column![
+            text(tr("modal-request-too-large")).size(18),
+            space().height(10),
+            text(description).size(14),
+            space().height(20),
+            buttons,
+        ])
+        .padding(20)
+        .style(modal_card_style)
+        .into()
+    }
+
+    fn connectivity_modal_view<'a>(
+        &self,
+        target: &Target,
+        report: Option<&ConnectivityReport>,
+    ) -> Element<'a, Message> {
+        let step_view = |name: String, step: Option<&connectivity::CheckStep>| {
+            let (mark, color, detail) = match step {
+                Some(step) => match &step.result {
+                    Ok(detail) => (
+                        "✓",
+                        Color::from_rgb(0.25, 0.63, 0.17),
+                        format!("{} ({} ms)", detail, step.elapsed.as_millis()),
+                    ),
+                    Err(detail) => (
+                        "✗",
+                        Color::from_rgb(0.82, 0.06, 0.22),
+                        format!("{} ({} ms)", detail, step.elapsed.as_millis()),
+                    ),
+                },
+                None => (
+                    "–",
+                    Color::from_rgb(0.6, 0.6, 0.6),
+                    tr("connectivity-not-run"),
+                ),
+            };
+
+            row![
+                text(mark).size(14).color(color).width(16),
+                column![
+                    text(name).size(14),
+                    text(detail).size(12).color(Color::from_rgb(0.4, 0.4, 0.4))
+                ]
+                .spacing(2),
+            ]
+            .spacing(6)
+        };
+
+        let results: Element<'a, Message> = match report {
+            None => text(tr_args("connectivity-checking", &[("target", target)]))
+                .size(14)
+                .into(),
+            Some(report) => {
+                let mut steps = column![
+                    step_view(tr("connectivity-dns"), Some(&report.dns)),
+                    step_view(tr("connectivity-tcp"), report.tcp.as_ref()),
+                ]
+                .spacing(10);
+                if report.target.tls {
+                    steps = steps.push(step_view(tr("connectivity-tls"), report.tls.as_ref()));
+                }
+                column![steps, text(report.summary()).size(14)]
+                    .spacing(15)
+                    .into()
+            }
+        };
+
+        let buttons = container(
+            row![
+                button(text(tr("connectivity-check-again")).size(16))
+                    .on_press_maybe(report.is_some().then_some(Message::CheckConnectivity))
+                    .padding(10)
+                    .style(modal_secondary_button_style),
+                space().width(10),
+                button(text(tr("close")).size(16))
+                    .on_press(Message::CloseConnectivity)
+                    .padding(10)
+                    .style(modal_primary_button_style),
+            ]
+            .align_y(iced::Alignment::Center),
+        )
+        .width(Fill)
+        .align_x(iced::Alignment::End);
+
+        container(column![
+            text(tr_args("connectivity-title", &[("target", target)])).size(18),
+            space().height(15),
+            results,
+            space().height(20),
+            buttons,
+        ])
+        .padding(20)
+        .style(modal_card_style)
+        .into()
+    }
+
+    fn save_conflict_modal_view<'a>(&self, conflict: &RequestConfig) -> Element<'a, Message> {
+        let description = tr_args("conflict-description", &[("name", &conflict.name)]);
+
+        let buttons = container(
+            row![
+                button(text(tr("cancel")).size(16))
+                    .on_press(Message::DismissConflict)
+                    .padding(10)
+                    .style(modal_secondary_button_style),
+                space().width(Fill),
+                button(text(tr("conflict-reload")).size(16))
+                    .on_press(Message::ConflictReload)
+                    .padding(10)
+                    .style(modal_secondary_button_style),
+                space().width(10),
+                button(text(tr("conflict-merge")).size(16))
+                    .on_press(Message::ConflictMerge)
+                    .padding(10)
+                    .style(modal_secondary_button_style),
+                space().width(10),
+                button(text(tr("conflict-overwrite")).size(16))
+                    .on_press(Message::ConflictOverwrite)
+                    .padding(10)
+                    .style(modal_primary_button_style),
+            ]
+            .align_y(iced::Alignment::Center),
+        )
+        .width(Fill);
+
+        container(column![
+            text(tr("conflict-title")).size(18),
+            space().height(10),
+            text(description).size(14),
+            space().height(20),
+            buttons,
+        ])
+        .padding(20)
+        .style(modal_card_style)
+        .into()
+    }
+
+    fn crash_recovery_modal_view<'a>(&self, recovery: &Recovery) -> Element<'a, Message> {
+        let crash_log = recovery::crash_log(&storage::StorageConfig::default().base_path);
+        let description = tr_args(
+            "recovery-description",
+            &[
+                ("message", &recovery.message),
+                ("path", &crash_log.display()),
+            ],
+        );
+        let restorable = self.recoverable_request();
+
+        let buttons = match restorable {
+            Some(_) => row![
+                space().width(Fill),
+                button(text(tr("recovery-discard")).size(16))
+                    .on_press(Message::DismissRecovery)
+                    .padding(10)
+                    .style(modal_secondary_button_style),
+                space().width(10),
+                button(text(tr("recovery-restore")).size(16))
+                    .on_press(Message::RestoreRecovery)
+                    .padding(10)
+                    .style(modal_primary_button_style),
+            ],
+            None => row![
+                space().width(Fill),
+                button(text(tr("close")).size(16))
+                    .on_press(Message::DismissRecovery)
+                    .padding(10)
+                    .style(modal_primary_button_style),
+            ],
+        }
+        .align_y(iced::Alignment::Center);
+
+        container(
+            column![
+                text(tr("recovery-title")).size(18),
+                space().height(10),
+                text(description).size(14),
+            ]
+            .push(restorable.map(|request| {
+                column![
+                    space().height(10),
+                    text(tr_args("recovery-restore-hint", &[("name", &request.name)])).size(14),
+                ]
+            }))
+            .push(space().height(20))
+            .push(buttons),
+        )
+        .padding(20)
+        .style(modal_card_style)
+        .into()
+    }
+
+    fn dropped_import_modal_view<'a>(
+        &self,
+        name: &str,
+        imported: &Result<Imported, String>,
+    ) -> Element<'a, Message> {
+        let (description, can_import) = match imported {
+            Ok(imported) => (
+                tr_args("import-file-question", &[("summary", &imported.summary())]),
+                true,
+            ),
+            Err(e) => (tr_args("import-file-error", &[("error", e)]), false),
+        };
+
+        let buttons = container(
+            row![
+                button(text(tr("cancel")).size(16))
+                    .on_press(Message::CancelDroppedImport)
+                    .padding(10)
+                    .style(modal_secondary_button_style),
+                space().width(10),
+                button(text(tr("import")).size(16))
+                    .on_press_maybe(can_import.then_some(Message::ConfirmDroppedImport))
+                    .padding(10)
+                    .style(modal_primary_button_style),
+            ]
+            .align_y(iced::Alignment::Center),
+        )
+        .width(Fill)
+        .align_x(iced::Alignment::End);
+
+        container(column![
+            text(tr_args("import-file-title", &[("name", &name)])).size(18),
+            space().height(10),
+            text(description).size(14),
+            space().height(20),
+            buttons,
+        ])
+        .padding(20)
+        .style(modal_card_style)
+        .into()
+    }
+
+    fn new_request_modal_view<'a>(
+        &'a self,
+        draft: &'a collections::NewRequestDraft,
+    ) -> Element<'a, Message> {
+        let folders: Vec<collections::FolderOption> = self
+            .collections
+            .iter()
+            .map(|collection| collections::FolderOption {
+                id: collection.id.clone(),
+                name: collection.name.clone(),
+            })
+            .collect();
+        let selected_folder = folders
+            .iter()
+            .find(|folder| folder.id == draft.collection_id)
+            .cloned();
+
+        let label = |label: String| text(label).size(14).width(80);
+
+        let form = column![
+            row![
+                label(tr("new-request-name")),
+                text_input(&tr("new-request-name-placeholder"), &draft.name)
+                    .id(self.collection_panel.new_request_name_id.clone())
+                    .on_input(|name| Message::CollectionPanel(
+                        collections::Message::NewRequestNameChanged(name)
+                    ))
+                    .on_submit(Message::CollectionPanel(
+                        collections::Message::NewRequestNameSubmitted
+                    ))
+                    .padding(8)
+                    .size(14),
+            ]
+            .align_y(iced::Alignment::Center),
+            row![
+                label(tr("new-request-request")),
+                pick_list(HttpMethod::ALL, Some(draft.method.clone()), |method| {
+                    Message::CollectionPanel(collections::Message::NewRequestMethodChanged(method))
+                })
+                .text_size(14),
+                space().width(8),
+                text_input("https://... (or \"POST https://...\")", &draft.url)
+                    .id(self.collection_panel.new_request_url_id.clone())
+                    .on_input(|url| Message::CollectionPanel(
+                        collections::Message::NewRequestUrlChanged(url)
+                    ))
+                    .on_submit(Message::CollectionPanel(
+                        collections::Message::ConfirmNewRequest
+                    ))
+                    .padding(8)
+                    .size(14),
+            ]
+            .align_y(iced::Alignment::Center),
+            row![
+                label(tr("new-request-folder")),
+                pick_list(folders, selected_folder, |folder| {
+                    Message::CollectionPanel(collections::Message::NewRequestFolderChanged(folder))
+                })
+                .text_size(14)
+                .width(Fill),
+            ]
+            .align_y(iced::Alignment::Center),
+        ]
+        .spacing(10);
+
+        let buttons = container(
+            row![
+                button(text(tr("cancel")).size(16))
+                    .on_press(Message::CollectionPanel(
+                        collections::Message::CancelNewRequest
+                    ))
+                    .padding(10)
+                    .style(modal_secondary_button_style),
+                space().width(10),
+                button(text(tr("new-request-create")).size(16))
+                    .on_press(Message::CollectionPanel(
+                        collections::Message::ConfirmNewRequest
+                    ))
+                    .padding(10)
+                    .style(modal_primary_button_style),
+            ]
+            .align_y(iced::Alignment::Center),
+        )
+        .width(Fill)
+        .align_x(iced::Alignment::End);
+
+        container(column![
+            text(tr("new-request-title")).size(18),
+            space().height(10),
+            form,
+            space().height(20),
+            buttons,
+        ])
+        .padding(20)
+        .style(modal_card_style)
+        .into()
+    }
+
+    fn import_modal_view(&self) -> Element<'_, Message> {
+        let input_field = text_input("https://...", &self.import_url)
+            .on_input(Message::ImportUrlChanged)
+            .on_submit(Message::ConfirmImport)
+            .padding(10)
+            .size(16);
+
+        let status: Element<'_, Message> = match &self.import_status {
+            _ if self.is_importing => text(tr("import-downloading")).size(14).into(),
+            Some(Ok(summary)) => text(summary).size(14).color(color!(0x40a02b)).into(),
+            Some(Err(e)) => text(e).size(14).color(color!(0xd20f39)).into(),
+            None => space().height(0).into(),
+        };
+
+        let buttons = container(
+            row![
+                button(text(tr("close")).size(16))
+                    .on_press(Message::HideImportModal)
+                    .padding(10)
+                    .style(modal_secondary_button_style),
+                space().width(10),
+                button(text(tr("import")).size(16))
+                    .on_press_maybe(
+                        (!self.is_importing && !self.import_url.trim().is_empty())
+                            .then_some(Message::ConfirmImport)
+                    )
+                    .padding(10)
+                    .style(modal_primary_button_style),
+            ]
+            .align_y(iced::Alignment::Center),
+        )
+        .width(Fill)
+        .align_x(iced::Alignment::End);
+
+        container(column![
+            text(tr("collections-import-url")).size(18),
+            space().height(10),
+            text(tr("import-url-hint")).size(14),
+            space().height(10),
+            input_field,
+            space().height(10),
+            status,
+            space().height(10),
+            buttons,
+        ])
+        .padding(20)
+        .style(modal_card_style)
+        .into()
+    }
+
     fn rename_modal_view(&self) -> Element<'_, Message> {
         let (title, description) = match &self.rename_target {
-            Some(RenameTarget::Folder(_)) => ("Rename Folder", "Enter a new name for the folder:"),
-            Some(RenameTarget::Request(_, _)) => {
-                ("Rename Request", "Enter a new name for the request:")
-            }
-            None => ("Rename", "Enter a new name:"),
+            Some(RenameTarget::Folder(_)) => (tr("rename-folder"), tr("rename-folder-hint")),
+            Some(RenameTarget::Request(_)) => (tr("rename-request"), tr("rename-request-hint")),
+            None => (tr("rename"), tr("rename-hint")),
         };
 
         let header =
             row![text(title).size(18), space().width(Fill),].align_y(iced::Alignment::Center);
 
-        let input_field = text_input("Enter new name...", &self.rename_input)
+        let input_field = text_input(&tr("rename-placeholder"), &self.rename_input)
             .on_input(Message::RenameInputChanged)
             .on_submit(Message::ConfirmRename)
             .padding(10)
             .size(16);
 
         let buttons = container(
-            row![
-                button(text("Cancel").size(16))
-                    .on_press(Message::HideRenameModal)
-                    .padding(10)
-                    .style(|_theme: &Theme, status| {
-                        let base = button::Style::default();
-                        match status {
-                            button::Status::Hovered => button::Style {
-                                background: Some(iced::Background::Color(color!(0xe4e4e7))),
-                                border: iced::Border {
-                                    color: color!(0xa1a1aa),
-                                    width: 0.0,
-                                    radius: 8.0.into(),
-                                },
-                                text_color: color!(0x18181b),
-                                snap: true,
-                                ..base
-                            },
-                            _ => button::Style {
-                                background: Some(iced::Background::Color(Color::WHITE)),
-                                border: iced::Border {
-                                    color: color!(0xe4e4e7),
-                                    width: 1.0,
-                                    radius: 8.0.into(),
-                                },
-                                text_color: color!(0x3f3f46),
-                                snap: true,
-                                ..base
-                            },
-                        }
-                    }),
+            row![
+                button(text(tr("cancel")).size(16))
+                    .on_press(Message::HideRenameModal)
+                    .padding(10)
+                    .style(modal_secondary_button_style),
                 space().width(10),
-                button(text("Rename").size(16))
+                button(text(tr("rename")).size(16))
                     .on_press(Message::ConfirmRename)
                     .padding(10)
-                    .style(|_theme: &Theme, status| {
-                        let base = button::Style::default();
-                        match status {
-                            button::Status::Hovered => button::Style {
-                                background: Some(iced::Background::Color(color!(0x4f46e5))),
-                                border: iced::Border {
-                                    color: color!(0x818cf8),
-                                    width: 0.0,
-                                    radius: 8.0.into(),
-                                },
-                                text_color: Color::WHITE,
-                                snap: true,
-                                ..base
-                            },
-                            _ => button::Style {
-                                background: Some(iced::Background::Color(color!(0x818cf8))),
-                                border: iced::Border {
-                                    color: color!(0xc7d2fe),
-                                    width: 0.0,
-                                    radius: 8.0.into(),
-                                },
-                                text_color: Color::WHITE,
-                                snap: true,
-                                ..base
-                            },
-                        }
-                    }),
+                    .style(modal_primary_button_style),
             ]
             .align_y(iced::Alignment::Center),
         )
@@ -2356,31 +5647,47 @@ impl BeamApp {
             buttons,
         ])
         .padding(20)
-        .style(|_theme: &Theme| container::Style {
-            background: Some(iced::Background::Color(Color::WHITE)),
-            border: iced::Border {
-                color: Color::from_rgb(0.7, 0.7, 0.7),
-                width: 1.0,
-                radius: 8.0.into(),
-            },
-            shadow: iced::Shadow {
-                color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
-                offset: Vector::new(0.0, 4.0),
-                blur_radius: 10.0,
-            },
-            snap: true,
-            ..Default::default()
-        })
+        .style(modal_card_style)
         .into()
     }
 
     fn subscription(&self) -> iced::Subscription<Message> {
-        let timer_subscription = if self.is_loading {
-            iced::time::every(std::time::Duration::from_millis(100)).map(|_| Message::TimerTick)
+        let timer_subscription =
+            if self.is_loading() || !self.pending_retries.is_empty() || self.is_run_waiting() {
+                iced::time::every(std::time::Duration::from_millis(100)).map(|_| Message::TimerTick)
+            } else {
+                iced::Subscription::none()
+            };
+
+        // Periodically retry queued requests until connectivity returns
+        let queue_subscription = if !self.offline_queue.is_empty() && self.queue_panel.auto_retry {
+            iced::time::every(std::time::Duration::from_secs(15))
+                .map(|_| Message::RetryQueuedRequests)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // Show the requests the webhook inbox received
+        let webhook_subscription = if self.webhook_panel.is_listening() {
+            iced::time::every(std::time::Duration::from_millis(500))
+                .map(|_| Message::WebhookPanel(webhook::Message::Poll))
         } else {
             iced::Subscription::none()
         };
 
+        // Expire the toasts shown
+        let toast_subscription = if self.toasts.is_empty() {
+            iced::Subscription::none()
+        } else {
+            iced::time::every(std::time::Duration::from_millis(500)).map(|_| Message::ToastTick)
+        };
+
+        // One timer for the checks that can wait a couple of seconds: theme files and
+        // environments.toml edited in another program, layout changes persisted in batches
+        // rather than on every resize event, and failures reported by background tasks
+        let periodic_subscription =
+            iced::time::every(std::time::Duration::from_secs(2)).map(|_| Message::PeriodicChecks);
+
         let keyboard_subscription = iced::event::listen_with(|event, status, _id| {
             // For Escape key, allow it through even when captured by a widget (e.g. text_input
             // in the search bar), so we can close the search bar with Esc.
@@ -2402,15 +5709,71 @@ impl BeamApp {
                 iced::Event::Keyboard(iced::keyboard::Event::ModifiersChanged(modifiers)) => {
                     Some(Message::ModifiersChanged(modifiers))
                 }
+                iced::Event::Window(iced::window::Event::FileDropped(path)) => {
+                    Some(Message::FileDropped(path))
+                }
+                iced::Event::Window(iced::window::Event::Resized(size)) => {
+                    Some(Message::WindowResized(size))
+                }
+                iced::Event::Window(iced::window::Event::Moved(position)) => {
+                    Some(Message::WindowMoved(position))
+                }
                 _ => None,
             }
         });
 
-        iced::Subscription::batch([timer_subscription, keyboard_subscription])
+        // Pick up links and files handed over by later launches
+        let launch_subscription = self.launches.subscription().map(Message::Launched);
+
+        // Check the monitored requests, each on its own interval
+        let monitored = self
+            .collections
+            .iter()
+            .flat_map(|collection| &collection.requests)
+            .any(|request| request.monitor.enabled);
+        let monitor_subscription = if monitored {
+            iced::time::every(std::time::Duration::from_secs(1)).map(|_| Message::CheckMonitors)
+        } else {
+            iced::Subscription::none()
+        };
+
+        let tray_subscription = if self.tray.is_some() {
+            iced::time::every(std::time::Duration::from_millis(300)).map(|_| Message::CheckTray)
+        } else {
+            iced::Subscription::none()
+        };
+
+        iced::Subscription::batch([
+            timer_subscription,
+            queue_subscription,
+            webhook_subscription,
+            periodic_subscription,
+            toast_subscription,
+            launch_subscription,
+            tray_subscription,
+            monitor_subscription,
+            keyboard_subscription,
+        ])
     }
 
-    fn initialize_debouncer(&mut self) {
-        let (debounce_tx, mut debounce_rx) = mpsc::channel::<RequestConfig>(10);
+    /// Starts the task writing requests in order, returning the task that reports the
+    /// autosaves it had to refuse
+    fn initialize_debouncer(&mut self) -> Task<Message> {
+        let Some(storage) = self.storage.clone() else {
+            self.toasts.error(
+                "Failed to open the workspace",
+                "Requests won't be saved".to_string(),
+            );
+            return Task::none();
+        };
+        let (conflict_tx, conflict_rx) = iced::futures::channel::mpsc::unbounded();
+        let writer = RequestWriter {
+            storage,
+            conflicts: conflict_tx,
+        };
+        self.writer = Some(writer.clone());
+
+        let (debounce_tx, mut debounce_rx) = mpsc::channel::<PendingWrite>(10);
         self.debounce_tx = Some(debounce_tx);
 
         // Start the debouncer task
@@ -2420,15 +5783,34 @@ impl BeamApp {
 
             loop {
                 match tokio::time::timeout(duration, debounce_rx.recv()).await {
-                    Ok(Some(request_config)) => {
+                    Ok(Some(PendingWrite::Draft(request_config))) => {
                         // Received a new request, store it and continue waiting
                         last_request = Some(request_config);
                         info!("Debouncer received request update");
                     }
+                    Ok(Some(PendingWrite::Save(request_config))) => {
+                        // The edits held back are part of what's saved
+                        if last_request
+                            .as_ref()
+                            .is_some_and(|pending| pending.id == request_config.id)
+                        {
+                            last_request = None;
+                        }
+                        writer.commit_request(request_config);
+                    }
+                    Ok(Some(PendingWrite::Revert(path))) => {
+                        if last_request
+                            .as_ref()
+                            .is_some_and(|pending| pending.path == path)
+                        {
+                            last_request = None;
+                        }
+                        writer.discard_draft(&path);
+                    }
                     Ok(None) => {
                         // Channel closed, save any pending request and exit
                         if let Some(request) = last_request {
-                            Self::save_request(request);
+                            writer.save_draft(request);
                         }
                         info!("Debounce channel closed");
                         break;
@@ -2437,44 +5819,311 @@ impl BeamApp {
                         // Timeout occurred, save the last request if any
                         if let Some(request) = last_request.take() {
                             info!("Debounce save request");
-                            Self::save_request(request);
+                            writer.save_draft(request);
                         }
                     }
                 }
             }
         });
+
+        Task::run(conflict_rx, Message::SaveConflict)
+    }
+
+    /// The workspace storage, for a task to take along
+    fn storage(&self) -> Result<storage::StorageManager, storage::StorageError> {
+        self.storage
+            .clone()
+            .ok_or(storage::StorageError::NotInitialized)
+    }
+
+    /// The in-memory copy of a conflicting request, which may have been edited further
+    /// since its autosave was refused
+    fn latest_request(&mut self, conflict: RequestConfig) -> RequestConfig {
+        find_request_mut(&mut self.collections, &conflict.id)
+            .map(|request| request.clone())
+            .unwrap_or(conflict)
+    }
+
+    /// The request a crash kept edits of, when it still exists and the edits aren't already
+    /// in its draft
+    fn recoverable_request(&self) -> Option<&RequestConfig> {
+        let request = self.crash_recovery.as_ref()?.request.as_ref()?;
+        let current = find_request(&self.collections, &request.id)?;
+        let unchanged = serde_json::to_value(current).ok() == serde_json::to_value(request).ok();
+        (!unchanged).then_some(request)
+    }
+
+    /// Replaces a request with a version read from disk without saving it back
+    fn apply_loaded_request(&mut self, request: RequestConfig) {
+        if request.id == self.current_request.id {
+            self.request_panel.reset_undo_histories(&request);
+            Self::update_editor_content(&mut self.request_body_content, request.body.clone());
+            Self::update_editor_content(
+                &mut self.post_script_content,
+                request.post_request_script.clone().unwrap_or_default(),
+            );
+            Self::update_editor_content(
+                &mut self.assertion_schema_content,
+                request.assertions.json_schema.clone(),
+            );
+            self.current_request = request.clone();
+        }
+
+        if let Some(existing) = find_request_mut(&mut self.collections, &request.id) {
+            *existing = request;
+        }
+    }
+
+    /// Reports the outcome of an import in the import dialog and as a toast, since
+    /// dropped files are imported without the dialog open
+    fn set_import_status(&mut self, status: Result<String, String>) {
+        match &status {
+            Ok(summary) => self.toasts.success(summary.clone()),
+            Err(e) => self.toasts.error("Import failed", e.clone()),
+        }
+        self.import_status = Some(status);
     }
 
-    fn save_request(request_config: RequestConfig) {
-        match storage::StorageManager::with_default_config() {
-            Ok(storage_manager) => {
-                info!("===request auto saved (debounced)");
+    /// Toasts the names of failed assertions or script tests, `kind` names which
+    fn report_failed_tests(&mut self, kind: &str, results: &[TestResult]) {
+        let failed: Vec<&str> = results
+            .iter()
+            .filter(|test| !test.passed)
+            .map(|test| test.name.as_str())
+            .collect();
+        if !failed.is_empty() {
+            self.toasts.error(
+                format!("{} of {} {} failed", failed.len(), results.len(), kind),
+                failed.join("\n"),
+            );
+        }
+    }
 
-                if let Err(e) = storage_manager
+    /// Stores a new request in the collection with id `collection_id` and opens it
+    fn add_request_to_collection(&mut self, collection_id: &str, request_config: RequestConfig) {
+        if let Some(collection) = find_collection_mut(&mut self.collections, collection_id) {
+            let mut new_req = request_config;
+            // Get the new request path using the storage manager
+            if let Some(storage_manager) = &self.storage {
+                let new_request_path = storage_manager
                     .storage()
-                    .save_request_by_path(&request_config)
+                    .get_new_request_path_from_collection(collection);
+
+                new_req.path = PathBuf::from(new_request_path);
+            } else {
+                error!("failed to get storage manager");
+                return;
+            };
+
+            self.last_opened_request = Some(new_req.id.clone());
+
+            let change =
+                AuditEntry::new(AuditOperation::Create, AuditSubject::Request, &new_req.name)
+                    .in_collection(&collection.name);
+            collection.requests.push(new_req.clone());
+            self.record_change(change);
+
+            self.current_request = new_req.clone();
+
+            if let Some(writer) = self.writer.clone() {
+                tokio::spawn(async move {
+                    writer.save_request(new_req);
+                });
+            }
+        }
+    }
+
+    /// Appends `change` to the audit log of the workspace. It's written right away so entries
+    /// keep the order changes were made in.
+    fn record_change(&mut self, change: AuditEntry) {
+        let log = AuditLog::new(&storage::StorageConfig::default().base_path);
+        if let Err(e) = log.append(&change) {
+            error!("Failed to record change in {:?}: {}", log.path(), e);
+            self.toasts.error("Failed to record change", e.to_string());
+        }
+    }
+
+    /// Reverses the latest structural change to the workspace
+    fn undo(&mut self) -> Task<Message> {
+        let Some(command) = self.undo_stack.pop() else {
+            self.toasts.info("Nothing to undo");
+            return Task::none();
+        };
+        let summary = command.undo_summary();
+
+        let task = match command {
+            WorkspaceCommand::DeleteRequest {
+                collection_id,
+                position,
+                request,
+            } => {
+                let Some(collection) = self
+                    .collections
+                    .iter_mut()
+                    .find(|collection| collection.id == collection_id)
+                else {
+                    self.toasts.error(
+                        "Can't restore request",
+                        format!("The collection of \"{}\" was deleted", request.name),
+                    );
+                    return Task::none();
+                };
+                let change =
+                    AuditEntry::new(AuditOperation::Create, AuditSubject::Request, &request.name)
+                        .in_collection(&collection.name);
+                let request = *request;
+                collection
+                    .requests
+                    .insert(position.min(collection.requests.len()), request.clone());
+                self.record_change(change);
+
+                // The file was deleted, it's written back where it was
+                if let Some(writer) = self.writer.clone() {
+                    tokio::spawn(async move {
+                        writer.save_request(request);
+                    });
+                }
+                Task::none()
+            }
+            WorkspaceCommand::RenameRequest {
+                request_id,
+                from,
+                to,
+            } => {
+                let Some(collection) = self.collections.iter_mut().find(|collection| {
+                    collection
+                        .requests
+                        .iter()
+                        .any(|request| request.id == request_id)
+                }) else {
+                    self.toasts
+                        .error("Can't rename request back", "The request was deleted");
+                    return Task::none();
+                };
+                let change = AuditEntry::new(AuditOperation::Rename, AuditSubject::Request, &from)
+                    .renamed_from(&to)
+                    .in_collection(&collection.name);
+                if let Some(request) = collection
+                    .requests
+                    .iter_mut()
+                    .find(|request| request.id == request_id)
                 {
-                    error!("Failed to save request: {}", e);
+                    request.name = from.clone();
                 }
+                let collection_name = collection.name.clone();
+                self.record_change(change);
+
+                let storage = self.storage();
+                tokio::spawn(async move {
+                    if let Ok(storage_manager) = storage
+                        && let Err(e) =
+                            storage_manager
+                                .storage()
+                                .rename_request(&collection_name, &to, &from)
+                    {
+                        report_background_error("Failed to rename request file", e.to_string());
+                    }
+                });
+                Task::none()
             }
-            Err(e) => {
-                error!("Failed to create storage manager: {}", e);
+            WorkspaceCommand::DeleteCollection {
+                position,
+                collection,
+            } => {
+                self.record_change(AuditEntry::new(
+                    AuditOperation::Create,
+                    AuditSubject::Collection,
+                    &collection.name,
+                ));
+                self.collections
+                    .insert(position.min(self.collections.len()), collection.clone());
+                self.show_welcome = false;
+
+                let storage = self.storage();
+                Task::perform(
+                    async move {
+                        storage
+                            .and_then(|storage_manager| {
+                                storage_manager
+                                    .storage()
+                                    .save_collection_with_requests(&collection)
+                            })
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::CollectionRestored,
+                )
+            }
+            WorkspaceCommand::RenameCollection {
+                collection_id,
+                from,
+                to,
+            } => {
+                let storage = self.storage();
+                Task::perform(
+                    async move {
+                        let result = storage
+                            .and_then(|storage_manager| {
+                                storage_manager.storage().rename_collection(&to, &from)
+                            })
+                            .map_err(|e| e.to_string());
+
+                        Message::CollectionRenamed {
+                            collection_id,
+                            new_name: from,
+                            result,
+                            undoable: false,
+                        }
+                    },
+                    |msg| msg,
+                )
+            }
+            WorkspaceCommand::DeleteVariable {
+                environment,
+                key,
+                variable,
+            } => {
+                let Some(env) = self
+                    .environments
+                    .iter_mut()
+                    .find(|env| env.name == environment)
+                else {
+                    self.toasts.error(
+                        "Can't restore variable",
+                        format!("The environment \"{}\" was deleted or renamed", environment),
+                    );
+                    return Task::none();
+                };
+                env.variables.insert(key, variable);
+                self.update(Message::SaveEnvironments)
+            }
+        };
+
+        self.toasts.success(summary);
+        task
+    }
+
+    /// Records the environments renamed while the environment popup was open
+    fn record_environment_renames(&mut self) {
+        let mut renamed: Vec<(usize, String)> = self.renamed_environments.drain().collect();
+        renamed.sort();
+        for (index, previous_name) in renamed {
+            let Some(environment) = self.environments.get(index) else {
+                continue;
+            };
+            if environment.name != previous_name {
+                let change = AuditEntry::new(
+                    AuditOperation::Rename,
+                    AuditSubject::Environment,
+                    &environment.name,
+                )
+                .renamed_from(previous_name);
+                self.record_change(change);
             }
         }
     }
 
     fn update_request_state(&mut self, request_config: RequestConfig) {
-        // If the user hasn't made any edits yet, ensure the URL baseline is synced.
-        // This prevents undo from clearing the entire input on the first edit.
-        if !self
-            .request_panel
-            .url_input
-            .has_history(&self.request_panel.history_registry)
-        {
-            self.request_panel
-                .url_input
-                .set_value(request_config.url.clone());
-        }
+        self.request_panel.sync_undo_histories(&request_config);
         self.current_request = request_config.clone();
 
         if self.request_body_content.text() != self.current_request.body {
@@ -2484,43 +6133,380 @@ impl BeamApp {
             );
         }
 
-        if let Some(collection) = self
-            .collections
-            .get_mut(self.current_request.collection_index)
-        {
-            if let Some(request) = collection
-                .requests
-                .get_mut(self.current_request.request_index)
-            {
-                *request = self.current_request.clone();
-            }
+        if let Some(request) = find_request_mut(&mut self.collections, &self.current_request.id) {
+            *request = self.current_request.clone();
         }
 
-        // Send to debounce channel if available
-        if let Some(tx) = &self.debounce_tx {
-            let _ = tx.try_send(request_config);
+        self.queue_draft(request_config);
+    }
+
+    /// Keeps the edits of a request in its draft, written once typing pauses
+    /// The pinned requests that still exist, labelled with their collection
+    fn pinned_requests(&self) -> Vec<PinnedRequest> {
+        self.layout
+            .pinned_requests
+            .iter()
+            .filter_map(|id| {
+                self.collections.iter().find_map(|collection| {
+                    collection
+                        .requests
+                        .iter()
+                        .find(|request| request.id == *id)
+                        .map(|request| PinnedRequest {
+                            id: id.clone(),
+                            label: format!("{} / {}", collection.name, request.name),
+                        })
+                })
+            })
+            .collect()
+    }
+
+    /// Shows, updates or removes the tray icon to match the settings and pins
+    fn sync_tray(&mut self) {
+        if !self.layout.show_tray {
+            self.tray = None;
+            return;
+        }
+
+        let pinned = self.pinned_requests();
+        match &mut self.tray {
+            Some(tray) => tray.set_pinned(&pinned),
+            None => match Tray::new(&pinned) {
+                Ok(tray) => self.tray = Some(tray),
+                Err(e) => {
+                    self.layout.show_tray = false;
+                    self.toasts.error("Could not show the tray icon", e);
+                }
+            },
+        }
+    }
+
+    /// Follows a beam:// link: opens the request it names or offers the import it links to
+    fn open_link(&mut self, link: &str) -> Task<Message> {
+        let link = match DeepLink::parse(link) {
+            Ok(link) => link,
+            Err(e) => {
+                self.toasts.error("Could not open the link", e);
+                return Task::none();
+            }
+        };
+
+        match link {
+            DeepLink::Open {
+                collection,
+                request,
+            } => {
+                let Some(collection_index) = self
+                    .collections
+                    .iter()
+                    .position(|c| c.id == collection || c.name.eq_ignore_ascii_case(&collection))
+                else {
+                    self.toasts.error(
+                        "Could not open the link",
+                        format!("There's no collection \"{}\"", collection),
+                    );
+                    return Task::none();
+                };
+
+                self.collections[collection_index].expanded = true;
+                let Some(request) = request else {
+                    return Task::none();
+                };
+                let Some(request_id) = self.collections[collection_index]
+                    .requests
+                    .iter()
+                    .find(|r| r.id == request || r.name.eq_ignore_ascii_case(&request))
+                    .map(|r| r.id.clone())
+                else {
+                    self.toasts.error(
+                        "Could not open the link",
+                        format!(
+                            "There's no request \"{}\" in \"{}\"",
+                            request, self.collections[collection_index].name
+                        ),
+                    );
+                    return Task::none();
+                };
+
+                self.update(Message::CollectionPanel(
+                    collections::Message::RequestSelected(request_id),
+                ))
+            }
+            DeepLink::Import { url } => {
+                self.import_url = url;
+                self.import_status = None;
+                self.show_import_modal = true;
+                Task::none()
+            }
         }
     }
 
-    fn theme(&self) -> Theme {
-        // Catppuccin Mocha color palette
-        let base = Color::from_rgb(0.118, 0.118, 0.180); // #1e1e2e
-        let text = Color::from_rgb(0.804, 0.839, 0.957); // #cdd6f4
-        let primary = Color::from_rgb(0.537, 0.706, 0.980); // #89b4fa (blue)
-        let success = Color::from_rgb(0.651, 0.890, 0.631); // #a6e3a1 (green)
-        let danger = Color::from_rgb(0.953, 0.545, 0.659); // #f38ba8 (red)
-        let warning = Color::from_rgb(0.980, 0.706, 0.529); // #fab387 (peach)
+    /// Loads the saved versions of the current request when the History tab is open
+    fn load_versions(&mut self) -> Task<Message> {
+        if self.request_panel.selected_tab != RequestTab::History {
+            return Task::none();
+        }
 
-        Theme::custom(
-            "Catppuccin Mocha".to_string(),
-            iced::theme::Palette {
-                background: base,
-                text,
-                primary,
-                success,
-                danger,
-                warning,
+        self.request_panel.versions = None;
+        let request_id = self.current_request.id.clone();
+        Task::perform(
+            {
+                let request_id = request_id.clone();
+                async move {
+                    VersionStore::new(&storage::StorageConfig::default().base_path)
+                        .history(&request_id)
+                        .map_err(|e| e.to_string())
+                }
+            },
+            move |versions| {
+                Message::RequestPanel(request::Message::VersionsLoaded(
+                    request_id.clone(),
+                    versions,
+                ))
             },
         )
     }
+
+    /// Replaces the body of the current request as an edit, which can be undone
+    fn replace_request_body(&mut self, body: String) {
+        self.request_body_content
+            .perform(text_editor::Action::SelectAll);
+        self.request_body_content
+            .perform(text_editor::Action::Edit(text_editor::Edit::Paste(
+                std::sync::Arc::new(body),
+            )));
+        self.current_request.body = self.request_body_content.text();
+        self.request_panel
+            .sync_undo_histories(&self.current_request);
+
+        if let Some(request) = find_request_mut(&mut self.collections, &self.current_request.id) {
+            *request = self.current_request.clone();
+        }
+
+        // Save the updated request
+        self.queue_draft(self.current_request.clone());
+    }
+
+    /// Rewrites the JSON body of the current request, if it's valid JSON
+    fn format_json_body(&mut self, formatting: JsonFormatting) {
+        if self.current_request.body_format != BodyFormat::Json {
+            return;
+        }
+        if let Some(body) = formatting.apply(&self.current_request.body)
+            && body != self.current_request.body
+        {
+            self.replace_request_body(body);
+        }
+    }
+
+    /// Formats the JSON body of `request` as set up to before sending it, and fails if it
+    /// must be valid JSON and isn't. The open request is formatted in its editor too.
+    fn prepare_body_for_send(&mut self, request: &mut RequestConfig) -> Result<(), String> {
+        if request.body_format != BodyFormat::Json || request.body.trim().is_empty() {
+            return Ok(());
+        }
+        if request.id == self.current_request.id && request.body == self.current_request.body {
+            self.format_json_body(self.layout.json_body.on_send);
+            request.body = self.current_request.body.clone();
+        } else if let Some(body) = self.layout.json_body.on_send.apply(&request.body) {
+            request.body = body;
+        }
+
+        if self.layout.json_body.block_malformed {
+            let resolved = self.resolve_request_config_variables(request);
+            serde_json::from_str::<serde_json::Value>(&resolved.body).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn queue_draft(&mut self, request: RequestConfig) {
+        recovery::track(&request);
+        self.drafts.insert(request.id.clone());
+        self.write_in_order(PendingWrite::Draft(request));
+    }
+
+    /// Hands a write to the debouncer, after the edits it's still holding back
+    fn write_in_order(&self, write: PendingWrite) {
+        if let Some(tx) = &self.debounce_tx
+            && tx.try_send(write).is_err()
+        {
+            info!("Debounce channel is full or closed");
+        }
+    }
+
+    /// The selected theme, `None` to follow the system's light or dark look
+    fn theme(&self) -> Option<Theme> {
+        self.layout
+            .theme
+            .as_deref()
+            .and_then(|name| self.themes.find(name))
+            .map(|theme| theme.to_theme())
+    }
+}
+
+/// A `{{variable}}` reference, capturing its name
+static VARIABLE_REFERENCE: std::sync::LazyLock<regex::Regex> =
+    std::sync::LazyLock::new(|| regex::Regex::new(r"\{\{([^}]+)\}\}").unwrap());
+
+/// Replaces `{{variable}}` references with values from the environment and `{{$name}}`
+/// placeholders with generated data. Values may reference other variables; `stack` holds
+/// the variables currently being expanded so cycles and chains deeper than
+/// `MAX_VARIABLE_DEPTH` are left unresolved.
+fn resolve_environment_variables(
+    environment: &Environment,
+    input: &str,
+    stack: &mut Vec<String>,
+) -> String {
+    let re = &*VARIABLE_REFERENCE;
+    re.replace_all(input, |captures: &regex::Captures| {
+        let var_name = captures[1].trim();
+        if let Some(generated) = faker::resolve_placeholder(var_name) {
+            return generated;
+        }
+
+        let Some(var_value) = environment.get_variable(var_name) else {
+            return captures[0].to_string();
+        };
+
+        if stack.iter().any(|name| name == var_name) {
+            warn!(
+                "Variable cycle detected: {} -> {}",
+                stack.join(" -> "),
+                var_name
+            );
+            return captures[0].to_string();
+        }
+
+        if stack.len() >= MAX_VARIABLE_DEPTH {
+            warn!("Variable {} exceeds the nesting limit", var_name);
+            return captures[0].to_string();
+        }
+
+        stack.push(var_name.to_string());
+        let resolved = resolve_environment_variables(environment, var_value, stack);
+        stack.pop();
+        resolved
+    })
+    .into_owned()
+}
+
+/// Shows the window, restoring it when minimized
+fn bring_to_front() -> Task<Message> {
+    iced::window::latest().and_then(|id| {
+        Task::batch([
+            iced::window::minimize(id, false),
+            iced::window::gain_focus(id),
+        ])
+    })
+}
+
+/// Sends a request of a collection run, checks its response and runs its post-request script
+async fn run_request(
+    request: RequestConfig,
+    config: RequestConfig,
+    environment: Environment,
+) -> RunResult {
+    let response = match send_request(config).await {
+        Ok(mut response) => {
+            response.test_results = assertions::evaluate(&request.assertions, &response);
+            if let Some(api_contract) = &request.contract {
+                response
+                    .test_results
+                    .extend(contract::check(api_contract, &response));
+            }
+            response
+        }
+        Err(error) => BeamApp::error_response(&error),
+    };
+
+    let now = chrono::Utc::now();
+    let mut result = RunResult::new(&response);
+    result.budget = (request.latency_budget > 0).then_some(request.latency_budget);
+    result.wait = rate_limit::parse(&response.headers, now)
+        .and_then(|limit| limit.wait(now))
+        .and_then(|wait| wait.to_std().ok());
+    result.retry_after = rate_limit::retry_delay(&response, now);
+    if response.status != 0
+        && let Some(script) = request.post_request_script.clone()
+        && !script.trim().is_empty()
+    {
+        let script_result =
+            script::execute_post_request_script(&script, request, response, &environment);
+        result.extracted = script_result.environment_changes;
+        result.test_results.extend(script_result.test_results);
+        result.next_request = script_result.next_request;
+        if !script_result.success {
+            result.script_error = Some(
+                script_result
+                    .error_message
+                    .unwrap_or_else(|| "Unknown error".to_string()),
+            );
+        }
+    }
+    result
+}
+
+/// A file to import, by name, and what it would import
+fn dropped_file(path: &std::path::Path) -> (String, Result<Imported, String>) {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    (name, import::parse_file(path))
+}
+
+fn modal_secondary_button_style(_theme: &Theme, status: button::Status) -> button::Style {
+    let base = button::Style::default();
+    match status {
+        button::Status::Hovered => button::Style {
+            background: Some(iced::Background::Color(color!(0xe4e4e7))),
+            border: iced::Border {
+                color: color!(0xa1a1aa),
+                width: 0.0,
+                radius: 8.0.into(),
+            },
+            text_color: color!(0x18181b),
+            snap: true,
+            ..base
+        },
+        _ => button::Style {
+            background: Some(iced::Background::Color(Color::WHITE)),
+            border: iced::Border {
+                color: color!(0xe4e4e7),
+                width: 1.0,
+                radius: 8.0.into(),
+            },
+            text_color: color!(0x3f3f46),
+            snap: true,
+            ..base
+        },
+    }
+}
+
+fn modal_primary_button_style(_theme: &Theme, status: button::Status) -> button::Style {
+    let base = button::Style::default();
+    match status {
+        button::Status::Hovered => button::Style {
+            background: Some(iced::Background::Color(color!(0x4f46e5))),
+            border: iced::Border {
+                color: color!(0x818cf8),
+                width: 0.0,
+                radius: 8.0.into(),
+            },
+            text_color: Color::WHITE,
+            snap: true,
+            ..base
+        },
+        _ => button::Style {
+            background: Some(iced::Background::Color(color!(0x818cf8))),
+            border: iced::Border {
+                color: color!(0xc7d2fe),
+                width: 0.0,
+                radius: 8.0.into(),
+            },
+            text_color: Color::WHITE,
+            snap: true,
+            ..base
+        },
+    }
 }
@@ -0,0 +1,237 @@
+use crate::faker;
+use crate::types::{MockBehavior, ResponseExample};
+use regex::Regex;
+use serde_json::Value;
+use std::time::Duration;
+
+/// The parts of an incoming request a mocked response can echo back
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MockRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl MockRequest {
+    /// Builds the request from its method, its target (`/users/1?page=2` or a full URL),
+    /// headers and body
+    pub fn new(method: &str, target: &str, headers: Vec<(String, String)>, body: String) -> Self {
+        let target = target.split('#').next().unwrap_or_default();
+        let target = match target.split_once("://") {
+            Some((_, rest)) => rest.find('/').map(|index| &rest[index..]).unwrap_or("/"),
+            None => target,
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        let query = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                let decode = |part: &str| {
+                    urlencoding::decode(&part.replace('+', " "))
+                        .map(|part| part.into_owned())
+                        .unwrap_or_else(|_| part.to_string())
+                };
+                (decode(key), decode(value))
+            })
+            .collect();
+
+        Self {
+            method: method.to_uppercase(),
+            path: if path.is_empty() {
+                "/".to_string()
+            } else {
+                path.to_string()
+            },
+            query,
+            headers,
+            body,
+        }
+    }
+
+    /// Value of a `request.*` placeholder, empty when the request doesn't have it
+    fn lookup(&self, reference: &str) -> String {
+        let (field, key) = reference.split_once('.').unwrap_or((reference, ""));
+
+        match (field, key) {
+            ("method", "") => self.method.clone(),
+            ("path", "") => self.path.clone(),
+            ("path", index) => index
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| self.path.split('/').filter(|s| !s.is_empty()).nth(index))
+                .unwrap_or_default()
+                .to_string(),
+            ("query", name) => self
+                .query
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default(),
+            ("header", name) => self
+                .headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default(),
+            ("body", "") => self.body.clone(),
+            ("body", path) => json_value(&self.body, path).unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Reads a dotted path such as `user.id` or `items.0.name` in a JSON body, strings
+/// without their quotes
+fn json_value(body: &str, path: &str) -> Option<String> {
+    let json: Value = serde_json::from_str(body).ok()?;
+
+    let mut value = &json;
+    for key in path.split('.') {
+        value = match value {
+            Value::Array(items) => items.get(key.parse::<usize>().ok()?)?,
+            _ => value.get(key)?,
+        };
+    }
+
+    Some(match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    })
+}
+
+/// Fills in the placeholders of an example: `{{request.method}}`, `{{request.path}}`,
+/// `{{request.path.0}}`, `{{request.query.name}}`, `{{request.header.name}}`,
+/// `{{request.body}}` and `{{request.body.json.path}}` echo the request, `{{$randomUUID}}`
+/// and the other dynamic variables generate data. Anything else is left as is.
+pub fn render_template(template: &str, request: &MockRequest) -> String {
+    if !template.contains("{{") {
+        return template.to_string();
+    }
+
+    let re = Regex::new(r"\{\{([^}]+)\}\}").unwrap();
+    re.replace_all(template, |captures: &regex::Captures| {
+        let reference = captures[1].trim();
+        match reference.strip_prefix("request.") {
+            Some(reference) => request.lookup(reference),
+            None => {
+                faker::resolve_placeholder(reference).unwrap_or_else(|| captures[0].to_string())
+            }
+        }
+    })
+    .into_owned()
+}
+
+/// What the mock answers to one request
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockReply {
+    pub status: u16,
+    pub status_text: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    /// How long to wait before answering
+    pub delay: Duration,
+}
+
+/// Answers `request` with `example`, or with the example's error status when `roll`, a
+/// number drawn from 0 to 99, falls within its error rate
+pub fn mock_reply(example: &ResponseExample, request: &MockRequest, roll: u8) -> MockReply {
+    let MockBehavior {
+        latency_ms,
+        error_rate,
+        error_status,
+    } = example.mock;
+    let delay = Duration::from_millis(latency_ms);
+
+    if roll < error_rate.min(100) {
+        let status_text = reqwest::StatusCode::from_u16(error_status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or_default()
+            .to_string();
+        return MockReply {
+            status: error_status,
+            body: format!(
+                r#"{{"error":"Injected failure","status":{}}}"#,
+                error_status
+            ),
+            status_text,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            delay,
+        };
+    }
+
+    MockReply {
+        status: example.status,
+        status_text: example.status_text.clone(),
+        headers: example
+            .headers
+            .iter()
+            .map(|(key, value)| (key.clone(), render_template(value, request)))
+            .collect(),
+        body: render_template(&example.body, request),
+        delay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(body: &str, mock: MockBehavior) -> ResponseExample {
+        ResponseExample {
+            name: "200 OK".to_string(),
+            status: 200,
+            status_text: "OK".to_string(),
+            headers: vec![("X-Echo".to_string(), "{{request.method}}".to_string())],
+            content_type: "application/json".to_string(),
+            body: body.to_string(),
+            saved_at: String::new(),
+            mock,
+        }
+    }
+
+    #[test]
+    fn echoes_the_request_into_the_template() {
+        let request = MockRequest::new(
+            "post",
+            "http://localhost:8080/users/42?name=Ada+L&x",
+            vec![("X-Trace".to_string(), "abc".to_string())],
+            r#"{"user":{"roles":["admin"]}}"#.to_string(),
+        );
+
+        assert_eq!(request.path, "/users/42");
+        assert_eq!(
+            render_template(
+                "{{request.method}} {{request.path.1}} {{ request.query.name }} {{request.header.x-trace}} {{request.body.user.roles.0}} [{{request.query.missing}}] {{unknown}}",
+                &request
+            ),
+            "POST 42 Ada L abc admin [] {{unknown}}"
+        );
+        assert_eq!(render_template("{{$randomUUID}}", &request).len(), 36);
+    }
+
+    #[test]
+    fn injects_failures_and_latency() {
+        let request = MockRequest::new("GET", "/ping?id=7", Vec::new(), String::new());
+        let mock = MockBehavior {
+            latency_ms: 250,
+            error_rate: 30,
+            error_status: 503,
+        };
+        let example = example(r#"{"id":"{{request.query.id}}"}"#, mock);
+
+        let reply = mock_reply(&example, &request, 30);
+        assert_eq!(reply.status, 200);
+        assert_eq!(reply.body, r#"{"id":"7"}"#);
+        assert_eq!(reply.headers[0].1, "GET");
+        assert_eq!(reply.delay, Duration::from_millis(250));
+
+        let reply = mock_reply(&example, &request, 29);
+        assert_eq!(reply.status, 503);
+        assert_eq!(reply.status_text, "Service Unavailable");
+    }
+}
@@ -0,0 +1,134 @@
+use crate::assertions;
+use crate::types::{Monitor, ResponseAssertions, ResponseData};
+use std::time::{Duration, Instant};
+
+/// Checks run every 5 minutes unless the monitor says otherwise
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Shortest interval, so a typo doesn't flood the API
+pub const MIN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Outcome of the latest check of a monitored request
+#[derive(Debug, Clone, PartialEq)]
+pub enum MonitorStatus {
+    Passing,
+    Failing(String), // What went wrong, e.g. `Status is 2xx: Got 503`
+}
+
+/// How long to wait between two checks of `monitor`
+pub fn interval(monitor: &Monitor) -> Duration {
+    if monitor.interval_seconds == 0 {
+        DEFAULT_INTERVAL
+    } else {
+        Duration::from_secs(monitor.interval_seconds).max(MIN_INTERVAL)
+    }
+}
+
+/// Whether a monitor last checked at `last_checked` is to be checked again
+pub fn is_due(monitor: &Monitor, last_checked: Option<Instant>, now: Instant) -> bool {
+    last_checked.is_none_or(|checked| now.saturating_duration_since(checked) >= interval(monitor))
+}
+
+/// Judges a response of a monitored request. Its expected status comes first, then the
+/// request's own assertions, and without either any status below 400 passes.
+pub fn check(
+    monitor: &Monitor,
+    assertions: &ResponseAssertions,
+    response: &ResponseData,
+) -> MonitorStatus {
+    // Failed sends have no status, their label says what went wrong
+    if response.status == 0 {
+        return MonitorStatus::Failing(response.status_text.clone());
+    }
+
+    let mut assertions = assertions.clone();
+    if !monitor.expected_status.trim().is_empty() {
+        assertions.status = monitor.expected_status.clone();
+    }
+
+    let results = assertions::evaluate(&assertions, response);
+    if results.is_empty() {
+        return if response.status < 400 {
+            MonitorStatus::Passing
+        } else {
+            MonitorStatus::Failing(format!("Got {} {}", response.status, response.status_text))
+        };
+    }
+
+    match results.into_iter().find(|result| !result.passed) {
+        Some(failed) => MonitorStatus::Failing(match failed.error_message {
+            Some(message) => format!("{}: {}", failed.name, message),
+            None => failed.name,
+        }),
+        None => MonitorStatus::Passing,
+    }
+}
+
+/// Whether going from `previous` to `current` is worth a notification: a monitor that was
+/// passing, or not checked yet, started failing
+pub fn started_failing(previous: Option<&MonitorStatus>, current: &MonitorStatus) -> bool {
+    matches!(current, MonitorStatus::Failing(_))
+        && !matches!(previous, Some(MonitorStatus::Failing(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, status_text: &str) -> ResponseData {
+        ResponseData {
+            status,
+            status_text: status_text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn checks_responses_against_the_expected_status() {
+        let mut monitor = Monitor::default();
+        let assertions = ResponseAssertions::default();
+
+        assert_eq!(
+            check(&monitor, &assertions, &response(204, "No Content")),
+            MonitorStatus::Passing
+        );
+        assert_eq!(
+            check(&monitor, &assertions, &response(503, "Service Unavailable")),
+            MonitorStatus::Failing("Got 503 Service Unavailable".to_string())
+        );
+        assert_eq!(
+            check(&monitor, &assertions, &response(0, "Connection refused")),
+            MonitorStatus::Failing("Connection refused".to_string())
+        );
+
+        monitor.expected_status = "2xx".to_string();
+        assert_eq!(
+            check(&monitor, &assertions, &response(302, "Found")),
+            MonitorStatus::Failing("Status is 2xx: Got 302".to_string())
+        );
+    }
+
+    #[test]
+    fn notifies_only_when_a_monitor_starts_failing() {
+        let failing = MonitorStatus::Failing("Got 500".to_string());
+
+        assert!(started_failing(None, &failing));
+        assert!(started_failing(Some(&MonitorStatus::Passing), &failing));
+        assert!(!started_failing(Some(&failing), &failing));
+        assert!(!started_failing(Some(&failing), &MonitorStatus::Passing));
+    }
+
+    #[test]
+    fn waits_the_interval_between_checks() {
+        let now = Instant::now();
+        let monitor = Monitor {
+            interval_seconds: 1,
+            ..Monitor::default()
+        };
+
+        assert!(is_due(&monitor, None, now));
+        assert!(!is_due(&monitor, Some(now), now + Duration::from_secs(5)));
+        assert!(is_due(&monitor, Some(now), now + MIN_INTERVAL));
+        assert_eq!(interval(&Monitor::default()), DEFAULT_INTERVAL);
+    }
+}
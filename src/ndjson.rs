@@ -0,0 +1,111 @@
+use serde_json::Value;
+
+/// Records shown at first and added by each "Show more"
+pub const PAGE_SIZE: usize = 200;
+
+/// Longest one-line preview of a record
+const PREVIEW_LENGTH: usize = 160;
+
+/// Whether the content type is newline delimited JSON, one record per line
+pub fn is_ndjson(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    matches!(
+        mime.as_str(),
+        "application/x-ndjson"
+            | "application/ndjson"
+            | "application/jsonl"
+            | "application/x-jsonlines"
+            | "application/json-seq"
+            | "application/stream+json"
+    )
+}
+
+/// A line of an NDJSON body
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub value: Result<Value, String>,
+    pub raw: String,
+}
+
+impl Record {
+    pub fn parse(line: &str) -> Record {
+        // json-seq prefixes records with the record separator
+        let raw = line.trim().trim_start_matches('\u{1e}').to_string();
+        Record {
+            value: serde_json::from_str(&raw).map_err(|e| e.to_string()),
+            raw,
+        }
+    }
+
+    /// The record on one line, shortened
+    pub fn preview(&self) -> String {
+        let compact = match &self.value {
+            Ok(value) => value.to_string(),
+            Err(_) => self.raw.clone(),
+        };
+        match compact.char_indices().nth(PREVIEW_LENGTH) {
+            Some((end, _)) => format!("{}…", &compact[..end]),
+            None => compact,
+        }
+    }
+
+    /// The record indented, or as received when it isn't valid JSON
+    pub fn pretty(&self) -> String {
+        self.value
+            .as_ref()
+            .ok()
+            .and_then(|value| serde_json::to_string_pretty(value).ok())
+            .unwrap_or_else(|| self.raw.clone())
+    }
+}
+
+/// The lines holding records, blank lines being skipped
+pub fn lines(body: &str) -> impl Iterator<Item = &str> {
+    body.lines().filter(|line| !line.trim().is_empty())
+}
+
+/// The complete lines of `buffer` past `offset`, with the offset following them. A
+/// trailing partial line is left for the next call.
+pub fn complete_lines(buffer: &[u8], offset: usize) -> Option<(String, usize)> {
+    let end = offset
+        + buffer
+            .get(offset..)?
+            .iter()
+            .rposition(|byte| *byte == b'\n')?
+        + 1;
+    Some((
+        String::from_utf8_lossy(&buffer[offset..end]).into_owned(),
+        end,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_ndjson_content_types() {
+        assert!(is_ndjson("application/x-ndjson; charset=utf-8"));
+        assert!(is_ndjson("application/jsonl"));
+        assert!(!is_ndjson("application/json"));
+    }
+
+    #[test]
+    fn parses_records_and_keeps_partial_lines() {
+        let buffer = b"{\"id\":1}\n\n{\"id\":2,\"tags\":[\"a\"]}\nnot json\n{\"id\":";
+        let (complete, offset) = complete_lines(buffer, 0).unwrap();
+
+        let records: Vec<Record> = lines(&complete).map(Record::parse).collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1].preview(), r#"{"id":2,"tags":["a"]}"#);
+        assert!(records[2].value.is_err());
+        assert_eq!(records[2].pretty(), "not json");
+        assert_eq!(&buffer[offset..], b"{\"id\":");
+        assert_eq!(complete_lines(buffer, offset), None);
+    }
+}
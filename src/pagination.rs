@@ -0,0 +1,326 @@
+use crate::http::send_request;
+use crate::json_path;
+use crate::types::{Pagination, PaginationStyle, RequestConfig, ResponseData};
+use regex::Regex;
+use serde_json::Value;
+use std::sync::LazyLock;
+
+/// Pages fetched at most by "Fetch all pages", the first one included
+pub const MAX_PAGES: usize = 50;
+
+static LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<([^>]*)>((?:\s*;\s*[^;,]+)*)").unwrap());
+
+/// The request for the page after `response`, the one `config` got. `None` on the last page.
+pub fn next_page(config: &RequestConfig, response: &ResponseData) -> Option<RequestConfig> {
+    let url = next_url(&request_url(config)?, &config.pagination, response)?;
+    // The query parameters are part of the next URL now
+    Some(RequestConfig {
+        url,
+        params: Vec::new(),
+        ..config.clone()
+    })
+}
+
+/// URL `config` is sent to, its query parameters included
+fn request_url(config: &RequestConfig) -> Option<String> {
+    let mut url = reqwest::Url::parse(&config.url).ok()?;
+    let params: Vec<&(String, String)> = config
+        .params
+        .iter()
+        .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+        .collect();
+    if !params.is_empty() {
+        url.query_pairs_mut()
+            .extend_pairs(params.iter().map(|(key, value)| (key, value)));
+    }
+    Some(url.to_string())
+}
+
+/// URL of the page after `response`, fetched from `url`. `None` on the last page.
+fn next_url(url: &str, pagination: &Pagination, response: &ResponseData) -> Option<String> {
+    match pagination.style {
+        PaginationStyle::None => None,
+        PaginationStyle::LinkHeader => {
+            let link = response
+                .headers
+                .iter()
+                .filter(|(name, _)| name.eq_ignore_ascii_case("link"))
+                .find_map(|(_, value)| next_link(value))?;
+            // Relative links are resolved against the page they came with
+            let base = reqwest::Url::parse(url).ok()?;
+            base.join(&link).ok().map(|next| next.to_string())
+        }
+        PaginationStyle::Cursor => {
+            let body: Value = serde_json::from_str(&response.body).ok()?;
+            let cursor = match json_path::select(&body, &pagination.cursor_path)
+                .ok()?
+                .first()?
+            {
+                Value::String(cursor) if !cursor.is_empty() => cursor.clone(),
+                Value::Number(cursor) => cursor.to_string(),
+                _ => return None,
+            };
+            with_query_param(url, &pagination.param, &cursor)
+        }
+        PaginationStyle::Page | PaginationStyle::Offset => {
+            let received = items(&response.body, &pagination.items_path).ok()?.len();
+            if received == 0 {
+                return None;
+            }
+
+            let current = reqwest::Url::parse(url)
+                .ok()?
+                .query_pairs()
+                .find(|(name, _)| name == pagination.param.as_str())
+                .and_then(|(_, value)| value.parse::<u64>().ok());
+            let next = if pagination.style == PaginationStyle::Page {
+                current.unwrap_or(1) + 1
+            } else {
+                current.unwrap_or(0) + received as u64
+            };
+            with_query_param(url, &pagination.param, &next.to_string())
+        }
+    }
+}
+
+/// Target of the `rel="next"` link of a Link header
+fn next_link(header: &str) -> Option<String> {
+    LINK.captures_iter(header).find_map(|link| {
+        let is_next = link[2].split(';').any(|param| {
+            param.split_once('=').is_some_and(|(name, value)| {
+                name.trim().eq_ignore_ascii_case("rel")
+                    && value
+                        .trim()
+                        .trim_matches('"')
+                        .split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("next"))
+            })
+        });
+        is_next.then(|| link[1].trim().to_string())
+    })
+}
+
+/// `url` with the query parameter `name` set to `value`, other parameters kept in order
+fn with_query_param(url: &str, name: &str, value: &str) -> Option<String> {
+    if name.trim().is_empty() {
+        return None;
+    }
+
+    let mut url = reqwest::Url::parse(url).ok()?;
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    match pairs.iter_mut().find(|(key, _)| key == name) {
+        Some(pair) => pair.1 = value.to_string(),
+        None => pairs.push((name.to_string(), value.to_string())),
+    }
+    url.query_pairs_mut().clear().extend_pairs(pairs);
+    Some(url.to_string())
+}
+
+/// Items of a page: the array `items_path` points to, or the body when it's empty
+pub fn items(body: &str, items_path: &str) -> Result<Vec<Value>, String> {
+    let body: Value =
+        serde_json::from_str(body).map_err(|e| format!("The page isn't JSON: {}", e))?;
+    if items_path.trim().is_empty() {
+        return match body {
+            Value::Array(items) => Ok(items),
+            _ => Err("Set the path to the items, the page isn't an array".to_string()),
+        };
+    }
+
+    let selected = json_path::select(&body, items_path)?;
+    Ok(match selected.as_slice() {
+        [Value::Array(items)] => items.clone(),
+        _ => selected.into_iter().cloned().collect(),
+    })
+}
+
+/// Items of every page, as one indented JSON array
+pub fn merge(pages: &[String], items_path: &str) -> Result<String, String> {
+    let mut all = Vec::new();
+    for (index, page) in pages.iter().enumerate() {
+        let page_items =
+            items(page, items_path).map_err(|e| format!("Page {}: {}", index + 1, e))?;
+        all.extend(page_items);
+    }
+    serde_json::to_string_pretty(&Value::Array(all)).map_err(|e| e.to_string())
+}
+
+/// Pages fetched one after the other, merged into one body
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchedPages {
+    pub pages: usize,
+    pub items: usize,
+    pub body: String,
+    /// Whether `MAX_PAGES` was reached before the last page
+    pub incomplete: bool,
+}
+
+/// Follows the pages after `first`, the response `config` got, and merges their items
+pub async fn fetch_all(config: RequestConfig, first: ResponseData) -> Result<FetchedPages, String> {
+    let pagination = config.pagination.clone();
+    let mut pages = vec![first.body.clone()];
+    let mut next = next_page(&config, &first);
+
+    while let Some(page_config) = next.take() {
+        if pages.len() >= MAX_PAGES {
+            next = Some(page_config);
+            break;
+        }
+
+        let response = send_request(page_config.clone())
+            .await
+            .map_err(|e| format!("Page {}: {}", pages.len() + 1, e))?;
+        if response.status >= 400 {
+            return Err(format!(
+                "Page {}: {} {}",
+                pages.len() + 1,
+                response.status,
+                response.status_text
+            ));
+        }
+
+        next = next_page(&page_config, &response);
+        pages.push(response.body);
+    }
+
+    let body = merge(&pages, &pagination.items_path)?;
+    Ok(FetchedPages {
+        pages: pages.len(),
+        items: pages
+            .iter()
+            .map(|page| items(page, &pagination.items_path).map_or(0, |items| items.len()))
+            .sum(),
+        body,
+        incomplete: next.is_some(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(headers: Vec<(&str, &str)>, body: &str) -> ResponseData {
+        ResponseData {
+            status: 200,
+            status_text: "OK".to_string(),
+            headers: headers
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: body.to_string(),
+            content_type: "application/json".to_string(),
+            size: body.len(),
+            ..Default::default()
+        }
+    }
+
+    fn pagination(style: PaginationStyle, param: &str, path: &str) -> Pagination {
+        Pagination {
+            style,
+            param: param.to_string(),
+            cursor_path: path.to_string(),
+            items_path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn follows_link_headers() {
+        let links = response(
+            vec![(
+                "Link",
+                r#"<https://api.example.com/repos?page=1>; rel="prev", </repos?page=3>; rel="next last""#,
+            )],
+            "[]",
+        );
+        let style = pagination(PaginationStyle::LinkHeader, "", "");
+
+        assert_eq!(
+            next_url("https://api.example.com/repos?page=2", &style, &links).as_deref(),
+            Some("https://api.example.com/repos?page=3")
+        );
+        assert_eq!(
+            next_url(
+                "https://api.example.com/repos",
+                &style,
+                &response(vec![], "[]")
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn sends_back_cursors_and_advances_pages_and_offsets() {
+        let cursor = pagination(PaginationStyle::Cursor, "after", "$.meta.next");
+        assert_eq!(
+            next_url(
+                "https://api.example.com/items?limit=2&after=a",
+                &cursor,
+                &response(vec![], r#"{"meta":{"next":"b c"}}"#)
+            )
+            .as_deref(),
+            Some("https://api.example.com/items?limit=2&after=b+c")
+        );
+        assert_eq!(
+            next_url(
+                "https://api.example.com/items",
+                &cursor,
+                &response(vec![], r#"{"meta":{"next":null}}"#)
+            ),
+            None
+        );
+
+        let page = pagination(PaginationStyle::Page, "page", "");
+        assert_eq!(
+            next_url("https://x.test/items", &page, &response(vec![], "[1,2]")).as_deref(),
+            Some("https://x.test/items?page=2")
+        );
+        assert_eq!(
+            next_url("https://x.test/items", &page, &response(vec![], "[]")),
+            None
+        );
+
+        let offset = pagination(PaginationStyle::Offset, "offset", "$.data");
+        assert_eq!(
+            next_url(
+                "https://x.test/items?offset=10",
+                &offset,
+                &response(vec![], r#"{"data":[1,2,3]}"#)
+            )
+            .as_deref(),
+            Some("https://x.test/items?offset=13")
+        );
+    }
+
+    #[test]
+    fn folds_query_parameters_into_the_next_page() {
+        let config = RequestConfig {
+            url: "https://x.test/items?limit=2".to_string(),
+            params: vec![
+                ("page".to_string(), "3".to_string()),
+                (String::new(), String::new()),
+            ],
+            pagination: pagination(PaginationStyle::Page, "page", ""),
+            ..RequestConfig::default()
+        };
+        let next = next_page(&config, &response(vec![], "[1,2]")).unwrap();
+
+        assert_eq!(next.url, "https://x.test/items?limit=2&page=4");
+        assert!(next.params.is_empty());
+    }
+
+    #[test]
+    fn merges_the_items_of_every_page() {
+        let pages = vec![
+            r#"{"data":[{"id":1}]}"#.to_string(),
+            r#"{"data":[{"id":2},{"id":3}]}"#.to_string(),
+        ];
+        let merged: Value = serde_json::from_str(&merge(&pages, "$.data").unwrap()).unwrap();
+
+        assert_eq!(merged, serde_json::json!([{"id":1},{"id":2},{"id":3}]));
+        assert!(merge(&pages, "").is_err());
+    }
+}
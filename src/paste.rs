@@ -0,0 +1,399 @@
+use crate::types::{AuthType, BodyFormat, HttpMethod, RequestConfig};
+use serde_json::Value;
+
+/// Where a pasted request came from, shown in the prompt offering to apply it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteSource {
+    Curl,
+    UrlWithBody,
+}
+
+impl PasteSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PasteSource::Curl => "a cURL command",
+            PasteSource::UrlWithBody => "a URL with a JSON body",
+        }
+    }
+}
+
+/// Request details recognised in text pasted into the URL bar
+#[derive(Debug, Clone, PartialEq)]
+pub struct PastedRequest {
+    pub source: PasteSource,
+    pub method: HttpMethod,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    pub basic_auth: Option<(String, String)>,
+}
+
+impl PastedRequest {
+    /// Replaces the method, URL, headers and body of `request`, leaving the rest alone
+    pub fn apply_to(&self, request: &mut RequestConfig) {
+        request.method = self.method.clone();
+        request.url = self.url.clone();
+        request.headers = self.headers.clone();
+        request.body = self.body.clone();
+
+        let content_type = self
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.clone());
+        if let Some(content_type) = &content_type {
+            request.content_type = content_type.clone();
+        }
+
+        request.body_format = if self.body.is_empty() {
+            BodyFormat::None
+        } else if content_type
+            .as_deref()
+            .is_some_and(|ct| ct.contains("json"))
+            || serde_json::from_str::<Value>(&self.body).is_ok()
+        {
+            BodyFormat::Json
+        } else if content_type.as_deref().is_some_and(|ct| ct.contains("xml")) {
+            BodyFormat::Xml
+        } else {
+            BodyFormat::Text
+        };
+
+        if let Some((username, password)) = &self.basic_auth {
+            request.auth_type = AuthType::Basic;
+            request.basic_username = username.clone();
+            request.basic_password = password.clone();
+        }
+    }
+}
+
+/// Recognises a curl command, or a URL followed by a JSON body, in pasted text. Anything
+/// else, including a bare URL, is left to the URL bar.
+pub fn parse(text: &str) -> Option<PastedRequest> {
+    let text = text.trim();
+    let first_word = text.split_whitespace().next()?;
+
+    if first_word.eq_ignore_ascii_case("curl") {
+        parse_curl(text)
+    } else {
+        parse_url_with_body(text)
+    }
+}
+
+/// `[METHOD] URL BODY` where the body is JSON, on one line or several
+fn parse_url_with_body(text: &str) -> Option<PastedRequest> {
+    let (first, rest) = split_word(text);
+    let (method, url, rest) = match HttpMethod::parse(first) {
+        Some(method) => {
+            let (url, rest) = split_word(rest);
+            (Some(method), url, rest)
+        }
+        None => (None, first, rest),
+    };
+
+    if !(url.starts_with("http://") || url.starts_with("https://") || url.starts_with("{{")) {
+        return None;
+    }
+
+    let body = rest.trim();
+    if !(body.starts_with('{') || body.starts_with('[')) {
+        return None;
+    }
+    serde_json::from_str::<Value>(body).ok()?;
+
+    Some(PastedRequest {
+        source: PasteSource::UrlWithBody,
+        method: method.unwrap_or(HttpMethod::POST),
+        url: url.to_string(),
+        headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+        body: body.to_string(),
+        basic_auth: None,
+    })
+}
+
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    text.split_once(char::is_whitespace).unwrap_or((text, ""))
+}
+
+/// curl options taking a value that don't affect the request Beam would send
+const IGNORED_VALUE_OPTIONS: &[&str] = &[
+    "-o",
+    "--output",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "-x",
+    "--proxy",
+    "--cacert",
+    "--cert",
+    "--key",
+    "-w",
+    "--write-out",
+    "-F",
+    "--form",
+    "--retry",
+    "-T",
+    "--upload-file",
+    "--resolve",
+    "-c",
+    "--cookie-jar",
+];
+
+fn parse_curl(command: &str) -> Option<PastedRequest> {
+    let words = shell_words(command);
+    let mut args = words.into_iter().skip(1);
+
+    let mut method = None;
+    let mut url = None;
+    let mut headers = Vec::new();
+    let mut data: Vec<String> = Vec::new();
+    let mut basic_auth = None;
+    let mut data_in_query = false;
+
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') || arg == "-" {
+            url.get_or_insert(arg);
+            continue;
+        }
+
+        // --name=value and -Xvalue forms
+        let (option, inline) = match arg.split_once('=') {
+            Some((option, value)) if arg.starts_with("--") => {
+                (option.to_string(), Some(value.to_string()))
+            }
+            _ if !arg.starts_with("--")
+                && arg.len() > 2
+                && arg[1..].starts_with(['X', 'H', 'd', 'u', 'b', 'A', 'e']) =>
+            {
+                (arg[..2].to_string(), Some(arg[2..].to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+        let mut value = || inline.clone().or_else(|| args.next());
+
+        match option.as_str() {
+            "-X" | "--request" => method = value().and_then(|m| HttpMethod::parse(&m)),
+            "-H" | "--header" => {
+                if let Some(header) = value() {
+                    if let Some((name, value)) = header.split_once(':') {
+                        headers.push((name.trim().to_string(), value.trim().to_string()));
+                    } else if let Some(name) = header.strip_suffix(';') {
+                        // curl's way of sending a header with an empty value
+                        headers.push((name.trim().to_string(), String::new()));
+                    }
+                }
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii"
+            | "--data-urlencode" => data.extend(value()),
+            "--json" => {
+                data.extend(value());
+                headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                headers.push(("Accept".to_string(), "application/json".to_string()));
+            }
+            "-u" | "--user" => {
+                basic_auth = value().map(|user| match user.split_once(':') {
+                    Some((name, password)) => (name.to_string(), password.to_string()),
+                    None => (user, String::new()),
+                });
+            }
+            "-A" | "--user-agent" => {
+                headers.extend(value().map(|agent| ("User-Agent".to_string(), agent)));
+            }
+            "-e" | "--referer" => {
+                headers.extend(value().map(|referer| ("Referer".to_string(), referer)));
+            }
+            "-b" | "--cookie" => {
+                headers.extend(value().map(|cookie| ("Cookie".to_string(), cookie)));
+            }
+            "--url" => url = value(),
+            "-G" | "--get" => data_in_query = true,
+            "-I" | "--head" => method = Some(HttpMethod::HEAD),
+            option if IGNORED_VALUE_OPTIONS.contains(&option) => {
+                value();
+            }
+            // Flags such as --compressed, -k or -sSL
+            _ => {}
+        }
+    }
+
+    let mut url = url?;
+    let mut body = data.join("&");
+
+    if data_in_query && !body.is_empty() {
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&std::mem::take(&mut body));
+    }
+
+    let method = method.unwrap_or(if body.is_empty() {
+        HttpMethod::GET
+    } else {
+        HttpMethod::POST
+    });
+
+    if !body.is_empty()
+        && !headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+    {
+        // What curl sends for -d without an explicit type
+        headers.push((
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        ));
+    }
+
+    Some(PastedRequest {
+        source: PasteSource::Curl,
+        method,
+        url,
+        headers,
+        body,
+        basic_auth,
+    })
+}
+
+/// Splits a shell command into words, handling quotes, `$'..'` strings, backslash escapes
+/// and line continuations
+fn shell_words(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(escaped) => {
+                    word.push(escaped);
+                    in_word = true;
+                }
+                None => {}
+            },
+            '\'' => {
+                in_word = true;
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    word.push(c);
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\'' => break,
+                        '\\' => match chars.next() {
+                            Some('n') => word.push('\n'),
+                            Some('t') => word.push('\t'),
+                            Some('r') => word.push('\r'),
+                            Some(escaped) => word.push(escaped),
+                            None => {}
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' if matches!(chars.peek(), Some('"' | '\\' | '$' | '`' | '\n')) => {
+                            if let Some(escaped) = chars.next()
+                                && escaped != '\n'
+                            {
+                                word.push(escaped);
+                            }
+                        }
+                        c => word.push(c),
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_multiline_curl_command() {
+        let command = r#"curl 'https://api.example.com/users?page=2' \
+  -X PUT \
+  -H 'Content-Type: application/json' \
+  -H "Authorization: Bearer abc" \
+  --data-raw $'{"name":"it\'s me"}' \
+  --compressed -sSL -u admin:secret"#;
+
+        let pasted = parse(command).unwrap();
+
+        assert_eq!(pasted.source, PasteSource::Curl);
+        assert_eq!(pasted.method, HttpMethod::PUT);
+        assert_eq!(pasted.url, "https://api.example.com/users?page=2");
+        assert_eq!(
+            pasted.headers,
+            vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Authorization".to_string(), "Bearer abc".to_string()),
+            ]
+        );
+        assert_eq!(pasted.body, r#"{"name":"it's me"}"#);
+        assert_eq!(
+            pasted.basic_auth,
+            Some(("admin".to_string(), "secret".to_string()))
+        );
+    }
+
+    #[test]
+    fn curl_data_defaults_to_form_post_and_get_moves_it_to_the_query() {
+        let post = parse("curl -d a=1 -d b=2 https://example.com").unwrap();
+        assert_eq!(post.method, HttpMethod::POST);
+        assert_eq!(post.body, "a=1&b=2");
+        assert_eq!(post.headers[0].1, "application/x-www-form-urlencoded");
+
+        let get = parse("curl -G -d q=beam https://example.com/search").unwrap();
+        assert_eq!(get.method, HttpMethod::GET);
+        assert_eq!(get.url, "https://example.com/search?q=beam");
+        assert!(get.body.is_empty());
+    }
+
+    #[test]
+    fn url_followed_by_json_body() {
+        let pasted = parse("PATCH https://example.com/items/1\n{\"done\": true}").unwrap();
+        assert_eq!(pasted.source, PasteSource::UrlWithBody);
+        assert_eq!(pasted.method, HttpMethod::PATCH);
+        assert_eq!(pasted.url, "https://example.com/items/1");
+        assert_eq!(pasted.body, "{\"done\": true}");
+
+        assert_eq!(
+            parse("https://example.com [1, 2]").unwrap().method,
+            HttpMethod::POST
+        );
+        assert!(parse("https://example.com/items?id=1").is_none());
+        assert!(parse("https://example.com {not json").is_none());
+    }
+}
@@ -0,0 +1,110 @@
+/// Path segments of a URL, without the scheme, host, query and fragment. `{{baseUrl}}`-style
+/// prefixes stay as a segment of their own and never look like a path variable.
+fn path_segments(url: &str) -> impl Iterator<Item = &str> {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest
+            .split_once('/')
+            .map(|(_, path)| path)
+            .unwrap_or_default(),
+        None => url,
+    };
+
+    path.split('/')
+}
+
+/// Name of the variable a segment stands for, `:id` and `{id}` are both accepted
+fn variable_name(segment: &str) -> Option<&str> {
+    let name = match segment.strip_prefix(':') {
+        Some(name) => name,
+        None => segment.strip_prefix('{')?.strip_suffix('}')?,
+    };
+
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    valid.then_some(name)
+}
+
+/// Path variables of a URL in order of appearance, each listed once
+pub fn path_variable_names(url: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in path_segments(url).filter_map(variable_name) {
+        if !names.iter().any(|existing| existing == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Replaces path variables that have a value with it, percent-encoded. Variables without a
+/// value are left in place so the problem shows in the sent URL.
+pub fn substitute_path_variables(url: &str, values: &[(String, String)]) -> String {
+    let (base, suffix) = match url.find(['?', '#']) {
+        Some(index) => url.split_at(index),
+        None => (url, ""),
+    };
+    let (prefix, path) = match base.split_once("://") {
+        Some((scheme, rest)) => match rest.split_once('/') {
+            Some((authority, path)) => (format!("{}://{}/", scheme, authority), path),
+            None => return url.to_string(),
+        },
+        None => (String::new(), base),
+    };
+
+    let path = path
+        .split('/')
+        .map(|segment| {
+            variable_name(segment)
+                .and_then(|name| {
+                    values
+                        .iter()
+                        .find(|(key, value)| key == name && !value.is_empty())
+                })
+                .map(|(_, value)| urlencoding::encode(value).into_owned())
+                .unwrap_or_else(|| segment.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    format!("{}{}{}", prefix, path, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_colon_and_brace_variables_but_not_environment_ones() {
+        assert_eq!(
+            path_variable_names("{{baseUrl}}/users/:userId/posts/{postId}?sort=:asc"),
+            vec!["userId", "postId"]
+        );
+        assert_eq!(
+            path_variable_names("http://localhost:8080/orgs/{{org}}/:id/:id"),
+            vec!["id"]
+        );
+        assert!(path_variable_names("https://example.com:443/").is_empty());
+    }
+
+    #[test]
+    fn substitutes_values_and_keeps_the_rest() {
+        let values = vec![
+            ("userId".to_string(), "a b/c".to_string()),
+            ("postId".to_string(), String::new()),
+        ];
+
+        assert_eq!(
+            substitute_path_variables(
+                "https://api.example.com/users/:userId/posts/{postId}?q=:userId",
+                &values
+            ),
+            "https://api.example.com/users/a%20b%2Fc/posts/{postId}?q=:userId"
+        );
+        assert_eq!(
+            substitute_path_variables("{{baseUrl}}/users/{userId}", &values),
+            "{{baseUrl}}/users/a%20b%2Fc"
+        );
+    }
+}
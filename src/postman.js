@@ -0,0 +1,465 @@
+// Postman's script API on top of Beam's, so the test scripts of imported Postman collections
+// run as they do in Postman or newman. Evaluated after `pm` and `beam` are set up, it reads
+// the request and response from `__pmContext` and records tests through `__beamRecordTest`.
+(function () {
+    var context = __pmContext;
+    var record = __beamRecordTest;
+    delete globalThis.__pmContext;
+    delete globalThis.__beamRecordTest;
+
+    function show(value) {
+        if (typeof value === 'string') {
+            return "'" + value + "'";
+        }
+        if (value === undefined) {
+            return 'undefined';
+        }
+        try {
+            return JSON.stringify(value);
+        } catch (e) {
+            return String(value);
+        }
+    }
+
+    function typeOf(value) {
+        if (value === null) {
+            return 'null';
+        }
+        if (Array.isArray(value)) {
+            return 'array';
+        }
+        if (value instanceof RegExp) {
+            return 'regexp';
+        }
+        return typeof value;
+    }
+
+    function equal(a, b) {
+        if (a === b) {
+            return true;
+        }
+        if (typeOf(a) !== typeOf(b)) {
+            return false;
+        }
+        if (typeof a !== 'object' || a === null) {
+            return a !== a && b !== b; // Both NaN
+        }
+        if (Array.isArray(a)) {
+            return a.length === b.length && a.every(function (item, i) {
+                return equal(item, b[i]);
+            });
+        }
+        var keys = Object.keys(a);
+        return keys.length === Object.keys(b).length && keys.every(function (key) {
+            return Object.prototype.hasOwnProperty.call(b, key) && equal(a[key], b[key]);
+        });
+    }
+
+    // How pm.environment.set stores a value
+    function stringify(value) {
+        if (typeof value === 'string') {
+            return value;
+        }
+        if (value === null || value === undefined) {
+            return '';
+        }
+        return typeof value === 'object' ? JSON.stringify(value) : String(value);
+    }
+
+    function header(name) {
+        var wanted = String(name).toLowerCase();
+        var found = context.headers.filter(function (pair) {
+            return pair[0].toLowerCase() === wanted;
+        });
+        return found.length ? found[0][1] : undefined;
+    }
+
+    // A subset of the chai assertions pm.expect offers
+    function Assertion(value, response, message) {
+        this._value = value;
+        this._response = !!response;
+        this._message = message;
+        this._negate = false;
+        this._deep = false;
+    }
+
+    ['to', 'be', 'been', 'is', 'that', 'which', 'and', 'has', 'have', 'with', 'at', 'of',
+        'same', 'does', 'still', 'any', 'all', 'own', 'nested'].forEach(function (name) {
+        Object.defineProperty(Assertion.prototype, name, {
+            get: function () {
+                return this;
+            }
+        });
+    });
+    Object.defineProperty(Assertion.prototype, 'not', {
+        get: function () {
+            this._negate = !this._negate;
+            return this;
+        }
+    });
+    Object.defineProperty(Assertion.prototype, 'deep', {
+        get: function () {
+            this._deep = true;
+            return this;
+        }
+    });
+
+    // Throws when `passed` doesn't match what the chain expects, chai's way of failing
+    Assertion.prototype._check = function (passed, phrase, subject) {
+        if (!!passed === this._negate) {
+            var error = new Error((this._message ? this._message + ': ' : '') + 'expected ' +
+                (subject === undefined ? show(this._value) : subject) +
+                (this._negate ? ' to not ' : ' to ') + phrase);
+            error.name = 'AssertionError';
+            throw error;
+        }
+        return this;
+    };
+
+    var methods = {
+        equal: function (expected) {
+            var passed = this._deep ? equal(this._value, expected) : this._value === expected;
+            return this._check(passed, (this._deep ? 'deeply equal ' : 'equal ') + show(expected));
+        },
+        eql: function (expected) {
+            return this._check(equal(this._value, expected), 'deeply equal ' + show(expected));
+        },
+        above: function (n) {
+            return this._check(this._value > n, 'be above ' + show(n));
+        },
+        least: function (n) {
+            return this._check(this._value >= n, 'be at least ' + show(n));
+        },
+        below: function (n) {
+            return this._check(this._value < n, 'be below ' + show(n));
+        },
+        most: function (n) {
+            return this._check(this._value <= n, 'be at most ' + show(n));
+        },
+        within: function (low, high) {
+            return this._check(this._value >= low && this._value <= high,
+                'be within ' + low + '..' + high);
+        },
+        a: function (type) {
+            return this._check(typeOf(this._value) === String(type).toLowerCase(),
+                'be a ' + type);
+        },
+        include: function (item) {
+            var value = this._value;
+            var passed;
+            if (typeof value === 'string') {
+                passed = value.indexOf(item) !== -1;
+            } else if (Array.isArray(value)) {
+                passed = value.some(function (entry) {
+                    return equal(entry, item);
+                });
+            } else if (value && typeof item === 'object') {
+                passed = Object.keys(item).every(function (key) {
+                    return equal(value[key], item[key]);
+                });
+            } else {
+                passed = false;
+            }
+            return this._check(passed, 'include ' + show(item));
+        },
+        property: function (name, expected) {
+            var value = this._value;
+            var has = value !== null && value !== undefined && name in Object(value);
+            if (arguments.length > 1) {
+                return this._check(has && equal(value[name], expected),
+                    'have property ' + show(name) + ' of ' + show(expected));
+            }
+            this._check(has, 'have property ' + show(name));
+            // Like chai, the assertions after it are about the property
+            if (!this._negate) {
+                this._value = value[name];
+            }
+            return this;
+        },
+        lengthOf: function (n) {
+            var length = this._value === null || this._value === undefined ?
+                undefined : this._value.length;
+            return this._check(length === n, 'have a length of ' + n + ' but got ' + length);
+        },
+        match: function (pattern) {
+            return this._check(pattern.test(this._value), 'match ' + pattern);
+        },
+        oneOf: function (list) {
+            var value = this._value;
+            return this._check(list.some(function (entry) {
+                return equal(entry, value);
+            }), 'be one of ' + show(list));
+        },
+        keys: function () {
+            var wanted = Array.isArray(arguments[0]) ? arguments[0] :
+                Array.prototype.slice.call(arguments);
+            var value = Object(this._value);
+            return this._check(wanted.every(function (key) {
+                return key in value;
+            }), 'have keys ' + show(wanted));
+        },
+        instanceOf: function (constructor) {
+            return this._check(this._value instanceof constructor,
+                'be an instance of ' + (constructor.name || 'the constructor'));
+        },
+        // Assertions about pm.response, as in `pm.response.to.have.status(200)`
+        status: function (expected) {
+            var actual = typeof expected === 'number' ? context.code : context.reason;
+            return this._check(actual === expected, 'have status ' + show(expected),
+                'response with status ' + show(actual));
+        },
+        header: function (name, expected) {
+            var actual = header(name);
+            var passed = arguments.length > 1 ? actual === expected : actual !== undefined;
+            return this._check(passed, 'have header ' + show(name) +
+                (arguments.length > 1 ? ' of ' + show(expected) : ''), 'response');
+        },
+        body: function (expected) {
+            var passed = arguments.length ? context.body === expected : context.body.length > 0;
+            return this._check(passed, 'have body' + (arguments.length ? ' ' + show(expected) : ''),
+                'response');
+        },
+        jsonBody: function (path, expected) {
+            var value;
+            try {
+                value = JSON.parse(context.body);
+            } catch (e) {
+                return this._check(false, 'have a JSON body', 'response');
+            }
+            if (!arguments.length) {
+                return this._check(true, 'have a JSON body', 'response');
+            }
+            var found = String(path).split('.').reduce(function (current, key) {
+                return current === null || current === undefined ? undefined : current[key];
+            }, value);
+            var passed = arguments.length > 1 ? equal(found, expected) : found !== undefined;
+            return this._check(passed, 'have JSON body ' + show(path) +
+                (arguments.length > 1 ? ' of ' + show(expected) : ''), 'response');
+        }
+    };
+    var aliases = {
+        equal: ['equals', 'eq'],
+        eql: ['eqls'],
+        above: ['gt', 'greaterThan'],
+        least: ['gte'],
+        below: ['lt', 'lessThan'],
+        most: ['lte'],
+        a: ['an'],
+        include: ['includes', 'contain', 'contains'],
+        lengthOf: ['length'],
+        instanceOf: ['instanceof']
+    };
+    Object.keys(methods).forEach(function (name) {
+        [name].concat(aliases[name] || []).forEach(function (alias) {
+            Assertion.prototype[alias] = methods[name];
+        });
+    });
+
+    function range(low, high, phrase) {
+        return function () {
+            return this._check(context.code >= low && context.code <= high, phrase,
+                'response with status ' + context.code);
+        };
+    }
+    var flags = {
+        ok: function () {
+            if (this._response) {
+                return range(200, 299, 'be ok').call(this);
+            }
+            return this._check(this._value, 'be truthy');
+        },
+        true: function () {
+            return this._check(this._value === true, 'be true');
+        },
+        false: function () {
+            return this._check(this._value === false, 'be false');
+        },
+        null: function () {
+            return this._check(this._value === null, 'be null');
+        },
+        undefined: function () {
+            return this._check(this._value === undefined, 'be undefined');
+        },
+        NaN: function () {
+            return this._check(this._value !== this._value, 'be NaN');
+        },
+        exist: function () {
+            return this._check(this._value !== null && this._value !== undefined, 'exist');
+        },
+        empty: function () {
+            var value = this._value;
+            var empty = typeof value === 'string' || Array.isArray(value) ? value.length === 0 :
+                value !== null && typeof value === 'object' && Object.keys(value).length === 0;
+            return this._check(empty, 'be empty');
+        },
+        json: function () {
+            return this._check(/json/i.test(header('Content-Type') || ''), 'be JSON',
+                'response');
+        },
+        success: range(200, 299, 'be successful'),
+        redirection: range(300, 399, 'be a redirection'),
+        clientError: range(400, 499, 'be a client error'),
+        serverError: range(500, 599, 'be a server error'),
+        error: range(400, 599, 'be an error'),
+        badRequest: range(400, 400, 'be a bad request'),
+        unauthorized: range(401, 401, 'be unauthorized'),
+        forbidden: range(403, 403, 'be forbidden'),
+        notFound: range(404, 404, 'be not found'),
+        rateLimited: range(429, 429, 'be rate limited')
+    };
+    Object.keys(flags).forEach(function (name) {
+        Object.defineProperty(Assertion.prototype, name, {
+            get: flags[name]
+        });
+    });
+
+    pm.expect = function (value, message) {
+        return new Assertion(value, false, message);
+    };
+
+    // A test passes unless its function throws or returns false
+    pm.test = function (name, fn) {
+        try {
+            var returned = fn();
+            record(String(name), returned !== false,
+                returned === false ? 'Test assertion failed' : '');
+        } catch (e) {
+            record(String(name), false, e && e.message ? e.message : String(e));
+        }
+    };
+    pm.test.skip = function () {};
+
+    // pm.response.status stays Beam's status code, Postman's status text is `reason()`
+    var response = pm.response;
+    response.code = context.code;
+    response.responseTime = context.time;
+    response.responseSize = context.size;
+    response.reason = function () {
+        return context.reason;
+    };
+    response.headers = {
+        get: header,
+        has: function (name) {
+            return header(name) !== undefined;
+        },
+        toObject: function () {
+            var headers = {};
+            context.headers.forEach(function (pair) {
+                headers[pair[0].toLowerCase()] = pair[1];
+            });
+            return headers;
+        }
+    };
+    Object.defineProperty(response, 'to', {
+        get: function () {
+            return new Assertion(response, true);
+        }
+    });
+
+    pm.request = {
+        url: {
+            toString: function () {
+                return context.url;
+            }
+        },
+        method: context.method,
+        headers: {
+            get: function (name) {
+                var wanted = String(name).toLowerCase();
+                var found = context.requestHeaders.filter(function (pair) {
+                    return pair[0].toLowerCase() === wanted;
+                });
+                return found.length ? found[0][1] : undefined;
+            }
+        }
+    };
+    pm.info = {
+        eventName: 'test',
+        requestName: context.name,
+        requestId: context.id,
+        iteration: 0
+    };
+
+    // Values set during the script are seen by later gets, as in Postman
+    var environment = pm.environment;
+    var set = environment.set;
+    var get = environment.get;
+    var unset = environment.unset;
+    var changed = {};
+    environment.set = function (key, value) {
+        set(key, value);
+        changed[key] = { value: stringify(value) };
+    };
+    environment.get = function (key) {
+        return Object.prototype.hasOwnProperty.call(changed, key) ? changed[key].value : get(key);
+    };
+    environment.unset = function (key) {
+        unset(key);
+        changed[key] = { value: undefined };
+    };
+    environment.has = function (key) {
+        return environment.get(key) !== undefined && environment.get(key) !== null;
+    };
+    environment.replaceIn = function (template) {
+        return String(template).replace(/\{\{\s*([^}]+?)\s*\}\}/g, function (match, name) {
+            var value = pm.variables.get(name);
+            return value === undefined || value === null ? match : value;
+        });
+    };
+
+    // Beam has a single scope of variables: collection variables, globals and iteration data
+    // read and write the environment of the run
+    var local = {};
+    pm.variables = {
+        get: function (key) {
+            return Object.prototype.hasOwnProperty.call(local, key) ? local[key] :
+                environment.get(key);
+        },
+        set: function (key, value) {
+            local[key] = value;
+        },
+        has: function (key) {
+            return pm.variables.get(key) !== undefined && pm.variables.get(key) !== null;
+        },
+        replaceIn: environment.replaceIn
+    };
+    pm.collectionVariables = environment;
+    pm.globals = environment;
+    pm.iterationData = {
+        get: environment.get,
+        has: environment.has
+    };
+
+    pm.execution = {
+        setNextRequest: beam.setNextRequest,
+        skipRequest: beam.skipRequest
+    };
+
+    // The API of Postman's legacy sandbox
+    globalThis.postman = {
+        setNextRequest: beam.setNextRequest,
+        getEnvironmentVariable: environment.get,
+        setEnvironmentVariable: environment.set,
+        clearEnvironmentVariable: environment.unset,
+        getGlobalVariable: environment.get,
+        setGlobalVariable: environment.set,
+        clearGlobalVariable: environment.unset
+    };
+    globalThis.tests = {};
+    globalThis.responseBody = context.body;
+    globalThis.responseTime = context.time;
+    globalThis.responseCode = {
+        code: context.code,
+        name: context.reason,
+        detail: context.reason
+    };
+    globalThis.responseHeaders = response.headers.toObject();
+
+    // Records the results of the legacy `tests["name"] = passed` syntax, once the script ran
+    globalThis.__pmFinish = function () {
+        Object.keys(globalThis.tests).forEach(function (name) {
+            var passed = !!globalThis.tests[name];
+            record(name, passed, passed ? '' : 'Test assertion failed');
+        });
+    };
+})();
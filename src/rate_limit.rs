@@ -0,0 +1,245 @@
+use crate::types::ResponseData;
+use chrono::{DateTime, Duration, Utc};
+
+/// Epoch seconds are told apart from a number of seconds to wait by their size
+const EPOCH_THRESHOLD: i64 = 1_000_000_000;
+
+/// Automatic resends of a request refused for its rate, before giving up
+pub const MAX_RETRIES: u32 = 3;
+
+/// Longest Retry-After wait that is sat out automatically, in seconds
+const MAX_RETRY_WAIT: i64 = 300;
+
+/// The remaining quota of a rate limited API, as its response headers tell it
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimit {
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    /// When the quota is refilled
+    pub reset_at: Option<DateTime<Utc>>,
+    /// When the server asked to be retried, from `Retry-After`
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+impl RateLimit {
+    /// Whether the next request would likely be refused
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0) || self.retry_at.is_some()
+    }
+
+    /// Whether less than a tenth of the quota is left
+    pub fn is_low(&self) -> bool {
+        match (self.remaining, self.limit) {
+            (Some(remaining), Some(limit)) if limit > 0 => remaining * 10 < limit,
+            _ => false,
+        }
+    }
+
+    /// How long to wait from `now` before sending again so the limit is respected, `None`
+    /// when there is quota left
+    pub fn wait(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let until = match self.retry_at {
+            Some(retry_at) => retry_at,
+            None if self.remaining == Some(0) => self.reset_at?,
+            None => return None,
+        };
+        (until > now).then(|| until - now)
+    }
+}
+
+/// The rate limit headers of a response: `X-RateLimit-*`, `RateLimit-*` and their variants,
+/// the combined `RateLimit` header and `Retry-After`. Times given in seconds count from the
+/// response's `Date`, or from `now` without one.
+pub fn parse(headers: &[(String, String)], now: DateTime<Utc>) -> Option<RateLimit> {
+    let header = |names: &[&str]| {
+        headers
+            .iter()
+            .find(|(name, _)| names.iter().any(|n| name.eq_ignore_ascii_case(n)))
+            .map(|(_, value)| value.trim())
+    };
+    let sent_at = header(&["date"])
+        .and_then(|date| DateTime::parse_from_rfc2822(date).ok())
+        .map(|date| date.with_timezone(&Utc))
+        .unwrap_or(now);
+
+    // `RateLimit: limit=100, remaining=50, reset=30`
+    let combined: Vec<(&str, &str)> = header(&["ratelimit"])
+        .map(|value| {
+            value
+                .split([',', ';'])
+                .filter_map(|item| item.split_once('='))
+                .map(|(key, value)| (key.trim(), value.trim().trim_matches('"')))
+                .collect()
+        })
+        .unwrap_or_default();
+    let field = |names: &[&str], key: &str| {
+        header(names).or_else(|| {
+            combined
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                .map(|(_, value)| *value)
+        })
+    };
+
+    let limit = field(
+        &["x-ratelimit-limit", "ratelimit-limit", "x-rate-limit-limit"],
+        "limit",
+    )
+    .and_then(leading_number);
+    let remaining = field(
+        &[
+            "x-ratelimit-remaining",
+            "ratelimit-remaining",
+            "x-rate-limit-remaining",
+        ],
+        "remaining",
+    )
+    .and_then(leading_number);
+    let reset_at = field(
+        &["x-ratelimit-reset", "ratelimit-reset", "x-rate-limit-reset"],
+        "reset",
+    )
+    .and_then(|value| value.parse::<f64>().ok())
+    .and_then(|seconds| {
+        let seconds = seconds.ceil() as i64;
+        if seconds >= EPOCH_THRESHOLD {
+            DateTime::from_timestamp(seconds, 0)
+        } else {
+            Some(sent_at + Duration::seconds(seconds))
+        }
+    });
+    let retry_at = header(&["retry-after"]).and_then(|value| match value.parse::<i64>() {
+        Ok(seconds) => Some(sent_at + Duration::seconds(seconds)),
+        Err(_) => DateTime::parse_from_rfc2822(value)
+            .ok()
+            .map(|date| date.with_timezone(&Utc)),
+    });
+
+    if limit.is_none() && remaining.is_none() && reset_at.is_none() && retry_at.is_none() {
+        return None;
+    }
+    Some(RateLimit {
+        limit,
+        remaining,
+        reset_at,
+        retry_at,
+    })
+}
+
+/// How long to wait before resending the request that got `response`: set when it's a 429
+/// or 503 with a Retry-After of at most five minutes
+pub fn retry_delay(response: &ResponseData, now: DateTime<Utc>) -> Option<std::time::Duration> {
+    if !matches!(response.status, 429 | 503) {
+        return None;
+    }
+
+    let retry_at = parse(&response.headers, now)?.retry_at?;
+    let wait = (retry_at - now).max(Duration::zero());
+    if wait > Duration::seconds(MAX_RETRY_WAIT) {
+        return None;
+    }
+    wait.to_std().ok()
+}
+
+/// The number a header value starts with, some APIs append the window as in `100, 100;w=60`
+fn leading_number(value: &str) -> Option<u64> {
+    value
+        .split([',', ';', ' '])
+        .next()
+        .and_then(|number| number.trim().parse().ok())
+}
+
+/// A wait as minutes and seconds, e.g. `2m 05s`
+pub fn format_wait(wait: Duration) -> String {
+    let seconds = wait.num_seconds().max(0);
+    match (seconds / 3600, seconds / 60 % 60, seconds % 60) {
+        (0, 0, seconds) => format!("{}s", seconds),
+        (0, minutes, seconds) => format!("{}m {:02}s", minutes, seconds),
+        (hours, minutes, _) => format!("{}h {:02}m", hours, minutes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn reads_quota_headers() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+        let github = parse(
+            &headers(&[
+                ("X-RateLimit-Limit", "60"),
+                ("X-RateLimit-Remaining", "4"),
+                ("X-RateLimit-Reset", "1700000090"),
+            ]),
+            now,
+        )
+        .unwrap();
+        assert_eq!(github.limit, Some(60));
+        assert_eq!(github.remaining, Some(4));
+        assert_eq!(github.reset_at, DateTime::from_timestamp(1_700_000_090, 0));
+        assert!(github.is_low());
+        assert_eq!(github.wait(now), None);
+
+        let draft = parse(
+            &headers(&[
+                ("Date", "Tue, 14 Nov 2023 22:13:20 GMT"),
+                ("RateLimit", "limit=100, remaining=0, reset=30"),
+            ]),
+            now + Duration::seconds(5),
+        )
+        .unwrap();
+        assert_eq!(draft.limit, Some(100));
+        assert_eq!(draft.wait(now), Some(Duration::seconds(30)));
+
+        assert_eq!(
+            parse(&headers(&[("Content-Type", "text/plain")]), now),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_retry_after_as_seconds_or_a_date() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+        let seconds = parse(&headers(&[("Retry-After", "120")]), now).unwrap();
+        assert!(seconds.is_exhausted());
+        assert_eq!(format_wait(seconds.wait(now).unwrap()), "2m 00s");
+
+        let date = parse(
+            &headers(&[("Retry-After", "Tue, 14 Nov 2023 22:14:05 GMT")]),
+            now,
+        )
+        .unwrap();
+        assert_eq!(format_wait(date.wait(now).unwrap()), "45s");
+    }
+
+    #[test]
+    fn retries_refused_requests_after_short_waits() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let response = |status: u16, retry_after: &str| ResponseData {
+            status,
+            headers: headers(&[("Retry-After", retry_after)]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            retry_delay(&response(429, "12"), now),
+            Some(std::time::Duration::from_secs(12))
+        );
+        assert_eq!(
+            retry_delay(&response(503, "Tue, 14 Nov 2023 22:13:00 GMT"), now),
+            Some(std::time::Duration::ZERO)
+        );
+        assert_eq!(retry_delay(&response(429, "3600"), now), None);
+        assert_eq!(retry_delay(&response(500, "12"), now), None);
+    }
+}
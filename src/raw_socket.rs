@@ -0,0 +1,274 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+/// Replies are cut off past this size, a raw socket may stream forever
+const MAX_REPLY_SIZE: usize = 1024 * 1024;
+
+/// Once a TCP reply started, the connection counts as done when it stays quiet this long
+const IDLE_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SocketProtocol {
+    #[default]
+    Tcp,
+    Udp,
+}
+
+impl std::fmt::Display for SocketProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SocketProtocol::Tcp => write!(f, "TCP"),
+            SocketProtocol::Udp => write!(f, "UDP"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SocketReply {
+    pub peer: SocketAddr,
+    /// Time to establish the TCP connection, UDP has none
+    pub connect_time: Option<Duration>,
+    pub elapsed: Duration,
+    pub bytes: Vec<u8>,
+    pub truncated: bool,
+}
+
+/// Bytes to send, typed as text with `\r`, `\n`, `\t`, `\0`, `\\` and `\xNN` escapes, or as
+/// hex digits that may be separated by spaces
+pub fn parse_payload(input: &str, hex: bool) -> Result<Vec<u8>, String> {
+    if hex {
+        let digits: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+        if let Some(other) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(format!("'{}' is not a hex digit", other));
+        }
+        if !digits.len().is_multiple_of(2) {
+            return Err("Hex needs two digits per byte".to_string());
+        }
+        // Only ASCII digits are left, so every byte is one digit
+        return Ok(digits
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| hex_value(pair[0]) << 4 | hex_value(pair[1]))
+            .collect());
+    }
+
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => bytes.push(b'\n'),
+            Some('r') => bytes.push(b'\r'),
+            Some('t') => bytes.push(b'\t'),
+            Some('0') => bytes.push(0),
+            Some('\\') => bytes.push(b'\\'),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                if hex.len() != 2 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(format!("'\\x{}' is not a hex byte", hex));
+                }
+                bytes.push(hex_value(hex.as_bytes()[0]) << 4 | hex_value(hex.as_bytes()[1]));
+            }
+            Some(other) => return Err(format!("Unknown escape '\\{}'", other)),
+            None => bytes.push(b'\\'),
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// The value of an ASCII hex digit
+fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+/// The reply as text when it is printable UTF-8, as a hex dump otherwise
+pub fn format_reply(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) if text.chars().all(|c| !c.is_control() || c.is_whitespace()) => text.to_string(),
+        _ => hex_dump(bytes),
+    }
+}
+
+/// 16 bytes per line: offset, hex bytes and their printable ASCII
+pub fn hex_dump(bytes: &[u8]) -> String {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(line, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|byte| format!("{:02x}", byte)).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&byte| {
+                    if byte.is_ascii_graphic() || byte == b' ' {
+                        byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{:08x}  {:<47}  |{}|", line * 16, hex.join(" "), ascii)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Sends `payload` to `address` (`host:port`) and collects the reply. TCP reads until the
+/// peer closes the connection or goes quiet, UDP waits for a single datagram. Blocking.
+pub fn exchange(
+    protocol: SocketProtocol,
+    address: &str,
+    payload: &[u8],
+    timeout: Duration,
+) -> Result<SocketReply, String> {
+    let address = address.trim();
+    let addresses: Vec<SocketAddr> = address
+        .to_socket_addrs()
+        .map_err(|e| format!("Can't resolve '{}': {}", address, e))?
+        .collect();
+    if addresses.is_empty() {
+        return Err(format!("'{}' resolves to no address", address));
+    }
+
+    let start = Instant::now();
+    match protocol {
+        SocketProtocol::Tcp => exchange_tcp(&addresses, payload, timeout, start),
+        SocketProtocol::Udp => exchange_udp(&addresses, payload, timeout, start),
+    }
+}
+
+fn exchange_tcp(
+    addresses: &[SocketAddr],
+    payload: &[u8],
+    timeout: Duration,
+    start: Instant,
+) -> Result<SocketReply, String> {
+    let mut last_error = None;
+    let mut connected = None;
+    for address in addresses {
+        match TcpStream::connect_timeout(address, timeout) {
+            Ok(stream) => {
+                connected = Some((stream, *address));
+                break;
+            }
+            Err(e) => last_error = Some(format!("Can't connect to {}: {}", address, e)),
+        }
+    }
+    let Some((mut stream, peer)) = connected else {
+        return Err(last_error.unwrap_or_default());
+    };
+    let connect_time = start.elapsed();
+
+    stream
+        .write_all(payload)
+        .map_err(|e| format!("Sending failed: {}", e))?;
+
+    let mut bytes = Vec::new();
+    let mut buffer = [0; 8192];
+    let mut truncated = false;
+    stream.set_read_timeout(Some(timeout)).ok();
+    loop {
+        match stream.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => {
+                bytes.extend_from_slice(&buffer[..read]);
+                if bytes.len() >= MAX_REPLY_SIZE {
+                    bytes.truncate(MAX_REPLY_SIZE);
+                    truncated = true;
+                    break;
+                }
+                stream.set_read_timeout(Some(IDLE_TIMEOUT)).ok();
+            }
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => break,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(format!("Reading the reply failed: {}", e)),
+        }
+    }
+
+    Ok(SocketReply {
+        peer,
+        connect_time: Some(connect_time),
+        elapsed: start.elapsed(),
+        bytes,
+        truncated,
+    })
+}
+
+fn exchange_udp(
+    addresses: &[SocketAddr],
+    payload: &[u8],
+    timeout: Duration,
+    start: Instant,
+) -> Result<SocketReply, String> {
+    let peer = addresses[0];
+    let local = if peer.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(local).map_err(|e| format!("Can't open a UDP socket: {}", e))?;
+    socket
+        .connect(peer)
+        .map_err(|e| format!("Can't reach {}: {}", peer, e))?;
+    socket
+        .send(payload)
+        .map_err(|e| format!("Sending failed: {}", e))?;
+
+    let mut buffer = vec![0; 65536];
+    socket.set_read_timeout(Some(timeout)).ok();
+    let bytes = match socket.recv(&mut buffer) {
+        Ok(read) => buffer[..read].to_vec(),
+        Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => Vec::new(),
+        Err(e) => return Err(format!("Reading the reply failed: {}", e)),
+    };
+
+    Ok(SocketReply {
+        peer,
+        connect_time: None,
+        elapsed: start.elapsed(),
+        bytes,
+        truncated: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_escaped_text_and_hex_payloads() {
+        assert_eq!(
+            parse_payload(r"PING\r\n\x00é\\", false).unwrap(),
+            b"PING\r\n\x00\xc3\xa9\\".to_vec()
+        );
+        assert!(parse_payload(r"\q", false).is_err());
+        assert!(parse_payload(r"\x4", false).is_err());
+        assert_eq!(
+            parse_payload("de ad BE ef", true).unwrap(),
+            vec![0xde, 0xad, 0xbe, 0xef]
+        );
+        assert!(parse_payload("abc", true).is_err());
+        assert!(parse_payload("aé1", true).is_err());
+        assert!(parse_payload("+f", true).is_err());
+        assert!(parse_payload(r"\x+f", false).is_err());
+    }
+
+    #[test]
+    fn formats_text_or_hex_dump() {
+        assert_eq!(format_reply(b"+PONG\r\n"), "+PONG\r\n");
+        assert_eq!(
+            format_reply(b"\x00\x01AB"),
+            format!("00000000  {:<47}  |..AB|", "00 01 41 42")
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::types::RequestConfig;
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::fs;
+use std::io::Write;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const RECOVERY_FILE: &str = "recovery.json";
+const CRASH_LOG: &str = "crash.log";
+
+/// What Beam was working on when it panicked, offered for restoring on the next start
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recovery {
+    pub timestamp: String, // RFC 3339
+    pub message: String,   // The panic message and where it happened
+    /// The request open in the editor, with the edits not yet written to its draft
+    pub request: Option<RequestConfig>,
+}
+
+/// The editor's latest state, kept up to date so the panic hook doesn't have to reach into
+/// the app
+static EDITING: Mutex<Option<RequestConfig>> = Mutex::new(None);
+
+/// Remembers the request being edited, to save it should Beam panic before it's written out
+pub fn track(request: &RequestConfig) {
+    if let Ok(mut editing) = EDITING.lock() {
+        *editing = Some(request.clone());
+    }
+}
+
+/// Installs a panic hook that writes the request being edited to `recovery.json` and the
+/// panic with its backtrace to `crash.log`, both in `base_path`, before the default hook
+/// reports it. A panic on another thread than the main one doesn't end Beam, so it's only
+/// logged.
+pub fn install(base_path: PathBuf) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let main_thread = std::thread::current().name() == Some("main");
+        if let Err(e) = save(&base_path, panic_message(info), main_thread) {
+            eprintln!("Failed to save the recovery file: {}", e);
+        }
+        default_hook(info);
+    }));
+}
+
+/// The recovery file left by a crash, removed so it's only offered once
+pub fn take(base_path: &Path) -> Option<Recovery> {
+    let path = base_path.join(RECOVERY_FILE);
+    let content = fs::read_to_string(&path).ok()?;
+    let _ = fs::remove_file(&path);
+    match serde_json::from_str(&content) {
+        Ok(recovery) => Some(recovery),
+        Err(e) => {
+            log::warn!("Ignoring an unreadable recovery file: {}", e);
+            None
+        }
+    }
+}
+
+/// Where the crash reports are appended
+pub fn crash_log(base_path: &Path) -> PathBuf {
+    base_path.join(CRASH_LOG)
+}
+
+fn save(base_path: &Path, message: String, main_thread: bool) -> std::io::Result<()> {
+    let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    fs::create_dir_all(base_path)?;
+
+    let mut log = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(crash_log(base_path))?;
+    writeln!(
+        log,
+        "[{}] Beam {} panicked: {}\n{}\n",
+        timestamp,
+        env!("CARGO_PKG_VERSION"),
+        message,
+        Backtrace::force_capture()
+    )?;
+    if !main_thread {
+        return Ok(());
+    }
+
+    // The lock is held only to clone, a panic while holding it just leaves the request out
+    let request = EDITING.try_lock().ok().and_then(|editing| editing.clone());
+    let recovery = Recovery {
+        timestamp,
+        message,
+        request,
+    };
+    let content = serde_json::to_string_pretty(&recovery).map_err(std::io::Error::other)?;
+    fs::write(base_path.join(RECOVERY_FILE), content)
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Unknown panic".to_string());
+    match info.location() {
+        Some(location) => format!("{} at {}:{}", message, location.file(), location.line()),
+        None => message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_the_request_being_edited_for_the_next_start() {
+        let dir = std::env::temp_dir().join(format!("beam-recovery-{}", std::process::id()));
+        let request = RequestConfig {
+            id: "r1".to_string(),
+            body: "{\"draft\": true}".to_string(),
+            ..RequestConfig::default()
+        };
+        track(&request);
+
+        save(&dir, "boom at src/main.rs:1".to_string(), true).unwrap();
+        let log = fs::read_to_string(crash_log(&dir)).unwrap();
+        assert!(log.contains("panicked: boom at src/main.rs:1"));
+
+        let recovery = take(&dir).unwrap();
+        assert_eq!(recovery.message, "boom at src/main.rs:1");
+        assert_eq!(recovery.request.unwrap().body, request.body);
+        assert!(take(&dir).is_none());
+
+        save(&dir, "boom at src/sync.rs:1".to_string(), false).unwrap();
+        let log = fs::read_to_string(crash_log(&dir)).unwrap();
+        assert!(log.contains("panicked: boom at src/sync.rs:1"));
+        assert!(take(&dir).is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
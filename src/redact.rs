@@ -0,0 +1,169 @@
+use crate::http::generate_curl_command;
+use crate::types::{Environment, RequestConfig};
+
+/// Stands in for a masked value
+pub const MASK: &str = "••••••••";
+
+/// Secrets shorter than this aren't masked, replacing every "1" or "ab" would garble the text
+const MIN_SECRET_LENGTH: usize = 4;
+
+/// Headers that carry credentials or session state
+const SENSITIVE_HEADERS: [&str; 8] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "api-key",
+    "x-auth-token",
+    "x-amz-security-token",
+];
+
+pub fn is_sensitive_header(name: &str) -> bool {
+    let name = name.trim();
+    SENSITIVE_HEADERS
+        .iter()
+        .any(|sensitive| name.eq_ignore_ascii_case(sensitive))
+}
+
+/// Values that must not show in logs and exports: the credentials of `config`, the values of
+/// its sensitive headers and the variables of `environment` marked secret. Longest first, so
+/// a secret containing another is masked whole.
+pub fn secrets(config: &RequestConfig, environment: Option<&Environment>) -> Vec<String> {
+    let mut secrets: Vec<String> = [
+        &config.bearer_token,
+        &config.basic_password,
+        &config.api_key,
+        &config.hmac_auth.key,
+        &config.hawk_auth.key,
+    ]
+    .into_iter()
+    .cloned()
+    .collect();
+    secrets.extend(
+        config
+            .headers
+            .iter()
+            .filter(|(name, _)| {
+                is_sensitive_header(name)
+                    || (!config.api_key_header.is_empty()
+                        && name.eq_ignore_ascii_case(&config.api_key_header))
+            })
+            .map(|(_, value)| value.clone()),
+    );
+    if let Some(environment) = environment {
+        secrets.extend(
+            environment
+                .variables
+                .values()
+                .filter(|variable| variable.secret)
+                .map(|variable| variable.value.clone()),
+        );
+        secrets.extend([
+            environment.auth.bearer_token.clone(),
+            environment.auth.basic_password.clone(),
+            environment.auth.api_key.clone(),
+        ]);
+    }
+
+    // A reference such as {{token}} is harmless, only literal values are secret
+    secrets.retain(|secret| {
+        let secret = secret.trim();
+        secret.chars().count() >= MIN_SECRET_LENGTH
+            && !(secret.starts_with("{{") && secret.ends_with("}}"))
+    });
+    secrets.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    secrets.dedup();
+    secrets
+}
+
+/// `text` with every secret replaced by the mask
+pub fn mask(text: &str, secrets: &[String]) -> String {
+    let mut masked = text.to_string();
+    for secret in secrets {
+        masked = masked.replace(secret.as_str(), MASK);
+    }
+    masked
+}
+
+/// A request as sent over the wire, with the values of its sensitive headers and secrets masked
+pub fn raw_request(raw: &str, secrets: &[String]) -> String {
+    let mut masked = String::with_capacity(raw.len());
+    let mut in_headers = true;
+    for line in raw.split_inclusive('\n') {
+        // The body starts after the first blank line
+        if line.trim().is_empty() {
+            in_headers = false;
+        }
+        match line.split_once(':') {
+            Some((name, value)) if in_headers && is_sensitive_header(name) => {
+                let ending = &value[value.trim_end().len()..];
+                masked.push_str(&format!("{}: {}{}", name, MASK, ending));
+            }
+            _ => masked.push_str(line),
+        }
+    }
+    mask(&masked, secrets)
+}
+
+/// The curl command of `config`, with its credentials and secrets masked
+pub fn curl_command(config: &RequestConfig, secrets: &[String]) -> String {
+    let mut config = config.clone();
+    for (name, value) in &mut config.headers {
+        if is_sensitive_header(name) && !value.is_empty() {
+            *value = MASK.to_string();
+        }
+    }
+    mask(&generate_curl_command(&config), secrets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AuthType, EnvironmentVariable};
+
+    #[test]
+    fn masks_credentials_and_secret_variables() {
+        let config = RequestConfig {
+            url: "https://api.example.com/items?key=sk_live_123".to_string(),
+            headers: vec![
+                ("Cookie".to_string(), "session=abcdef".to_string()),
+                ("Accept".to_string(), "application/json".to_string()),
+            ],
+            auth_type: AuthType::Bearer,
+            bearer_token: "{{token}}".to_string(),
+            ..RequestConfig::default()
+        };
+        let mut environment = Environment::new("Production".to_string());
+        environment.variables.insert(
+            "api_key".to_string(),
+            EnvironmentVariable {
+                secret: true,
+                ..EnvironmentVariable::new("sk_live_123".to_string())
+            },
+        );
+        environment.variables.insert(
+            "region".to_string(),
+            EnvironmentVariable::new("eu-west-1".to_string()),
+        );
+
+        let secrets = secrets(&config, Some(&environment));
+        assert_eq!(secrets, vec!["session=abcdef", "sk_live_123"]);
+
+        let curl = curl_command(&config, &secrets);
+        assert!(curl.contains("'Cookie: ••••••••'"));
+        assert!(curl.contains("key=••••••••"));
+        assert!(curl.contains("Bearer {{token}}"));
+        assert!(curl.contains("application/json"));
+    }
+
+    #[test]
+    fn masks_sensitive_headers_of_raw_requests() {
+        let raw = "GET /me HTTP/1.1\r\nhost: example.com\r\nauthorization: Bearer eyJhbGc\r\n\r\nCookie: not a header";
+
+        assert_eq!(
+            raw_request(raw, &[]),
+            "GET /me HTTP/1.1\r\nhost: example.com\r\nauthorization: ••••••••\r\n\r\nCookie: not a header"
+        );
+    }
+}
@@ -0,0 +1,1023 @@
+use crate::rate_limit;
+use crate::share;
+use crate::types::{
+    Environment, JumpWhen, RequestConfig, ResponseData, RunConfiguration, RunStep, TestResult,
+};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Requests in flight at once in a parallel run, unless the run says otherwise
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Most requests in flight at once, so a run doesn't flood the API
+pub const MAX_CONCURRENCY: usize = 16;
+
+/// A run ends after this many sends, so loops and jumps can't keep it going forever
+pub const MAX_SENDS: usize = 1000;
+
+/// Most times a failed request is sent again
+pub const MAX_RETRIES: u32 = 5;
+
+/// How the requests of a run are sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RunMode {
+    /// One after the other, variables set by a script are seen by the requests after it
+    #[default]
+    Sequential,
+    /// Several at once, each seeing the environment as it was when the run started
+    Parallel,
+}
+
+/// Where a post-request script sends a sequential run next
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NextRequest {
+    /// `beam.setNextRequest("name")`: carry on at the step of the request with this name.
+    /// A name no step has ends the run.
+    Named(String),
+    /// `beam.setNextRequest(null)`: end the run
+    End,
+    /// `beam.skipRequest()`: leave out the step that would be sent next
+    Skip,
+}
+
+/// Outcome of one request of a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunResult {
+    pub status: u16,         // 0 when the request couldn't be sent
+    pub status_text: String, // Or what went wrong
+    pub time: u64,
+    pub test_results: Vec<TestResult>, // Assertions, then the tests of the script
+    /// Variables its post-request script set
+    pub extracted: BTreeMap<String, String>,
+    pub script_error: Option<String>,
+    /// Set by its post-request script, taking precedence over the loop and jump of its step
+    pub next_request: Option<NextRequest>,
+    /// Latency budget of the request in milliseconds, see `over_budget`
+    pub budget: Option<u64>,
+    /// How long its rate limit headers ask to wait before the next send
+    pub wait: Option<Duration>,
+    /// Set for a 429 or 503 that can be sent again once its Retry-After is up
+    pub retry_after: Option<Duration>,
+}
+
+impl RunResult {
+    pub fn new(response: &ResponseData) -> Self {
+        Self {
+            status: response.status,
+            status_text: response.status_text.clone(),
+            time: response.time,
+            test_results: response.test_results.clone(),
+            extracted: BTreeMap::new(),
+            script_error: None,
+            next_request: None,
+            budget: None,
+            wait: None,
+            retry_after: None,
+        }
+    }
+
+    /// Sent, but slower than the latency budget of its request. Doesn't make it fail.
+    pub fn over_budget(&self) -> bool {
+        self.status != 0 && self.budget.is_some_and(|budget| self.time > budget)
+    }
+
+    /// Sent, with a passing script and checks. Without checks any status below 400 passes.
+    pub fn passed(&self) -> bool {
+        if self.status == 0 || self.script_error.is_some() {
+            return false;
+        }
+        if self.test_results.is_empty() {
+            self.status < 400
+        } else {
+            self.test_results.iter().all(|result| result.passed)
+        }
+    }
+}
+
+/// A request sent by a run, with its result once it completes
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunEntry {
+    pub request: usize,    // Index into the requests of the run
+    pub iteration: usize,  // Row of the iteration data it was sent with
+    pub retries: u32,      // Times it was sent again after failing
+    pub rate_limited: u32, // Times it was sent again after its Retry-After
+    /// The latest result, the failed one while a retry waits to be sent
+    pub result: Option<RunResult>,
+}
+
+/// A step of a run, its loop and jump pointing at other steps by index
+#[derive(Debug, Clone)]
+struct Step {
+    skip: bool,
+    loop_back: Option<(usize, u32)>,
+    jump: Option<(JumpWhen, String, Option<usize>)>, // `None` ends the run
+}
+
+/// A run of the requests of a collection, in the order of its run configuration
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub collection: String,
+    pub mode: RunMode,
+    pub concurrency: usize,
+    pub requests: Vec<RequestConfig>, // One per step
+    /// Ids of the requests that chain variables, see `chained_requests`
+    pub chained: HashSet<String>,
+    /// Variables the requests are resolved with, its name is the one picked for the run
+    pub environment: Environment,
+    /// Rows of variables, the steps are gone through once per row. Their values take
+    /// precedence over those of the environment.
+    pub data: Vec<BTreeMap<String, String>>,
+    pub iteration: usize,
+    pub entries: Vec<RunEntry>, // In the order they were sent
+    pub stopped: bool,
+    /// Stopped after `MAX_SENDS` sends
+    pub limit_reached: bool,
+    /// Stopped at a failed request, see `RunConfiguration::stop_on_failure`
+    pub stopped_on_failure: bool,
+    pub delay: Duration,
+    pub stop_on_failure: bool,
+    pub retries: u32,
+    /// Send requests refused for their rate again, see `RunConfiguration::retry_after_rate_limit`
+    pub retry_after_rate_limit: bool,
+    steps: Vec<Step>,
+    next: usize,
+    in_flight: usize,
+    loops: HashMap<usize, u32>, // Rounds left of the loops being gone through, by step
+    retry_queue: VecDeque<usize>, // Entries to send again, before the next step
+    not_before: Option<Instant>, // When the delay before the next request is up
+    rate_limited_until: Option<Instant>, // When the wait a rate limit asked for is up
+}
+
+impl Run {
+    pub fn new(
+        collection: String,
+        requests: &[RequestConfig],
+        configuration: &RunConfiguration,
+        environment: Environment,
+        data: Vec<BTreeMap<String, String>>,
+        mode: RunMode,
+        concurrency: usize,
+    ) -> Self {
+        let order = ordered_steps(configuration, requests);
+        let position = |id: &str| order.iter().position(|step| step.request_id == id);
+
+        let steps = order
+            .iter()
+            .enumerate()
+            .map(|(index, step)| Step {
+                skip: step.skip,
+                loop_back: step.loop_back.as_ref().and_then(|loop_back| {
+                    position(&loop_back.to)
+                        .filter(|to| *to <= index)
+                        .map(|to| (to, loop_back.times))
+                }),
+                jump: step.jump.as_ref().and_then(|jump| {
+                    if jump.to.is_empty() {
+                        Some((jump.when, jump.test.clone(), None))
+                    } else {
+                        position(&jump.to).map(|to| (jump.when, jump.test.clone(), Some(to)))
+                    }
+                }),
+            })
+            .collect();
+        let requests: Vec<RequestConfig> = order
+            .iter()
+            .filter_map(|step| requests.iter().find(|r| r.id == step.request_id))
+            .cloned()
+            .collect();
+        let sent: Vec<RequestConfig> = requests
+            .iter()
+            .zip(&order)
+            .filter(|(_, step)| !step.skip)
+            .map(|(request, _)| request.clone())
+            .collect();
+
+        Self {
+            collection,
+            mode,
+            concurrency: concurrency.clamp(1, MAX_CONCURRENCY),
+            chained: chained_requests(&sent),
+            environment,
+            data,
+            iteration: 0,
+            requests,
+            entries: Vec::new(),
+            stopped: false,
+            limit_reached: false,
+            stopped_on_failure: false,
+            delay: Duration::from_millis(configuration.delay_ms),
+            stop_on_failure: configuration.stop_on_failure,
+            retries: configuration.retries.min(MAX_RETRIES),
+            retry_after_rate_limit: configuration.retry_after_rate_limit,
+            steps,
+            next: 0,
+            in_flight: 0,
+            loops: HashMap::new(),
+            retry_queue: VecDeque::new(),
+            not_before: None,
+            rate_limited_until: None,
+        }
+    }
+
+    /// Entries of the requests to send now: the next one of a sequential run, or as many as
+    /// fit in the concurrency of a parallel one. Failed entries to retry go first. With a
+    /// delay, nothing is sent until it's up and a parallel run sends one request at a time.
+    pub fn start_next(&mut self) -> Vec<usize> {
+        let limit = match self.mode {
+            RunMode::Sequential => 1,
+            RunMode::Parallel => self.concurrency,
+        };
+
+        let mut started = Vec::new();
+        while !self.stopped && self.in_flight < limit {
+            let now = Instant::now();
+            if self.not_before.is_some_and(|due| now < due) {
+                break;
+            }
+            self.not_before = None;
+
+            if let Some(entry) = self.retry_queue.pop_front() {
+                self.entries[entry].result = None;
+                started.push(entry);
+                self.in_flight += 1;
+                self.delay_parallel(now);
+                continue;
+            }
+
+            // Past the last step, the next iteration starts over
+            if self.next_step().is_none() && self.iteration + 1 < self.iterations() {
+                self.iteration += 1;
+                self.next = 0;
+                self.loops.clear();
+            }
+            let Some(step) = self.next_step() else {
+                break;
+            };
+            if self.entries.len() >= MAX_SENDS {
+                self.stopped = true;
+                self.limit_reached = true;
+                break;
+            }
+
+            self.entries.push(RunEntry {
+                request: step,
+                iteration: self.iteration,
+                retries: 0,
+                rate_limited: 0,
+                result: None,
+            });
+            started.push(self.entries.len() - 1);
+            self.next = step + 1;
+            self.in_flight += 1;
+            self.delay_parallel(now);
+        }
+        started
+    }
+
+    /// Holds the next request of a parallel run back for the delay, counted from when the
+    /// last one was sent
+    fn delay_parallel(&mut self, now: Instant) {
+        if self.mode == RunMode::Parallel && !self.delay.is_zero() {
+            self.hold_until(now + self.delay);
+        }
+    }
+
+    /// Sends nothing before `due`, keeping a later hold in place
+    fn hold_until(&mut self, due: Instant) {
+        self.not_before = Some(self.not_before.map_or(due, |held| held.max(due)));
+    }
+
+    /// Files the result of an entry. Whatever the mode, nothing is sent until the wait its
+    /// rate limit headers ask for is up. A rate limited one is queued to be sent again once
+    /// its Retry-After is up, when the run retries those, and a failed one while it has
+    /// retries left. A sequential run passes the variables its script set on to the requests
+    /// after it and follows the loop or jump of its step. A parallel run keeps the variables
+    /// with the result so the outcome doesn't depend on which request finished first, and
+    /// goes through the steps in order.
+    pub fn complete(&mut self, entry: usize, result: RunResult) {
+        let Some(step) = self.entries.get(entry).map(|entry| entry.request) else {
+            return;
+        };
+        self.in_flight = self.in_flight.saturating_sub(1);
+        let now = Instant::now();
+        // A sequential run waits for the delay from when the last request completed
+        if self.mode == RunMode::Sequential && !self.delay.is_zero() {
+            self.hold_until(now + self.delay);
+        }
+        if let Some(wait) = result.wait {
+            self.hold_until(now + wait);
+            self.rate_limited_until = self.not_before;
+        }
+
+        if let Some(retry_after) = result.retry_after
+            && self.retry_after_rate_limit
+            && !self.stopped
+            && self.entries[entry].rate_limited < rate_limit::MAX_RETRIES
+        {
+            self.hold_until(now + retry_after);
+            self.rate_limited_until = self.not_before;
+            self.entries[entry].rate_limited += 1;
+            self.entries[entry].result = Some(result);
+            self.retry_queue.push_back(entry);
+            return;
+        }
+        if !result.passed() && !self.stopped && self.entries[entry].retries < self.retries {
+            self.entries[entry].retries += 1;
+            self.entries[entry].result = Some(result);
+            self.retry_queue.push_back(entry);
+            return;
+        }
+        if !result.passed() && self.stop_on_failure {
+            self.stopped = true;
+            self.stopped_on_failure = true;
+        }
+
+        if self.mode == RunMode::Sequential {
+            for (key, value) in &result.extracted {
+                match self.environment.variables.get_mut(key) {
+                    Some(variable) => variable.value = value.clone(),
+                    None => self.environment.add_variable(key.clone(), value.clone()),
+                }
+            }
+            self.follow(step, &result);
+        }
+        self.entries[entry].result = Some(result);
+    }
+
+    /// Picks the step after `step` of a sequential run: the one its script asked for, else
+    /// the one its jump points at when the jump applies, otherwise the start of its loop
+    /// until the loop's rounds are done
+    fn follow(&mut self, step: usize, result: &RunResult) {
+        let Some(current) = self.steps.get(step) else {
+            return;
+        };
+
+        if let Some(next_request) = &result.next_request {
+            self.loops.clear();
+            match next_request {
+                NextRequest::Named(name) => match self.position_of(name) {
+                    Some(to) => self.next = to,
+                    None => self.end_run(),
+                },
+                NextRequest::End => self.end_run(),
+                NextRequest::Skip => {
+                    if let Some(skipped) = self.next_step() {
+                        self.next = skipped + 1;
+                    }
+                }
+            }
+            return;
+        }
+
+        if let Some((when, test, to)) = &current.jump {
+            let passed = if test.is_empty() {
+                result.passed()
+            } else {
+                result
+                    .test_results
+                    .iter()
+                    .any(|check| check.name == *test && check.passed)
+            };
+            if passed == (*when == JumpWhen::Passed) {
+                // Loops gone through again after a jump start over
+                self.loops.clear();
+                match to {
+                    Some(to) => self.next = *to,
+                    None => self.end_run(),
+                }
+                return;
+            }
+        }
+
+        if let Some((to, times)) = current.loop_back {
+            let left = self.loops.entry(step).or_insert(times);
+            if *left > 0 {
+                *left -= 1;
+                self.next = to;
+            } else {
+                self.loops.remove(&step);
+            }
+        }
+    }
+
+    /// The step of the request named `name`. Requests imported from folders are named
+    /// `Folder / Request` while Postman scripts name them `Request`, so the last part of the
+    /// name does when no name matches whole.
+    fn position_of(&self, name: &str) -> Option<usize> {
+        self.requests
+            .iter()
+            .position(|request| request.name == name)
+            .or_else(|| {
+                self.requests
+                    .iter()
+                    .position(|request| request.name.rsplit(" / ").next() == Some(name))
+            })
+    }
+
+    /// Sends nothing after the requests in flight, in this iteration or the ones after it
+    fn end_run(&mut self) {
+        self.next = self.steps.len();
+        self.iteration = self.iterations() - 1;
+    }
+
+    /// The first step from `next` on that isn't skipped
+    fn next_step(&self) -> Option<usize> {
+        (self.next..self.steps.len()).find(|step| !self.steps[*step].skip)
+    }
+
+    /// Sends no more requests, those in flight still complete. Entries waiting for a retry
+    /// keep their failed result.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+        self.retry_queue.clear();
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.in_flight == 0
+            && (self.stopped
+                || (self.retry_queue.is_empty()
+                    && self.next_step().is_none()
+                    && self.iteration + 1 >= self.iterations()))
+    }
+
+    /// Waiting for the delay before the next request is up, `start_next` sends it once it is
+    pub fn is_waiting(&self) -> bool {
+        self.not_before.is_some() && !self.is_finished()
+    }
+
+    /// What is left of the wait a rate limit asked for, while the run sits it out
+    pub fn rate_limit_wait(&self) -> Option<Duration> {
+        if !self.is_waiting() {
+            return None;
+        }
+        self.rate_limited_until
+            .and_then(|until| until.checked_duration_since(Instant::now()))
+    }
+
+    /// Times the steps are gone through, once without iteration data
+    pub fn iterations(&self) -> usize {
+        self.data.len().max(1)
+    }
+
+    /// The environment an entry is resolved with: the run's, with the values of its row of
+    /// iteration data
+    pub fn environment_for(&self, entry: usize) -> Environment {
+        let mut environment = self.environment.clone();
+        if let Some(row) = self
+            .entries
+            .get(entry)
+            .and_then(|entry| self.data.get(entry.iteration))
+        {
+            for (key, value) in row {
+                match environment.variables.get_mut(key) {
+                    Some(variable) => {
+                        variable.value = value.clone();
+                        variable.enabled = true;
+                    }
+                    None => environment.add_variable(key.clone(), value.clone()),
+                }
+            }
+        }
+        environment
+    }
+
+    pub fn completed(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.result.is_some())
+            .count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.result.as_ref())
+            .filter(|result| !result.passed())
+            .count()
+    }
+
+    /// Checks of the completed requests, assertions and script tests, and how many of them
+    /// failed
+    pub fn checks(&self) -> (usize, usize) {
+        let checks = self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.result.as_ref())
+            .flat_map(|result| &result.test_results);
+        checks.fold((0, 0), |(total, failed), check| {
+            (total + 1, failed + usize::from(!check.passed))
+        })
+    }
+
+    /// Times requests were sent again after failing
+    pub fn retried(&self) -> u32 {
+        self.entries.iter().map(|entry| entry.retries).sum()
+    }
+
+    /// Completed requests slower than their latency budget
+    pub fn over_budget(&self) -> usize {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.result.as_ref())
+            .filter(|result| result.over_budget())
+            .count()
+    }
+}
+
+/// The steps of a run of `requests`: those of the configuration whose request still exists,
+/// then the requests added since, as plain steps
+pub fn ordered_steps(configuration: &RunConfiguration, requests: &[RequestConfig]) -> Vec<RunStep> {
+    let mut steps: Vec<RunStep> = Vec::new();
+    for step in &configuration.steps {
+        if requests.iter().any(|request| request.id == step.request_id)
+            && !steps
+                .iter()
+                .any(|known| known.request_id == step.request_id)
+        {
+            steps.push(step.clone());
+        }
+    }
+    for request in requests {
+        if !steps.iter().any(|step| step.request_id == request.id) {
+            steps.push(RunStep::new(request.id.clone()));
+        }
+    }
+    steps
+}
+
+/// Reads iteration data: a JSON array of objects, or CSV with the variable names in its
+/// first row
+pub fn parse_iteration_data(content: &str) -> Result<Vec<BTreeMap<String, String>>, String> {
+    let content = content.trim_start_matches('\u{feff}').trim();
+    if content.starts_with('[') {
+        let rows: Vec<serde_json::Map<String, serde_json::Value>> =
+            serde_json::from_str(content)
+                .map_err(|e| format!("Invalid JSON iteration data: {}", e))?;
+        return Ok(rows
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|(key, value)| {
+                        let value = match value {
+                            serde_json::Value::String(value) => value,
+                            serde_json::Value::Null => String::new(),
+                            other => other.to_string(),
+                        };
+                        (key, value)
+                    })
+                    .collect()
+            })
+            .collect());
+    }
+
+    let mut records = csv_records(content).into_iter();
+    let header = records.next().ok_or("The iteration data is empty")?;
+    Ok(records
+        .filter(|record| record.iter().any(|field| !field.is_empty()))
+        .map(|record| {
+            header
+                .iter()
+                .map(|name| name.trim().to_string())
+                .zip(record.into_iter().chain(std::iter::repeat(String::new())))
+                .filter(|(name, _)| !name.is_empty())
+                .collect()
+        })
+        .collect())
+}
+
+/// Splits CSV into records of fields. Quoted fields may hold commas, line breaks and
+/// doubled quotes.
+fn csv_records(content: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// Variables a post-request script sets, e.g. `token` for `pm.environment.set("token", ...)`.
+/// Names built at run time can't be known, so they're left out.
+pub fn variables_set(script: &str) -> Vec<String> {
+    let re = Regex::new(r#"\benvironment\s*\.\s*set\s*\(\s*["'`]([^"'`]+)["'`]"#).unwrap();
+    let mut names: Vec<String> = re
+        .captures_iter(script)
+        .map(|captures| captures[1].trim().to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Ids of the requests that chain variables: those whose script sets a variable another
+/// request uses, and those using it. They depend on the order they're sent in, so they're
+/// unsafe to send in parallel.
+pub fn chained_requests(requests: &[RequestConfig]) -> HashSet<String> {
+    let set: Vec<(&str, Vec<String>)> = requests
+        .iter()
+        .map(|request| {
+            let script = request.post_request_script.as_deref().unwrap_or_default();
+            (request.id.as_str(), variables_set(script))
+        })
+        .collect();
+
+    let mut chained = HashSet::new();
+    for request in requests {
+        let used = share::referenced_variables(request);
+        for (setter, names) in &set {
+            if *setter != request.id && names.iter().any(|name| used.contains(name)) {
+                chained.insert(setter.to_string());
+                chained.insert(request.id.clone());
+            }
+        }
+    }
+    chained
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Jump, LoopBack};
+
+    fn request(id: &str, url: &str, script: Option<&str>) -> RequestConfig {
+        RequestConfig {
+            id: id.to_string(),
+            url: url.to_string(),
+            post_request_script: script.map(str::to_string),
+            ..RequestConfig::default()
+        }
+    }
+
+    fn result(status: u16, extracted: &[(&str, &str)]) -> RunResult {
+        RunResult {
+            status,
+            status_text: String::new(),
+            time: 10,
+            test_results: vec![],
+            extracted: extracted
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            script_error: None,
+            next_request: None,
+            budget: None,
+            wait: None,
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn flags_requests_that_chain_variables() {
+        let requests = vec![
+            request(
+                "login",
+                "https://api.test/login",
+                Some("pm.environment.set('token', pm.response.json().token);"),
+            ),
+            request("me", "https://api.test/me?token={{ token }}", None),
+            request("health", "{{host}}/health", None),
+        ];
+        assert_eq!(
+            variables_set("pm.environment.set(\"a\", 1); pm.environment.set(`b`, 2)"),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            chained_requests(&requests),
+            HashSet::from(["login".to_string(), "me".to_string()])
+        );
+    }
+
+    #[test]
+    fn carries_variables_only_through_sequential_runs() {
+        let requests = vec![request("a", "", None), request("b", "", None)];
+        let environment = Environment::new("Staging".to_string());
+
+        let mut run = Run::new(
+            "Users".to_string(),
+            &requests,
+            &RunConfiguration::default(),
+            environment.clone(),
+            Vec::new(),
+            RunMode::Sequential,
+            DEFAULT_CONCURRENCY,
+        );
+        assert_eq!(run.start_next(), vec![0]);
+        assert!(run.start_next().is_empty());
+        run.complete(0, result(200, &[("token", "t1")]));
+        assert_eq!(
+            run.environment.get_variable("token"),
+            Some(&"t1".to_string())
+        );
+        assert_eq!(run.start_next(), vec![1]);
+        run.complete(1, result(500, &[]));
+        assert!(run.is_finished());
+        assert_eq!((run.completed(), run.failed()), (2, 1));
+
+        let mut run = Run::new(
+            "Users".to_string(),
+            &requests,
+            &RunConfiguration::default(),
+            environment,
+            Vec::new(),
+            RunMode::Parallel,
+            DEFAULT_CONCURRENCY,
+        );
+        assert_eq!(run.start_next(), vec![0, 1]);
+        run.complete(0, result(200, &[("token", "t1")]));
+        assert_eq!(run.environment.get_variable("token"), None);
+        assert!(!run.is_finished());
+    }
+
+    #[test]
+    fn follows_the_order_loops_and_jumps_of_the_configuration() {
+        let requests = vec![
+            request("login", "", None),
+            request("list", "", None),
+            request("page", "", None),
+            request("logout", "", None),
+            request("added", "", None),
+        ];
+        let step = |id: &str| RunStep::new(id.to_string());
+        let configuration = RunConfiguration {
+            steps: vec![
+                step("logout"),
+                RunStep {
+                    skip: true,
+                    ..step("gone")
+                },
+                step("login"),
+                step("list"),
+                RunStep {
+                    loop_back: Some(LoopBack {
+                        to: "list".to_string(),
+                        times: 1,
+                    }),
+                    jump: Some(Jump {
+                        when: JumpWhen::Failed,
+                        test: String::new(),
+                        to: "logout".to_string(),
+                    }),
+                    ..step("page")
+                },
+                RunStep {
+                    skip: true,
+                    ..step("added")
+                },
+            ],
+            ..RunConfiguration::default()
+        };
+        assert_eq!(
+            ordered_steps(&configuration, &requests)
+                .iter()
+                .map(|step| step.request_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["logout", "login", "list", "page", "added"]
+        );
+
+        let mut run = Run::new(
+            "Users".to_string(),
+            &requests,
+            &configuration,
+            Environment::new(String::new()),
+            Vec::new(),
+            RunMode::Sequential,
+            DEFAULT_CONCURRENCY,
+        );
+        let mut sent = Vec::new();
+        // The second round of `page` fails and jumps back to `logout`, which passes
+        let mut statuses = [200, 200, 200, 200, 200, 500, 200, 200, 200, 200].into_iter();
+        loop {
+            let started = run.start_next();
+            let Some(&entry) = started.first() else {
+                break;
+            };
+            sent.push(run.requests[run.entries[entry].request].id.clone());
+            run.complete(entry, result(statuses.next().unwrap_or(200), &[]));
+            if sent.len() > 20 {
+                break;
+            }
+        }
+        assert_eq!(
+            sent,
+            vec![
+                "logout", "login", "list", "page", "list", "page", "logout", "login", "list",
+                "page", "list", "page"
+            ]
+        );
+        assert!(run.is_finished());
+    }
+
+    #[test]
+    fn lets_scripts_pick_the_next_request() {
+        let requests: Vec<RequestConfig> = ["refresh", "list", "audit", "logout"]
+            .iter()
+            .map(|name| RequestConfig {
+                name: name.to_string(),
+                ..request(name, "", None)
+            })
+            .collect();
+        let mut run = Run::new(
+            "Users".to_string(),
+            &requests,
+            &RunConfiguration::default(),
+            Environment::new(String::new()),
+            Vec::new(),
+            RunMode::Sequential,
+            DEFAULT_CONCURRENCY,
+        );
+        // `list` sends `refresh` again once, then skips `audit`
+        let mut flow = [
+            None,
+            Some(NextRequest::Named("refresh".to_string())),
+            None,
+            Some(NextRequest::Skip),
+            Some(NextRequest::End),
+        ]
+        .into_iter();
+        let mut sent = Vec::new();
+        while let Some(&entry) = run.start_next().first() {
+            sent.push(run.requests[run.entries[entry].request].name.clone());
+            let mut completed = result(200, &[]);
+            completed.next_request = flow.next().flatten();
+            run.complete(entry, completed);
+        }
+        assert_eq!(sent, vec!["refresh", "list", "refresh", "list", "logout"]);
+        assert!(run.is_finished());
+    }
+
+    #[test]
+    fn retries_failed_requests_then_stops_at_the_failure() {
+        let requests = vec![request("a", "", None), request("b", "", None)];
+        let configuration = RunConfiguration {
+            retries: 2,
+            stop_on_failure: true,
+            ..RunConfiguration::default()
+        };
+        let mut run = Run::new(
+            "Users".to_string(),
+            &requests,
+            &configuration,
+            Environment::new(String::new()),
+            Vec::new(),
+            RunMode::Sequential,
+            DEFAULT_CONCURRENCY,
+        );
+        assert_eq!(run.start_next(), vec![0]);
+        run.complete(0, result(503, &[]));
+        assert_eq!(run.start_next(), vec![0]);
+        run.complete(0, result(200, &[]));
+        assert_eq!(run.entries[0].retries, 1);
+
+        assert_eq!(run.start_next(), vec![1]);
+        for _ in 0..2 {
+            run.complete(1, result(500, &[]));
+            assert!(!run.is_finished());
+            assert_eq!(run.start_next(), vec![1]);
+        }
+        run.complete(1, result(500, &[]));
+        assert!(run.stopped_on_failure && run.is_finished());
+        assert_eq!((run.completed(), run.failed(), run.retried()), (2, 1, 3));
+
+        // The next request waits for the delay
+        let configuration = RunConfiguration {
+            delay_ms: 60_000,
+            ..RunConfiguration::default()
+        };
+        let mut run = Run::new(
+            "Users".to_string(),
+            &requests,
+            &configuration,
+            Environment::new(String::new()),
+            Vec::new(),
+            RunMode::Sequential,
+            DEFAULT_CONCURRENCY,
+        );
+        assert_eq!(run.start_next(), vec![0]);
+        run.complete(0, result(200, &[]));
+        assert!(run.is_waiting());
+        assert!(run.start_next().is_empty());
+    }
+
+    #[test]
+    fn waits_out_rate_limits_and_flags_slow_requests() {
+        let requests = vec![request("a", "", None), request("b", "", None)];
+        let configuration = RunConfiguration {
+            retry_after_rate_limit: true,
+            ..RunConfiguration::default()
+        };
+        let mut run = Run::new(
+            "Users".to_string(),
+            &requests,
+            &configuration,
+            Environment::new(String::new()),
+            Vec::new(),
+            RunMode::Parallel,
+            DEFAULT_CONCURRENCY,
+        );
+        assert_eq!(run.start_next(), vec![0, 1]);
+
+        // A 429 is sent again once its Retry-After is up, without using a retry
+        let refused = RunResult {
+            retry_after: Some(Duration::ZERO),
+            ..result(429, &[])
+        };
+        run.complete(0, refused);
+        assert_eq!(run.start_next(), vec![0]);
+        assert_eq!(
+            (run.entries[0].rate_limited, run.entries[0].retries),
+            (1, 0)
+        );
+        run.complete(0, result(200, &[]));
+
+        let slow = RunResult {
+            budget: Some(5),
+            ..result(200, &[])
+        };
+        run.complete(1, slow);
+        assert!(run.is_finished());
+        assert_eq!((run.failed(), run.over_budget()), (0, 1));
+
+        // Without the option, a 429 is a failure like any other, its wait still held
+        let mut run = Run::new(
+            "Users".to_string(),
+            &requests,
+            &RunConfiguration::default(),
+            Environment::new(String::new()),
+            Vec::new(),
+            RunMode::Sequential,
+            DEFAULT_CONCURRENCY,
+        );
+        assert_eq!(run.start_next(), vec![0]);
+        let refused = RunResult {
+            wait: Some(Duration::from_secs(60)),
+            retry_after: Some(Duration::from_secs(60)),
+            ..result(429, &[])
+        };
+        run.complete(0, refused);
+        assert!(run.rate_limit_wait().is_some());
+        assert!(run.start_next().is_empty());
+        assert_eq!(run.failed(), 1);
+    }
+
+    #[test]
+    fn goes_through_the_steps_once_per_row_of_data() {
+        let data =
+            parse_iteration_data("user,note\nalice,\"says \"\"hi\"\", twice\"\r\nbob\n\n").unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0]["note"], "says \"hi\", twice");
+        assert_eq!(data[1]["note"], "");
+        assert_eq!(
+            parse_iteration_data(r#"[{"user": "carol", "age": 30}]"#).unwrap()[0]["age"],
+            "30"
+        );
+        assert!(parse_iteration_data("[{").is_err());
+
+        let mut environment = Environment::new("Staging".to_string());
+        environment.add_variable("user".to_string(), "nobody".to_string());
+        let mut run = Run::new(
+            "Users".to_string(),
+            &[request("a", "", None), request("b", "", None)],
+            &RunConfiguration::default(),
+            environment,
+            data,
+            RunMode::Parallel,
+            DEFAULT_CONCURRENCY,
+        );
+        assert_eq!(run.start_next(), vec![0, 1, 2, 3]);
+        assert_eq!(
+            run.environment_for(3).get_variable("user"),
+            Some(&"bob".to_string())
+        );
+        for entry in 0..4 {
+            run.complete(entry, result(200, &[]));
+        }
+        assert!(run.is_finished());
+        assert_eq!(
+            run.environment.get_variable("user"),
+            Some(&"nobody".to_string())
+        );
+    }
+}
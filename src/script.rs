@@ -1,9 +1,14 @@
-use beam::types::{Environment, RequestConfig, ResponseData};
+use beam::faker;
+use beam::runner::NextRequest;
+use beam::types::{Environment, RequestConfig, ResponseData, TestResult};
 use log::{error, info};
 use rquickjs::{Context, Object, Runtime, function::Func};
 use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
 
+/// Postman's script API, see the file for what it covers
+const POSTMAN_COMPAT: &str = include_str!("postman.js");
+
 #[derive(Debug, Clone)]
 pub struct ScriptExecutionResult {
     pub success: bool,
@@ -11,13 +16,8 @@ pub struct ScriptExecutionResult {
     pub environment_changes: BTreeMap<String, String>,
     pub test_results: Vec<TestResult>,
     pub console_output: Vec<String>,
-}
-
-#[derive(Debug, Clone)]
-pub struct TestResult {
-    pub name: String,
-    pub passed: bool,
-    pub error_message: Option<String>,
+    /// Where the script asked a collection run to go next
+    pub next_request: Option<NextRequest>,
 }
 
 pub fn execute_post_request_script(
@@ -26,7 +26,8 @@ pub fn execute_post_request_script(
     response: ResponseData,
     environment: &Environment,
 ) -> ScriptExecutionResult {
-    info!("Executing post-request script: {}", script);
+    // Scripts and what they set often hold tokens, so only their size and outcome are logged
+    info!("Executing post-request script ({} bytes)", script.len());
 
     let mut result = ScriptExecutionResult {
         success: false,
@@ -34,6 +35,7 @@ pub fn execute_post_request_script(
         environment_changes: BTreeMap::new(),
         test_results: Vec::new(),
         console_output: Vec::new(),
+        next_request: None,
     };
 
     // Basic script validation
@@ -67,6 +69,7 @@ pub fn execute_post_request_script(
     let console_output_shared = Arc::new(Mutex::new(Vec::new()));
     let env_changes_shared = Arc::new(Mutex::new(BTreeMap::new()));
     let test_results_shared = Arc::new(Mutex::new(Vec::new()));
+    let next_request_shared = Arc::new(Mutex::new(None));
 
     // Execute the script within the context
     let execution_result = context.with(|ctx| {
@@ -84,6 +87,7 @@ pub fn execute_post_request_script(
             Arc::clone(&console_output_shared),
             Arc::clone(&env_changes_shared),
             Arc::clone(&test_results_shared),
+            Arc::clone(&next_request_shared),
         ) {
             Ok(_) => {
                 info!("Global objects setup complete");
@@ -99,14 +103,14 @@ pub fn execute_post_request_script(
         // Test if pm.response.json() works
         info!("Testing pm.response.json()...");
         match ctx.eval::<rquickjs::Value, _>("pm.response.json()") {
-            Ok(val) => info!("pm.response.json() test successful: {:?}", val),
+            Ok(_) => info!("pm.response.json() test successful"),
             Err(e) => error!("pm.response.json() test failed: {:?}", e),
         }
 
         // Test property access
         info!("Testing pm.response.json().refreshToken...");
         match ctx.eval::<rquickjs::Value, _>("pm.response.json().refreshToken") {
-            Ok(val) => info!("Property access test successful: {:?}", val),
+            Ok(_) => info!("Property access test successful"),
             Err(e) => error!("Property access test failed: {:?}", e),
         }
 
@@ -121,13 +125,16 @@ pub fn execute_post_request_script(
         match ctx.eval::<(), _>(script) {
             Ok(_) => {
                 result.success = true;
+                // Tests of Postman's legacy `tests["name"] = passed` syntax
+                if let Err(e) = ctx.eval::<(), _>("__pmFinish()") {
+                    error!("Failed to record the legacy tests: {:?}", e);
+                }
                 Ok(())
             }
             Err(e) => {
                 // Try to get more detailed error information
                 let error_msg = format!("JavaScript execution error: {:?}", e);
-                error!("Script execution failed. Script content: {}", script);
-                error!("Error details: {:?}", e);
+                error!("Script execution failed: {:?}", e);
 
                 // Try to extract more info from the error
                 let err_str = e.to_string();
@@ -146,10 +153,11 @@ pub fn execute_post_request_script(
     result.console_output.extend(captured_console);
     result.environment_changes = env_changes_shared.lock().unwrap().clone();
     result.test_results = test_results_shared.lock().unwrap().clone();
+    result.next_request = next_request_shared.lock().unwrap().clone();
 
     info!(
-        "===After script execution, environment changes: {:?}",
-        result.environment_changes
+        "===After script execution, {} environment change(s)",
+        result.environment_changes.len()
     );
 
     if let Err(e) = execution_result {
@@ -159,14 +167,16 @@ pub fn execute_post_request_script(
     result
 }
 
+#[allow(clippy::too_many_arguments)]
 fn setup_global_objects(
     ctx: &rquickjs::Ctx,
-    _request: &RequestConfig,
+    request: &RequestConfig,
     response: &ResponseData,
     environment: &Environment,
     console_output: Arc<Mutex<Vec<String>>>,
     env_changes: Arc<Mutex<BTreeMap<String, String>>>,
     test_results: Arc<Mutex<Vec<TestResult>>>,
+    next_request: Arc<Mutex<Option<NextRequest>>>,
 ) -> Result<(), rquickjs::Error> {
     info!("Setting up global objects...");
 
@@ -212,7 +222,7 @@ fn setup_global_objects(
     let set_fn_rust = Func::new(move |key: String, value: String| {
         let mut changes = env_changes_clone.lock().unwrap();
         changes.insert(key.clone(), value.clone());
-        info!("===Environment variable set: {} = {}", key, value);
+        info!("===Environment variable set: {}", key);
     });
 
     // Set the Rust function directly
@@ -253,7 +263,7 @@ fn setup_global_objects(
             .filter(|v| v.enabled)
             .map(|v| v.value.clone())
     });
-    env_obj.prop("get", get_fn)?;
+    env_obj.set("get", get_fn)?;
 
     // Add pm.environment.unset method - simple version
     let env_changes_clone = Arc::clone(&env_changes);
@@ -261,7 +271,7 @@ fn setup_global_objects(
         let mut changes = env_changes_clone.lock().unwrap();
         changes.remove(&key);
     });
-    env_obj.prop("unset", unset_fn)?;
+    env_obj.set("unset", unset_fn)?;
 
     pm.prop("environment", env_obj)?;
     info!("pm.environment object setup complete");
@@ -278,30 +288,17 @@ fn setup_global_objects(
     let text_fn = Func::new(move || -> String { response_body_for_text.clone() });
     response_obj.prop("text", text_fn)?;
 
-    // Add pm.test method for test assertions - simplified version
+    // pm.test is defined with the Postman API, which records the outcome of each test here
     let test_results_clone = Arc::clone(&test_results);
-    let test_fn = Func::new(move |name: String, test_func: rquickjs::Function| {
-        let test_result = match test_func.call::<_, bool>(()) {
-            Ok(passed) => TestResult {
-                name: name.clone(),
-                passed,
-                error_message: if passed {
-                    None
-                } else {
-                    Some("Test assertion failed".to_string())
-                },
-            },
-            Err(e) => TestResult {
-                name: name.clone(),
-                passed: false,
-                error_message: Some(format!("Test execution error: {:?}", e)),
-            },
-        };
-
+    let record_test_fn = Func::new(move |name: String, passed: bool, message: String| {
         let mut results = test_results_clone.lock().unwrap();
-        results.push(test_result);
+        results.push(TestResult {
+            name,
+            passed,
+            error_message: (!passed).then_some(message),
+        });
     });
-    pm.prop("test", test_fn)?;
+    ctx.globals().set("__beamRecordTest", record_test_fn)?;
 
     // Add response object to pm
     pm.prop("response", response_obj)?;
@@ -338,5 +335,155 @@ fn setup_global_objects(
 
     info!("pm.response object setup complete");
 
+    setup_beam_object(ctx, next_request)?;
+
+    // What the Postman API tells about the exchange, besides the body it parses
+    let postman_context = serde_json::json!({
+        "code": response.status,
+        "reason": response.status_text,
+        "time": response.time,
+        "size": response.size,
+        "headers": response.headers,
+        "body": response.body,
+        "id": request.id,
+        "name": request.name,
+        "method": request.method.to_string(),
+        "url": request.url,
+        "requestHeaders": request.headers,
+    });
+    let postman_context = ctx.json_parse(postman_context.to_string())?;
+    ctx.globals().set("__pmContext", postman_context)?;
+    ctx.eval::<(), _>(POSTMAN_COMPAT)?;
+    info!("Postman API setup complete");
+
+    Ok(())
+}
+
+/// Exposes `beam.faker.<placeholder>()`, mirroring the `{{$placeholder}}` request variables,
+/// and `beam.setNextRequest(name)` / `beam.skipRequest()` steering a collection run. The last
+/// call of the script wins, outside of a run they do nothing.
+fn setup_beam_object(
+    ctx: &rquickjs::Ctx,
+    next_request: Arc<Mutex<Option<NextRequest>>>,
+) -> Result<(), rquickjs::Error> {
+    let fake_fn = Func::new(|name: String, argument: String| -> Option<String> {
+        let argument = if argument.is_empty() {
+            None
+        } else {
+            Some(argument.as_str())
+        };
+        faker::generate(&name, argument)
+    });
+    ctx.globals().set("__beamFake", fake_fn)?;
+
+    // `null` ends the run, like an unknown name would
+    let next_request_clone = Arc::clone(&next_request);
+    let set_next_fn = Func::new(move |name: Option<String>| {
+        *next_request_clone.lock().unwrap() = Some(match name {
+            Some(name) => NextRequest::Named(name),
+            None => NextRequest::End,
+        });
+    });
+    ctx.globals().set("__beamSetNext", set_next_fn)?;
+
+    let skip_fn = Func::new(move || {
+        *next_request.lock().unwrap() = Some(NextRequest::Skip);
+    });
+    ctx.globals().set("__beamSkip", skip_fn)?;
+
+    let names = faker::PLACEHOLDERS
+        .iter()
+        .map(|name| format!("'{}'", name))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let faker_eval = format!(
+        r#"
+        (function() {{
+            var fake = __beamFake;
+            var setNext = __beamSetNext;
+            var skip = __beamSkip;
+            var faker = {{}};
+            [{}].forEach(function(name) {{
+                faker[name] = function(arg) {{
+                    return fake(name, arg === undefined ? '' : String(arg));
+                }};
+            }});
+            globalThis.beam = {{
+                faker: faker,
+                setNextRequest: function(name) {{
+                    setNext(name === null || name === undefined ? null : String(name));
+                }},
+                skipRequest: function() {{
+                    skip();
+                }}
+            }};
+        }})();
+    "#,
+        names
+    );
+    ctx.eval::<(), _>(faker_eval)?;
+
+    let _ = ctx.globals().remove("__beamFake");
+    let _ = ctx.globals().remove("__beamSetNext");
+    let _ = ctx.globals().remove("__beamSkip");
+    info!("beam object setup complete");
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_postman_test_scripts() {
+        let response = ResponseData {
+            status: 201,
+            status_text: "Created".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: r#"{"id": 7, "tags": ["new"]}"#.to_string(),
+            content_type: "application/json".to_string(),
+            size: 26,
+            time: 42,
+            ..Default::default()
+        };
+        let script = r#"
+            pm.test("created", function () {
+                pm.response.to.have.status(201);
+                pm.response.to.be.json;
+                pm.expect(pm.response.json()).to.have.property("id").that.is.a("number");
+                pm.expect(pm.response.json().tags).to.include("new");
+            });
+            pm.test("fast", () => pm.expect(pm.response.responseTime).to.be.below(10));
+            pm.environment.set("user", pm.response.json().id);
+            tests["has user"] = pm.environment.get("user") === "7";
+            postman.setNextRequest("Delete user");
+        "#;
+
+        let result = execute_post_request_script(
+            script,
+            RequestConfig::default(),
+            response,
+            &Environment::new(String::new()),
+        );
+        assert!(result.success, "{:?}", result.error_message);
+        let outcomes: Vec<(&str, bool)> = result
+            .test_results
+            .iter()
+            .map(|test| (test.name.as_str(), test.passed))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![("created", true), ("fast", false), ("has user", true)]
+        );
+        assert_eq!(
+            result.test_results[1].error_message.as_deref(),
+            Some("expected 42 to be below 10")
+        );
+        assert_eq!(
+            result.next_request,
+            Some(NextRequest::Named("Delete user".to_string()))
+        );
+    }
+}
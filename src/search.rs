@@ -0,0 +1,118 @@
+use regex::Regex;
+use std::ops::Range;
+
+/// What a find bar looks for: the query as typed, or a regular expression
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Text(String),
+    Regex(Regex),
+}
+
+impl Pattern {
+    /// Builds the pattern of a find bar, failing with a readable reason when the query isn't
+    /// a valid regular expression
+    pub fn new(query: &str, regex: bool) -> Result<Pattern, String> {
+        if !regex {
+            return Ok(Pattern::Text(query.to_string()));
+        }
+
+        Regex::new(query).map(Pattern::Regex).map_err(|e| match e {
+            regex::Error::Syntax(message) => message
+                .lines()
+                .last()
+                .unwrap_or_default()
+                .trim_start_matches("error: ")
+                .to_string(),
+            other => other.to_string(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Pattern::Text(query) => query.is_empty(),
+            Pattern::Regex(regex) => regex.as_str().is_empty(),
+        }
+    }
+
+    /// Byte ranges of the matches in `text`, in order. Empty matches, such as those of `a*`,
+    /// can't be highlighted or stepped through, so they're left out.
+    pub fn find_all(&self, text: &str) -> Vec<Range<usize>> {
+        match self {
+            Pattern::Text(query) if query.is_empty() => Vec::new(),
+            Pattern::Text(query) => text
+                .match_indices(query.as_str())
+                .map(|(start, found)| start..start + found.len())
+                .collect(),
+            Pattern::Regex(regex) => regex
+                .find_iter(text)
+                .map(|found| found.range())
+                .filter(|range| !range.is_empty())
+                .collect(),
+        }
+    }
+
+    /// The first match starting at `from` or later, wrapping around to the start of `text`
+    pub fn next(&self, text: &str, from: usize) -> Option<Range<usize>> {
+        let matches = self.find_all(text);
+        matches
+            .iter()
+            .find(|found| found.start >= from)
+            .or(matches.first())
+            .cloned()
+    }
+
+    /// The match before the one ending at `cursor`, wrapping around to the end of `text`
+    pub fn previous(&self, text: &str, cursor: usize) -> Option<Range<usize>> {
+        let matches = self.find_all(text);
+        let before: Vec<&Range<usize>> =
+            matches.iter().filter(|found| found.end <= cursor).collect();
+        let found = match before.as_slice() {
+            [.., earlier, current] if current.end == cursor => Some(*earlier),
+            [current] if current.end == cursor => None,
+            [.., last] => Some(*last),
+            [] => None,
+        };
+        found.or(matches.last()).cloned()
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Pattern::Text(a), Pattern::Text(b)) => a == b,
+            (Pattern::Regex(a), Pattern::Regex(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_through_text_and_regex_matches() {
+        let text = "id: 12, name: a, id: 345";
+
+        let plain = Pattern::new("id", false).unwrap();
+        assert_eq!(plain.find_all(text), vec![0..2, 17..19]);
+        assert_eq!(plain.next(text, 1), Some(17..19));
+        assert_eq!(plain.next(text, 18), Some(0..2));
+
+        let digits = Pattern::new(r"\d+", true).unwrap();
+        assert_eq!(digits.find_all(text), vec![4..6, 21..24]);
+        assert_eq!(digits.previous(text, 24), Some(4..6));
+        assert_eq!(digits.previous(text, 6), Some(21..24));
+        assert_eq!(digits.previous(text, 10), Some(4..6));
+
+        // Empty matches are skipped rather than found everywhere
+        assert!(Pattern::new("x*", true).unwrap().find_all(text).is_empty());
+    }
+
+    #[test]
+    fn reports_invalid_regexes() {
+        let error = Pattern::new("(unclosed", true).unwrap_err();
+        assert_eq!(error, "unclosed group");
+        assert!(Pattern::new("(unclosed", false).is_ok());
+    }
+}
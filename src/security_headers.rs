@@ -0,0 +1,345 @@
+use crate::types::ResponseData;
+
+/// HSTS shorter than this (180 days) is flagged, browsers' preload lists ask for a year
+const MIN_HSTS_MAX_AGE: u64 = 15_552_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// One header looked at, with why it matters
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub header: &'static str,
+    pub outcome: Outcome,
+    pub explanation: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Grade {
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+impl std::fmt::Display for Grade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let grade = match self {
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+            Grade::D => "D",
+            Grade::F => "F",
+        };
+        write!(f, "{}", grade)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audit {
+    pub grade: Grade,
+    pub checks: Vec<Check>,
+}
+
+/// Grades the security headers of `response`, received from `url`. A failed check costs 20
+/// points out of 100, a warning 5.
+pub fn audit(response: &ResponseData, url: &str) -> Audit {
+    let header = |name: &str| {
+        response
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+    };
+    let is_html = response.content_type.contains("html");
+    // Certificates are only collected over TLS, the URL may start with a variable
+    let is_https = !response.certificates.is_empty()
+        || url.trim().to_ascii_lowercase().starts_with("https://");
+
+    let mut checks = Vec::new();
+    let mut check = |header: &'static str, outcome: Outcome, explanation: &str| {
+        checks.push(Check {
+            header,
+            outcome,
+            explanation: explanation.to_string(),
+        })
+    };
+
+    let hsts_max_age = header("strict-transport-security").map(|value| {
+        value
+            .split(';')
+            .filter_map(|directive| directive.trim().split_once('='))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("max-age"))
+            .and_then(|(_, age)| age.trim().trim_matches('"').parse::<u64>().ok())
+            .unwrap_or(0)
+    });
+    match hsts_max_age {
+        _ if !is_https => check(
+            "Strict-Transport-Security",
+            Outcome::Warn,
+            "The response came over plain HTTP, where nothing protects it from being read or altered.",
+        ),
+        Some(age) if age >= MIN_HSTS_MAX_AGE => check(
+            "Strict-Transport-Security",
+            Outcome::Pass,
+            "Browsers will only use HTTPS for this host.",
+        ),
+        Some(_) => check(
+            "Strict-Transport-Security",
+            Outcome::Warn,
+            "max-age is under 180 days, browsers forget to insist on HTTPS quickly.",
+        ),
+        None => check(
+            "Strict-Transport-Security",
+            Outcome::Fail,
+            "Missing: a first plain HTTP visit can be intercepted and downgraded.",
+        ),
+    }
+
+    let csp = header("content-security-policy");
+    match csp {
+        Some(policy) if policy.contains("'unsafe-inline'") || policy.contains("'unsafe-eval'") => {
+            check(
+                "Content-Security-Policy",
+                Outcome::Warn,
+                "Allows 'unsafe-inline' or 'unsafe-eval', which undoes most of its protection against script injection.",
+            )
+        }
+        Some(_) => check(
+            "Content-Security-Policy",
+            Outcome::Pass,
+            "Limits where scripts, styles and frames can be loaded from.",
+        ),
+        None if is_html => check(
+            "Content-Security-Policy",
+            Outcome::Fail,
+            "Missing on an HTML page: injected scripts run with no restriction.",
+        ),
+        None => check(
+            "Content-Security-Policy",
+            Outcome::Warn,
+            "Missing: even APIs benefit from default-src 'none'; frame-ancestors 'none' in case a response is rendered.",
+        ),
+    }
+
+    match header("x-content-type-options") {
+        Some(value) if value.eq_ignore_ascii_case("nosniff") => check(
+            "X-Content-Type-Options",
+            Outcome::Pass,
+            "Browsers won't guess a different content type than the one declared.",
+        ),
+        _ => check(
+            "X-Content-Type-Options",
+            Outcome::Fail,
+            "Should be nosniff: browsers may otherwise treat a response as a script or page.",
+        ),
+    }
+
+    let frame_ancestors = csp.is_some_and(|policy| policy.contains("frame-ancestors"));
+    match header("x-frame-options") {
+        _ if frame_ancestors => check(
+            "X-Frame-Options",
+            Outcome::Pass,
+            "Framing is controlled by frame-ancestors in the Content-Security-Policy.",
+        ),
+        Some(value)
+            if value.eq_ignore_ascii_case("deny") || value.eq_ignore_ascii_case("sameorigin") =>
+        {
+            check(
+                "X-Frame-Options",
+                Outcome::Pass,
+                "Other sites can't embed the response in a frame to clickjack it.",
+            )
+        }
+        _ => check(
+            "X-Frame-Options",
+            if is_html {
+                Outcome::Fail
+            } else {
+                Outcome::Warn
+            },
+            "Missing: other sites can embed the response in a frame (set DENY or frame-ancestors).",
+        ),
+    }
+
+    match header("referrer-policy") {
+        Some(policy) if policy.eq_ignore_ascii_case("unsafe-url") => check(
+            "Referrer-Policy",
+            Outcome::Warn,
+            "unsafe-url sends full URLs, tokens in query strings included, to other sites.",
+        ),
+        Some(_) => check(
+            "Referrer-Policy",
+            Outcome::Pass,
+            "Controls how much of the URL other sites see in Referer.",
+        ),
+        None => check(
+            "Referrer-Policy",
+            Outcome::Warn,
+            "Missing: browsers fall back to their default, which may leak paths to other sites.",
+        ),
+    }
+
+    if is_html {
+        match header("permissions-policy") {
+            Some(_) => check(
+                "Permissions-Policy",
+                Outcome::Pass,
+                "Restricts the browser features (camera, geolocation...) the page can use.",
+            ),
+            None => check(
+                "Permissions-Policy",
+                Outcome::Warn,
+                "Missing: embedded content may ask for the camera, geolocation and other features.",
+            ),
+        }
+    }
+
+    let allow_origin = header("access-control-allow-origin");
+    let allow_credentials = header("access-control-allow-credentials")
+        .is_some_and(|value| value.eq_ignore_ascii_case("true"));
+    match allow_origin {
+        Some("*") if allow_credentials => check(
+            "Access-Control-Allow-Origin",
+            Outcome::Fail,
+            "Any origin combined with credentials: browsers refuse it, and reflecting origins instead would expose user data.",
+        ),
+        Some("*") => check(
+            "Access-Control-Allow-Origin",
+            Outcome::Warn,
+            "Any site can read the response from a browser. Fine for public data only.",
+        ),
+        Some(_) => check(
+            "Access-Control-Allow-Origin",
+            Outcome::Pass,
+            "Cross-origin reads are limited to the listed origin.",
+        ),
+        None => {}
+    }
+
+    let leaks = ["server", "x-powered-by", "x-aspnet-version"]
+        .into_iter()
+        .filter_map(header)
+        .any(|value| value.chars().any(|c| c.is_ascii_digit()));
+    if leaks {
+        check(
+            "Server",
+            Outcome::Warn,
+            "Server or X-Powered-By gives software versions, which helps attackers pick known vulnerabilities.",
+        );
+    }
+
+    let insecure_cookie = response
+        .headers
+        .iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case("set-cookie"))
+        .any(|(_, cookie)| {
+            let attributes = cookie.to_ascii_lowercase();
+            !attributes.contains("httponly") || (is_https && !attributes.contains("secure"))
+        });
+    if insecure_cookie {
+        check(
+            "Set-Cookie",
+            Outcome::Warn,
+            "A cookie lacks HttpOnly or Secure, scripts can read it or it can travel over plain HTTP.",
+        );
+    }
+
+    let penalty: u32 = checks
+        .iter()
+        .map(|check| match check.outcome {
+            Outcome::Pass => 0,
+            Outcome::Warn => 5,
+            Outcome::Fail => 20,
+        })
+        .sum();
+    let grade = match 100u32.saturating_sub(penalty) {
+        90.. => Grade::A,
+        80.. => Grade::B,
+        65.. => Grade::C,
+        50.. => Grade::D,
+        _ => Grade::F,
+    };
+
+    Audit { grade, checks }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(content_type: &str, headers: &[(&str, &str)]) -> ResponseData {
+        ResponseData {
+            status: 200,
+            status_text: "OK".to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            content_type: content_type.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn grades_well_configured_apis_highly() {
+        let audit = audit(
+            &response(
+                "application/json",
+                &[
+                    (
+                        "Strict-Transport-Security",
+                        "max-age=31536000; includeSubDomains",
+                    ),
+                    (
+                        "Content-Security-Policy",
+                        "default-src 'none'; frame-ancestors 'none'",
+                    ),
+                    ("X-Content-Type-Options", "nosniff"),
+                    ("Referrer-Policy", "no-referrer"),
+                ],
+            ),
+            "https://api.example.com/users",
+        );
+
+        assert_eq!(audit.grade, Grade::A);
+        assert!(
+            audit
+                .checks
+                .iter()
+                .all(|check| check.outcome == Outcome::Pass)
+        );
+    }
+
+    #[test]
+    fn fails_bare_html_pages() {
+        let audit = audit(
+            &response(
+                "text/html",
+                &[
+                    ("Server", "nginx/1.18.0"),
+                    ("Access-Control-Allow-Origin", "*"),
+                    ("Set-Cookie", "session=abc; Path=/"),
+                ],
+            ),
+            "HTTPS://example.com",
+        );
+
+        assert_eq!(audit.grade, Grade::F);
+        let outcome = |header: &str| {
+            audit
+                .checks
+                .iter()
+                .find(|check| check.header == header)
+                .map(|check| check.outcome)
+        };
+        assert_eq!(outcome("Strict-Transport-Security"), Some(Outcome::Fail));
+        assert_eq!(outcome("Server"), Some(Outcome::Warn));
+        assert_eq!(outcome("Set-Cookie"), Some(Outcome::Warn));
+    }
+}
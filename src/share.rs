@@ -0,0 +1,162 @@
+use crate::redact;
+use crate::types::{RequestConfig, new_id};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Extension of a request shared on its own
+pub const EXTENSION: &str = "beamreq";
+
+/// A single request in a file of its own, e.g. to attach to a bug report. The values of its
+/// variables stay in the sender's environments, only their names are listed so the receiver
+/// knows what to set.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SharedRequest {
+    #[serde(default)]
+    pub variables: Vec<String>,
+    pub request: RequestConfig,
+}
+
+/// The `.beamreq` content of `request`, without what its runs left behind and with `secrets`
+/// masked
+pub fn export(request: &RequestConfig, secrets: &[String]) -> Result<String, String> {
+    let request = portable(request);
+    let shared = SharedRequest {
+        variables: referenced_variables(&request),
+        request,
+    };
+    let content = toml::to_string_pretty(&shared).map_err(|e| e.to_string())?;
+    Ok(redact::mask(&content, secrets))
+}
+
+/// `request` without what its runs left behind or where it's stored, to send elsewhere
+pub fn portable(request: &RequestConfig) -> RequestConfig {
+    let mut request = request.clone();
+    request.path = Default::default();
+    request.run_history.clear();
+    request.last_response = None;
+    request.cache_validators = Default::default();
+    request
+}
+
+/// Reads a `.beamreq` file as a new request, not yet in a collection
+pub fn parse(content: &str) -> Result<RequestConfig, String> {
+    let shared: SharedRequest =
+        toml::from_str(content).map_err(|e| format!("Invalid shared request: {}", e))?;
+    let mut request = shared.request;
+    request.id = new_id();
+    request.path = Default::default();
+    Ok(request)
+}
+
+/// A `{{name}}` reference, capturing the name
+static REFERENCE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{\{([^}]+)\}\}").unwrap());
+
+/// The environment variables `request` references as `{{name}}`, by name. Dynamic
+/// placeholders such as `{{$uuid}}` are generated, so they aren't listed.
+pub fn referenced_variables(request: &RequestConfig) -> Vec<String> {
+    let Ok(content) = toml::to_string(request) else {
+        return Vec::new();
+    };
+    REFERENCE
+        .captures_iter(&content)
+        .map(|captures| captures[1].trim().to_string())
+        .filter(|name| !name.is_empty() && !name.starts_with('$'))
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect()
+}
+
+/// Writes the export of a request into `dir`, named after it without replacing an earlier
+/// export
+pub fn write_export(dir: &Path, name: &str, content: &str) -> Result<PathBuf, String> {
+    write_file(dir, name, EXTENSION, content)
+}
+
+/// Writes `content` to a new `<name>.<extension>` file in `dir`, numbering the name when the
+/// file exists
+pub fn write_file(
+    dir: &Path,
+    name: &str,
+    extension: &str,
+    content: &str,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+
+    let stem: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let stem = match stem.trim_matches('-') {
+        "" => "request",
+        stem => stem,
+    };
+    let mut path = dir.join(format!("{}.{}", stem, extension));
+    let mut counter = 2;
+    while path.exists() {
+        path = dir.join(format!("{} ({}).{}", stem, counter, extension));
+        counter += 1;
+    }
+
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Where exports go: the downloads folder, or the home folder without one
+pub fn export_dir() -> PathBuf {
+    dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_a_request_with_its_variables_and_without_secrets() {
+        let request: RequestConfig = toml::from_str(
+            r#"
+            id = "r1"
+            name = "Create user"
+            path = "/tmp/users/0001.toml"
+            method = "POST"
+            url = "{{base_url}}/users?trace={{$uuid}}"
+            headers = [["X-Tenant", "{{ tenant }}"]]
+            params = []
+            body = '{"name": "{{user_name}}"}'
+            content_type = "application/json"
+            auth_type = "Bearer"
+            bearer_token = "s3cr3t-token"
+            basic_username = ""
+            basic_password = ""
+            api_key = ""
+            api_key_header = ""
+            collection_index = 0
+            request_index = 0
+            "#,
+        )
+        .unwrap();
+
+        let content = export(&request, &["s3cr3t-token".to_string()]).unwrap();
+        assert!(!content.contains("s3cr3t-token"));
+        assert!(!content.contains("/tmp/users"));
+
+        let shared: SharedRequest = toml::from_str(&content).unwrap();
+        assert_eq!(shared.variables, vec!["base_url", "tenant", "user_name"]);
+
+        let imported = parse(&content).unwrap();
+        assert_eq!(imported.name, "Create user");
+        assert_eq!(imported.bearer_token, redact::MASK);
+        assert_ne!(imported.id, "r1");
+    }
+}
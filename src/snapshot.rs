@@ -0,0 +1,230 @@
+use crate::assertions::summarize_errors;
+use crate::json_path;
+use crate::types::{ResponseData, ResponseSnapshot, TestResult};
+use serde_json::Value;
+
+const NAME: &str = "Body matches snapshot";
+
+/// Records the body of `response` to compare later responses with
+pub fn record(response: &ResponseData) -> ResponseSnapshot {
+    ResponseSnapshot {
+        status: response.status,
+        body: response.body.clone(),
+        recorded_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Compares a response with the snapshot. JSON bodies are compared as values, so
+/// formatting and key order don't matter, leaving out what `ignore_paths` point to. Other
+/// bodies are compared line by line.
+pub fn check(
+    snapshot: &ResponseSnapshot,
+    response: &ResponseData,
+    ignore_paths: &[String],
+) -> TestResult {
+    let mut differences = Vec::new();
+    if response.status != snapshot.status {
+        differences.push(format!(
+            "Status: expected {}, got {}",
+            snapshot.status, response.status
+        ));
+    }
+
+    match (
+        serde_json::from_str::<Value>(&snapshot.body),
+        serde_json::from_str::<Value>(&response.body),
+    ) {
+        (Ok(mut expected), Ok(mut actual)) => {
+            match remove_ignored(&mut expected, &mut actual, ignore_paths) {
+                Ok(()) => json_differences(&expected, &actual, "$", &mut differences),
+                Err(e) => differences.push(e),
+            }
+        }
+        _ => {
+            if let Some(difference) = text_difference(&snapshot.body, &response.body) {
+                differences.push(difference);
+            }
+        }
+    }
+
+    if differences.is_empty() {
+        TestResult::pass(NAME)
+    } else {
+        TestResult::fail(NAME, summarize_errors(differences))
+    }
+}
+
+/// Removes the ignored paths from both values, so they are left out of a comparison
+pub fn remove_ignored(
+    expected: &mut Value,
+    actual: &mut Value,
+    ignore_paths: &[String],
+) -> Result<(), String> {
+    for path in ignore_paths.iter().filter(|path| !path.trim().is_empty()) {
+        json_path::remove(expected, path)?;
+        json_path::remove(actual, path)?;
+    }
+    Ok(())
+}
+
+/// One message per value that differs, addressed with a JSONPath such as `$.items[0].id`
+pub fn json_differences(expected: &Value, actual: &Value, path: &str, out: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected_value) in expected {
+                let child = format!("{}.{}", path, key);
+                match actual.get(key) {
+                    Some(actual_value) => {
+                        json_differences(expected_value, actual_value, &child, out)
+                    }
+                    None => out.push(format!("{}: missing", child)),
+                }
+            }
+            for key in actual.keys().filter(|key| !expected.contains_key(*key)) {
+                out.push(format!("{}.{}: unexpected", path, key));
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            for (index, (expected_item, actual_item)) in expected.iter().zip(actual).enumerate() {
+                json_differences(
+                    expected_item,
+                    actual_item,
+                    &format!("{}[{}]", path, index),
+                    out,
+                );
+            }
+            if expected.len() != actual.len() {
+                out.push(format!(
+                    "{}: expected {} items, got {}",
+                    path,
+                    expected.len(),
+                    actual.len()
+                ));
+            }
+        }
+        (expected, actual) if expected != actual => {
+            out.push(format!("{}: expected {}, got {}", path, expected, actual))
+        }
+        _ => {}
+    }
+}
+
+/// The first line that differs, `None` when both texts are the same
+fn text_difference(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(expected), Some(actual)) if expected == actual => line += 1,
+            (Some(expected), Some(actual)) => {
+                return Some(format!(
+                    "Line {}: expected \"{}\", got \"{}\"",
+                    line, expected, actual
+                ));
+            }
+            (Some(_), None) => return Some(format!("Line {}: the body ends early", line)),
+            (None, Some(_)) => return Some(format!("Line {}: unexpected extra lines", line)),
+            // Only line endings differ
+            (None, None) => return Some("Line endings differ".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, body: &str) -> ResponseData {
+        ResponseData {
+            status,
+            body: body.to_string(),
+            size: body.len(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn compares_json_as_values() {
+        let snapshot = record(&response(
+            200,
+            r#"{"id":1,"tags":["a","b"],"user":{"name":"Ada"}}"#,
+        ));
+
+        assert!(
+            check(
+                &snapshot,
+                &response(
+                    200,
+                    r#"{ "user": {"name": "Ada"}, "tags": ["a", "b"], "id": 1 }"#
+                ),
+                &[]
+            )
+            .passed
+        );
+
+        let result = check(
+            &snapshot,
+            &response(201, r#"{"id":2,"tags":["a"],"user":{},"extra":true}"#),
+            &[],
+        );
+        assert!(!result.passed);
+        assert_eq!(
+            result.error_message.unwrap(),
+            "Status: expected 200, got 201\n$.id: expected 1, got 2\n$.tags: expected 2 items, got 1\n$.user.name: missing\n$.extra: unexpected"
+        );
+    }
+
+    #[test]
+    fn leaves_out_ignored_paths() {
+        let snapshot = record(&response(
+            200,
+            r#"{"id":"a1","createdAt":"2024-01-01","items":[{"id":1,"name":"x"}]}"#,
+        ));
+        let later = response(
+            200,
+            r#"{"id":"b2","createdAt":"2024-02-01","items":[{"id":2,"name":"x"}]}"#,
+        );
+        let ignore = |paths: &[&str]| paths.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        assert!(
+            check(
+                &snapshot,
+                &later,
+                &ignore(&["$.id", "createdAt", "$.items[*].id"])
+            )
+            .passed
+        );
+        assert!(check(&snapshot, &later, &ignore(&["$..id", "$.createdAt", ""])).passed);
+        assert_eq!(
+            check(&snapshot, &later, &ignore(&["$..id"]))
+                .error_message
+                .unwrap(),
+            "$.createdAt: expected \"2024-01-01\", got \"2024-02-01\""
+        );
+        assert!(!check(&snapshot, &later, &ignore(&["$.items["])).passed);
+    }
+
+    #[test]
+    fn reports_the_first_different_line_of_text() {
+        let snapshot = record(&response(200, "one\ntwo\nthree"));
+
+        assert!(check(&snapshot, &response(200, "one\ntwo\nthree"), &[]).passed);
+        assert_eq!(
+            check(&snapshot, &response(200, "one\n2\nthree"), &[])
+                .error_message
+                .unwrap(),
+            "Line 2: expected \"two\", got \"2\""
+        );
+        assert_eq!(
+            check(&snapshot, &response(200, "one\ntwo"), &[])
+                .error_message
+                .unwrap(),
+            "Line 3: the body ends early"
+        );
+    }
+}
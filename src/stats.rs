@@ -0,0 +1,349 @@
+use crate::types::{HttpMethod, RequestCollection, RequestConfig, RunRecord};
+use chrono::{DateTime, Local, NaiveDate};
+use std::collections::BTreeMap;
+
+/// Number of days shown in the errors over time chart
+pub const DAYS: usize = 14;
+
+/// Run history of one request, summed up
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointStats {
+    pub name: String,
+    pub method: HttpMethod,
+    pub runs: usize,
+    pub failures: usize,
+    pub average_ms: u64,
+    pub slowest_ms: u64,
+    pub over_budget: usize,
+}
+
+/// Runs and failures of a day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStats {
+    pub date: NaiveDate,
+    pub runs: usize,
+    pub failures: usize,
+}
+
+/// Run history of every request of a collection, summed up
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionStats {
+    pub name: String,
+    pub runs: usize,
+    pub failures: usize,
+    pub average_ms: u64,
+    /// Requests that were sent at least once, slowest on average first
+    pub endpoints: Vec<EndpointStats>,
+    /// The last `DAYS` days, oldest first
+    pub days: Vec<DayStats>,
+}
+
+impl CollectionStats {
+    /// Share of runs that got a 1xx, 2xx or 3xx response, in percent
+    pub fn success_rate(&self) -> f64 {
+        if self.runs == 0 {
+            return 0.0;
+        }
+        (self.runs - self.failures) as f64 * 100.0 / self.runs as f64
+    }
+}
+
+/// How often a request was sent
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestUsage {
+    pub name: String,
+    pub collection: String,
+    pub method: HttpMethod,
+    pub runs: usize,
+}
+
+/// Requests sent to a host and how long they took
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostStats {
+    pub host: String,
+    pub runs: usize,
+    pub average_ms: u64,
+}
+
+/// How Beam is used across all collections. Computed on the spot from the run history kept
+/// with the requests, nothing is collected or sent anywhere.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageStats {
+    pub runs: usize,
+    pub average_ms: u64,
+    /// Requests sent at least once, most sent first
+    pub requests: Vec<RequestUsage>,
+    /// Hosts sent to, most sent to first
+    pub hosts: Vec<HostStats>,
+}
+
+/// Runs without a response or answered with an error status count as failures
+pub fn is_failure(run: &RunRecord) -> bool {
+    run.status == 0 || run.status >= 400
+}
+
+pub fn collection_stats(collection: &RequestCollection, today: NaiveDate) -> CollectionStats {
+    let mut endpoints = Vec::new();
+    let mut per_day: BTreeMap<NaiveDate, (usize, usize)> = BTreeMap::new();
+    let (mut runs, mut failures, mut total_ms) = (0, 0, 0);
+
+    for request in &collection.requests {
+        let history = &request.run_history;
+        if history.is_empty() {
+            continue;
+        }
+
+        let request_failures = history.iter().filter(|run| is_failure(run)).count();
+        let request_ms: u64 = history.iter().map(|run| run.duration).sum();
+        endpoints.push(EndpointStats {
+            name: request.name.clone(),
+            method: request.method.clone(),
+            runs: history.len(),
+            failures: request_failures,
+            average_ms: request_ms / history.len() as u64,
+            slowest_ms: history.iter().map(|run| run.duration).max().unwrap_or(0),
+            over_budget: history.iter().filter(|run| run.over_budget()).count(),
+        });
+
+        runs += history.len();
+        failures += request_failures;
+        total_ms += request_ms;
+
+        for run in history {
+            if let Ok(time) = DateTime::parse_from_rfc3339(&run.timestamp) {
+                let day = per_day
+                    .entry(time.with_timezone(&Local).date_naive())
+                    .or_default();
+                day.0 += 1;
+                day.1 += usize::from(is_failure(run));
+            }
+        }
+    }
+
+    endpoints.sort_by_key(|endpoint| std::cmp::Reverse(endpoint.average_ms));
+
+    let days = (0..DAYS as u64)
+        .rev()
+        .filter_map(|ago| today.checked_sub_days(chrono::Days::new(ago)))
+        .map(|date| {
+            let (runs, failures) = per_day.get(&date).copied().unwrap_or_default();
+            DayStats {
+                date,
+                runs,
+                failures,
+            }
+        })
+        .collect();
+
+    CollectionStats {
+        name: collection.name.clone(),
+        runs,
+        failures,
+        average_ms: if runs == 0 { 0 } else { total_ms / runs as u64 },
+        endpoints,
+        days,
+    }
+}
+
+/// Usage of every request of `collections`, `host_of` telling where a request is sent, e.g.
+/// with its variables resolved
+pub fn usage_stats(
+    collections: &[RequestCollection],
+    host_of: impl Fn(&RequestConfig) -> String,
+) -> UsageStats {
+    let mut requests = Vec::new();
+    let mut per_host: BTreeMap<String, (usize, u64)> = BTreeMap::new();
+    let (mut runs, mut total_ms) = (0, 0);
+
+    for collection in collections {
+        for request in &collection.requests {
+            let history = &request.run_history;
+            if history.is_empty() {
+                continue;
+            }
+
+            let request_ms: u64 = history.iter().map(|run| run.duration).sum();
+            requests.push(RequestUsage {
+                name: request.name.clone(),
+                collection: collection.name.clone(),
+                method: request.method.clone(),
+                runs: history.len(),
+            });
+
+            let host = per_host.entry(host_of(request)).or_default();
+            host.0 += history.len();
+            host.1 += request_ms;
+            runs += history.len();
+            total_ms += request_ms;
+        }
+    }
+
+    requests.sort_by_key(|request| std::cmp::Reverse(request.runs));
+    let mut hosts: Vec<HostStats> = per_host
+        .into_iter()
+        .map(|(host, (runs, total_ms))| HostStats {
+            host,
+            runs,
+            average_ms: total_ms / runs as u64,
+        })
+        .collect();
+    hosts.sort_by_key(|host| std::cmp::Reverse(host.runs));
+
+    UsageStats {
+        runs,
+        average_ms: if runs == 0 { 0 } else { total_ms / runs as u64 },
+        requests,
+        hosts,
+    }
+}
+
+/// The host and port of `url`, or its start when it isn't a full URL yet
+pub fn host(url: &str) -> String {
+    let url = url.trim();
+    let with_scheme = if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("http://{}", url)
+    };
+    match reqwest::Url::parse(&with_scheme) {
+        Ok(parsed) if parsed.host_str().is_some() => {
+            let host = parsed.host_str().unwrap_or_default();
+            match parsed.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.to_string(),
+            }
+        }
+        _ => url
+            .split("://")
+            .last()
+            .unwrap_or_default()
+            .split(['/', '?'])
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(timestamp: &str, status: u16, duration: u64) -> RunRecord {
+        RunRecord {
+            timestamp: timestamp.to_string(),
+            status,
+            duration,
+            budget: Some(250),
+            note: String::new(),
+        }
+    }
+
+    #[test]
+    fn sums_up_runs_per_request_and_day() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+        let noon = |day: u32| {
+            NaiveDate::from_ymd_opt(2024, 3, day)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_local_timezone(Local)
+                .unwrap()
+                .to_rfc3339()
+        };
+
+        let mut users = RequestConfig {
+            name: "Users".to_string(),
+            ..RequestConfig::default()
+        };
+        users.run_history = vec![run(&noon(19), 200, 100), run(&noon(20), 500, 300)];
+        let mut orders = RequestConfig {
+            name: "Orders".to_string(),
+            ..RequestConfig::default()
+        };
+        orders.run_history = vec![run(&noon(20), 0, 800), run(&noon(1), 201, 400)];
+        let collection = RequestCollection {
+            name: "Shop".to_string(),
+            id: String::new(),
+            folder_name: String::new(),
+            requests: vec![users, orders, RequestConfig::default()],
+            expanded: true,
+            color: None,
+            icon: None,
+            sort: Default::default(),
+            run_configuration: Default::default(),
+        };
+
+        let stats = collection_stats(&collection, today);
+
+        assert_eq!((stats.runs, stats.failures, stats.average_ms), (4, 2, 400));
+        assert_eq!(stats.success_rate(), 50.0);
+        assert_eq!(stats.endpoints.len(), 2);
+        assert_eq!(stats.endpoints[0].name, "Orders");
+        assert_eq!(
+            (stats.endpoints[0].average_ms, stats.endpoints[0].slowest_ms),
+            (600, 800)
+        );
+        assert_eq!(stats.endpoints[1].over_budget, 1);
+
+        assert_eq!(stats.days.len(), DAYS);
+        assert_eq!(stats.days[DAYS - 1].date, today);
+        assert_eq!(
+            (stats.days[DAYS - 1].runs, stats.days[DAYS - 1].failures),
+            (2, 2)
+        );
+        assert_eq!(stats.days[DAYS - 2].runs, 1);
+        // Older than the chart
+        assert_eq!(stats.days.iter().map(|day| day.runs).sum::<usize>(), 3);
+    }
+
+    #[test]
+    fn sums_up_usage_per_request_and_host() {
+        let request = |name: &str, url: &str, durations: &[u64]| RequestConfig {
+            name: name.to_string(),
+            url: url.to_string(),
+            run_history: durations
+                .iter()
+                .map(|duration| run("2024-03-20T12:00:00Z", 200, *duration))
+                .collect(),
+            ..RequestConfig::default()
+        };
+        let collection = RequestCollection {
+            name: "Shop".to_string(),
+            id: String::new(),
+            folder_name: String::new(),
+            requests: vec![
+                request("Users", "https://api.example.com/users", &[100]),
+                request("Orders", "https://api.example.com:8443/orders", &[200, 400]),
+                request("Search", "api.example.com/search?q=1", &[300, 500, 700]),
+                request("Unsent", "https://example.com", &[]),
+            ],
+            expanded: true,
+            color: None,
+            icon: None,
+            sort: Default::default(),
+            run_configuration: Default::default(),
+        };
+
+        let stats = usage_stats(&[collection], |request| host(&request.url));
+
+        assert_eq!((stats.runs, stats.average_ms), (6, 366));
+        let names: Vec<&str> = stats.requests.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["Search", "Orders", "Users"]);
+        assert_eq!(
+            stats.hosts,
+            vec![
+                HostStats {
+                    host: "api.example.com".to_string(),
+                    runs: 4,
+                    average_ms: 400,
+                },
+                HostStats {
+                    host: "api.example.com:8443".to_string(),
+                    runs: 2,
+                    average_ms: 300,
+                },
+            ]
+        );
+        assert_eq!(host("{{base_url}}/users"), "{{base_url}}");
+    }
+}
@@ -0,0 +1,190 @@
+use super::StorageError;
+use crate::i18n::tr_args;
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOperation {
+    Create,
+    Rename,
+    Delete,
+}
+
+/// What kind of workspace item was changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditSubject {
+    Collection,
+    Request,
+    Environment,
+}
+
+/// One change to the workspace: who made it, when, and to what
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: String, // RFC 3339
+    pub user: String,
+    pub operation: AuditOperation,
+    pub subject: AuditSubject,
+    pub name: String,
+    /// The name before a rename
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_name: Option<String>,
+    /// The collection of a request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+}
+
+impl AuditEntry {
+    /// An entry made now by the current user
+    pub fn new(operation: AuditOperation, subject: AuditSubject, name: impl Into<String>) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            user: current_user(),
+            operation,
+            subject,
+            name: name.into(),
+            previous_name: None,
+            collection: None,
+        }
+    }
+
+    pub fn renamed_from(mut self, previous_name: impl Into<String>) -> Self {
+        self.previous_name = Some(previous_name.into());
+        self
+    }
+
+    pub fn in_collection(mut self, collection: impl Into<String>) -> Self {
+        self.collection = Some(collection.into());
+        self
+    }
+
+    /// The change in a sentence, e.g. `Renamed request "List" to "List users" in Users`
+    pub fn summary(&self) -> String {
+        let subject = match self.subject {
+            AuditSubject::Collection => "collection",
+            AuditSubject::Request => "request",
+            AuditSubject::Environment => "environment",
+        };
+        let operation = match self.operation {
+            AuditOperation::Create => "create",
+            AuditOperation::Rename => "rename",
+            AuditOperation::Delete => "delete",
+        };
+        let previous_name = self.previous_name.as_deref().unwrap_or(&self.name);
+        let summary = tr_args(
+            &format!("audit-{}-{}", operation, subject),
+            &[("name", &self.name), ("previous", &previous_name)],
+        );
+        match &self.collection {
+            Some(collection) => tr_args(
+                "audit-in-collection",
+                &[("summary", &summary), ("collection", collection)],
+            ),
+            None => summary,
+        }
+    }
+}
+
+/// The user changes are attributed to, from the environment of the process
+pub fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .filter(|user| !user.trim().is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Append-only record of the collections, requests and environments created, renamed and
+/// deleted. It's stored as NDJSON in `audit.ndjson` next to the collections, so it travels
+/// with a shared workspace and entries are never rewritten.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn new(base_path: &Path) -> Self {
+        Self {
+            path: base_path.join("audit.ndjson"),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, entry: &AuditEntry) -> Result<(), StorageError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let line = serde_json::to_string(entry)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+
+    /// Every entry, the latest first. A workspace without changes yet has none.
+    pub fn load(&self) -> Result<Vec<AuditEntry>, StorageError> {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => {
+                let mut entries = parse_ndjson(&content);
+                entries.reverse();
+                Ok(entries)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Reads NDJSON entries, skipping lines that don't parse such as one cut short by a crash
+fn parse_ndjson(content: &str) -> Vec<AuditEntry> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_entries_and_describes_them() {
+        let content = concat!(
+            r#"{"timestamp":"2024-01-31T12:00:00Z","user":"ana","operation":"create","subject":"collection","name":"Users"}"#,
+            "\n",
+            r#"{"timestamp":"2024-01-31T12:05:00Z","user":"bo","operation":"rename","subject":"request","name":"List users","previous_name":"List","collection":"Users"}"#,
+            "\n",
+            r#"{"timestamp":"2024-01-31T12:06"#,
+        );
+
+        let entries = parse_ndjson(content);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].summary(), "Created collection \"Users\"");
+        assert_eq!(entries[1].user, "bo");
+        assert_eq!(
+            entries[1].summary(),
+            "Renamed request \"List\" to \"List users\" in Users"
+        );
+
+        let line = serde_json::to_string(&AuditEntry {
+            timestamp: "2024-01-31T12:10:00Z".to_string(),
+            ..entries[0].clone()
+        })
+        .unwrap();
+        assert!(!line.contains("previous_name"));
+    }
+}
@@ -18,9 +18,14 @@ impl ToPersistent<PersistentCollection> for RequestCollection {
             name: self.name.clone(),
             requests: self.requests.iter().map(|r| r.to_persistent()).collect(),
             metadata: CollectionMetadata {
+                id: self.id.clone(),
                 name: self.name.clone(),
                 description: None,
                 expanded: self.expanded,
+                color: self.color,
+                icon: self.icon,
+                sort: self.sort,
+                run: self.run_configuration.clone(),
             },
         }
     }
@@ -2,19 +2,29 @@ use super::{
     CollectionMetadata, CollectionStorage, EnvironmentsMetadata, PersistentEnvironments,
     PersistentRequest, StorageError,
 };
-use crate::storage::RequestMetadata;
-use crate::types::{Environment, RequestCollection, RequestConfig, SerializableRequestConfig};
-use log::{error, info};
+use crate::constant::{DEFAULT_MAX_DOWNLOAD_SIZE, DEFAULT_MAX_UPLOAD_SIZE};
+use crate::types::{
+    CollectionSort, ContentEncoding, Environment, HttpMethod, RequestCollection, RequestConfig,
+    RunConfiguration, SerializableRequestConfig, WindowLayout, new_id,
+};
+use log::{error, info, warn};
 use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 /// TOML-based file storage implementation
 pub struct TomlFileStorage {
     base_path: PathBuf,
     collections_path: PathBuf,
     environments_path: PathBuf,
+    /// Contents of files as Beam last read or wrote them, used to notice changes made by
+    /// another Beam instance or an external editor
+    known_contents: Mutex<HashMap<PathBuf, String>>,
 }
 
 impl TomlFileStorage {
@@ -27,6 +37,7 @@ impl TomlFileStorage {
             base_path,
             collections_path,
             environments_path,
+            known_contents: Mutex::new(HashMap::new()),
         }
     }
 
@@ -77,6 +88,11 @@ impl TomlFileStorage {
         self.base_path.join("last_opened_request.toml")
     }
 
+    /// Get the path for the window layout file
+    fn window_layout_path(&self) -> PathBuf {
+        self.base_path.join("layout.toml")
+    }
+
     /// Save a collection to disk (metadata only)
     fn save_collection_to_disk(&self, collection: &RequestCollection) -> Result<(), StorageError> {
         // Try to find existing collection directory by name first
@@ -93,16 +109,35 @@ impl TomlFileStorage {
 
         // Save collection metadata with the collection name stored in TOML
         let metadata = CollectionMetadata {
+            id: collection.id.clone(),
             name: collection.name.clone(),
             description: None,
             expanded: collection.expanded,
+            color: collection.color,
+            icon: collection.icon,
+            sort: collection.sort,
+            run: collection.run_configuration.clone(),
         };
 
         let metadata_content = toml::to_string_pretty(&metadata)
             .map_err(|e| StorageError::SerializationError(e.to_string()))?;
 
         let metadata_path = collection_dir.join("collection.toml");
-        fs::write(&metadata_path, metadata_content)?;
+        self.write_atomic(&metadata_path, metadata_content)?;
+
+        Ok(())
+    }
+
+    /// Writes an id into an existing collection.toml, keeping the rest of its metadata
+    fn store_collection_id(&self, metadata_path: &Path, id: &str) -> Result<(), StorageError> {
+        let content = fs::read_to_string(metadata_path)?;
+        let mut metadata = toml::from_str::<CollectionMetadata>(&content)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        metadata.id = id.to_string();
+
+        let metadata_content = toml::to_string_pretty(&metadata)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        self.write_atomic(metadata_path, metadata_content)?;
 
         Ok(())
     }
@@ -279,15 +314,20 @@ impl TomlFileStorage {
 
             // Create collection.toml with the original folder name as the collection name
             let metadata = CollectionMetadata {
+                id: new_id(),
                 name: original_name.clone(),
                 description: None,
                 expanded: false,
+                color: None,
+                icon: None,
+                sort: CollectionSort::default(),
+                run: RunConfiguration::default(),
             };
 
             let metadata_content = toml::to_string_pretty(&metadata)
                 .map_err(|e| StorageError::SerializationError(e.to_string()))?;
             let metadata_path = new_path.join("collection.toml");
-            fs::write(&metadata_path, metadata_content)?;
+            self.write_atomic(&metadata_path, metadata_content)?;
 
             // Copy all request files from old to new location
             let old_entries = fs::read_dir(&old_path)?;
@@ -330,7 +370,7 @@ impl TomlFileStorage {
                         // Write the updated metadata back
                         let metadata_content = toml::to_string_pretty(&metadata)
                             .map_err(|e| StorageError::SerializationError(e.to_string()))?;
-                        fs::write(&metadata_path, metadata_content)?;
+                        self.write_atomic(&metadata_path, metadata_content)?;
 
                         println!(
                             "Fixed collection metadata for folder '{}' with name '{}'",
@@ -348,14 +388,19 @@ impl TomlFileStorage {
                         folder_name.clone()
                     };
                     let metadata = CollectionMetadata {
+                        id: new_id(),
                         name: collection_name.clone(),
                         description: None,
                         expanded: false,
+                        color: None,
+                        icon: None,
+                        sort: CollectionSort::default(),
+                        run: RunConfiguration::default(),
                     };
 
                     let metadata_content = toml::to_string_pretty(&metadata)
                         .map_err(|e| StorageError::SerializationError(e.to_string()))?;
-                    fs::write(&metadata_path, metadata_content)?;
+                    self.write_atomic(&metadata_path, metadata_content)?;
 
                     println!(
                         "Created new collection metadata for folder '{}' with name '{}'",
@@ -371,14 +416,19 @@ impl TomlFileStorage {
                     folder_name.clone()
                 };
                 let metadata = CollectionMetadata {
+                    id: new_id(),
                     name: collection_name.clone(),
                     description: None,
                     expanded: false,
+                    color: None,
+                    icon: None,
+                    sort: CollectionSort::default(),
+                    run: RunConfiguration::default(),
                 };
 
                 let metadata_content = toml::to_string_pretty(&metadata)
                     .map_err(|e| StorageError::SerializationError(e.to_string()))?;
-                fs::write(&metadata_path, metadata_content)?;
+                self.write_atomic(&metadata_path, metadata_content)?;
 
                 println!(
                     "Created collection metadata for folder '{}' with name '{}'",
@@ -394,17 +444,15 @@ impl TomlFileStorage {
 
 impl CollectionStorage for TomlFileStorage {
     fn load_collections(&self) -> Result<Vec<RequestCollection>, StorageError> {
+        // First run: the welcome screen offers to create collections
         if !self.collections_path.exists() {
-            self.initialize_default_collections()?;
+            return Ok(Vec::new());
         }
 
         // First, collect all collection directories with their metadata
         let mut collection_data = Vec::new();
         let collection_entries = fs::read_dir(&self.collections_path)?;
 
-        let mut collecion_index = 0;
-        let mut request_index = 0;
-
         for entry in collection_entries {
             let entry = entry?;
             let collection_path = entry.path();
@@ -426,6 +474,11 @@ impl CollectionStorage for TomlFileStorage {
                 };
 
             let mut collection_name: String = String::new();
+            let mut collection_id: String = String::new();
+            let mut collection_color = None;
+            let mut collection_icon = None;
+            let mut collection_sort = CollectionSort::default();
+            let mut collection_run = RunConfiguration::default();
 
             let mut requests = Vec::new();
 
@@ -446,41 +499,75 @@ impl CollectionStorage for TomlFileStorage {
                     if let Ok(content) = fs::read_to_string(&metadata_path) {
                         if let Ok(metadata) = toml::from_str::<CollectionMetadata>(&content) {
                             collection_name = metadata.name;
+                            collection_id = metadata.id;
+                            collection_color = metadata.color;
+                            collection_icon = metadata.icon;
+                            collection_sort = metadata.sort;
+                            collection_run = metadata.run;
                         }
                     }
                 } else {
                     // request toml files
-                    if let Ok(content) = fs::read_to_string(&request_path) {
-                        match toml::from_str::<SerializableRequestConfig>(&content) {
-                            Ok(r) => {
-                                requests.push(RequestConfig {
-                                    name: r.name.unwrap(),
-                                    path: request_path.clone(),
-                                    method: r.method,
-                                    url: r.url.unwrap_or_default(),
-                                    headers: r.headers,
-                                    params: r.params,
-                                    body: r.body.unwrap_or_default(),
-                                    content_type: r.content_type.unwrap_or_default(),
-                                    auth_type: r.auth_type.unwrap_or_default(),
-                                    body_format: r.body_format.unwrap_or_default(),
-                                    bearer_token: r.bearer_token.unwrap_or_default(),
-                                    basic_username: r.basic_username.unwrap_or_default(),
-                                    basic_password: r.basic_password.unwrap_or_default(),
-                                    api_key: r.api_key.unwrap_or_default(),
-                                    api_key_header: r.api_key_header.unwrap_or_default(),
-                                    metadata: r.metadata,
-                                    collection_index: collecion_index,
-                                    request_index: request_index,
-                                    post_request_script: r.post_request_script,
-                                    last_response: r.last_response,
-                                });
-
-                                request_index += 1;
-                            }
-                            Err(e) => {
-                                error!("===fail to parse: {:?}", e);
-                            }
+                    match self.read_toml_with_recovery::<SerializableRequestConfig>(&request_path) {
+                        Ok(r) => {
+                            requests.push(RequestConfig {
+                                id: r.id.unwrap_or_default(),
+                                name: r.name.unwrap(),
+                                path: request_path.clone(),
+                                method: r.method,
+                                url: r.url.unwrap_or_default(),
+                                headers: r.headers,
+                                params: r.params,
+                                body: r.body.unwrap_or_default(),
+                                content_type: r.content_type.unwrap_or_default(),
+                                auth_type: r.auth_type.unwrap_or_default(),
+                                body_format: r.body_format.unwrap_or_default(),
+                                bearer_token: r.bearer_token.unwrap_or_default(),
+                                basic_username: r.basic_username.unwrap_or_default(),
+                                basic_password: r.basic_password.unwrap_or_default(),
+                                api_key: r.api_key.unwrap_or_default(),
+                                api_key_header: r.api_key_header.unwrap_or_default(),
+                                api_key_location: r.api_key_location.unwrap_or_default(),
+                                metadata: r.metadata,
+                                unix_socket: r.unix_socket.unwrap_or_default(),
+                                max_upload_size: r
+                                    .max_upload_size
+                                    .unwrap_or(DEFAULT_MAX_UPLOAD_SIZE),
+                                max_download_size: r
+                                    .max_download_size
+                                    .unwrap_or(DEFAULT_MAX_DOWNLOAD_SIZE),
+                                latency_budget: r.latency_budget.unwrap_or_default(),
+                                retry_after_rate_limit: r
+                                    .retry_after_rate_limit
+                                    .unwrap_or_default(),
+                                audit_security_headers: r
+                                    .audit_security_headers
+                                    .unwrap_or_default(),
+                                accept_encoding: r
+                                    .accept_encoding
+                                    .unwrap_or_else(|| ContentEncoding::ALL.to_vec()),
+                                decompress_response: r.decompress_response.unwrap_or(true),
+                                send_query_as_typed: r.send_query_as_typed.unwrap_or_default(),
+                                connection: r.connection.unwrap_or_default(),
+                                hmac_auth: r.hmac_auth.unwrap_or_default(),
+                                hawk_auth: r.hawk_auth.unwrap_or_default(),
+                                pagination: r.pagination.unwrap_or_default(),
+                                cache_validators: r.cache_validators.unwrap_or_default(),
+                                run_history: r.run_history.unwrap_or_default(),
+                                examples: r.examples.unwrap_or_default(),
+                                post_request_script: r.post_request_script,
+                                path_variables: r.path_variables.unwrap_or_default(),
+                                param_docs: r.param_docs.unwrap_or_default(),
+                                header_docs: r.header_docs.unwrap_or_default(),
+                                assertions: r.assertions.unwrap_or_default(),
+                                token_refresh: r.token_refresh.unwrap_or_default(),
+                                contract: r.contract,
+                                monitor: r.monitor.unwrap_or_default(),
+                                last_response: r.last_response,
+                            });
+                        }
+                        Err(e) => {
+                            error!("===fail to parse {:?}: {}", request_path, e);
                         }
                     }
                 }
@@ -501,15 +588,32 @@ impl CollectionStorage for TomlFileStorage {
                 file_name_a.cmp(file_name_b)
             });
 
+            // Collections and requests saved before ids existed get one assigned now
+            for request in requests.iter_mut().filter(|request| request.id.is_empty()) {
+                request.id = new_id();
+                if let Err(e) = self.save_request_by_path(request) {
+                    error!("Failed to store id for request {:?}: {}", request.path, e);
+                }
+            }
+
+            if collection_id.is_empty() {
+                collection_id = new_id();
+                if let Err(e) = self.store_collection_id(&metadata_path, &collection_id) {
+                    error!("Failed to store id for collection {}: {}", folder_name, e);
+                }
+            }
+
             collection_data.push(RequestCollection {
+                id: collection_id,
                 folder_name: folder_name,
                 name: collection_name,
                 requests: requests,
                 expanded: false,
+                color: collection_color,
+                icon: collection_icon,
+                sort: collection_sort,
+                run_configuration: collection_run,
             });
-
-            collecion_index += 1;
-            request_index = 0;
         }
 
         // Sort by numeric folder names
@@ -519,14 +623,6 @@ impl CollectionStorage for TomlFileStorage {
             num_a.cmp(&num_b)
         });
 
-        // Update collection_index and request_index after sorting
-        for (collection_idx, collection) in collection_data.iter_mut().enumerate() {
-            for (request_idx, request) in collection.requests.iter_mut().enumerate() {
-                request.collection_index = collection_idx;
-                request.request_index = request_idx;
-            }
-        }
-
         // Load collections in sorted order using their actual names from metadata
         // let mut collections = Vec::new();
         // for (index, (_folder_name, collection)) in collection_data.iter().enumerate() {
@@ -566,7 +662,7 @@ impl CollectionStorage for TomlFileStorage {
         }
     }
 
-    fn rename_collection(&self, old_name: &str, new_name: &str) -> Result<(), StorageError> {
+    fn rename_collection(&self, old_name: &str, new_name: &str) -> Result<PathBuf, StorageError> {
         // Find collection directory by old name
         let collection_dir = match self.find_collection_directory_by_name(old_name)? {
             Some(dir) => dir,
@@ -598,9 +694,9 @@ impl CollectionStorage for TomlFileStorage {
         // Save updated metadata
         let metadata_content = toml::to_string_pretty(&metadata)
             .map_err(|e| StorageError::SerializationError(e.to_string()))?;
-        fs::write(&metadata_path, metadata_content)?;
+        self.write_atomic(&metadata_path, metadata_content)?;
 
-        Ok(())
+        Ok(collection_dir)
     }
 
     fn save_request(
@@ -660,7 +756,7 @@ impl CollectionStorage for TomlFileStorage {
             collection_dir.join(filename)
         };
 
-        fs::write(&request_path, request_content)?;
+        self.write_atomic(&request_path, request_content)?;
 
         Ok(())
     }
@@ -733,12 +829,25 @@ impl CollectionStorage for TomlFileStorage {
             collection_dir.join(filename)
         };
 
-        fs::write(&request_path, request_content)?;
+        self.write_atomic(&request_path, request_content)?;
 
         Ok(())
     }
 
     fn save_request_by_path(&self, request_config: &RequestConfig) -> Result<(), StorageError> {
+        if self.changed_on_disk(&request_config.path) {
+            return Err(StorageError::Conflict(
+                request_config.path.display().to_string(),
+            ));
+        }
+
+        self.overwrite_request_by_path(request_config)
+    }
+
+    fn overwrite_request_by_path(
+        &self,
+        request_config: &RequestConfig,
+    ) -> Result<(), StorageError> {
         // Check if the path is empty (new request without a file path)
         if request_config.path.as_os_str().is_empty() {
             return Err(StorageError::InvalidFormat(
@@ -754,11 +863,50 @@ impl CollectionStorage for TomlFileStorage {
             fs::create_dir_all(parent)?;
         }
 
-        fs::write(&request_config.path, request_content)?;
+        self.write_atomic(&request_config.path, request_content)?;
 
         Ok(())
     }
 
+    fn load_request_by_path(&self, request_path: &Path) -> Result<RequestConfig, StorageError> {
+        let mut request: RequestConfig = self.read_toml_with_recovery(request_path)?;
+        request.path = request_path.to_path_buf();
+        Ok(request)
+    }
+
+    fn merge_request_by_path(
+        &self,
+        request_config: &RequestConfig,
+    ) -> Result<RequestConfig, StorageError> {
+        let to_table = |content: &str| {
+            toml::from_str::<toml::Table>(content)
+                .map_err(|e| StorageError::SerializationError(e.to_string()))
+        };
+
+        let base = match self.known_contents(&request_config.path) {
+            Some(content) => to_table(&content)?,
+            None => toml::Table::new(),
+        };
+        let mut merged = to_table(&fs::read_to_string(&request_config.path)?)?;
+        let ours = to_table(
+            &toml::to_string(request_config)
+                .map_err(|e| StorageError::SerializationError(e.to_string()))?,
+        )?;
+
+        for (key, value) in ours {
+            if base.get(&key) != Some(&value) {
+                merged.insert(key, value);
+            }
+        }
+
+        let mut request: RequestConfig = toml::Value::Table(merged)
+            .try_into()
+            .map_err(|e: toml::de::Error| StorageError::SerializationError(e.to_string()))?;
+        request.path = request_config.path.clone();
+
+        Ok(request)
+    }
+
     fn get_new_request_path_from_collection(&self, collection: &RequestCollection) -> String {
         // If collection is empty, update the request path and save
         // else deduce the new file name, and save
@@ -846,9 +994,45 @@ impl CollectionStorage for TomlFileStorage {
     /// Delete a request by its file path directly (more efficient when path is known)
     fn delete_request_by_path(&self, request_path: &Path) -> Result<(), StorageError> {
         fs::remove_file(request_path)?;
+        let _ = fs::remove_file(sibling_path(request_path, "bak"));
+        self.delete_draft(request_path)
+    }
+
+    fn save_draft(&self, request_config: &RequestConfig) -> Result<(), StorageError> {
+        if request_config.path.as_os_str().is_empty() {
+            return Err(StorageError::InvalidFormat(
+                "Request path is empty".to_string(),
+            ));
+        }
+
+        let draft_content = toml::to_string_pretty(request_config)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        self.write_atomic(draft_path(&request_config.path), draft_content.as_bytes())?;
         Ok(())
     }
 
+    fn load_draft(&self, request_path: &Path) -> Result<Option<RequestConfig>, StorageError> {
+        let path = draft_path(request_path);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let mut draft: RequestConfig = toml::from_str(&fs::read_to_string(&path)?)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        // Saving the draft writes the request's own file
+        draft.path = request_path.to_path_buf();
+        Ok(Some(draft))
+    }
+
+    fn delete_draft(&self, request_path: &Path) -> Result<(), StorageError> {
+        let path = draft_path(request_path);
+        let _ = fs::remove_file(sibling_path(&path, "bak"));
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
     fn rename_request(
         &self,
         collection_name: &str,
@@ -909,7 +1093,7 @@ impl CollectionStorage for TomlFileStorage {
         let updated_content = toml::to_string_pretty(&persistent_request)
             .map_err(|e| StorageError::SerializationError(e.to_string()))?;
 
-        fs::write(&file_path, updated_content)?;
+        self.write_atomic(&file_path, updated_content)?;
 
         Ok(())
     }
@@ -923,9 +1107,8 @@ impl CollectionStorage for TomlFileStorage {
             });
         }
 
-        let content = fs::read_to_string(&self.environments_path)?;
-        let persistent_envs: PersistentEnvironments = toml::from_str(&content)
-            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        let persistent_envs: PersistentEnvironments =
+            self.read_toml_with_recovery(&self.environments_path)?;
 
         Ok(persistent_envs)
     }
@@ -951,7 +1134,7 @@ impl CollectionStorage for TomlFileStorage {
         let content = toml::to_string_pretty(&persistent_data)
             .map_err(|e| StorageError::SerializationError(e.to_string()))?;
 
-        fs::write(&self.environments_path, content)?;
+        self.write_atomic(&self.environments_path, content)?;
         Ok(())
     }
 
@@ -960,46 +1143,50 @@ impl CollectionStorage for TomlFileStorage {
             return Ok(None);
         }
 
+        let persistent_envs: PersistentEnvironments =
+            self.read_toml_with_recovery(&self.environments_path)?;
+
+        Ok(persistent_envs.active_environment)
+    }
+
+    fn reload_environments(&self) -> Result<Option<PersistentEnvironments>, StorageError> {
+        if !self.changed_on_disk(&self.environments_path) {
+            return Ok(None);
+        }
         let content = fs::read_to_string(&self.environments_path)?;
         let persistent_envs: PersistentEnvironments = toml::from_str(&content)
             .map_err(|e| StorageError::SerializationError(e.to_string()))?;
-
-        Ok(persistent_envs.active_environment)
+        self.remember_contents(&self.environments_path, &content);
+        Ok(Some(persistent_envs))
     }
 
-    fn save_last_opened_request(
-        &self,
-        collection_index: usize,
-        request_index: usize,
-    ) -> Result<(), StorageError> {
+    fn save_last_opened_request(&self, request_id: &str) -> Result<(), StorageError> {
         // Create base directory only when saving
         fs::create_dir_all(&self.base_path)?;
 
         #[derive(Serialize)]
-        struct LastOpenedRequest {
-            collection_index: usize,
-            request_index: usize,
+        struct LastOpenedRequest<'a> {
+            request_id: &'a str,
         }
 
-        let data = LastOpenedRequest {
-            collection_index,
-            request_index,
-        };
+        let data = LastOpenedRequest { request_id };
 
         let content = toml::to_string_pretty(&data)
             .map_err(|e| StorageError::SerializationError(e.to_string()))?;
 
-        fs::write(self.last_opened_request_path(), content)?;
+        self.write_atomic(self.last_opened_request_path(), content)?;
         Ok(())
     }
 
-    fn load_last_opened_request(&self) -> Result<Option<(usize, usize)>, StorageError> {
+    fn load_last_opened_request(&self) -> Result<Option<String>, StorageError> {
         use serde::Deserialize;
 
+        // Older versions stored the position of the request instead of its id
         #[derive(Deserialize)]
         struct LastOpenedRequest {
-            collection_index: usize,
-            request_index: usize,
+            request_id: Option<String>,
+            collection_index: Option<usize>,
+            request_index: Option<usize>,
         }
 
         let path = self.last_opened_request_path();
@@ -1012,7 +1199,47 @@ impl CollectionStorage for TomlFileStorage {
         let data: LastOpenedRequest = toml::from_str(&content)
             .map_err(|e| StorageError::SerializationError(e.to_string()))?;
 
-        Ok(Some((data.collection_index, data.request_index)))
+        if let Some(request_id) = data.request_id {
+            return Ok(Some(request_id));
+        }
+
+        let (Some(collection_index), Some(request_index)) =
+            (data.collection_index, data.request_index)
+        else {
+            return Ok(None);
+        };
+
+        let request_id = self
+            .load_collections()?
+            .get(collection_index)
+            .and_then(|collection| collection.requests.get(request_index))
+            .map(|request| request.id.clone());
+
+        if let Some(request_id) = &request_id {
+            self.save_last_opened_request(request_id)?;
+        }
+
+        Ok(request_id)
+    }
+
+    fn save_window_layout(&self, layout: &WindowLayout) -> Result<(), StorageError> {
+        fs::create_dir_all(&self.base_path)?;
+
+        let content = toml::to_string_pretty(layout)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+
+        self.write_atomic(self.window_layout_path(), content)?;
+        Ok(())
+    }
+
+    fn load_window_layout(&self) -> Result<Option<WindowLayout>, StorageError> {
+        let path = self.window_layout_path();
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        self.read_toml_with_recovery(&path).map(Some)
     }
 
     fn load_request_by_indices(
@@ -1145,84 +1372,79 @@ impl CollectionStorage for TomlFileStorage {
         Ok(())
     }
 
-    /// Initialize default collections and files when the app is launched for the first time
-    /// Creates:
-    /// - collections/0001/0001.toml (default request)
-    /// - collections/0001/collection.toml (collection metadata)
-    /// - last_opened_request.toml (tracks last opened request)
+    /// Creates the sample collection offered on the welcome screen: a handful of
+    /// httpbin.org requests covering query params, JSON bodies, auth and status codes.
+    /// The first request becomes the last opened one.
     fn initialize_default_collections(&self) -> Result<(), StorageError> {
-        // Check if collections already exist - if so, don't create defaults
-        if self.collections_path.exists() && self.collections_path.read_dir()?.next().is_some() {
-            info!("Collections already exist, skipping default initialization");
-            return Ok(());
-        }
-
-        // Create the first collection directory: collections/0001
-        let first_collection_dir = self.collections_path.join("0001");
-        fs::create_dir_all(&first_collection_dir)?;
-
-        // Create the first request file: collections/0001/0001.toml
-        let first_request_path = first_collection_dir.join("0001.toml");
-        let default_request = RequestConfig {
-            name: "My First Request".to_string(),
-            path: first_request_path.clone(),
-            method: crate::types::HttpMethod::GET,
-            url: "https://httpbin.org/get".to_string(),
-            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
-            params: vec![],
-            body: String::new(),
-            content_type: "application/json".to_string(),
-            auth_type: crate::types::AuthType::None,
-            body_format: crate::types::BodyFormat::Json,
-            bearer_token: String::new(),
-            basic_username: String::new(),
-            basic_password: String::new(),
-            api_key: String::new(),
-            api_key_header: String::new(),
-            collection_index: 0,
-            request_index: 0,
-            metadata: Some(RequestMetadata::default()),
-            post_request_script: None,
-            last_response: None,
+        let sample = |name: &str, method: HttpMethod, url: &str| RequestConfig {
+            name: name.to_string(),
+            method,
+            url: url.to_string(),
+            ..RequestConfig::default()
         };
 
-        let request_content = toml::to_string_pretty(&default_request)
-            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
-        fs::write(&first_request_path, request_content)?;
-
-        // Create the collection metadata file: collections/0001/collection.toml
-        let collection_metadata_path = first_collection_dir.join("collection.toml");
-        let default_collection = RequestCollection {
-            name: "Default Collection".to_string(),
-            folder_name: "0001".to_string(),
-            requests: vec![],
-            // requests: vec![default_request], // Include the first request
-            expanded: true,
-        };
-
-        let collection_content = toml::to_string_pretty(&default_collection)
-            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
-        fs::write(&collection_metadata_path, collection_content)?;
+        let mut get = sample("Get with query", HttpMethod::GET, "https://httpbin.org/get");
+        get.params = vec![("hello".to_string(), "world".to_string())];
 
-        // Create the last opened request file: last_opened_request.toml
-        #[derive(Serialize)]
-        struct LastOpenedRequestData {
-            collection_index: usize,
-            request_index: usize,
-        }
-
-        let last_opened_data = LastOpenedRequestData {
-            collection_index: 0,
-            request_index: 0,
-        };
+        let mut post = sample("Post JSON", HttpMethod::POST, "https://httpbin.org/post");
+        post.body_format = crate::types::BodyFormat::Json;
+        post.content_type = "application/json".to_string();
+        post.headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        post.body = "{\n  \"name\": \"{{$randomFullName}}\",\n  \"email\": \"{{$randomEmail}}\"\n}"
+            .to_string();
 
-        let last_opened_content = toml::to_string_pretty(&last_opened_data)
-            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        let mut bearer = sample("Bearer auth", HttpMethod::GET, "https://httpbin.org/bearer");
+        bearer.auth_type = crate::types::AuthType::Bearer;
+        bearer.bearer_token = "sample-token".to_string();
 
-        let last_opened_path = self.last_opened_request_path();
-        fs::write(&last_opened_path, last_opened_content)?;
+        let mut basic = sample(
+            "Basic auth",
+            HttpMethod::GET,
+            "https://httpbin.org/basic-auth/beam/secret",
+        );
+        basic.auth_type = crate::types::AuthType::Basic;
+        basic.basic_username = "beam".to_string();
+        basic.basic_password = "secret".to_string();
+
+        let requests = vec![
+            get,
+            post,
+            bearer,
+            basic,
+            sample(
+                "Not found",
+                HttpMethod::GET,
+                "https://httpbin.org/status/404",
+            ),
+            sample(
+                "Slow response",
+                HttpMethod::GET,
+                "https://httpbin.org/delay/2",
+            ),
+        ];
+        let first_request_id = requests[0].id.clone();
+
+        let mut name = "httpbin Examples".to_string();
+        let mut counter = 2;
+        while self.find_collection_directory_by_name(&name)?.is_some() {
+            name = format!("httpbin Examples ({})", counter);
+            counter += 1;
+        }
 
-        info!("Default collections initialized successfully");
+        self.save_collection_with_requests(&RequestCollection {
+            id: new_id(),
+            name,
+            folder_name: String::new(),
+            requests,
+            expanded: true,
+            color: None,
+            icon: None,
+            sort: CollectionSort::default(),
+            run_configuration: RunConfiguration::default(),
+        })?;
+        self.save_last_opened_request(&first_request_id)?;
+
+        info!("Sample collection created");
         Ok(())
     }
 
@@ -1290,6 +1512,113 @@ fn sort_by_numeric_prefix(names: &mut Vec<String>) {
     });
 }
 
+impl TomlFileStorage {
+    /// Writes a file with `write_atomic` and remembers what was written
+    fn write_atomic(
+        &self,
+        path: impl AsRef<Path>,
+        content: impl AsRef<[u8]>,
+    ) -> std::io::Result<()> {
+        let path = path.as_ref();
+        write_atomic(path, content.as_ref())?;
+        self.remember_contents(path, &String::from_utf8_lossy(content.as_ref()));
+        Ok(())
+    }
+
+    /// Reads and parses a TOML file, falling back to the `.bak` copy kept by `write_atomic`
+    /// when the file is damaged. A recovered backup is written back in place of the file.
+    fn read_toml_with_recovery<T: DeserializeOwned>(&self, path: &Path) -> Result<T, StorageError> {
+        let error = match fs::read_to_string(path) {
+            Ok(content) => match toml::from_str::<T>(&content) {
+                Ok(value) => {
+                    self.remember_contents(path, &content);
+                    return Ok(value);
+                }
+                Err(e) => StorageError::SerializationError(e.to_string()),
+            },
+            Err(e) => StorageError::IoError(e),
+        };
+
+        let Ok(backup) = fs::read_to_string(sibling_path(path, "bak")) else {
+            return Err(error);
+        };
+
+        match toml::from_str::<T>(&backup) {
+            Ok(value) => {
+                warn!("Recovered {:?} from its backup: {}", path, error);
+                self.write_atomic(path, &backup)?;
+                Ok(value)
+            }
+            Err(_) => Err(error),
+        }
+    }
+
+    fn remember_contents(&self, path: &Path, content: &str) {
+        if let Ok(mut known) = self.known_contents.lock() {
+            known.insert(path.to_path_buf(), content.to_string());
+        }
+    }
+
+    fn known_contents(&self, path: &Path) -> Option<String> {
+        self.known_contents.lock().ok()?.get(path).cloned()
+    }
+
+    /// True if the file differs from the version Beam last read or wrote
+    fn changed_on_disk(&self, path: &Path) -> bool {
+        match (self.known_contents(path), fs::read_to_string(path)) {
+            (Some(known), Ok(current)) => known != current,
+            _ => false,
+        }
+    }
+}
+
+/// Writes a file atomically: the content goes to a temporary sibling that is synced to
+/// disk and renamed over the target, so a crash mid-save never leaves a truncated file.
+/// The previous version is kept next to it as `<file>.bak`.
+pub(crate) fn write_atomic(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    // Unique per write so concurrent saves of the same file don't share a temp file
+    let tmp_path = sibling_path(path, &format!("{}.tmp", new_id()));
+
+    let replaced = (|| {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        drop(file);
+
+        if path.exists() {
+            fs::copy(path, sibling_path(path, "bak"))?;
+        }
+        fs::rename(&tmp_path, path)
+    })();
+    // A failed write leaves the target as it was, and no temp file behind
+    if let Err(e) = replaced {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+
+    // Make the rename itself durable
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        fs::File::open(parent)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
+/// `name.toml` -> `name.toml.draft`, where unsaved edits are kept. It isn't a `.toml` file so
+/// it's never loaded as a request of its own.
+pub fn draft_path(request_path: &Path) -> PathBuf {
+    sibling_path(request_path, "draft")
+}
+
+/// `name.toml` -> `name.toml.<suffix>`
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
 /// Recursively copy a directory
 #[allow(dead_code)]
 fn copy_dir_all(src: &Path, dst: &Path) -> Result<(), std::io::Error> {
@@ -1,10 +1,14 @@
-use super::types::{Environment, RequestCollection, RequestConfig};
+use super::types::{Environment, RequestCollection, RequestConfig, WindowLayout};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::Arc;
 
+pub mod audit_log;
 pub mod conversions;
 pub mod file_storage;
 pub mod persistent_types;
+pub mod stream_log;
+pub mod versions;
 
 // Re-export for convenience
 pub use file_storage::TomlFileStorage;
@@ -28,8 +32,8 @@ pub trait CollectionStorage: Send + Sync {
     /// Delete a collection from storage
     fn delete_collection_by_folder_name(&self, folder_name: &str);
 
-    /// Rename a collection
-    fn rename_collection(&self, old_name: &str, new_name: &str) -> Result<(), StorageError>;
+    /// Rename a collection, returning the directory holding its requests
+    fn rename_collection(&self, old_name: &str, new_name: &str) -> Result<PathBuf, StorageError>;
 
     /// Save a request within a collection
     fn save_request(
@@ -49,9 +53,36 @@ pub trait CollectionStorage: Send + Sync {
         request_config: &RequestConfig,
     ) -> Result<(), StorageError>;
 
-    /// Save a request directly to a file path (simplified version)
+    /// Save a request directly to a file path (simplified version). Fails with
+    /// `StorageError::Conflict` if the file was changed outside Beam since it was loaded
     fn save_request_by_path(&self, request_config: &RequestConfig) -> Result<(), StorageError>;
 
+    /// Save a request to its file path even if the file was changed outside Beam
+    fn overwrite_request_by_path(&self, request_config: &RequestConfig)
+    -> Result<(), StorageError>;
+
+    /// Load a single request from its file path
+    fn load_request_by_path(&self, request_path: &std::path::Path)
+    -> Result<RequestConfig, StorageError>;
+
+    /// Combine the in-memory request with the version changed on disk: fields edited in
+    /// Beam win, everything else is taken from disk
+    fn merge_request_by_path(
+        &self,
+        request_config: &RequestConfig,
+    ) -> Result<RequestConfig, StorageError>;
+
+    /// Write the in-progress edits of a request next to its file, which keeps the last saved
+    /// version
+    fn save_draft(&self, request_config: &RequestConfig) -> Result<(), StorageError>;
+
+    /// The unsaved edits of the request at `request_path`, `None` when it has none
+    fn load_draft(&self, request_path: &std::path::Path)
+    -> Result<Option<RequestConfig>, StorageError>;
+
+    /// Drop the unsaved edits of the request at `request_path`
+    fn delete_draft(&self, request_path: &std::path::Path) -> Result<(), StorageError>;
+
     /// Delete a request from a collection
     fn delete_request(&self, collection_name: &str, request_name: &str)
     -> Result<(), StorageError>;
@@ -83,15 +114,21 @@ pub trait CollectionStorage: Send + Sync {
     /// Load active environment name from storage
     fn load_active_environment(&self) -> Result<Option<String>, StorageError>;
 
-    /// Save the last opened request
-    fn save_last_opened_request(
-        &self,
-        collection_index: usize,
-        request_index: usize,
-    ) -> Result<(), StorageError>;
+    /// Environments as another program changed them since Beam last read or wrote them, None
+    /// when unchanged. A damaged file isn't recovered from its backup, it may be mid-edit.
+    fn reload_environments(&self) -> Result<Option<PersistentEnvironments>, StorageError>;
 
-    /// Load the last opened request
-    fn load_last_opened_request(&self) -> Result<Option<(usize, usize)>, StorageError>;
+    /// Save the id of the last opened request
+    fn save_last_opened_request(&self, request_id: &str) -> Result<(), StorageError>;
+
+    /// Load the id of the last opened request
+    fn load_last_opened_request(&self) -> Result<Option<String>, StorageError>;
+
+    /// Save the window size, position and pane layout
+    fn save_window_layout(&self, layout: &WindowLayout) -> Result<(), StorageError>;
+
+    /// Load the window layout saved by the previous session
+    fn load_window_layout(&self) -> Result<Option<WindowLayout>, StorageError>;
 
     /// Load a specific request by collection and request indices
     fn load_request_by_indices(
@@ -104,7 +141,7 @@ pub trait CollectionStorage: Send + Sync {
     /// Initialize storage (create directories, etc.)
     fn initialize_storage(&self) -> Result<(), StorageError>;
 
-    /// Initialize default collections and files when the app is launched for the first time
+    /// Create the sample collection offered on the welcome screen
     fn initialize_default_collections(&self) -> Result<(), StorageError>;
 
     /// Create a backup of the storage
@@ -166,6 +203,9 @@ pub enum StorageError {
     #[error("Invalid format: {0}")]
     InvalidFormat(String),
 
+    #[error("File was changed outside Beam: {0}")]
+    Conflict(String),
+
     #[error("Permission denied: {0}")]
     PermissionDenied(String),
 
@@ -173,9 +213,11 @@ pub enum StorageError {
     NotInitialized,
 }
 
-/// Storage manager that provides a unified interface
+/// Storage manager that provides a unified interface. Clones share the same storage, so
+/// what one clone reads or writes is known to the others.
+#[derive(Clone)]
 pub struct StorageManager {
-    storage: Box<dyn CollectionStorage>,
+    storage: Arc<dyn CollectionStorage>,
     config: StorageConfig,
 }
 
@@ -190,8 +232,8 @@ impl std::fmt::Debug for StorageManager {
 impl StorageManager {
     /// Create a new storage manager with the specified configuration
     pub fn new(config: StorageConfig) -> Result<Self, StorageError> {
-        let storage: Box<dyn CollectionStorage> = match config.storage_type {
-            StorageType::TomlFiles => Box::new(TomlFileStorage::new(config.base_path.clone())),
+        let storage: Arc<dyn CollectionStorage> = match config.storage_type {
+            StorageType::TomlFiles => Arc::new(TomlFileStorage::new(config.base_path.clone())),
             StorageType::Sqlite => {
                 return Err(StorageError::InvalidFormat(
                     "SQLite not implemented yet".to_string(),
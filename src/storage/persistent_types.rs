@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{Utc};
-use crate::types::Environment;
+use crate::types::{CollectionColor, CollectionIcon, CollectionSort, Environment, RunConfiguration};
 
 /// Persistent version of a request that can be serialized
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,18 +60,33 @@ pub struct PersistentCollection {
 /// Metadata for a collection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollectionMetadata {
+    #[serde(default)]
+    pub id: String,
     pub name: String,
     pub description: Option<String>,
     pub expanded: bool,
+    #[serde(default)]
+    pub color: Option<CollectionColor>,
+    #[serde(default)]
+    pub icon: Option<CollectionIcon>,
+    #[serde(default)]
+    pub sort: CollectionSort,
+    #[serde(default, skip_serializing_if = "RunConfiguration::is_empty")]
+    pub run: RunConfiguration,
 }
 
 impl Default for CollectionMetadata {
     fn default() -> Self {
         // let now = chrono::Utc::now().to_rfc3339();
         Self {
+            id: crate::types::new_id(),
             name: "New Collection".to_string(),
             description: None,
             expanded: false,
+            color: None,
+            icon: None,
+            sort: CollectionSort::default(),
+            run: RunConfiguration::default(),
         }
     }
 }
@@ -0,0 +1,152 @@
+use super::StorageError;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Which way a message of a WebSocket or SSE session went
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamDirection {
+    Sent,
+    Received,
+}
+
+/// One message of a streaming session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamMessage {
+    pub timestamp: String, // RFC 3339 with milliseconds
+    pub direction: StreamDirection,
+    pub data: String,
+}
+
+impl StreamMessage {
+    pub fn new(direction: StreamDirection, data: impl Into<String>) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            direction,
+            data: data.into(),
+        }
+    }
+}
+
+/// Message log of one streaming session. It's stored as NDJSON, one message per line in
+/// `streams/<request id>/<start time>.ndjson`, so messages are appended while the session
+/// runs and the file can be exported as is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamLog {
+    path: PathBuf,
+}
+
+impl StreamLog {
+    pub fn new(base_path: &Path, request_id: &str, started_at: DateTime<Utc>) -> Self {
+        Self {
+            path: streams_dir(base_path, request_id).join(format!(
+                "{}.ndjson",
+                started_at.format("%Y%m%dT%H%M%S%.3fZ")
+            )),
+        }
+    }
+
+    /// Logs of the sessions of a request, the latest first
+    pub fn sessions(base_path: &Path, request_id: &str) -> Vec<StreamLog> {
+        let Ok(entries) = fs::read_dir(streams_dir(base_path, request_id)) else {
+            return Vec::new();
+        };
+
+        let mut logs: Vec<StreamLog> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ndjson"))
+            .map(|path| StreamLog { path })
+            .collect();
+        logs.sort_by(|a, b| b.path.cmp(&a.path));
+        logs
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, message: &StreamMessage) -> Result<(), StorageError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let line = serde_json::to_string(message)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+
+    pub fn load(&self) -> Result<Vec<StreamMessage>, StorageError> {
+        Ok(parse_ndjson(&fs::read_to_string(&self.path)?))
+    }
+
+    /// Copies the log to `destination` for analysis with other tools
+    pub fn export(&self, destination: &Path) -> Result<(), StorageError> {
+        fs::copy(&self.path, destination)?;
+        Ok(())
+    }
+
+    pub fn delete(&self) -> Result<(), StorageError> {
+        fs::remove_file(&self.path)?;
+        Ok(())
+    }
+}
+
+fn streams_dir(base_path: &Path, request_id: &str) -> PathBuf {
+    base_path.join("streams").join(request_id)
+}
+
+pub fn to_ndjson(messages: &[StreamMessage]) -> String {
+    messages
+        .iter()
+        .filter_map(|message| serde_json::to_string(message).ok())
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Reads NDJSON messages, skipping lines that don't parse such as one cut short by a crash
+pub fn parse_ndjson(content: &str) -> Vec<StreamMessage> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_messages_through_ndjson() {
+        let messages = vec![
+            StreamMessage {
+                timestamp: "2024-01-31T12:00:00.000Z".to_string(),
+                direction: StreamDirection::Sent,
+                data: "{\"type\":\"subscribe\"}".to_string(),
+            },
+            StreamMessage {
+                timestamp: "2024-01-31T12:00:00.250Z".to_string(),
+                direction: StreamDirection::Received,
+                data: "line one\nline two".to_string(),
+            },
+        ];
+
+        let ndjson = to_ndjson(&messages);
+
+        assert_eq!(ndjson.lines().count(), 2);
+        assert!(
+            ndjson.starts_with(
+                r#"{"timestamp":"2024-01-31T12:00:00.000Z","direction":"sent","data":"#
+            )
+        );
+        assert_eq!(parse_ndjson(&(ndjson + "{\"timestamp\":\"2024")), messages);
+    }
+}
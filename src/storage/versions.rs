@@ -0,0 +1,241 @@
+use super::StorageError;
+use crate::signing::hex;
+use crate::types::RequestConfig;
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Versions kept for each request, older ones are pruned
+pub const MAX_VERSIONS: usize = 20;
+
+/// Fields that change as a request is used rather than edited, left out of its versions
+const UNVERSIONED_FIELDS: [&str; 6] = [
+    "path",
+    // Positions written by versions that didn't identify requests by id
+    "collection_index",
+    "request_index",
+    "cache_validators",
+    "run_history",
+    "last_response",
+];
+
+/// A saved state of a request, its content stored under `hash`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestVersion {
+    pub timestamp: String, // RFC 3339
+    pub hash: String,      // SHA-256 of the content
+}
+
+/// A field whose value differs between two versions, rendered as TOML. `None` when the
+/// field isn't set in that version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// A version with what changed since the one before it, nothing for the first one
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionChanges {
+    pub version: RequestVersion,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Content-addressed history of the requests saved. Each distinct content is stored once
+/// in `versions/objects/<hash>.toml` and every request lists its versions in
+/// `versions/<request id>.json`, next to the collections.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionStore {
+    path: PathBuf,
+}
+
+impl VersionStore {
+    pub fn new(base_path: &Path) -> Self {
+        Self {
+            path: base_path.join("versions"),
+        }
+    }
+
+    /// Adds the request as its latest version, unless its content didn't change
+    pub fn record(&self, request: &RequestConfig) -> Result<(), StorageError> {
+        if request.id.is_empty() {
+            return Ok(());
+        }
+
+        let content = toml::to_string_pretty(&snapshot(request)?)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        let hash = hex(&Sha256::digest(content.as_bytes()));
+
+        let mut versions = self.index(&request.id)?;
+        if versions.last().is_some_and(|latest| latest.hash == hash) {
+            return Ok(());
+        }
+
+        let object = self.object_path(&hash);
+        if !object.exists() {
+            if let Some(parent) = object.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&object, content)?;
+        }
+
+        versions.push(RequestVersion {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            hash,
+        });
+        let dropped: Vec<RequestVersion> = versions
+            .drain(..versions.len().saturating_sub(MAX_VERSIONS))
+            .collect();
+        self.write_index(&request.id, &versions)?;
+
+        // Objects hold the id of their request, so one dropped from its index is unused
+        for version in dropped {
+            if !versions.iter().any(|kept| kept.hash == version.hash) {
+                let _ = fs::remove_file(self.object_path(&version.hash));
+            }
+        }
+        Ok(())
+    }
+
+    /// The versions of a request, the latest first, each with its changes since the previous
+    pub fn history(&self, request_id: &str) -> Result<Vec<VersionChanges>, StorageError> {
+        let mut history = Vec::new();
+        let mut previous: Option<toml::Table> = None;
+        for version in self.index(request_id)? {
+            // A missing object still lists the version, it just can't be compared
+            let content = self.load(&version.hash).ok();
+            let changes = match (&previous, &content) {
+                (Some(before), Some(after)) => field_changes(before, after),
+                _ => Vec::new(),
+            };
+            history.push(VersionChanges { version, changes });
+            previous = content;
+        }
+        history.reverse();
+        Ok(history)
+    }
+
+    /// The request with the fields of one of its versions, keeping its file, runs and last
+    /// response
+    pub fn restore(
+        &self,
+        request: &RequestConfig,
+        hash: &str,
+    ) -> Result<RequestConfig, StorageError> {
+        let version = self.load(hash)?;
+        let mut table = toml::Table::try_from(request)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        table.retain(|field, _| UNVERSIONED_FIELDS.contains(&field));
+        table.extend(version);
+
+        table
+            .try_into()
+            .map_err(|e| StorageError::InvalidFormat(e.to_string()))
+    }
+
+    fn index_path(&self, request_id: &str) -> PathBuf {
+        self.path.join(format!("{}.json", request_id))
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.path.join("objects").join(format!("{}.toml", hash))
+    }
+
+    fn index(&self, request_id: &str) -> Result<Vec<RequestVersion>, StorageError> {
+        match fs::read_to_string(self.index_path(request_id)) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| StorageError::InvalidFormat(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn write_index(
+        &self,
+        request_id: &str,
+        versions: &[RequestVersion],
+    ) -> Result<(), StorageError> {
+        fs::create_dir_all(&self.path)?;
+        let content = serde_json::to_string_pretty(versions)
+            .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+        fs::write(self.index_path(request_id), content)?;
+        Ok(())
+    }
+
+    fn load(&self, hash: &str) -> Result<toml::Table, StorageError> {
+        let content = fs::read_to_string(self.object_path(hash))?;
+        toml::from_str(&content).map_err(|e| StorageError::InvalidFormat(e.to_string()))
+    }
+}
+
+/// The fields of a request that make up a version
+fn snapshot(request: &RequestConfig) -> Result<toml::Table, StorageError> {
+    let mut table = toml::Table::try_from(request)
+        .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+    table.retain(|field, _| !UNVERSIONED_FIELDS.contains(&field));
+    Ok(table)
+}
+
+/// The top-level fields that differ between two versions, by name
+pub fn field_changes(before: &toml::Table, after: &toml::Table) -> Vec<FieldChange> {
+    let fields: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    fields
+        .into_iter()
+        .filter(|field| before.get(*field) != after.get(*field))
+        .map(|field| FieldChange {
+            field: field.clone(),
+            before: before.get(field).map(render),
+            after: after.get(field).map(render),
+        })
+        .collect()
+}
+
+fn render(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_the_fields_that_changed() {
+        let before: toml::Table =
+            toml::from_str("name = \"List users\"\nurl = \"/users\"\nheaders = []\n").unwrap();
+        let after: toml::Table = toml::from_str(
+            "name = \"List users\"\nurl = \"/users?page=2\"\nheaders = [[\"Accept\", \"*/*\"]]\nbody = \"\"\n",
+        )
+        .unwrap();
+
+        let changes = field_changes(&before, &after);
+
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    field: "body".to_string(),
+                    before: None,
+                    after: Some(String::new()),
+                },
+                FieldChange {
+                    field: "headers".to_string(),
+                    before: Some("[]".to_string()),
+                    after: Some("[[\"Accept\", \"*/*\"]]".to_string()),
+                },
+                FieldChange {
+                    field: "url".to_string(),
+                    before: Some("/users".to_string()),
+                    after: Some("/users?page=2".to_string()),
+                },
+            ]
+        );
+        assert!(field_changes(&after, &after).is_empty());
+    }
+}
@@ -0,0 +1,497 @@
+use crate::signing::{hex, hmac};
+use crate::storage::file_storage::write_atomic;
+use crate::types::HmacAlgorithm;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Settings file for the remote sync backend, stored next to the collections
+const CONFIG_FILE: &str = "sync.toml";
+/// Hashes of the files as they were after the last successful sync
+const STATE_FILE: &str = "sync_state.toml";
+/// Remote copy of the file hashes, used to see what changed remotely
+const MANIFEST_FILE: &str = "manifest.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RemoteKind {
+    #[default]
+    WebDav,
+    S3,
+}
+
+impl RemoteKind {
+    pub const ALL: [RemoteKind; 2] = [RemoteKind::WebDav, RemoteKind::S3];
+}
+
+impl std::fmt::Display for RemoteKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemoteKind::WebDav => write!(f, "WebDAV"),
+            RemoteKind::S3 => write!(f, "S3"),
+        }
+    }
+}
+
+/// Where the workspace is synced to. WebDAV uses `username`/`password` with basic
+/// auth; S3-compatible stores use `bucket`, `region` and the access key pair.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteSyncConfig {
+    pub kind: RemoteKind,
+    pub endpoint: String,
+    pub prefix: String,
+    pub username: String,
+    pub password: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+impl RemoteSyncConfig {
+    pub fn is_configured(&self) -> bool {
+        match self.kind {
+            RemoteKind::WebDav => !self.endpoint.trim().is_empty(),
+            RemoteKind::S3 => {
+                !self.endpoint.trim().is_empty()
+                    && !self.bucket.trim().is_empty()
+                    && !self.access_key.is_empty()
+                    && !self.secret_key.is_empty()
+            }
+        }
+    }
+}
+
+/// How to settle a file that changed both locally and remotely
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    KeepLocal,
+    KeepRemote,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    pub pushed: usize,
+    pub pulled: usize,
+    /// Workspace-relative paths changed on both sides, left untouched
+    pub conflicts: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    files: BTreeMap<String, String>,
+}
+
+pub fn load_config(base_path: &Path) -> RemoteSyncConfig {
+    fs::read_to_string(base_path.join(CONFIG_FILE))
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Writes the settings, readable only by the user as they hold the password or secret key
+pub fn save_config(base_path: &Path, config: &RemoteSyncConfig) -> Result<(), String> {
+    let content = toml::to_string_pretty(config).map_err(|e| e.to_string())?;
+    let path = base_path.join(CONFIG_FILE);
+    write_atomic(&path, content.as_bytes()).map_err(|e| e.to_string())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Pushes local changes and pulls remote ones. A file changed on both sides since the
+/// last sync is reported as a conflict unless `resolutions` says which side wins.
+pub async fn sync(
+    base_path: PathBuf,
+    config: RemoteSyncConfig,
+    resolutions: BTreeMap<String, Resolution>,
+) -> Result<SyncReport, String> {
+    let remote = Remote::new(config)?;
+
+    let base = read_manifest(&fs::read_to_string(base_path.join(STATE_FILE)).unwrap_or_default());
+    let remote_manifest = match remote.get(MANIFEST_FILE).await? {
+        Some(content) => read_manifest(&String::from_utf8_lossy(&content)),
+        None => Manifest::default(),
+    };
+    // Paths are joined to `base_path` to pull or delete files, so none may lead out of it
+    check_manifest(&base)?;
+    check_manifest(&remote_manifest)?;
+    let local = scan_workspace(&base_path)?;
+
+    let mut report = SyncReport::default();
+    let mut synced = remote_manifest.files.clone();
+
+    let paths: BTreeSet<&String> = local
+        .keys()
+        .chain(remote_manifest.files.keys())
+        .chain(base.files.keys())
+        .collect();
+
+    for path in paths {
+        let local_hash = local.get(path).map(|(hash, _)| hash);
+        let remote_hash = remote_manifest.files.get(path);
+        let base_hash = base.files.get(path);
+
+        if local_hash == remote_hash {
+            continue;
+        }
+
+        let local_changed = local_hash != base_hash;
+        let remote_changed = remote_hash != base_hash;
+
+        let push = match (local_changed, remote_changed) {
+            (true, false) => true,
+            (false, true) => false,
+            _ => match resolutions.get(path) {
+                Some(Resolution::KeepLocal) => true,
+                Some(Resolution::KeepRemote) => false,
+                None => {
+                    report.conflicts.push(path.clone());
+                    continue;
+                }
+            },
+        };
+
+        if push {
+            match local.get(path) {
+                Some((hash, content)) => {
+                    remote.put(path, content.clone()).await?;
+                    synced.insert(path.clone(), hash.clone());
+                }
+                None => {
+                    remote.delete(path).await?;
+                    synced.remove(path);
+                }
+            }
+            report.pushed += 1;
+        } else {
+            let local_path = base_path.join(path);
+            match remote.get(path).await? {
+                Some(content) => {
+                    if let Some(parent) = local_path.parent() {
+                        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                    }
+                    write_atomic(&local_path, &content).map_err(|e| e.to_string())?;
+                }
+                None => {
+                    let _ = fs::remove_file(&local_path);
+                }
+            }
+            report.pulled += 1;
+        }
+    }
+
+    let manifest = Manifest { files: synced };
+    let manifest_content = toml::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    if report.pushed > 0 {
+        remote
+            .put(MANIFEST_FILE, manifest_content.clone().into_bytes())
+            .await?;
+    }
+
+    // Conflicting files keep their old base so they are detected again next time
+    let mut state = manifest;
+    for path in &report.conflicts {
+        match base.files.get(path) {
+            Some(hash) => state.files.insert(path.clone(), hash.clone()),
+            None => state.files.remove(path),
+        };
+    }
+    let state_content = toml::to_string_pretty(&state).map_err(|e| e.to_string())?;
+    write_atomic(&base_path.join(STATE_FILE), state_content.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    Ok(report)
+}
+
+fn read_manifest(content: &str) -> Manifest {
+    toml::from_str(content).unwrap_or_default()
+}
+
+/// Refuses a manifest listing a file that isn't synced: only `environments.toml` and the
+/// files under `collections/` are, by a relative path without `..`
+fn check_manifest(manifest: &Manifest) -> Result<(), String> {
+    match manifest.files.keys().find(|path| !is_synced_path(path)) {
+        Some(path) => Err(format!(
+            "The sync manifest lists {}, which is outside the workspace",
+            path
+        )),
+        None => Ok(()),
+    }
+}
+
+fn is_synced_path(path: &str) -> bool {
+    let relative = Path::new(path)
+        .components()
+        .all(|component| matches!(component, std::path::Component::Normal(_)));
+    relative && (path == "environments.toml" || path.starts_with("collections/"))
+}
+
+fn content_hash(content: &[u8]) -> String {
+    hex(&Sha256::digest(content))
+}
+
+/// Collections and environments, keyed by their `/`-separated path relative to
+/// `base_path`, with their hash and content
+fn scan_workspace(base_path: &Path) -> Result<BTreeMap<String, (String, Vec<u8>)>, String> {
+    let mut files = BTreeMap::new();
+
+    let environments = base_path.join("environments.toml");
+    if let Ok(content) = fs::read(&environments) {
+        files.insert(
+            "environments.toml".to_string(),
+            (content_hash(&content), content),
+        );
+    }
+
+    let mut pending = vec![base_path.join("collections")];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "toml") {
+                let content = fs::read(&path).map_err(|e| e.to_string())?;
+                let relative = path
+                    .strip_prefix(base_path)
+                    .map_err(|e| e.to_string())?
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.insert(relative, (content_hash(&content), content));
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+struct Remote {
+    config: RemoteSyncConfig,
+    client: reqwest::Client,
+}
+
+impl Remote {
+    fn new(config: RemoteSyncConfig) -> Result<Self, String> {
+        if !config.is_configured() {
+            return Err("Remote sync is not configured".to_string());
+        }
+
+        Ok(Self {
+            config,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    fn key(&self, path: &str) -> String {
+        let prefix = self.config.prefix.trim_matches('/');
+        if prefix.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}/{}", prefix, path)
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        let endpoint = self.config.endpoint.trim().trim_end_matches('/');
+        let key = self
+            .key(path)
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        match self.config.kind {
+            RemoteKind::WebDav => format!("{}/{}", endpoint, key),
+            RemoteKind::S3 => format!("{}/{}/{}", endpoint, self.config.bucket.trim(), key),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, url: &str, body: &[u8]) -> reqwest::RequestBuilder {
+        let builder = self.client.request(method.clone(), url);
+
+        match self.config.kind {
+            RemoteKind::WebDav if !self.config.username.is_empty() => {
+                builder.basic_auth(&self.config.username, Some(&self.config.password))
+            }
+            RemoteKind::WebDav => builder,
+            RemoteKind::S3 => s3_sign(&self.config, builder, &method, url, body),
+        }
+    }
+
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response, String> {
+        self.request(method, &self.url(path), &body)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Sync request failed: {}", e))
+    }
+
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, String> {
+        let response = self.send(reqwest::Method::GET, path, vec![]).await?;
+
+        match response.status() {
+            status if status.is_success() => response
+                .bytes()
+                .await
+                .map(|bytes| Some(bytes.to_vec()))
+                .map_err(|e| e.to_string()),
+            reqwest::StatusCode::NOT_FOUND => Ok(None),
+            status => Err(format!("Failed to download {}: HTTP {}", path, status)),
+        }
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<(), String> {
+        if self.config.kind == RemoteKind::WebDav {
+            self.create_parent_collections(path).await;
+        }
+
+        let response = self.send(reqwest::Method::PUT, path, content).await?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Failed to upload {}: HTTP {}",
+                path,
+                response.status()
+            ))
+        }
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), String> {
+        let response = self.send(reqwest::Method::DELETE, path, vec![]).await?;
+        let status = response.status();
+        if status.is_success() || status == reqwest::StatusCode::NOT_FOUND {
+            Ok(())
+        } else {
+            Err(format!("Failed to delete {}: HTTP {}", path, status))
+        }
+    }
+
+    /// WebDAV servers refuse uploads into missing directories; existing ones answer
+    /// MKCOL with an error, which is ignored
+    async fn create_parent_collections(&self, path: &str) {
+        let Ok(mkcol) = reqwest::Method::from_bytes(b"MKCOL") else {
+            return;
+        };
+        let key = self.key(path);
+        let segments: Vec<&str> = key.split('/').collect();
+
+        for depth in 1..segments.len() {
+            let url = format!(
+                "{}/{}/",
+                self.config.endpoint.trim().trim_end_matches('/'),
+                segments[..depth]
+                    .iter()
+                    .map(|segment| urlencoding::encode(segment).into_owned())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            );
+            let _ = self.request(mkcol.clone(), &url, &[]).send().await;
+        }
+    }
+}
+
+/// Signs an S3 request with AWS Signature Version 4
+fn s3_sign(
+    config: &RemoteSyncConfig,
+    builder: reqwest::RequestBuilder,
+    method: &reqwest::Method,
+    url: &str,
+    body: &[u8],
+) -> reqwest::RequestBuilder {
+    let Ok(parsed) = reqwest::Url::parse(url) else {
+        return builder;
+    };
+
+    let host = match (parsed.host_str(), parsed.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        _ => return builder,
+    };
+    let region = match config.region.trim() {
+        "" => "us-east-1",
+        region => region,
+    };
+
+    let now = chrono::Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let payload_hash = content_hash(body);
+
+    let canonical_request = format!(
+        "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+        method,
+        parsed.path(),
+        host,
+        payload_hash,
+        amz_date,
+        payload_hash
+    );
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        content_hash(canonical_request.as_bytes())
+    );
+
+    let signing_key = [region, "s3", "aws4_request"].iter().fold(
+        hmac(
+            HmacAlgorithm::Sha256,
+            format!("AWS4{}", config.secret_key).as_bytes(),
+            date.as_bytes(),
+        ),
+        |key, part| hmac(HmacAlgorithm::Sha256, &key, part.as_bytes()),
+    );
+    let signature = hex(&hmac(
+        HmacAlgorithm::Sha256,
+        &signing_key,
+        string_to_sign.as_bytes(),
+    ));
+
+    builder
+        .header("x-amz-date", amz_date)
+        .header("x-amz-content-sha256", payload_hash)
+        .header(
+            "Authorization",
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+                config.access_key, scope, signature
+            ),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_manifest_paths_outside_the_workspace() {
+        let manifest = |path: &str| Manifest {
+            files: BTreeMap::from([(path.to_string(), "hash".to_string())]),
+        };
+
+        assert!(check_manifest(&manifest("environments.toml")).is_ok());
+        assert!(check_manifest(&manifest("collections/users/list.toml")).is_ok());
+        for path in ["../x", "/etc/x", "collections/../../x", "sync.toml"] {
+            assert!(check_manifest(&manifest(path)).is_err(), "{}", path);
+        }
+    }
+}
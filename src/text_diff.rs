@@ -0,0 +1,109 @@
+/// Texts whose changed parts have more lines than this, multiplied, aren't compared
+const MAX_COMPARED: usize = 4_000_000;
+
+/// A line of a diff between two texts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+impl DiffLine {
+    pub fn is_change(&self) -> bool {
+        !matches!(self, DiffLine::Same(_))
+    }
+}
+
+/// The lines of `old` and `new` in order, marked as kept, removed or added. None when the
+/// texts are too large to compare.
+pub fn line_diff(old: &str, new: &str) -> Option<Vec<DiffLine>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Only the middle part that differs goes through the longest common subsequence
+    let prefix = old
+        .iter()
+        .zip(&new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+    if old_middle.len() * new_middle.len() > MAX_COMPARED {
+        return None;
+    }
+
+    // common[i][j]: length of the longest common subsequence of old_middle[i..] and new_middle[j..]
+    let width = new_middle.len() + 1;
+    let mut common = vec![0u32; (old_middle.len() + 1) * width];
+    for i in (0..old_middle.len()).rev() {
+        for j in (0..new_middle.len()).rev() {
+            common[i * width + j] = if old_middle[i] == new_middle[j] {
+                common[(i + 1) * width + j + 1] + 1
+            } else {
+                common[(i + 1) * width + j].max(common[i * width + j + 1])
+            };
+        }
+    }
+
+    let same = |line: &&str| DiffLine::Same(line.to_string());
+    let mut lines: Vec<DiffLine> = old[..prefix].iter().map(same).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < old_middle.len() || j < new_middle.len() {
+        if i < old_middle.len() && j < new_middle.len() && old_middle[i] == new_middle[j] {
+            lines.push(DiffLine::Same(old_middle[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if j < new_middle.len()
+            && (i == old_middle.len() || common[i * width + j + 1] > common[(i + 1) * width + j])
+        {
+            lines.push(DiffLine::Added(new_middle[j].to_string()));
+            j += 1;
+        } else {
+            lines.push(DiffLine::Removed(old_middle[i].to_string()));
+            i += 1;
+        }
+    }
+    lines.extend(old[old.len() - suffix..].iter().map(same));
+    Some(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_lines() {
+        let old = "{\n  \"name\": \"Ada\",\n  \"role\": \"admin\"\n}";
+        let new = "{\n  \"name\": \"Grace\",\n  \"role\": \"admin\",\n  \"active\": true\n}";
+        let diff = line_diff(old, new).unwrap();
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("{".to_string()),
+                DiffLine::Removed("  \"name\": \"Ada\",".to_string()),
+                DiffLine::Removed("  \"role\": \"admin\"".to_string()),
+                DiffLine::Added("  \"name\": \"Grace\",".to_string()),
+                DiffLine::Added("  \"role\": \"admin\",".to_string()),
+                DiffLine::Added("  \"active\": true".to_string()),
+                DiffLine::Same("}".to_string()),
+            ]
+        );
+        assert!(
+            line_diff(old, old)
+                .unwrap()
+                .iter()
+                .all(|line| !line.is_change())
+        );
+        assert_eq!(
+            line_diff("", "a"),
+            Some(vec![DiffLine::Added("a".to_string())])
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use iced::theme::Palette;
+use iced::{Color, Theme};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// A color scheme for the app, built in or read from a theme file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomTheme {
+    pub name: String,
+    pub palette: Palette,
+    /// The file it was read from, `None` for built-in themes
+    pub path: Option<PathBuf>,
+}
+
+impl CustomTheme {
+    pub fn to_theme(&self) -> Theme {
+        Theme::custom(self.name.clone(), self.palette)
+    }
+}
+
+impl std::fmt::Display for CustomTheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// The themes available and the files that couldn't be read
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Themes {
+    pub themes: Vec<CustomTheme>,
+    pub errors: Vec<String>,
+}
+
+impl Themes {
+    pub fn find(&self, name: &str) -> Option<&CustomTheme> {
+        self.themes.iter().find(|theme| theme.name == name)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    name: Option<String>,
+    colors: ThemeColors,
+}
+
+/// Semantic colors as hex strings, e.g. `#1e1e2e`. The status colors fall back to the ones
+/// of the light theme.
+#[derive(Debug, Deserialize)]
+struct ThemeColors {
+    background: String,
+    text: String,
+    primary: String,
+    success: Option<String>,
+    danger: Option<String>,
+    warning: Option<String>,
+}
+
+/// Where theme files are looked up: `~/.beam/themes/`
+pub fn themes_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".beam")
+        .join("themes")
+}
+
+/// Catppuccin Mocha, the dark theme that ships with the app
+pub fn built_in() -> CustomTheme {
+    CustomTheme {
+        name: "Catppuccin Mocha".to_string(),
+        palette: Palette {
+            background: Color::from_rgb(0.118, 0.118, 0.180), // #1e1e2e
+            text: Color::from_rgb(0.804, 0.839, 0.957),       // #cdd6f4
+            primary: Color::from_rgb(0.537, 0.706, 0.980),    // #89b4fa (blue)
+            success: Color::from_rgb(0.651, 0.890, 0.631),    // #a6e3a1 (green)
+            danger: Color::from_rgb(0.953, 0.545, 0.659),     // #f38ba8 (red)
+            warning: Color::from_rgb(0.980, 0.706, 0.529),    // #fab387 (peach)
+        },
+        path: None,
+    }
+}
+
+/// Reads a TOML theme file, named after `file_stem` when it doesn't set a name
+pub fn parse(content: &str, file_stem: &str) -> Result<CustomTheme, String> {
+    let file: ThemeFile = toml::from_str(content).map_err(|e| e.message().to_string())?;
+    let color = |field: &str, value: &str| {
+        parse_hex(value).ok_or_else(|| format!("{} is not a hex color: {}", field, value))
+    };
+    let status = |field: &str, value: &Option<String>, fallback: Color| match value {
+        Some(value) => color(field, value),
+        None => Ok(fallback),
+    };
+    let colors = &file.colors;
+
+    Ok(CustomTheme {
+        name: file
+            .name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| file_stem.to_string()),
+        palette: Palette {
+            background: color("background", &colors.background)?,
+            text: color("text", &colors.text)?,
+            primary: color("primary", &colors.primary)?,
+            success: status("success", &colors.success, Palette::LIGHT.success)?,
+            danger: status("danger", &colors.danger, Palette::LIGHT.danger)?,
+            warning: status("warning", &colors.warning, Palette::LIGHT.warning)?,
+        },
+        path: None,
+    })
+}
+
+/// `#rgb`, `#rrggbb` or `#rrggbbaa`, the `#` being optional
+fn parse_hex(value: &str) -> Option<Color> {
+    let hex = value.trim().trim_start_matches('#');
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize, width: usize| {
+        let digits = &hex[i * width..(i + 1) * width];
+        let value = u8::from_str_radix(digits, 16).ok()?;
+        Some(if width == 1 { value * 17 } else { value })
+    };
+
+    match hex.len() {
+        3 => Some(Color::from_rgb8(
+            channel(0, 1)?,
+            channel(1, 1)?,
+            channel(2, 1)?,
+        )),
+        6 => Some(Color::from_rgb8(
+            channel(0, 2)?,
+            channel(1, 2)?,
+            channel(2, 2)?,
+        )),
+        8 => Some(Color::from_rgba8(
+            channel(0, 2)?,
+            channel(1, 2)?,
+            channel(2, 2)?,
+            channel(3, 2)? as f32 / 255.0,
+        )),
+        _ => None,
+    }
+}
+
+/// The built-in theme followed by the `.toml` files of `dir`, sorted by name. A theme named
+/// like an earlier one is skipped so names stay unique.
+pub fn load(dir: &Path) -> Themes {
+    let mut themes = Themes {
+        themes: vec![built_in()],
+        errors: Vec::new(),
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return themes;
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    let mut loaded = Vec::new();
+    for path in paths {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let file_stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let theme = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| parse(&content, &file_stem));
+
+        match theme {
+            Ok(theme)
+                if themes.find(&theme.name).is_some()
+                    || loaded
+                        .iter()
+                        .any(|existing: &CustomTheme| existing.name == theme.name) =>
+            {
+                themes.errors.push(format!(
+                    "{}: a theme named \"{}\" already exists",
+                    file_name, theme.name
+                ))
+            }
+            Ok(theme) => loaded.push(CustomTheme {
+                path: Some(path),
+                ..theme
+            }),
+            Err(e) => themes.errors.push(format!("{}: {}", file_name, e)),
+        }
+    }
+
+    loaded.sort_by_key(|theme| theme.name.to_lowercase());
+    themes.themes.extend(loaded);
+    themes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_theme_files() {
+        let theme = parse(
+            r##"
+            [colors]
+            background = "#fdf6e3"
+            text = "#657B83"
+            primary = "268bd2"
+            danger = "#f00"
+            "##,
+            "solarized-light",
+        )
+        .unwrap();
+
+        assert_eq!(theme.name, "solarized-light");
+        assert_eq!(theme.palette.background, Color::from_rgb8(0xfd, 0xf6, 0xe3));
+        assert_eq!(theme.palette.text, Color::from_rgb8(0x65, 0x7b, 0x83));
+        assert_eq!(theme.palette.primary, Color::from_rgb8(0x26, 0x8b, 0xd2));
+        assert_eq!(theme.palette.danger, Color::from_rgb8(0xff, 0, 0));
+        assert_eq!(theme.palette.success, Palette::LIGHT.success);
+    }
+
+    #[test]
+    fn rejects_invalid_colors() {
+        let error = parse(
+            "name = \"Broken\"\n[colors]\nbackground = \"#12345\"\ntext = \"#fff\"\nprimary = \"#000\"",
+            "broken",
+        )
+        .unwrap_err();
+        assert_eq!(error, "background is not a hex color: #12345");
+
+        assert!(parse("[colors]\nbackground = \"#fff\"", "incomplete").is_err());
+    }
+}
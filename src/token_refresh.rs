@@ -0,0 +1,85 @@
+use crate::tools::decode_jwt;
+use serde_json::Value;
+
+/// A JWT expiring within this many seconds is renewed already, so it can't expire in flight
+const EXPIRY_LEEWAY: i64 = 30;
+
+/// Where OAuth 2 token endpoints put the token
+const DEFAULT_TOKEN_PATH: &str = "access_token";
+
+/// Whether `token` has to be renewed before sending. JWTs are checked against their `exp`
+/// claim, other tokens against `ttl_seconds` from `fetched_at`, the Unix time they were
+/// stored. A token fetched before Beam started has no known age and counts as expired.
+pub fn needs_refresh(token: &str, fetched_at: Option<i64>, ttl_seconds: u64, now: i64) -> bool {
+    let token = token.trim();
+    if token.is_empty() {
+        return true;
+    }
+
+    if let Ok(jwt) = decode_jwt(token)
+        && let Some(expires_at) = jwt.expires_at
+    {
+        return expires_at - EXPIRY_LEEWAY <= now;
+    }
+
+    ttl_seconds > 0 && fetched_at.is_none_or(|fetched_at| now - fetched_at >= ttl_seconds as i64)
+}
+
+/// Reads the token at a dotted `path` such as `data.token` or `$.tokens.0.value` in a JSON
+/// body, `access_token` when the path is empty
+pub fn extract_token(body: &str, path: &str) -> Result<String, String> {
+    let json: Value =
+        serde_json::from_str(body).map_err(|e| format!("The token response is not JSON: {}", e))?;
+
+    let path = path.trim().trim_start_matches('$').trim_start_matches('.');
+    let path = if path.is_empty() {
+        DEFAULT_TOKEN_PATH
+    } else {
+        path
+    };
+
+    let mut value = &json;
+    for key in path.split('.') {
+        value = match value {
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+            _ => value.get(key),
+        }
+        .ok_or_else(|| format!("The token response has no '{}'", path))?;
+    }
+
+    match value {
+        Value::String(token) if !token.is_empty() => Ok(token.clone()),
+        Value::String(_) | Value::Null => Err(format!("'{}' is empty in the token response", path)),
+        other => Ok(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_jwt_expiry_then_ttl() {
+        // {"alg":"none"}.{"exp":1000}
+        let jwt = "eyJhbGciOiJub25lIn0.eyJleHAiOjEwMDB9.";
+
+        assert!(needs_refresh("", None, 0, 0));
+        assert!(!needs_refresh(jwt, None, 0, 900));
+        assert!(needs_refresh(jwt, None, 0, 980));
+        assert!(!needs_refresh("opaque", None, 0, 5000));
+        assert!(needs_refresh("opaque", None, 60, 5000));
+        assert!(!needs_refresh("opaque", Some(4950), 60, 5000));
+        assert!(needs_refresh("opaque", Some(4940), 60, 5000));
+    }
+
+    #[test]
+    fn extracts_token_by_path() {
+        let body = r#"{"access_token":"abc","data":{"tokens":[{"value":"def"}]},"empty":""}"#;
+
+        assert_eq!(extract_token(body, "").unwrap(), "abc");
+        assert_eq!(extract_token(body, "$.data.tokens.0.value").unwrap(), "def");
+        assert!(extract_token(body, "data.missing").is_err());
+        assert!(extract_token(body, "empty").is_err());
+        assert!(extract_token("<html>", "").is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use base64::Engine;
+use base64::engine::general_purpose;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::Value;
+
+/// Epoch values from this one on are taken as milliseconds, it's year 33658 in seconds
+const MILLISECONDS_THRESHOLD: i64 = 1_000_000_000_000;
+
+pub fn base64_encode(input: &str) -> String {
+    general_purpose::STANDARD.encode(input)
+}
+
+/// Decodes standard or URL-safe base64, with or without padding
+pub fn base64_decode(input: &str) -> Result<String, String> {
+    let bytes = base64_decode_bytes(input)?;
+    String::from_utf8(bytes).map_err(|e| {
+        format!(
+            "Decoded {} bytes that aren't UTF-8 text",
+            e.as_bytes().len()
+        )
+    })
+}
+
+fn base64_decode_bytes(input: &str) -> Result<Vec<u8>, String> {
+    let cleaned: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '-' => '+',
+            '_' => '/',
+            c => c,
+        })
+        .collect();
+    general_purpose::STANDARD_NO_PAD
+        .decode(cleaned.trim_end_matches('='))
+        .map_err(|e| format!("Invalid base64: {}", e))
+}
+
+pub fn url_encode(input: &str) -> String {
+    urlencoding::encode(input).into_owned()
+}
+
+/// Decodes percent escapes, `+` being a space as in form values
+pub fn url_decode(input: &str) -> Result<String, String> {
+    urlencoding::decode(&input.replace('+', " "))
+        .map(|decoded| decoded.into_owned())
+        .map_err(|_| "The escapes don't decode to UTF-8 text".to_string())
+}
+
+/// Header and claims of a JWT, the signature isn't verified
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedJwt {
+    pub header: String,
+    pub payload: String,
+    pub issued_at: Option<i64>,
+    pub expires_at: Option<i64>,
+}
+
+impl DecodedJwt {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now().timestamp())
+    }
+}
+
+/// Decodes a JWT, with or without its `Bearer ` prefix, pretty-printing the header and claims
+pub fn decode_jwt(token: &str) -> Result<DecodedJwt, String> {
+    let token = token.trim();
+    let token = token
+        .strip_prefix("Bearer ")
+        .or_else(|| token.strip_prefix("bearer "))
+        .unwrap_or(token)
+        .trim();
+
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(format!(
+            "A JWT has 3 parts separated by dots, found {}",
+            parts.len()
+        ));
+    }
+
+    let decode_part = |part: &str, name: &str| -> Result<Value, String> {
+        let bytes = base64_decode_bytes(part).map_err(|e| format!("{}: {}", name, e))?;
+        serde_json::from_slice(&bytes).map_err(|e| format!("{} is not JSON: {}", name, e))
+    };
+    let header = decode_part(parts[0], "Header")?;
+    let payload = decode_part(parts[1], "Payload")?;
+
+    Ok(DecodedJwt {
+        header: serde_json::to_string_pretty(&header).unwrap_or_default(),
+        payload: serde_json::to_string_pretty(&payload).unwrap_or_default(),
+        issued_at: payload.get("iat").and_then(Value::as_i64),
+        expires_at: payload.get("exp").and_then(Value::as_i64),
+    })
+}
+
+/// First decodable JWT in `text`, such as an `Authorization` header value or an
+/// `access_token` in a JSON body
+pub fn find_jwt(text: &str) -> Option<&str> {
+    let is_token_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.');
+
+    let mut rest = text;
+    while let Some(start) = rest.find("eyJ") {
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| !is_token_char(c))
+            .unwrap_or(candidate.len());
+        let token = &candidate[..end];
+        if decode_jwt(token).is_ok() {
+            return Some(token);
+        }
+        rest = &candidate[end..];
+    }
+
+    None
+}
+
+/// Converts seconds or milliseconds since the epoch to an RFC 3339 UTC time
+pub fn epoch_to_iso(input: &str) -> Result<String, String> {
+    let value: i64 = input
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{}' is not a whole number", input.trim()))?;
+
+    let time = if value.abs() >= MILLISECONDS_THRESHOLD {
+        DateTime::<Utc>::from_timestamp_millis(value)
+    } else {
+        DateTime::<Utc>::from_timestamp(value, 0)
+    };
+
+    time.map(|time| time.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        .ok_or_else(|| "Out of the supported time range".to_string())
+}
+
+/// Converts an RFC 3339 time to seconds and milliseconds since the epoch
+pub fn iso_to_epoch(input: &str) -> Result<String, String> {
+    let time = DateTime::parse_from_rfc3339(input.trim())
+        .map_err(|e| format!("Not an RFC 3339 time such as 2024-01-31T12:00:00Z: {}", e))?;
+
+    Ok(format!(
+        "{} s\n{} ms",
+        time.timestamp(),
+        time.timestamp_millis()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_and_url_round_trip() {
+        assert_eq!(base64_encode("beam?>"), "YmVhbT8+");
+        assert_eq!(base64_decode("YmVhbT8-").unwrap(), "beam?>");
+        assert_eq!(base64_decode("YmVhbQ").unwrap(), "beam");
+        assert!(base64_decode("//8=").is_err());
+
+        assert_eq!(url_encode("a b&c=é"), "a%20b%26c%3D%C3%A9");
+        assert_eq!(url_decode("a%20b+c").unwrap(), "a b c");
+    }
+
+    #[test]
+    fn decodes_jwt_header_and_claims() {
+        let token = "Bearer eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiIxMjM0NTY3ODkwIiwiaWF0IjoxNTE2MjM5MDIyLCJleHAiOjE1MTYyNDI2MjJ9.sig";
+
+        let jwt = decode_jwt(token).unwrap();
+
+        assert!(jwt.header.contains("\"alg\": \"HS256\""));
+        assert!(jwt.payload.contains("\"sub\": \"1234567890\""));
+        assert_eq!(jwt.issued_at, Some(1516239022));
+        assert_eq!(jwt.expires_at, Some(1516242622));
+        assert!(jwt.is_expired());
+        assert!(decode_jwt("not.a-token").is_err());
+
+        let body = format!(r#"{{"id_token":"eyJhbGciOi.x.y","access_token":"{}"}}"#, &token[7..]);
+        assert_eq!(find_jwt(&body), Some(&token[7..]));
+        assert_eq!(find_jwt("eyJ nothing here"), None);
+    }
+
+    #[test]
+    fn converts_epoch_seconds_and_milliseconds() {
+        assert_eq!(epoch_to_iso("1700000000").unwrap(), "2023-11-14T22:13:20Z");
+        assert_eq!(
+            epoch_to_iso("1700000000123").unwrap(),
+            "2023-11-14T22:13:20.123Z"
+        );
+        assert_eq!(
+            iso_to_epoch("2023-11-14T23:13:20.5+01:00").unwrap(),
+            "1700000000 s\n1700000000500 ms"
+        );
+    }
+}
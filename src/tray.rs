@@ -0,0 +1,331 @@
+use crate::types::ResponseData;
+use std::process::Command;
+
+/// A pinned request as listed in the tray menu
+#[derive(Debug, Clone, PartialEq)]
+pub struct PinnedRequest {
+    pub id: String,
+    pub label: String, // e.g. "Users / List users"
+}
+
+/// What was picked in the tray menu
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrayEvent {
+    ShowWindow,
+    Send(String), // Request id
+}
+
+/// Whether this build has the tray icon, it's left out without the `tray` feature
+pub fn is_supported() -> bool {
+    cfg!(feature = "tray")
+}
+
+/// The icon in the system tray or macOS menu bar, with a menu to send the pinned requests
+/// without bringing up the window
+pub struct Tray {
+    #[cfg(feature = "tray")]
+    inner: platform::Tray,
+}
+
+impl Tray {
+    pub fn new(pinned: &[PinnedRequest]) -> Result<Self, String> {
+        #[cfg(feature = "tray")]
+        {
+            platform::Tray::new(pinned).map(|inner| Self { inner })
+        }
+        #[cfg(not(feature = "tray"))]
+        {
+            let _ = pinned;
+            Err("This build of Beam has no tray icon".to_string())
+        }
+    }
+
+    pub fn set_pinned(&mut self, pinned: &[PinnedRequest]) {
+        #[cfg(feature = "tray")]
+        self.inner.set_pinned(pinned);
+        #[cfg(not(feature = "tray"))]
+        let _ = pinned;
+    }
+
+    /// Shows the outcome of the last send in the tooltip of the icon
+    pub fn set_status(&mut self, status: &str) {
+        #[cfg(feature = "tray")]
+        self.inner.set_status(status);
+        #[cfg(not(feature = "tray"))]
+        let _ = status;
+    }
+
+    /// The entries picked since the last call
+    pub fn events(&self) -> Vec<TrayEvent> {
+        #[cfg(feature = "tray")]
+        {
+            platform::events()
+        }
+        #[cfg(not(feature = "tray"))]
+        {
+            Vec::new()
+        }
+    }
+}
+
+impl std::fmt::Debug for Tray {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tray").finish_non_exhaustive()
+    }
+}
+
+/// The outcome of a send in a line, e.g. `200 OK in 120 ms, 1 of 3 checks failed`
+pub fn summary(response: &ResponseData) -> String {
+    // Failed sends have no status, their label says what went wrong
+    if response.status == 0 {
+        return response.status_text.clone();
+    }
+
+    let mut summary = format!(
+        "{} {} in {} ms",
+        response.status, response.status_text, response.time
+    );
+    let failed = response
+        .test_results
+        .iter()
+        .filter(|result| !result.passed)
+        .count();
+    if failed > 0 {
+        summary.push_str(&format!(
+            ", {} of {} checks failed",
+            failed,
+            response.test_results.len()
+        ));
+    }
+    summary
+}
+
+/// Shows a desktop notification through the tools the OS comes with. Windows has none that
+/// works without setup, the tooltip of the tray icon shows the status there.
+pub fn notify(title: &str, body: &str) {
+    let command = if cfg!(target_os = "macos") {
+        let quote = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification \"{}\" with title \"{}\"",
+            quote(body),
+            quote(title)
+        ));
+        Some(command)
+    } else if cfg!(target_os = "linux") {
+        let mut command = Command::new("notify-send");
+        command.args(["--app-name=Beam", title, body]);
+        Some(command)
+    } else {
+        None
+    };
+
+    if let Some(mut command) = command
+        && let Err(e) = command.spawn()
+    {
+        log::warn!("Failed to show a notification: {}", e);
+    }
+}
+
+#[cfg(feature = "tray")]
+mod platform {
+    use super::{PinnedRequest, TrayEvent};
+    use crate::i18n::{tr, tr_args};
+    use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+    use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
+
+    const SHOW_WINDOW_ID: &str = "beam-show-window";
+    const SEND_ID_PREFIX: &str = "beam-send:";
+
+    /// Brand indigo, #4f46e5
+    const ICON_COLOR: [u8; 3] = [0x4f, 0x46, 0xe5];
+
+    fn menu(pinned: &[PinnedRequest]) -> Menu {
+        let menu = Menu::new();
+        let _ = menu.append(&MenuItem::with_id(
+            SHOW_WINDOW_ID,
+            tr("tray-show"),
+            true,
+            None,
+        ));
+        let _ = menu.append(&PredefinedMenuItem::separator());
+        if pinned.is_empty() {
+            let _ = menu.append(&MenuItem::new(tr("tray-empty"), false, None));
+        }
+        for request in pinned {
+            let _ = menu.append(&MenuItem::with_id(
+                format!("{}{}", SEND_ID_PREFIX, request.id),
+                tr_args("tray-send", &[("name", &request.label)]),
+                true,
+                None,
+            ));
+        }
+        menu
+    }
+
+    /// A filled circle, drawn rather than decoded so no image format is needed
+    fn icon() -> Result<Icon, String> {
+        const SIZE: u32 = 32;
+        let center = (SIZE as f32 - 1.0) / 2.0;
+        let mut rgba = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let distance = ((x as f32 - center).powi(2) + (y as f32 - center).powi(2)).sqrt();
+                let alpha = (center - distance + 0.5).clamp(0.0, 1.0);
+                rgba.extend_from_slice(&ICON_COLOR);
+                rgba.push((alpha * 255.0) as u8);
+            }
+        }
+        Icon::from_rgba(rgba, SIZE, SIZE).map_err(|e| e.to_string())
+    }
+
+    fn build(pinned: &[PinnedRequest]) -> Result<TrayIcon, String> {
+        TrayIconBuilder::new()
+            .with_menu(Box::new(menu(pinned)))
+            .with_tooltip("Beam")
+            .with_icon(icon()?)
+            .build()
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn events() -> Vec<TrayEvent> {
+        MenuEvent::receiver()
+            .try_iter()
+            .filter_map(|event| {
+                let id = event.id.0;
+                if id == SHOW_WINDOW_ID {
+                    Some(TrayEvent::ShowWindow)
+                } else {
+                    id.strip_prefix(SEND_ID_PREFIX)
+                        .map(|request_id| TrayEvent::Send(request_id.to_string()))
+                }
+            })
+            .collect()
+    }
+
+    /// On macOS and Windows the icon lives on the main thread, next to the window
+    #[cfg(not(target_os = "linux"))]
+    pub struct Tray {
+        icon: TrayIcon,
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    impl Tray {
+        pub fn new(pinned: &[PinnedRequest]) -> Result<Self, String> {
+            Ok(Self {
+                icon: build(pinned)?,
+            })
+        }
+
+        pub fn set_pinned(&mut self, pinned: &[PinnedRequest]) {
+            self.icon.set_menu(Some(Box::new(menu(pinned))));
+        }
+
+        pub fn set_status(&mut self, status: &str) {
+            let _ = self.icon.set_tooltip(Some(status));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    enum Update {
+        Pinned(Vec<PinnedRequest>),
+        Status(String),
+    }
+
+    /// On Linux the icon needs a GTK loop, which runs on a thread of its own. It ends once
+    /// the tray is dropped.
+    #[cfg(target_os = "linux")]
+    pub struct Tray {
+        updates: std::sync::mpsc::Sender<Update>,
+    }
+
+    #[cfg(target_os = "linux")]
+    impl Tray {
+        pub fn new(pinned: &[PinnedRequest]) -> Result<Self, String> {
+            use gtk::glib;
+            use std::sync::mpsc::{self, TryRecvError};
+
+            let (updates, received) = mpsc::channel::<Update>();
+            let (ready, started) = mpsc::channel::<Result<(), String>>();
+            let pinned = pinned.to_vec();
+
+            std::thread::spawn(move || {
+                let icon = gtk::init()
+                    .map_err(|e| e.to_string())
+                    .and_then(|_| build(&pinned));
+                let icon = match icon {
+                    Ok(icon) => {
+                        let _ = ready.send(Ok(()));
+                        icon
+                    }
+                    Err(e) => {
+                        let _ = ready.send(Err(e));
+                        return;
+                    }
+                };
+
+                glib::timeout_add_local(std::time::Duration::from_millis(200), move || {
+                    loop {
+                        match received.try_recv() {
+                            Ok(Update::Pinned(pinned)) => {
+                                icon.set_menu(Some(Box::new(menu(&pinned))));
+                            }
+                            Ok(Update::Status(status)) => {
+                                let _ = icon.set_tooltip(Some(status));
+                            }
+                            Err(TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                            Err(TryRecvError::Disconnected) => {
+                                gtk::main_quit();
+                                return glib::ControlFlow::Break;
+                            }
+                        }
+                    }
+                });
+                gtk::main();
+            });
+
+            started
+                .recv()
+                .map_err(|e| e.to_string())
+                .and_then(|result| result)?;
+            Ok(Self { updates })
+        }
+
+        pub fn set_pinned(&mut self, pinned: &[PinnedRequest]) {
+            let _ = self.updates.send(Update::Pinned(pinned.to_vec()));
+        }
+
+        pub fn set_status(&mut self, status: &str) {
+            let _ = self.updates.send(Update::Status(status.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TestResult;
+
+    #[test]
+    fn summarizes_a_send_for_the_notification() {
+        let mut response = ResponseData {
+            status: 200,
+            status_text: "OK".to_string(),
+            time: 120,
+            test_results: vec![TestResult::pass("status")],
+            ..Default::default()
+        };
+        assert_eq!(summary(&response), "200 OK in 120 ms");
+
+        response.test_results.push(TestResult {
+            name: "body".to_string(),
+            passed: false,
+            error_message: None,
+        });
+        assert_eq!(summary(&response), "200 OK in 120 ms, 1 of 2 checks failed");
+
+        response.status = 0;
+        response.status_text = "Connection refused".to_string();
+        assert_eq!(summary(&response), "Connection refused");
+    }
+}
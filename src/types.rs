@@ -1,9 +1,84 @@
+use crate::conditional;
+use crate::constant::{DEFAULT_MAX_DOWNLOAD_SIZE, DEFAULT_MAX_UPLOAD_SIZE, MAX_RUN_HISTORY};
+use crate::i18n::{Language, tr};
 use crate::storage::persistent_types::RequestMetadata;
 use iced::Color;
 use iced::advanced::text::Highlighter;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+/// Generates a random (version 4) UUID used to identify collections and requests
+pub fn new_id() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Finds a request by id across all collections, for reading
+pub fn find_request<'a>(
+    collections: &'a [RequestCollection],
+    id: &str,
+) -> Option<&'a RequestConfig> {
+    collections
+        .iter()
+        .flat_map(|collection| collection.requests.iter())
+        .find(|request| request.id == id)
+}
+
+/// Finds a request by id across all collections
+pub fn find_request_mut<'a>(
+    collections: &'a mut [RequestCollection],
+    id: &str,
+) -> Option<&'a mut RequestConfig> {
+    collections
+        .iter_mut()
+        .flat_map(|collection| collection.requests.iter_mut())
+        .find(|request| request.id == id)
+}
+
+/// Finds a collection by id
+pub fn find_collection<'a>(
+    collections: &'a [RequestCollection],
+    id: &str,
+) -> Option<&'a RequestCollection> {
+    collections.iter().find(|collection| collection.id == id)
+}
+
+/// Finds a collection by id, for changing it
+pub fn find_collection_mut<'a>(
+    collections: &'a mut [RequestCollection],
+    id: &str,
+) -> Option<&'a mut RequestCollection> {
+    collections
+        .iter_mut()
+        .find(|collection| collection.id == id)
+}
+
+/// Where the request with this id currently is, as (collection, request) indices
+pub fn request_position(collections: &[RequestCollection], id: &str) -> Option<(usize, usize)> {
+    collections
+        .iter()
+        .enumerate()
+        .find_map(|(collection_index, collection)| {
+            collection
+                .requests
+                .iter()
+                .position(|request| request.id == id)
+                .map(|request_index| (collection_index, request_index))
+        })
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum HttpMethod {
     GET,
@@ -15,12 +90,35 @@ pub enum HttpMethod {
     OPTIONS,
 }
 
+impl HttpMethod {
+    pub const ALL: [HttpMethod; 7] = [
+        HttpMethod::GET,
+        HttpMethod::POST,
+        HttpMethod::PUT,
+        HttpMethod::DELETE,
+        HttpMethod::PATCH,
+        HttpMethod::HEAD,
+        HttpMethod::OPTIONS,
+    ];
+
+    /// Parses a method name case-insensitively, e.g. the first word of "post https://..."
+    pub fn parse(method: &str) -> Option<HttpMethod> {
+        HttpMethod::ALL
+            .into_iter()
+            .find(|candidate| candidate.to_string().eq_ignore_ascii_case(method))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AuthType {
     None,
     Bearer,
     Basic,
     ApiKey,
+    Hmac,
+    Hawk,
+    // Uses the default auth of the active environment
+    InheritFromEnvironment,
 }
 
 impl Default for AuthType {
@@ -29,10 +127,184 @@ impl Default for AuthType {
     }
 }
 
+/// Where an API key is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ApiKeyLocation {
+    #[default]
+    Header,
+    Query,
+}
+
+impl std::fmt::Display for ApiKeyLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiKeyLocation::Header => write!(f, "Header"),
+            ApiKeyLocation::Query => write!(f, "Query Params"),
+        }
+    }
+}
+
+/// Hash function used for HMAC request signing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum HmacAlgorithm {
+    #[default]
+    Sha256,
+    Sha512,
+}
+
+impl std::fmt::Display for HmacAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HmacAlgorithm::Sha256 => write!(f, "HMAC-SHA256"),
+            HmacAlgorithm::Sha512 => write!(f, "HMAC-SHA512"),
+        }
+    }
+}
+
+/// Settings for the generic HMAC signature auth type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HmacAuth {
+    pub algorithm: HmacAlgorithm,
+    pub key: String,
+    // Template for the signed string, see `signing::hmac_headers` for placeholders
+    pub string_to_sign: String,
+    pub header_name: String,
+    pub header_value: String,
+}
+
+impl Default for HmacAuth {
+    fn default() -> Self {
+        Self {
+            algorithm: HmacAlgorithm::default(),
+            key: String::new(),
+            string_to_sign: "{method}\\n{path}\\n{date}\\n{body_hash}".to_string(),
+            header_name: "Authorization".to_string(),
+            header_value: "HMAC {signature}".to_string(),
+        }
+    }
+}
+
+/// Hawk credentials
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HawkAuth {
+    pub id: String,
+    pub key: String,
+    pub ext: String,
+}
+
+/// Per-request exceptions to how requests are normally sent, which follow redirects, go
+/// through the system proxy and verify TLS certificates
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnectionOverrides {
+    pub disable_redirects: bool,
+    pub disable_proxy: bool,
+    pub accept_invalid_certs: bool,
+}
+
+impl ConnectionOverrides {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `ETag` and `Last-Modified` of the last full response, sent back by conditional requests
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// How an API points to the next page of results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PaginationStyle {
+    #[default]
+    None,
+    /// `Link: <...>; rel="next"`
+    LinkHeader,
+    /// A cursor from the body is sent back in a query parameter
+    Cursor,
+    /// A page number query parameter, incremented by one
+    Page,
+    /// An offset query parameter, advanced by the number of items received
+    Offset,
+}
+
+impl PaginationStyle {
+    pub const ALL: [PaginationStyle; 5] = [
+        PaginationStyle::None,
+        PaginationStyle::LinkHeader,
+        PaginationStyle::Cursor,
+        PaginationStyle::Page,
+        PaginationStyle::Offset,
+    ];
+}
+
+impl std::fmt::Display for PaginationStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaginationStyle::None => write!(f, "None"),
+            PaginationStyle::LinkHeader => write!(f, "Link header"),
+            PaginationStyle::Cursor => write!(f, "Cursor"),
+            PaginationStyle::Page => write!(f, "Page number"),
+            PaginationStyle::Offset => write!(f, "Offset"),
+        }
+    }
+}
+
+/// Where to find the next page of a paginated response
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Pagination {
+    pub style: PaginationStyle,
+    /// Query parameter carrying the cursor, page number or offset
+    pub param: String,
+    /// JSONPath to the next cursor in the body
+    pub cursor_path: String,
+    /// JSONPath to the items of a page, the body itself when empty
+    pub items_path: String,
+}
+
+/// Compression schemes Beam can advertise in `Accept-Encoding` and decode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentEncoding {
+    Gzip,
+    Br,
+    Zstd,
+}
+
+impl ContentEncoding {
+    pub const ALL: [ContentEncoding; 3] = [
+        ContentEncoding::Gzip,
+        ContentEncoding::Br,
+        ContentEncoding::Zstd,
+    ];
+}
+
+impl std::fmt::Display for ContentEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentEncoding::Gzip => write!(f, "gzip"),
+            ContentEncoding::Br => write!(f, "br"),
+            ContentEncoding::Zstd => write!(f, "zstd"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum RenameTarget {
-    Folder(usize),         // collection_index
-    Request(usize, usize), // (collection_index, request_index)
+    Folder(String),  // Collection id
+    Request(String), // Request id
 }
 
 #[allow(dead_code)]
@@ -75,10 +347,193 @@ impl Highlighter for ResponseHighlighter {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequestCollection {
+    pub id: String,
     pub name: String,
     pub folder_name: String,
     pub requests: Vec<RequestConfig>,
     pub expanded: bool,
+    #[serde(default)]
+    pub color: Option<CollectionColor>,
+    #[serde(default)]
+    pub icon: Option<CollectionIcon>,
+    #[serde(default)]
+    pub sort: CollectionSort,
+    #[serde(default)]
+    pub run_configuration: RunConfiguration,
+}
+
+/// How the collection runner goes through a collection, kept in its collection.toml. Without
+/// steps every request is sent, in the order of the files.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunConfiguration {
+    #[serde(default)]
+    pub steps: Vec<RunStep>,
+    /// Wait before each request after the first, in milliseconds
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// End the run at the first request that fails, once its retries are used up
+    #[serde(default)]
+    pub stop_on_failure: bool,
+    /// Times a failed request is sent again before its failure counts
+    #[serde(default)]
+    pub retries: u32,
+    /// Send a 429 or 503 again once its Retry-After is up, without counting it as a retry
+    #[serde(default)]
+    pub retry_after_rate_limit: bool,
+}
+
+impl RunConfiguration {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A request of a run configuration, in the order they're sent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunStep {
+    pub request_id: String,
+    #[serde(default)]
+    pub skip: bool,
+    #[serde(default)]
+    pub loop_back: Option<LoopBack>,
+    #[serde(default)]
+    pub jump: Option<Jump>,
+}
+
+impl RunStep {
+    pub fn new(request_id: String) -> Self {
+        Self {
+            request_id,
+            skip: false,
+            loop_back: None,
+            jump: None,
+        }
+    }
+}
+
+/// Sends the steps from the one of request `to` up to this one again, `times` more times
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopBack {
+    pub to: String,
+    pub times: u32,
+}
+
+/// Goes on with another step than the next one, depending on how this one's checks did
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Jump {
+    pub when: JumpWhen,
+    /// Name of the check to go by, empty for all of them
+    #[serde(default)]
+    pub test: String,
+    /// Request of the step to go to, empty to end the run
+    #[serde(default)]
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JumpWhen {
+    #[default]
+    Failed,
+    Passed,
+}
+
+impl JumpWhen {
+    pub const ALL: [JumpWhen; 2] = [JumpWhen::Failed, JumpWhen::Passed];
+}
+
+impl std::fmt::Display for JumpWhen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JumpWhen::Failed => write!(f, "fails"),
+            JumpWhen::Passed => write!(f, "passes"),
+        }
+    }
+}
+
+/// Order of the requests of a collection in the sidebar, the files keep their manual order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionSort {
+    #[default]
+    Manual,
+    Name,
+    Method,
+    LastSent,
+}
+
+impl CollectionSort {
+    pub const ALL: [CollectionSort; 4] = [
+        CollectionSort::Manual,
+        CollectionSort::Name,
+        CollectionSort::Method,
+        CollectionSort::LastSent,
+    ];
+}
+
+impl std::fmt::Display for CollectionSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CollectionSort::Manual => write!(f, "Manual"),
+            CollectionSort::Name => write!(f, "Name"),
+            CollectionSort::Method => write!(f, "Method"),
+            CollectionSort::LastSent => write!(f, "Last Sent"),
+        }
+    }
+}
+
+/// Swatch shown next to a collection in the sidebar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectionColor {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Teal,
+    Blue,
+    Purple,
+    Pink,
+}
+
+impl CollectionColor {
+    pub const ALL: [CollectionColor; 8] = [
+        CollectionColor::Red,
+        CollectionColor::Orange,
+        CollectionColor::Yellow,
+        CollectionColor::Green,
+        CollectionColor::Teal,
+        CollectionColor::Blue,
+        CollectionColor::Purple,
+        CollectionColor::Pink,
+    ];
+}
+
+/// Icon shown in place of the default folder for a collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectionIcon {
+    Folder,
+    Globe,
+    Database,
+    Lock,
+    User,
+    Cart,
+    Zap,
+    Cloud,
+}
+
+impl CollectionIcon {
+    pub const ALL: [CollectionIcon; 8] = [
+        CollectionIcon::Folder,
+        CollectionIcon::Globe,
+        CollectionIcon::Database,
+        CollectionIcon::Lock,
+        CollectionIcon::User,
+        CollectionIcon::Cart,
+        CollectionIcon::Zap,
+        CollectionIcon::Cloud,
+    ];
 }
 
 // impl RequestCollection {
@@ -120,6 +575,9 @@ pub struct RequestCollection {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RequestConfig {
+    // Stable identifier, it survives reordering and deletion of other requests
+    #[serde(default)]
+    pub id: String,
     pub name: String,
     pub path: PathBuf,
     pub method: HttpMethod,
@@ -130,6 +588,16 @@ pub struct RequestConfig {
     pub content_type: String,
     pub auth_type: AuthType,
 
+    // Values of `:name` / `{name}` path segments, substituted into the URL when sending
+    #[serde(default)]
+    pub path_variables: Vec<(String, String)>,
+
+    // Descriptions and value types of params and headers, keyed by name
+    #[serde(default)]
+    pub param_docs: BTreeMap<String, FieldDoc>,
+    #[serde(default)]
+    pub header_docs: BTreeMap<String, FieldDoc>,
+
     #[serde(default)]
     pub body_format: BodyFormat,
 
@@ -139,9 +607,69 @@ pub struct RequestConfig {
     pub basic_password: String,
     pub api_key: String,
     pub api_key_header: String,
+    #[serde(default)]
+    pub api_key_location: ApiKeyLocation,
+
+    // Unix domain socket to connect through instead of TCP (e.g. /var/run/docker.sock)
+    #[serde(default)]
+    pub unix_socket: String,
+
+    // Size guards in bytes, 0 disables the limit
+    #[serde(default = "default_max_upload_size")]
+    pub max_upload_size: u64,
+
+    #[serde(default = "default_max_download_size")]
+    pub max_download_size: u64,
+
+    // Runs slower than this many milliseconds are flagged, 0 disables the budget
+    #[serde(default)]
+    pub latency_budget: u64,
+
+    // Resends a 429 or 503 response once its Retry-After wait is over
+    #[serde(default)]
+    pub retry_after_rate_limit: bool,
+
+    // Grades the security headers of responses in the response panel
+    #[serde(default)]
+    pub audit_security_headers: bool,
+
+    // Compression: encodings advertised in Accept-Encoding, empty to disable
+    #[serde(default = "default_accept_encoding")]
+    pub accept_encoding: Vec<ContentEncoding>,
+
+    #[serde(default = "default_decompress_response")]
+    pub decompress_response: bool,
+
+    // Skips percent-encoding unsafe query characters at send time
+    #[serde(default)]
+    pub send_query_as_typed: bool,
+
+    // Redirect, proxy and TLS behaviour that differs from the defaults for this request only
+    #[serde(default)]
+    pub connection: ConnectionOverrides,
+
+    // Request signing settings for the Hmac and Hawk auth types
+    #[serde(default)]
+    pub hmac_auth: HmacAuth,
+
+    #[serde(default)]
+    pub hawk_auth: HawkAuth,
 
-    pub collection_index: usize,
-    pub request_index: usize,
+    // How to follow the pages of a paginated API
+    #[serde(default)]
+    pub pagination: Pagination,
+
+    // Validators of the last response, for conditional sends
+    #[serde(default)]
+    pub cache_validators: CacheValidators,
+
+    // Status and duration of the most recent sends, oldest first
+    #[serde(default)]
+    pub run_history: Vec<RunRecord>,
+
+    // Named responses kept as examples of what the request returns
+    #[serde(default)]
+    pub examples: Vec<ResponseExample>,
 
     #[serde(default)]
     pub metadata: Option<RequestMetadata>,
@@ -149,13 +677,47 @@ pub struct RequestConfig {
     #[serde(default)]
     pub post_request_script: Option<String>,
 
+    // Expectations checked against every response
+    #[serde(default)]
+    pub assertions: ResponseAssertions,
+
+    // Request run first to renew the bearer token once it's missing or expired
+    #[serde(default)]
+    pub token_refresh: TokenRefresh,
+
+    // OpenAPI operation the request was imported from, responses are checked against it
+    #[serde(default)]
+    pub contract: Option<ApiContract>,
+
+    // Background health check sending the request on an interval
+    #[serde(default)]
+    pub monitor: Monitor,
+
     #[serde(default)]
     pub last_response: Option<ResponseData>,
 }
 
+fn default_accept_encoding() -> Vec<ContentEncoding> {
+    ContentEncoding::ALL.to_vec()
+}
+
+fn default_decompress_response() -> bool {
+    true
+}
+
+fn default_max_upload_size() -> u64 {
+    DEFAULT_MAX_UPLOAD_SIZE
+}
+
+fn default_max_download_size() -> u64 {
+    DEFAULT_MAX_DOWNLOAD_SIZE
+}
+
 /// Serializable version of RequestConfig for storage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SerializableRequestConfig {
+    #[serde(default)]
+    pub id: Option<String>,
     pub name: Option<String>, // Add name field for identification
     pub method: HttpMethod,
     pub url: Option<String>,
@@ -172,6 +734,56 @@ pub struct SerializableRequestConfig {
     pub basic_password: Option<String>,
     pub api_key: Option<String>,
     pub api_key_header: Option<String>,
+    #[serde(default)]
+    pub api_key_location: Option<ApiKeyLocation>,
+
+    #[serde(default)]
+    pub unix_socket: Option<String>,
+
+    #[serde(default)]
+    pub max_upload_size: Option<u64>,
+
+    #[serde(default)]
+    pub max_download_size: Option<u64>,
+
+    #[serde(default)]
+    pub latency_budget: Option<u64>,
+
+    #[serde(default)]
+    pub retry_after_rate_limit: Option<bool>,
+
+    #[serde(default)]
+    pub audit_security_headers: Option<bool>,
+
+    #[serde(default)]
+    pub accept_encoding: Option<Vec<ContentEncoding>>,
+
+    #[serde(default)]
+    pub decompress_response: Option<bool>,
+
+    #[serde(default)]
+    pub send_query_as_typed: Option<bool>,
+
+    #[serde(default)]
+    pub connection: Option<ConnectionOverrides>,
+
+    #[serde(default)]
+    pub hmac_auth: Option<HmacAuth>,
+
+    #[serde(default)]
+    pub hawk_auth: Option<HawkAuth>,
+
+    #[serde(default)]
+    pub pagination: Option<Pagination>,
+
+    #[serde(default)]
+    pub cache_validators: Option<CacheValidators>,
+
+    #[serde(default)]
+    pub run_history: Option<Vec<RunRecord>>,
+
+    #[serde(default)]
+    pub examples: Option<Vec<ResponseExample>>,
 
     // Metadata field (optional for backward compatibility)
     #[serde(default)]
@@ -181,6 +793,27 @@ pub struct SerializableRequestConfig {
     #[serde(default)]
     pub post_request_script: Option<String>,
 
+    #[serde(default)]
+    pub path_variables: Option<Vec<(String, String)>>,
+
+    #[serde(default)]
+    pub param_docs: Option<BTreeMap<String, FieldDoc>>,
+
+    #[serde(default)]
+    pub header_docs: Option<BTreeMap<String, FieldDoc>>,
+
+    #[serde(default)]
+    pub assertions: Option<ResponseAssertions>,
+
+    #[serde(default)]
+    pub token_refresh: Option<TokenRefresh>,
+
+    #[serde(default)]
+    pub contract: Option<ApiContract>,
+
+    #[serde(default)]
+    pub monitor: Option<Monitor>,
+
     // Last response (optional for backward compatibility)
     #[serde(default)]
     pub last_response: Option<ResponseData>,
@@ -189,6 +822,7 @@ pub struct SerializableRequestConfig {
 impl Clone for RequestConfig {
     fn clone(&self) -> Self {
         Self {
+            id: self.id.clone(),
             name: self.name.clone(),
             path: self.path.clone(),
             method: self.method.clone(),
@@ -204,19 +838,87 @@ impl Clone for RequestConfig {
             basic_password: self.basic_password.clone(),
             api_key: self.api_key.clone(),
             api_key_header: self.api_key_header.clone(),
-            collection_index: self.collection_index,
-            request_index: self.request_index,
+            api_key_location: self.api_key_location,
+            unix_socket: self.unix_socket.clone(),
+            max_upload_size: self.max_upload_size,
+            max_download_size: self.max_download_size,
+            latency_budget: self.latency_budget,
+            retry_after_rate_limit: self.retry_after_rate_limit,
+            audit_security_headers: self.audit_security_headers,
+            accept_encoding: self.accept_encoding.clone(),
+            decompress_response: self.decompress_response,
+            send_query_as_typed: self.send_query_as_typed,
+            connection: self.connection.clone(),
+            hmac_auth: self.hmac_auth.clone(),
+            hawk_auth: self.hawk_auth.clone(),
+            pagination: self.pagination.clone(),
+            cache_validators: self.cache_validators.clone(),
+            run_history: self.run_history.clone(),
+            examples: self.examples.clone(),
             // TODO: check this
             metadata: Some(RequestMetadata::default()),
             post_request_script: self.post_request_script.clone(),
+            path_variables: self.path_variables.clone(),
+            param_docs: self.param_docs.clone(),
+            header_docs: self.header_docs.clone(),
+            assertions: self.assertions.clone(),
+            token_refresh: self.token_refresh.clone(),
+            contract: self.contract.clone(),
+            monitor: self.monitor.clone(),
             last_response: self.last_response.clone(),
         }
     }
 }
 
+impl RequestConfig {
+    /// Stores the response as the latest one and appends it to the run history
+    pub fn record_response(&mut self, response: ResponseData) {
+        self.run_history.push(RunRecord {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            status: response.status,
+            duration: response.time,
+            budget: (self.latency_budget > 0).then_some(self.latency_budget),
+            note: String::new(),
+        });
+        if self.run_history.len() > MAX_RUN_HISTORY {
+            let excess = self.run_history.len() - MAX_RUN_HISTORY;
+            self.run_history.drain(..excess);
+        }
+
+        conditional::remember_validators(&mut self.cache_validators, &response);
+        self.last_response = Some(response);
+    }
+
+    /// Keeps `response` as an example, named after its status unless that name is taken
+    pub fn add_example(&mut self, response: &ResponseData) -> usize {
+        let base = format!("{} {}", response.status, response.status_text)
+            .trim()
+            .to_string();
+        let mut name = base.clone();
+        let mut counter = 2;
+        while self.examples.iter().any(|example| example.name == name) {
+            name = format!("{} ({})", base, counter);
+            counter += 1;
+        }
+
+        self.examples.push(ResponseExample {
+            name,
+            status: response.status,
+            status_text: response.status_text.clone(),
+            headers: response.headers.clone(),
+            content_type: response.content_type.clone(),
+            body: response.body.clone(),
+            saved_at: chrono::Utc::now().to_rfc3339(),
+            mock: MockBehavior::default(),
+        });
+        self.examples.len() - 1
+    }
+}
+
 impl Default for RequestConfig {
     fn default() -> Self {
         Self {
+            id: new_id(),
             name: "New Request".to_string(),
             path: PathBuf::new(),
             method: HttpMethod::GET,
@@ -232,10 +934,32 @@ impl Default for RequestConfig {
             basic_password: String::new(),
             api_key: String::new(),
             api_key_header: String::new(),
-            collection_index: 0,
-            request_index: 0,
+            api_key_location: ApiKeyLocation::default(),
             metadata: Some(RequestMetadata::default()),
+            unix_socket: String::new(),
+            max_upload_size: DEFAULT_MAX_UPLOAD_SIZE,
+            max_download_size: DEFAULT_MAX_DOWNLOAD_SIZE,
+            latency_budget: 0,
+            retry_after_rate_limit: false,
+            audit_security_headers: false,
+            accept_encoding: ContentEncoding::ALL.to_vec(),
+            decompress_response: true,
+            send_query_as_typed: false,
+            connection: ConnectionOverrides::default(),
+            hmac_auth: HmacAuth::default(),
+            hawk_auth: HawkAuth::default(),
+            pagination: Pagination::default(),
+            cache_validators: CacheValidators::default(),
+            run_history: Vec::new(),
+            examples: Vec::new(),
             post_request_script: None,
+            path_variables: Vec::new(),
+            param_docs: BTreeMap::new(),
+            header_docs: BTreeMap::new(),
+            assertions: ResponseAssertions::default(),
+            token_refresh: TokenRefresh::default(),
+            contract: None,
+            monitor: Monitor::default(),
             last_response: None,
         }
     }
@@ -268,6 +992,61 @@ pub struct EnvironmentVariable {
     pub value: String,
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    /// Masked in the environment editor, logs and exports
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub secret: bool,
+    #[serde(
+        default,
+        rename = "type",
+        skip_serializing_if = "VariableType::is_string"
+    )]
+    pub value_type: VariableType,
+}
+
+/// What a variable holds, which decides how it's written into a JSON body
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableType {
+    #[default]
+    String,
+    Number,
+    Bool,
+    Json,
+}
+
+impl VariableType {
+    pub const ALL: [VariableType; 4] = [
+        VariableType::String,
+        VariableType::Number,
+        VariableType::Bool,
+        VariableType::Json,
+    ];
+
+    pub fn is_string(&self) -> bool {
+        *self == VariableType::String
+    }
+
+    /// Whether `value` is a JSON literal of this type, which is written unquoted
+    pub fn is_literal(&self, value: &str) -> bool {
+        let parsed = || serde_json::from_str::<serde_json::Value>(value.trim());
+        match self {
+            VariableType::String => false,
+            VariableType::Number => parsed().is_ok_and(|value| value.is_number()),
+            VariableType::Bool => parsed().is_ok_and(|value| value.is_boolean()),
+            VariableType::Json => parsed().is_ok(),
+        }
+    }
+}
+
+impl std::fmt::Display for VariableType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VariableType::String => write!(f, "string"),
+            VariableType::Number => write!(f, "number"),
+            VariableType::Bool => write!(f, "bool"),
+            VariableType::Json => write!(f, "json"),
+        }
+    }
 }
 
 fn default_enabled() -> bool {
@@ -279,6 +1058,8 @@ impl EnvironmentVariable {
         Self {
             value,
             enabled: true,
+            secret: false,
+            value_type: VariableType::String,
         }
     }
 }
@@ -297,6 +1078,8 @@ impl<'de> serde::Deserialize<'de> for Environment {
             #[serde(deserialize_with = "deserialize_variables")]
             variables: std::collections::BTreeMap<String, EnvironmentVariable>,
             description: Option<String>,
+            #[serde(default)]
+            auth: EnvironmentAuth,
         }
 
         fn deserialize_variables<'de, D>(
@@ -364,6 +1147,7 @@ impl<'de> serde::Deserialize<'de> for Environment {
             name: helper.name,
             variables: helper.variables,
             description: helper.description,
+            auth: helper.auth,
         })
     }
 }
@@ -374,10 +1158,15 @@ impl serde::Serialize for Environment {
         S: serde::Serializer,
     {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("Environment", 3)?;
+        let mut state = serializer.serialize_struct("Environment", 4)?;
         state.serialize_field("name", &self.name)?;
         state.serialize_field("variables", &self.variables)?;
         state.serialize_field("description", &self.description)?;
+        if self.auth.auth_type == AuthType::None {
+            state.skip_field("auth")?;
+        } else {
+            state.serialize_field("auth", &self.auth)?;
+        }
         state.end()
     }
 }
@@ -387,6 +1176,47 @@ pub struct Environment {
     pub name: String,
     pub variables: std::collections::BTreeMap<String, EnvironmentVariable>,
     pub description: Option<String>,
+    pub auth: EnvironmentAuth,
+}
+
+/// Auth shared by the requests of an environment set to `AuthType::InheritFromEnvironment`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentAuth {
+    #[serde(default)]
+    pub auth_type: AuthType,
+    #[serde(default)]
+    pub bearer_token: String,
+    #[serde(default)]
+    pub basic_username: String,
+    #[serde(default)]
+    pub basic_password: String,
+    #[serde(default)]
+    pub api_key: String,
+    #[serde(default)]
+    pub api_key_header: String,
+    #[serde(default)]
+    pub api_key_location: ApiKeyLocation,
+}
+
+impl EnvironmentAuth {
+    /// Auth types an environment can provide
+    pub const TYPES: [AuthType; 4] = [
+        AuthType::None,
+        AuthType::Bearer,
+        AuthType::Basic,
+        AuthType::ApiKey,
+    ];
+
+    /// Replaces the inherited auth of `request` with this one
+    pub fn apply_to(&self, request: &mut RequestConfig) {
+        request.auth_type = self.auth_type.clone();
+        request.bearer_token = self.bearer_token.clone();
+        request.basic_username = self.basic_username.clone();
+        request.basic_password = self.basic_password.clone();
+        request.api_key = self.api_key.clone();
+        request.api_key_header = self.api_key_header.clone();
+        request.api_key_location = self.api_key_location;
+    }
 }
 
 impl Environment {
@@ -395,6 +1225,7 @@ impl Environment {
             name,
             variables: std::collections::BTreeMap::new(),
             description: None,
+            auth: EnvironmentAuth::default(),
         }
     }
 
@@ -423,13 +1254,16 @@ impl Environment {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RequestTab {
     Body,
     Params,
     Headers,
     Auth,
     PostScript,
+    Assertions,
+    Settings,
+    History,
     // #[allow(dead_code)]
     // Environment,
 }
@@ -461,13 +1295,131 @@ impl std::fmt::Display for BodyFormat {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ResponseTab {
     Body,
     Headers,
+    Caching,
+    Request,
+    Tests,
+    Certificate,
+    History,
+    Examples,
+    Chart,
+    Records,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Where the response pane sits relative to the request pane
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ResponsePosition {
+    #[default]
+    Right,
+    Bottom,
+}
+
+/// Window and panel layout, restored on launch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowLayout {
+    pub width: f32,
+    pub height: f32,
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub sidebar_ratio: f32, // Share of the window taken by the collections sidebar
+    pub request_ratio: f32, // Share of the remaining width taken by the request pane
+    pub sidebar_collapsed: bool,
+    pub response_position: ResponsePosition,
+    pub request_tab: RequestTab,
+    pub response_tab: ResponseTab,
+    pub theme: Option<String>, // Name of the selected theme, the system's look when unset
+    pub language: Option<Language>, // Interface language, the system's when unset
+    pub large_targets: bool,   // Larger click targets with higher contrast
+    pub show_tray: bool,       // Icon in the system tray / menu bar
+    pub pinned_requests: Vec<String>, // Ids of the requests listed in the tray menu
+    pub usage_stats: bool,     // Opted in to the local usage statistics page
+    /// Tab last shown for each request, by id. `request_tab` is for the requests not in it.
+    pub request_tabs: BTreeMap<String, RequestTab>,
+    pub json_body: JsonBodyFormatting, // Formatting of JSON bodies on save and send
+}
+
+impl Default for WindowLayout {
+    fn default() -> Self {
+        Self {
+            width: 1200.0,
+            height: 800.0,
+            x: None,
+            y: None,
+            // Collections: 25%, Request Config: 40%, Response: 35%
+            sidebar_ratio: 0.25,
+            request_ratio: 0.466,
+            sidebar_collapsed: false,
+            response_position: ResponsePosition::Right,
+            request_tab: RequestTab::Body,
+            response_tab: ResponseTab::Body,
+            theme: None,
+            language: None,
+            large_targets: false,
+            show_tray: false,
+            pinned_requests: Vec::new(),
+            usage_stats: false,
+            request_tabs: BTreeMap::new(),
+            json_body: JsonBodyFormatting::default(),
+        }
+    }
+}
+
+/// How a JSON body is rewritten
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonFormatting {
+    #[default]
+    Off,
+    Pretty,
+    Minify,
+}
+
+impl JsonFormatting {
+    pub const ALL: [JsonFormatting; 3] = [
+        JsonFormatting::Off,
+        JsonFormatting::Pretty,
+        JsonFormatting::Minify,
+    ];
+
+    /// The body rewritten, None when it's left as is: formatting is off, or the body isn't
+    /// valid JSON (e.g. a `{{variable}}` stands for a value)
+    pub fn apply(&self, body: &str) -> Option<String> {
+        let value = match self {
+            JsonFormatting::Off => return None,
+            _ => serde_json::from_str::<serde_json::Value>(body).ok()?,
+        };
+        match self {
+            JsonFormatting::Pretty => serde_json::to_string_pretty(&value).ok(),
+            _ => serde_json::to_string(&value).ok(),
+        }
+    }
+}
+
+impl std::fmt::Display for JsonFormatting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonFormatting::Off => write!(f, "{}", tr("settings-json-off")),
+            JsonFormatting::Pretty => write!(f, "{}", tr("settings-json-pretty")),
+            JsonFormatting::Minify => write!(f, "{}", tr("settings-json-minify")),
+        }
+    }
+}
+
+/// Rewriting of JSON request bodies, all off unless opted in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonBodyFormatting {
+    pub on_save: JsonFormatting,
+    pub on_send: JsonFormatting,
+    /// Refuse to send a body that isn't valid JSON once its variables are resolved
+    pub block_malformed: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ResponseData {
     pub status: u16,
     pub status_text: String,
@@ -477,7 +1429,261 @@ pub struct ResponseData {
     pub content_type: String,
     pub is_binary: bool,
     pub size: usize,
+    #[serde(default)]
+    pub compressed_size: Option<usize>, // Size on the wire when the body was compressed
     pub time: u64, // milliseconds
+    #[serde(default)]
+    pub raw_request: String, // Request as it was sent over the wire
+    #[serde(default)]
+    pub body_file: Option<PathBuf>, // Temp copy of a binary body, for opening externally
+    #[serde(default)]
+    pub certificates: Vec<CertificateInfo>, // Server certificate chain, leaf first
+    #[serde(default)]
+    pub test_results: Vec<TestResult>, // Assertion and script test outcomes
+}
+
+/// Documentation of a query param or header, shown as a hint in its value field
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldDoc {
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub value_type: ValueType,
+}
+
+impl FieldDoc {
+    pub fn is_empty(&self) -> bool {
+        self.description.is_empty() && self.value_type == ValueType::String
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueType {
+    #[default]
+    String,
+    Number,
+    Bool,
+    Date,
+}
+
+impl ValueType {
+    pub const ALL: [ValueType; 4] = [
+        ValueType::String,
+        ValueType::Number,
+        ValueType::Bool,
+        ValueType::Date,
+    ];
+}
+
+impl std::fmt::Display for ValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueType::String => write!(f, "string"),
+            ValueType::Number => write!(f, "number"),
+            ValueType::Bool => write!(f, "bool"),
+            ValueType::Date => write!(f, "date"),
+        }
+    }
+}
+
+/// Declarative checks run against each response, no script needed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResponseAssertions {
+    /// Expected status code such as `200`, or a class such as `2xx`; empty to skip
+    #[serde(default)]
+    pub status: String,
+    /// Header name and expected value, `*` or an empty value only checks presence
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// JSON Schema the body has to satisfy; empty to skip
+    #[serde(default)]
+    pub json_schema: String,
+    /// Recorded response later responses have to match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<ResponseSnapshot>,
+    /// JSONPaths of volatile values, such as timestamps and ids, left out of comparisons
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore_paths: Vec<String>,
+}
+
+impl ResponseAssertions {
+    pub fn is_empty(&self) -> bool {
+        self.status.trim().is_empty()
+            && self.headers.is_empty()
+            && self.json_schema.trim().is_empty()
+            && self.snapshot.is_none()
+    }
+}
+
+/// Status and body of a response as recorded for snapshot testing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseSnapshot {
+    pub status: u16,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub recorded_at: String, // RFC 3339
+}
+
+/// Links a request to the one returning its bearer token, so an expired token is renewed
+/// before sending
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenRefresh {
+    /// Id of the request returning the token
+    #[serde(default)]
+    pub request_id: String,
+    /// Environment variable holding the token, `token` for `{{token}}`
+    #[serde(default)]
+    pub variable: String,
+    /// Dotted path to the token in the JSON response, such as `access_token` or `data.token`
+    #[serde(default)]
+    pub token_path: String,
+    /// How long a token lasts when it isn't a JWT with an `exp` claim, 0 if it doesn't expire
+    #[serde(default)]
+    pub ttl_seconds: u64,
+}
+
+impl TokenRefresh {
+    pub fn is_configured(&self) -> bool {
+        !self.request_id.is_empty() && !self.variable.trim().is_empty()
+    }
+}
+
+/// Sends a request in the background on an interval, flagging the API as down once its
+/// responses stop passing
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Monitor {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds between checks, 0 for the default of 5 minutes
+    #[serde(default)]
+    pub interval_seconds: u64,
+    /// Status a healthy response has, such as `200` or `2xx`; empty to go by the request's
+    /// assertions
+    #[serde(default)]
+    pub expected_status: String,
+}
+
+/// What an OpenAPI operation documents about its responses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiContract {
+    /// Method and path template, e.g. `GET /pets/{id}`
+    pub operation: String,
+    /// The operation's `responses` object as JSON, with `$ref`s inlined
+    pub responses: String,
+}
+
+/// Outcome of one assertion or script test
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestResult {
+    pub name: String,
+    pub passed: bool,
+    pub error_message: Option<String>,
+}
+
+impl TestResult {
+    pub fn pass(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            passed: true,
+            error_message: None,
+        }
+    }
+
+    pub fn fail(name: impl Into<String>, message: String) -> Self {
+        Self {
+            name: name.into(),
+            passed: false,
+            error_message: Some(message),
+        }
+    }
+}
+
+/// Parts of a TLS certificate that help when debugging HTTPS issues
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub subject_alt_names: Vec<String>,
+    pub serial: String,
+    pub not_before: String, // RFC 3339
+    pub not_after: String,  // RFC 3339
+}
+
+/// Outcome of a single send of a request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub timestamp: String, // RFC 3339
+    pub status: u16,       // 0 when no response was received
+    pub duration: u64,     // milliseconds
+    /// Latency budget of the request when it ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<u64>,
+    #[serde(default)]
+    pub note: String,
+}
+
+impl RunRecord {
+    pub fn over_budget(&self) -> bool {
+        self.budget.is_some_and(|budget| self.duration > budget)
+    }
+}
+
+/// A response saved with its request as an example of what it returns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseExample {
+    pub name: String,
+    pub status: u16,
+    #[serde(default)]
+    pub status_text: String,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    #[serde(default)]
+    pub content_type: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub saved_at: String, // RFC 3339
+    #[serde(default, skip_serializing_if = "MockBehavior::is_default")]
+    pub mock: MockBehavior,
+}
+
+/// How the mock server serves an example, slowing it down or failing at random so
+/// client retry logic can be exercised
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MockBehavior {
+    pub latency_ms: u64,
+    /// Percentage of requests answered with `error_status` instead of the example
+    pub error_rate: u8,
+    pub error_status: u16,
+}
+
+impl Default for MockBehavior {
+    fn default() -> Self {
+        Self {
+            latency_ms: 0,
+            error_rate: 0,
+            error_status: 503,
+        }
+    }
+}
+
+impl MockBehavior {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A request that failed because the network was unavailable and is waiting to be retried
+#[derive(Debug, Clone)]
+pub struct QueuedRequest {
+    pub id: u64,
+    pub request: RequestConfig,
+    pub attempts: u32,
+    pub last_error: String,
+    pub in_flight: bool,
 }
 
 impl std::fmt::Display for HttpMethod {
@@ -501,6 +1707,9 @@ impl std::fmt::Display for AuthType {
             AuthType::Bearer => write!(f, "Bearer Token"),
             AuthType::Basic => write!(f, "Basic Auth"),
             AuthType::ApiKey => write!(f, "API Key"),
+            AuthType::Hmac => write!(f, "HMAC Signature"),
+            AuthType::Hawk => write!(f, "Hawk"),
+            AuthType::InheritFromEnvironment => write!(f, "Inherit from Environment"),
         }
     }
 }
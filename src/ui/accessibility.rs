@@ -0,0 +1,60 @@
+use iced::widget::{container, text, tooltip};
+use iced::{Color, Element};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static LARGE_TARGETS: AtomicBool = AtomicBool::new(false);
+
+/// Switches to larger click targets with higher contrast
+pub fn set_large_targets(enabled: bool) {
+    LARGE_TARGETS.store(enabled, Ordering::Relaxed);
+}
+
+pub fn large_targets() -> bool {
+    LARGE_TARGETS.load(Ordering::Relaxed)
+}
+
+/// The padding of a clickable control, grown by half with large targets
+pub fn padding(padding: f32) -> f32 {
+    if large_targets() {
+        (padding * 1.5).max(padding + 4.0)
+    } else {
+        padding
+    }
+}
+
+/// The size of an icon in a control
+pub fn icon_size(size: f32) -> f32 {
+    if large_targets() { size * 1.25 } else { size }
+}
+
+/// A secondary text or icon color, darkened with large targets so it stands out from the
+/// light backgrounds
+pub fn contrast(color: Color) -> Color {
+    if large_targets() {
+        Color {
+            r: color.r * 0.45,
+            g: color.g * 0.45,
+            b: color.b * 0.45,
+            ..color
+        }
+    } else {
+        color
+    }
+}
+
+/// Names a control that only shows an icon. iced doesn't expose widgets to screen readers
+/// yet, so the name is shown while the pointer is over the control.
+pub fn labeled<'a, Message: 'a>(
+    control: impl Into<Element<'a, Message>>,
+    name: String,
+    position: tooltip::Position,
+) -> Element<'a, Message> {
+    tooltip(
+        control,
+        container(text(name).size(12))
+            .padding([4, 8])
+            .style(container::rounded_box),
+        position,
+    )
+    .into()
+}
@@ -0,0 +1,131 @@
+use crate::i18n::{tr, tr_args};
+use crate::storage::audit_log::{AuditEntry, AuditOperation};
+use iced::widget::button::Status;
+use iced::widget::container::Style;
+use iced::widget::{button, column, container, row, scrollable, space, text};
+use iced::{Background, Border, Color, Element, Font, Length, Shadow, Vector};
+
+/// Entries listed, older ones stay in the file
+const MAX_ENTRIES: usize = 500;
+
+const MUTED_COLOR: Color = Color::from_rgb(0.5, 0.5, 0.5);
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Close,
+}
+
+/// The collections, requests and environments created, renamed and deleted, the latest first
+pub fn view(entries: &[AuditEntry]) -> Element<'_, Message> {
+    let content: Element<'_, Message> = if entries.is_empty() {
+        text(tr("audit-empty"))
+            .size(14)
+            .color(MUTED_COLOR)
+            .into()
+    } else {
+        let mut list = column![].spacing(6);
+        for entry in entries.iter().take(MAX_ENTRIES) {
+            list = list.push(entry_row(entry));
+        }
+        if entries.len() > MAX_ENTRIES {
+            list = list.push(
+                text(tr_args(
+                    "audit-older",
+                    &[("count", &(entries.len() - MAX_ENTRIES))],
+                ))
+                .size(12)
+                .color(MUTED_COLOR),
+            );
+        }
+
+        scrollable(list).height(Length::Fill).into()
+    };
+
+    let footer = row![
+        space().width(Length::Fill),
+        button(text(tr("close")).size(16))
+            .on_press(Message::Close)
+            .padding(10)
+            .style(audit_button_style),
+    ];
+
+    container(
+        column![
+            text(tr("audit-title")).size(18),
+            container(content).height(Length::Fill),
+            footer,
+        ]
+        .spacing(15),
+    )
+    .padding(20)
+    .height(Length::Fill)
+    .style(|_theme| Style {
+        background: Some(Background::Color(Color::WHITE)),
+        border: Border {
+            color: Color::from_rgb(0.7, 0.7, 0.7),
+            width: 1.0,
+            radius: 8.0.into(),
+        },
+        shadow: Shadow {
+            color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+            offset: Vector::new(0.0, 4.0),
+            blur_radius: 10.0,
+        },
+        ..Style::default()
+    })
+    .into()
+}
+
+fn entry_row(entry: &AuditEntry) -> Element<'_, Message> {
+    let when = chrono::DateTime::parse_from_rfc3339(&entry.timestamp)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|_| entry.timestamp.clone());
+    let color = match entry.operation {
+        AuditOperation::Create => Color::from_rgb(0.0, 0.6, 0.3),
+        AuditOperation::Rename => Color::from_rgb(0.3, 0.5, 0.9),
+        AuditOperation::Delete => Color::from_rgb(0.82, 0.06, 0.22),
+    };
+
+    row![
+        text(when)
+            .size(12)
+            .font(Font::MONOSPACE)
+            .color(MUTED_COLOR)
+            .width(Length::Fixed(130.0)),
+        text(&entry.user)
+            .size(12)
+            .color(MUTED_COLOR)
+            .width(Length::Fixed(100.0)),
+        text(entry.summary())
+            .size(13)
+            .color(color)
+            .width(Length::Fill),
+    ]
+    .spacing(8)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+fn audit_button_style(_theme: &iced::Theme, status: Status) -> button::Style {
+    let base = button::Style {
+        text_color: Color::from_rgb(0.2, 0.2, 0.2),
+        border: Border {
+            color: Color::from_rgb(0.8, 0.8, 0.8),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..button::Style::default()
+    };
+
+    match status {
+        Status::Hovered => button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 0.9))),
+            ..base
+        },
+        _ => base,
+    }
+}
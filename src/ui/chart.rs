@@ -0,0 +1,268 @@
+use crate::chart::DataPoint;
+use iced::alignment::Vertical;
+use iced::widget::canvas::{self, Canvas, Geometry, Path, Stroke};
+use iced::widget::text::Alignment;
+use iced::{Color, Element, Length, Point, Rectangle, Size, mouse};
+
+/// Room left of the plot for the value labels
+const LEFT_MARGIN: f32 = 60.0;
+/// Room under the plot for the first and last labels
+const BOTTOM_MARGIN: f32 = 20.0;
+const TOP_MARGIN: f32 = 10.0;
+
+const AXIS_COLOR: Color = Color::from_rgb(0.75, 0.75, 0.75);
+const LABEL_COLOR: Color = Color::from_rgb(0.45, 0.45, 0.45);
+const SERIES_COLOR: Color = Color::from_rgb(0.3, 0.5, 0.9);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChartKind {
+    #[default]
+    Line,
+    Bar,
+}
+
+impl ChartKind {
+    pub const ALL: [ChartKind; 2] = [ChartKind::Line, ChartKind::Bar];
+}
+
+impl std::fmt::Display for ChartKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChartKind::Line => write!(f, "Line"),
+            ChartKind::Bar => write!(f, "Bar"),
+        }
+    }
+}
+
+/// Line or bar chart of a series of values, showing the value under the cursor
+#[derive(Debug)]
+pub struct Chart {
+    points: Vec<DataPoint>,
+    kind: ChartKind,
+}
+
+impl Chart {
+    /// `points` can't be empty
+    pub fn new(points: Vec<DataPoint>, kind: ChartKind) -> Self {
+        Self { points, kind }
+    }
+
+    pub fn view<'a, Message: 'a>(self) -> Element<'a, Message> {
+        Canvas::new(self)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    /// Lowest and highest value shown, the baseline of bars being 0
+    fn range(&self) -> (f64, f64) {
+        let values = self.points.iter().map(|point| point.value);
+        let mut low = values.clone().fold(f64::INFINITY, f64::min);
+        let mut high = values.fold(f64::NEG_INFINITY, f64::max);
+        if self.kind == ChartKind::Bar {
+            low = low.min(0.0);
+            high = high.max(0.0);
+        }
+        if low == high {
+            low -= 1.0;
+            high += 1.0;
+        }
+        (low, high)
+    }
+}
+
+impl<Message> canvas::Program<Message> for Chart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &iced::Renderer,
+        _theme: &iced::Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let plot = Rectangle {
+            x: LEFT_MARGIN,
+            y: TOP_MARGIN,
+            width: (bounds.width - LEFT_MARGIN - 10.0).max(1.0),
+            height: (bounds.height - TOP_MARGIN - BOTTOM_MARGIN).max(1.0),
+        };
+        let (low, high) = self.range();
+        let y = |value: f64| plot.y + plot.height * (1.0 - ((value - low) / (high - low)) as f32);
+        let count = self.points.len();
+        // Bars are centered in equal slots, line points spread from edge to edge
+        let slot = plot.width / count as f32;
+        let x = |index: usize| match self.kind {
+            ChartKind::Bar => plot.x + slot * (index as f32 + 0.5),
+            ChartKind::Line if count > 1 => plot.x + plot.width * index as f32 / (count - 1) as f32,
+            ChartKind::Line => plot.x + plot.width / 2.0,
+        };
+
+        let axis = Stroke::default().with_width(1.0).with_color(AXIS_COLOR);
+        frame.stroke(
+            &Path::line(
+                Point::new(plot.x, plot.y),
+                Point::new(plot.x, plot.y + plot.height),
+            ),
+            axis,
+        );
+        for value in [low, (low + high) / 2.0, high] {
+            let at = y(value);
+            frame.stroke(
+                &Path::line(Point::new(plot.x - 4.0, at), Point::new(plot.x, at)),
+                axis,
+            );
+            frame.fill_text(label(
+                format_value(value),
+                Point::new(plot.x - 6.0, at),
+                Alignment::Right,
+                Vertical::Center,
+            ));
+        }
+        let baseline = y(low.max(0.0).min(high));
+        frame.stroke(
+            &Path::line(
+                Point::new(plot.x, baseline),
+                Point::new(plot.x + plot.width, baseline),
+            ),
+            axis,
+        );
+
+        let bottom = plot.y + plot.height + 4.0;
+        let first = self.points[0]
+            .label
+            .clone()
+            .unwrap_or_else(|| "0".to_string());
+        frame.fill_text(label(
+            first,
+            Point::new(plot.x, bottom),
+            Alignment::Left,
+            Vertical::Top,
+        ));
+        if count > 1 {
+            let last = self.points[count - 1]
+                .label
+                .clone()
+                .unwrap_or_else(|| (count - 1).to_string());
+            frame.fill_text(label(
+                last,
+                Point::new(plot.x + plot.width, bottom),
+                Alignment::Right,
+                Vertical::Top,
+            ));
+        }
+
+        match self.kind {
+            ChartKind::Line => {
+                let line = Path::new(|builder| {
+                    for (index, point) in self.points.iter().enumerate() {
+                        let at = Point::new(x(index), y(point.value));
+                        if index == 0 {
+                            builder.move_to(at);
+                        } else {
+                            builder.line_to(at);
+                        }
+                    }
+                });
+                frame.stroke(
+                    &line,
+                    Stroke::default().with_width(2.0).with_color(SERIES_COLOR),
+                );
+                if count <= 60 {
+                    for (index, point) in self.points.iter().enumerate() {
+                        frame.fill(
+                            &Path::circle(Point::new(x(index), y(point.value)), 2.5),
+                            SERIES_COLOR,
+                        );
+                    }
+                }
+            }
+            ChartKind::Bar => {
+                let width = (slot * 0.8).max(1.0);
+                for (index, point) in self.points.iter().enumerate() {
+                    let top = y(point.value).min(baseline);
+                    let height = (y(point.value) - baseline).abs().max(1.0);
+                    frame.fill_rectangle(
+                        Point::new(x(index) - width / 2.0, top),
+                        Size::new(width, height),
+                        SERIES_COLOR,
+                    );
+                }
+            }
+        }
+
+        // Value of the point closest to the cursor
+        if let Some(position) = cursor.position_in(bounds)
+            && plot.contains(position)
+        {
+            let index = match self.kind {
+                ChartKind::Bar => ((position.x - plot.x) / slot) as usize,
+                ChartKind::Line if count > 1 => {
+                    ((position.x - plot.x) / plot.width * (count - 1) as f32).round() as usize
+                }
+                ChartKind::Line => 0,
+            }
+            .min(count - 1);
+            let point = &self.points[index];
+            let at = Point::new(x(index), y(point.value));
+
+            frame.stroke(
+                &Path::line(
+                    Point::new(at.x, plot.y),
+                    Point::new(at.x, plot.y + plot.height),
+                ),
+                Stroke::default().with_width(1.0).with_color(AXIS_COLOR),
+            );
+            let description = match &point.label {
+                Some(name) => format!("{}: {}", name, format_value(point.value)),
+                None => format!("#{}: {}", index, format_value(point.value)),
+            };
+            let align = if at.x > plot.x + plot.width / 2.0 {
+                Alignment::Right
+            } else {
+                Alignment::Left
+            };
+            let offset = if align == Alignment::Right { -6.0 } else { 6.0 };
+            frame.fill_text(canvas::Text {
+                color: Color::BLACK,
+                size: 13.0.into(),
+                ..label(
+                    description,
+                    Point::new(at.x + offset, plot.y),
+                    align,
+                    Vertical::Top,
+                )
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+fn label(content: String, position: Point, align_x: Alignment, align_y: Vertical) -> canvas::Text {
+    canvas::Text {
+        content,
+        position,
+        color: LABEL_COLOR,
+        size: 11.0.into(),
+        align_x,
+        align_y,
+        ..canvas::Text::default()
+    }
+}
+
+/// Short form of an axis or point value
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else if value.abs() >= 1000.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.3}", value)
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
@@ -1,48 +1,121 @@
-use crate::types::{HttpMethod, RenameTarget, RequestCollection, RequestConfig};
-use crate::ui::{IconName, icon};
+use crate::i18n::{tr, tr_args};
+use crate::monitor::MonitorStatus;
+use crate::types::{
+    CollectionColor, CollectionIcon, CollectionSort, HttpMethod, RenameTarget, RequestCollection, RequestConfig,
+    RunConfiguration, RunRecord, find_collection, find_request, new_id,
+};
+use crate::ui::{IconName, accessibility, icon, method_color, method_short_label};
 use iced::widget::button::Status;
 use iced::widget::container::Style;
 use iced::widget::{button, column, container, row, scrollable, space, text};
 use iced::{Background, Border, Color, Element, Length, Shadow, Theme, Vector};
 use iced_aw::ContextMenu;
 use log::info;
+use std::collections::HashMap;
 use std::time::Instant;
 
+const FOCUS_RING_COLOR: Color = Color::from_rgb(0.31, 0.27, 0.9); // #4f46e5
+
 #[derive(Debug, Clone)]
 pub enum Action {
-    ToggleCollection(usize),
-    SelectRequestConfig(usize, usize),
-    SaveRequestToCollection(RequestConfig),
+    ToggleCollection(String),
+    SelectRequestConfig(String),
+    SaveRequestToCollection(String, RequestConfig), // (collection id, new request)
     SaveNewCollection(RequestCollection),
-    SendRequest(usize, usize, Instant),
-    DuplicateRequest(usize, usize),
-    DeleteRequest(usize, usize),
-    RenameRequest(usize, usize),
-    RenameCollection(usize),
-    DeleteCollection(usize),
+    SendRequest(String, Instant),
+    CancelRequest(String),
+    DuplicateRequest(String),
+    DeleteRequest(String),
+    RenameRequest(String),
+    RenameCollection(String),
+    DeleteCollection(String),
+    SetCollectionAppearance {
+        collection_id: String,
+        color: Option<CollectionColor>,
+        icon: Option<CollectionIcon>,
+    },
+    SetCollectionSort(String, CollectionSort),
+    ImportFromUrl,
+    OpenRemoteSync,
+    ShowCollectionStats(String),
+    RunCollection(String),
+    ExportCiBundle(String),
+    CopyRequestAsCurl(String, bool), // (request id, reveal secrets)
+    ExportRequest(String),
+    CopyLink(String),
+    TogglePinned(String), // Pinned requests are listed in the tray menu
+    Focus(iced::widget::Id),
     None,
 }
 
+// Collections and requests are referred to by id, so a message still points at the same
+// one after the sidebar was reordered
 #[derive(Debug, Clone)]
 pub enum Message {
-    CollectionToggled(usize),
-    RequestSelected(usize, usize),
+    CollectionToggled(String),
+    RequestSelected(String),
 
-    ShowRenameModal(usize, usize), // (collection_index, request_index)
+    ShowRenameModal(String),
     // HideRenameModal,
     // RenameInputChanged(String),
     // ConfirmRename,
-    AddHttpRequest(usize),
-    DeleteFolder(usize),
-    AddFolder(usize),
-    RenameFolder(usize),
+    AddHttpRequest(String),
+    DeleteFolder(String),
+    AddFolder,
+    RenameFolder(String),
+    SetFolderColor(String, Option<CollectionColor>),
+    SetFolderIcon(String, Option<CollectionIcon>),
+    SetFolderSort(String, CollectionSort),
+    ImportFromUrl,
+    OpenRemoteSync,
+    ShowStatistics(String),
+    RunCollection(String),
+    ExportCiBundle(String),
+
+    // New request dialog
+    NewRequestNameChanged(String),
+    NewRequestMethodChanged(HttpMethod),
+    NewRequestUrlChanged(String),
+    NewRequestFolderChanged(FolderOption),
+    NewRequestNameSubmitted,
+    ConfirmNewRequest,
+    CancelNewRequest,
 
     // Request context menu actions
-    SendRequestFromMenu(usize, usize),
-    CopyRequestAsCurl(usize, usize),
-    RenameRequest(usize, usize),
-    DuplicateRequest(usize, usize),
-    DeleteRequest(usize, usize),
+    SendRequestFromMenu(String),
+    CancelRequestFromMenu(String),
+    CopyRequestAsCurl(String),
+    CopyRequestAsCurlWithSecrets(String),
+    ExportRequest(String),
+    CopyLink(String),
+    TogglePinned(String),
+    RenameRequest(String),
+    DuplicateRequest(String),
+    DeleteRequest(String),
+
+    // Keyboard navigation
+    FocusSidebar,
+    BlurSidebar,
+    Navigate(Navigation),
+}
+
+/// A row of the sidebar, for keyboard navigation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SidebarItem {
+    Collection(String),
+    Request(String, String), // (collection id, request id)
+}
+
+/// What a key does while the sidebar has keyboard focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Navigation {
+    Up,
+    Down,
+    Collapse,
+    Expand,
+    Activate,
+    OpenMenu,
+    Back,
 }
 
 #[derive(Debug, Clone)]
@@ -51,12 +124,43 @@ pub struct CollectionPanel {
 
     // Double-click detection state
     pub last_click_time: Option<std::time::Instant>,
-    pub last_click_target: Option<(usize, usize)>, // (collection_index, request_index)
+    pub last_click_target: Option<String>, // Request id
 
     // Rename modal state
     pub show_rename_modal: bool,
     pub rename_input: String,
     pub rename_target: Option<RenameTarget>, // What is being renamed
+
+    // New request dialog, open while Some
+    pub new_request: Option<NewRequestDraft>,
+    pub new_request_name_id: iced::widget::Id,
+    pub new_request_url_id: iced::widget::Id,
+
+    // Keyboard navigation: the focused row, and the highlighted entry of its menu when open
+    pub keyboard_cursor: Option<SidebarItem>,
+    pub keyboard_menu: Option<usize>,
+}
+
+/// Fields of the new request dialog
+#[derive(Debug, Clone)]
+pub struct NewRequestDraft {
+    pub collection_id: String,
+    pub name: String,
+    pub method: HttpMethod,
+    pub url: String,
+}
+
+/// A collection in the new request dialog's folder picker
+#[derive(Debug, Clone, PartialEq)]
+pub struct FolderOption {
+    pub id: String,
+    pub name: String,
+}
+
+impl std::fmt::Display for FolderOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
 }
 
 impl CollectionPanel {
@@ -69,17 +173,30 @@ impl CollectionPanel {
             show_rename_modal: false,
             rename_input: String::new(),
             rename_target: None,
+
+            new_request: None,
+            new_request_name_id: iced::widget::Id::unique(),
+            new_request_url_id: iced::widget::Id::unique(),
+
+            keyboard_cursor: None,
+            keyboard_menu: None,
         }
     }
 
     pub fn view<'a>(
         &'a self,
         collections: &'a [RequestCollection],
-        last_opened_request: Option<(usize, usize)>,
+        last_opened_request: Option<&'a str>,
+        in_flight: &[&str],
+        drafts: &[&str],
+        pinned: &[&str],
+        monitors: &HashMap<String, MonitorStatus>,
     ) -> Element<'a, Message> {
         let mut content = column![];
 
-        for (collection_index, collection) in collections.iter().enumerate() {
+        for collection in collections {
+            let item = SidebarItem::Collection(collection.id.clone());
+            let is_focused = self.keyboard_cursor.as_ref() == Some(&item);
             let collection_header = button(
                 row![
                     icon(if collection.expanded {
@@ -89,86 +206,88 @@ impl CollectionPanel {
                     })
                     .size(12),
                     space().width(5),
+                    collection_icon(collection),
                     text(&collection.name).size(14)
                 ]
                 .align_y(iced::Alignment::Center),
             )
-            .on_press(Message::CollectionToggled(collection_index))
+            .on_press(Message::CollectionToggled(collection.id.clone()))
+            .padding([accessibility::padding(5.0), 10.0])
             .style(move |_theme, status| {
                 let base = button::Style::default();
-                match status {
+                let style = match status {
                     Status::Hovered => button::Style {
                         background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 0.9))),
                         ..base
                     },
                     _ => base,
-                }
+                };
+                with_focus_ring(style, is_focused)
             })
             .width(Length::Fill);
 
             // Wrap the collection header with ContextMenu
             let collection_with_context_menu = ContextMenu::new(collection_header, move || {
-                container(
-                    column![
-                        button(text("Add Request"))
-                            .on_press(Message::AddHttpRequest(collection_index))
-                            .width(Length::Fill)
-                            .style(context_menu_item_style()),
-                        button(text("Add Folder"))
-                            .on_press(Message::AddFolder(collection_index))
-                            .width(Length::Fill)
-                            .style(context_menu_item_style()),
-                        button(text("Rename"))
-                            .on_press(Message::RenameFolder(collection_index))
-                            .width(Length::Fill)
-                            .style(context_menu_item_style()),
-                        button(text("Delete"))
-                            .on_press(Message::DeleteFolder(collection_index))
-                            .width(Length::Fill)
-                            .style(context_menu_item_style()),
-                    ]
-                    .spacing(2),
-                )
-                .width(Length::Fixed(150.0))
-                .style(|_theme| Style {
-                    background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 0.9))),
-                    border: Border {
-                        color: Color::from_rgb(0.8, 0.8, 0.8),
-                        width: 1.0,
-                        radius: 4.0.into(),
-                    },
-                    shadow: Shadow {
-                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.1),
-                        offset: Vector::new(2.0, 2.0),
-                        blur_radius: 4.0,
-                    },
-                    ..Style::default()
-                })
+                let mut items = column![].spacing(2);
+                for (label, message) in collection_menu_entries(&collection.id) {
+                    items = items.push(menu_item(label, message, false));
+                }
+
+                container(items.extend([
+                    sort_options(&collection.id, collection.sort),
+                    color_picker(&collection.id),
+                    icon_picker(&collection.id),
+                ]))
+                .width(Length::Fixed(180.0))
+                .style(context_menu_style)
                 .padding(4)
                 .into()
             });
 
             content = content.push(collection_with_context_menu);
+            if is_focused && let Some(highlighted) = self.keyboard_menu {
+                content = content.push(keyboard_menu(
+                    menu_entries(&item, collections, in_flight, pinned),
+                    highlighted,
+                ));
+            }
 
             if collection.expanded {
-                for (request_index, request) in collection.requests.iter().enumerate() {
-                    let is_selected =
-                        last_opened_request == Some((collection_index, request_index));
+                for request_index in sorted_request_indices(collection) {
+                    let request = &collection.requests[request_index];
+                    let is_selected = last_opened_request == Some(request.id.as_str());
+                    let is_in_flight = in_flight.contains(&request.id.as_str());
+                    let is_pinned = pinned.contains(&request.id.as_str());
+                    let has_draft = drafts.contains(&request.id.as_str());
+                    let item = SidebarItem::Request(collection.id.clone(), request.id.clone());
+                    let is_focused = self.keyboard_cursor.as_ref() == Some(&item);
 
                     let request_button = button(
                         row![
                             space().width(20),
                             method_badge(&request.method),
-                            space().width(8),
-                            text(&request.name).size(12)
+                            space().width(4),
+                            text(&request.name).size(12),
+                            unsaved_marker(has_draft),
+                            space().width(Length::Fill),
+                            if is_in_flight {
+                                in_flight_badge()
+                            } else {
+                                run_sparkline(&request.run_history)
+                            },
+                            request
+                                .monitor
+                                .enabled
+                                .then(|| monitor_badge(monitors.get(&request.id))),
                         ]
                         .align_y(iced::Alignment::Center),
                     )
-                    .on_press(Message::RequestSelected(collection_index, request_index))
+                    .on_press(Message::RequestSelected(request.id.clone()))
+                    .padding([accessibility::padding(5.0), 10.0])
                     .style(move |_theme, status| {
                         let base = button::Style::default();
 
-                        match status {
+                        let style = match status {
                             Status::Pressed => {
                                 if is_selected {
                                     button::Style {
@@ -215,72 +334,34 @@ impl CollectionPanel {
                                     base
                                 }
                             }
-                        }
+                        };
+                        with_focus_ring(style, is_focused)
                     })
                     .width(Length::Fill);
 
                     // Wrap the request button with ContextMenu
                     let request_with_context_menu = ContextMenu::new(request_button, move || {
-                        container(
-                            column![
-                                button(text("Send Request"))
-                                    .on_press(Message::SendRequestFromMenu(
-                                        collection_index,
-                                        request_index
-                                    ))
-                                    .width(Length::Fill)
-                                    .style(context_menu_item_style()),
-                                button(text("Copy as cURL"))
-                                    .on_press(Message::CopyRequestAsCurl(
-                                        collection_index,
-                                        request_index
-                                    ))
-                                    .width(Length::Fill)
-                                    .style(context_menu_item_style()),
-                                button(text("Rename"))
-                                    .on_press(Message::RenameRequest(
-                                        collection_index,
-                                        request_index
-                                    ))
-                                    .width(Length::Fill)
-                                    .style(context_menu_item_style()),
-                                button(text("Duplicate"))
-                                    .on_press(Message::DuplicateRequest(
-                                        collection_index,
-                                        request_index
-                                    ))
-                                    .width(Length::Fill)
-                                    .style(context_menu_item_style()),
-                                button(text("Delete"))
-                                    .on_press(Message::DeleteRequest(
-                                        collection_index,
-                                        request_index
-                                    ))
-                                    .width(Length::Fill)
-                                    .style(context_menu_item_style()),
-                            ]
-                            .spacing(2),
-                        )
-                        .width(Length::Fixed(150.0))
-                        .style(|_theme| Style {
-                            background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 0.9))),
-                            border: Border {
-                                color: Color::from_rgb(0.8, 0.8, 0.8),
-                                width: 1.0,
-                                radius: 4.0.into(),
-                            },
-                            shadow: Shadow {
-                                color: Color::from_rgba(0.0, 0.0, 0.0, 0.1),
-                                offset: Vector::new(2.0, 2.0),
-                                blur_radius: 4.0,
-                            },
-                            ..Style::default()
-                        })
-                        .padding(4)
-                        .into()
+                        let mut items = column![].spacing(2);
+                        for (label, message) in
+                            request_menu_entries(&request.id, is_in_flight, is_pinned)
+                        {
+                            items = items.push(menu_item(label, message, false));
+                        }
+
+                        container(items)
+                            .width(Length::Fixed(150.0))
+                            .style(context_menu_style)
+                            .padding(4)
+                            .into()
                     });
 
                     content = content.push(request_with_context_menu);
+                    if is_focused && let Some(highlighted) = self.keyboard_menu {
+                        content = content.push(keyboard_menu(
+                            menu_entries(&item, collections, in_flight, pinned),
+                            highlighted,
+                        ));
+                    }
                 }
             }
         }
@@ -299,108 +380,485 @@ impl CollectionPanel {
         .into()
     }
 
-    pub fn update(&mut self, message: Message, collections: &[RequestCollection]) -> Action {
+    pub fn update(
+        &mut self,
+        message: Message,
+        collections: &[RequestCollection],
+        in_flight: &[&str],
+        pinned: &[&str],
+    ) -> Action {
+        // An entry of the keyboard menu clicked with the mouse closes it like any other menu
+        if self.keyboard_menu.is_some() && !matches!(message, Message::Navigate(_)) {
+            self.blur();
+        }
+
         match message {
-            Message::CollectionToggled(index) => Action::ToggleCollection(index),
-            Message::RequestSelected(collection_index, request_index) => {
-                info!("===select request1: {:?}", collection_index);
-                if let Some(collection) = collections.get(collection_index) {
-                    if let Some(request) = collection.requests.get(request_index) {
-                        let now = std::time::Instant::now();
-                        let current_target = (collection_index, request_index);
-
-                        // Check for double-click (within 500ms and same target)
-                        let is_double_click = if let (Some(last_time), Some(last_target)) =
-                            (self.last_click_time, self.last_click_target)
-                        {
-                            last_target == current_target
-                                && now.duration_since(last_time).as_millis() < 500
-                        } else {
-                            false
-                        };
+            Message::CollectionToggled(collection_id) => {
+                self.blur();
+                Action::ToggleCollection(collection_id)
+            }
+            Message::RequestSelected(request_id) => {
+                self.blur();
+                info!("===select request1: {:?}", request_id);
+                if let Some(request) = find_request(collections, &request_id) {
+                    let now = std::time::Instant::now();
 
-                        // Update click tracking
-                        self.last_click_time = Some(now);
-                        self.last_click_target = Some(current_target);
+                    // Check for double-click (within 500ms and same target)
+                    let is_double_click = if let (Some(last_time), Some(last_target)) =
+                        (self.last_click_time, &self.last_click_target)
+                    {
+                        *last_target == request_id
+                            && now.duration_since(last_time).as_millis() < 500
+                    } else {
+                        false
+                    };
 
-                        if is_double_click {
-                            self.show_rename_modal = true;
-                            self.rename_target =
-                                Some(RenameTarget::Request(collection_index, request_index));
-                            self.rename_input = request.name.clone();
+                    // Update click tracking
+                    self.last_click_time = Some(now);
+                    self.last_click_target = Some(request_id.clone());
+
+                    if is_double_click {
+                        self.show_rename_modal = true;
+                        self.rename_target = Some(RenameTarget::Request(request_id));
+                        self.rename_input = request.name.clone();
 
-                            Action::None
-                        } else {
-                            // info!("===select request3");
-                            return Action::SelectRequestConfig(collection_index, request_index);
-                        }
-                    } else {
                         Action::None
+                    } else {
+                        // info!("===select request3");
+                        Action::SelectRequestConfig(request_id)
                     }
                 } else {
                     Action::None
                 }
             }
-            Message::AddHttpRequest(collection_index) => {
-                if let Some(collection) = collections.get(collection_index) {
-                    let mut new_request = RequestConfig::default();
+            Message::AddHttpRequest(collection_id) => {
+                if find_collection(collections, &collection_id).is_none() {
+                    return Action::None;
+                }
 
-                    new_request.collection_index = collection_index;
-                    let len = collection.requests.len();
-                    new_request.name = format!("New Request {}", collection.requests.len() + 1);
-                    new_request.request_index = len;
+                self.new_request = Some(NewRequestDraft {
+                    collection_id,
+                    name: String::new(),
+                    method: HttpMethod::GET,
+                    url: String::new(),
+                });
 
-                    Action::SaveRequestToCollection(new_request)
-                } else {
-                    Action::None
+                Action::Focus(self.new_request_name_id.clone())
+            }
+            Message::NewRequestNameChanged(name) => {
+                if let Some(draft) = &mut self.new_request {
+                    draft.name = name;
+                }
+                Action::None
+            }
+            Message::NewRequestMethodChanged(method) => {
+                if let Some(draft) = &mut self.new_request {
+                    draft.method = method;
+                }
+                Action::None
+            }
+            Message::NewRequestUrlChanged(url) => {
+                if let Some(draft) = &mut self.new_request {
+                    // Quick entry: "POST https://..." picks the method and keeps the URL
+                    match url.split_once(' ') {
+                        Some((method, rest)) if HttpMethod::parse(method).is_some() => {
+                            draft.method = HttpMethod::parse(method).unwrap_or(HttpMethod::GET);
+                            draft.url = rest.trim_start().to_string();
+                        }
+                        _ => draft.url = url,
+                    }
                 }
+                Action::None
             }
-            Message::DeleteFolder(collection_index) => Action::DeleteCollection(collection_index),
-            Message::AddFolder(collection_index) => {
+            Message::NewRequestFolderChanged(folder) => {
+                if let Some(draft) = &mut self.new_request {
+                    draft.collection_id = folder.id;
+                }
+                Action::None
+            }
+            Message::NewRequestNameSubmitted => Action::Focus(self.new_request_url_id.clone()),
+            Message::ConfirmNewRequest => {
+                let Some(draft) = self.new_request.take() else {
+                    return Action::None;
+                };
+                let Some(collection) = find_collection(collections, &draft.collection_id) else {
+                    return Action::None;
+                };
+
+                let name = match draft.name.trim() {
+                    "" => default_request_name(&draft.url, collection.requests.len() + 1),
+                    name => name.to_string(),
+                };
+
+                Action::SaveRequestToCollection(
+                    draft.collection_id,
+                    RequestConfig {
+                        name,
+                        method: draft.method,
+                        url: draft.url.trim().to_string(),
+                        ..RequestConfig::default()
+                    },
+                )
+            }
+            Message::CancelNewRequest => {
+                self.new_request = None;
+                Action::None
+            }
+            Message::DeleteFolder(collection_id) => Action::DeleteCollection(collection_id),
+            Message::ImportFromUrl => Action::ImportFromUrl,
+            Message::OpenRemoteSync => Action::OpenRemoteSync,
+            Message::ShowStatistics(collection_id) => Action::ShowCollectionStats(collection_id),
+            Message::RunCollection(collection_id) => Action::RunCollection(collection_id),
+            Message::ExportCiBundle(collection_id) => Action::ExportCiBundle(collection_id),
+            Message::AddFolder => {
                 let new_collection = RequestCollection {
-                    folder_name: format!("{:0>4}", collection_index),
+                    id: new_id(),
+                    folder_name: next_folder_name(collections),
                     name: format!("New Collection {}", collections.len() + 1),
                     requests: vec![],
                     expanded: true,
+                    color: None,
+                    icon: None,
+                    sort: CollectionSort::default(),
+                    run_configuration: RunConfiguration::default(),
                 };
 
                 Action::SaveNewCollection(new_collection)
             }
-            Message::RenameFolder(collection_index) => Action::RenameCollection(collection_index),
-            Message::SendRequestFromMenu(collection_index, request_index) => {
-                Action::SendRequest(collection_index, request_index, Instant::now())
+            Message::RenameFolder(collection_id) => Action::RenameCollection(collection_id),
+            Message::SetFolderColor(collection_id, color) => {
+                match find_collection(collections, &collection_id) {
+                    Some(collection) => Action::SetCollectionAppearance {
+                        icon: collection.icon,
+                        collection_id,
+                        color,
+                    },
+                    None => Action::None,
+                }
+            }
+            Message::SetFolderSort(collection_id, sort) => {
+                Action::SetCollectionSort(collection_id, sort)
             }
-            Message::CopyRequestAsCurl(collection_index, request_index) => {
-                if let Some(collection) = collections.get(collection_index) {
-                    if let Some(request) = collection.requests.get(request_index) {
-                        let curl_command = crate::http::generate_curl_command(request);
-                        // TODO: In a real app, you'd copy to clipboard here
-                        info!("Curl command: {}", curl_command);
-                    }
+            Message::SetFolderIcon(collection_id, icon) => {
+                match find_collection(collections, &collection_id) {
+                    Some(collection) => Action::SetCollectionAppearance {
+                        color: collection.color,
+                        collection_id,
+                        icon,
+                    },
+                    None => Action::None,
+                }
+            }
+            Message::SendRequestFromMenu(request_id) => {
+                Action::SendRequest(request_id, Instant::now())
+            }
+            Message::CancelRequestFromMenu(request_id) => Action::CancelRequest(request_id),
+            Message::CopyRequestAsCurl(request_id) => Action::CopyRequestAsCurl(request_id, false),
+            Message::CopyRequestAsCurlWithSecrets(request_id) => {
+                Action::CopyRequestAsCurl(request_id, true)
+            }
+            Message::ExportRequest(request_id) => Action::ExportRequest(request_id),
+            Message::CopyLink(request_id) => Action::CopyLink(request_id),
+            Message::TogglePinned(request_id) => Action::TogglePinned(request_id),
+            Message::RenameRequest(request_id) => Action::RenameRequest(request_id),
+            Message::DuplicateRequest(request_id) => Action::DuplicateRequest(request_id),
+            Message::DeleteRequest(request_id) => Action::DeleteRequest(request_id),
+            Message::ShowRenameModal(request_id) => {
+                if let Some(request) = find_request(collections, &request_id) {
+                    self.show_rename_modal = true;
+                    self.rename_input = request.name.clone();
+                    self.rename_target = Some(RenameTarget::Request(request_id));
                 }
                 Action::None
             }
-            Message::RenameRequest(collection_index, request_index) => {
-                Action::RenameRequest(collection_index, request_index)
+            Message::FocusSidebar => {
+                let items = visible_items(collections);
+                if !self
+                    .keyboard_cursor
+                    .as_ref()
+                    .is_some_and(|item| items.contains(item))
+                {
+                    self.keyboard_cursor = items.first().cloned();
+                }
+                Action::None
             }
-            Message::DuplicateRequest(collection_index, request_index) => {
-                Action::DuplicateRequest(collection_index, request_index)
+            Message::BlurSidebar => {
+                self.blur();
+                Action::None
             }
-            Message::DeleteRequest(collection_index, request_index) => {
-                Action::DeleteRequest(collection_index, request_index)
+            Message::Navigate(navigation) => {
+                self.navigate(navigation, collections, in_flight, pinned)
             }
-            Message::ShowRenameModal(collection_index, request_index) => {
-                if let Some(collection) = collections.get(collection_index) {
-                    if let Some(request) = collection.requests.get(request_index) {
-                        self.show_rename_modal = true;
-                        self.rename_input = request.name.clone();
-                        self.rename_target =
-                            Some(RenameTarget::Request(collection_index, request_index));
+        }
+    }
+
+    /// Whether the sidebar takes the arrow, Enter and Space keys
+    pub fn has_keyboard_focus(&self) -> bool {
+        self.keyboard_cursor.is_some()
+    }
+
+    fn blur(&mut self) {
+        self.keyboard_cursor = None;
+        self.keyboard_menu = None;
+    }
+
+    /// Moves through the rows like a tree: Up/Down between rows, Left/Right to collapse and
+    /// expand, Enter/Space to open, and through the entries of the row's menu while it's open
+    fn navigate(
+        &mut self,
+        navigation: Navigation,
+        collections: &[RequestCollection],
+        in_flight: &[&str],
+        pinned: &[&str],
+    ) -> Action {
+        let items = visible_items(collections);
+        let Some(cursor) = self
+            .keyboard_cursor
+            .take()
+            .filter(|item| items.contains(item))
+            .or_else(|| items.first().cloned())
+        else {
+            self.blur();
+            return Action::None;
+        };
+        self.keyboard_cursor = Some(cursor.clone());
+
+        if let Some(highlighted) = self.keyboard_menu {
+            let entries = menu_entries(&cursor, collections, in_flight, pinned);
+            match navigation {
+                Navigation::Up => {
+                    self.keyboard_menu =
+                        Some(highlighted.checked_sub(1).unwrap_or(entries.len() - 1));
+                }
+                Navigation::Down => self.keyboard_menu = Some((highlighted + 1) % entries.len()),
+                Navigation::Activate => {
+                    // The entry may open a dialog, which then gets the keyboard
+                    self.blur();
+                    if let Some((_, message)) = entries.into_iter().nth(highlighted) {
+                        return self.update(message, collections, in_flight, pinned);
                     }
                 }
-                Action::None
+                Navigation::OpenMenu | Navigation::Back => self.keyboard_menu = None,
+                Navigation::Collapse | Navigation::Expand => {}
             }
+            return Action::None;
         }
+
+        let position = items.iter().position(|item| *item == cursor).unwrap_or(0);
+        let next = items.get(position + 1).cloned();
+        let is_expanded = |collection_id: &str| {
+            find_collection(collections, collection_id).is_some_and(|collection| collection.expanded)
+        };
+        match (navigation, cursor) {
+            (Navigation::Up, _) => {
+                self.keyboard_cursor = Some(items[position.saturating_sub(1)].clone())
+            }
+            (Navigation::Down, cursor) => self.keyboard_cursor = next.or(Some(cursor)),
+            (Navigation::Collapse, SidebarItem::Request(collection_id, _)) => {
+                self.keyboard_cursor = Some(SidebarItem::Collection(collection_id));
+            }
+            (Navigation::Collapse, SidebarItem::Collection(collection_id))
+                if is_expanded(&collection_id) =>
+            {
+                return Action::ToggleCollection(collection_id);
+            }
+            (Navigation::Expand, SidebarItem::Collection(collection_id)) => {
+                if !is_expanded(&collection_id) {
+                    return Action::ToggleCollection(collection_id);
+                }
+                if let Some(request @ SidebarItem::Request(..)) = next {
+                    self.keyboard_cursor = Some(request);
+                }
+            }
+            (Navigation::Activate, SidebarItem::Collection(collection_id)) => {
+                return Action::ToggleCollection(collection_id);
+            }
+            (Navigation::Activate, SidebarItem::Request(_, request_id)) => {
+                return Action::SelectRequestConfig(request_id);
+            }
+            (Navigation::OpenMenu, _) => self.keyboard_menu = Some(0),
+            (Navigation::Back, _) => self.blur(),
+            _ => {}
+        }
+        Action::None
+    }
+}
+
+/// The rows of the sidebar from top to bottom: every collection, followed by its requests
+/// when it's expanded
+fn visible_items(collections: &[RequestCollection]) -> Vec<SidebarItem> {
+    let mut items = Vec::new();
+    for collection in collections {
+        items.push(SidebarItem::Collection(collection.id.clone()));
+        if collection.expanded {
+            items.extend(
+                sorted_request_indices(collection)
+                    .into_iter()
+                    .map(|request_index| {
+                        SidebarItem::Request(
+                            collection.id.clone(),
+                            collection.requests[request_index].id.clone(),
+                        )
+                    }),
+            );
+        }
+    }
+    items
+}
+
+/// Folder for a new collection, numbered after the last one as storage numbers them
+fn next_folder_name(collections: &[RequestCollection]) -> String {
+    let last = collections
+        .iter()
+        .filter_map(|collection| collection.folder_name.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0);
+    format!("{:04}", last + 1)
+}
+
+/// Entries of the collection context menu, besides the sort, colour and icon pickers
+fn collection_menu_entries(collection_id: &str) -> Vec<(String, Message)> {
+    let id = || collection_id.to_string();
+    vec![
+        (tr("collections-add-request"), Message::AddHttpRequest(id())),
+        (tr("collections-add-folder"), Message::AddFolder),
+        (tr("collections-import-url"), Message::ImportFromUrl),
+        (tr("collections-remote-sync"), Message::OpenRemoteSync),
+        (tr("collections-run"), Message::RunCollection(id())),
+        (
+            tr("collections-export-ci-bundle"),
+            Message::ExportCiBundle(id()),
+        ),
+        (tr("collections-statistics"), Message::ShowStatistics(id())),
+        (tr("rename"), Message::RenameFolder(id())),
+        (tr("delete"), Message::DeleteFolder(id())),
+    ]
+}
+
+fn request_menu_entries(
+    request_id: &str,
+    is_in_flight: bool,
+    is_pinned: bool,
+) -> Vec<(String, Message)> {
+    let id = || request_id.to_string();
+    vec![
+        if is_in_flight {
+            (
+                tr("collections-cancel-request"),
+                Message::CancelRequestFromMenu(id()),
+            )
+        } else {
+            (
+                tr("collections-send-request"),
+                Message::SendRequestFromMenu(id()),
+            )
+        },
+        (tr("collections-copy-curl"), Message::CopyRequestAsCurl(id())),
+        (
+            tr("collections-copy-curl-secrets"),
+            Message::CopyRequestAsCurlWithSecrets(id()),
+        ),
+        (
+            tr("collections-export-request"),
+            Message::ExportRequest(id()),
+        ),
+        (tr("collections-copy-link"), Message::CopyLink(id())),
+        (
+            tr(if is_pinned {
+                "collections-unpin-tray"
+            } else {
+                "collections-pin-tray"
+            }),
+            Message::TogglePinned(id()),
+        ),
+        (tr("rename"), Message::RenameRequest(id())),
+        (tr("collections-duplicate"), Message::DuplicateRequest(id())),
+        (tr("delete"), Message::DeleteRequest(id())),
+    ]
+}
+
+/// The menu of a row as opened with the keyboard. The collection one lists the sort orders
+/// as entries, its colour and icon swatches are left to the mouse.
+fn menu_entries(
+    item: &SidebarItem,
+    collections: &[RequestCollection],
+    in_flight: &[&str],
+    pinned: &[&str],
+) -> Vec<(String, Message)> {
+    match item {
+        SidebarItem::Collection(collection_id) => {
+            let mut entries = collection_menu_entries(collection_id);
+            entries.extend(CollectionSort::ALL.into_iter().map(|sort| {
+                (
+                    format!("{} {}", tr("collections-sort-by"), sort),
+                    Message::SetFolderSort(collection_id.clone(), sort),
+                )
+            }));
+            entries
+        }
+        SidebarItem::Request(_, request_id) => {
+            let request = find_request(collections, request_id);
+            let is_in_flight =
+                request.is_some_and(|request| in_flight.contains(&request.id.as_str()));
+            let is_pinned = request.is_some_and(|request| pinned.contains(&request.id.as_str()));
+            request_menu_entries(request_id, is_in_flight, is_pinned)
+        }
+    }
+}
+
+fn menu_item<'a>(label: String, message: Message, highlighted: bool) -> Element<'a, Message> {
+    button(text(label))
+        .on_press(message)
+        .padding([accessibility::padding(5.0), 10.0])
+        .width(Length::Fill)
+        .style(move |theme, status| {
+            with_focus_ring(context_menu_item_style()(theme, status), highlighted)
+        })
+        .into()
+}
+
+/// The menu of the focused row, drawn under it when opened with Shift+F10 or the menu key
+fn keyboard_menu<'a>(entries: Vec<(String, Message)>, highlighted: usize) -> Element<'a, Message> {
+    let mut items = column![].spacing(2);
+    for (index, (label, message)) in entries.into_iter().enumerate() {
+        items = items.push(menu_item(label, message, index == highlighted));
+    }
+
+    container(items)
+        .style(context_menu_style)
+        .padding(4)
+        .into()
+}
+
+fn context_menu_style(_theme: &Theme) -> Style {
+    Style {
+        background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 0.9))),
+        border: Border {
+            color: Color::from_rgb(0.8, 0.8, 0.8),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        shadow: Shadow {
+            color: Color::from_rgba(0.0, 0.0, 0.0, 0.1),
+            offset: Vector::new(2.0, 2.0),
+            blur_radius: 4.0,
+        },
+        ..Style::default()
+    }
+}
+
+/// Outlines the row or menu entry that has keyboard focus
+fn with_focus_ring(style: button::Style, focused: bool) -> button::Style {
+    if !focused {
+        return style;
+    }
+    button::Style {
+        border: Border {
+            color: FOCUS_RING_COLOR,
+            width: 2.0,
+            radius: 4.0.into(),
+        },
+        ..style
     }
 }
 
@@ -419,43 +877,308 @@ fn context_menu_item_style() -> impl Fn(&Theme, Status) -> button::Style {
         }
     }
 }
-fn method_badge<'a>(method: &'a HttpMethod) -> Element<'a, Message> {
-    let (color, text_color) = match method {
-        HttpMethod::GET => (Color::from_rgb(0.0, 0.8, 0.0), Color::WHITE),
-        HttpMethod::POST => (Color::from_rgb(1.0, 0.6, 0.0), Color::WHITE),
-        HttpMethod::PUT => (Color::from_rgb(0.0, 0.4, 0.8), Color::WHITE),
-        HttpMethod::DELETE => (Color::from_rgb(0.8, 0.0, 0.0), Color::WHITE),
-        HttpMethod::PATCH => (Color::from_rgb(0.6, 0.0, 0.8), Color::WHITE),
-        HttpMethod::HEAD => (Color::from_rgb(0.5, 0.5, 0.5), Color::WHITE),
-        HttpMethod::OPTIONS => (Color::from_rgb(0.3, 0.3, 0.3), Color::WHITE),
-    };
 
-    // Truncate method name to maximum 4 characters
-    let method_text = match method {
-        HttpMethod::DELETE => "DELE".to_string(),
-        HttpMethod::OPTIONS => "OPTN".to_string(),
-        HttpMethod::PATCH => "PACH".to_string(),
-        _ => {
-            let method_str = method.to_string();
-            if method_str.len() > 4 {
-                method_str[..4].to_string()
-            } else {
-                method_str
-            }
+fn collection_color(color: CollectionColor) -> Color {
+    match color {
+        CollectionColor::Red => Color::from_rgb(0.86, 0.15, 0.15),
+        CollectionColor::Orange => Color::from_rgb(0.92, 0.35, 0.05),
+        CollectionColor::Yellow => Color::from_rgb(0.79, 0.54, 0.02),
+        CollectionColor::Green => Color::from_rgb(0.09, 0.64, 0.29),
+        CollectionColor::Teal => Color::from_rgb(0.05, 0.58, 0.53),
+        CollectionColor::Blue => Color::from_rgb(0.15, 0.39, 0.92),
+        CollectionColor::Purple => Color::from_rgb(0.58, 0.2, 0.92),
+        CollectionColor::Pink => Color::from_rgb(0.86, 0.15, 0.47),
+    }
+}
+
+fn collection_icon_name(icon: CollectionIcon) -> IconName {
+    match icon {
+        CollectionIcon::Folder => IconName::Folder,
+        CollectionIcon::Globe => IconName::Globe,
+        CollectionIcon::Database => IconName::Database,
+        CollectionIcon::Lock => IconName::Lock,
+        CollectionIcon::User => IconName::User,
+        CollectionIcon::Cart => IconName::ShoppingCart,
+        CollectionIcon::Zap => IconName::Zap,
+        CollectionIcon::Cloud => IconName::Cloud,
+    }
+}
+
+/// Icon tinted with the collection's colour, nothing for collections left at the defaults
+fn collection_icon<'a>(collection: &RequestCollection) -> Element<'a, Message> {
+    if collection.color.is_none() && collection.icon.is_none() {
+        return space().into();
+    }
+
+    let color = collection
+        .color
+        .map(collection_color)
+        .unwrap_or(Color::from_rgb(0.45, 0.45, 0.45));
+
+    row![
+        icon(collection_icon_name(
+            collection.icon.unwrap_or(CollectionIcon::Folder)
+        ))
+        .size(14)
+        .color(color),
+        space().width(6),
+    ]
+    .into()
+}
+
+/// Indices into `collection.requests` in the order the sidebar shows them. Ties keep the
+/// manual order, requests never sent go last when sorting by last sent.
+fn sorted_request_indices(collection: &RequestCollection) -> Vec<usize> {
+    let requests = &collection.requests;
+    let mut indices: Vec<usize> = (0..requests.len()).collect();
+
+    match collection.sort {
+        CollectionSort::Manual => {}
+        CollectionSort::Name => {
+            indices.sort_by_cached_key(|&index| requests[index].name.to_lowercase());
         }
-    };
+        CollectionSort::Method => indices.sort_by_key(|&index| {
+            HttpMethod::ALL
+                .iter()
+                .position(|method| *method == requests[index].method)
+        }),
+        CollectionSort::LastSent => indices.sort_by(|&a, &b| {
+            // RFC 3339 timestamps in UTC compare chronologically as strings
+            let last_sent = |index: usize| {
+                requests[index]
+                    .run_history
+                    .last()
+                    .map(|run| run.timestamp.as_str())
+            };
+            match (last_sent(a), last_sent(b)) {
+                (Some(a), Some(b)) => b.cmp(a),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+        }),
+    }
+
+    indices
+}
+
+/// "Sort by" entries of the collection context menu, the current one is ticked
+fn sort_options<'a>(collection_id: &str, current: CollectionSort) -> Element<'a, Message> {
+    let mut options = column![
+        container(
+            text(tr("collections-sort-by"))
+                .size(11)
+                .color(Color::from_rgb(0.45, 0.45, 0.45))
+        )
+        .padding([2, 5])
+    ];
+
+    for sort in CollectionSort::ALL {
+        let label = if sort == current {
+            format!("✓ {}", sort)
+        } else {
+            format!("   {}", sort)
+        };
+        options = options.push(
+            button(text(label))
+                .on_press(Message::SetFolderSort(collection_id.to_string(), sort))
+                .width(Length::Fill)
+                .style(context_menu_item_style()),
+        );
+    }
+
+    options.spacing(2).into()
+}
+
+/// Row of swatches in the collection context menu, the first one clears the colour
+fn color_picker<'a>(collection_id: &str) -> Element<'a, Message> {
+    let mut swatches = row![
+        button(text("×").size(10).center())
+            .on_press(Message::SetFolderColor(collection_id.to_string(), None))
+            .width(Length::Fixed(14.0))
+            .height(Length::Fixed(14.0))
+            .padding(0)
+            .style(context_menu_item_style())
+    ]
+    .spacing(4);
+
+    for color in CollectionColor::ALL {
+        let fill = collection_color(color);
+        swatches = swatches.push(
+            button(space())
+                .on_press(Message::SetFolderColor(
+                    collection_id.to_string(),
+                    Some(color),
+                ))
+                .width(Length::Fixed(14.0))
+                .height(Length::Fixed(14.0))
+                .padding(0)
+                .style(move |_theme, status| button::Style {
+                    background: Some(Background::Color(fill)),
+                    border: Border {
+                        color: match status {
+                            Status::Hovered => Color::from_rgb(0.2, 0.2, 0.2),
+                            _ => Color::TRANSPARENT,
+                        },
+                        width: 1.0,
+                        radius: 7.0.into(),
+                    },
+                    ..button::Style::default()
+                }),
+        );
+    }
+
+    container(swatches).padding([4, 5]).into()
+}
+
+/// Row of icons in the collection context menu, the first one clears the icon
+fn icon_picker<'a>(collection_id: &str) -> Element<'a, Message> {
+    let mut icons = row![
+        button(text("×").size(12).center())
+            .on_press(Message::SetFolderIcon(collection_id.to_string(), None))
+            .width(Length::Fixed(18.0))
+            .padding(2)
+            .style(context_menu_item_style())
+    ]
+    .spacing(1);
+
+    for collection_icon in CollectionIcon::ALL {
+        icons = icons.push(
+            button(icon(collection_icon_name(collection_icon)).size(14))
+                .on_press(Message::SetFolderIcon(
+                    collection_id.to_string(),
+                    Some(collection_icon),
+                ))
+                .padding(2)
+                .style(context_menu_item_style()),
+        );
+    }
+
+    container(icons).padding([0, 3]).into()
+}
 
-    container(text(method_text).size(10).color(text_color))
-        .width(Length::Fixed(32.0))
-        .align_x(iced::alignment::Horizontal::Center)
+/// Dot after the name of a request with edits that aren't saved yet
+fn unsaved_marker<'a>(has_draft: bool) -> Element<'a, Message> {
+    if has_draft {
+        text(" ●")
+            .size(9)
+            .color(Color::from_rgb(0.92, 0.55, 0.1))
+            .into()
+    } else {
+        space().into()
+    }
+}
+
+/// Shown in place of the sparkline while the request is being sent
+fn in_flight_badge<'a>() -> Element<'a, Message> {
+    let accent = Color::from_rgb(0.51, 0.55, 0.97);
+
+    container(text(tr("collections-sending")).size(10).color(accent))
+        .padding([1, 6])
         .style(move |_theme| Style {
-            background: Some(Background::Color(color)),
+            background: Some(Background::Color(Color::from_rgba(
+                accent.r, accent.g, accent.b, 0.12,
+            ))),
             border: Border {
-                radius: 3.0.into(),
+                radius: 8.0.into(),
                 ..Border::default()
             },
             ..Style::default()
         })
-        .padding([2, 3])
         .into()
 }
+
+/// Dot after a monitored request: green while its checks pass, red once they fail and
+/// grey until the first check
+fn monitor_badge<'a>(status: Option<&MonitorStatus>) -> Element<'a, Message> {
+    let (color, label) = match status {
+        Some(MonitorStatus::Passing) => (
+            Color::from_rgb(0.13, 0.7, 0.3),
+            tr("collections-monitor-passing"),
+        ),
+        Some(MonitorStatus::Failing(reason)) => (
+            Color::from_rgb(0.86, 0.2, 0.2),
+            tr_args("collections-monitor-failing", &[("reason", reason)]),
+        ),
+        None => (
+            Color::from_rgb(0.6, 0.6, 0.6),
+            tr("collections-monitor-pending"),
+        ),
+    };
+
+    accessibility::labeled(
+        container(text("●").size(10).color(color)).padding(iced::Padding::ZERO.left(6.0)),
+        label,
+        iced::widget::tooltip::Position::Left,
+    )
+}
+
+// Number of runs shown in the sidebar sparkline
+const SPARKLINE_RUNS: usize = 10;
+const SPARKLINE_HEIGHT: f32 = 14.0;
+
+/// Tiny bar chart of the latest runs: bar height follows the duration, colour the status
+fn run_sparkline<'a>(runs: &[RunRecord]) -> Element<'a, Message> {
+    let recent = &runs[runs.len().saturating_sub(SPARKLINE_RUNS)..];
+    let slowest = recent.iter().map(|run| run.duration).max().unwrap_or(0).max(1);
+
+    let mut bars = row![].spacing(1).align_y(iced::Alignment::End);
+    for run in recent {
+        let height = (run.duration as f32 / slowest as f32 * SPARKLINE_HEIGHT).max(2.0);
+        let color = run_status_color(run.status);
+
+        bars = bars.push(
+            container(space())
+                .width(Length::Fixed(3.0))
+                .height(Length::Fixed(height))
+                .style(move |_theme| Style {
+                    background: Some(Background::Color(color)),
+                    ..Style::default()
+                }),
+        );
+    }
+
+    container(bars)
+        .height(Length::Fixed(SPARKLINE_HEIGHT))
+        .align_y(iced::alignment::Vertical::Bottom)
+        .into()
+}
+
+fn run_status_color(status: u16) -> Color {
+    match status {
+        200..=299 => Color::from_rgb(0.0, 0.7, 0.0),
+        300..=399 => Color::from_rgb(0.0, 0.4, 0.8),
+        400..=499 => Color::from_rgb(1.0, 0.6, 0.0),
+        _ => Color::from_rgb(0.8, 0.0, 0.0),
+    }
+}
+
+/// Name for a request created without one: the last path segment of its URL, or
+/// "New Request N"
+fn default_request_name(url: &str, number: usize) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map(|(_, path)| path).unwrap_or_default(),
+        None => url,
+    };
+
+    match path.trim_end_matches('/').rsplit('/').next() {
+        Some(segment) if !segment.is_empty() && !segment.contains("{{") => segment.to_string(),
+        _ => format!("New Request {}", number),
+    }
+}
+
+/// Method name in its conventional colour, in a fixed-width column so request names line up
+fn method_badge<'a>(method: &HttpMethod) -> Element<'a, Message> {
+    container(
+        text(method_short_label(method))
+            .size(10)
+            .color(method_color(method))
+            .font(iced::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            }),
+    )
+    .width(Length::Fixed(36.0))
+    .into()
+}
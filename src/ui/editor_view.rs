@@ -11,6 +11,7 @@ use iced::{
     widget::text_editor::Position,
 };
 
+use crate::search::Pattern;
 use crate::ui::widget_calc::{self, compute_visual_rows};
 use std::cell::RefCell;
 
@@ -20,6 +21,11 @@ pub enum Action {
     Redo,
     Find,
     ScrollToMatch(f32),
+    /// Line operations, only raised for multi-line editors
+    DuplicateLine,
+    MoveLineUp,
+    MoveLineDown,
+    ToggleComment,
 }
 
 #[allow(missing_debug_implementations)]
@@ -31,7 +37,7 @@ where
     content: Element<'a, Message, Theme, Renderer>,
     on_change: F,
     search_active_match: Option<(Position, Position)>,
-    search_query: Option<String>,
+    search_pattern: Option<Pattern>,
     content_ref: Option<&'a iced::widget::text_editor::Content>,
     font: Font,
     text_size: Pixels,
@@ -53,8 +59,8 @@ struct Cache {
     visual_rows: Vec<crate::ui::widget_calc::VisualRow>,
     /// Pre-computed search matches bounds cache.
     search_matches: Vec<iced::Rectangle>,
-    /// Search query at which search_matches was computed. Invalidated on changes.
-    last_search_query: Option<String>,
+    /// Search pattern at which search_matches was computed. Invalidated on changes.
+    last_search_pattern: Option<Pattern>,
     /// Content width at which visual_rows was computed. Invalidated when this changes.
     last_width: f32,
     /// Editor content version at which visual_rows was computed. Invalidated when this changes.
@@ -78,7 +84,7 @@ where
             content: content.into(),
             on_change,
             search_active_match: None,
-            search_query: None,
+            search_pattern: None,
             content_ref: None,
             font: Font::MONOSPACE,
             text_size: Pixels(14.0),
@@ -96,8 +102,8 @@ where
         self
     }
 
-    pub fn search_query(mut self, search_query: String) -> Self {
-        self.search_query = Some(search_query);
+    pub fn search_pattern(mut self, search_pattern: Pattern) -> Self {
+        self.search_pattern = Some(search_pattern);
         self
     }
 
@@ -258,31 +264,57 @@ where
         // Intercept Cmd+Z and Cmd+Shift+Z BEFORE the wrapped widget sees them
         if let Event::Keyboard(iced::keyboard::Event::KeyPressed { key, modifiers, .. }) = event {
             // Check if the wrapped widget is focused (text_input or text_editor)
-            let is_focused =
+            let (is_focused, is_editor) =
                 if let iced::advanced::widget::tree::State::Some(state) = &tree.children[0].state {
                     if let Some(state) =
                         state.downcast_ref::<iced::widget::text_input::State<Renderer::Paragraph>>()
                     {
-                        state.is_focused()
+                        (state.is_focused(), false)
                     } else if let Some(state) = state
                         .downcast_ref::<iced::widget::text_editor::State<
                             iced::advanced::text::highlighter::PlainText,
                         >>()
                     {
-                        state.is_focused()
+                        (state.is_focused(), true)
                     } else if let Some(state) = state
                         .downcast_ref::<iced::widget::text_editor::State<
                             iced::highlighter::Highlighter,
                         >>()
                     {
-                        state.is_focused()
+                        (state.is_focused(), true)
                     } else {
-                        false
+                        (false, false)
                     }
                 } else {
-                    false
+                    (false, false)
                 };
 
+            if is_focused && is_editor {
+                let line_action = match key.as_ref() {
+                    // Duplicate line: Cmd+D
+                    Key::Character(c) if c == "d" && modifiers.command() => {
+                        Some(Action::DuplicateLine)
+                    }
+                    // Toggle comment: Cmd+/
+                    Key::Character(c) if c == "/" && modifiers.command() => {
+                        Some(Action::ToggleComment)
+                    }
+                    // Move line: Alt+Up / Alt+Down
+                    Key::Named(iced::keyboard::key::Named::ArrowUp) if modifiers.alt() => {
+                        Some(Action::MoveLineUp)
+                    }
+                    Key::Named(iced::keyboard::key::Named::ArrowDown) if modifiers.alt() => {
+                        Some(Action::MoveLineDown)
+                    }
+                    _ => None,
+                };
+
+                if let Some(action) = line_action {
+                    shell.publish((self.on_change)(action));
+                    return;
+                }
+            }
+
             if is_focused {
                 match (key.as_ref(), modifiers.command(), modifiers.shift()) {
                     (Key::Character(c), true, false) if c == "z" => {
@@ -594,23 +626,21 @@ where
             }
 
             // Always recalculate search highlights if content/query changed
-            let search_query_changed =
-                cache.last_search_query.as_deref() != self.search_query.as_deref();
-            if needs_rebuild || search_query_changed {
-                cache.last_search_query = self.search_query.clone();
+            let search_pattern_changed = cache.last_search_pattern != self.search_pattern;
+            if needs_rebuild || search_pattern_changed {
+                cache.last_search_pattern = self.search_pattern.clone();
                 cache.search_matches.clear();
 
-                if let Some(query) = &self.search_query {
-                    if !query.is_empty() {
+                if let Some(pattern) = &self.search_pattern {
+                    if !pattern.is_empty() {
                         // Scan content line by line instead of whole text to build per-line paragraph geometry easily
                         let line_count = content.line_count();
                         for i in 0..line_count {
                             if let Some(line) = content.line(i) {
                                 let line_text = line.text;
-                                let mut start_idx = 0;
-                                while let Some(match_idx) = line_text[start_idx..].find(query) {
-                                    let absolute_idx = start_idx + match_idx;
-                                    let match_len = query.len();
+                                for found in pattern.find_all(&line_text) {
+                                    let absolute_idx = found.start;
+                                    let match_len = found.len();
 
                                     // Build spans to measure the exact bounding boxes of the match
                                     let span_before = &line_text[0..absolute_idx];
@@ -658,8 +688,6 @@ where
                                             height: rect.height,
                                         });
                                     }
-
-                                    start_idx = absolute_idx + match_len;
                                 }
                             }
                         }
@@ -901,7 +929,7 @@ mod tests {
                 },
             ],
             search_matches: vec![],
-            last_search_query: None,
+            last_search_pattern: None,
             last_width: 0.0,
             last_version: 0,
             char_width: 0.0,
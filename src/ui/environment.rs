@@ -1,6 +1,10 @@
-use crate::types::Environment;
+use crate::i18n::tr;
+use crate::types::{ApiKeyLocation, AuthType, Environment, EnvironmentAuth, VariableType};
 use crate::ui::{IconName, icon};
-use iced::widget::{button, checkbox, column, container, row, scrollable, space, text, text_input};
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, space, text, text_input,
+    tooltip,
+};
 use iced::{Color, Element, Fill, Length, Padding, Theme, Vector};
 
 #[derive(Debug, Clone)]
@@ -9,11 +13,14 @@ pub enum Action {
     DeleteEnvironment(usize),
     EnvironmentNameChanged(usize, String),
     EnvironmentDescriptionChanged(usize, String),
+    EnvironmentAuthChanged(usize, EnvironmentAuth),
     VariableKeyChanged(usize, String, String), // (env_index, old_key, new_key)
     VariableValueChanged(usize, String, String), // (env_index, key, new_value)
     AddVariable(usize),
     RemoveVariable(usize, String), // (env_index, key)
     ToggleVariable(usize, String), // (env_index, key)
+    ToggleSecret(usize, String),   // (env_index, key)
+    VariableTypeChanged(usize, String, VariableType), // (env_index, key, type)
     ClosePopup,
     EnvironmentSelected(usize),
     None,
@@ -25,11 +32,14 @@ pub enum Message {
     DeleteEnvironment(usize),
     EnvironmentNameChanged(usize, String),
     EnvironmentDescriptionChanged(usize, String),
+    EnvironmentAuthChanged(usize, EnvironmentAuth),
     VariableKeyChanged(usize, String, String),
     VariableValueChanged(usize, String, String),
     AddVariable(usize),
     RemoveVariable(usize, String),
     ToggleVariable(usize, String),
+    ToggleSecret(usize, String),
+    VariableTypeChanged(usize, String, VariableType),
     ClosePopup,
     EnvironmentSelected(usize),
 }
@@ -54,6 +64,9 @@ impl EnvironmentPanel {
             Message::EnvironmentDescriptionChanged(env_index, description) => {
                 Action::EnvironmentDescriptionChanged(env_index, description)
             }
+            Message::EnvironmentAuthChanged(env_index, auth) => {
+                Action::EnvironmentAuthChanged(env_index, auth)
+            }
             Message::VariableKeyChanged(env_index, old_key, new_key) => {
                 Action::VariableKeyChanged(env_index, old_key, new_key)
             }
@@ -63,6 +76,10 @@ impl EnvironmentPanel {
             Message::AddVariable(env_index) => Action::AddVariable(env_index),
             Message::RemoveVariable(env_index, key) => Action::RemoveVariable(env_index, key),
             Message::ToggleVariable(env_index, key) => Action::ToggleVariable(env_index, key),
+            Message::ToggleSecret(env_index, key) => Action::ToggleSecret(env_index, key),
+            Message::VariableTypeChanged(env_index, key, value_type) => {
+                Action::VariableTypeChanged(env_index, key, value_type)
+            }
             Message::ClosePopup => {
                 self.show_popup = false;
                 Action::ClosePopup
@@ -115,7 +132,7 @@ impl EnvironmentPanel {
         });
 
         let header = row![
-            text("Environments")
+            text(tr("environments-title"))
                 .size(16)
                 .color(Color::from_rgb(0.3, 0.3, 0.3)),
             space().width(Fill),
@@ -128,7 +145,7 @@ impl EnvironmentPanel {
             row![
                 icon(IconName::Add).size(14).color(Color::WHITE),
                 space().width(8),
-                text("New Environment").size(14)
+                text(tr("environments-new")).size(14)
             ]
             .align_y(iced::Alignment::Center),
         )
@@ -173,7 +190,7 @@ impl EnvironmentPanel {
                         }),
                         space().width(Fill),
                         if is_active {
-                            container(text("Active").size(10).color(Color::WHITE))
+                            container(text(tr("environments-active")).size(10).color(Color::WHITE))
                                 .padding([2, 6])
                                 .style(|_theme: &Theme| container::Style {
                                     background: Some(iced::Background::Color(Color::from_rgb(
@@ -290,7 +307,7 @@ impl EnvironmentPanel {
                             }
                         }),
                     space().width(10),
-                    container(text("Active").size(12).color(Color::WHITE))
+                    container(text(tr("environments-active")).size(12).color(Color::WHITE))
                         .padding([4, 10])
                         .style(|_theme: &Theme| container::Style {
                             background: Some(iced::Background::Color(Color::from_rgb(
@@ -310,7 +327,7 @@ impl EnvironmentPanel {
 
                 // Description
                 panel_content = panel_content.push(
-                    text("Define variables that can be used across your requests")
+                    text(tr("environments-hint"))
                         .size(13)
                         .color(Color::from_rgb(0.5, 0.5, 0.5)),
                 );
@@ -320,7 +337,7 @@ impl EnvironmentPanel {
                 // Variables section header
                 let enabled_count = active_env.variables.values().filter(|v| v.enabled).count();
                 let variables_header = row![
-                    text("Variables").size(14),
+                    text(tr("environments-variables")).size(14),
                     space().width(10),
                     container(
                         text(format!("{} enabled", enabled_count))
@@ -339,7 +356,7 @@ impl EnvironmentPanel {
                         ..Default::default()
                     }),
                     space().width(Fill),
-                    text("Show Values")
+                    text(tr("environments-show-values"))
                         .size(13)
                         .color(Color::from_rgb(0.5, 0.5, 0.5))
                 ]
@@ -356,7 +373,7 @@ impl EnvironmentPanel {
                     row![
                         container(text("").width(40)), // Toggle button column
                         container(
-                            text("Key")
+                            text(tr("environments-key"))
                                 .size(12)
                                 .color(Color::from_rgb(0.3, 0.3, 0.3))
                                 .font(iced::Font {
@@ -367,7 +384,7 @@ impl EnvironmentPanel {
                         .width(Length::FillPortion(3))
                         .padding([6, 8]),
                         container(
-                            text("Value")
+                            text(tr("environments-value"))
                                 .size(12)
                                 .color(Color::from_rgb(0.3, 0.3, 0.3))
                                 .font(iced::Font {
@@ -377,7 +394,19 @@ impl EnvironmentPanel {
                         )
                         .width(Length::FillPortion(7))
                         .padding([6, 8]),
-                        container(text("").width(40)) // Delete button column
+                        container(
+                            text(tr("environments-type"))
+                                .size(12)
+                                .color(Color::from_rgb(0.3, 0.3, 0.3))
+                                .font(iced::Font {
+                                    weight: iced::font::Weight::Bold,
+                                    ..Default::default()
+                                })
+                        )
+                        .width(90)
+                        .padding([6, 8]),
+                        container(text("").width(40)), // Secret button column
+                        container(text("").width(40))  // Delete button column
                     ]
                     .spacing(10),
                 )
@@ -427,6 +456,39 @@ impl EnvironmentPanel {
                     let key_clone3 = key.clone();
                     let key_clone4 = key.clone();
                     let is_enabled = var.enabled;
+                    let is_secret = var.secret;
+
+                    // Secret values are masked here and in logs and exports
+                    let secret_button = button(
+                        container(icon(IconName::Lock).size(14).color(if is_secret {
+                            Color::from_rgb(0.1, 0.1, 0.1)
+                        } else {
+                            Color::from_rgb(0.75, 0.75, 0.75)
+                        }))
+                        .align_x(iced::alignment::Horizontal::Center)
+                        .align_y(iced::alignment::Vertical::Center)
+                        .width(Length::Fill)
+                        .height(Length::Fill),
+                    )
+                    .on_press(Message::ToggleSecret(active_idx, key.clone()))
+                    .width(32)
+                    .height(32)
+                    .style(|_theme: &Theme, status| match status {
+                        button::Status::Hovered | button::Status::Pressed => button::Style {
+                            background: Some(iced::Background::Color(Color::from_rgb(
+                                0.95, 0.95, 0.95,
+                            ))),
+                            border: iced::Border {
+                                radius: 6.0.into(),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        _ => button::Style {
+                            background: Some(iced::Background::Color(Color::TRANSPARENT)),
+                            ..Default::default()
+                        },
+                    });
 
                     // Toggle checkbox
                     let toggle_checkbox = checkbox(is_enabled)
@@ -555,6 +617,7 @@ impl EnvironmentPanel {
                                     key_clone2.clone(),
                                     input
                                 ))
+                                .secure(is_secret)
                                 .padding(8)
                                 .size(13)
                                 .width(Length::FillPortion(7))
@@ -578,6 +641,34 @@ impl EnvironmentPanel {
                                         selection: Color::from_rgb(0.7, 0.85, 1.0),
                                     }
                                 }),
+                            pick_list(VariableType::ALL, Some(var.value_type), {
+                                let key = key.clone();
+                                move |value_type| {
+                                    Message::VariableTypeChanged(
+                                        active_idx,
+                                        key.clone(),
+                                        value_type,
+                                    )
+                                }
+                            })
+                            .text_size(13)
+                            .width(90),
+                            tooltip(
+                                container(secret_button)
+                                    .width(40)
+                                    .align_x(iced::alignment::Horizontal::Center),
+                                container(
+                                    text(if is_secret {
+                                        tr("environments-secret")
+                                    } else {
+                                        tr("environments-mark-secret")
+                                    })
+                                    .size(12)
+                                )
+                                .padding([4, 8])
+                                .style(container::rounded_box),
+                                tooltip::Position::Top,
+                            ),
                             container(delete_button)
                                 .width(40)
                                 .align_x(iced::alignment::Horizontal::Center)
@@ -620,7 +711,7 @@ impl EnvironmentPanel {
                         row![
                             icon(IconName::Add).size(14),
                             space().width(6),
-                            text("Add Variable").size(13)
+                            text(tr("environments-add-variable")).size(13)
                         ]
                         .align_y(iced::Alignment::Center),
                     )
@@ -657,29 +748,31 @@ impl EnvironmentPanel {
                 panel_content = panel_content.push(
                     container(
                         column![
-                            text("Using Variables").size(14),
+                            text(tr("environments-using-variables")).size(14),
                             space().height(6),
-                            text("Reference variables in your requests using the syntax: {{variable_name}}")
+                            text(tr("environments-using-variables-hint"))
                                 .size(12)
                                 .color(Color::from_rgb(0.5, 0.5, 0.5)),
                             space().height(4),
-                            text("Example: {{base_url}}/api/users")
+                            text(tr("environments-example"))
                                 .size(12)
                                 .color(Color::from_rgb(0.5, 0.5, 0.5))
                         ]
-                        .spacing(0)
+                        .spacing(0),
                     )
                     .width(Length::Fill)
                     .padding(12)
                     .style(|_theme: &Theme| container::Style {
-                        background: Some(iced::Background::Color(Color::from_rgb(0.97, 0.97, 0.98))),
+                        background: Some(iced::Background::Color(Color::from_rgb(
+                            0.97, 0.97, 0.98,
+                        ))),
                         border: iced::Border {
                             color: Color::from_rgb(0.9, 0.9, 0.92),
                             width: 1.0,
                             radius: 6.0.into(),
                         },
                         ..Default::default()
-                    })
+                    }),
                 );
 
                 // Environment management section
@@ -687,14 +780,14 @@ impl EnvironmentPanel {
                 panel_content = panel_content.push(
                     container(
                         column![
-                            text("Environment Settings").size(14),
+                            text(tr("environments-settings")).size(14),
                             space().height(10),
-                            text("Description")
+                            text(tr("environments-description"))
                                 .size(12)
                                 .color(Color::from_rgb(0.5, 0.5, 0.5)),
                             space().height(4),
                             text_input(
-                                "Environment description",
+                                &tr("environments-description-placeholder"),
                                 active_env.description.as_deref().unwrap_or(""),
                             )
                             .on_input(move |input| {
@@ -722,6 +815,8 @@ impl EnvironmentPanel {
                                     selection: Color::from_rgb(0.7, 0.85, 1.0),
                                 }
                             }),
+                            space().height(12),
+                            default_auth_settings(active_idx, &active_env.auth),
                         ]
                         .spacing(0),
                     )
@@ -748,7 +843,7 @@ impl EnvironmentPanel {
                                     .size(14)
                                     .color(Color::from_rgb(0.8, 0.3, 0.3)),
                                 space().width(6),
-                                text("Delete Environment").size(13)
+                                text(tr("environments-delete")).size(13)
                             ]
                             .align_y(iced::Alignment::Center),
                         )
@@ -789,7 +884,7 @@ impl EnvironmentPanel {
                     ))
             } else {
                 scrollable(column![
-                    text("No environment selected")
+                    text(tr("environments-none-selected"))
                         .size(14)
                         .color(Color::from_rgb(0.5, 0.5, 0.5))
                 ])
@@ -800,11 +895,11 @@ impl EnvironmentPanel {
             }
         } else {
             scrollable(column![
-                text("No environment selected")
+                text(tr("environments-none-selected"))
                     .size(14)
                     .color(Color::from_rgb(0.5, 0.5, 0.5)),
                 space().height(10),
-                text("Select an environment from the sidebar or create a new one")
+                text(tr("environments-none-selected-hint"))
                     .size(13)
                     .color(Color::from_rgb(0.6, 0.6, 0.6))
             ])
@@ -852,3 +947,128 @@ impl EnvironmentPanel {
             .into()
     }
 }
+
+/// Auth used by the environment's requests set to inherit it
+fn default_auth_settings<'a>(env_index: usize, auth: &EnvironmentAuth) -> Element<'a, Message> {
+    let field = |label: String,
+                 placeholder: String,
+                 value: &str,
+                 secure: bool,
+                 set: fn(&mut EnvironmentAuth, String)| {
+        let auth = auth.clone();
+        row![
+            text(label)
+                .size(12)
+                .color(Color::from_rgb(0.5, 0.5, 0.5))
+                .width(Length::Fixed(110.0)),
+            text_input(&placeholder, value)
+                .on_input(move |value| {
+                    let mut auth = auth.clone();
+                    set(&mut auth, value);
+                    Message::EnvironmentAuthChanged(env_index, auth)
+                })
+                .secure(secure)
+                .padding(8)
+                .size(13),
+        ]
+        .align_y(iced::Alignment::Center)
+    };
+
+    let type_picker = {
+        let auth = auth.clone();
+        pick_list(
+            EnvironmentAuth::TYPES,
+            Some(auth.auth_type.clone()),
+            move |auth_type| {
+                Message::EnvironmentAuthChanged(
+                    env_index,
+                    EnvironmentAuth {
+                        auth_type,
+                        ..auth.clone()
+                    },
+                )
+            },
+        )
+        .text_size(13)
+    };
+
+    let mut settings = column![
+        text(tr("environments-default-auth"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        text(tr("environments-default-auth-hint"))
+            .size(12)
+            .color(Color::from_rgb(0.6, 0.6, 0.6)),
+        type_picker,
+    ]
+    .spacing(6);
+
+    settings = match auth.auth_type {
+        AuthType::Bearer => settings.push(field(
+            tr("environments-token"),
+            tr("environments-token-placeholder"),
+            &auth.bearer_token,
+            false,
+            |auth, value| auth.bearer_token = value,
+        )),
+        AuthType::Basic => settings
+            .push(field(
+                tr("environments-username"),
+                tr("environments-username"),
+                &auth.basic_username,
+                false,
+                |auth, value| auth.basic_username = value,
+            ))
+            .push(field(
+                tr("environments-password"),
+                tr("environments-password"),
+                &auth.basic_password,
+                true,
+                |auth, value| auth.basic_password = value,
+            )),
+        AuthType::ApiKey => {
+            let auth_for_location = auth.clone();
+            settings
+                .push(
+                    row![
+                        text(tr("environments-add-to"))
+                            .size(12)
+                            .color(Color::from_rgb(0.5, 0.5, 0.5))
+                            .width(Length::Fixed(110.0)),
+                        pick_list(
+                            [ApiKeyLocation::Header, ApiKeyLocation::Query],
+                            Some(auth.api_key_location),
+                            move |api_key_location| {
+                                Message::EnvironmentAuthChanged(
+                                    env_index,
+                                    EnvironmentAuth {
+                                        api_key_location,
+                                        ..auth_for_location.clone()
+                                    },
+                                )
+                            },
+                        )
+                        .text_size(13),
+                    ]
+                    .align_y(iced::Alignment::Center),
+                )
+                .push(field(
+                    tr("environments-key-name"),
+                    "X-API-Key".to_string(),
+                    &auth.api_key_header,
+                    false,
+                    |auth, value| auth.api_key_header = value,
+                ))
+                .push(field(
+                    tr("environments-api-key"),
+                    tr("environments-api-key-placeholder"),
+                    &auth.api_key,
+                    true,
+                    |auth, value| auth.api_key = value,
+                ))
+        }
+        _ => settings,
+    };
+
+    settings.into()
+}
@@ -14,6 +14,26 @@ pub enum IconName {
     Add,
     Indent,
     Trash,
+    Maximize,
+    Minimize,
+    PanelLeftClose,
+    PanelLeftOpen,
+    PanelBottom,
+    PanelRight,
+    Folder,
+    Globe,
+    Database,
+    Lock,
+    User,
+    ShoppingCart,
+    Zap,
+    Cloud,
+    Wrench,
+    Inbox,
+    History,
+    Settings,
+    GripVertical,
+    Info,
 }
 
 impl IconName {
@@ -29,6 +49,26 @@ impl IconName {
             IconName::Add => "add.svg",
             IconName::Indent => "indent.svg",
             IconName::Trash => "trash.svg",
+            IconName::Maximize => "maximize.svg",
+            IconName::Minimize => "minimize.svg",
+            IconName::PanelLeftClose => "panel-left-close.svg",
+            IconName::PanelLeftOpen => "panel-left-open.svg",
+            IconName::PanelBottom => "panel-bottom.svg",
+            IconName::PanelRight => "panel-right.svg",
+            IconName::Folder => "folder.svg",
+            IconName::Globe => "globe.svg",
+            IconName::Database => "database.svg",
+            IconName::Lock => "lock.svg",
+            IconName::User => "user.svg",
+            IconName::ShoppingCart => "shopping-cart.svg",
+            IconName::Zap => "zap.svg",
+            IconName::Cloud => "cloud.svg",
+            IconName::Wrench => "wrench.svg",
+            IconName::Inbox => "inbox.svg",
+            IconName::History => "history.svg",
+            IconName::Settings => "settings.svg",
+            IconName::GripVertical => "grip-vertical.svg",
+            IconName::Info => "info.svg",
         }
     }
 }
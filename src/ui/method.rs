@@ -0,0 +1,28 @@
+use crate::types::HttpMethod;
+use iced::Color;
+
+/// Conventional colour of an HTTP method, shared by the sidebar, the URL bar and the queue
+pub fn method_color(method: &HttpMethod) -> Color {
+    match method {
+        HttpMethod::GET => Color::from_rgb(0.09, 0.6, 0.25),
+        HttpMethod::POST => Color::from_rgb(0.9, 0.5, 0.0),
+        HttpMethod::PUT => Color::from_rgb(0.15, 0.39, 0.85),
+        HttpMethod::DELETE => Color::from_rgb(0.82, 0.1, 0.1),
+        HttpMethod::PATCH => Color::from_rgb(0.55, 0.2, 0.85),
+        HttpMethod::HEAD => Color::from_rgb(0.1, 0.55, 0.55),
+        HttpMethod::OPTIONS => Color::from_rgb(0.75, 0.2, 0.5),
+    }
+}
+
+/// Method name short enough for narrow columns such as the sidebar
+pub fn method_short_label(method: &HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::GET => "GET",
+        HttpMethod::POST => "POST",
+        HttpMethod::PUT => "PUT",
+        HttpMethod::DELETE => "DEL",
+        HttpMethod::PATCH => "PATCH",
+        HttpMethod::HEAD => "HEAD",
+        HttpMethod::OPTIONS => "OPT",
+    }
+}
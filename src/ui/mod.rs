@@ -1,18 +1,37 @@
+pub mod accessibility;
+pub mod audit_log;
+pub mod chart;
 pub mod collections;
 pub mod editor_view;
 pub mod environment;
 pub mod floating_element;
 pub mod icon;
+pub mod method;
+pub mod queue;
 pub mod request;
 pub mod response;
+pub mod runner;
+pub mod settings;
 pub mod spinner;
+pub mod stats;
+pub mod sync;
+pub mod toast;
+pub mod tools;
 pub mod undoable_editor;
 pub mod undoable_input;
+pub mod webhook;
+pub mod welcome;
 pub mod widget_calc;
 
 pub use collections::CollectionPanel;
 pub use environment::EnvironmentPanel;
 pub use icon::{IconName, icon};
+pub use method::{method_color, method_short_label};
+pub use queue::QueuePanel;
 pub use request::*;
 pub use response::ResponsePanel;
+pub use runner::RunnerDialog;
 pub use spinner::Spinner;
+pub use sync::SyncPanel;
+pub use tools::ToolsPanel;
+pub use webhook::WebhookPanel;
@@ -0,0 +1,153 @@
+use crate::i18n::tr;
+use crate::types::QueuedRequest;
+use crate::ui::{IconName, accessibility, icon, method_color};
+use iced::widget::button::Status;
+use iced::widget::container::Style;
+use iced::widget::{button, checkbox, column, container, row, scrollable, space, text, tooltip};
+use iced::{Background, Border, Color, Element, Length};
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    Retry(u64),
+    RetryAll,
+    Remove(u64),
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Retry(u64),
+    RetryAll,
+    Remove(u64),
+    AutoRetryToggled(bool),
+}
+
+/// Lists requests that were queued while the network was unavailable
+#[derive(Debug, Clone)]
+pub struct QueuePanel {
+    pub auto_retry: bool,
+}
+
+impl Default for QueuePanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueuePanel {
+    pub fn new() -> Self {
+        Self { auto_retry: true }
+    }
+
+    pub fn update(&mut self, message: Message) -> Action {
+        match message {
+            Message::Retry(id) => Action::Retry(id),
+            Message::RetryAll => Action::RetryAll,
+            Message::Remove(id) => Action::Remove(id),
+            Message::AutoRetryToggled(enabled) => {
+                self.auto_retry = enabled;
+                Action::None
+            }
+        }
+    }
+
+    pub fn view<'a>(&'a self, queue: &'a [QueuedRequest]) -> Element<'a, Message> {
+        let header = row![
+            text(format!("Offline Queue ({})", queue.len())).size(14),
+            space().width(Length::Fill),
+            checkbox(self.auto_retry)
+                .label("Auto retry")
+                .on_toggle(Message::AutoRetryToggled)
+                .size(14)
+                .text_size(12),
+            space().width(8),
+            button(text(tr("queue-retry-all")).size(12))
+                .on_press(Message::RetryAll)
+                .padding([2, 8])
+                .style(queue_button_style),
+        ]
+        .align_y(iced::Alignment::Center);
+
+        let mut entries = column![].spacing(4);
+
+        for queued in queue {
+            let status = if queued.in_flight {
+                "Retrying...".to_string()
+            } else {
+                format!("{} attempt(s) - {}", queued.attempts, queued.last_error)
+            };
+
+            let mut retry_button = button(text(tr("queue-retry")).size(12))
+                .padding([2, 8])
+                .style(queue_button_style);
+            if !queued.in_flight {
+                retry_button = retry_button.on_press(Message::Retry(queued.id));
+            }
+
+            entries = entries.push(
+                row![
+                    column![
+                        row![
+                            text(queued.request.method.to_string())
+                                .size(12)
+                                .color(method_color(&queued.request.method)),
+                            text(&queued.request.name).size(12),
+                        ]
+                        .spacing(4),
+                        text(status).size(11).color(Color::from_rgb(0.5, 0.5, 0.5)),
+                    ]
+                    .width(Length::Fill),
+                    retry_button,
+                    accessibility::labeled(
+                        button(icon(IconName::Trash).size(accessibility::icon_size(12.0)))
+                            .on_press(Message::Remove(queued.id))
+                            .padding(accessibility::padding(4.0))
+                            .style(queue_button_style),
+                        tr("a11y-remove-queued"),
+                        tooltip::Position::Left,
+                    ),
+                ]
+                .spacing(4)
+                .align_y(iced::Alignment::Center),
+            );
+        }
+
+        container(column![header, scrollable(entries).height(Length::Shrink)].spacing(8))
+            .padding(10)
+            .width(Length::Fill)
+            .style(|_theme| Style {
+                background: Some(Background::Color(Color::from_rgb(0.97, 0.97, 0.97))),
+                border: Border {
+                    color: Color::from_rgb(0.85, 0.85, 0.85),
+                    width: 1.0,
+                    radius: 0.0.into(),
+                },
+                ..Style::default()
+            })
+            .into()
+    }
+}
+
+fn queue_button_style(_theme: &iced::Theme, status: Status) -> button::Style {
+    let base = button::Style {
+        text_color: Color::from_rgb(0.2, 0.2, 0.2),
+        border: Border {
+            color: Color::from_rgb(0.8, 0.8, 0.8),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..button::Style::default()
+    };
+
+    match status {
+        Status::Hovered => button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 0.9))),
+            ..base
+        },
+        Status::Disabled => button::Style {
+            text_color: Color::from_rgb(0.6, 0.6, 0.6),
+            ..base
+        },
+        _ => base,
+    }
+}
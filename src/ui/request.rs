@@ -1,18 +1,39 @@
-use crate::constant::{REQUEST_BODY_EDITOR_ID, REQUEST_BODY_SCROLLABLE_ID};
-use crate::types::{AuthType, BodyFormat, Environment, HttpMethod, RequestConfig, RequestTab};
+use crate::constant::{POST_SCRIPT_EDITOR_ID, REQUEST_BODY_EDITOR_ID, REQUEST_BODY_SCROLLABLE_ID};
+use crate::duplicate_headers::{self, Fix};
+use crate::http::{computed_auth_headers, computed_auth_query, request_payload_size};
+use crate::i18n::{tr, tr_args};
+use crate::paste::{self, PastedRequest};
+use crate::path_variables::path_variable_names;
+use crate::rate_limit::MAX_RETRIES;
+use crate::search::Pattern;
+use crate::snapshot;
+use crate::storage::versions::VersionChanges;
+use crate::text_diff::{DiffLine, line_diff};
+use crate::tools::find_jwt;
+use crate::types::{
+    ApiKeyLocation, AuthType, BodyFormat, ContentEncoding, Environment, FieldDoc, HmacAlgorithm,
+    HttpMethod, PaginationStyle, RequestCollection, RequestConfig, RequestTab, ValueType,
+};
+use crate::ui::accessibility;
 use crate::ui::floating_element;
+use crate::ui::response::format_bytes;
 use crate::ui::undoable_editor::UndoableEditor;
 use crate::ui::undoable_input::UndoableInput;
-use crate::ui::{IconName, icon, undoable_editor, undoable_input};
-use iced::highlighter;
+use crate::ui::{IconName, icon, method_color, undoable_editor, undoable_input};
+use crate::url_encoding::{decode_url, encode_query_values};
 use iced::widget::button::Status;
+use iced::widget::scrollable::AbsoluteOffset;
 use iced::widget::{
-    Space, button, column, container, mouse_area, pick_list, row, scrollable, space, text,
-    text_editor, text_input,
+    Space, button, checkbox, column, container, mouse_area, pick_list, row, scrollable, space,
+    text, text_editor, text_input, tooltip,
 };
 use iced::{
     Background, Border, Color, Element, Fill, Length, Padding, Shadow, Task, Theme, Vector,
 };
+use iced_aw::ContextMenu;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::LazyLock;
 use std::time::Instant;
 
 // Action is returned from update function, to trigger a side effect, used in the main
@@ -30,13 +51,18 @@ pub enum Action {
     // The components needs to run a task
     Run(iced::Task<Message>),
     EditRequestBody(text_editor::Action),
-    EditRequestPostRequestScript(text_editor::Action),
+    EditAssertionSchema(text_editor::Action),
     Focus(iced::widget::Id),
     SearchNext(iced::widget::Id),
     SearchPrevious(iced::widget::Id),
     SubmitSearch(iced::widget::Id),
     FormatRequestBody(String),
     OpenEnvironmentPopup,
+    InspectToken(String),
+    CheckConnectivity,
+    LoadVersions,
+    RestoreVersion(String),
+    OpenHeaderDocs(String),
     // The component does not require any additional actions
     None,
 }
@@ -46,6 +72,9 @@ pub enum Message {
     ClickSendRequest,
     CancelRequest,
     UrlInputMessage(undoable_input::Message),
+    UrlPasted(Option<String>),
+    ApplyPastedRequest,
+    DismissPastedRequest,
     EditorMessage(undoable_editor::Message),
     // UrlInputChanged(String),
     // SetProcessingCmdZ(bool),
@@ -57,27 +86,92 @@ pub enum Message {
     CancelButtonHovered(bool),
 
     TabSelected(RequestTab),
+    TabScrolled(AbsoluteOffset),
+    ToggleBodyDiff,
+    VersionsLoaded(String, Result<Vec<VersionChanges>, String>),
+    RestoreVersion(String),
     HeaderKeyChanged(usize, String),
     HeaderValueChanged(usize, String),
+    HeaderTypeChanged(usize, ValueType),
+    HeaderDescriptionChanged(usize, String),
     AddHeader,
     AddHeaderFocusKey(usize),
     AddHeaderFocusValue(usize),
     RemoveHeader(usize),
+    OpenHeaderDocs(String),
+    FixDuplicateHeader(String, Fix),
     ParamKeyChanged(usize, String),
     ParamValueChanged(usize, String),
+    ParamTypeChanged(usize, ValueType),
+    ParamDescriptionChanged(usize, String),
+    PathVariableChanged(String, String),
+    ToggleFieldDocs(bool),
     AddParam,
     AddParamFocusKey(usize),
     AddParamFocusValue(usize),
     RemoveParam(usize),
+    RowDragStarted(RowTable, usize),
+    RowDragOver(usize),
+    RowDropped,
+    RowDragCancelled,
     BodyChanged(text_editor::Action),
     BodyFormatChanged(BodyFormat),
     AuthTypeChanged(AuthType),
     BearerTokenChanged(String),
+    TokenRequestSelected(TokenRequestOption),
+    TokenVariableChanged(String),
+    TokenPathChanged(String),
+    TokenTtlChanged(String),
     BasicUsernameChanged(String),
     BasicPasswordChanged(String),
     ApiKeyChanged(String),
     ApiKeyHeaderChanged(String),
-    ScriptChanged(text_editor::Action),
+    ApiKeyLocationChanged(ApiKeyLocation),
+    HmacAlgorithmChanged(HmacAlgorithm),
+    HmacKeyChanged(String),
+    HmacStringToSignChanged(String),
+    HmacHeaderNameChanged(String),
+    HmacHeaderValueChanged(String),
+    HawkIdChanged(String),
+    HawkKeyChanged(String),
+    HawkExtChanged(String),
+    ToggleRevealAuth,
+    InspectToken(String),
+    CheckConnectivity,
+    UnixSocketChanged(String),
+    MaxUploadSizeChanged(String),
+    AcceptEncodingToggled(ContentEncoding, bool),
+    DecompressResponseToggled(bool),
+    SendQueryAsTypedToggled(bool),
+    FollowRedirectsToggled(bool),
+    UseProxyToggled(bool),
+    VerifyTlsToggled(bool),
+    EncodeQueryValues,
+    DecodeUrl,
+    MaxDownloadSizeChanged(String),
+    LatencyBudgetChanged(String),
+    RetryAfterRateLimitToggled(bool),
+    AuditSecurityHeadersToggled(bool),
+    MonitorToggled(bool),
+    MonitorIntervalChanged(String),
+    MonitorExpectedStatusChanged(String),
+    PaginationStyleSelected(PaginationStyle),
+    PaginationParamChanged(String),
+    PaginationCursorPathChanged(String),
+    PaginationItemsPathChanged(String),
+    ScriptEditorMessage(undoable_editor::Message),
+    AssertionStatusChanged(String),
+    AssertionHeaderNameChanged(usize, String),
+    AssertionHeaderValueChanged(usize, String),
+    AddAssertionHeader,
+    RemoveAssertionHeader(usize),
+    AssertionSchemaChanged(text_editor::Action),
+    RecordSnapshot,
+    ClearSnapshot,
+    IgnorePathChanged(usize, String),
+    AddIgnorePath,
+    RemoveIgnorePath(usize),
+    DetachContract,
 
     // Environment management
     OpenEnvironmentPopup,
@@ -89,6 +183,7 @@ pub enum Message {
     DoNothing, // Used to prevent event propagation
     EnvironmentSelected(usize),
     SearchQueryChanged(String),
+    ToggleSearchRegex,
     FindNext,
     FindPrevious,
     SubmitSearch,
@@ -108,20 +203,143 @@ pub struct RequestPanel {
     pub send_button_hovered: bool,
     pub cancel_button_hovered: bool,
     pub selected_tab: RequestTab,
-    pub script_editor_content: text_editor::Content,
+    /// Tab last shown for each request left, by id
+    pub request_tabs: BTreeMap<String, RequestTab>,
+    /// Where the tab shown is scrolled to, and where it was for each request left
+    pub scroll_offset: AbsoluteOffset,
+    pub scroll_offsets: HashMap<String, AbsoluteOffset>,
     pub show_search: bool,
     pub search_query: String,
+    pub search_regex: bool, // The query is a regular expression
     pub search_input_id: iced::widget::Id,
     pub search_selection: Option<(text_editor::Position, text_editor::Position)>,
     pub url_input: UndoableInput,
     pub body_editor: UndoableEditor,
+    pub script_editor: UndoableEditor,
     pub history_registry: HistoryRegistry,
+    pub reveal_auth_secrets: bool,
+    // Type and description columns in the Params and Headers tables
+    pub show_field_docs: bool,
+    // Param or header row dragged by its handle
+    pub row_drag: Option<RowDrag>,
+    // Request recognised in the last text pasted into the URL bar, until applied or dismissed
+    pub pasted_request: Option<PastedRequest>,
+    // Saved versions of the request in the History tab, None while they load
+    pub versions: Option<Result<Vec<VersionChanges>, String>>,
+    /// Body of each request as it was when last sent, by id, and whether the changes made
+    /// to it since are shown
+    pub sent_bodies: HashMap<String, String>,
+    pub show_body_diff: bool,
+    // Diff shown for those changes, worked out again only when the body or sent body changes
+    pub body_diff: Option<BodyDiff>,
+}
+
+/// Line diff of a request's body against the one last sent, with the texts it was made from
+#[derive(Debug, Clone)]
+pub struct BodyDiff {
+    request_id: String,
+    sent: String,
+    current: String,
+    // None when the bodies are too large to compare
+    lines: Option<Vec<DiffLine>>,
+}
+
+/// Table of the Params or Headers tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowTable {
+    Params,
+    Headers,
+}
+
+/// A row of the Params or Headers tab being dragged, and the row it's over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowDrag {
+    pub table: RowTable,
+    pub from: usize,
+    pub over: usize,
+}
+
+/// Values computed from the request after variables are resolved, shown before sending
+#[derive(Debug, Clone, Default)]
+pub struct RequestPreview {
+    pub payload_size: (usize, usize),
+    pub auth_headers: Vec<(String, String)>,
+    pub auth_query: Vec<(String, String)>,
+    /// JWT sent in the auth or the headers, which can be inspected locally
+    pub token: Option<String>,
+    /// Headers and query parameters sent, the ones the auth adds included
+    pub header_count: usize,
+    pub param_count: usize,
+    /// Variables still referenced as `{{name}}` after resolution, by name
+    pub unresolved_variables: Vec<String>,
+}
+
+impl RequestPreview {
+    pub fn new(resolved_request: &RequestConfig) -> Self {
+        let auth_headers = computed_auth_headers(resolved_request);
+        let auth_query = computed_auth_query(resolved_request);
+        Self {
+            payload_size: request_payload_size(resolved_request),
+            token: auth_headers
+                .iter()
+                .chain(&resolved_request.headers)
+                .find_map(|(_, value)| find_jwt(value))
+                .map(str::to_string),
+            header_count: sent_fields(&resolved_request.headers).count() + auth_headers.len(),
+            param_count: sent_fields(&resolved_request.params).count() + auth_query.len(),
+            unresolved_variables: unresolved_variables(
+                resolved_request,
+                &auth_headers,
+                &auth_query,
+            ),
+            auth_headers,
+            auth_query,
+        }
+    }
+}
+
+/// Headers or params that are sent, an empty name or value leaves one out
+fn sent_fields(fields: &[(String, String)]) -> impl Iterator<Item = &(String, String)> {
+    fields
+        .iter()
+        .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+}
+
+static VARIABLE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{\{([^}]+)\}\}").unwrap());
+
+/// Variables left as `{{name}}` in the parts of a resolved request that are sent
+fn unresolved_variables(
+    resolved_request: &RequestConfig,
+    auth_headers: &[(String, String)],
+    auth_query: &[(String, String)],
+) -> Vec<String> {
+    let has_body = matches!(
+        resolved_request.method,
+        HttpMethod::POST | HttpMethod::PUT | HttpMethod::PATCH
+    );
+    let fields = sent_fields(&resolved_request.headers)
+        .chain(sent_fields(&resolved_request.params))
+        .chain(auth_headers)
+        .chain(auth_query)
+        .flat_map(|(key, value)| [key.as_str(), value.as_str()]);
+
+    std::iter::once(resolved_request.url.as_str())
+        .chain(fields)
+        .chain(has_body.then_some(resolved_request.body.as_str()))
+        .flat_map(|text| VARIABLE.captures_iter(text))
+        .map(|captures| captures[1].trim().to_string())
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect()
 }
 
 impl Default for RequestPanel {
     fn default() -> Self {
         Self {
             selected_tab: RequestTab::Body,
+            request_tabs: BTreeMap::new(),
+            scroll_offset: AbsoluteOffset::default(),
+            scroll_offsets: HashMap::new(),
             url_input: UndoableInput::new_empty(
                 iced::widget::Id::new(crate::constant::URL_INPUT_ID),
                 "Enter URL...".to_string(),
@@ -136,13 +354,22 @@ impl Default for RequestPanel {
             body_format_menu_open: false,
             send_button_hovered: false,
             cancel_button_hovered: false,
-
-            script_editor_content: text_editor::Content::new(),
+            script_editor: UndoableEditor::new_empty(iced::widget::Id::new(POST_SCRIPT_EDITOR_ID))
+                .comment_prefix("//"),
             show_search: false,
             search_query: String::new(),
+            search_regex: false,
             search_input_id: iced::widget::Id::unique(),
             search_selection: None,
             history_registry: HistoryRegistry::new(),
+            reveal_auth_secrets: false,
+            show_field_docs: false,
+            row_drag: None,
+            pasted_request: None,
+            versions: None,
+            sent_bodies: HashMap::new(),
+            show_body_diff: false,
+            body_diff: None,
         }
     }
 }
@@ -152,13 +379,84 @@ impl RequestPanel {
         Self::default()
     }
 
-    pub fn reset_undo_histories(&mut self, current_url: &str, current_body: &str) {
+    /// What the find bar looks for, or why its regular expression is invalid
+    pub fn search_pattern(&self) -> Result<Pattern, String> {
+        Pattern::new(&self.search_query, self.search_regex)
+    }
+
+    /// Starts fresh URL, body and script histories, e.g. when another request is opened
+    pub fn reset_undo_histories(&mut self, request: &RequestConfig) {
         self.history_registry.clear();
-        self.url_input.set_value(current_url.to_string());
+        self.pasted_request = None;
+        self.url_input.set_value(request.url.clone());
         self.body_editor = UndoableEditor::new(
-            iced::widget::Id::new(crate::constant::REQUEST_BODY_EDITOR_ID),
-            current_body.to_string(),
+            iced::widget::Id::new(REQUEST_BODY_EDITOR_ID),
+            request.body.clone(),
         );
+        self.script_editor = UndoableEditor::new(
+            iced::widget::Id::new(POST_SCRIPT_EDITOR_ID),
+            request.post_request_script.clone().unwrap_or_default(),
+        )
+        .comment_prefix("//");
+    }
+
+    /// Remembers the tab and scroll position of the request left, then shows the tab `to`
+    /// was left on, scrolled as it was. A request not opened before keeps the tab shown.
+    pub fn switch_request(&mut self, from: &str, to: &str) -> Task<Message> {
+        if from == to {
+            return Task::none();
+        }
+        self.row_drag = None;
+        if !from.is_empty() {
+            self.request_tabs
+                .insert(from.to_string(), self.selected_tab.clone());
+            self.scroll_offsets
+                .insert(from.to_string(), self.scroll_offset);
+        }
+
+        if let Some(tab) = self.request_tabs.get(to) {
+            self.selected_tab = tab.clone();
+        }
+        self.scroll_offset = self.scroll_offsets.get(to).copied().unwrap_or_default();
+        iced::widget::operation::scroll_to(
+            tab_scrollable_id(&self.selected_tab),
+            self.scroll_offset,
+        )
+        .map(|_: ()| Message::DoNothing)
+    }
+
+    /// Keeps the undo baselines in step with a request changed outside the URL input and
+    /// body editor, so that the change can be undone and the next keystroke is diffed
+    /// against the current text
+    pub fn sync_undo_histories(&mut self, request: &RequestConfig) {
+        self.url_input
+            .sync_value(&request.url, &mut self.history_registry);
+        self.body_editor
+            .sync_text(&request.body, &mut self.history_registry);
+    }
+
+    /// Works out the diff of the body changes again if they're shown and the current or last
+    /// sent body of `request` differs from the one the diff was made from
+    pub fn refresh_body_diff(&mut self, request: &RequestConfig) {
+        let sent = self
+            .sent_bodies
+            .get(&request.id)
+            .filter(|sent| self.show_body_diff && **sent != request.body);
+        let Some(sent) = sent else {
+            self.body_diff = None;
+            return;
+        };
+        let up_to_date = self.body_diff.as_ref().is_some_and(|diff| {
+            diff.request_id == request.id && diff.sent == *sent && diff.current == request.body
+        });
+        if !up_to_date {
+            self.body_diff = Some(BodyDiff {
+                request_id: request.id.clone(),
+                sent: sent.clone(),
+                current: request.body.clone(),
+                lines: line_diff(sent, &request.body),
+            });
+        }
     }
 
     pub fn update<'a>(
@@ -167,11 +465,22 @@ impl RequestPanel {
         current_request: &RequestConfig,
         environments: &'a Vec<Environment>,
         request_body_content: &mut text_editor::Content,
+        post_script_content: &mut text_editor::Content,
     ) -> Action {
         match message {
             Message::UrlInputMessage(msg) => {
+                // The URL may have been changed elsewhere (params, imports) since the last
+                // keystroke, diff against what is on screen rather than a stale baseline
+                self.url_input
+                    .sync_value(&current_request.url, &mut self.history_registry);
+                let pasted = matches!(msg, undoable_input::Message::Pasted(_));
                 let (new_url, task) = self.url_input.update(msg, &mut self.history_registry);
-                let mapped_task = task.map(Message::UrlInputMessage);
+                let mut mapped_task = task.map(Message::UrlInputMessage);
+                if pasted {
+                    // The input drops line breaks from pasted text, read the original
+                    mapped_task =
+                        Task::batch([mapped_task, iced::clipboard::read().map(Message::UrlPasted)]);
+                }
                 if let Some(new_url) = new_url {
                     let mut request = current_request.clone();
                     request.url = new_url;
@@ -180,6 +489,23 @@ impl RequestPanel {
                     Action::Run(mapped_task)
                 }
             }
+            Message::UrlPasted(text) => {
+                self.pasted_request = text.as_deref().and_then(paste::parse);
+                Action::None
+            }
+            Message::ApplyPastedRequest => {
+                if let Some(pasted) = self.pasted_request.take() {
+                    let mut request = current_request.clone();
+                    pasted.apply_to(&mut request);
+                    Action::UpdateCurrentRequest(request)
+                } else {
+                    Action::None
+                }
+            }
+            Message::DismissPastedRequest => {
+                self.pasted_request = None;
+                Action::None
+            }
             Message::EditorMessage(msg) => {
                 if let undoable_editor::Message::Find = msg {
                     self.show_search = true;
@@ -214,13 +540,33 @@ impl RequestPanel {
                 Action::None
             }
             Message::TabSelected(tab) => {
+                let load_versions = tab == RequestTab::History;
                 self.selected_tab = tab;
+                self.scroll_offset = AbsoluteOffset::default();
+                if load_versions {
+                    self.versions = None;
+                    Action::LoadVersions
+                } else {
+                    Action::None
+                }
+            }
+            Message::TabScrolled(offset) => {
+                self.scroll_offset = offset;
+                Action::None
+            }
+            Message::VersionsLoaded(request_id, versions) => {
+                // Versions of a request that's no longer open are dropped
+                if request_id == current_request.id {
+                    self.versions = Some(versions);
+                }
                 Action::None
             }
+            Message::RestoreVersion(hash) => Action::RestoreVersion(hash),
             Message::HeaderKeyChanged(index, key) => {
                 let mut request = current_request.clone();
                 if let Some(header) = request.headers.get_mut(index) {
-                    header.0 = key;
+                    let old_key = std::mem::replace(&mut header.0, key.clone());
+                    rename_field_doc(&mut request.header_docs, &request.headers, &old_key, &key);
                 }
                 // Auto-append a new empty row when the user starts typing in the last row.
                 let last = request.headers.len().saturating_sub(1);
@@ -274,14 +620,27 @@ impl RequestPanel {
             Message::RemoveHeader(index) => {
                 let mut request = current_request.clone();
                 if index < request.headers.len() {
-                    request.headers.remove(index);
+                    let (key, _) = request.headers.remove(index);
+                    rename_field_doc(&mut request.header_docs, &request.headers, &key, "");
                 }
                 Action::UpdateCurrentRequest(request)
             }
+            Message::OpenHeaderDocs(name) => Action::OpenHeaderDocs(name),
+            Message::ToggleBodyDiff => {
+                self.show_body_diff = !self.show_body_diff;
+                self.refresh_body_diff(current_request);
+                Action::None
+            }
+            Message::FixDuplicateHeader(name, fix) => {
+                let mut request = current_request.clone();
+                duplicate_headers::fix(&mut request.headers, &name, fix);
+                Action::UpdateCurrentRequest(request)
+            }
             Message::ParamKeyChanged(index, key) => {
                 let mut request = current_request.clone();
                 if let Some(param) = request.params.get_mut(index) {
-                    param.0 = key;
+                    let old_key = std::mem::replace(&mut param.0, key.clone());
+                    rename_field_doc(&mut request.param_docs, &request.params, &old_key, &key);
                 }
                 // Auto-append a new empty row when the user starts typing in the last row.
                 let last = request.params.len().saturating_sub(1);
@@ -336,13 +695,104 @@ impl RequestPanel {
                 let task = iced::widget::operation::focus(id).map(|_: ()| Message::DoNothing);
                 Action::UpdateCurrentRequestAndRun(request, task)
             }
+            Message::PathVariableChanged(name, value) => {
+                let mut request = current_request.clone();
+                match request
+                    .path_variables
+                    .iter_mut()
+                    .find(|(key, _)| *key == name)
+                {
+                    Some(variable) => variable.1 = value,
+                    None => request.path_variables.push((name, value)),
+                }
+                // Forget variables no longer in the URL
+                let names = path_variable_names(&request.url);
+                request
+                    .path_variables
+                    .retain(|(key, _)| names.contains(key));
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::ParamTypeChanged(index, value_type) => {
+                let mut request = current_request.clone();
+                if let Some((key, _)) = current_request.params.get(index) {
+                    edit_field_doc(&mut request.param_docs, key, |doc| {
+                        doc.value_type = value_type
+                    });
+                }
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::ParamDescriptionChanged(index, description) => {
+                let mut request = current_request.clone();
+                if let Some((key, _)) = current_request.params.get(index) {
+                    edit_field_doc(&mut request.param_docs, key, |doc| {
+                        doc.description = description
+                    });
+                }
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::HeaderTypeChanged(index, value_type) => {
+                let mut request = current_request.clone();
+                if let Some((key, _)) = current_request.headers.get(index) {
+                    edit_field_doc(&mut request.header_docs, key, |doc| {
+                        doc.value_type = value_type
+                    });
+                }
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::HeaderDescriptionChanged(index, description) => {
+                let mut request = current_request.clone();
+                if let Some((key, _)) = current_request.headers.get(index) {
+                    edit_field_doc(&mut request.header_docs, key, |doc| {
+                        doc.description = description
+                    });
+                }
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::ToggleFieldDocs(show) => {
+                self.show_field_docs = show;
+                Action::None
+            }
             Message::RemoveParam(index) => {
                 let mut request = current_request.clone();
                 if index < request.params.len() {
-                    request.params.remove(index);
+                    let (key, _) = request.params.remove(index);
+                    rename_field_doc(&mut request.param_docs, &request.params, &key, "");
                 }
                 Action::UpdateCurrentRequest(request)
             }
+            Message::RowDragStarted(table, index) => {
+                self.row_drag = Some(RowDrag {
+                    table,
+                    from: index,
+                    over: index,
+                });
+                Action::None
+            }
+            Message::RowDragOver(index) => {
+                if let Some(drag) = &mut self.row_drag {
+                    drag.over = index;
+                }
+                Action::None
+            }
+            Message::RowDropped => {
+                let Some(drag) = self.row_drag.take() else {
+                    return Action::None;
+                };
+                let mut request = current_request.clone();
+                let rows = match drag.table {
+                    RowTable::Params => &mut request.params,
+                    RowTable::Headers => &mut request.headers,
+                };
+                if move_row(rows, drag.from, drag.over) {
+                    Action::UpdateCurrentRequest(request)
+                } else {
+                    Action::None
+                }
+            }
+            Message::RowDragCancelled => {
+                self.row_drag = None;
+                Action::None
+            }
             Message::BodyChanged(action) => Action::EditRequestBody(action),
             Message::BodyFormatChanged(format) => {
                 let mut request = current_request.clone();
@@ -391,6 +841,45 @@ impl RequestPanel {
                 request.bearer_token = token;
                 Action::UpdateCurrentRequest(request)
             }
+            Message::TokenRequestSelected(option) => {
+                let mut request = current_request.clone();
+                // A bearer token of just `{{name}}` says which variable to refresh
+                if request.token_refresh.variable.is_empty()
+                    && let Some(name) = request
+                        .bearer_token
+                        .trim()
+                        .strip_prefix("{{")
+                        .and_then(|rest| rest.strip_suffix("}}"))
+                {
+                    request.token_refresh.variable = name.trim().to_string();
+                }
+                request.token_refresh.request_id = option.id;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::TokenVariableChanged(variable) => {
+                let mut request = current_request.clone();
+                request.token_refresh.variable = variable;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::TokenPathChanged(path) => {
+                let mut request = current_request.clone();
+                request.token_refresh.token_path = path;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::TokenTtlChanged(ttl) => {
+                let ttl = ttl.trim();
+                let ttl_seconds = if ttl.is_empty() {
+                    0
+                } else {
+                    match ttl.parse() {
+                        Ok(ttl_seconds) => ttl_seconds,
+                        Err(_) => return Action::None,
+                    }
+                };
+                let mut request = current_request.clone();
+                request.token_refresh.ttl_seconds = ttl_seconds;
+                Action::UpdateCurrentRequest(request)
+            }
             Message::BasicUsernameChanged(username) => {
                 let mut request = current_request.clone();
                 request.basic_username = username;
@@ -411,6 +900,202 @@ impl RequestPanel {
                 request.api_key_header = header;
                 Action::UpdateCurrentRequest(request)
             }
+            Message::UnixSocketChanged(path) => {
+                let mut request = current_request.clone();
+                request.unix_socket = path;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::AcceptEncodingToggled(encoding, enabled) => {
+                let mut request = current_request.clone();
+                request.accept_encoding.retain(|e| *e != encoding);
+                if enabled {
+                    request.accept_encoding.push(encoding);
+                }
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::SendQueryAsTypedToggled(enabled) => {
+                let mut request = current_request.clone();
+                request.send_query_as_typed = enabled;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::FollowRedirectsToggled(enabled) => {
+                let mut request = current_request.clone();
+                request.connection.disable_redirects = !enabled;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::UseProxyToggled(enabled) => {
+                let mut request = current_request.clone();
+                request.connection.disable_proxy = !enabled;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::VerifyTlsToggled(enabled) => {
+                let mut request = current_request.clone();
+                request.connection.accept_invalid_certs = !enabled;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::EncodeQueryValues => {
+                let mut request = current_request.clone();
+                request.url = encode_query_values(&request.url);
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::DecodeUrl => {
+                let mut request = current_request.clone();
+                request.url = decode_url(&request.url);
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::DecompressResponseToggled(enabled) => {
+                let mut request = current_request.clone();
+                request.decompress_response = enabled;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::MaxUploadSizeChanged(value) => match parse_size_limit(&value) {
+                Some(limit) => {
+                    let mut request = current_request.clone();
+                    request.max_upload_size = limit;
+                    Action::UpdateCurrentRequest(request)
+                }
+                None => Action::None,
+            },
+            Message::MaxDownloadSizeChanged(value) => match parse_size_limit(&value) {
+                Some(limit) => {
+                    let mut request = current_request.clone();
+                    request.max_download_size = limit;
+                    Action::UpdateCurrentRequest(request)
+                }
+                None => Action::None,
+            },
+            Message::LatencyBudgetChanged(value) => {
+                let value = value.trim();
+                let budget = if value.is_empty() {
+                    Some(0)
+                } else {
+                    value.parse::<u64>().ok()
+                };
+                match budget {
+                    Some(budget) => {
+                        let mut request = current_request.clone();
+                        request.latency_budget = budget;
+                        Action::UpdateCurrentRequest(request)
+                    }
+                    None => Action::None,
+                }
+            }
+            Message::RetryAfterRateLimitToggled(enabled) => {
+                let mut request = current_request.clone();
+                request.retry_after_rate_limit = enabled;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::AuditSecurityHeadersToggled(enabled) => {
+                let mut request = current_request.clone();
+                request.audit_security_headers = enabled;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::MonitorToggled(enabled) => {
+                let mut request = current_request.clone();
+                request.monitor.enabled = enabled;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::MonitorIntervalChanged(value) => {
+                let value = value.trim();
+                let interval = if value.is_empty() {
+                    Some(0)
+                } else {
+                    value.parse::<u64>().ok()
+                };
+                match interval {
+                    Some(interval) => {
+                        let mut request = current_request.clone();
+                        request.monitor.interval_seconds = interval;
+                        Action::UpdateCurrentRequest(request)
+                    }
+                    None => Action::None,
+                }
+            }
+            Message::MonitorExpectedStatusChanged(status) => {
+                let mut request = current_request.clone();
+                request.monitor.expected_status = status;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::PaginationStyleSelected(style) => {
+                let mut request = current_request.clone();
+                if request.pagination.param.is_empty() {
+                    request.pagination.param = match style {
+                        PaginationStyle::Cursor => "cursor",
+                        PaginationStyle::Page => "page",
+                        PaginationStyle::Offset => "offset",
+                        PaginationStyle::None | PaginationStyle::LinkHeader => "",
+                    }
+                    .to_string();
+                }
+                request.pagination.style = style;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::PaginationParamChanged(param) => {
+                let mut request = current_request.clone();
+                request.pagination.param = param;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::PaginationCursorPathChanged(path) => {
+                let mut request = current_request.clone();
+                request.pagination.cursor_path = path;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::PaginationItemsPathChanged(path) => {
+                let mut request = current_request.clone();
+                request.pagination.items_path = path;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::ApiKeyLocationChanged(location) => {
+                let mut request = current_request.clone();
+                request.api_key_location = location;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::HmacAlgorithmChanged(algorithm) => {
+                let mut request = current_request.clone();
+                request.hmac_auth.algorithm = algorithm;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::HmacKeyChanged(key) => {
+                let mut request = current_request.clone();
+                request.hmac_auth.key = key;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::HmacStringToSignChanged(template) => {
+                let mut request = current_request.clone();
+                request.hmac_auth.string_to_sign = template;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::HmacHeaderNameChanged(name) => {
+                let mut request = current_request.clone();
+                request.hmac_auth.header_name = name;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::HmacHeaderValueChanged(value) => {
+                let mut request = current_request.clone();
+                request.hmac_auth.header_value = value;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::HawkIdChanged(id) => {
+                let mut request = current_request.clone();
+                request.hawk_auth.id = id;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::HawkKeyChanged(key) => {
+                let mut request = current_request.clone();
+                request.hawk_auth.key = key;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::HawkExtChanged(ext) => {
+                let mut request = current_request.clone();
+                request.hawk_auth.ext = ext;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::ToggleRevealAuth => {
+                self.reveal_auth_secrets = !self.reveal_auth_secrets;
+                Action::None
+            }
+            Message::InspectToken(token) => Action::InspectToken(token),
+            Message::CheckConnectivity => Action::CheckConnectivity,
             Message::OpenEnvironmentPopup => Action::OpenEnvironmentPopup,
             Message::EnvironmentSelected(index) => {
                 if index < environments.len() {
@@ -419,6 +1104,11 @@ impl RequestPanel {
                     Action::None
                 }
             }
+            Message::ToggleSearchRegex => {
+                self.search_regex = !self.search_regex;
+                self.search_selection = None;
+                Action::Focus(self.search_input_id.clone())
+            }
             Message::SearchQueryChanged(query) => {
                 self.search_query = query;
                 Action::None
@@ -478,38 +1168,125 @@ impl RequestPanel {
                 self.body_format_menu_open = false;
                 Action::None
             }
-            Message::ScriptChanged(action) => Action::EditRequestPostRequestScript(action),
-            Message::DoNothing => Action::None,
-        }
-    }
-
-    pub fn view<'a>(
-        &'a self,
-        current_request: &'a RequestConfig,
-        request_body_content: &'a text_editor::Content,
-        post_script_content: &'a text_editor::Content,
-        is_loading: bool,
-        environments: &'a [Environment],
-        active_environment: Option<usize>,
-    ) -> Element<'a, Message> {
-        // Environment pick_list for the URL row
-        let env_pick_list = {
-            // Create list of environment options including all self.environments plus "Configure"
-            let mut env_options: Vec<String> =
-                environments.iter().map(|env| env.name.clone()).collect();
-            env_options.push("Configure".to_string());
-
-            // Determine the selected value
-            let selected_env = if let Some(active_idx) = active_environment {
-                if let Some(env) = environments.get(active_idx) {
-                    Some(env.name.clone())
+            Message::ScriptEditorMessage(msg) => {
+                if let Some(new_script) =
+                    self.script_editor
+                        .update(msg, post_script_content, &mut self.history_registry)
+                {
+                    let mut request = current_request.clone();
+                    request.post_request_script = Some(new_script);
+                    Action::UpdateCurrentRequest(request)
                 } else {
-                    None
+                    Action::None
                 }
-            } else {
-                None
-            };
-
+            }
+            Message::AssertionStatusChanged(status) => {
+                let mut request = current_request.clone();
+                request.assertions.status = status;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::AssertionHeaderNameChanged(index, name) => {
+                let mut request = current_request.clone();
+                if let Some(header) = request.assertions.headers.get_mut(index) {
+                    header.0 = name;
+                }
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::AssertionHeaderValueChanged(index, value) => {
+                let mut request = current_request.clone();
+                if let Some(header) = request.assertions.headers.get_mut(index) {
+                    header.1 = value;
+                }
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::AddAssertionHeader => {
+                let mut request = current_request.clone();
+                request
+                    .assertions
+                    .headers
+                    .push((String::new(), String::new()));
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::RemoveAssertionHeader(index) => {
+                let mut request = current_request.clone();
+                if index < request.assertions.headers.len() {
+                    request.assertions.headers.remove(index);
+                }
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::AssertionSchemaChanged(action) => Action::EditAssertionSchema(action),
+            Message::RecordSnapshot => match &current_request.last_response {
+                Some(response) if response.status != 0 => {
+                    let mut request = current_request.clone();
+                    request.assertions.snapshot = Some(snapshot::record(response));
+                    Action::UpdateCurrentRequest(request)
+                }
+                _ => Action::None,
+            },
+            Message::ClearSnapshot => {
+                let mut request = current_request.clone();
+                request.assertions.snapshot = None;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::IgnorePathChanged(index, path) => {
+                let mut request = current_request.clone();
+                if let Some(existing) = request.assertions.ignore_paths.get_mut(index) {
+                    *existing = path;
+                }
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::AddIgnorePath => {
+                let mut request = current_request.clone();
+                request.assertions.ignore_paths.push(String::new());
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::RemoveIgnorePath(index) => {
+                let mut request = current_request.clone();
+                if index < request.assertions.ignore_paths.len() {
+                    request.assertions.ignore_paths.remove(index);
+                }
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::DetachContract => {
+                let mut request = current_request.clone();
+                request.contract = None;
+                Action::UpdateCurrentRequest(request)
+            }
+            Message::DoNothing => Action::None,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn view<'a>(
+        &'a self,
+        current_request: &'a RequestConfig,
+        request_body_content: &'a text_editor::Content,
+        post_script_content: &'a text_editor::Content,
+        assertion_schema_content: &'a text_editor::Content,
+        is_loading: bool,
+        environments: &'a [Environment],
+        active_environment: Option<usize>,
+        collections: &'a [RequestCollection],
+        preview: RequestPreview,
+    ) -> Element<'a, Message> {
+        // Environment pick_list for the URL row
+        let env_pick_list = {
+            // Create list of environment options including all self.environments plus "Configure"
+            let mut env_options: Vec<String> =
+                environments.iter().map(|env| env.name.clone()).collect();
+            env_options.push("Configure".to_string());
+
+            // Determine the selected value
+            let selected_env = if let Some(active_idx) = active_environment {
+                if let Some(env) = environments.get(active_idx) {
+                    Some(env.name.clone())
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
             pick_list(env_options, selected_env, |selected| {
                 if selected == "Configure" {
                     Message::OpenEnvironmentPopup
@@ -528,7 +1305,7 @@ impl RequestPanel {
 
         // Environment bar
         let env_bar = row![
-            text("Environment:").size(14),
+            text(tr("request-environment")).size(14),
             space().width(5),
             env_pick_list,
         ]
@@ -552,10 +1329,14 @@ impl RequestPanel {
             };
 
             mouse_area(
-                button(icon(IconName::Cancel).size(16).color(cancel_color))
-                    .padding(8)
-                    .on_press(Message::CancelRequest)
-                    .style(icon_button_style(true)),
+                button(
+                    icon(IconName::Cancel)
+                        .size(accessibility::icon_size(16.0))
+                        .color(accessibility::contrast(cancel_color)),
+                )
+                .padding(accessibility::padding(8.0))
+                .on_press(Message::CancelRequest)
+                .style(icon_button_style(true)),
             )
             .on_enter(Message::CancelButtonHovered(true))
             .on_exit(Message::CancelButtonHovered(false))
@@ -569,24 +1350,42 @@ impl RequestPanel {
 
             if url_valid {
                 mouse_area(
-                    button(icon(IconName::Send).size(16).color(send_color))
-                        .padding(8)
-                        .on_press(Message::ClickSendRequest)
-                        .style(icon_button_style(true)),
+                    button(
+                        icon(IconName::Send)
+                            .size(accessibility::icon_size(16.0))
+                            .color(accessibility::contrast(send_color)),
+                    )
+                    .padding(accessibility::padding(8.0))
+                    .on_press(Message::ClickSendRequest)
+                    .style(icon_button_style(true)),
                 )
                 .on_enter(Message::SendButtonHovered(true))
                 .on_exit(Message::SendButtonHovered(false))
             } else {
                 mouse_area(
-                    button(icon(IconName::Send).size(16).color(send_color))
-                        .padding(8)
-                        .style(icon_button_style(false)),
+                    button(
+                        icon(IconName::Send)
+                            .size(accessibility::icon_size(16.0))
+                            .color(accessibility::contrast(send_color)),
+                    )
+                    .padding(accessibility::padding(8.0))
+                    .style(icon_button_style(false)),
                 )
                 .on_enter(Message::SendButtonHovered(true))
                 .on_exit(Message::SendButtonHovered(false))
             }
         };
 
+        let send_button = accessibility::labeled(
+            send_button,
+            tr(if is_loading {
+                "a11y-cancel"
+            } else {
+                "a11y-send"
+            }),
+            tooltip::Position::Bottom,
+        );
+
         let base_input = container(row![
             method_label,
             self.url_input
@@ -609,9 +1408,55 @@ impl RequestPanel {
             snap: true,
         });
 
-        let connected_input = base_input;
+        let send_as_typed = current_request.send_query_as_typed;
+        let connected_input = ContextMenu::new(base_input, move || {
+            container(
+                column![
+                    button(text(tr("request-encode-query")).size(13))
+                        .on_press(Message::EncodeQueryValues)
+                        .width(Length::Fill)
+                        .style(dropdown_item_style()),
+                    button(text(tr("request-decode-url")).size(13))
+                        .on_press(Message::DecodeUrl)
+                        .width(Length::Fill)
+                        .style(dropdown_item_style()),
+                    button(
+                        text(if send_as_typed {
+                            "✓ Send Query as Typed"
+                        } else {
+                            "Send Query as Typed"
+                        })
+                        .size(13)
+                    )
+                    .on_press(Message::SendQueryAsTypedToggled(!send_as_typed))
+                    .width(Length::Fill)
+                    .style(dropdown_item_style()),
+                ]
+                .spacing(2),
+            )
+            .width(Length::Fixed(200.0))
+            .padding(4)
+            .style(|_theme| container::Style {
+                background: Some(Background::Color(Color::WHITE)),
+                border: Border {
+                    color: Color::from_rgb(0.9, 0.9, 0.9),
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                shadow: Shadow {
+                    color: Color::from_rgba(0.0, 0.0, 0.0, 0.1),
+                    offset: Vector::new(0.0, 2.0),
+                    blur_radius: 4.0,
+                },
+                ..container::Style::default()
+            })
+            .into()
+        });
 
-        let url_row = row![connected_input].align_y(iced::Alignment::Center);
+        let mut url_row = column![row![connected_input].align_y(iced::Alignment::Center)];
+        if let Some(pasted) = &self.pasted_request {
+            url_row = url_row.push(paste_prompt(pasted)).spacing(5);
+        }
 
         // Body tab button (format button moved into body editor overlay)
         // Label: default "Body"; after selection show selected format using Content-Type
@@ -621,7 +1466,7 @@ impl RequestPanel {
                 None => current_request.body_format.to_string(),
             }
         } else {
-            "Body".to_string()
+            tr("tab-body")
         };
 
         let body_tab_button = tab_button(
@@ -633,34 +1478,66 @@ impl RequestPanel {
         let tabs = row![
             body_tab_button,
             tab_button(
-                "Params".to_string(),
+                tr("request-tab-params"),
                 self.selected_tab == RequestTab::Params,
                 RequestTab::Params
             ),
             tab_button(
-                "Headers".to_string(),
+                tr("tab-headers"),
                 self.selected_tab == RequestTab::Headers,
                 RequestTab::Headers
             ),
             tab_button(
-                "Auth".to_string(),
+                tr("request-tab-auth"),
                 self.selected_tab == RequestTab::Auth,
                 RequestTab::Auth
             ),
             tab_button(
-                "Script".to_string(),
+                tr("request-tab-script"),
                 self.selected_tab == RequestTab::PostScript,
                 RequestTab::PostScript
             ),
+            tab_button(
+                tr("request-tab-assertions"),
+                self.selected_tab == RequestTab::Assertions,
+                RequestTab::Assertions
+            ),
+            tab_button(
+                tr("request-tab-settings"),
+                self.selected_tab == RequestTab::Settings,
+                RequestTab::Settings
+            ),
+            tab_button(
+                tr("request-tab-history"),
+                self.selected_tab == RequestTab::History,
+                RequestTab::History
+            ),
         ]
         .spacing(5);
 
         let tab_content = match self.selected_tab {
-            RequestTab::Body => self.body_tab(&request_body_content, current_request.body_format),
-            RequestTab::Params => params_tab(&current_request),
-            RequestTab::Headers => headers_tab(&current_request),
-            RequestTab::Auth => auth_tab(&current_request),
-            RequestTab::PostScript => post_script_tab(&post_script_content),
+            RequestTab::Body => self.body_tab(&request_body_content, current_request),
+            RequestTab::Params => params_tab(
+                current_request,
+                self.show_field_docs,
+                self.row_drag.filter(|drag| drag.table == RowTable::Params),
+            ),
+            RequestTab::Headers => headers_tab(
+                current_request,
+                &preview.auth_headers,
+                self.show_field_docs,
+                self.row_drag.filter(|drag| drag.table == RowTable::Headers),
+            ),
+            RequestTab::Auth => auth_tab(
+                current_request,
+                &preview,
+                self.reveal_auth_secrets,
+                collections,
+            ),
+            RequestTab::PostScript => self.post_script_tab(post_script_content),
+            RequestTab::Assertions => assertions_tab(current_request, assertion_schema_content),
+            RequestTab::Settings => settings_tab(current_request),
+            RequestTab::History => history_tab(&self.versions),
             // RequestTab::Environment => body_tab(&request_body_content); // Fallback to body tab if somehow Environment is selected
         };
 
@@ -671,7 +1548,8 @@ impl RequestPanel {
             space().height(5),
             tabs,
             space().height(5),
-            tab_content
+            container(tab_content).height(Fill),
+            request_footer(current_request, &preview)
         ]
         .spacing(5)
         .padding(15);
@@ -761,11 +1639,12 @@ impl RequestPanel {
     fn body_tab<'a>(
         &'a self,
         request_body: &'a text_editor::Content,
-        body_format: BodyFormat,
+        current_request: &'a RequestConfig,
     ) -> Element<'a, Message> {
+        let body_format = current_request.body_format;
         match body_format {
             BodyFormat::None => container(
-                text("No body")
+                text(tr("request-no-body"))
                     .size(14)
                     .color(Color::from_rgb(0.6, 0.6, 0.6)),
             )
@@ -789,7 +1668,7 @@ impl RequestPanel {
                             request_body,
                             syntax,
                             if self.show_search {
-                                Some(self.search_query.as_str())
+                                self.search_pattern().ok()
                             } else {
                                 None
                             },
@@ -797,7 +1676,8 @@ impl RequestPanel {
                         )
                         .map(Message::EditorMessage),
                 )
-                .id(iced::widget::Id::new(REQUEST_BODY_SCROLLABLE_ID))
+                .id(tab_scrollable_id(&RequestTab::Body))
+                .on_scroll(|viewport| Message::TabScrolled(viewport.absolute_offset()))
                 .height(Length::Fill);
 
                 let format_button = body_format_button();
@@ -808,7 +1688,7 @@ impl RequestPanel {
                         .position(floating_element::AnchorPosition::TopRight)
                         .height(Length::Fill);
 
-                if self.show_search {
+                let editor: Element<'a, Message> = if self.show_search {
                     let search_bar = iced::widget::container(
                         iced::widget::row![
                             iced::widget::text_input("Find", &self.search_query)
@@ -829,6 +1709,10 @@ impl RequestPanel {
                                     value: theme.palette().text,
                                     selection: theme.palette().primary,
                                 }),
+                            crate::ui::response::regex_toggle(
+                                self.search_regex,
+                                Message::ToggleSearchRegex
+                            ),
                             iced::widget::button(
                                 icon(IconName::ChevronDown)
                                     .size(14)
@@ -928,6 +1812,11 @@ impl RequestPanel {
                         ..container::Style::default()
                     });
 
+                    let search_bar =
+                        column![search_bar].push(self.search_pattern().err().map(|error| {
+                            text(error).size(11).color(Color::from_rgb(0.86, 0.2, 0.2))
+                        }));
+
                     floating_element::FloatingElement::new(editor_with_format, search_bar)
                         .offset(iced::Vector::new(10.0, 0.0))
                         .position(floating_element::AnchorPosition::BottomRight)
@@ -935,10 +1824,48 @@ impl RequestPanel {
                         .into()
                 } else {
                     editor_with_format.into()
+                };
+
+                match self
+                    .sent_bodies
+                    .get(&current_request.id)
+                    .filter(|sent| **sent != current_request.body)
+                {
+                    Some(_) => column![
+                        body_changes_bar(self.show_body_diff),
+                        self.body_diff
+                            .as_ref()
+                            .filter(|_| self.show_body_diff)
+                            .map(|diff| body_diff(diff.lines.as_deref())),
+                        editor,
+                    ]
+                    .spacing(6)
+                    .into(),
+                    None => editor,
                 }
             }
         }
     }
+
+    fn post_script_tab<'a>(
+        &'a self,
+        script_content: &'a text_editor::Content,
+    ) -> Element<'a, Message> {
+        tab_scrollable(
+            RequestTab::PostScript,
+            self.script_editor
+                .view(
+                    POST_SCRIPT_EDITOR_ID,
+                    script_content,
+                    Some("javascript"),
+                    None,
+                    None,
+                )
+                .map(Message::ScriptEditorMessage),
+        )
+        .height(Length::Fill)
+        .into()
+    }
 }
 
 fn tab_button<'a>(label: String, is_active: bool, tab: RequestTab) -> Element<'a, Message> {
@@ -1015,11 +1942,141 @@ fn tab_button<'a>(label: String, is_active: bool, tab: RequestTab) -> Element<'a
         .into()
 }
 
-fn params_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
+/// Notice that the body changed since the request was last sent
+fn body_changes_bar<'a>(showing_diff: bool) -> Element<'a, Message> {
+    row![
+        text(tr("request-body-changed"))
+            .size(12)
+            .color(Color::from_rgb(0.8, 0.5, 0.0)),
+        space().width(Fill),
+        button(
+            text(if showing_diff {
+                "Hide Changes"
+            } else {
+                "Show Changes"
+            })
+            .size(12)
+        )
+        .on_press(Message::ToggleBodyDiff)
+        .padding([2, 8]),
+    ]
+    .spacing(6)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+/// Lines of the body changed since it was sent, with one unchanged line around each change.
+/// None when the bodies were too large to compare.
+fn body_diff<'a>(lines: Option<&'a [DiffLine]>) -> Element<'a, Message> {
+    let Some(lines) = lines else {
+        return text(tr("request-body-too-large-to-compare"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5))
+            .into();
+    };
+
+    let near_change = |index: usize| {
+        lines[index.saturating_sub(1)..(index + 2).min(lines.len())]
+            .iter()
+            .any(DiffLine::is_change)
+    };
+    let mut list = column![].spacing(1);
+    let mut skipped = false;
+    for (index, line) in lines.iter().enumerate() {
+        if !near_change(index) {
+            if !skipped {
+                list = list.push(text("⋯").size(12).color(Color::from_rgb(0.5, 0.5, 0.5)));
+            }
+            skipped = true;
+            continue;
+        }
+        skipped = false;
+
+        let (prefix, content, color, background) = match line {
+            DiffLine::Same(content) => (" ", content, Color::from_rgb(0.4, 0.4, 0.4), None),
+            DiffLine::Removed(content) => (
+                "-",
+                content,
+                Color::from_rgb(0.7, 0.1, 0.1),
+                Some(Color::from_rgb(1.0, 0.93, 0.93)),
+            ),
+            DiffLine::Added(content) => (
+                "+",
+                content,
+                Color::from_rgb(0.1, 0.5, 0.1),
+                Some(Color::from_rgb(0.92, 0.98, 0.92)),
+            ),
+        };
+        list = list.push(
+            container(
+                text(format!("{} {}", prefix, content))
+                    .size(12)
+                    .font(iced::Font::MONOSPACE)
+                    .color(color),
+            )
+            .width(Fill)
+            .style(move |_theme| container::Style {
+                background: background.map(Background::Color),
+                ..container::Style::default()
+            }),
+        );
+    }
+
+    container(scrollable(list).height(Length::Shrink))
+        .max_height(200)
+        .padding(6)
+        .width(Fill)
+        .style(|_theme| container::Style {
+            border: Border {
+                color: Color::from_rgb(0.85, 0.85, 0.85),
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..container::Style::default()
+        })
+        .into()
+}
+
+fn params_tab<'a>(
+    config: &'a RequestConfig,
+    show_docs: bool,
+    drag: Option<RowDrag>,
+) -> Element<'a, Message> {
     let mut content = column![];
 
+    let path_variables = path_variable_names(&config.url);
+    if !path_variables.is_empty() {
+        content = content.push(text(tr("request-path-variables")).size(13));
+        for name in path_variables {
+            let value = config
+                .path_variables
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.as_str())
+                .unwrap_or_default();
+
+            content = content.push(
+                row![
+                    container(text(name.clone()).size(14))
+                        .padding([5, 5])
+                        .width(Length::FillPortion(2)),
+                    text_input("Value", value)
+                        .on_input(move |value| Message::PathVariableChanged(name.clone(), value))
+                        .width(Length::FillPortion(3))
+                        .style(custom_input_style),
+                    Space::new().width(30),
+                ]
+                .spacing(10)
+                .align_y(iced::Alignment::Center),
+            );
+        }
+        content = content.push(text(tr("request-query-parameters")).size(13));
+    }
+    content = content.push(field_docs_toggle(show_docs));
+
     // Determine how many rows to render. We always show at least one empty row.
     let row_count = config.params.len().max(1);
+    let movable = movable_rows(&config.params);
 
     for index in 0..row_count {
         let (key, value) = config
@@ -1038,7 +2095,9 @@ fn params_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
             .width(Length::FillPortion(2))
             .style(custom_input_style);
 
-        let value_input = text_input("Parameter value", value)
+        let doc = config.param_docs.get(key);
+
+        let value_input = text_input(&value_placeholder("Parameter value", doc), value)
             .id(iced::widget::Id::from(format!("param_{}_value", index)))
             .on_input(move |input| Message::ParamValueChanged(index, input))
             .width(Length::FillPortion(3))
@@ -1060,8 +2119,22 @@ fn params_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
             Space::new().width(30).into()
         };
 
-        let param_row: Element<'_, Message> = row![key_input, value_input, delete_button]
-            .spacing(10)
+        let mut param_row = row![
+            row_handle(RowTable::Params, index, index < movable),
+            key_input,
+            value_input
+        ]
+        .spacing(10);
+        if show_docs {
+            param_row = param_row.push(field_doc_inputs(
+                doc,
+                !key.is_empty(),
+                move |value_type| Message::ParamTypeChanged(index, value_type),
+                move |description| Message::ParamDescriptionChanged(index, description),
+            ));
+        }
+        let param_row: Element<'_, Message> = param_row
+            .push(delete_button)
             .align_y(iced::Alignment::Center)
             .into();
 
@@ -1070,10 +2143,26 @@ fn params_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
         // Once the new row appears this row is no longer last, so the overlay disappears.
         let row_element: Element<'_, Message> = if is_last {
             let overlay = row![
-                mouse_area(Space::new().width(Length::Fill).height(Length::Fill))
-                    .on_press(Message::AddParamFocusKey(index)),
-                mouse_area(Space::new().width(Length::Fill).height(Length::Fill))
-                    .on_press(Message::AddParamFocusValue(index)),
+                // Spacer matching the drag handle
+                Space::new().width(ROW_HANDLE_WIDTH),
+                mouse_area(
+                    Space::new()
+                        .width(Length::FillPortion(2))
+                        .height(Length::Fill)
+                )
+                .on_press(Message::AddParamFocusKey(index)),
+                mouse_area(
+                    Space::new()
+                        .width(Length::FillPortion(3))
+                        .height(Length::Fill)
+                )
+                .on_press(Message::AddParamFocusValue(index)),
+                // Leave the type and description inputs reachable
+                Space::new().width(if show_docs {
+                    Length::FillPortion(4)
+                } else {
+                    Length::Shrink
+                }),
                 // Spacer matching the delete-button column — no overlay here.
                 Space::new().width(50),
             ]
@@ -1086,16 +2175,240 @@ fn params_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
             param_row
         };
 
-        content = content.push(row_element);
+        content = content.push(drop_target(row_element, index, drag));
     }
 
-    scrollable(content.spacing(10)).height(Length::Fill).into()
+    tab_scrollable(RequestTab::Params, drag_area(content.spacing(10), drag))
+        .height(Length::Fill)
+        .into()
 }
 
-fn headers_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
-    let mut content = column![];
+const ROW_HANDLE_WIDTH: f32 = 16.0;
+
+/// Handle a param or header row is dragged by, a blank of the same width for the row
+/// still to be typed into
+fn row_handle<'a>(table: RowTable, index: usize, movable: bool) -> Element<'a, Message> {
+    if !movable {
+        return Space::new().width(ROW_HANDLE_WIDTH).into();
+    }
+    mouse_area(
+        icon(IconName::GripVertical)
+            .size(ROW_HANDLE_WIDTH)
+            .color(Color::from_rgb(0.6, 0.6, 0.6)),
+    )
+    .on_press(Message::RowDragStarted(table, index))
+    .interaction(iced::mouse::Interaction::Grab)
+    .into()
+}
+
+/// A param or header row, marked while a dragged row would be dropped on it
+fn drop_target<'a>(
+    content: Element<'a, Message>,
+    index: usize,
+    drag: Option<RowDrag>,
+) -> Element<'a, Message> {
+    let target = drag.is_some_and(|drag| drag.over == index && drag.from != index);
+    let mut area = mouse_area(container(content).style(move |_theme| {
+        if target {
+            container::Style {
+                border: Border {
+                    color: Color::from_rgb(0.4, 0.6, 0.9),
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                ..container::Style::default()
+            }
+        } else {
+            container::Style::default()
+        }
+    }));
+    if drag.is_some() {
+        area = area.on_enter(Message::RowDragOver(index));
+    }
+    area.into()
+}
+
+/// The rows of a tab, dropping the dragged row when the button is released and giving up
+/// the drag when the pointer leaves
+fn drag_area<'a>(
+    content: impl Into<Element<'a, Message>>,
+    drag: Option<RowDrag>,
+) -> Element<'a, Message> {
+    let mut area = mouse_area(content);
+    if drag.is_some() {
+        area = area
+            .on_release(Message::RowDropped)
+            .on_exit(Message::RowDragCancelled);
+    }
+    area.into()
+}
+
+/// Number of rows that can be moved, all but the empty row kept last to type into
+fn movable_rows(rows: &[(String, String)]) -> usize {
+    match rows.last() {
+        Some((key, value)) if key.is_empty() && value.is_empty() => rows.len() - 1,
+        _ => rows.len(),
+    }
+}
+
+/// Moves the row at `from` to `to`, keeping the empty row last. Returns whether the order
+/// changed.
+fn move_row(rows: &mut Vec<(String, String)>, from: usize, to: usize) -> bool {
+    let movable = movable_rows(rows);
+    if from >= movable {
+        return false;
+    }
+    let to = to.min(movable - 1);
+    if from == to {
+        return false;
+    }
+    let row = rows.remove(from);
+    rows.insert(to, row);
+    true
+}
+
+/// Standard headers documented on MDN, as the page for each is named
+const STANDARD_HEADERS: &[&str] = &[
+    "Accept",
+    "Accept-Encoding",
+    "Accept-Language",
+    "Accept-Ranges",
+    "Access-Control-Allow-Credentials",
+    "Access-Control-Allow-Headers",
+    "Access-Control-Allow-Methods",
+    "Access-Control-Allow-Origin",
+    "Access-Control-Expose-Headers",
+    "Access-Control-Max-Age",
+    "Access-Control-Request-Headers",
+    "Access-Control-Request-Method",
+    "Age",
+    "Allow",
+    "Authorization",
+    "Cache-Control",
+    "Connection",
+    "Content-Disposition",
+    "Content-Encoding",
+    "Content-Language",
+    "Content-Length",
+    "Content-Location",
+    "Content-Range",
+    "Content-Security-Policy",
+    "Content-Type",
+    "Cookie",
+    "Date",
+    "ETag",
+    "Expect",
+    "Expires",
+    "Forwarded",
+    "From",
+    "Host",
+    "If-Match",
+    "If-Modified-Since",
+    "If-None-Match",
+    "If-Range",
+    "If-Unmodified-Since",
+    "Keep-Alive",
+    "Last-Modified",
+    "Link",
+    "Location",
+    "Origin",
+    "Pragma",
+    "Proxy-Authorization",
+    "Range",
+    "Referer",
+    "Retry-After",
+    "Server",
+    "Set-Cookie",
+    "TE",
+    "Trailer",
+    "Transfer-Encoding",
+    "Upgrade",
+    "User-Agent",
+    "Vary",
+    "Via",
+    "WWW-Authenticate",
+    "X-Forwarded-For",
+    "X-Forwarded-Host",
+    "X-Forwarded-Proto",
+    "X-Requested-With",
+];
+
+/// Reference documentation for a standard header, None for the others
+pub fn header_docs_url(name: &str) -> Option<String> {
+    let name = STANDARD_HEADERS
+        .iter()
+        .find(|standard| standard.eq_ignore_ascii_case(name.trim()))?;
+    Some(format!(
+        "https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/{}",
+        name
+    ))
+}
+
+/// Info icon opening the documentation of a standard header, a blank of the same width for
+/// the others
+fn header_docs_link<'a>(name: &str) -> Element<'a, Message> {
+    if header_docs_url(name).is_none() {
+        return Space::new().width(30).into();
+    }
+    tooltip(
+        button(
+            icon(IconName::Info)
+                .size(24)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        )
+        .on_press(Message::OpenHeaderDocs(name.trim().to_string()))
+        .width(Length::Fixed(30.0))
+        .height(Length::Fixed(30.0))
+        .padding(Padding::from(6.0))
+        .style(icon_button_style(true)),
+        container(text(format!("{} on MDN", name.trim())).size(12))
+            .padding([4, 8])
+            .style(container::rounded_box),
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
+/// Warning about a header sent more than once, with the ways to send it once
+fn duplicate_header_warning<'a>(duplicate: duplicate_headers::Duplicate) -> Element<'a, Message> {
+    let fix_button = |label: &'a str, fix: Fix| {
+        button(text(label).size(12))
+            .on_press(Message::FixDuplicateHeader(duplicate.name.clone(), fix))
+            .padding([2, 8])
+    };
+
+    let mut warning = row![
+        text(duplicate.summary())
+            .size(12)
+            .color(Color::from_rgb(0.8, 0.5, 0.0)),
+        space().width(Fill),
+    ]
+    .spacing(6)
+    .align_y(iced::Alignment::Center);
+    if duplicate.rows.len() > 1 {
+        warning = warning
+            .push(fix_button("Merge", Fix::Merge))
+            .push(fix_button("Keep First", Fix::KeepFirst));
+    }
+    if duplicate.auth {
+        warning = warning.push(fix_button("Use Auth Settings", Fix::UseAuth));
+    }
+    warning.into()
+}
+
+fn headers_tab<'a>(
+    config: &'a RequestConfig,
+    auth_headers: &[(String, String)],
+    show_docs: bool,
+    drag: Option<RowDrag>,
+) -> Element<'a, Message> {
+    let mut content = column![field_docs_toggle(show_docs)];
+    for duplicate in duplicate_headers::find(&config.headers, auth_headers) {
+        content = content.push(duplicate_header_warning(duplicate));
+    }
 
     let row_count = config.headers.len().max(1);
+    let movable = movable_rows(&config.headers);
 
     for index in 0..row_count {
         let (key, value) = config
@@ -1112,7 +2425,9 @@ fn headers_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
             .width(Length::FillPortion(2))
             .style(custom_input_style);
 
-        let value_input = text_input("Header value", value)
+        let doc = config.header_docs.get(key);
+
+        let value_input = text_input(&value_placeholder("Header value", doc), value)
             .id(iced::widget::Id::from(format!("header_{}_value", index)))
             .on_input(move |input| Message::HeaderValueChanged(index, input))
             .width(Length::FillPortion(3))
@@ -1134,17 +2449,50 @@ fn headers_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
             Space::new().width(30).into()
         };
 
-        let header_row: Element<'_, Message> = row![key_input, value_input, delete_button]
-            .spacing(10)
+        let mut header_row = row![
+            row_handle(RowTable::Headers, index, index < movable),
+            key_input,
+            value_input,
+            header_docs_link(key)
+        ]
+        .spacing(10);
+        if show_docs {
+            header_row = header_row.push(field_doc_inputs(
+                doc,
+                !key.is_empty(),
+                move |value_type| Message::HeaderTypeChanged(index, value_type),
+                move |description| Message::HeaderDescriptionChanged(index, description),
+            ));
+        }
+        let header_row: Element<'_, Message> = header_row
+            .push(delete_button)
             .align_y(iced::Alignment::Center)
             .into();
 
         let row_element: Element<'_, Message> = if is_last {
             let overlay = row![
-                mouse_area(Space::new().width(Length::Fill).height(Length::Fill))
-                    .on_press(Message::AddHeaderFocusKey(index)),
-                mouse_area(Space::new().width(Length::Fill).height(Length::Fill))
-                    .on_press(Message::AddHeaderFocusValue(index)),
+                // Spacer matching the drag handle
+                Space::new().width(ROW_HANDLE_WIDTH),
+                mouse_area(
+                    Space::new()
+                        .width(Length::FillPortion(2))
+                        .height(Length::Fill)
+                )
+                .on_press(Message::AddHeaderFocusKey(index)),
+                mouse_area(
+                    Space::new()
+                        .width(Length::FillPortion(3))
+                        .height(Length::Fill)
+                )
+                .on_press(Message::AddHeaderFocusValue(index)),
+                // Spacer matching the docs link
+                Space::new().width(30),
+                // Leave the type and description inputs reachable
+                Space::new().width(if show_docs {
+                    Length::FillPortion(4)
+                } else {
+                    Length::Shrink
+                }),
                 Space::new().width(50),
             ]
             .spacing(10)
@@ -1156,10 +2504,114 @@ fn headers_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
             header_row
         };
 
-        content = content.push(row_element);
+        content = content.push(drop_target(row_element, index, drag));
+    }
+
+    tab_scrollable(RequestTab::Headers, drag_area(content.spacing(10), drag))
+        .height(Length::Fill)
+        .into()
+}
+
+/// The scrollable of a tab's content, by id so the position a request was left at can be
+/// restored
+fn tab_scrollable_id(tab: &RequestTab) -> iced::widget::Id {
+    match tab {
+        RequestTab::Body => iced::widget::Id::new(REQUEST_BODY_SCROLLABLE_ID),
+        tab => iced::widget::Id::from(format!("request-tab-{:?}-scrollable", tab)),
+    }
+}
+
+fn tab_scrollable<'a>(
+    tab: RequestTab,
+    content: impl Into<Element<'a, Message>>,
+) -> iced::widget::Scrollable<'a, Message> {
+    scrollable(content)
+        .id(tab_scrollable_id(&tab))
+        .on_scroll(|viewport| Message::TabScrolled(viewport.absolute_offset()))
+}
+
+/// Moves the docs of a renamed or removed param or header, unless another row still uses
+/// the old name. An empty `new` name drops them.
+fn rename_field_doc(
+    docs: &mut BTreeMap<String, FieldDoc>,
+    fields: &[(String, String)],
+    old: &str,
+    new: &str,
+) {
+    if old == new || fields.iter().any(|(key, _)| key == old) {
+        return;
+    }
+
+    if let Some(doc) = docs.remove(old)
+        && !new.is_empty()
+    {
+        docs.entry(new.to_string()).or_insert(doc);
+    }
+}
+
+/// Edits the docs of the named param or header, dropping them once back to the defaults
+fn edit_field_doc(
+    docs: &mut BTreeMap<String, FieldDoc>,
+    key: &str,
+    edit: impl FnOnce(&mut FieldDoc),
+) {
+    if key.is_empty() {
+        return;
+    }
+
+    let doc = docs.entry(key.to_string()).or_default();
+    edit(doc);
+    if doc.is_empty() {
+        docs.remove(key);
+    }
+}
+
+/// The description, or the type when it isn't a plain string, hints at what to enter
+fn value_placeholder(default: &str, doc: Option<&FieldDoc>) -> String {
+    match doc {
+        Some(doc) if !doc.description.is_empty() => doc.description.clone(),
+        Some(doc) if doc.value_type != ValueType::String => {
+            format!("{} ({})", default, doc.value_type)
+        }
+        _ => default.to_string(),
+    }
+}
+
+fn field_docs_toggle<'a>(show_docs: bool) -> Element<'a, Message> {
+    checkbox(show_docs)
+        .label("Types and descriptions")
+        .size(14)
+        .text_size(12)
+        .on_toggle(Message::ToggleFieldDocs)
+        .into()
+}
+
+/// Type picker and description input of a param or header row, disabled until it has a name
+fn field_doc_inputs<'a>(
+    doc: Option<&FieldDoc>,
+    enabled: bool,
+    on_type: impl Fn(ValueType) -> Message + 'a,
+    on_description: impl Fn(String) -> Message + 'a,
+) -> Element<'a, Message> {
+    let value_type = doc.map(|doc| doc.value_type).unwrap_or_default();
+    let description = doc.map(|doc| doc.description.clone()).unwrap_or_default();
+
+    let type_picker = pick_list(ValueType::ALL, Some(value_type), on_type)
+        .text_size(12)
+        .width(Length::Fixed(90.0));
+    let mut description_input = text_input("Description", &description)
+        .size(12)
+        .width(Length::Fill)
+        .style(custom_input_style);
+    if enabled {
+        description_input = description_input.on_input(on_description);
     }
 
-    scrollable(content.spacing(10)).height(Length::Fill).into()
+    row![type_picker, description_input]
+        .spacing(10)
+        .width(Length::FillPortion(4))
+        .align_y(iced::Alignment::Center)
+        .into()
 }
 
 // Helper function for send/cancel button styling
@@ -1208,15 +2660,23 @@ fn dropdown_item_style() -> impl Fn(&Theme, Status) -> button::Style {
     }
 }
 
-fn auth_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
+fn auth_tab<'a>(
+    config: &'a RequestConfig,
+    preview: &RequestPreview,
+    reveal_secrets: bool,
+    collections: &'a [RequestCollection],
+) -> Element<'a, Message> {
     let auth_type_picker = column![
-        text("Authentication Type"),
+        text(tr("request-auth-type")),
         pick_list(
             vec![
                 AuthType::None,
                 AuthType::Bearer,
                 AuthType::Basic,
-                AuthType::ApiKey
+                AuthType::ApiKey,
+                AuthType::Hmac,
+                AuthType::Hawk,
+                AuthType::InheritFromEnvironment
             ],
             Some(config.auth_type.clone()),
             Message::AuthTypeChanged
@@ -1226,62 +2686,929 @@ fn auth_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
 
     let auth_config = match config.auth_type {
         AuthType::None => {
-            column![text("No authentication required")]
+            column![text(tr("request-auth-none"))]
         }
+        AuthType::InheritFromEnvironment => column![text(tr("request-auth-inherit")).size(14),],
         AuthType::Bearer => column![
-            text("Bearer Token"),
+            text(tr("request-auth-bearer")),
             text_input("Enter bearer token", &config.bearer_token)
                 .on_input(Message::BearerTokenChanged)
                 .width(Fill),
+            space().height(10),
+            token_refresh_view(config, collections),
         ]
         .spacing(5),
         AuthType::Basic => column![
-            text("Basic Authentication"),
-            text("Username"),
+            text(tr("request-auth-basic")),
+            text(tr("request-auth-username")),
             text_input("Enter username", &config.basic_username)
                 .on_input(Message::BasicUsernameChanged)
                 .width(Fill),
-            text("Password"),
+            text(tr("request-auth-password")),
             text_input("Enter password", &config.basic_password)
                 .on_input(Message::BasicPasswordChanged)
                 .width(Fill),
         ]
         .spacing(5),
         AuthType::ApiKey => column![
-            text("API Key Authentication"),
-            text("Header Name"),
-            text_input("Header name (e.g., X-API-Key)", &config.api_key_header)
+            text(tr("request-auth-api-key")),
+            text(tr("request-auth-add-to")),
+            pick_list(
+                vec![ApiKeyLocation::Header, ApiKeyLocation::Query],
+                Some(config.api_key_location),
+                Message::ApiKeyLocationChanged
+            ),
+            text(match config.api_key_location {
+                ApiKeyLocation::Header => "Header Name",
+                ApiKeyLocation::Query => "Query Parameter Name",
+            }),
+            text_input("Key name (e.g., X-API-Key)", &config.api_key_header)
                 .on_input(Message::ApiKeyHeaderChanged)
                 .width(Fill),
-            text("API Key"),
+            text(tr("request-auth-api-key-value")),
             text_input("Enter API key", &config.api_key)
                 .on_input(Message::ApiKeyChanged)
                 .width(Fill),
         ]
         .spacing(5),
-    };
-
-    column![auth_type_picker, space().height(10), auth_config]
-        .spacing(10)
-        .into()
-}
-
-fn method_button(method: &HttpMethod) -> Element<'_, Message> {
-    button(text(method.to_string()))
-        .on_press(Message::ToggleMethodMenu)
-        .padding(Padding::from(7))
-        .width(Length::Fixed(match method {
-            HttpMethod::GET => 40.0,
-            HttpMethod::PUT => 40.0,
-            HttpMethod::POST => 50.0,
-            HttpMethod::HEAD => 50.0,
-            HttpMethod::PATCH => 65.0,
-            HttpMethod::DELETE => 65.0,
-            HttpMethod::OPTIONS => 80.0,
-        }))
-        .style(|theme: &Theme, _status: Status| button::Style {
+        AuthType::Hmac => column![
+            text(tr("request-auth-hmac")),
+            text(tr("request-auth-algorithm")),
+            pick_list(
+                vec![HmacAlgorithm::Sha256, HmacAlgorithm::Sha512],
+                Some(config.hmac_auth.algorithm),
+                Message::HmacAlgorithmChanged
+            ),
+            text(tr("request-auth-secret-key")),
+            text_input("Enter signing key", &config.hmac_auth.key)
+                .on_input(Message::HmacKeyChanged)
+                .width(Fill),
+            text(tr("request-auth-string-to-sign")),
+            text_input(
+                "{method}\\n{path}\\n{date}\\n{body_hash}",
+                &config.hmac_auth.string_to_sign
+            )
+            .on_input(Message::HmacStringToSignChanged)
+            .font(iced::Font::MONOSPACE)
+            .width(Fill),
+            text(tr("request-auth-string-to-sign-hint"))
+                .size(12)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+            text(tr("request-auth-header-name")),
+            text_input("Authorization", &config.hmac_auth.header_name)
+                .on_input(Message::HmacHeaderNameChanged)
+                .width(Fill),
+            text(tr("request-auth-header-value")),
+            text_input("HMAC {signature}", &config.hmac_auth.header_value)
+                .on_input(Message::HmacHeaderValueChanged)
+                .width(Fill),
+        ]
+        .spacing(5),
+        AuthType::Hawk => column![
+            text(tr("request-auth-hawk")),
+            text(tr("request-auth-hawk-id")),
+            text_input("Enter Hawk ID", &config.hawk_auth.id)
+                .on_input(Message::HawkIdChanged)
+                .width(Fill),
+            text(tr("request-auth-hawk-key")),
+            text_input("Enter Hawk key", &config.hawk_auth.key)
+                .on_input(Message::HawkKeyChanged)
+                .width(Fill),
+            text(tr("request-auth-hawk-ext")),
+            text_input("Optional", &config.hawk_auth.ext)
+                .on_input(Message::HawkExtChanged)
+                .width(Fill),
+        ]
+        .spacing(5),
+    };
+
+    column![
+        auth_type_picker,
+        space().height(10),
+        auth_config,
+        space().height(10),
+        computed_auth_view(preview, reveal_secrets)
+    ]
+    .spacing(10)
+    .into()
+}
+
+/// A request that can provide the bearer token, in the token refresh picker
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenRequestOption {
+    pub id: String,
+    pub name: String,
+}
+
+impl std::fmt::Display for TokenRequestOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Links the request to the one returning its token, run first when the token has expired
+fn token_refresh_view<'a>(
+    config: &'a RequestConfig,
+    collections: &'a [RequestCollection],
+) -> Element<'a, Message> {
+    let mut options = vec![TokenRequestOption {
+        id: String::new(),
+        name: "None".to_string(),
+    }];
+    for collection in collections {
+        for request in &collection.requests {
+            if request.id != config.id {
+                options.push(TokenRequestOption {
+                    id: request.id.clone(),
+                    name: format!("{} / {}", collection.name, request.name),
+                });
+            }
+        }
+    }
+
+    let refresh = &config.token_refresh;
+    let selected = options
+        .iter()
+        .find(|option| option.id == refresh.request_id)
+        .cloned();
+
+    let label = |label: &'static str| text(label).size(14).width(Length::Fixed(120.0));
+    let mut form = column![
+        text(tr("request-token-refresh")).size(14),
+        text(tr("request-token-refresh-hint"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        row![
+            label("Token request"),
+            pick_list(options, selected, Message::TokenRequestSelected).text_size(14),
+        ]
+        .align_y(iced::Alignment::Center),
+    ]
+    .spacing(6);
+
+    if !refresh.request_id.is_empty() {
+        form = form
+            .push(
+                row![
+                    label("Variable"),
+                    text_input("token", &refresh.variable)
+                        .on_input(Message::TokenVariableChanged)
+                        .size(14),
+                ]
+                .align_y(iced::Alignment::Center),
+            )
+            .push(
+                row![
+                    label("Token path"),
+                    text_input("access_token", &refresh.token_path)
+                        .on_input(Message::TokenPathChanged)
+                        .size(14),
+                ]
+                .align_y(iced::Alignment::Center),
+            )
+            .push(
+                row![
+                    label("Lifetime (s)"),
+                    text_input(
+                        "For tokens that aren't JWTs, empty if they don't expire",
+                        &if refresh.ttl_seconds == 0 {
+                            String::new()
+                        } else {
+                            refresh.ttl_seconds.to_string()
+                        }
+                    )
+                    .on_input(Message::TokenTtlChanged)
+                    .size(14),
+                ]
+                .align_y(iced::Alignment::Center),
+            );
+    }
+
+    form.into()
+}
+
+/// Read-only list of the headers and query parameters the auth type will add
+fn computed_auth_view<'a>(preview: &RequestPreview, reveal_secrets: bool) -> Element<'a, Message> {
+    let header = row![
+        text(tr("request-computed-headers")).size(14),
+        space().width(Fill),
+        preview.token.clone().map(|token| {
+            button(text(tr("inspect-token")).size(12))
+                .on_press(Message::InspectToken(token))
+                .padding([2, 8])
+        }),
+        button(text(if reveal_secrets { "Hide" } else { "Reveal" }).size(12))
+            .on_press(Message::ToggleRevealAuth)
+            .padding([2, 8]),
+    ]
+    .spacing(6)
+    .align_y(iced::Alignment::Center);
+
+    let entries: Vec<(String, String)> = preview
+        .auth_headers
+        .iter()
+        .map(|(name, value)| (format!("{}:", name), value.clone()))
+        .chain(
+            preview
+                .auth_query
+                .iter()
+                .map(|(name, value)| (format!("?{}=", name), value.clone())),
+        )
+        .collect();
+
+    let mut list = column![].spacing(4);
+    if entries.is_empty() {
+        list = list.push(
+            text(tr("request-no-computed-headers"))
+                .size(12)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+    }
+
+    for (name, value) in entries {
+        let value = if reveal_secrets {
+            value
+        } else {
+            mask_secret(&value)
+        };
+
+        list = list.push(
+            row![
+                text(name).size(12).font(iced::Font::MONOSPACE),
+                text(value).size(12).font(iced::Font::MONOSPACE),
+            ]
+            .spacing(6),
+        );
+    }
+
+    column![header, list].spacing(8).into()
+}
+
+/// Masks a credential, keeping the auth scheme (e.g. "Bearer") visible
+fn mask_secret(value: &str) -> String {
+    match value.split_once(' ') {
+        Some((scheme, secret)) if scheme == "Bearer" || scheme == "Basic" => {
+            format!("{} {}", scheme, "•".repeat(secret.chars().count().min(12)))
+        }
+        _ => "•".repeat(value.chars().count().min(12)),
+    }
+}
+
+/// The saved versions of the request, the latest first, with what each one changed
+fn history_tab(versions: &Option<Result<Vec<VersionChanges>, String>>) -> Element<'_, Message> {
+    let muted = Color::from_rgb(0.5, 0.5, 0.5);
+    let versions = match versions {
+        None => return text(tr("history-loading")).size(13).color(muted).into(),
+        Some(Err(error)) => {
+            return text(error)
+                .size(13)
+                .color(Color::from_rgb(0.82, 0.06, 0.22))
+                .into();
+        }
+        Some(Ok(versions)) if versions.is_empty() => {
+            return text(tr("history-empty")).size(13).color(muted).into();
+        }
+        Some(Ok(versions)) => versions,
+    };
+
+    let mut list = column![].spacing(12);
+    for (index, entry) in versions.iter().enumerate() {
+        let when = chrono::DateTime::parse_from_rfc3339(&entry.version.timestamp)
+            .map(|time| {
+                time.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|_| entry.version.timestamp.clone());
+        // The latest version is what's saved, restoring it would change nothing
+        let action: Element<'_, Message> = if index == 0 {
+            text(tr("history-latest")).size(12).color(muted).into()
+        } else {
+            button(text(tr("history-restore")).size(13))
+                .on_press(Message::RestoreVersion(entry.version.hash.clone()))
+                .padding([4, 8])
+                .style(icon_button_style(true))
+                .into()
+        };
+
+        let mut version = column![
+            row![
+                text(when).size(13).font(iced::Font::MONOSPACE),
+                text(entry.version.hash.chars().take(8).collect::<String>())
+                    .size(12)
+                    .font(iced::Font::MONOSPACE)
+                    .color(muted),
+                space().width(Fill),
+                action,
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+        ]
+        .spacing(4);
+
+        if entry.changes.is_empty() {
+            let note = if index + 1 == versions.len() {
+                tr("history-first")
+            } else {
+                tr("history-no-changes")
+            };
+            version = version.push(text(note).size(12).color(muted));
+        }
+        for change in &entry.changes {
+            let mut field = column![text(&change.field).size(12)].spacing(2);
+            if let Some(before) = &change.before {
+                field = field.push(diff_line('-', before, Color::from_rgb(0.82, 0.06, 0.22)));
+            }
+            if let Some(after) = &change.after {
+                field = field.push(diff_line('+', after, Color::from_rgb(0.0, 0.6, 0.3)));
+            }
+            version = version.push(field);
+        }
+
+        list =
+            list.push(
+                container(version)
+                    .padding(8)
+                    .width(Fill)
+                    .style(|_theme| container::Style {
+                        border: Border {
+                            color: Color::from_rgb(0.9, 0.9, 0.9),
+                            width: 1.0,
+                            radius: 4.0.into(),
+                        },
+                        ..container::Style::default()
+                    }),
+            );
+    }
+
+    column![
+        text(tr_args("history-count", &[("count", &versions.len())]))
+            .size(12)
+            .color(muted),
+        scrollable(list).height(Fill),
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// A value before or after a change, long ones cut short
+fn diff_line<'a>(sign: char, value: &str, color: Color) -> Element<'a, Message> {
+    const MAX_CHARS: usize = 300;
+    let value = match value.char_indices().nth(MAX_CHARS) {
+        Some((end, _)) => format!("{}…", &value[..end]),
+        None => value.to_string(),
+    };
+    text(format!("{} {}", sign, value))
+        .size(12)
+        .font(iced::Font::MONOSPACE)
+        .color(color)
+        .into()
+}
+
+fn assertions_tab<'a>(
+    config: &'a RequestConfig,
+    schema_content: &'a text_editor::Content,
+) -> Element<'a, Message> {
+    let hint = |hint: &'a str| text(hint).size(12).color(Color::from_rgb(0.5, 0.5, 0.5));
+
+    let status = column![
+        text(tr("request-assert-status")),
+        text_input("e.g. 200 or 2xx", &config.assertions.status)
+            .on_input(Message::AssertionStatusChanged)
+            .style(custom_input_style)
+            .width(Fill),
+    ]
+    .spacing(5);
+
+    let mut headers = column![text(tr("tab-headers"))].spacing(5);
+    for (index, (name, value)) in config.assertions.headers.iter().enumerate() {
+        headers = headers.push(
+            row![
+                text_input("Header name", name)
+                    .on_input(move |input| Message::AssertionHeaderNameChanged(index, input))
+                    .width(Length::FillPortion(2))
+                    .style(custom_input_style),
+                text_input("Expected value, * for any", value)
+                    .on_input(move |input| Message::AssertionHeaderValueChanged(index, input))
+                    .width(Length::FillPortion(3))
+                    .style(custom_input_style),
+                button(
+                    icon(IconName::Trash)
+                        .size(24)
+                        .color(Color::from_rgb(0.5, 0.5, 0.5)),
+                )
+                .on_press(Message::RemoveAssertionHeader(index))
+                .width(Length::Fixed(30.0))
+                .height(Length::Fixed(30.0))
+                .padding(Padding::from(6.0))
+                .style(icon_button_style(true)),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+    headers = headers.push(
+        button(text(tr("request-assert-add-header")).size(13))
+            .on_press(Message::AddAssertionHeader)
+            .padding([4, 8])
+            .style(icon_button_style(true)),
+    );
+
+    let schema = column![
+        text(tr("request-assert-schema")),
+        text_editor(schema_content)
+            .on_action(Message::AssertionSchemaChanged)
+            .placeholder("{ \"type\": \"object\", \"required\": [\"id\"] }")
+            .font(iced::Font::MONOSPACE)
+            .size(13)
+            .height(Length::Fixed(200.0)),
+        hint("The response body has to match this schema. Local $refs are supported."),
+    ]
+    .spacing(5);
+
+    let can_record = config
+        .last_response
+        .as_ref()
+        .is_some_and(|response| response.status != 0);
+    let snapshot = match &config.assertions.snapshot {
+        Some(snapshot) => {
+            let recorded_at = chrono::DateTime::parse_from_rfc3339(&snapshot.recorded_at)
+                .map(|time| {
+                    time.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            column![
+                text(tr("request-snapshot")),
+                row![
+                    text(format!(
+                        "Status {} and a {} byte body recorded {}",
+                        snapshot.status,
+                        snapshot.body.len(),
+                        recorded_at
+                    ))
+                    .size(13)
+                    .width(Fill),
+                    button(text(tr("request-snapshot-update")).size(13))
+                        .on_press_maybe(can_record.then_some(Message::RecordSnapshot))
+                        .padding([4, 8])
+                        .style(icon_button_style(true)),
+                    button(text(tr("remove")).size(13))
+                        .on_press(Message::ClearSnapshot)
+                        .padding([4, 8])
+                        .style(icon_button_style(true)),
+                ]
+                .spacing(10)
+                .align_y(iced::Alignment::Center),
+                hint("Responses have to match it. JSON is compared by value, so formatting and key order don't matter."),
+            ]
+            .spacing(5)
+        }
+        None => column![
+            text(tr("request-snapshot")),
+            button(text(tr("request-snapshot-record")).size(13))
+                .on_press_maybe(can_record.then_some(Message::RecordSnapshot))
+                .padding([4, 8])
+                .style(icon_button_style(true)),
+            hint("Later responses are diffed against the recorded status and body."),
+        ]
+        .spacing(5),
+    };
+
+    let mut ignore_paths = column![text(tr("request-snapshot-ignored"))].spacing(5);
+    for (index, path) in config.assertions.ignore_paths.iter().enumerate() {
+        ignore_paths = ignore_paths.push(
+            row![
+                text_input("$.updatedAt", path)
+                    .on_input(move |input| Message::IgnorePathChanged(index, input))
+                    .font(iced::Font::MONOSPACE)
+                    .width(Fill)
+                    .style(custom_input_style),
+                button(
+                    icon(IconName::Trash)
+                        .size(24)
+                        .color(Color::from_rgb(0.5, 0.5, 0.5)),
+                )
+                .on_press(Message::RemoveIgnorePath(index))
+                .width(Length::Fixed(30.0))
+                .height(Length::Fixed(30.0))
+                .padding(Padding::from(6.0))
+                .style(icon_button_style(true)),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+    ignore_paths = ignore_paths.push(
+        button(text(tr("request-snapshot-add-ignored")).size(13))
+            .on_press(Message::AddIgnorePath)
+            .padding([4, 8])
+            .style(icon_button_style(true)),
+    );
+    ignore_paths = ignore_paths.push(hint(
+        "Left out of snapshot comparisons, e.g. $.updatedAt, $.items[*].id or $..timestamp",
+    ));
+
+    let mut content = column![
+        hint("Checked after every send, results show in the response's Tests tab."),
+        status,
+        headers,
+        schema,
+        snapshot,
+        ignore_paths,
+    ]
+    .spacing(15);
+
+    if let Some(contract) = &config.contract {
+        content = content.push(
+            column![
+                text(tr("request-contract")),
+                row![
+                    text(format!(
+                        "Responses are checked against {} from the imported spec.",
+                        contract.operation
+                    ))
+                    .size(13)
+                    .width(Fill),
+                    button(text(tr("request-contract-detach")).size(13))
+                        .on_press(Message::DetachContract)
+                        .padding([4, 8])
+                        .style(icon_button_style(true)),
+                ]
+                .spacing(10)
+                .align_y(iced::Alignment::Center),
+            ]
+            .spacing(5),
+        );
+    }
+
+    tab_scrollable(RequestTab::Assertions, content)
+        .height(Length::Fill)
+        .into()
+}
+
+fn settings_tab<'a>(config: &'a RequestConfig) -> Element<'a, Message> {
+    let limits = column![
+        text(tr("request-max-upload")),
+        text_input("No limit", &size_limit_to_string(config.max_upload_size))
+            .on_input(Message::MaxUploadSizeChanged)
+            .width(Fill),
+        text(tr("request-max-download")),
+        text_input("No limit", &size_limit_to_string(config.max_download_size))
+            .on_input(Message::MaxDownloadSizeChanged)
+            .width(Fill),
+        text(tr("request-size-limit-hint"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    ]
+    .spacing(5);
+
+    let latency_budget = if config.latency_budget == 0 {
+        String::new()
+    } else {
+        config.latency_budget.to_string()
+    };
+    let budget = column![
+        text(tr("request-latency-budget")),
+        text_input("No budget", &latency_budget)
+            .on_input(Message::LatencyBudgetChanged)
+            .width(Fill),
+        text(tr("request-latency-budget-hint"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    ]
+    .spacing(5);
+
+    let retries = column![
+        checkbox(config.retry_after_rate_limit)
+            .label("Retry rate limited responses")
+            .on_toggle(Message::RetryAfterRateLimitToggled),
+        text(format!(
+            "429 and 503 responses with a Retry-After of up to 5 minutes are sent again once it is over, up to {} times.",
+            MAX_RETRIES
+        ))
+        .size(12)
+        .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    ]
+    .spacing(5);
+
+    let security = column![
+        checkbox(config.audit_security_headers)
+            .label("Audit security headers")
+            .on_toggle(Message::AuditSecurityHeadersToggled),
+        text(tr("request-security-headers-hint"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    ]
+    .spacing(5);
+
+    let monitor_interval = if config.monitor.interval_seconds == 0 {
+        String::new()
+    } else {
+        config.monitor.interval_seconds.to_string()
+    };
+    let monitor = column![
+        checkbox(config.monitor.enabled)
+            .label("Monitor in the background")
+            .on_toggle(Message::MonitorToggled),
+    ]
+    .push(config.monitor.enabled.then(|| {
+        column![
+            text(tr("request-monitor-interval")),
+            text_input("300", &monitor_interval)
+                .on_input(Message::MonitorIntervalChanged)
+                .width(Fill),
+            text(tr("request-monitor-status")),
+            text_input("e.g. 200 or 2xx", &config.monitor.expected_status)
+                .on_input(Message::MonitorExpectedStatusChanged)
+                .width(Fill),
+        ]
+        .spacing(5)
+    }))
+    .push(
+        text(tr("request-monitor-hint"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    )
+    .spacing(5);
+
+    let pagination = &config.pagination;
+    let paged_by_param = matches!(
+        pagination.style,
+        PaginationStyle::Cursor | PaginationStyle::Page | PaginationStyle::Offset
+    );
+    let paging = column![
+        text(tr("request-pagination")),
+        pick_list(
+            PaginationStyle::ALL,
+            Some(pagination.style),
+            Message::PaginationStyleSelected
+        ),
+    ]
+    .push(paged_by_param.then(|| {
+        text_input("Query parameter, e.g. page", &pagination.param)
+            .on_input(Message::PaginationParamChanged)
+            .width(Fill)
+    }))
+    .push((pagination.style == PaginationStyle::Cursor).then(|| {
+        text_input("Path to the next cursor, e.g. $.meta.next", &pagination.cursor_path)
+            .on_input(Message::PaginationCursorPathChanged)
+            .width(Fill)
+    }))
+    .push((pagination.style != PaginationStyle::None).then(|| {
+        text_input("Path to the items, e.g. $.data", &pagination.items_path)
+            .on_input(Message::PaginationItemsPathChanged)
+            .width(Fill)
+    }))
+    .push(
+        text(match pagination.style {
+            PaginationStyle::None => "Pick how the API pages its results to fetch the next pages from the response panel.",
+            PaginationStyle::LinkHeader => "The next page is the rel=\"next\" link of the Link header.",
+            PaginationStyle::Cursor => "The cursor read from each page is sent back in the query parameter.",
+            PaginationStyle::Page => "The page number in the query parameter goes up by one, starting from 1.",
+            PaginationStyle::Offset => "The offset in the query parameter goes up by the number of items received.",
+        })
+        .size(12)
+        .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    )
+    .spacing(5);
+
+    let transport = column![
+        text(tr("request-unix-socket")),
+        text_input("e.g. /var/run/docker.sock", &config.unix_socket)
+            .on_input(Message::UnixSocketChanged)
+            .width(Fill),
+        text(tr("request-unix-socket-hint"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    ]
+    .spacing(5);
+
+    let query_encoding = column![
+        checkbox(config.send_query_as_typed)
+            .label("Send query as typed")
+            .on_toggle(Message::SendQueryAsTypedToggled),
+        text(tr("request-query-encoding-hint"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    ]
+    .spacing(5);
+
+    let connection = column![
+        text(tr("request-connection")),
+        checkbox(!config.connection.disable_redirects)
+            .label("Follow redirects")
+            .on_toggle(Message::FollowRedirectsToggled),
+        checkbox(!config.connection.disable_proxy)
+            .label("Use the system proxy")
+            .on_toggle(Message::UseProxyToggled),
+        checkbox(!config.connection.accept_invalid_certs)
+            .label("Verify TLS certificates")
+            .on_toggle(Message::VerifyTlsToggled),
+        text(if config.connection.is_default() {
+            "Applies to this request only."
+        } else {
+            "This request is sent differently from the others, see the unchecked options."
+        })
+        .size(12)
+        .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        button(text(tr("check-connectivity")).size(13))
+            .on_press(Message::CheckConnectivity)
+            .padding([4, 10]),
+    ]
+    .spacing(5);
+
+    let mut encodings = row![].spacing(15);
+    for encoding in ContentEncoding::ALL {
+        encodings = encodings.push(
+            checkbox(config.accept_encoding.contains(&encoding))
+                .label(encoding.to_string())
+                .on_toggle(move |enabled| Message::AcceptEncodingToggled(encoding, enabled)),
+        );
+    }
+
+    let compression = column![
+        text(tr("request-accept-encoding")),
+        encodings,
+        checkbox(config.decompress_response)
+            .label("Decompress responses automatically")
+            .on_toggle(Message::DecompressResponseToggled),
+        text(tr("request-accept-encoding-hint"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    ]
+    .spacing(5);
+
+    tab_scrollable(
+        RequestTab::Settings,
+        column![
+            transport,
+            space().height(10),
+            connection,
+            space().height(10),
+            query_encoding,
+            space().height(10),
+            compression,
+            space().height(10),
+            limits,
+            space().height(10),
+            budget,
+            space().height(10),
+            retries,
+            space().height(10),
+            security,
+            space().height(10),
+            monitor,
+            space().height(10),
+            paging
+        ]
+        .spacing(10),
+    )
+    .into()
+}
+
+/// Parses a size limit entered in KB, an empty value disables the limit. None for a value
+/// that isn't a number or overflows once in bytes.
+fn parse_size_limit(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Some(0);
+    }
+
+    value
+        .parse::<u64>()
+        .ok()
+        .and_then(|kb| kb.checked_mul(1024))
+}
+
+fn size_limit_to_string(limit: u64) -> String {
+    if limit == 0 {
+        String::new()
+    } else {
+        (limit / 1024).to_string()
+    }
+}
+
+fn request_footer<'a>(config: &'a RequestConfig, preview: &RequestPreview) -> Element<'a, Message> {
+    let (headers_size, body_size) = preview.payload_size;
+    let total = headers_size + body_size;
+
+    let exceeds_limit = config.max_upload_size > 0 && total as u64 > config.max_upload_size;
+    let color = if exceeds_limit {
+        Color::from_rgb(0.8, 0.0, 0.0)
+    } else {
+        Color::from_rgb(0.5, 0.5, 0.5)
+    };
+
+    let muted = Color::from_rgb(0.5, 0.5, 0.5);
+    let plural = |count: usize, noun: &str| {
+        format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+    };
+
+    let mut footer = row![
+        text(format!(
+            "Request size: {} (headers {}, body {})",
+            format_bytes(total),
+            format_bytes(headers_size),
+            format_bytes(body_size)
+        ))
+        .size(12)
+        .color(color),
+        text(plural(preview.header_count, "header"))
+            .size(12)
+            .color(muted),
+        text(plural(preview.param_count, "param"))
+            .size(12)
+            .color(muted),
+    ]
+    .spacing(12)
+    .align_y(iced::Alignment::Center);
+
+    let unresolved = &preview.unresolved_variables;
+    if !unresolved.is_empty() {
+        footer = footer.push(tooltip(
+            text(plural(unresolved.len(), "unresolved variable"))
+                .size(12)
+                .color(Color::from_rgb(0.8, 0.5, 0.0)),
+            container(text(unresolved.join(", ")).size(12))
+                .max_width(320)
+                .padding([4, 8])
+                .style(container::rounded_box),
+            tooltip::Position::Top,
+        ));
+    }
+
+    footer.into()
+}
+
+/// Offers to fill in the request from a pasted curl command or URL with a body
+fn paste_prompt(pasted: &PastedRequest) -> Element<'_, Message> {
+    let mut parts = vec![format!("{} {}", pasted.method, pasted.url)];
+    if !pasted.headers.is_empty() {
+        parts.push(format!("{} headers", pasted.headers.len()));
+    }
+    if !pasted.body.is_empty() {
+        parts.push("body".to_string());
+    }
+    if pasted.basic_auth.is_some() {
+        parts.push("basic auth".to_string());
+    }
+
+    container(
+        row![
+            column![
+                text(format!("Pasted text looks like {}", pasted.source.label())).size(13),
+                text(parts.join(", "))
+                    .size(11)
+                    .color(Color::from_rgb(0.45, 0.45, 0.45)),
+            ]
+            .width(Length::Fill),
+            button(text(tr("request-paste-fill")).size(12))
+                .on_press(Message::ApplyPastedRequest)
+                .padding([4, 10]),
+            button(text(tr("request-paste-keep")).size(12))
+                .on_press(Message::DismissPastedRequest)
+                .padding([4, 10])
+                .style(button::secondary),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center),
+    )
+    .padding([6, 10])
+    .style(|_theme| container::Style {
+        background: Some(Background::Color(Color::from_rgb(0.93, 0.95, 1.0))),
+        border: Border {
+            color: Color::from_rgb(0.75, 0.8, 0.95),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..container::Style::default()
+    })
+    .into()
+}
+
+fn method_button(method: &HttpMethod) -> Element<'_, Message> {
+    let color = method_color(method);
+
+    let padding = accessibility::padding(7.0);
+    let control = button(text(method.to_string()))
+        .on_press(Message::ToggleMethodMenu)
+        .padding(Padding::from(padding))
+        .width(Length::Fixed(
+            match method {
+                HttpMethod::GET => 40.0,
+                HttpMethod::PUT => 40.0,
+                HttpMethod::POST => 50.0,
+                HttpMethod::HEAD => 50.0,
+                HttpMethod::PATCH => 65.0,
+                HttpMethod::DELETE => 65.0,
+                HttpMethod::OPTIONS => 80.0,
+            } + 2.0 * (padding - 7.0),
+        ))
+        .style(move |theme: &Theme, _status: Status| button::Style {
             background: Some(Background::Color(theme.palette().background)),
-            text_color: theme.palette().text,
+            text_color: color,
             border: Border {
                 color: Color::TRANSPARENT,
                 width: 0.0,
@@ -1289,12 +3616,13 @@ fn method_button(method: &HttpMethod) -> Element<'_, Message> {
             },
             shadow: Default::default(),
             snap: true,
-        })
-        .into()
+        });
+
+    accessibility::labeled(control, tr("a11y-method"), tooltip::Position::Bottom)
 }
 
 fn body_format_button() -> Element<'static, Message> {
-    button(
+    let control = button(
         icon(IconName::Indent)
             .size(28)
             .color(Color::from_rgb(0.5, 0.5, 0.5)),
@@ -1324,8 +3652,9 @@ fn body_format_button() -> Element<'static, Message> {
                 ..base
             },
         }
-    })
-    .into()
+    });
+
+    accessibility::labeled(control, tr("a11y-format-body"), tooltip::Position::Left)
 }
 
 fn method_dropdown() -> Element<'static, Message> {
@@ -1344,7 +3673,7 @@ fn method_dropdown() -> Element<'static, Message> {
     let method_buttons: Vec<Element<'static, Message>> = methods
         .iter()
         .map(|method| {
-            button(text(method.to_string()))
+            button(text(method.to_string()).color(method_color(method)))
                 .on_press(Message::MethodChanged(method.clone()))
                 .width(Length::Fixed(90.0))
                 .style(dropdown_item_style())
@@ -1429,53 +3758,6 @@ fn body_format_dropdown() -> Element<'static, Message> {
         .into()
 }
 
-fn post_script_tab<'a>(script_content: &'a text_editor::Content) -> Element<'a, Message> {
-    // let script_content = config
-    //     .post_request_script
-    //     .as_deref()
-    //     .unwrap_or("// No script defined");
-
-    // let help_text = text("Post-request scripts run after receiving a response. Use 'pm' object to access response data and environment variables.")
-    //     .size(12)
-    //     .color(Color::from_rgb(0.6, 0.6, 0.6));
-
-    // let example_text =
-    //     text("Example: pm.environment.set('token', pm.response.json().access_token);")
-    //         .size(11)
-    //         .color(Color::from_rgb(0.5, 0.5, 0.5));
-
-    // let script_display = container(scrollable(text(script_content).size(14)))
-    //     .height(Length::Fill)
-    //     .padding(10)
-    //     .style(|theme: &Theme| container::Style {
-    //         background: Some(Background::Color(Color::from_rgb(0.95, 0.95, 0.95))),
-    //         border: Border {
-    //             color: Color::from_rgb(0.8, 0.8, 0.8),
-    //             width: 1.0,
-    //             radius: 4.0.into(),
-    //         },
-    //         ..Default::default()
-    //     });
-    let script_editor_widget = text_editor(script_content)
-        .highlight("javascript", highlighter::Theme::Base16Mocha)
-        .on_action(Message::ScriptChanged)
-        .placeholder("// Enter your post-request script here...")
-        .style(
-            |theme: &Theme, _status: text_editor::Status| text_editor::Style {
-                background: Background::Color(theme.palette().background),
-                border: Border {
-                    color: Color::from_rgb(0.9, 0.9, 0.9),
-                    width: 1.0,
-                    radius: 4.0.into(),
-                },
-                placeholder: Color::from_rgb(0.6, 0.6, 0.6),
-                value: theme.palette().text,
-                selection: theme.palette().primary,
-            },
-        );
-
-    scrollable(script_editor_widget).height(Length::Fill).into()
-}
 fn custom_input_style(
     theme: &Theme,
     status: iced::widget::text_input::Status,
@@ -1532,3 +3814,87 @@ fn custom_input_style(
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(keys: &[&str]) -> Vec<(String, String)> {
+        keys.iter()
+            .map(|key| (key.to_string(), String::new()))
+            .collect()
+    }
+
+    #[test]
+    fn counts_what_the_request_sends() {
+        let request = RequestConfig {
+            method: HttpMethod::GET,
+            url: "https://{{host}}/users/{{ id }}".to_string(),
+            headers: vec![
+                ("Accept".to_string(), "application/json".to_string()),
+                ("X-Tenant".to_string(), "{{tenant}}".to_string()),
+                (String::new(), String::new()),
+            ],
+            params: vec![("page".to_string(), String::new())],
+            body: "{{ignored}}".to_string(),
+            auth_type: AuthType::Bearer,
+            bearer_token: "{{token}}".to_string(),
+            ..RequestConfig::default()
+        };
+
+        let preview = RequestPreview::new(&request);
+        assert_eq!(preview.header_count, 3);
+        assert_eq!(preview.param_count, 0);
+        assert_eq!(
+            preview.unresolved_variables,
+            vec!["host", "id", "tenant", "token"]
+        );
+    }
+
+    #[test]
+    fn links_standard_headers_to_their_docs() {
+        assert_eq!(
+            header_docs_url(" content-type").as_deref(),
+            Some("https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Type")
+        );
+        assert_eq!(header_docs_url("X-Api-Key"), None);
+        assert_eq!(header_docs_url(""), None);
+    }
+
+    #[test]
+    fn moves_rows_keeping_the_empty_row_last() {
+        let mut headers = rows(&["Accept", "Authorization", "X-Trace", ""]);
+        assert!(move_row(&mut headers, 2, 0));
+        assert_eq!(headers, rows(&["X-Trace", "Accept", "Authorization", ""]));
+
+        // Dropped on the empty row, it goes just above it
+        assert!(move_row(&mut headers, 0, 3));
+        assert_eq!(headers, rows(&["Accept", "Authorization", "X-Trace", ""]));
+
+        assert!(!move_row(&mut headers, 3, 0));
+        assert!(!move_row(&mut headers, 1, 1));
+    }
+
+    #[test]
+    fn remembers_the_tab_and_scroll_position_of_each_request() {
+        let mut panel = RequestPanel::new();
+        panel.selected_tab = RequestTab::Headers;
+        panel.scroll_offset = AbsoluteOffset { x: 0.0, y: 120.0 };
+
+        let _ = panel.switch_request("a", "b");
+        assert_eq!(panel.selected_tab, RequestTab::Headers);
+        assert_eq!(panel.scroll_offset, AbsoluteOffset::default());
+
+        let _ = panel.update(
+            Message::TabSelected(RequestTab::Auth),
+            &RequestConfig::default(),
+            &Vec::new(),
+            &mut text_editor::Content::new(),
+            &mut text_editor::Content::new(),
+        );
+        let _ = panel.switch_request("b", "a");
+        assert_eq!(panel.selected_tab, RequestTab::Headers);
+        assert_eq!(panel.scroll_offset.y, 120.0);
+        assert_eq!(panel.request_tabs["b"], RequestTab::Auth);
+    }
+}
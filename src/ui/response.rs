@@ -1,14 +1,32 @@
-use crate::constant::{RESPONSE_BODY_EDITOR_ID, RESPONSE_BODY_SCROLLABLE_ID};
-use crate::types::{ResponseData, ResponseTab};
+use crate::caching;
+use crate::chart;
+use crate::constant::{
+    RESPONSE_BODY_EDITOR_ID, RESPONSE_BODY_SCROLLABLE_ID, RESPONSE_PREVIEW_SIZE,
+};
+use crate::external::OpenTarget;
+use crate::http::StreamedBody;
+use crate::i18n::{tr, tr_args};
+use crate::ndjson;
+use crate::rate_limit::{self, RateLimit};
+use crate::redact;
+use crate::search::Pattern;
+use crate::security_headers::{Audit, Grade, Outcome};
+use crate::tools;
+use crate::types::{
+    CacheValidators, MockBehavior, ResponseData, ResponseExample, ResponseTab, RunRecord,
+};
+use crate::ui::chart::{Chart, ChartKind};
 use crate::ui::floating_element;
 use crate::ui::undoable_editor::{self, UndoableEditor};
 use crate::ui::{IconName, Spinner, icon};
 use iced::widget::button::Status;
 use iced::widget::container::Style;
 use iced::widget::{
-    button, column, container, row, scrollable, space, text, text_editor, text_input,
+    button, column, container, pick_list, row, scrollable, space, text, text_editor, text_input,
+    tooltip,
 };
 use iced::{Background, Border, Color, Element, Length, Padding, Theme};
+use std::collections::HashSet;
 
 #[derive(Debug)]
 pub enum Action {
@@ -20,6 +38,22 @@ pub enum Action {
     Focus(iced::widget::Id),
     // The components needs to run a task
     Run(iced::Task<Message>),
+    QueueRequest,
+    DownloadAnyway,
+    FetchNextPage,
+    FetchAllPages,
+    CancelRetry,
+    SendConditional,
+    OpenExternally,
+    OpenStatusDocs(u16),
+    ShowFullResponse,
+    InspectToken(String),
+    CheckConnectivity,
+    UpdateRunNote(usize, String),
+    SaveExample,
+    RenameExample(usize, String),
+    UpdateExampleMock(usize, MockBehavior),
+    DeleteExample(usize),
     None,
 }
 
@@ -29,6 +63,7 @@ pub enum Message {
     TabSelected(ResponseTab),
     FormatResponseBody,
     SearchQueryChanged(String),
+    ToggleSearchRegex,
     FindNext,
     FindPrevious,
     SubmitSearch,
@@ -37,6 +72,29 @@ pub enum Message {
     SearchFound(text_editor::Position, text_editor::Position),
     SearchNotFound,
     FocusSearch,
+    QueueRequest,
+    DownloadAnyway,
+    FetchNextPage,
+    FetchAllPages,
+    CancelRetry,
+    SendConditional,
+    OpenExternally,
+    OpenStatusDocs(u16),
+    ShowFullResponse,
+    InspectToken(String),
+    CheckConnectivity,
+    RunNoteChanged(usize, String),
+    SaveExample,
+    ExampleSelected(ExampleOption),
+    ExampleNameChanged(usize, String),
+    ExampleMockChanged(usize, MockBehavior),
+    DeleteExample(usize),
+    HistoryQueryChanged(String),
+    ChartPathChanged(String),
+    ChartKindSelected(ChartKind),
+    RecordToggled(usize),
+    ShowMoreRecords,
+    RevealSecretsToggled,
     DoNothing, // Used to prevent event propagation
     ScrollToMatchResponse(f32),
 }
@@ -47,9 +105,42 @@ pub struct ResponsePanel {
     pub spinner: Spinner,
     pub show_search: bool,
     pub search_query: String,
+    pub search_regex: bool, // The query is a regular expression
     pub search_input_id: iced::widget::Id,
     pub search_selection: Option<(text_editor::Position, text_editor::Position)>,
     pub body_editor: UndoableEditor,
+    pub history_query: String,
+    pub selected_example: usize,
+    pub chart_path: String,
+    pub chart_kind: ChartKind,
+    streamed: Option<StreamedRecords>,
+    visible_records: usize,
+    expanded_records: HashSet<usize>,
+    /// Whether the Request tab shows credentials instead of masking them
+    reveal_secrets: bool,
+}
+
+/// NDJSON records of a response that is still being received
+#[derive(Debug)]
+struct StreamedRecords {
+    source: StreamedBody,
+    /// Complete lines received so far
+    body: String,
+    count: usize,
+    offset: usize,
+}
+
+/// An example in the examples dropdown
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExampleOption {
+    pub index: usize,
+    pub name: String,
+}
+
+impl std::fmt::Display for ExampleOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
 }
 
 impl ResponsePanel {
@@ -59,12 +150,26 @@ impl ResponsePanel {
             spinner: Spinner::new(),
             show_search: false,
             search_query: String::new(),
+            search_regex: false,
             search_input_id: iced::widget::Id::unique(),
             search_selection: None,
             body_editor: UndoableEditor::new_empty(iced::widget::Id::new(RESPONSE_BODY_EDITOR_ID)),
+            history_query: String::new(),
+            selected_example: 0,
+            chart_path: String::new(),
+            chart_kind: ChartKind::default(),
+            streamed: None,
+            visible_records: ndjson::PAGE_SIZE,
+            expanded_records: HashSet::new(),
+            reveal_secrets: false,
         }
     }
 
+    /// What the find bar looks for, or why its regular expression is invalid
+    pub fn search_pattern(&self) -> Result<Pattern, String> {
+        Pattern::new(&self.search_query, self.search_regex)
+    }
+
     pub fn update(&mut self, message: Message, response: &Option<ResponseData>) -> Action {
         match message {
             Message::EditorMessage(editor_message) => {
@@ -132,6 +237,11 @@ impl ResponsePanel {
                 self.search_query = query;
                 Action::None
             }
+            Message::ToggleSearchRegex => {
+                self.search_regex = !self.search_regex;
+                self.search_selection = None;
+                Action::Focus(self.search_input_id.clone())
+            }
             Message::FindNext => Action::SearchNext(self.search_input_id.clone()),
             Message::FindPrevious => Action::SearchPrevious(self.search_input_id.clone()),
             Message::SubmitSearch => Action::SubmitSearch(self.search_input_id.clone()),
@@ -174,17 +284,105 @@ impl ResponsePanel {
                 Action::Focus(self.search_input_id.clone())
             }
             Message::FocusSearch => Action::Focus(self.search_input_id.clone()),
+            Message::QueueRequest => Action::QueueRequest,
+            Message::DownloadAnyway => Action::DownloadAnyway,
+            Message::FetchNextPage => Action::FetchNextPage,
+            Message::FetchAllPages => Action::FetchAllPages,
+            Message::CancelRetry => Action::CancelRetry,
+            Message::SendConditional => Action::SendConditional,
+            Message::OpenExternally => Action::OpenExternally,
+            Message::OpenStatusDocs(status) => Action::OpenStatusDocs(status),
+            Message::ShowFullResponse => Action::ShowFullResponse,
+            Message::InspectToken(token) => Action::InspectToken(token),
+            Message::CheckConnectivity => Action::CheckConnectivity,
+            Message::RunNoteChanged(index, note) => Action::UpdateRunNote(index, note),
+            Message::SaveExample => Action::SaveExample,
+            Message::ExampleSelected(option) => {
+                self.selected_example = option.index;
+                Action::None
+            }
+            Message::ExampleNameChanged(index, name) => Action::RenameExample(index, name),
+            Message::ExampleMockChanged(index, mock) => Action::UpdateExampleMock(index, mock),
+            Message::DeleteExample(index) => {
+                self.selected_example = self.selected_example.saturating_sub(1);
+                Action::DeleteExample(index)
+            }
+            Message::HistoryQueryChanged(query) => {
+                self.history_query = query;
+                Action::None
+            }
+            Message::ChartPathChanged(path) => {
+                self.chart_path = path;
+                Action::None
+            }
+            Message::ChartKindSelected(kind) => {
+                self.chart_kind = kind;
+                Action::None
+            }
+            Message::RecordToggled(index) => {
+                if !self.expanded_records.remove(&index) {
+                    self.expanded_records.insert(index);
+                }
+                Action::None
+            }
+            Message::ShowMoreRecords => {
+                self.visible_records += ndjson::PAGE_SIZE;
+                Action::None
+            }
+            Message::RevealSecretsToggled => {
+                self.reveal_secrets = !self.reveal_secrets;
+                Action::None
+            }
             Message::DoNothing => Action::None,
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn view<'a>(
         &'a self,
         response: &'a Option<ResponseData>,
+        run_history: &'a [RunRecord],
+        examples: &'a [ResponseExample],
+        cache_validators: &'a CacheValidators,
+        secrets: Vec<String>, // Masked in the sent request unless revealed
+        security_audit: Option<Audit>,
         response_body_content: &'a text_editor::Content,
         is_loading: bool,
         elapsed_time: u64,
+        latency_budget: u64,
+        can_queue: bool,
+        can_download_anyway: bool,
+        has_next_page: bool,
+        retry_countdown: Option<(u64, u32)>, // (seconds left, attempt)
+        truncated_body_size: Option<usize>,
     ) -> Element<'a, Message> {
+        if is_loading
+            && let Some(streamed) = self.streamed.as_ref().filter(|streamed| streamed.count > 0)
+        {
+            return column![
+                row![
+                    container(self.spinner.view().map(|_| Message::DoNothing)).padding([0, 3]),
+                    space().width(20),
+                    text(format!(
+                        "Time: {}ms, {} records received",
+                        elapsed_time, streamed.count
+                    ))
+                    .size(14)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5)),
+                ]
+                .align_y(iced::Alignment::Center),
+                records_list(
+                    &streamed.body,
+                    streamed.count,
+                    self.visible_records,
+                    &self.expanded_records
+                ),
+            ]
+            .spacing(10)
+            .padding(10)
+            .into();
+        }
+
         let mut status_row = vec![];
 
         // Add loading indicator if loading (on the left)
@@ -195,81 +393,366 @@ impl ResponsePanel {
         // Add response status or placeholder
         match response {
             Some(resp) => {
-                let status_color = if resp.status >= 200 && resp.status < 300 {
-                    Color::from_rgb(0.0, 0.8, 0.0)
-                } else if resp.status >= 400 {
-                    Color::from_rgb(0.8, 0.0, 0.0)
-                } else {
-                    Color::from_rgb(1.0, 0.6, 0.0)
-                };
+                let status_color = status_color(resp.status);
+
+                let status_badge = container(
+                    text(format!("{} {}", resp.status, resp.status_text))
+                        .color(status_color)
+                        .size(14),
+                )
+                .style(move |_theme| Style {
+                    background: Some(Background::Color(Color::from_rgba(
+                        status_color.r,
+                        status_color.g,
+                        status_color.b,
+                        0.1,
+                    ))),
+                    border: Border {
+                        radius: 4.0.into(),
+                        ..Border::default()
+                    },
+                    ..Style::default()
+                })
+                .padding([4, 8]);
 
                 status_row.push(
-                    container(
-                        text(format!("{} {}", resp.status, resp.status_text))
-                            .color(status_color)
-                            .size(14),
+                    tooltip(
+                        status_badge,
+                        container(text(status_explanation(resp.status)).size(12))
+                            .max_width(320)
+                            .padding([6, 10])
+                            .style(container::rounded_box),
+                        tooltip::Position::Bottom,
                     )
-                    .style(move |_theme| Style {
-                        background: Some(Background::Color(Color::from_rgba(
-                            status_color.r,
-                            status_color.g,
-                            status_color.b,
-                            0.1,
-                        ))),
-                        border: Border {
-                            radius: 4.0.into(),
-                            ..Border::default()
-                        },
-                        ..Style::default()
-                    })
-                    .padding([4, 8])
                     .into(),
                 );
 
                 status_row.push(space().width(20).into());
-                let time_text = if is_loading {
-                    format!("Time: {}ms", elapsed_time)
+                let time = if is_loading { elapsed_time } else { resp.time };
+                let over_budget = latency_budget > 0 && time > latency_budget;
+                let time_text = if over_budget {
+                    format!("Time: {}ms (over the {}ms budget)", time, latency_budget)
                 } else {
-                    format!("Time: {}ms", resp.time)
+                    format!("Time: {}ms", time)
                 };
                 status_row.push(
                     text(time_text)
                         .size(14)
-                        .color(Color::from_rgb(0.5, 0.5, 0.5))
+                        .color(if over_budget {
+                            OVER_BUDGET_COLOR
+                        } else {
+                            Color::from_rgb(0.5, 0.5, 0.5)
+                        })
                         .into(),
                 );
                 status_row.push(space().width(20).into());
+                let size_text = match resp.compressed_size {
+                    Some(compressed) => format!(
+                        "Size: {} ({} compressed)",
+                        format_bytes(resp.size),
+                        format_bytes(compressed)
+                    ),
+                    None => format!("Size: {}", format_bytes(resp.size)),
+                };
                 status_row.push(
-                    text(format!("Size: {}", format_bytes(resp.size)))
+                    text(size_text)
                         .size(14)
                         .color(Color::from_rgb(0.5, 0.5, 0.5))
                         .into(),
                 );
+                if let Some(limit) = rate_limit::parse(&resp.headers, chrono::Utc::now()) {
+                    status_row.push(space().width(20).into());
+                    status_row.push(rate_limit_badge(&limit));
+                }
+                if let Some(audit) = security_audit {
+                    status_row.push(space().width(20).into());
+                    status_row.push(security_badge(audit));
+                }
 
-                let status_info: Element<'_, Message> =
+                let status_info: Element<'_, Message> = row![
                     Element::from(row(status_row).align_y(iced::Alignment::Center))
-                        .map(|_| Message::DoNothing);
+                        .map(|_| Message::DoNothing),
+                    space().width(6),
+                    button(text(tr("response-docs")).size(12))
+                        .on_press(Message::OpenStatusDocs(resp.status))
+                        .padding([2, 6])
+                        .style(|_theme, status| button::Style {
+                            background: None,
+                            text_color: match status {
+                                Status::Hovered => Color::from_rgb(0.2, 0.35, 0.9),
+                                _ => Color::from_rgb(0.5, 0.5, 0.5),
+                            },
+                            ..button::Style::default()
+                        }),
+                ]
+                .push(response_jwt(resp).map(|token| {
+                    button(text(tr("inspect-token")).size(12))
+                        .on_press(Message::InspectToken(token.to_string()))
+                        .padding([2, 6])
+                        .style(|_theme, status| button::Style {
+                            background: None,
+                            text_color: match status {
+                                Status::Hovered => Color::from_rgb(0.2, 0.35, 0.9),
+                                _ => Color::from_rgb(0.5, 0.5, 0.5),
+                            },
+                            ..button::Style::default()
+                        })
+                }))
+                .push((!cache_validators.is_empty() && !is_loading).then(|| {
+                    let mut sent = Vec::new();
+                    if let Some(etag) = &cache_validators.etag {
+                        sent.push(format!("If-None-Match: {}", etag));
+                    }
+                    if let Some(last_modified) = &cache_validators.last_modified {
+                        sent.push(format!("If-Modified-Since: {}", last_modified));
+                    }
+                    tooltip(
+                        button(text(tr("response-send-conditional")).size(12))
+                            .on_press(Message::SendConditional)
+                            .padding([2, 6])
+                            .style(|_theme, status| button::Style {
+                                background: None,
+                                text_color: match status {
+                                    Status::Hovered => Color::from_rgb(0.2, 0.35, 0.9),
+                                    _ => Color::from_rgb(0.5, 0.5, 0.5),
+                                },
+                                ..button::Style::default()
+                            }),
+                        container(text(sent.join("\n")).size(12).font(iced::Font::MONOSPACE))
+                            .padding([6, 10])
+                            .style(container::rounded_box),
+                        tooltip::Position::Bottom,
+                    )
+                }))
+                // Status 0 means the request never got an answer
+                .push((resp.status == 0 && !is_loading).then(|| {
+                    button(text(tr("check-connectivity")).size(12))
+                        .on_press(Message::CheckConnectivity)
+                        .padding([2, 6])
+                        .style(|_theme, status| button::Style {
+                            background: None,
+                            text_color: match status {
+                                Status::Hovered => Color::from_rgb(0.2, 0.35, 0.9),
+                                _ => Color::from_rgb(0.5, 0.5, 0.5),
+                            },
+                            ..button::Style::default()
+                        })
+                }))
+                .align_y(iced::Alignment::Center)
+                .into();
+
+                // HTML pages, PDFs, images etc. are easier to look at in their own app
+                let status_info: Element<'_, Message> = match OpenTarget::for_response(resp) {
+                    Some(target) if !is_loading => row![
+                        status_info,
+                        space().width(Length::Fill),
+                        button(text(target.label()).size(12))
+                            .on_press(Message::OpenExternally)
+                            .padding([2, 8]),
+                    ]
+                    .align_y(iced::Alignment::Center)
+                    .into(),
+                    _ => status_info,
+                };
+
+                // Count down to the retry of a rate limited request, or offer to queue the
+                // request when the network could not be reached
+                let status_info: Element<'_, Message> = if let Some((seconds, attempt)) =
+                    retry_countdown
+                {
+                    column![
+                        status_info,
+                        row![
+                            text(format!(
+                                "Rate limited, retrying in {}s (attempt {} of {}).",
+                                seconds,
+                                attempt,
+                                rate_limit::MAX_RETRIES
+                            ))
+                            .size(12)
+                            .color(OVER_BUDGET_COLOR),
+                            space().width(10),
+                            button(text(tr("response-cancel-retry")).size(12))
+                                .on_press(Message::CancelRetry)
+                                .padding([2, 8]),
+                        ]
+                        .align_y(iced::Alignment::Center),
+                    ]
+                    .spacing(8)
+                    .into()
+                } else if can_queue {
+                    column![
+                        status_info,
+                        row![
+                            text(tr("response-offline"))
+                                .size(12)
+                                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+                            space().width(10),
+                            button(text(tr("response-queue-retry")).size(12))
+                                .on_press(Message::QueueRequest)
+                                .padding([2, 8]),
+                        ]
+                        .align_y(iced::Alignment::Center),
+                    ]
+                    .spacing(8)
+                    .into()
+                } else if can_download_anyway {
+                    column![
+                        status_info,
+                        row![
+                            text(tr("response-too-large"))
+                                .size(12)
+                                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+                            space().width(10),
+                            button(text(tr("response-download-anyway")).size(12))
+                                .on_press(Message::DownloadAnyway)
+                                .padding([2, 8]),
+                        ]
+                        .align_y(iced::Alignment::Center),
+                    ]
+                    .spacing(8)
+                    .into()
+                } else if let Some(full_size) = truncated_body_size {
+                    column![
+                        status_info,
+                        row![
+                            text(format!(
+                                "Showing the first {} of the response.",
+                                format_bytes(RESPONSE_PREVIEW_SIZE)
+                            ))
+                            .size(12)
+                            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+                            space().width(10),
+                            button(
+                                text(format!("Show full response ({})", format_bytes(full_size)))
+                                    .size(12)
+                            )
+                            .on_press(Message::ShowFullResponse)
+                            .padding([2, 8]),
+                        ]
+                        .align_y(iced::Alignment::Center),
+                    ]
+                    .spacing(8)
+                    .into()
+                } else if has_next_page && !is_loading {
+                    column![
+                        status_info,
+                        row![
+                            text(tr("response-more-pages"))
+                                .size(12)
+                                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+                            space().width(10),
+                            button(text(tr("response-next-page")).size(12))
+                                .on_press(Message::FetchNextPage)
+                                .padding([2, 8]),
+                            button(text(tr("response-all-pages")).size(12))
+                                .on_press(Message::FetchAllPages)
+                                .padding([2, 8]),
+                        ]
+                        .spacing(5)
+                        .align_y(iced::Alignment::Center),
+                    ]
+                    .spacing(8)
+                    .into()
+                } else {
+                    status_info
+                };
+
+                // A 304 has no body of its own, say why rather than show an empty one
+                let status_info: Element<'_, Message> = if resp.status == 304 {
+                    column![
+                        status_info,
+                        text(
+                            "Not Modified: the server confirmed the copy matching the validators \
+                             sent is still current, so it sent no body."
+                        )
+                        .size(12)
+                        .color(Color::from_rgb(0.0, 0.5, 0.3)),
+                    ]
+                    .spacing(8)
+                    .into()
+                } else {
+                    status_info
+                };
 
-                let tabs = row![
+                let mut tabs = row![
                     response_tab_button(
-                        "Body",
+                        tr("tab-body"),
                         self.selected_tab == ResponseTab::Body,
                         ResponseTab::Body
                     ),
                     response_tab_button(
-                        "Headers",
+                        tr("tab-headers"),
                         self.selected_tab == ResponseTab::Headers,
                         ResponseTab::Headers
                     ),
+                    response_tab_button(
+                        tr("response-tab-caching"),
+                        self.selected_tab == ResponseTab::Caching,
+                        ResponseTab::Caching
+                    ),
+                    response_tab_button(
+                        tr("response-tab-request"),
+                        self.selected_tab == ResponseTab::Request,
+                        ResponseTab::Request
+                    ),
                 ]
                 .spacing(5);
+                if !resp.test_results.is_empty() {
+                    let passed = resp.test_results.iter().filter(|test| test.passed).count();
+                    tabs = tabs.push(response_tab_button(
+                        tr_args(
+                            "response-tab-tests",
+                            &[("passed", &passed), ("total", &resp.test_results.len())],
+                        ),
+                        self.selected_tab == ResponseTab::Tests,
+                        ResponseTab::Tests,
+                    ));
+                }
+                if !resp.certificates.is_empty() {
+                    tabs = tabs.push(response_tab_button(
+                        tr("response-tab-certificate"),
+                        self.selected_tab == ResponseTab::Certificate,
+                        ResponseTab::Certificate,
+                    ));
+                }
+                if ndjson::is_ndjson(&resp.content_type)
+                    || self.selected_tab == ResponseTab::Records
+                {
+                    tabs = tabs.push(response_tab_button(
+                        tr("response-tab-records"),
+                        self.selected_tab == ResponseTab::Records,
+                        ResponseTab::Records,
+                    ));
+                }
+                if resp.content_type.contains("json") || self.selected_tab == ResponseTab::Chart {
+                    tabs = tabs.push(response_tab_button(
+                        tr("response-tab-chart"),
+                        self.selected_tab == ResponseTab::Chart,
+                        ResponseTab::Chart,
+                    ));
+                }
+                tabs = tabs.push(response_tab_button(
+                    tr("response-tab-history"),
+                    self.selected_tab == ResponseTab::History,
+                    ResponseTab::History,
+                ));
+                tabs = tabs.push(response_tab_button(
+                    if examples.is_empty() {
+                        tr("response-tab-examples")
+                    } else {
+                        tr_args("response-tab-examples-count", &[("count", &examples.len())])
+                    },
+                    self.selected_tab == ResponseTab::Examples,
+                    ResponseTab::Examples,
+                ));
 
                 let tab_content = match self.selected_tab {
                     ResponseTab::Body => self.response_body_tab(resp, response_body_content),
                     ResponseTab::Headers => match response {
                         Some(resp) => response_headers_tab(&resp),
                         None => container(
-                            text("No headers available")
+                            text(tr("response-no-headers"))
                                 .size(14)
                                 .color(Color::from_rgb(0.5, 0.5, 0.5)),
                         )
@@ -277,6 +760,19 @@ impl ResponsePanel {
                         .center_x(Length::Fill)
                         .into(),
                     },
+                    ResponseTab::Caching => caching_tab(resp),
+                    ResponseTab::Request => raw_request_tab(resp, self.reveal_secrets, &secrets),
+                    ResponseTab::Tests => tests_tab(resp),
+                    ResponseTab::Certificate => certificate_tab(resp),
+                    ResponseTab::History => run_history_tab(run_history, &self.history_query),
+                    ResponseTab::Examples => examples_tab(examples, self.selected_example),
+                    ResponseTab::Chart => chart_tab(resp, &self.chart_path, self.chart_kind),
+                    ResponseTab::Records => records_list(
+                        &resp.body,
+                        ndjson::lines(&resp.body).count(),
+                        self.visible_records,
+                        &self.expanded_records,
+                    ),
                 };
 
                 column![
@@ -295,14 +791,14 @@ impl ResponsePanel {
                     container(column![
                         space().height(100),
                         container(
-                            text("No response yet")
+                            text(tr("response-empty"))
                                 .size(16)
                                 .color(Color::from_rgb(0.5, 0.5, 0.5))
                         )
                         .center_x(Length::Fill)
                         .width(Length::Fill),
                         container(
-                            text("Send a request to see the response here")
+                            text(tr("response-empty-hint"))
                                 .size(14)
                                 .color(Color::from_rgb(0.7, 0.7, 0.7))
                         )
@@ -351,7 +847,7 @@ impl ResponsePanel {
             // For binary responses, show metadata instead of content
             let binary_info = column![
                 container(
-                    text("Binary Response")
+                    text(tr("response-binary"))
                         .size(16)
                         .color(Color::from_rgb(0.0, 0.5, 1.0))
                 )
@@ -372,7 +868,7 @@ impl ResponsePanel {
                     .size(14)
                     .color(Color::from_rgb(0.3, 0.3, 0.3)),
                 space().height(15),
-                text("Preview (first 100 bytes as hex):")
+                text(tr("response-binary-preview"))
                     .size(14)
                     .color(Color::from_rgb(0.3, 0.3, 0.3)),
                 space().height(5),
@@ -408,7 +904,7 @@ impl ResponsePanel {
                     content,
                     Some(syntax_language),
                     if self.show_search {
-                        Some(self.search_query.as_str())
+                        self.search_pattern().ok()
                     } else {
                         None
                     },
@@ -449,6 +945,7 @@ impl ResponsePanel {
                                 value: theme.palette().text,
                                 selection: theme.palette().primary,
                             }),
+                        regex_toggle(self.search_regex, Message::ToggleSearchRegex),
                         button(
                             icon(IconName::ChevronDown)
                                 .size(14)
@@ -542,6 +1039,12 @@ impl ResponsePanel {
                     ..container::Style::default()
                 });
 
+                let search_bar = column![search_bar].push(
+                    self.search_pattern()
+                        .err()
+                        .map(|error| text(error).size(11).color(Color::from_rgb(0.86, 0.2, 0.2))),
+                );
+
                 floating_element::FloatingElement::new(editor_with_format, search_bar)
                     .offset(iced::Vector::new(10.0, 0.0))
                     .position(floating_element::AnchorPosition::BottomRight)
@@ -560,10 +1063,47 @@ impl ResponsePanel {
     pub fn update_spinner(&mut self) {
         self.spinner.update();
     }
+
+    /// Picks up the NDJSON records received since the last call from the body of the
+    /// request on screen, `None` once it's no longer being received
+    pub fn follow_stream(&mut self, source: Option<&StreamedBody>) {
+        let Some(source) = source else {
+            self.streamed = None;
+            return;
+        };
+
+        let streamed = match &mut self.streamed {
+            Some(streamed) if std::sync::Arc::ptr_eq(&streamed.source, source) => streamed,
+            _ => {
+                self.visible_records = ndjson::PAGE_SIZE;
+                self.expanded_records.clear();
+                self.streamed.insert(StreamedRecords {
+                    source: source.clone(),
+                    body: String::new(),
+                    count: 0,
+                    offset: 0,
+                })
+            }
+        };
+
+        let lines = match streamed.source.lock() {
+            Ok(buffer) => ndjson::complete_lines(&buffer, streamed.offset),
+            Err(_) => None,
+        };
+        if let Some((lines, offset)) = lines {
+            if streamed.count == 0 {
+                // The records stay in view once the response is complete
+                self.selected_tab = ResponseTab::Records;
+            }
+            streamed.count += ndjson::lines(&lines).count();
+            streamed.body.push_str(&lines);
+            streamed.offset = offset;
+        }
+    }
 }
 
 fn response_tab_button<'a>(
-    label: &'a str,
+    label: impl text::IntoFragment<'a>,
     is_active: bool,
     tab: ResponseTab,
 ) -> Element<'a, Message> {
@@ -605,6 +1145,39 @@ fn response_tab_button<'a>(
         .into()
 }
 
+/// `.*` button of a find bar, switching its query between plain text and a regular
+/// expression
+pub fn regex_toggle<'a, Message: Clone + 'a>(
+    enabled: bool,
+    on_press: Message,
+) -> Element<'a, Message> {
+    let color = if enabled {
+        Color::from_rgb(0.31, 0.27, 0.9) // #4f46e5
+    } else {
+        Color::from_rgb(0.4, 0.4, 0.4)
+    };
+
+    crate::ui::accessibility::labeled(
+        button(text(".*").size(12).font(iced::Font::MONOSPACE).color(color))
+            .on_press(on_press)
+            .padding([1, 4])
+            .style(move |_theme, status| button::Style {
+                background: match status {
+                    Status::Hovered => Some(Background::Color(Color::from_rgb(0.85, 0.85, 0.85))),
+                    _ if enabled => Some(Background::Color(Color::from_rgb(0.88, 0.89, 0.99))),
+                    _ => None,
+                },
+                border: Border {
+                    radius: 6.0.into(),
+                    ..Border::default()
+                },
+                ..button::Style::default()
+            }),
+        "Regular expression".to_string(),
+        iced::widget::tooltip::Position::Top,
+    )
+}
+
 fn response_format_button() -> Element<'static, Message> {
     button(
         icon(IconName::Indent)
@@ -682,7 +1255,823 @@ fn response_headers_tab<'a>(response: &'a ResponseData) -> Element<'a, Message>
     scrollable(content.spacing(5)).height(Length::Fill).into()
 }
 
-fn format_bytes(bytes: usize) -> String {
+/// NDJSON records, one per row, expanded to their indented JSON on click. Only the first
+/// `visible` are built, more are added on request.
+fn records_list<'a>(
+    body: &'a str,
+    total: usize,
+    visible: usize,
+    expanded: &HashSet<usize>,
+) -> Element<'a, Message> {
+    let mut list = column![].spacing(2);
+    for (index, line) in ndjson::lines(body).take(visible).enumerate() {
+        let record = ndjson::Record::parse(line);
+        let is_expanded = expanded.contains(&index);
+
+        let header = button(
+            row![
+                icon(if is_expanded {
+                    IconName::ChevronDown
+                } else {
+                    IconName::ChevronRight
+                })
+                .size(12),
+                text(format!("{}", index + 1))
+                    .size(12)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5))
+                    .width(Length::Fixed(45.0)),
+                text(record.preview())
+                    .size(12)
+                    .font(iced::Font::MONOSPACE)
+                    .color(if record.value.is_ok() {
+                        Color::from_rgb(0.2, 0.2, 0.2)
+                    } else {
+                        Color::from_rgb(0.82, 0.06, 0.22)
+                    }),
+            ]
+            .spacing(6)
+            .align_y(iced::Alignment::Center),
+        )
+        .on_press(Message::RecordToggled(index))
+        .width(Length::Fill)
+        .padding([3, 6])
+        .style(|_theme, status| button::Style {
+            background: match status {
+                Status::Hovered => Some(Background::Color(Color::from_rgb(0.94, 0.94, 0.94))),
+                _ => None,
+            },
+            ..button::Style::default()
+        });
+        list = list.push(header);
+
+        if is_expanded {
+            let details = match &record.value {
+                Ok(_) => record.pretty(),
+                Err(e) => format!("Invalid JSON: {}\n{}", e, record.raw),
+            };
+            list = list.push(
+                container(text(details).size(12).font(iced::Font::MONOSPACE)).padding(Padding {
+                    top: 2.0,
+                    right: 6.0,
+                    bottom: 6.0,
+                    left: 70.0,
+                }),
+            );
+        }
+    }
+
+    if total > visible {
+        list = list.push(
+            button(text(format!("Show more ({} of {} shown)", visible, total)).size(13))
+                .on_press(Message::ShowMoreRecords)
+                .padding([4, 10]),
+        );
+    } else if total == 0 {
+        list = list.push(
+            text(tr("response-no-records"))
+                .size(14)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+    }
+
+    scrollable(list)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .into()
+}
+
+/// Chart of the numbers a JSONPath points to in the body
+fn chart_tab<'a>(
+    response: &'a ResponseData,
+    path: &'a str,
+    kind: ChartKind,
+) -> Element<'a, Message> {
+    let controls = row![
+        text_input("JSONPath to numbers, e.g. $.data[*].value", path)
+            .on_input(Message::ChartPathChanged)
+            .font(iced::Font::MONOSPACE)
+            .size(13)
+            .padding(6),
+        pick_list(ChartKind::ALL, Some(kind), Message::ChartKindSelected)
+            .text_size(13)
+            .width(Length::Fixed(90.0)),
+    ]
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
+
+    let hint = |message: String| -> Element<'a, Message> {
+        container(text(message).size(14).color(Color::from_rgb(0.5, 0.5, 0.5)))
+            .padding(20)
+            .center_x(Length::Fill)
+            .into()
+    };
+    let content = if path.trim().is_empty() {
+        hint(
+            "Point to an array of numbers, numeric strings or [timestamp, value] pairs."
+                .to_string(),
+        )
+    } else {
+        match chart::data_points(&response.body, path) {
+            Ok(points) => column![
+                text(format!("{} values", points.len()))
+                    .size(12)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5)),
+                Chart::new(points, kind).view(),
+            ]
+            .spacing(6)
+            .height(Length::Fill)
+            .into(),
+            Err(e) => hint(e),
+        }
+    };
+
+    column![controls, content].spacing(10).into()
+}
+
+/// Assertion and script test outcomes, failures first
+fn tests_tab<'a>(response: &'a ResponseData) -> Element<'a, Message> {
+    if response.test_results.is_empty() {
+        return container(
+            text(tr("response-no-tests"))
+                .size(14)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        )
+        .padding(20)
+        .center_x(Length::Fill)
+        .into();
+    }
+
+    let mut results: Vec<_> = response.test_results.iter().collect();
+    results.sort_by_key(|test| test.passed);
+
+    let mut content = column![].spacing(10);
+    for test in results {
+        let (label, color) = if test.passed {
+            ("PASS", Color::from_rgb(0.25, 0.63, 0.17))
+        } else {
+            ("FAIL", Color::from_rgb(0.82, 0.06, 0.22))
+        };
+
+        let mut entry = column![
+            row![
+                text(label)
+                    .size(12)
+                    .font(iced::Font::MONOSPACE)
+                    .color(color)
+                    .width(Length::Fixed(40.0)),
+                text(&test.name).size(14),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+        ]
+        .spacing(4);
+        if let Some(message) = &test.error_message {
+            entry = entry.push(
+                row![
+                    space().width(Length::Fixed(40.0)),
+                    text(message)
+                        .size(13)
+                        .font(iced::Font::MONOSPACE)
+                        .color(Color::from_rgb(0.4, 0.4, 0.4)),
+                ]
+                .spacing(10),
+            );
+        }
+        content = content.push(entry);
+    }
+
+    scrollable(content).height(Length::Fill).into()
+}
+
+fn certificate_tab<'a>(response: &'a ResponseData) -> Element<'a, Message> {
+    if response.certificates.is_empty() {
+        return container(
+            text(tr("response-no-certificate"))
+                .size(14)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        )
+        .padding(20)
+        .center_x(Length::Fill)
+        .into();
+    }
+
+    let field = |label: &'a str, value: String| {
+        row![
+            text(label)
+                .size(14)
+                .color(Color::from_rgb(0.5, 0.5, 0.5))
+                .width(Length::Fixed(120.0)),
+            text(value).size(14),
+        ]
+        .spacing(10)
+    };
+
+    let mut content = column![].spacing(20);
+    for (index, cert) in response.certificates.iter().enumerate() {
+        let expiry = chrono::DateTime::parse_from_rfc3339(&cert.not_after).ok();
+        let days_left = expiry.map(|time| (time.to_utc() - chrono::Utc::now()).num_days());
+        let (expiry_text, expiry_color) = match days_left {
+            Some(days) if days < 0 => (
+                format!("{} (expired)", cert.not_after),
+                Color::from_rgb(0.8, 0.0, 0.0),
+            ),
+            Some(days) if days < 30 => (
+                format!("{} ({} days left)", cert.not_after, days),
+                Color::from_rgb(0.95, 0.55, 0.0),
+            ),
+            Some(days) => (
+                format!("{} ({} days left)", cert.not_after, days),
+                Color::from_rgb(0.0, 0.0, 0.0),
+            ),
+            None => (cert.not_after.clone(), Color::from_rgb(0.0, 0.0, 0.0)),
+        };
+
+        content = content.push(
+            column![
+                text(if index == 0 {
+                    "Server certificate".to_string()
+                } else {
+                    format!("Issuer certificate #{}", index)
+                })
+                .size(15),
+                field("Subject", cert.subject.clone()),
+                field("Issuer", cert.issuer.clone()),
+                field("Alt names", cert.subject_alt_names.join(", ")),
+                field("Serial", cert.serial.clone()),
+                field("Valid from", cert.not_before.clone()),
+                row![
+                    text(tr("response-certificate-valid-until"))
+                        .size(14)
+                        .color(Color::from_rgb(0.5, 0.5, 0.5))
+                        .width(Length::Fixed(120.0)),
+                    text(expiry_text).size(14).color(expiry_color),
+                ]
+                .spacing(10),
+            ]
+            .spacing(6),
+        );
+    }
+
+    scrollable(content).height(Length::Fill).into()
+}
+
+/// How browsers and shared caches would store the response, from its caching headers
+fn caching_tab<'a>(response: &ResponseData) -> Element<'a, Message> {
+    let report = caching::analyze(response);
+    let muted = Color::from_rgb(0.5, 0.5, 0.5);
+
+    let mut headers = column![].spacing(4);
+    if report.headers.is_empty() {
+        headers = headers.push(text(tr("response-none")).size(14).color(muted));
+    }
+    for (name, value) in report.headers {
+        headers = headers.push(
+            row![
+                text(name)
+                    .size(13)
+                    .font(iced::Font::MONOSPACE)
+                    .color(muted)
+                    .width(Length::Fixed(140.0)),
+                text(value).size(13).font(iced::Font::MONOSPACE),
+            ]
+            .spacing(10),
+        );
+    }
+
+    let verdict = |label: &'a str, description: String| {
+        row![
+            text(label)
+                .size(14)
+                .color(muted)
+                .width(Length::Fixed(140.0)),
+            text(description).size(14),
+        ]
+        .spacing(10)
+    };
+
+    let mut notes = column![].spacing(6);
+    for note in report.notes {
+        let (marker, color) = match note.severity {
+            caching::Severity::Warning => ("!", Color::from_rgb(0.85, 0.5, 0.0)),
+            caching::Severity::Info => ("•", muted),
+        };
+        notes = notes.push(
+            row![
+                text(marker)
+                    .size(14)
+                    .color(color)
+                    .width(Length::Fixed(12.0)),
+                text(note.text).size(13),
+            ]
+            .spacing(6),
+        );
+    }
+
+    scrollable(
+        column![
+            text(tr("tab-headers")).size(15),
+            headers,
+            text(tr("response-caching-how")).size(15),
+            verdict("Browsers", report.private_cache),
+            verdict("Shared caches", report.shared_cache),
+            text(tr("response-caching-notes")).size(15),
+            notes,
+        ]
+        .spacing(10),
+    )
+    .height(Length::Fill)
+    .into()
+}
+
+/// The request as sent, its credentials and secrets masked unless `reveal` is set
+fn raw_request_tab<'a>(
+    response: &'a ResponseData,
+    reveal: bool,
+    secrets: &[String],
+) -> Element<'a, Message> {
+    if response.raw_request.is_empty() {
+        return container(
+            text(tr("response-no-request"))
+                .size(14)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        )
+        .padding(20)
+        .center_x(Length::Fill)
+        .into();
+    }
+
+    let raw_request = if reveal {
+        response.raw_request.clone()
+    } else {
+        redact::raw_request(&response.raw_request, secrets)
+    };
+
+    column![
+        row![
+            space().width(Length::Fill),
+            button(
+                text(if reveal {
+                    "Hide Secrets"
+                } else {
+                    "Reveal Secrets"
+                })
+                .size(12)
+            )
+            .on_press(Message::RevealSecretsToggled)
+            .style(button::secondary)
+            .padding([4, 8]),
+        ],
+        scrollable(text(raw_request).size(13).font(iced::Font::MONOSPACE))
+            .height(Length::Fill)
+            .width(Length::Fill),
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Remaining quota and reset time of a rate limited API, red once the quota is used up
+fn rate_limit_badge<'a, M: 'a>(limit: &RateLimit) -> Element<'a, M> {
+    let clock = |time: chrono::DateTime<chrono::Utc>| {
+        time.with_timezone(&chrono::Local)
+            .format("%H:%M:%S")
+            .to_string()
+    };
+
+    let mut label = match (limit.remaining, limit.limit) {
+        (Some(remaining), Some(quota)) => format!("Quota: {} of {} left", remaining, quota),
+        (Some(remaining), None) => format!("Quota: {} left", remaining),
+        _ => "Rate limited".to_string(),
+    };
+    if let Some(retry_at) = limit.retry_at {
+        label.push_str(&format!(", retry at {}", clock(retry_at)));
+    } else if let Some(reset_at) = limit.reset_at {
+        label.push_str(&format!(", resets at {}", clock(reset_at)));
+    }
+
+    let color = if limit.is_exhausted() {
+        OVER_BUDGET_COLOR
+    } else if limit.is_low() {
+        Color::from_rgb(0.85, 0.5, 0.0)
+    } else {
+        Color::from_rgb(0.5, 0.5, 0.5)
+    };
+    let details = match limit.wait(chrono::Utc::now()) {
+        Some(wait) => format!(
+            "Sending again before {} will likely be refused.",
+            rate_limit::format_wait(wait)
+        ),
+        None if limit.is_exhausted() => "The limit should be lifted by now.".to_string(),
+        None => "From the rate limit headers of the response.".to_string(),
+    };
+
+    tooltip(
+        container(text(label).size(13).color(color))
+            .padding([4, 8])
+            .style(move |_theme| Style {
+                border: Border {
+                    color: Color { a: 0.4, ..color },
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                ..Style::default()
+            }),
+        container(text(details).size(12))
+            .max_width(320)
+            .padding([6, 10])
+            .style(container::rounded_box),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+/// Grade of the response's security headers, each check explained on hover
+fn security_badge<'a, M: 'a>(audit: Audit) -> Element<'a, M> {
+    let color = match audit.grade {
+        Grade::A | Grade::B => Color::from_rgb(0.0, 0.6, 0.3),
+        Grade::C | Grade::D => Color::from_rgb(0.85, 0.5, 0.0),
+        Grade::F => OVER_BUDGET_COLOR,
+    };
+
+    let mut checks = column![].spacing(6);
+    for check in audit.checks {
+        let (marker, marker_color) = match check.outcome {
+            Outcome::Pass => ("✓", Color::from_rgb(0.0, 0.6, 0.3)),
+            Outcome::Warn => ("!", Color::from_rgb(0.85, 0.5, 0.0)),
+            Outcome::Fail => ("✗", OVER_BUDGET_COLOR),
+        };
+        checks = checks.push(
+            row![
+                text(marker)
+                    .size(12)
+                    .color(marker_color)
+                    .width(Length::Fixed(12.0)),
+                column![
+                    text(check.header).size(12).font(iced::Font::MONOSPACE),
+                    text(check.explanation)
+                        .size(12)
+                        .color(Color::from_rgb(0.4, 0.4, 0.4)),
+                ]
+                .spacing(2),
+            ]
+            .spacing(6),
+        );
+    }
+
+    tooltip(
+        container(
+            text(format!("Security: {}", audit.grade))
+                .size(13)
+                .color(color),
+        )
+        .padding([4, 8])
+        .style(move |_theme| Style {
+            border: Border {
+                color: Color { a: 0.4, ..color },
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Style::default()
+        }),
+        container(checks)
+            .max_width(420)
+            .padding([8, 10])
+            .style(container::rounded_box),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+/// Durations past the request's latency budget
+const OVER_BUDGET_COLOR: Color = Color::from_rgb(0.82, 0.06, 0.22);
+
+/// Past runs of the request, newest first, with an editable note on each
+fn run_history_tab<'a>(runs: &'a [RunRecord], query: &'a str) -> Element<'a, Message> {
+    let search = text_input("Search notes...", query)
+        .on_input(Message::HistoryQueryChanged)
+        .size(13)
+        .padding(6);
+
+    let query_lower = query.to_lowercase();
+    let mut list = column![].spacing(6);
+
+    for (index, run) in runs.iter().enumerate().rev() {
+        if !query_lower.is_empty()
+            && !run.note.to_lowercase().contains(&query_lower)
+            && !run.status.to_string().contains(&query_lower)
+        {
+            continue;
+        }
+
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&run.timestamp)
+            .map(|time| {
+                time.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|_| run.timestamp.clone());
+        let status = if run.status == 0 {
+            "Error".to_string()
+        } else {
+            run.status.to_string()
+        };
+
+        let duration = text(format!("{}ms", run.duration))
+            .size(12)
+            .width(Length::Fixed(70.0));
+        let duration: Element<'_, Message> = match run.budget {
+            Some(budget) if run.over_budget() => tooltip(
+                duration.color(OVER_BUDGET_COLOR),
+                container(text(format!("Over the {}ms budget", budget)).size(12))
+                    .padding([4, 8])
+                    .style(container::rounded_box),
+                tooltip::Position::Top,
+            )
+            .into(),
+            _ => duration.color(Color::from_rgb(0.5, 0.5, 0.5)).into(),
+        };
+
+        list = list.push(
+            row![
+                text(timestamp)
+                    .size(12)
+                    .font(iced::Font::MONOSPACE)
+                    .width(Length::Fixed(150.0)),
+                text(status).size(12).width(Length::Fixed(50.0)),
+                duration,
+                text_input("Add a note...", &run.note)
+                    .on_input(move |note| Message::RunNoteChanged(index, note))
+                    .size(12)
+                    .padding(4),
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+
+    if runs.is_empty() {
+        list = list.push(
+            text(tr("response-not-sent"))
+                .size(14)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+    }
+
+    column![search, scrollable(list).height(Length::Fill)]
+        .spacing(10)
+        .into()
+}
+
+/// Saved examples, one shown at a time through the dropdown
+fn examples_tab(examples: &[ResponseExample], selected: usize) -> Element<'_, Message> {
+    let save = button(text(tr("response-save-example")).size(13))
+        .on_press(Message::SaveExample)
+        .padding([4, 10]);
+
+    let options: Vec<ExampleOption> = examples
+        .iter()
+        .enumerate()
+        .map(|(index, example)| ExampleOption {
+            index,
+            name: example.name.clone(),
+        })
+        .collect();
+    let selected = selected.min(examples.len().saturating_sub(1));
+    let Some(example) = examples.get(selected) else {
+        return column![
+            save,
+            text(tr("response-examples-hint"))
+                .size(14)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        ]
+        .spacing(10)
+        .into();
+    };
+
+    let header = row![
+        pick_list(
+            options.clone(),
+            options.get(selected).cloned(),
+            Message::ExampleSelected
+        )
+        .text_size(13)
+        .width(Length::Fixed(200.0)),
+        text_input("Example name", &example.name)
+            .on_input(move |name| Message::ExampleNameChanged(selected, name))
+            .size(13)
+            .padding(4),
+        button(text(tr("delete")).size(13))
+            .on_press(Message::DeleteExample(selected))
+            .padding([4, 10]),
+        save,
+    ]
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
+
+    let saved_at = chrono::DateTime::parse_from_rfc3339(&example.saved_at)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_default();
+    let details = row![
+        text(format!("{} {}", example.status, example.status_text))
+            .size(13)
+            .color(status_color(example.status)),
+        text(&example.content_type)
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        space().width(Length::Fill),
+        text(saved_at)
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    ]
+    .spacing(10)
+    .align_y(iced::Alignment::Center);
+
+    // Empty means 0, anything that isn't a number keeps the current value
+    let parse = |value: String| -> Option<u64> {
+        let value = value.trim();
+        if value.is_empty() {
+            Some(0)
+        } else {
+            value.parse().ok()
+        }
+    };
+    let mock = example.mock;
+    let mock_settings = row![
+        text(tr("response-mock")).size(13),
+        text_input("0", &mock.latency_ms.to_string())
+            .on_input(move |value| {
+                let latency_ms = parse(value).unwrap_or(mock.latency_ms);
+                Message::ExampleMockChanged(selected, MockBehavior { latency_ms, ..mock })
+            })
+            .size(13)
+            .padding(4)
+            .width(Length::Fixed(70.0)),
+        text(tr("response-mock-latency")).size(13),
+        text_input("0", &mock.error_rate.to_string())
+            .on_input(move |value| {
+                let error_rate = parse(value)
+                    .and_then(|rate| u8::try_from(rate).ok())
+                    .filter(|rate| *rate <= 100)
+                    .unwrap_or(mock.error_rate);
+                Message::ExampleMockChanged(selected, MockBehavior { error_rate, ..mock })
+            })
+            .size(13)
+            .padding(4)
+            .width(Length::Fixed(50.0)),
+        text(tr("response-mock-error-rate")).size(13),
+        text_input("503", &mock.error_status.to_string())
+            .on_input(move |value| {
+                let error_status = parse(value)
+                    .and_then(|status| u16::try_from(status).ok())
+                    .filter(|status| (100..=599).contains(status))
+                    .unwrap_or(mock.error_status);
+                Message::ExampleMockChanged(
+                    selected,
+                    MockBehavior {
+                        error_status,
+                        ..mock
+                    },
+                )
+            })
+            .size(13)
+            .padding(4)
+            .width(Length::Fixed(60.0)),
+    ]
+    .spacing(6)
+    .align_y(iced::Alignment::Center);
+    let placeholders = text(
+        "The body and headers can echo the request with {{request.method}}, {{request.path}}, \
+         {{request.query.name}}, {{request.header.name}} or {{request.body.field}}, and \
+         generate data with {{$randomUUID}}, {{$randomInt}}...",
+    )
+    .size(12)
+    .color(Color::from_rgb(0.5, 0.5, 0.5));
+
+    let body = if example.body.len() > RESPONSE_PREVIEW_SIZE {
+        let mut end = RESPONSE_PREVIEW_SIZE;
+        while !example.body.is_char_boundary(end) {
+            end -= 1;
+        }
+        &example.body[..end]
+    } else {
+        example.body.as_str()
+    };
+
+    column![
+        header,
+        details,
+        mock_settings,
+        placeholders,
+        scrollable(
+            text(body)
+                .size(12)
+                .font(iced::Font::MONOSPACE)
+                .width(Length::Fill)
+        )
+        .height(Length::Fill),
+    ]
+    .spacing(10)
+    .into()
+}
+
+/// Bodies up to this size are searched for a JWT to offer inspecting
+const JWT_SCAN_LIMIT: usize = 256 * 1024;
+
+/// A JWT in the response headers or text body, such as a freshly issued access token
+fn response_jwt(response: &ResponseData) -> Option<&str> {
+    response
+        .headers
+        .iter()
+        .find_map(|(_, value)| tools::find_jwt(value))
+        .or_else(|| {
+            (!response.is_binary && response.body.len() <= JWT_SCAN_LIMIT)
+                .then(|| tools::find_jwt(&response.body))
+                .flatten()
+        })
+}
+
+/// Badge color for a status class: 2xx green, 3xx blue, 4xx orange, 5xx red
+fn status_color(status: u16) -> Color {
+    match status {
+        200..=299 => Color::from_rgb(0.0, 0.65, 0.0),
+        300..=399 => Color::from_rgb(0.0, 0.45, 0.85),
+        400..=499 => Color::from_rgb(0.95, 0.55, 0.0),
+        500..=599 => Color::from_rgb(0.8, 0.0, 0.0),
+        _ => Color::from_rgb(0.5, 0.5, 0.5),
+    }
+}
+
+/// Short, human-readable meaning of a status code
+pub fn status_explanation(status: u16) -> &'static str {
+    match status {
+        0 => "No response was received, the body explains what went wrong.",
+        100 => "Continue: the server received the headers and the client should send the body.",
+        101 => {
+            "Switching Protocols: the server is switching to the protocol requested in the Upgrade header."
+        }
+        200 => "OK: the request succeeded.",
+        201 => "Created: the request succeeded and a new resource was created.",
+        202 => "Accepted: the request was received but has not been acted upon yet.",
+        204 => "No Content: the request succeeded and there is no body to return.",
+        206 => "Partial Content: only the range requested in the Range header is returned.",
+        301 => "Moved Permanently: the resource now lives at the URL in the Location header.",
+        302 => "Found: the resource is temporarily at the URL in the Location header.",
+        303 => "See Other: fetch the result with a GET to the URL in the Location header.",
+        304 => "Not Modified: the cached copy is still valid, so no body is sent.",
+        307 => "Temporary Redirect: repeat the same request at the URL in the Location header.",
+        308 => "Permanent Redirect: repeat the same request at the new URL from now on.",
+        400 => {
+            "Bad Request: the server could not understand the request, check the syntax and body."
+        }
+        401 => "Unauthorized: authentication is missing or invalid.",
+        403 => "Forbidden: the server knows who you are but refuses access.",
+        404 => "Not Found: the server has nothing at this URL.",
+        405 => "Method Not Allowed: the resource does not support this HTTP method.",
+        406 => "Not Acceptable: no representation matches the Accept headers.",
+        408 => "Request Timeout: the server gave up waiting for the request.",
+        409 => "Conflict: the request conflicts with the current state of the resource.",
+        410 => "Gone: the resource was removed and will not come back.",
+        411 => "Length Required: the server needs a Content-Length header.",
+        412 => "Precondition Failed: a conditional header such as If-Match did not hold.",
+        413 => "Content Too Large: the request body exceeds what the server accepts.",
+        414 => "URI Too Long: the URL is longer than the server will process.",
+        415 => "Unsupported Media Type: the server does not accept this Content-Type.",
+        418 => "I'm a teapot: the server refuses to brew coffee.",
+        422 => "Unprocessable Content: the body is well-formed but fails validation.",
+        425 => "Too Early: the server will not risk processing a request that might be replayed.",
+        428 => "Precondition Required: the server requires a conditional request.",
+        429 => "Too Many Requests: you are being rate limited, see the Retry-After header.",
+        431 => "Request Header Fields Too Large: the headers are too big for the server.",
+        451 => "Unavailable For Legal Reasons: access was blocked for legal reasons.",
+        500 => "Internal Server Error: the server hit an unexpected condition.",
+        501 => "Not Implemented: the server does not support this functionality.",
+        502 => "Bad Gateway: an upstream server returned an invalid response.",
+        503 => "Service Unavailable: the server is overloaded or down for maintenance.",
+        504 => "Gateway Timeout: an upstream server did not respond in time.",
+        505 => "HTTP Version Not Supported: the server does not support this HTTP version.",
+        _ => match status / 100 {
+            1 => "Informational: the request was received and is being processed.",
+            2 => "Success: the request was received, understood and accepted.",
+            3 => "Redirection: further action is needed to complete the request.",
+            4 => "Client error: the request contains an error or cannot be fulfilled.",
+            5 => "Server error: the server failed to fulfil a valid request.",
+            _ => "Non-standard status code.",
+        },
+    }
+}
+
+/// Reference documentation for a status code
+pub fn status_docs_url(status: u16) -> String {
+    format!(
+        "https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/{}",
+        status
+    )
+}
+
+pub fn format_bytes(bytes: usize) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     const THRESHOLD: f64 = 1024.0;
 
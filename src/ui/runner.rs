@@ -0,0 +1,985 @@
+use crate::i18n::tr;
+use crate::rate_limit;
+use crate::runner::{
+    self, DEFAULT_CONCURRENCY, MAX_CONCURRENCY, MAX_RETRIES, MAX_SENDS, NextRequest, Run, RunMode,
+    RunResult,
+};
+use crate::types::{
+    Environment, Jump, JumpWhen, LoopBack, RequestCollection, RequestConfig, RunConfiguration,
+    RunStep,
+};
+use crate::ui::{IconName, icon, method_color};
+use iced::widget::button::Status;
+use iced::widget::container::Style;
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, space, text, text_input,
+    tooltip,
+};
+use iced::{Background, Border, Color, Element, Font, Length, Shadow, Vector};
+use std::collections::HashSet;
+
+const SUCCESS_COLOR: Color = Color::from_rgb(0.0, 0.6, 0.3);
+const FAILURE_COLOR: Color = Color::from_rgb(0.82, 0.06, 0.22);
+const WARNING_COLOR: Color = Color::from_rgb(0.8, 0.5, 0.0);
+const MUTED_COLOR: Color = Color::from_rgb(0.5, 0.5, 0.5);
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    /// Start a run of the collection with the dialog's `configuration()`, `mode` and
+    /// `concurrency()`
+    Start,
+    Stop,
+    /// Keep the edited run configuration with the collection
+    SaveConfiguration(RunConfiguration),
+    Close,
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ModeChanged(RunMode),
+    ConcurrencyChanged(String),
+    EnvironmentSelected(EnvironmentChoice),
+    DataPathChanged(String),
+    DelayChanged(String),
+    RetriesChanged(String),
+    StopOnFailureToggled(bool),
+    RetryAfterRateLimitToggled(bool),
+    MoveUp(usize),
+    MoveDown(usize),
+    SendToggled(usize, bool),
+    EditFlow(usize),
+    LoopTargetSelected(usize, StepChoice),
+    LoopTimesChanged(usize, String),
+    RemoveLoop(usize),
+    JumpWhenSelected(usize, JumpWhen),
+    JumpTestChanged(usize, String),
+    JumpTargetSelected(usize, StepChoice),
+    RemoveJump(usize),
+    EditSteps,
+    Start,
+    Stop,
+    Close,
+}
+
+/// A step to loop back or jump to, as listed in the pickers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepChoice {
+    pub request_id: String, // Empty for the end of the run
+    pub label: String,
+}
+
+impl std::fmt::Display for StepChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+/// The environment a run resolves its requests with, by name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvironmentChoice(pub Option<String>);
+
+impl std::fmt::Display for EnvironmentChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "No environment"),
+        }
+    }
+}
+
+/// Runs the requests of a collection and reports how each of them did. Until a run starts
+/// it edits the collection's run configuration: the order of the steps, the ones skipped,
+/// loops and jumps.
+#[derive(Debug, Clone)]
+pub struct RunnerDialog {
+    pub collection_id: String,
+    pub run_id: u64,
+    pub mode: RunMode,
+    pub concurrency: String,
+    /// Environment of the runs, the active one until another is picked
+    pub environment: Option<String>,
+    /// CSV or JSON file of iteration data, empty to go through the steps once
+    pub data_path: String,
+    pub delay_ms: String,
+    pub retries: String,
+    pub stop_on_failure: bool,
+    pub retry_after_rate_limit: bool,
+    pub steps: Vec<RunStep>,
+    pub editing: Option<usize>, // Step whose loop and jump are shown
+    pub run: Option<Run>,
+}
+
+impl RunnerDialog {
+    pub fn new(collection: &RequestCollection, environment: Option<String>) -> Self {
+        Self {
+            collection_id: collection.id.clone(),
+            run_id: 0,
+            mode: RunMode::default(),
+            concurrency: DEFAULT_CONCURRENCY.to_string(),
+            environment,
+            data_path: String::new(),
+            delay_ms: collection.run_configuration.delay_ms.to_string(),
+            retries: collection.run_configuration.retries.to_string(),
+            stop_on_failure: collection.run_configuration.stop_on_failure,
+            retry_after_rate_limit: collection.run_configuration.retry_after_rate_limit,
+            steps: runner::ordered_steps(&collection.run_configuration, &collection.requests),
+            editing: None,
+            run: None,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Action {
+        match message {
+            Message::ModeChanged(mode) => self.mode = mode,
+            Message::ConcurrencyChanged(value) => {
+                if value.chars().all(|c| c.is_ascii_digit()) {
+                    self.concurrency = value;
+                }
+            }
+            Message::EnvironmentSelected(EnvironmentChoice(name)) => self.environment = name,
+            Message::DataPathChanged(path) => self.data_path = path,
+            Message::DelayChanged(value) => {
+                if value.chars().all(|c| c.is_ascii_digit()) {
+                    self.delay_ms = value;
+                    return self.configuration_changed();
+                }
+            }
+            Message::RetriesChanged(value) => {
+                if value.chars().all(|c| c.is_ascii_digit()) {
+                    self.retries = value;
+                    return self.configuration_changed();
+                }
+            }
+            Message::StopOnFailureToggled(stop) => {
+                self.stop_on_failure = stop;
+                return self.configuration_changed();
+            }
+            Message::RetryAfterRateLimitToggled(retry) => {
+                self.retry_after_rate_limit = retry;
+                return self.configuration_changed();
+            }
+            Message::MoveUp(index) => {
+                if index > 0 && index < self.steps.len() {
+                    self.steps.swap(index - 1, index);
+                    self.editing = self.editing.map(|editing| match editing {
+                        e if e == index => index - 1,
+                        e if e == index - 1 => index,
+                        e => e,
+                    });
+                    return self.configuration_changed();
+                }
+            }
+            Message::MoveDown(index) => {
+                if index + 1 < self.steps.len() {
+                    return self.update(Message::MoveUp(index + 1));
+                }
+            }
+            Message::SendToggled(index, send) => {
+                if let Some(step) = self.steps.get_mut(index) {
+                    step.skip = !send;
+                    return self.configuration_changed();
+                }
+            }
+            Message::EditFlow(index) => {
+                self.editing = (self.editing != Some(index)).then_some(index);
+            }
+            Message::LoopTargetSelected(index, choice) => {
+                if let Some(step) = self.steps.get_mut(index) {
+                    let times = step
+                        .loop_back
+                        .as_ref()
+                        .map_or(1, |loop_back| loop_back.times);
+                    step.loop_back = Some(LoopBack {
+                        to: choice.request_id,
+                        times,
+                    });
+                    return self.configuration_changed();
+                }
+            }
+            Message::LoopTimesChanged(index, value) => {
+                if let Some(loop_back) = self
+                    .steps
+                    .get_mut(index)
+                    .and_then(|step| step.loop_back.as_mut())
+                    && let Ok(times) = value.parse::<u32>()
+                {
+                    loop_back.times = times;
+                    return self.configuration_changed();
+                }
+            }
+            Message::RemoveLoop(index) => {
+                if let Some(step) = self.steps.get_mut(index) {
+                    step.loop_back = None;
+                    return self.configuration_changed();
+                }
+            }
+            Message::JumpWhenSelected(index, when) => {
+                if let Some(step) = self.steps.get_mut(index) {
+                    step.jump.get_or_insert_with(new_jump).when = when;
+                    return self.configuration_changed();
+                }
+            }
+            Message::JumpTestChanged(index, test) => {
+                if let Some(step) = self.steps.get_mut(index) {
+                    step.jump.get_or_insert_with(new_jump).test = test;
+                    return self.configuration_changed();
+                }
+            }
+            Message::JumpTargetSelected(index, choice) => {
+                if let Some(step) = self.steps.get_mut(index) {
+                    step.jump.get_or_insert_with(new_jump).to = choice.request_id;
+                    return self.configuration_changed();
+                }
+            }
+            Message::RemoveJump(index) => {
+                if let Some(step) = self.steps.get_mut(index) {
+                    step.jump = None;
+                    return self.configuration_changed();
+                }
+            }
+            Message::EditSteps => {
+                if !self.is_running() {
+                    self.run = None;
+                }
+            }
+            Message::Start => {
+                if self.is_running() {
+                    return Action::None;
+                }
+                return Action::Start;
+            }
+            Message::Stop => return Action::Stop,
+            Message::Close => return Action::Close,
+        }
+
+        Action::None
+    }
+
+    /// Drops the loops that no longer go back to an earlier step, then asks for the
+    /// configuration to be saved
+    fn configuration_changed(&mut self) -> Action {
+        let ids: Vec<String> = self
+            .steps
+            .iter()
+            .map(|step| step.request_id.clone())
+            .collect();
+        for (index, step) in self.steps.iter_mut().enumerate() {
+            if step
+                .loop_back
+                .as_ref()
+                .is_some_and(|loop_back| !ids[..=index].contains(&loop_back.to))
+            {
+                step.loop_back = None;
+            }
+        }
+        Action::SaveConfiguration(self.configuration())
+    }
+
+    pub fn configuration(&self) -> RunConfiguration {
+        RunConfiguration {
+            steps: self.steps.clone(),
+            delay_ms: self.delay_ms.parse().unwrap_or(0),
+            stop_on_failure: self.stop_on_failure,
+            retries: self.retries.parse().unwrap_or(0).min(MAX_RETRIES),
+            retry_after_rate_limit: self.retry_after_rate_limit,
+        }
+    }
+
+    /// Requests in flight at once in a parallel run
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+            .parse()
+            .unwrap_or(DEFAULT_CONCURRENCY)
+            .clamp(1, MAX_CONCURRENCY)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.run.as_ref().is_some_and(|run| !run.is_finished())
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        collection: &'a RequestCollection,
+        environments: &'a [Environment],
+    ) -> Element<'a, Message> {
+        let running = self.is_running();
+
+        let mut environment_choices = vec![EnvironmentChoice(None)];
+        environment_choices.extend(
+            environments
+                .iter()
+                .map(|environment| EnvironmentChoice(Some(environment.name.clone()))),
+        );
+        let setup = column![
+            row![
+                text(tr("runner-environment"))
+                    .size(14)
+                    .width(Length::Fixed(110.0)),
+                pick_list(
+                    environment_choices,
+                    Some(EnvironmentChoice(self.environment.clone())),
+                    Message::EnvironmentSelected
+                )
+                .text_size(14),
+            ]
+            .align_y(iced::Alignment::Center),
+            row![
+                text(tr("runner-iteration-data"))
+                    .size(14)
+                    .width(Length::Fixed(110.0)),
+                text_input("Path to a CSV or JSON file, optional", &self.data_path)
+                    .on_input_maybe((!running).then_some(Message::DataPathChanged))
+                    .padding(4)
+                    .size(14),
+            ]
+            .align_y(iced::Alignment::Center),
+        ]
+        .spacing(8);
+
+        let mut options = row![
+            text(tr("runner-mode")).size(14).width(Length::Fixed(110.0)),
+            choice_button(
+                "Sequential",
+                self.mode == RunMode::Sequential,
+                (!running).then_some(Message::ModeChanged(RunMode::Sequential))
+            ),
+            choice_button(
+                "Parallel",
+                self.mode == RunMode::Parallel,
+                (!running).then_some(Message::ModeChanged(RunMode::Parallel))
+            ),
+        ]
+        .spacing(4)
+        .align_y(iced::Alignment::Center);
+        if self.mode == RunMode::Parallel {
+            options = options.push(space().width(16)).push(
+                row![
+                    text(tr("runner-concurrency")).size(14),
+                    text_input(&DEFAULT_CONCURRENCY.to_string(), &self.concurrency)
+                        .on_input_maybe((!running).then_some(Message::ConcurrencyChanged))
+                        .width(Length::Fixed(60.0))
+                        .padding(4)
+                        .size(14),
+                ]
+                .spacing(8)
+                .align_y(iced::Alignment::Center),
+            );
+        }
+
+        let failures = row![
+            text(tr("runner-delay"))
+                .size(14)
+                .width(Length::Fixed(110.0)),
+            text_input("0", &self.delay_ms)
+                .on_input_maybe((!running).then_some(Message::DelayChanged))
+                .width(Length::Fixed(70.0))
+                .padding(4)
+                .size(14),
+            text(tr("runner-delay-unit")).size(14),
+            space().width(16),
+            text(tr("runner-retries")).size(14),
+            text_input("0", &self.retries)
+                .on_input_maybe((!running).then_some(Message::RetriesChanged))
+                .width(Length::Fixed(50.0))
+                .padding(4)
+                .size(14),
+            space().width(16),
+            checkbox(self.stop_on_failure)
+                .label("Stop at the first failure")
+                .on_toggle_maybe((!running).then_some(Message::StopOnFailureToggled))
+                .size(14)
+                .text_size(14),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+        let rate_limits = row![
+            space().width(Length::Fixed(110.0)),
+            checkbox(self.retry_after_rate_limit)
+                .label(format!(
+                    "Send rate limited requests again after their Retry-After, up to {} times",
+                    rate_limit::MAX_RETRIES
+                ))
+                .on_toggle_maybe((!running).then_some(Message::RetryAfterRateLimitToggled))
+                .size(14)
+                .text_size(14),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+
+        let find = |id: &str| collection.requests.iter().find(|request| request.id == id);
+        let chained = match &self.run {
+            Some(run) => run.chained.clone(),
+            None => {
+                let sent: Vec<RequestConfig> = self
+                    .steps
+                    .iter()
+                    .filter(|step| !step.skip)
+                    .filter_map(|step| find(&step.request_id).cloned())
+                    .collect();
+                runner::chained_requests(&sent)
+            }
+        };
+
+        let mut hint = match self.mode {
+            RunMode::Sequential => {
+                "Requests are sent one after the other, variables set by a script are seen by the requests after it. Scripts can pick the next request with beam.setNextRequest(name) or leave it out with beam.skipRequest().".to_string()
+            }
+            RunMode::Parallel => {
+                "Requests are sent at once, each seeing the environment as it was when the run started. Variables set by a script stay with its result, loops and jumps only apply to sequential runs.".to_string()
+            }
+        };
+        if self.mode == RunMode::Parallel && !chained.is_empty() {
+            hint.push_str(&format!(
+                " {} requests chain variables and may fail when sent in parallel.",
+                chained.len()
+            ));
+        }
+
+        let mut rows = column![].spacing(4);
+        match &self.run {
+            // The requests sent so far, in order
+            Some(run) => {
+                for entry in &run.entries {
+                    if let Some(request) = run.requests.get(entry.request) {
+                        rows = rows.push(request_row(
+                            request,
+                            entry.result.clone(),
+                            entry.retries + entry.rate_limited,
+                            (run.iterations() > 1).then_some(entry.iteration + 1),
+                            &chained,
+                            self.mode,
+                        ));
+                    }
+                }
+            }
+            // The steps of the run configuration, until a run starts
+            None => {
+                let choices: Vec<StepChoice> = self
+                    .steps
+                    .iter()
+                    .filter_map(|step| {
+                        find(&step.request_id).map(|request| StepChoice {
+                            request_id: request.id.clone(),
+                            label: request.name.clone(),
+                        })
+                    })
+                    .collect();
+                for (index, step) in self.steps.iter().enumerate() {
+                    if let Some(request) = find(&step.request_id) {
+                        rows = rows.push(step_row(
+                            index,
+                            step,
+                            request,
+                            &choices,
+                            self.editing == Some(index),
+                            self.mode == RunMode::Parallel && chained.contains(&request.id),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let status: Element<'_, Message> = match &self.run {
+            Some(run) => {
+                let environment = match run.environment.name.as_str() {
+                    "" => "no environment".to_string(),
+                    name => name.to_string(),
+                };
+                let mut summary = format!(
+                    "With {}, {} sent, {} failed",
+                    environment,
+                    run.completed(),
+                    run.failed()
+                );
+                if run.iterations() > 1 {
+                    summary.push_str(&format!(
+                        ", iteration {} of {}",
+                        run.iteration + 1,
+                        run.iterations()
+                    ));
+                }
+                let (checks, failed_checks) = run.checks();
+                if checks > 0 {
+                    summary.push_str(&format!(", {} of {} checks failed", failed_checks, checks));
+                }
+                let retried = run.retried();
+                if retried > 0 {
+                    summary.push_str(&format!(", {} retries", retried));
+                }
+                let over_budget = run.over_budget();
+                if over_budget > 0 {
+                    summary.push_str(&format!(", {} over their latency budget", over_budget));
+                }
+                if run.limit_reached {
+                    summary.push_str(&format!(", stopped after {} sends", MAX_SENDS));
+                } else if run.stopped_on_failure {
+                    summary.push_str(", stopped at the first failure");
+                } else if run.stopped {
+                    summary.push_str(", stopped");
+                } else if run.is_finished() {
+                    summary.push_str(", done");
+                } else if let Some(wait) = run.rate_limit_wait()
+                    && let Ok(wait) = chrono::Duration::from_std(wait)
+                {
+                    summary.push_str(&format!(
+                        ", waiting {} for the rate limit",
+                        rate_limit::format_wait(wait)
+                    ));
+                } else if run.is_waiting() && !run.delay.is_zero() {
+                    summary.push_str(&format!(", waiting {} ms", run.delay.as_millis()));
+                }
+                text(summary)
+                    .size(14)
+                    .color(if run.failed() > 0 {
+                        FAILURE_COLOR
+                    } else {
+                        SUCCESS_COLOR
+                    })
+                    .into()
+            }
+            None => space().height(0).into(),
+        };
+
+        let action = if running {
+            button(text(tr("stop")).size(16))
+                .on_press(Message::Stop)
+                .padding(10)
+                .style(runner_button_style)
+        } else {
+            button(
+                text(if self.run.is_some() {
+                    "Run again"
+                } else {
+                    "Run"
+                })
+                .size(16),
+            )
+            .on_press_maybe((!collection.requests.is_empty()).then_some(Message::Start))
+            .padding(10)
+            .style(runner_button_style)
+        };
+        let buttons = row![
+            space().width(Length::Fill),
+            button(text(tr("close")).size(16))
+                .on_press(Message::Close)
+                .padding(10)
+                .style(runner_button_style),
+        ]
+        .push((self.run.is_some() && !running).then(|| {
+            button(text(tr("runner-edit-steps")).size(16))
+                .on_press(Message::EditSteps)
+                .padding(10)
+                .style(runner_button_style)
+        }))
+        .push(action)
+        .spacing(10);
+
+        container(
+            column![
+                text(format!("Run {}", collection.name)).size(18),
+                setup,
+                options,
+                failures,
+                rate_limits,
+                text(hint).size(12).color(MUTED_COLOR),
+                scrollable(rows).height(Length::Fill),
+                status,
+                buttons,
+            ]
+            .spacing(12),
+        )
+        .padding(20)
+        .height(Length::Fill)
+        .style(|_theme| Style {
+            background: Some(Background::Color(Color::WHITE)),
+            border: Border {
+                color: Color::from_rgb(0.7, 0.7, 0.7),
+                width: 1.0,
+                radius: 8.0.into(),
+            },
+            shadow: Shadow {
+                color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                offset: Vector::new(0.0, 4.0),
+                blur_radius: 10.0,
+            },
+            ..Style::default()
+        })
+        .into()
+    }
+}
+
+fn new_jump() -> Jump {
+    Jump {
+        when: JumpWhen::default(),
+        test: String::new(),
+        to: String::new(),
+    }
+}
+
+/// A step of the run configuration, with its loop and jump below it while edited
+fn step_row<'a>(
+    index: usize,
+    step: &'a RunStep,
+    request: &'a RequestConfig,
+    choices: &[StepChoice],
+    editing: bool,
+    chained: bool,
+) -> Element<'a, Message> {
+    let label = |id: &str| {
+        choices
+            .iter()
+            .find(|choice| choice.request_id == id)
+            .map_or("the end".to_string(), |choice| choice.label.clone())
+    };
+    let arrow = |name: IconName, message: Message| {
+        button(icon(name).size(12).color(MUTED_COLOR))
+            .on_press(message)
+            .padding(2)
+            .style(runner_button_style)
+    };
+
+    let mut name = row![text(&request.name).size(13).color(if step.skip {
+        MUTED_COLOR
+    } else {
+        Color::BLACK
+    })]
+    .spacing(6)
+    .align_y(iced::Alignment::Center);
+    if chained {
+        name = name.push(chained_flag());
+    }
+
+    let mut flow = Vec::new();
+    if let Some(loop_back) = &step.loop_back {
+        flow.push(format!(
+            "loops back to {} {} more times",
+            label(&loop_back.to),
+            loop_back.times
+        ));
+    }
+    if let Some(jump) = &step.jump {
+        let check = if jump.test.is_empty() {
+            "it".to_string()
+        } else {
+            format!("\"{}\"", jump.test)
+        };
+        flow.push(format!(
+            "when {} {}, goes to {}",
+            check,
+            jump.when,
+            label(&jump.to)
+        ));
+    }
+
+    let mut entry = column![
+        row![
+            arrow(IconName::ChevronUp, Message::MoveUp(index)),
+            arrow(IconName::ChevronDown, Message::MoveDown(index)),
+            checkbox(!step.skip)
+                .on_toggle(move |send| Message::SendToggled(index, send))
+                .size(14),
+            text(request.method.to_string())
+                .size(12)
+                .font(Font::MONOSPACE)
+                .color(method_color(&request.method))
+                .width(Length::Fixed(60.0)),
+            container(name).width(Length::Fill),
+            text(flow.join(", ")).size(11).color(MUTED_COLOR),
+            button(text(if editing { "Done" } else { "Flow" }).size(12))
+                .on_press(Message::EditFlow(index))
+                .padding([2, 8])
+                .style(runner_button_style),
+        ]
+        .spacing(6)
+        .align_y(iced::Alignment::Center)
+    ]
+    .spacing(6);
+
+    if editing {
+        let end = StepChoice {
+            request_id: String::new(),
+            label: "End of the run".to_string(),
+        };
+        // Loops go back to this step or an earlier one
+        let loop_choices: Vec<StepChoice> = choices.iter().take(index + 1).cloned().collect();
+        let loop_target = step
+            .loop_back
+            .as_ref()
+            .and_then(|loop_back| {
+                choices
+                    .iter()
+                    .find(|choice| choice.request_id == loop_back.to)
+            })
+            .cloned();
+        let mut jump_choices = choices.to_vec();
+        jump_choices.push(end.clone());
+        let jump_target = step.jump.as_ref().map(|jump| {
+            jump_choices
+                .iter()
+                .find(|choice| choice.request_id == jump.to)
+                .cloned()
+                .unwrap_or(end)
+        });
+        let times = step
+            .loop_back
+            .as_ref()
+            .map(|loop_back| loop_back.times.to_string())
+            .unwrap_or_default();
+
+        entry = entry.push(
+            row![
+                text(tr("runner-loop-back"))
+                    .size(12)
+                    .width(Length::Fixed(90.0)),
+                pick_list(loop_choices, loop_target, move |choice| {
+                    Message::LoopTargetSelected(index, choice)
+                })
+                .placeholder("No loop")
+                .text_size(12),
+                text_input("1", &times)
+                    .on_input_maybe(
+                        step.loop_back
+                            .is_some()
+                            .then_some(move |value| Message::LoopTimesChanged(index, value))
+                    )
+                    .width(Length::Fixed(50.0))
+                    .padding(4)
+                    .size(12),
+                text(tr("runner-loop-times")).size(12),
+                space().width(Length::Fill),
+                button(text(tr("remove")).size(12))
+                    .on_press_maybe(
+                        step.loop_back
+                            .is_some()
+                            .then_some(Message::RemoveLoop(index))
+                    )
+                    .padding([2, 8])
+                    .style(runner_button_style),
+            ]
+            .spacing(6)
+            .align_y(iced::Alignment::Center),
+        );
+        entry = entry.push(
+            row![
+                text(tr("runner-jump-when"))
+                    .size(12)
+                    .width(Length::Fixed(90.0)),
+                text_input(
+                    "all checks",
+                    step.jump.as_ref().map_or("", |jump| jump.test.as_str())
+                )
+                .on_input(move |value| Message::JumpTestChanged(index, value))
+                .width(Length::Fixed(140.0))
+                .padding(4)
+                .size(12),
+                pick_list(
+                    JumpWhen::ALL,
+                    step.jump.as_ref().map(|jump| jump.when),
+                    move |when| Message::JumpWhenSelected(index, when)
+                )
+                .placeholder("fails")
+                .text_size(12),
+                text(tr("runner-jump-to")).size(12),
+                pick_list(jump_choices, jump_target, move |choice| {
+                    Message::JumpTargetSelected(index, choice)
+                })
+                .placeholder("No jump")
+                .text_size(12),
+                space().width(Length::Fill),
+                button(text(tr("remove")).size(12))
+                    .on_press_maybe(step.jump.is_some().then_some(Message::RemoveJump(index)))
+                    .padding([2, 8])
+                    .style(runner_button_style),
+            ]
+            .spacing(6)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+
+    container(entry)
+        .padding([4, 8])
+        .width(Length::Fill)
+        .style(|_theme| Style {
+            border: Border {
+                color: Color::from_rgb(0.9, 0.9, 0.9),
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Style::default()
+        })
+        .into()
+}
+
+fn chained_flag<'a>() -> Element<'a, Message> {
+    tooltip(
+        text(tr("runner-chained")).size(11).color(WARNING_COLOR),
+        container(text(tr("runner-chained-hint")).size(12))
+            .max_width(320)
+            .padding([4, 8])
+            .style(container::rounded_box),
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
+fn request_row<'a>(
+    request: &'a RequestConfig,
+    result: Option<RunResult>,
+    retries: u32,
+    iteration: Option<usize>,
+    chained: &HashSet<String>,
+    mode: RunMode,
+) -> Element<'a, Message> {
+    let mut name = row![]
+        .push(iteration.map(|iteration| {
+            text(format!("#{}", iteration))
+                .size(11)
+                .font(Font::MONOSPACE)
+                .color(MUTED_COLOR)
+        }))
+        .push(text(&request.name).size(13))
+        .spacing(6)
+        .align_y(iced::Alignment::Center);
+    if mode == RunMode::Parallel && chained.contains(&request.id) {
+        name = name.push(chained_flag());
+    }
+
+    let outcome: Element<'_, Message> = match &result {
+        Some(result) => {
+            let mut summary = if result.status == 0 {
+                result.status_text.clone()
+            } else {
+                format!("{} in {} ms", result.status, result.time)
+            };
+            if let Some(budget) = result.budget.filter(|_| result.over_budget()) {
+                summary.push_str(&format!(", over the {} ms budget", budget));
+            }
+            let failed = result
+                .test_results
+                .iter()
+                .filter(|test| !test.passed)
+                .count();
+            if failed > 0 {
+                summary.push_str(&format!(
+                    ", {} of {} checks failed",
+                    failed,
+                    result.test_results.len()
+                ));
+            }
+            if let Some(error) = &result.script_error {
+                summary.push_str(&format!(", script failed: {}", error));
+            }
+            if retries > 0 {
+                summary.push_str(&format!(", after {} retries", retries));
+            }
+            if mode == RunMode::Sequential {
+                match &result.next_request {
+                    Some(NextRequest::Named(name)) => {
+                        summary.push_str(&format!(", script goes to {}", name));
+                    }
+                    Some(NextRequest::End) => summary.push_str(", script ends the run"),
+                    Some(NextRequest::Skip) => summary.push_str(", script skips the next request"),
+                    None => {}
+                }
+            }
+            text(summary)
+                .size(12)
+                .color(if !result.passed() {
+                    FAILURE_COLOR
+                } else if result.over_budget() {
+                    WARNING_COLOR
+                } else {
+                    SUCCESS_COLOR
+                })
+                .into()
+        }
+        None => space().width(0).into(),
+    };
+
+    let mut entry = column![
+        row![
+            text(request.method.to_string())
+                .size(12)
+                .font(Font::MONOSPACE)
+                .color(method_color(&request.method))
+                .width(Length::Fixed(60.0)),
+            container(name).width(Length::Fill),
+            outcome,
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center)
+    ]
+    .spacing(2);
+
+    if let Some(result) = &result
+        && !result.extracted.is_empty()
+    {
+        let variables = result
+            .extracted
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        entry = entry.push(
+            text(format!("Set {}", variables))
+                .size(11)
+                .color(MUTED_COLOR),
+        );
+    }
+
+    container(entry)
+        .padding([4, 8])
+        .width(Length::Fill)
+        .style(|_theme| Style {
+            border: Border {
+                color: Color::from_rgb(0.9, 0.9, 0.9),
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Style::default()
+        })
+        .into()
+}
+
+fn choice_button(label: &str, selected: bool, message: Option<Message>) -> Element<'_, Message> {
+    button(text(label).size(12))
+        .on_press_maybe(message)
+        .padding([2, 8])
+        .style(move |theme, status| {
+            let style = runner_button_style(theme, status);
+            if selected {
+                button::Style {
+                    background: Some(Background::Color(Color::from_rgb(0.85, 0.9, 1.0))),
+                    ..style
+                }
+            } else {
+                style
+            }
+        })
+        .into()
+}
+
+fn runner_button_style(_theme: &iced::Theme, status: Status) -> button::Style {
+    let base = button::Style {
+        text_color: Color::from_rgb(0.2, 0.2, 0.2),
+        border: Border {
+            color: Color::from_rgb(0.8, 0.8, 0.8),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..button::Style::default()
+    };
+
+    match status {
+        Status::Hovered => button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 0.9))),
+            ..base
+        },
+        Status::Disabled => button::Style {
+            text_color: Color::from_rgb(0.6, 0.6, 0.6),
+            ..base
+        },
+        _ => base,
+    }
+}
@@ -0,0 +1,265 @@
+use crate::i18n::{Language, tr};
+use crate::theme::Themes;
+use crate::tray;
+use crate::types::{JsonBodyFormatting, JsonFormatting};
+use iced::widget::button::Status;
+use iced::widget::container::Style;
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, space, text,
+};
+use iced::{Background, Border, Color, Element, Length, Shadow, Vector};
+
+const MUTED_COLOR: Color = Color::from_rgb(0.5, 0.5, 0.5);
+const ERROR_COLOR: Color = Color::from_rgb(0.82, 0.06, 0.22);
+
+/// The look of the app: the system's light or dark one, or a theme by name
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThemeChoice {
+    System,
+    Named(String),
+}
+
+impl std::fmt::Display for ThemeChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeChoice::System => write!(f, "{}", tr("settings-theme-system")),
+            ThemeChoice::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// The interface language: the system's, or one picked
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LanguageChoice {
+    System,
+    Fixed(Language),
+}
+
+impl std::fmt::Display for LanguageChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LanguageChoice::System => write!(f, "{}", tr("settings-language-system")),
+            LanguageChoice::Fixed(language) => write!(f, "{}", language),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ThemeSelected(ThemeChoice),
+    LanguageSelected(LanguageChoice),
+    LargeTargetsToggled(bool),
+    TrayToggled(bool),
+    UsageStatsToggled(bool),
+    JsonOnSaveSelected(JsonFormatting),
+    JsonOnSendSelected(JsonFormatting),
+    BlockMalformedJsonToggled(bool),
+    OpenUsageStats,
+    OpenThemesFolder,
+    Close,
+}
+
+/// App-wide preferences
+pub fn view<'a>(
+    themes: &'a Themes,
+    selected_theme: Option<&'a str>,
+    language: Option<Language>,
+    large_targets: bool,
+    show_tray: bool,
+    usage_stats: bool,
+    json_body: JsonBodyFormatting,
+) -> Element<'a, Message> {
+    let choices: Vec<ThemeChoice> = std::iter::once(ThemeChoice::System)
+        .chain(
+            themes
+                .themes
+                .iter()
+                .map(|theme| ThemeChoice::Named(theme.name.clone())),
+        )
+        .collect();
+    let selected = match selected_theme {
+        Some(name) => ThemeChoice::Named(name.to_string()),
+        None => ThemeChoice::System,
+    };
+
+    let mut theme_section = column![
+        text(tr("settings-theme")).size(15),
+        row![
+            pick_list(choices, Some(selected), Message::ThemeSelected).width(Length::Fill),
+            button(text(tr("settings-open-themes-folder")).size(14))
+                .on_press(Message::OpenThemesFolder)
+                .padding([6, 10])
+                .style(settings_button_style),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center),
+        text(tr("settings-themes-hint")).size(12).color(MUTED_COLOR),
+    ]
+    .spacing(8);
+    if selected_theme.is_some_and(|name| themes.find(name).is_none()) {
+        theme_section = theme_section.push(
+            text(tr("settings-theme-missing"))
+                .size(12)
+                .color(ERROR_COLOR),
+        );
+    }
+    for error in &themes.errors {
+        theme_section = theme_section.push(text(error).size(12).color(ERROR_COLOR));
+    }
+
+    let languages: Vec<LanguageChoice> = std::iter::once(LanguageChoice::System)
+        .chain(Language::ALL.into_iter().map(LanguageChoice::Fixed))
+        .collect();
+    let language_section = column![
+        text(tr("settings-language")).size(15),
+        pick_list(
+            languages,
+            Some(language.map_or(LanguageChoice::System, LanguageChoice::Fixed)),
+            Message::LanguageSelected
+        )
+        .width(Length::Fill),
+    ]
+    .spacing(8);
+
+    let accessibility_section = column![
+        text(tr("settings-accessibility")).size(15),
+        checkbox(large_targets)
+            .label(tr("settings-large-targets"))
+            .on_toggle(Message::LargeTargetsToggled),
+    ]
+    .spacing(8);
+
+    let mut tray_section = column![
+        text(tr("settings-tray")).size(15),
+        checkbox(show_tray)
+            .label(tr("settings-show-tray"))
+            .on_toggle_maybe(tray::is_supported().then_some(Message::TrayToggled)),
+        text(tr("settings-tray-hint")).size(12).color(MUTED_COLOR),
+    ]
+    .spacing(8);
+    if !tray::is_supported() {
+        tray_section = tray_section.push(
+            text(tr("settings-tray-unsupported"))
+                .size(12)
+                .color(ERROR_COLOR),
+        );
+    }
+
+    let json_section = column![
+        text(tr("settings-json")).size(15),
+        row![
+            text(tr("settings-json-on-save")).size(14).width(Length::Fill),
+            pick_list(
+                JsonFormatting::ALL,
+                Some(json_body.on_save),
+                Message::JsonOnSaveSelected
+            )
+            .width(Length::Fixed(160.0)),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center),
+        row![
+            text(tr("settings-json-on-send")).size(14).width(Length::Fill),
+            pick_list(
+                JsonFormatting::ALL,
+                Some(json_body.on_send),
+                Message::JsonOnSendSelected
+            )
+            .width(Length::Fixed(160.0)),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center),
+        checkbox(json_body.block_malformed)
+            .label(tr("settings-json-block-malformed"))
+            .on_toggle(Message::BlockMalformedJsonToggled),
+        text(tr("settings-json-hint")).size(12).color(MUTED_COLOR),
+    ]
+    .spacing(8);
+
+    let usage_section = column![
+        text(tr("settings-usage")).size(15),
+        row![
+            checkbox(usage_stats)
+                .label(tr("settings-usage-stats"))
+                .on_toggle(Message::UsageStatsToggled)
+                .width(Length::Fill),
+            button(text(tr("settings-open-usage")).size(14))
+                .on_press_maybe(usage_stats.then_some(Message::OpenUsageStats))
+                .padding([6, 10])
+                .style(settings_button_style),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center),
+        text(tr("settings-usage-hint")).size(12).color(MUTED_COLOR),
+    ]
+    .spacing(8);
+
+    let footer = row![
+        space().width(Length::Fill),
+        button(text(tr("close")).size(16))
+            .on_press(Message::Close)
+            .padding(10)
+            .style(settings_button_style),
+    ];
+
+    container(
+        column![
+            text(tr("settings-title")).size(18),
+            container(scrollable(
+                column![
+                    theme_section,
+                    language_section,
+                    accessibility_section,
+                    tray_section,
+                    json_section,
+                    usage_section
+                ]
+                .spacing(20)
+            ))
+            .height(Length::Fill),
+            footer,
+        ]
+        .spacing(15),
+    )
+    .padding(20)
+    .height(Length::Fill)
+    // Drawn with the palette so a theme can be judged while picking it
+    .style(|theme: &iced::Theme| Style {
+        background: Some(Background::Color(theme.palette().background)),
+        border: Border {
+            color: Color::from_rgb(0.7, 0.7, 0.7),
+            width: 1.0,
+            radius: 8.0.into(),
+        },
+        shadow: Shadow {
+            color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+            offset: Vector::new(0.0, 4.0),
+            blur_radius: 10.0,
+        },
+        ..Style::default()
+    })
+    .into()
+}
+
+fn settings_button_style(theme: &iced::Theme, status: Status) -> button::Style {
+    let base = button::Style {
+        text_color: theme.palette().text,
+        border: Border {
+            color: Color::from_rgb(0.8, 0.8, 0.8),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..button::Style::default()
+    };
+
+    match status {
+        Status::Hovered => button::Style {
+            background: Some(Background::Color(Color {
+                a: 0.1,
+                ..theme.palette().text
+            })),
+            ..base
+        },
+        _ => base,
+    }
+}
@@ -0,0 +1,385 @@
+use crate::i18n::{tr, tr_args};
+use crate::stats::{CollectionStats, DayStats, EndpointStats, HostStats, RequestUsage, UsageStats};
+use crate::ui::method_color;
+use iced::widget::button::Status;
+use iced::widget::container::Style;
+use iced::widget::{button, column, container, row, scrollable, space, text, tooltip};
+use iced::{Background, Border, Color, Element, Font, Length, Shadow, Vector};
+
+/// Endpoints listed under the slowest ones
+const SLOWEST_ENDPOINTS: usize = 10;
+
+/// Requests listed under the most used ones, and hosts under the latency per host
+const MOST_USED: usize = 10;
+
+const DAY_CHART_HEIGHT: f32 = 80.0;
+
+const SUCCESS_COLOR: Color = Color::from_rgb(0.0, 0.6, 0.3);
+const FAILURE_COLOR: Color = Color::from_rgb(0.82, 0.06, 0.22);
+const MUTED_COLOR: Color = Color::from_rgb(0.5, 0.5, 0.5);
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Close,
+}
+
+/// Success rate, latency and failures of the requests of a collection, from their run history
+pub fn view(stats: &CollectionStats) -> Element<'_, Message> {
+    let content: Element<'_, Message> = if stats.runs == 0 {
+        text(tr("stats-empty"))
+            .size(14)
+            .color(MUTED_COLOR)
+            .into()
+    } else {
+        let success_rate = stats.success_rate();
+        let summary = row![
+            figure(tr("stats-runs"), stats.runs.to_string(), Color::BLACK),
+            figure(
+                tr("stats-success-rate"),
+                format!("{:.1}%", success_rate),
+                if success_rate >= 95.0 {
+                    SUCCESS_COLOR
+                } else {
+                    FAILURE_COLOR
+                }
+            ),
+            figure(
+                tr("stats-average-latency"),
+                format!("{} ms", stats.average_ms),
+                Color::BLACK
+            ),
+            figure(
+                tr("stats-failures"),
+                stats.failures.to_string(),
+                if stats.failures == 0 {
+                    Color::BLACK
+                } else {
+                    FAILURE_COLOR
+                }
+            ),
+        ]
+        .spacing(10);
+
+        let slowest_ms = stats
+            .endpoints
+            .first()
+            .map(|endpoint| endpoint.average_ms)
+            .unwrap_or(0)
+            .max(1);
+        let mut endpoints = column![].spacing(6);
+        for endpoint in stats.endpoints.iter().take(SLOWEST_ENDPOINTS) {
+            endpoints = endpoints.push(endpoint_row(endpoint, slowest_ms));
+        }
+
+        let busiest_day = stats
+            .days
+            .iter()
+            .map(|day| day.runs)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let mut days = row![].spacing(4).align_y(iced::Alignment::End);
+        for day in &stats.days {
+            days = days.push(day_bar(day, busiest_day));
+        }
+
+        scrollable(
+            column![
+                summary,
+                text(tr("stats-slowest")).size(15),
+                text(tr("stats-slowest-hint"))
+                    .size(12)
+                    .color(MUTED_COLOR),
+                endpoints,
+                text(tr_args("stats-runs-by-day", &[("days", &stats.days.len())])).size(15),
+                text(tr("stats-runs-by-day-hint"))
+                    .size(12)
+                    .color(MUTED_COLOR),
+                days,
+            ]
+            .spacing(10),
+        )
+        .height(Length::Fill)
+        .into()
+    };
+
+    frame(tr_args("stats-title", &[("name", &stats.name)]), content)
+}
+
+/// Requests sent, the most used ones and the latency per host across all collections
+pub fn usage_view(stats: &UsageStats) -> Element<'_, Message> {
+    let content: Element<'_, Message> = if stats.runs == 0 {
+        text(tr("stats-empty"))
+            .size(14)
+            .color(MUTED_COLOR)
+            .into()
+    } else {
+        let summary = row![
+            figure(tr("stats-runs"), stats.runs.to_string(), Color::BLACK),
+            figure(
+                tr("usage-requests-used"),
+                stats.requests.len().to_string(),
+                Color::BLACK
+            ),
+            figure(
+                tr("stats-average-latency"),
+                format!("{} ms", stats.average_ms),
+                Color::BLACK
+            ),
+        ]
+        .spacing(10);
+
+        let most_runs = stats.requests.first().map_or(1, |request| request.runs);
+        let mut requests = column![].spacing(6);
+        for request in stats.requests.iter().take(MOST_USED) {
+            requests = requests.push(usage_row(request, most_runs));
+        }
+
+        let mut hosts = column![].spacing(6);
+        for host in stats.hosts.iter().take(MOST_USED) {
+            hosts = hosts.push(host_row(host));
+        }
+
+        scrollable(
+            column![
+                summary,
+                text(tr("usage-most-used")).size(15),
+                requests,
+                text(tr("usage-hosts")).size(15),
+                hosts,
+                text(tr("usage-hint")).size(12).color(MUTED_COLOR),
+            ]
+            .spacing(10),
+        )
+        .height(Length::Fill)
+        .into()
+    };
+
+    frame(tr("usage-title"), content)
+}
+
+/// Dialog around a statistics page, with its title and a Close button
+fn frame<'a>(title: String, content: Element<'a, Message>) -> Element<'a, Message> {
+    let footer = row![
+        space().width(Length::Fill),
+        button(text(tr("close")).size(16))
+            .on_press(Message::Close)
+            .padding(10)
+            .style(stats_button_style),
+    ];
+
+    container(
+        column![
+            text(title).size(18),
+            container(content).height(Length::Fill),
+            footer,
+        ]
+        .spacing(15),
+    )
+    .padding(20)
+    .height(Length::Fill)
+    .style(|_theme| Style {
+        background: Some(Background::Color(Color::WHITE)),
+        border: Border {
+            color: Color::from_rgb(0.7, 0.7, 0.7),
+            width: 1.0,
+            radius: 8.0.into(),
+        },
+        shadow: Shadow {
+            color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+            offset: Vector::new(0.0, 4.0),
+            blur_radius: 10.0,
+        },
+        ..Style::default()
+    })
+    .into()
+}
+
+fn figure<'a>(label: String, value: String, color: Color) -> Element<'a, Message> {
+    container(
+        column![
+            text(label).size(12).color(MUTED_COLOR),
+            text(value).size(20).color(color),
+        ]
+        .spacing(4),
+    )
+    .padding([8, 12])
+    .width(Length::Fill)
+    .style(|_theme| Style {
+        border: Border {
+            color: Color::from_rgb(0.85, 0.85, 0.85),
+            width: 1.0,
+            radius: 6.0.into(),
+        },
+        ..Style::default()
+    })
+    .into()
+}
+
+fn endpoint_row(endpoint: &EndpointStats, slowest_ms: u64) -> Element<'_, Message> {
+    let percent = (endpoint.average_ms * 100 / slowest_ms).clamp(1, 100) as u16;
+    let bar_color = if endpoint.failures > 0 {
+        FAILURE_COLOR
+    } else {
+        Color::from_rgb(0.3, 0.5, 0.9)
+    };
+    let bar = row![
+        container(space())
+            .width(Length::FillPortion(percent))
+            .height(Length::Fixed(8.0))
+            .style(move |_theme| Style {
+                background: Some(Background::Color(bar_color)),
+                border: Border {
+                    radius: 2.0.into(),
+                    ..Border::default()
+                },
+                ..Style::default()
+            }),
+    ]
+    .push((percent < 100).then(|| space().width(Length::FillPortion(100 - percent))));
+
+    let mut details = format!(
+        "{} ms average, {} ms slowest, {} runs",
+        endpoint.average_ms, endpoint.slowest_ms, endpoint.runs
+    );
+    if endpoint.failures > 0 {
+        details.push_str(&format!(", {} failed", endpoint.failures));
+    }
+    if endpoint.over_budget > 0 {
+        details.push_str(&format!(", {} over budget", endpoint.over_budget));
+    }
+
+    column![
+        row![
+            text(endpoint.method.to_string())
+                .size(12)
+                .font(Font::MONOSPACE)
+                .color(method_color(&endpoint.method))
+                .width(Length::Fixed(60.0)),
+            text(&endpoint.name).size(13).width(Length::Fill),
+            text(details).size(12).color(MUTED_COLOR),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center),
+        bar,
+    ]
+    .spacing(3)
+    .into()
+}
+
+fn usage_row(request: &RequestUsage, most_runs: usize) -> Element<'_, Message> {
+    let percent = (request.runs * 100 / most_runs.max(1)).clamp(1, 100) as u16;
+    let bar = row![
+        container(space())
+            .width(Length::FillPortion(percent))
+            .height(Length::Fixed(8.0))
+            .style(|_theme| Style {
+                background: Some(Background::Color(Color::from_rgb(0.3, 0.5, 0.9))),
+                border: Border {
+                    radius: 2.0.into(),
+                    ..Border::default()
+                },
+                ..Style::default()
+            }),
+    ]
+    .push((percent < 100).then(|| space().width(Length::FillPortion(100 - percent))));
+
+    column![
+        row![
+            text(request.method.to_string())
+                .size(12)
+                .font(Font::MONOSPACE)
+                .color(method_color(&request.method))
+                .width(Length::Fixed(60.0)),
+            text(&request.name).size(13).width(Length::Fill),
+            text(format!("{}, {} runs", request.collection, request.runs))
+                .size(12)
+                .color(MUTED_COLOR),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center),
+        bar,
+    ]
+    .spacing(3)
+    .into()
+}
+
+fn host_row(host: &HostStats) -> Element<'_, Message> {
+    row![
+        text(&host.host)
+            .size(13)
+            .font(Font::MONOSPACE)
+            .width(Length::Fill),
+        text(format!("{} ms average, {} runs", host.average_ms, host.runs))
+            .size(12)
+            .color(MUTED_COLOR),
+    ]
+    .spacing(8)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+fn day_bar(day: &DayStats, busiest_day: usize) -> Element<'_, Message> {
+    let height = |runs: usize| runs as f32 / busiest_day as f32 * DAY_CHART_HEIGHT;
+    let segment = |runs: usize, color: Color| {
+        container(space())
+            .width(Length::Fill)
+            .height(Length::Fixed(height(runs)))
+            .style(move |_theme| Style {
+                background: Some(Background::Color(color)),
+                ..Style::default()
+            })
+    };
+
+    let bar = column![
+        segment(day.failures, FAILURE_COLOR),
+        segment(day.runs - day.failures, Color::from_rgb(0.6, 0.75, 0.95)),
+    ];
+
+    tooltip(
+        column![
+            container(bar)
+                .height(Length::Fixed(DAY_CHART_HEIGHT))
+                .align_y(iced::alignment::Vertical::Bottom),
+            text(day.date.format("%d").to_string())
+                .size(10)
+                .color(MUTED_COLOR),
+        ]
+        .spacing(2)
+        .width(Length::Fill)
+        .align_x(iced::Alignment::Center),
+        container(
+            text(format!(
+                "{}: {} runs, {} failed",
+                day.date.format("%Y-%m-%d"),
+                day.runs,
+                day.failures
+            ))
+            .size(12),
+        )
+        .padding([4, 8])
+        .style(container::rounded_box),
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
+fn stats_button_style(_theme: &iced::Theme, status: Status) -> button::Style {
+    let base = button::Style {
+        text_color: Color::from_rgb(0.2, 0.2, 0.2),
+        border: Border {
+            color: Color::from_rgb(0.8, 0.8, 0.8),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..button::Style::default()
+    };
+
+    match status {
+        Status::Hovered => button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 0.9))),
+            ..base
+        },
+        _ => base,
+    }
+}
@@ -0,0 +1,300 @@
+use crate::i18n::tr;
+use crate::sync::{RemoteKind, RemoteSyncConfig, Resolution, SyncReport};
+use iced::widget::button::Status;
+use iced::widget::container::Style;
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, space, text, text_input,
+};
+use iced::{Background, Border, Color, Element, Length, Shadow, Vector};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    /// Sync using the panel's current `config` and `resolutions`
+    Sync,
+    Close,
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    KindChanged(RemoteKind),
+    EndpointChanged(String),
+    PrefixChanged(String),
+    UsernameChanged(String),
+    PasswordChanged(String),
+    BucketChanged(String),
+    RegionChanged(String),
+    AccessKeyChanged(String),
+    SecretKeyChanged(String),
+    ResolveConflict(String, Resolution),
+    SyncNow,
+    Close,
+}
+
+/// Settings and status of the S3/WebDAV workspace sync
+#[derive(Debug, Clone, Default)]
+pub struct SyncPanel {
+    pub config: RemoteSyncConfig,
+    pub is_syncing: bool,
+    pub status: Option<Result<String, String>>,
+    pub conflicts: Vec<String>,
+    pub resolutions: BTreeMap<String, Resolution>,
+}
+
+impl SyncPanel {
+    pub fn new(config: RemoteSyncConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Action {
+        match message {
+            Message::KindChanged(kind) => self.config.kind = kind,
+            Message::EndpointChanged(value) => self.config.endpoint = value,
+            Message::PrefixChanged(value) => self.config.prefix = value,
+            Message::UsernameChanged(value) => self.config.username = value,
+            Message::PasswordChanged(value) => self.config.password = value,
+            Message::BucketChanged(value) => self.config.bucket = value,
+            Message::RegionChanged(value) => self.config.region = value,
+            Message::AccessKeyChanged(value) => self.config.access_key = value,
+            Message::SecretKeyChanged(value) => self.config.secret_key = value,
+            Message::ResolveConflict(path, resolution) => {
+                self.resolutions.insert(path, resolution);
+            }
+            Message::SyncNow => {
+                if self.is_syncing {
+                    return Action::None;
+                }
+                self.is_syncing = true;
+                self.status = None;
+                return Action::Sync;
+            }
+            Message::Close => return Action::Close,
+        }
+
+        Action::None
+    }
+
+    /// Records the outcome of a sync started with `Action::Sync`
+    pub fn finish(&mut self, result: Result<SyncReport, String>) {
+        self.is_syncing = false;
+
+        match result {
+            Ok(report) => {
+                self.resolutions
+                    .retain(|path, _| report.conflicts.contains(path));
+                self.status = Some(if report.conflicts.is_empty() {
+                    Ok(format!(
+                        "Synced: {} pushed, {} pulled",
+                        report.pushed, report.pulled
+                    ))
+                } else {
+                    Err(format!(
+                        "{} pushed, {} pulled, {} conflicts. Choose a side and sync again.",
+                        report.pushed,
+                        report.pulled,
+                        report.conflicts.len()
+                    ))
+                });
+                self.conflicts = report.conflicts;
+            }
+            Err(e) => self.status = Some(Err(e)),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let field = |label: &'static str, value: &str, on_input: fn(String) -> Message| {
+            row![
+                text(label).size(14).width(Length::Fixed(110.0)),
+                text_input("", value).on_input(on_input).padding(6).size(14),
+            ]
+            .align_y(iced::Alignment::Center)
+        };
+        let secret_field = |label: &'static str, value: &str, on_input: fn(String) -> Message| {
+            row![
+                text(label).size(14).width(Length::Fixed(110.0)),
+                text_input("", value)
+                    .on_input(on_input)
+                    .secure(true)
+                    .padding(6)
+                    .size(14),
+            ]
+            .align_y(iced::Alignment::Center)
+        };
+
+        let mut form = column![
+            row![
+                text(tr("sync-backend"))
+                    .size(14)
+                    .width(Length::Fixed(110.0)),
+                pick_list(
+                    RemoteKind::ALL,
+                    Some(self.config.kind),
+                    Message::KindChanged
+                )
+                .text_size(14),
+            ]
+            .align_y(iced::Alignment::Center),
+            field("Endpoint", &self.config.endpoint, Message::EndpointChanged),
+        ]
+        .spacing(8);
+
+        form = match self.config.kind {
+            RemoteKind::WebDav => form
+                .push(field(
+                    "Username",
+                    &self.config.username,
+                    Message::UsernameChanged,
+                ))
+                .push(secret_field(
+                    "Password",
+                    &self.config.password,
+                    Message::PasswordChanged,
+                )),
+            RemoteKind::S3 => form
+                .push(field("Bucket", &self.config.bucket, Message::BucketChanged))
+                .push(field("Region", &self.config.region, Message::RegionChanged))
+                .push(field(
+                    "Access key",
+                    &self.config.access_key,
+                    Message::AccessKeyChanged,
+                ))
+                .push(secret_field(
+                    "Secret key",
+                    &self.config.secret_key,
+                    Message::SecretKeyChanged,
+                )),
+        };
+        form = form.push(field("Folder", &self.config.prefix, Message::PrefixChanged));
+        if !self.config.password.is_empty() || !self.config.secret_key.is_empty() {
+            form = form.push(
+                text(tr("sync-secrets-warning"))
+                    .size(12)
+                    .color(Color::from_rgb(0.8, 0.5, 0.0)),
+            );
+        }
+
+        let mut conflicts = column![].spacing(4);
+        for path in &self.conflicts {
+            let chosen = self.resolutions.get(path).copied();
+            conflicts = conflicts.push(
+                row![
+                    text(path).size(12).width(Length::Fill),
+                    choice_button(
+                        "Keep local",
+                        chosen == Some(Resolution::KeepLocal),
+                        Message::ResolveConflict(path.clone(), Resolution::KeepLocal)
+                    ),
+                    choice_button(
+                        "Keep remote",
+                        chosen == Some(Resolution::KeepRemote),
+                        Message::ResolveConflict(path.clone(), Resolution::KeepRemote)
+                    ),
+                ]
+                .spacing(4)
+                .align_y(iced::Alignment::Center),
+            );
+        }
+
+        let status: Element<'_, Message> = match &self.status {
+            _ if self.is_syncing => text(tr("sync-syncing")).size(14).into(),
+            Some(Ok(summary)) => text(summary)
+                .size(14)
+                .color(Color::from_rgb(0.25, 0.63, 0.17))
+                .into(),
+            Some(Err(e)) => text(e)
+                .size(14)
+                .color(Color::from_rgb(0.82, 0.06, 0.22))
+                .into(),
+            None => space().height(0).into(),
+        };
+
+        let buttons = row![
+            space().width(Length::Fill),
+            button(text(tr("close")).size(16))
+                .on_press(Message::Close)
+                .padding(10)
+                .style(sync_button_style),
+            button(text(tr("sync-now")).size(16))
+                .on_press_maybe(
+                    (!self.is_syncing && self.config.is_configured()).then_some(Message::SyncNow)
+                )
+                .padding(10)
+                .style(sync_button_style),
+        ]
+        .spacing(10);
+
+        container(
+            column![
+                text(tr("sync-title")).size(18),
+                text(tr("sync-hint")).size(14),
+                form,
+                scrollable(conflicts).height(Length::Shrink),
+                status,
+                buttons,
+            ]
+            .spacing(12),
+        )
+        .padding(20)
+        .style(|_theme| Style {
+            background: Some(Background::Color(Color::WHITE)),
+            border: Border {
+                color: Color::from_rgb(0.7, 0.7, 0.7),
+                width: 1.0,
+                radius: 8.0.into(),
+            },
+            shadow: Shadow {
+                color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                offset: Vector::new(0.0, 4.0),
+                blur_radius: 10.0,
+            },
+            ..Style::default()
+        })
+        .into()
+    }
+}
+
+fn choice_button(label: &str, selected: bool, message: Message) -> Element<'_, Message> {
+    button(text(label).size(12))
+        .on_press(message)
+        .padding([2, 8])
+        .style(move |theme, status| {
+            let style = sync_button_style(theme, status);
+            if selected {
+                button::Style {
+                    background: Some(Background::Color(Color::from_rgb(0.85, 0.9, 1.0))),
+                    ..style
+                }
+            } else {
+                style
+            }
+        })
+        .into()
+}
+
+fn sync_button_style(_theme: &iced::Theme, status: Status) -> button::Style {
+    let base = button::Style {
+        text_color: Color::from_rgb(0.2, 0.2, 0.2),
+        border: Border {
+            color: Color::from_rgb(0.8, 0.8, 0.8),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..button::Style::default()
+    };
+
+    match status {
+        Status::Hovered => button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 0.9))),
+            ..base
+        },
+        Status::Disabled => button::Style {
+            text_color: Color::from_rgb(0.6, 0.6, 0.6),
+            ..base
+        },
+        _ => base,
+    }
+}
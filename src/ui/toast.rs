@@ -0,0 +1,180 @@
+use crate::i18n::tr;
+use iced::widget::container::Style;
+use iced::widget::{button, column, container, row, space, text};
+use iced::{Background, Border, Color, Element, Length, Shadow, Vector};
+use std::time::{Duration, Instant};
+
+/// How long a toast stays up unless it was expanded
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// Toasts shown at once, older ones are dropped first
+const MAX_TOASTS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Success,
+    Info,
+    Error,
+}
+
+impl ToastKind {
+    fn color(&self) -> Color {
+        match self {
+            ToastKind::Success => Color::from_rgb(0.25, 0.63, 0.17),
+            ToastKind::Info => Color::from_rgb(0.2, 0.45, 0.85),
+            ToastKind::Error => Color::from_rgb(0.82, 0.06, 0.22),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub id: u64,
+    pub kind: ToastKind,
+    pub title: String,
+    pub detail: Option<String>,
+    pub expanded: bool,
+    pub created_at: Instant,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ToggleExpanded(u64),
+    Dismiss(u64),
+}
+
+/// Short-lived notifications stacked in the bottom-right corner
+#[derive(Debug, Default)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+    next_id: u64,
+}
+
+impl Toasts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    pub fn push(&mut self, kind: ToastKind, title: impl Into<String>, detail: Option<String>) {
+        self.toasts.push(Toast {
+            id: self.next_id,
+            kind,
+            title: title.into(),
+            detail: detail.filter(|detail| !detail.is_empty()),
+            expanded: false,
+            created_at: Instant::now(),
+        });
+        self.next_id += 1;
+
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    pub fn success(&mut self, title: impl Into<String>) {
+        self.push(ToastKind::Success, title, None);
+    }
+
+    pub fn info(&mut self, title: impl Into<String>) {
+        self.push(ToastKind::Info, title, None);
+    }
+
+    pub fn error(&mut self, title: impl Into<String>, detail: impl Into<String>) {
+        self.push(ToastKind::Error, title, Some(detail.into()));
+    }
+
+    /// Drops toasts that have been up long enough, expanded ones stay until dismissed
+    pub fn tick(&mut self, now: Instant) {
+        self.toasts.retain(|toast| {
+            toast.expanded || now.duration_since(toast.created_at) < TOAST_DURATION
+        });
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::ToggleExpanded(id) => {
+                if let Some(toast) = self.toasts.iter_mut().find(|toast| toast.id == id) {
+                    toast.expanded = !toast.expanded;
+                }
+            }
+            Message::Dismiss(id) => self.toasts.retain(|toast| toast.id != id),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let mut list = column![].spacing(8).width(Length::Fixed(320.0));
+
+        for toast in &self.toasts {
+            list = list.push(toast_view(toast));
+        }
+
+        container(list)
+            .align_right(Length::Fill)
+            .align_bottom(Length::Fill)
+            .padding(16)
+            .into()
+    }
+}
+
+fn toast_view(toast: &Toast) -> Element<'_, Message> {
+    let accent = toast.kind.color();
+
+    let mut body = column![
+        row![
+            text(&toast.title).size(14).width(Length::Fill),
+            button(text("×").size(14))
+                .on_press(Message::Dismiss(toast.id))
+                .padding([0, 4])
+                .style(|_theme, _status| button::Style {
+                    background: None,
+                    text_color: Color::from_rgb(0.5, 0.5, 0.5),
+                    ..button::Style::default()
+                }),
+        ]
+        .align_y(iced::Alignment::Center)
+    ]
+    .spacing(4);
+
+    if let Some(detail) = &toast.detail {
+        body = body.push(if toast.expanded {
+            text(detail).size(12)
+        } else {
+            text(tr("toast-details"))
+                .size(12)
+                .color(Color::from_rgb(0.5, 0.5, 0.5))
+        });
+    }
+
+    let card = container(row![
+        container(space().width(4).height(Length::Fill)).style(move |_theme| Style {
+            background: Some(Background::Color(accent)),
+            ..Style::default()
+        }),
+        container(body).padding([8, 10]).width(Length::Fill),
+    ])
+    .height(Length::Shrink)
+    .style(|_theme| Style {
+        background: Some(Background::Color(Color::WHITE)),
+        border: Border {
+            color: Color::from_rgb(0.85, 0.85, 0.85),
+            width: 1.0,
+            radius: 6.0.into(),
+        },
+        shadow: Shadow {
+            color: Color::from_rgba(0.0, 0.0, 0.0, 0.2),
+            offset: Vector::new(0.0, 2.0),
+            blur_radius: 8.0,
+        },
+        ..Style::default()
+    });
+
+    button(card)
+        .on_press(Message::ToggleExpanded(toast.id))
+        .padding(0)
+        .style(|_theme, _status| button::Style::default())
+        .into()
+}
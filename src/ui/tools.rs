@@ -0,0 +1,458 @@
+use crate::i18n::tr;
+use crate::raw_socket::{self, SocketProtocol, SocketReply};
+use crate::tools::{self, DecodedJwt};
+use crate::types::new_id;
+use iced::widget::button::Status;
+use iced::widget::container::Style;
+use iced::widget::{
+    button, checkbox, column, container, row, scrollable, space, text, text_input,
+};
+use iced::{Background, Border, Color, Element, Font, Length, Shadow, Task, Vector};
+use std::time::Duration;
+
+/// How long the socket tool waits to connect and for the first bytes of a reply
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum Action {
+    CopyToClipboard(String),
+    // The components needs to run a task
+    Run(Task<Message>),
+    Close,
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ToolSelected(Tool),
+    InputChanged(String),
+    /// Runs the tool forwards: encode, generate or epoch to ISO 8601
+    Apply,
+    /// Runs the tool backwards: decode or ISO 8601 to epoch
+    Reverse,
+    UseCurrentTime,
+    SocketProtocolSelected(SocketProtocol),
+    SocketAddressChanged(String),
+    SocketHexToggled(bool),
+    SocketReplied(Result<SocketReply, String>),
+    CopyOutput,
+    Close,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tool {
+    #[default]
+    Base64,
+    Url,
+    Jwt,
+    Uuid,
+    Time,
+    Socket,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 6] = [
+        Tool::Base64,
+        Tool::Url,
+        Tool::Jwt,
+        Tool::Uuid,
+        Tool::Time,
+        Tool::Socket,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Tool::Base64 => "Base64",
+            Tool::Url => "URL",
+            Tool::Jwt => "JWT",
+            Tool::Uuid => "UUID",
+            Tool::Time => "Epoch / ISO",
+            Tool::Socket => "TCP / UDP",
+        }
+    }
+
+    fn placeholder(&self) -> &'static str {
+        match self {
+            Tool::Base64 => "Text to encode or base64 to decode",
+            Tool::Url => "Text to encode or percent-encoded text to decode",
+            Tool::Jwt => "eyJhbGciOi... or Bearer eyJhbGciOi...",
+            Tool::Uuid => "",
+            Tool::Time => "1700000000, 1700000000000 or 2023-11-14T22:13:20Z",
+            Tool::Socket => "Text to send, with \\r\\n or \\xNN escapes",
+        }
+    }
+
+    /// Labels of the forward and reverse buttons, the reverse one being optional
+    fn button_labels(&self) -> (&'static str, Option<&'static str>) {
+        match self {
+            Tool::Base64 | Tool::Url => ("Encode", Some("Decode")),
+            Tool::Jwt => ("Decode", None),
+            Tool::Uuid => ("Generate", None),
+            Tool::Time => ("To ISO 8601", Some("To Epoch")),
+            Tool::Socket => ("Send", None),
+        }
+    }
+}
+
+/// One-off converters for debugging: base64, URL encoding, JWTs, UUIDs and timestamps.
+/// They run locally, only the socket tool sends what is typed, to the address given.
+#[derive(Debug, Clone, Default)]
+pub struct ToolsPanel {
+    pub tool: Tool,
+    pub input: String,
+    output: Option<Result<String, String>>,
+    jwt: Option<DecodedJwt>,
+    socket_protocol: SocketProtocol,
+    socket_address: String,
+    socket_hex: bool,
+    socket_sending: bool,
+    /// Peer, timings and size of the last socket reply
+    socket_summary: Option<String>,
+}
+
+impl ToolsPanel {
+    pub fn update(&mut self, message: Message) -> Action {
+        match message {
+            Message::ToolSelected(tool) => {
+                if tool != self.tool {
+                    self.tool = tool;
+                    self.output = None;
+                    self.jwt = None;
+                    self.socket_summary = None;
+                }
+            }
+            Message::InputChanged(input) => self.input = input,
+            Message::Apply if self.tool == Tool::Socket => return self.send_socket(),
+            Message::Apply => self.run(true),
+            Message::Reverse => self.run(false),
+            Message::UseCurrentTime => {
+                self.input = chrono::Utc::now().timestamp().to_string();
+                self.run(true);
+            }
+            Message::SocketProtocolSelected(protocol) => self.socket_protocol = protocol,
+            Message::SocketAddressChanged(address) => self.socket_address = address,
+            Message::SocketHexToggled(hex) => self.socket_hex = hex,
+            Message::SocketReplied(result) => {
+                self.socket_sending = false;
+                self.socket_summary = None;
+                self.output = Some(result.map(|reply| {
+                    let mut summary = format!("{} bytes from {}", reply.bytes.len(), reply.peer);
+                    if let Some(connect_time) = reply.connect_time {
+                        summary.push_str(&format!(
+                            ", connected in {} ms",
+                            connect_time.as_millis()
+                        ));
+                    }
+                    summary.push_str(&format!(", done in {} ms", reply.elapsed.as_millis()));
+                    if reply.truncated {
+                        summary.push_str(", cut off at 1 MB");
+                    }
+                    self.socket_summary = Some(summary);
+
+                    if reply.bytes.is_empty() {
+                        "(no reply)".to_string()
+                    } else {
+                        raw_socket::format_reply(&reply.bytes)
+                    }
+                }));
+            }
+            Message::CopyOutput => {
+                if let Some(Ok(output)) = &self.output {
+                    return Action::CopyToClipboard(output.clone());
+                }
+            }
+            Message::Close => return Action::Close,
+        }
+
+        Action::None
+    }
+
+    /// Switches to the JWT tool and decodes `token`
+    pub fn inspect_jwt(&mut self, token: String) {
+        self.tool = Tool::Jwt;
+        self.input = token;
+        self.run(true);
+    }
+
+    fn send_socket(&mut self) -> Action {
+        if self.socket_sending {
+            return Action::None;
+        }
+        let payload = match raw_socket::parse_payload(&self.input, self.socket_hex) {
+            Ok(payload) => payload,
+            Err(e) => {
+                self.output = Some(Err(e));
+                return Action::None;
+            }
+        };
+
+        self.socket_sending = true;
+        self.socket_summary = None;
+        let (protocol, address) = (self.socket_protocol, self.socket_address.clone());
+        Action::Run(Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    raw_socket::exchange(protocol, &address, &payload, SOCKET_TIMEOUT)
+                })
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result)
+            },
+            Message::SocketReplied,
+        ))
+    }
+
+    fn run(&mut self, forward: bool) {
+        self.jwt = None;
+        self.output = Some(match (self.tool, forward) {
+            (Tool::Base64, true) => Ok(tools::base64_encode(&self.input)),
+            (Tool::Base64, false) => tools::base64_decode(&self.input),
+            (Tool::Url, true) => Ok(tools::url_encode(&self.input)),
+            (Tool::Url, false) => tools::url_decode(&self.input),
+            (Tool::Jwt, _) => tools::decode_jwt(&self.input).map(|jwt| {
+                let output = format!("{}\n{}", jwt.header, jwt.payload);
+                self.jwt = Some(jwt);
+                output
+            }),
+            (Tool::Uuid, _) => Ok(new_id()),
+            (Tool::Time, true) => tools::epoch_to_iso(&self.input),
+            (Tool::Time, false) => tools::iso_to_epoch(&self.input),
+            (Tool::Socket, _) => return,
+        });
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let mut tabs = row![].spacing(4);
+        for tool in Tool::ALL {
+            tabs = tabs.push(tool_tab(tool, tool == self.tool));
+        }
+
+        let (apply_label, reverse_label) = self.tool.button_labels();
+        let mut buttons = row![
+            button(text(apply_label).size(14))
+                .on_press_maybe((!self.socket_sending).then_some(Message::Apply))
+                .padding([6, 12])
+                .style(tools_button_style)
+        ]
+        .spacing(8);
+        if let Some(label) = reverse_label {
+            buttons = buttons.push(
+                button(text(label).size(14))
+                    .on_press(Message::Reverse)
+                    .padding([6, 12])
+                    .style(tools_button_style),
+            );
+        }
+        if self.tool == Tool::Time {
+            buttons = buttons.push(
+                button(text(tr("tools-now")).size(14))
+                    .on_press(Message::UseCurrentTime)
+                    .padding([6, 12])
+                    .style(tools_button_style),
+            );
+        }
+
+        let input: Element<'_, Message> = if self.tool == Tool::Uuid {
+            space().height(0).into()
+        } else {
+            text_input(self.tool.placeholder(), &self.input)
+                .on_input(Message::InputChanged)
+                .on_submit(Message::Apply)
+                .padding(8)
+                .size(14)
+                .font(Font::MONOSPACE)
+                .into()
+        };
+
+        let socket_settings: Element<'_, Message> = if self.tool == Tool::Socket {
+            let protocols = [SocketProtocol::Tcp, SocketProtocol::Udp]
+                .into_iter()
+                .fold(row![].spacing(4), |protocols, protocol| {
+                    protocols.push(toggle_button(
+                        protocol.to_string(),
+                        protocol == self.socket_protocol,
+                        Message::SocketProtocolSelected(protocol),
+                    ))
+                });
+            row![
+                protocols,
+                text_input("host:port", &self.socket_address)
+                    .on_input(Message::SocketAddressChanged)
+                    .on_submit(Message::Apply)
+                    .padding(6)
+                    .size(14)
+                    .font(Font::MONOSPACE),
+                checkbox(self.socket_hex)
+                    .label("Hex")
+                    .size(14)
+                    .text_size(13)
+                    .on_toggle(Message::SocketHexToggled),
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center)
+            .into()
+        } else {
+            space().height(0).into()
+        };
+
+        if self.socket_sending {
+            buttons = buttons.push(text(tr("tools-waiting")).size(13));
+        } else if let Some(summary) = &self.socket_summary {
+            buttons = buttons.push(
+                text(summary)
+                    .size(13)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5)),
+            );
+        }
+        let buttons = buttons.align_y(iced::Alignment::Center);
+
+        let output: Element<'_, Message> = match (&self.output, &self.jwt) {
+            (Some(Ok(_)), Some(jwt)) => jwt_view(jwt),
+            (Some(Ok(output)), None) => output_box(text(output).size(13).font(Font::MONOSPACE)),
+            (Some(Err(e)), _) => text(e)
+                .size(14)
+                .color(Color::from_rgb(0.82, 0.06, 0.22))
+                .into(),
+            (None, _) => space().height(0).into(),
+        };
+
+        let footer = row![
+            space().width(Length::Fill),
+            button(text(tr("tools-copy-output")).size(16))
+                .on_press_maybe(matches!(self.output, Some(Ok(_))).then_some(Message::CopyOutput))
+                .padding(10)
+                .style(tools_button_style),
+            button(text(tr("close")).size(16))
+                .on_press(Message::Close)
+                .padding(10)
+                .style(tools_button_style),
+        ]
+        .spacing(10);
+
+        container(column![
+                text(tr("tools-title")).size(18),
+                tabs,
+                socket_settings,
+                input,
+                buttons,
+                output,
+                footer
+            ].spacing(12))
+            .padding(20)
+            .style(|_theme| Style {
+                background: Some(Background::Color(Color::WHITE)),
+                border: Border {
+                    color: Color::from_rgb(0.7, 0.7, 0.7),
+                    width: 1.0,
+                    radius: 8.0.into(),
+                },
+                shadow: Shadow {
+                    color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                    offset: Vector::new(0.0, 4.0),
+                    blur_radius: 10.0,
+                },
+                ..Style::default()
+            })
+            .into()
+    }
+}
+
+fn jwt_view(jwt: &DecodedJwt) -> Element<'_, Message> {
+    let format_time = |timestamp: i64| {
+        tools::epoch_to_iso(&timestamp.to_string()).unwrap_or_else(|_| timestamp.to_string())
+    };
+
+    let mut details = column![].spacing(4);
+    if let Some(issued_at) = jwt.issued_at {
+        details = details.push(text(format!("Issued {}", format_time(issued_at))).size(13));
+    }
+    if let Some(expires_at) = jwt.expires_at {
+        let (label, color) = if jwt.is_expired() {
+            ("Expired", Color::from_rgb(0.82, 0.06, 0.22))
+        } else {
+            ("Expires", Color::from_rgb(0.25, 0.63, 0.17))
+        };
+        details = details.push(
+            text(format!("{} {}", label, format_time(expires_at)))
+                .size(13)
+                .color(color),
+        );
+    }
+
+    column![
+        text(tr("tools-jwt-header")).size(14),
+        output_box(text(&jwt.header).size(13).font(Font::MONOSPACE)),
+        text(tr("tools-jwt-payload")).size(14),
+        output_box(text(&jwt.payload).size(13).font(Font::MONOSPACE)),
+        details,
+        text(tr("tools-jwt-unverified"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    ]
+    .spacing(6)
+    .into()
+}
+
+fn output_box<'a>(content: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
+    container(scrollable(
+        container(content).padding(8).width(Length::Fill),
+    ))
+    .max_height(220)
+    .width(Length::Fill)
+    .style(|_theme| Style {
+        background: Some(Background::Color(Color::from_rgb(0.97, 0.97, 0.97))),
+        border: Border {
+            color: Color::from_rgb(0.85, 0.85, 0.85),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..Style::default()
+    })
+    .into()
+}
+
+fn tool_tab<'a>(tool: Tool, selected: bool) -> Element<'a, Message> {
+    toggle_button(tool.label().to_string(), selected, Message::ToolSelected(tool))
+}
+
+fn toggle_button<'a>(label: String, selected: bool, message: Message) -> Element<'a, Message> {
+    button(text(label).size(13))
+        .on_press(message)
+        .padding([4, 10])
+        .style(move |theme, status| {
+            let style = tools_button_style(theme, status);
+            if selected {
+                button::Style {
+                    background: Some(Background::Color(Color::from_rgb(0.85, 0.9, 1.0))),
+                    ..style
+                }
+            } else {
+                style
+            }
+        })
+        .into()
+}
+
+fn tools_button_style(_theme: &iced::Theme, status: Status) -> button::Style {
+    let base = button::Style {
+        text_color: Color::from_rgb(0.2, 0.2, 0.2),
+        border: Border {
+            color: Color::from_rgb(0.8, 0.8, 0.8),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..button::Style::default()
+    };
+
+    match status {
+        Status::Hovered => button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 0.9))),
+            ..base
+        },
+        Status::Disabled => button::Style {
+            text_color: Color::from_rgb(0.6, 0.6, 0.6),
+            ..base
+        },
+        _ => base,
+    }
+}
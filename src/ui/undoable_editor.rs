@@ -1,4 +1,5 @@
 use crate::history::{Command, TextEditorCommand};
+use crate::search::Pattern;
 use crate::ui::editor_view::{Action as UndoableAction, EditorView};
 use iced::advanced::text;
 use iced::widget::text_editor;
@@ -11,6 +12,10 @@ pub enum Message {
     Redo,
     Find,
     ScrollToMatch(f32),
+    DuplicateLine,
+    MoveLineUp,
+    MoveLineDown,
+    ToggleComment,
 }
 
 #[derive(Debug, Clone)]
@@ -22,6 +27,8 @@ pub struct UndoableEditor {
     /// Track cursor and anchor as positions
     cursor: text_editor::Position,
     anchor: text_editor::Position,
+    /// Line comment marker used by `ToggleComment`, none disables it
+    comment_prefix: Option<&'static str>,
 }
 
 impl UndoableEditor {
@@ -34,6 +41,7 @@ impl UndoableEditor {
             version: 0,
             cursor: pos,
             anchor: pos,
+            comment_prefix: None,
         }
     }
 
@@ -46,6 +54,7 @@ impl UndoableEditor {
             version: 0,
             cursor: pos,
             anchor: pos,
+            comment_prefix: None,
         }
     }
 
@@ -54,6 +63,38 @@ impl UndoableEditor {
         self
     }
 
+    pub fn comment_prefix(mut self, prefix: &'static str) -> Self {
+        self.comment_prefix = Some(prefix);
+        self
+    }
+
+    /// Brings the baseline in line with text that was changed outside the editor (formatting,
+    /// a reloaded request), recording it as one undoable replacement. Without this the next
+    /// keystroke would be diffed against stale text.
+    pub fn sync_text(
+        &mut self,
+        text: &str,
+        history_registry: &mut crate::history::HistoryRegistry,
+    ) {
+        if self.rope == text {
+            return;
+        }
+
+        let cmd = TextEditorCommand::Replace {
+            at: 0,
+            old: self.rope.to_string(),
+            new: text.to_string(),
+            cursor_before: 0,
+            cursor_after: 0,
+            timestamp: std::time::Instant::now(),
+        };
+        history_registry
+            .get_or_create_editor(self.id.clone())
+            .push(cmd);
+        self.rope = ropey::Rope::from_str(text);
+        self.version += 1;
+    }
+
     /// Helper to convert a `text_editor::Position` to a char offset in the `Rope`.
     fn pos_to_char(&self, pos: text_editor::Position) -> usize {
         let n_lines = self.rope.len_lines();
@@ -305,17 +346,193 @@ impl UndoableEditor {
                     None
                 }
             }
+            Message::DuplicateLine => self.duplicate_lines(content, history),
+            Message::MoveLineUp => self.move_lines(content, history, true),
+            Message::MoveLineDown => self.move_lines(content, history, false),
+            Message::ToggleComment => self.toggle_comment(content, history),
             Message::Find => None,
             Message::ScrollToMatch(_) => None,
         }
     }
 
+    /// First and last line touched by the cursor or selection, with the char range they
+    /// span including the trailing newline when there is one
+    fn selected_lines(
+        &mut self,
+        content: &text_editor::Content,
+    ) -> (usize, usize, std::ops::Range<usize>) {
+        let cursor = content.cursor();
+        self.cursor = cursor.position;
+        self.anchor = cursor.selection.unwrap_or(cursor.position);
+
+        let last_line = self.rope.len_lines().saturating_sub(1);
+        let first = self.cursor.line.min(self.anchor.line).min(last_line);
+        let last = self.cursor.line.max(self.anchor.line).min(last_line);
+
+        (
+            first,
+            last,
+            self.line_start(first)..self.line_start(last + 1),
+        )
+    }
+
+    fn line_start(&self, line: usize) -> usize {
+        if line < self.rope.len_lines() {
+            self.rope.line_to_char(line)
+        } else {
+            self.rope.len_chars()
+        }
+    }
+
+    /// Replaces `range` with `new` as one undoable step, then moves the cursor and selection
+    /// anchor down by `line_shift` lines (or up when negative)
+    fn replace_range(
+        &mut self,
+        content: &mut text_editor::Content,
+        history: &mut crate::history::History<TextEditorCommand>,
+        range: std::ops::Range<usize>,
+        new: String,
+        line_shift: isize,
+    ) -> Option<String> {
+        let shift = |pos: text_editor::Position| text_editor::Position {
+            line: pos.line.saturating_add_signed(line_shift),
+            column: pos.column,
+        };
+        let (cursor, anchor) = (shift(self.cursor), shift(self.anchor));
+
+        let mut cmd = TextEditorCommand::Replace {
+            at: range.start,
+            old: self.rope.slice(range.clone()).to_string(),
+            new,
+            cursor_before: self.pos_to_char(self.cursor),
+            cursor_after: 0,
+            timestamp: std::time::Instant::now(),
+        };
+        cmd.execute(&mut self.rope);
+        if let TextEditorCommand::Replace { cursor_after, .. } = &mut cmd {
+            *cursor_after = self.pos_to_char(cursor);
+        }
+        history.push(cmd);
+
+        let rope_str = self.rope.to_string();
+        content.perform(text_editor::Action::SelectAll);
+        content.perform(text_editor::Action::Edit(text_editor::Edit::Paste(
+            std::sync::Arc::new(rope_str.clone()),
+        )));
+        content.move_to(text_editor::Cursor {
+            position: cursor,
+            selection: (anchor != cursor).then_some(anchor),
+        });
+        self.cursor = cursor;
+        self.anchor = anchor;
+        self.version += 1;
+
+        Some(rope_str)
+    }
+
+    fn duplicate_lines(
+        &mut self,
+        content: &mut text_editor::Content,
+        history: &mut crate::history::History<TextEditorCommand>,
+    ) -> Option<String> {
+        let (first, last, range) = self.selected_lines(content);
+        let block = self.rope.slice(range.clone()).to_string();
+
+        // The last line has no newline of its own, so the copy goes after a new one
+        let copy = if block.ends_with('\n') {
+            format!("{block}{block}")
+        } else {
+            format!("{block}\n{block}")
+        };
+
+        self.replace_range(content, history, range, copy, (last - first + 1) as isize)
+    }
+
+    fn move_lines(
+        &mut self,
+        content: &mut text_editor::Content,
+        history: &mut crate::history::History<TextEditorCommand>,
+        up: bool,
+    ) -> Option<String> {
+        let (first, last, range) = self.selected_lines(content);
+
+        let (neighbour, block, replaced) = if up {
+            if first == 0 {
+                return None;
+            }
+            let neighbour = self.line_start(first - 1)..range.start;
+            (neighbour.clone(), range.clone(), neighbour.start..range.end)
+        } else {
+            if range.end >= self.rope.len_chars() {
+                return None;
+            }
+            let neighbour = range.end..self.line_start(last + 2);
+            (neighbour.clone(), range.clone(), range.start..neighbour.end)
+        };
+
+        let neighbour = self.rope.slice(neighbour).to_string();
+        let block = self.rope.slice(block).to_string();
+
+        // Whichever line ends up last keeps the missing trailing newline of the document
+        let (top, bottom) = if up {
+            (block, neighbour)
+        } else {
+            (neighbour, block)
+        };
+        let swapped = if top.ends_with('\n') {
+            format!("{top}{bottom}")
+        } else {
+            format!("{top}\n{}", bottom.strip_suffix('\n').unwrap_or(&bottom))
+        };
+
+        self.replace_range(content, history, replaced, swapped, if up { -1 } else { 1 })
+    }
+
+    /// Comments the selected lines out, or back in when all of them are already commented
+    fn toggle_comment(
+        &mut self,
+        content: &mut text_editor::Content,
+        history: &mut crate::history::History<TextEditorCommand>,
+    ) -> Option<String> {
+        let prefix = self.comment_prefix?;
+        let (_, _, range) = self.selected_lines(content);
+        let block = self.rope.slice(range.clone()).to_string();
+
+        let lines: Vec<&str> = block.split_inclusive('\n').collect();
+        let code_lines = || lines.iter().filter(|line| !line.trim().is_empty());
+        let trim_indent = |line: &'_ str| line.trim_start_matches([' ', '\t']).len();
+        let uncomment =
+            code_lines().all(|line| line[line.len() - trim_indent(line)..].starts_with(prefix));
+        let indent = code_lines()
+            .map(|line| line.len() - trim_indent(line))
+            .min()
+            .unwrap_or(0);
+
+        let toggled: String = lines
+            .iter()
+            .map(|line| {
+                if line.trim().is_empty() {
+                    line.to_string()
+                } else if uncomment {
+                    let rest = line.trim_start_matches([' ', '\t']);
+                    let code = &rest[prefix.len()..];
+                    let code = code.strip_prefix(' ').unwrap_or(code);
+                    format!("{}{}", &line[..line.len() - rest.len()], code)
+                } else {
+                    format!("{}{} {}", &line[..indent], prefix, &line[indent..])
+                }
+            })
+            .collect();
+
+        self.replace_range(content, history, range, toggled, 0)
+    }
+
     pub fn view<'a>(
         &'a self,
         _editor_id: impl Into<iced::widget::Id>,
         content: &'a text_editor::Content,
         syntax: Option<&'a str>,
-        search_query: Option<&'a str>,
+        search_pattern: Option<Pattern>,
         search_active_match: Option<(text_editor::Position, text_editor::Position)>,
     ) -> Element<'a, Message> {
         let editor_id = _editor_id.into();
@@ -356,7 +573,7 @@ impl UndoableEditor {
         Self::wrap_in_undoable(
             editor,
             content,
-            search_query,
+            search_pattern,
             search_active_match,
             self.version,
         )
@@ -379,7 +596,7 @@ impl UndoableEditor {
     fn wrap_in_undoable<'a>(
         editor: impl Into<Element<'a, Message>>,
         content: &'a text_editor::Content,
-        search_query: Option<&'a str>,
+        search_pattern: Option<Pattern>,
         search_active_match: Option<(text_editor::Position, text_editor::Position)>,
         version: usize,
     ) -> Element<'a, Message> {
@@ -388,6 +605,10 @@ impl UndoableEditor {
             UndoableAction::Redo => Message::Redo,
             UndoableAction::Find => Message::Find,
             UndoableAction::ScrollToMatch(y) => Message::ScrollToMatch(y),
+            UndoableAction::DuplicateLine => Message::DuplicateLine,
+            UndoableAction::MoveLineUp => Message::MoveLineUp,
+            UndoableAction::MoveLineDown => Message::MoveLineDown,
+            UndoableAction::ToggleComment => Message::ToggleComment,
         })
         .content_ref(content)
         .search_active_match(search_active_match)
@@ -397,8 +618,8 @@ impl UndoableEditor {
         .padding(5.0)
         .padding_right(20.0);
 
-        if let Some(query) = search_query {
-            view = view.search_query(query.to_string());
+        if let Some(pattern) = search_pattern {
+            view = view.search_pattern(pattern);
         }
 
         view.into()
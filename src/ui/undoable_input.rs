@@ -1,4 +1,4 @@
-use crate::history::diff_to_command;
+use crate::history::{TextInputCommand, diff_to_command};
 use crate::ui::editor_view::{Action as UndoableAction, EditorView};
 use iced::widget::text_input;
 use iced::{Background, Border, Color, Element, Length, Theme};
@@ -6,6 +6,8 @@ use iced::{Background, Border, Color, Element, Length, Theme};
 #[derive(Debug, Clone)]
 pub enum Message {
     Changed(String),
+    // Same as Changed, lets the owner look at the clipboard for what was pasted
+    Pasted(String),
     Undo,
     Redo,
     None,
@@ -68,6 +70,28 @@ impl UndoableInput {
         self.value = value;
     }
 
+    /// Sync the baseline with a value changed outside the input (e.g. editing query params),
+    /// recording the change as its own step so typing right after it isn't merged into it.
+    pub fn sync_value(
+        &mut self,
+        value: &str,
+        history_registry: &mut crate::history::HistoryRegistry,
+    ) {
+        if self.value == value {
+            return;
+        }
+
+        history_registry
+            .get_or_create_input(self.id.clone())
+            .push(TextInputCommand::Replace {
+                at: 0,
+                at_char: 0,
+                old: std::mem::replace(&mut self.value, value.to_string()),
+                new: value.to_string(),
+                timestamp: std::time::Instant::now(),
+            });
+    }
+
     /// Update the component with a message.
     /// Returns (Some(new_value), Task) if the value changed.
     pub fn update(
@@ -78,7 +102,7 @@ impl UndoableInput {
         let history = history_registry.get_or_create_input(self.id.clone());
 
         match message {
-            Message::Changed(new_value) => {
+            Message::Changed(new_value) | Message::Pasted(new_value) => {
                 if let Some(cmd) = diff_to_command(&self.value, &new_value) {
                     history.push(cmd);
                     self.value = new_value.clone();
@@ -119,6 +143,7 @@ impl UndoableInput {
         let input = text_input(&self.placeholder, value)
             .id(self.id.clone())
             .on_input(Message::Changed)
+            .on_paste(Message::Pasted)
             .size(self.size)
             .padding(self.padding)
             .width(Length::Fill)
@@ -142,9 +167,56 @@ impl UndoableInput {
         EditorView::new(input, |action| match action {
             UndoableAction::Undo => Message::Undo,
             UndoableAction::Redo => Message::Redo,
-            UndoableAction::Find => Message::None,
-            UndoableAction::ScrollToMatch(_) => Message::None,
+            _ => Message::None,
         })
         .into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::HistoryRegistry;
+
+    fn url_input(value: &str) -> UndoableInput {
+        UndoableInput::new(
+            iced::widget::Id::new("test-url-input"),
+            value.to_string(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn first_keystroke_undo_keeps_loaded_value() {
+        let mut registry = HistoryRegistry::new();
+        let mut input = url_input("");
+        input.set_value("https://example.com".to_string());
+
+        let _ = input.update(
+            Message::Changed("https://example.com/".to_string()),
+            &mut registry,
+        );
+        let (value, _) = input.update(Message::Undo, &mut registry);
+
+        assert_eq!(value.as_deref(), Some("https://example.com"));
+    }
+
+    #[test]
+    fn external_change_is_undoable_and_becomes_the_baseline() {
+        let mut registry = HistoryRegistry::new();
+        let mut input = url_input("https://example.com");
+
+        input.sync_value("https://example.com?page=1", &mut registry);
+        let _ = input.update(
+            Message::Changed("https://example.com?page=12".to_string()),
+            &mut registry,
+        );
+
+        let (value, _) = input.update(Message::Undo, &mut registry);
+        assert_eq!(value.as_deref(), Some("https://example.com?page=1"));
+        let (value, _) = input.update(Message::Undo, &mut registry);
+        assert_eq!(value.as_deref(), Some("https://example.com"));
+        let (value, _) = input.update(Message::Redo, &mut registry);
+        assert_eq!(value.as_deref(), Some("https://example.com?page=1"));
+    }
+}
@@ -0,0 +1,346 @@
+use crate::i18n::tr;
+use crate::ui::response::format_bytes;
+use crate::webhook::{CapturedRequest, WebhookInbox};
+use iced::widget::button::Status;
+use iced::widget::container::Style;
+use iced::widget::{button, column, container, row, scrollable, space, text, text_input};
+use iced::{Background, Border, Color, Element, Font, Length, Shadow, Vector};
+
+/// Older requests are dropped past this many
+const MAX_CAPTURED: usize = 200;
+
+const DEFAULT_PORT: &str = "8787";
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    CopyToClipboard(String),
+    Close,
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    PortChanged(String),
+    Start,
+    Stop,
+    /// Picks up the requests the listener received since the last poll
+    Poll,
+    RequestSelected(usize),
+    Clear,
+    CopyUrl,
+    CopyBody,
+    Close,
+}
+
+/// Captures the requests sent to a local listener, to test the webhooks a service sends.
+/// The listener keeps running when the panel is closed, until it's stopped.
+#[derive(Debug)]
+pub struct WebhookPanel {
+    port: String,
+    inbox: Option<WebhookInbox>,
+    error: Option<String>,
+    /// Newest first
+    requests: Vec<CapturedRequest>,
+    selected: usize,
+}
+
+impl Default for WebhookPanel {
+    fn default() -> Self {
+        Self {
+            port: DEFAULT_PORT.to_string(),
+            inbox: None,
+            error: None,
+            requests: Vec::new(),
+            selected: 0,
+        }
+    }
+}
+
+impl WebhookPanel {
+    pub fn is_listening(&self) -> bool {
+        self.inbox.is_some()
+    }
+
+    pub fn update(&mut self, message: Message) -> Action {
+        match message {
+            Message::PortChanged(port) => {
+                if port.chars().all(|c| c.is_ascii_digit()) {
+                    self.port = port;
+                }
+            }
+            Message::Start => {
+                let port = self.port.trim().parse::<u16>().unwrap_or(0);
+                match WebhookInbox::start(port) {
+                    Ok(inbox) => {
+                        self.port = inbox.port().to_string();
+                        self.inbox = Some(inbox);
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+            }
+            Message::Stop => self.inbox = None,
+            Message::Poll => {
+                if let Some(inbox) = &self.inbox {
+                    let received = inbox.take_received();
+                    if !received.is_empty() {
+                        // Keep the selection on the same request
+                        if !self.requests.is_empty() {
+                            self.selected += received.len();
+                        }
+                        for request in received {
+                            self.requests.insert(0, request);
+                        }
+                        self.requests.truncate(MAX_CAPTURED);
+                        self.selected = self.selected.min(self.requests.len() - 1);
+                    }
+                }
+            }
+            Message::RequestSelected(index) => self.selected = index,
+            Message::Clear => {
+                self.requests.clear();
+                self.selected = 0;
+            }
+            Message::CopyUrl => {
+                if let Some(inbox) = &self.inbox {
+                    return Action::CopyToClipboard(inbox.url());
+                }
+            }
+            Message::CopyBody => {
+                if let Some(request) = self.requests.get(self.selected) {
+                    return Action::CopyToClipboard(request.body.clone());
+                }
+            }
+            Message::Close => return Action::Close,
+        }
+
+        Action::None
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let listener: Element<'_, Message> = match &self.inbox {
+            Some(inbox) => row![
+                text(tr("webhook-listening")).size(14),
+                text(inbox.url()).size(14).font(Font::MONOSPACE),
+                button(text(tr("webhook-copy-url")).size(13))
+                    .on_press(Message::CopyUrl)
+                    .padding([4, 10])
+                    .style(webhook_button_style),
+                space().width(Length::Fill),
+                button(text(tr("stop")).size(13))
+                    .on_press(Message::Stop)
+                    .padding([4, 10])
+                    .style(webhook_button_style),
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center)
+            .into(),
+            None => row![
+                text(tr("webhook-port")).size(14),
+                text_input(DEFAULT_PORT, &self.port)
+                    .on_input(Message::PortChanged)
+                    .on_submit(Message::Start)
+                    .padding(6)
+                    .size(14)
+                    .width(Length::Fixed(90.0)),
+                button(text(tr("webhook-start")).size(13))
+                    .on_press(Message::Start)
+                    .padding([4, 10])
+                    .style(webhook_button_style),
+                text(tr("webhook-hint"))
+                    .size(12)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5)),
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center)
+            .into(),
+        };
+
+        let error = self.error.as_ref().map(|error| {
+            text(error)
+                .size(13)
+                .color(Color::from_rgb(0.82, 0.06, 0.22))
+        });
+
+        let mut list = column![].spacing(2);
+        for (index, request) in self.requests.iter().enumerate() {
+            list = list.push(request_row(request, index, index == self.selected));
+        }
+        let list: Element<'_, Message> = if self.requests.is_empty() {
+            text(if self.inbox.is_some() {
+                "Waiting for requests..."
+            } else {
+                "Start listening, then point the service at the URL."
+            })
+            .size(13)
+            .color(Color::from_rgb(0.5, 0.5, 0.5))
+            .into()
+        } else {
+            scrollable(list).height(Length::Fill).into()
+        };
+
+        let details: Element<'_, Message> = match self.requests.get(self.selected) {
+            Some(request) => request_details(request),
+            None => space().into(),
+        };
+
+        let footer = row![
+            button(text(tr("webhook-clear")).size(16))
+                .on_press_maybe((!self.requests.is_empty()).then_some(Message::Clear))
+                .padding(10)
+                .style(webhook_button_style),
+            space().width(Length::Fill),
+            button(text(tr("webhook-copy-body")).size(16))
+                .on_press_maybe(self.requests.get(self.selected).map(|_| Message::CopyBody))
+                .padding(10)
+                .style(webhook_button_style),
+            button(text(tr("close")).size(16))
+                .on_press(Message::Close)
+                .padding(10)
+                .style(webhook_button_style),
+        ]
+        .spacing(10);
+
+        container(
+            column![
+                text(tr("webhook-title")).size(18),
+                listener,
+                column![].push(error),
+                row![
+                    container(list).width(Length::Fixed(260.0)),
+                    container(details).width(Length::Fill),
+                ]
+                .spacing(12)
+                .height(Length::Fill),
+                footer,
+            ]
+            .spacing(12),
+        )
+        .padding(20)
+        .height(Length::Fill)
+        .style(|_theme| Style {
+            background: Some(Background::Color(Color::WHITE)),
+            border: Border {
+                color: Color::from_rgb(0.7, 0.7, 0.7),
+                width: 1.0,
+                radius: 8.0.into(),
+            },
+            shadow: Shadow {
+                color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                offset: Vector::new(0.0, 4.0),
+                blur_radius: 10.0,
+            },
+            ..Style::default()
+        })
+        .into()
+    }
+}
+
+fn request_row(request: &CapturedRequest, index: usize, selected: bool) -> Element<'_, Message> {
+    let time = chrono::DateTime::parse_from_rfc3339(&request.received_at)
+        .map(|time| time.format("%H:%M:%S").to_string())
+        .unwrap_or_default();
+
+    button(
+        column![
+            row![
+                text(&request.method).size(13).font(Font::MONOSPACE),
+                text(&request.target).size(13),
+            ]
+            .spacing(6),
+            text(format!("{}  {}", time, format_bytes(request.size)))
+                .size(11)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        ]
+        .spacing(2),
+    )
+    .on_press(Message::RequestSelected(index))
+    .width(Length::Fill)
+    .padding([4, 8])
+    .style(move |theme, status| {
+        let style = webhook_button_style(theme, status);
+        if selected {
+            button::Style {
+                background: Some(Background::Color(Color::from_rgb(0.85, 0.9, 1.0))),
+                ..style
+            }
+        } else {
+            style
+        }
+    })
+    .into()
+}
+
+fn request_details(request: &CapturedRequest) -> Element<'_, Message> {
+    let received_at = chrono::DateTime::parse_from_rfc3339(&request.received_at)
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+    let mut summary = format!(
+        "{} from {}, {} in {} ms",
+        received_at,
+        request.remote,
+        format_bytes(request.size),
+        request.duration.as_millis()
+    );
+    if request.truncated {
+        summary.push_str(", incomplete");
+    }
+
+    let mut headers = column![].spacing(2);
+    for (key, value) in &request.headers {
+        headers = headers.push(
+            row![
+                text(format!("{}:", key)).size(12).font(Font::MONOSPACE),
+                text(value).size(12).font(Font::MONOSPACE),
+            ]
+            .spacing(6),
+        );
+    }
+
+    // JSON payloads are easier to read indented
+    let body = serde_json::from_str::<serde_json::Value>(&request.body)
+        .ok()
+        .and_then(|json| serde_json::to_string_pretty(&json).ok())
+        .unwrap_or_else(|| request.body.clone());
+
+    scrollable(
+        column![
+            text(format!("{} {}", request.method, request.target))
+                .size(15)
+                .font(Font::MONOSPACE),
+            text(summary).size(12).color(Color::from_rgb(0.5, 0.5, 0.5)),
+            text(tr("tab-headers")).size(14),
+            headers,
+            text(tr("tab-body")).size(14),
+            text(body).size(12).font(Font::MONOSPACE),
+        ]
+        .spacing(8)
+        .width(Length::Fill),
+    )
+    .height(Length::Fill)
+    .into()
+}
+
+fn webhook_button_style(_theme: &iced::Theme, status: Status) -> button::Style {
+    let base = button::Style {
+        text_color: Color::from_rgb(0.2, 0.2, 0.2),
+        border: Border {
+            color: Color::from_rgb(0.8, 0.8, 0.8),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..button::Style::default()
+    };
+
+    match status {
+        Status::Hovered => button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 0.9))),
+            ..base
+        },
+        Status::Disabled => button::Style {
+            text_color: Color::from_rgb(0.6, 0.6, 0.6),
+            ..base
+        },
+        _ => base,
+    }
+}
@@ -0,0 +1,92 @@
+use crate::i18n::tr;
+use iced::widget::button::Status;
+use iced::widget::container::Style;
+use iced::widget::{button, column, container, row, text};
+use iced::{Background, Border, Color, Element, Length};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    CreateSampleCollection,
+    ImportExisting,
+    StartBlank,
+}
+
+/// First-run view shown while there are no collections
+pub fn view<'a>() -> Element<'a, Message> {
+    let choice = |title: String, description: String, message: Message| {
+        button(
+            column![
+                text(title).size(16),
+                text(description)
+                    .size(13)
+                    .color(Color::from_rgb(0.45, 0.45, 0.45)),
+            ]
+            .spacing(6),
+        )
+        .on_press(message)
+        .padding(16)
+        .width(Length::Fixed(220.0))
+        .height(Length::Fixed(110.0))
+        .style(choice_button_style)
+    };
+
+    container(
+        column![
+            text(tr("welcome-title")).size(28),
+            text(tr("welcome-subtitle"))
+                .size(15)
+                .color(Color::from_rgb(0.4, 0.4, 0.4)),
+            row![
+                choice(
+                    tr("welcome-sample"),
+                    tr("welcome-sample-description"),
+                    Message::CreateSampleCollection,
+                ),
+                choice(
+                    tr("import"),
+                    tr("welcome-import-description"),
+                    Message::ImportExisting,
+                ),
+                choice(
+                    tr("welcome-blank"),
+                    tr("welcome-blank-description"),
+                    Message::StartBlank,
+                ),
+            ]
+            .spacing(16),
+        ]
+        .spacing(20)
+        .align_x(iced::Alignment::Center),
+    )
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .style(|_theme| Style {
+        background: Some(Background::Color(Color::from_rgb(0.980, 0.980, 0.980))),
+        ..Style::default()
+    })
+    .into()
+}
+
+fn choice_button_style(_theme: &iced::Theme, status: Status) -> button::Style {
+    let base = button::Style {
+        background: Some(Background::Color(Color::WHITE)),
+        text_color: Color::from_rgb(0.2, 0.2, 0.2),
+        border: Border {
+            color: Color::from_rgb(0.85, 0.85, 0.85),
+            width: 1.0,
+            radius: 8.0.into(),
+        },
+        ..button::Style::default()
+    };
+
+    match status {
+        Status::Hovered => button::Style {
+            border: Border {
+                color: Color::from_rgb(0.51, 0.55, 0.97),
+                ..base.border
+            },
+            ..base
+        },
+        _ => base,
+    }
+}
@@ -0,0 +1,124 @@
+use crate::types::{EnvironmentVariable, RequestCollection, RequestConfig};
+
+/// Structural changes kept for undo, older ones are dropped
+const MAX_COMMANDS: usize = 100;
+
+/// A change to the structure of the workspace, holding what's needed to reverse it. Edits to
+/// text have their own undo in the inputs and editors.
+#[derive(Debug, Clone)]
+pub enum WorkspaceCommand {
+    DeleteRequest {
+        collection_id: String,
+        position: usize, // Index of the request in the collection
+        request: Box<RequestConfig>,
+    },
+    RenameRequest {
+        request_id: String,
+        from: String,
+        to: String,
+    },
+    DeleteCollection {
+        position: usize,
+        collection: RequestCollection,
+    },
+    RenameCollection {
+        collection_id: String,
+        from: String,
+        to: String,
+    },
+    DeleteVariable {
+        environment: String, // Name of the environment
+        key: String,
+        variable: EnvironmentVariable,
+    },
+}
+
+impl WorkspaceCommand {
+    /// What undoing the change does, e.g. `Restored request "List users"`
+    pub fn undo_summary(&self) -> String {
+        match self {
+            WorkspaceCommand::DeleteRequest { request, .. } => {
+                format!("Restored request \"{}\"", request.name)
+            }
+            WorkspaceCommand::RenameRequest { from, to, .. } => {
+                format!("Renamed request \"{}\" back to \"{}\"", to, from)
+            }
+            WorkspaceCommand::DeleteCollection { collection, .. } => {
+                format!("Restored collection \"{}\"", collection.name)
+            }
+            WorkspaceCommand::RenameCollection { from, to, .. } => {
+                format!("Renamed collection \"{}\" back to \"{}\"", to, from)
+            }
+            WorkspaceCommand::DeleteVariable {
+                environment, key, ..
+            } => format!("Restored variable \"{}\" in {}", key, environment),
+        }
+    }
+}
+
+/// The structural changes made in this session, the latest last
+#[derive(Debug, Clone, Default)]
+pub struct UndoStack {
+    commands: Vec<WorkspaceCommand>,
+}
+
+impl UndoStack {
+    pub fn push(&mut self, command: WorkspaceCommand) {
+        if self.commands.len() == MAX_COMMANDS {
+            self.commands.remove(0);
+        }
+        self.commands.push(command);
+    }
+
+    /// The latest change, removed from the stack to be reversed
+    pub fn pop(&mut self) -> Option<WorkspaceCommand> {
+        self.commands.pop()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename(to: &str) -> WorkspaceCommand {
+        WorkspaceCommand::RenameCollection {
+            collection_id: "c1".to_string(),
+            from: "Users".to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn undoes_the_latest_change_first_and_keeps_a_bounded_history() {
+        let mut stack = UndoStack::default();
+        for i in 0..MAX_COMMANDS + 5 {
+            stack.push(rename(&format!("Users {}", i)));
+        }
+
+        let latest = stack.pop().unwrap();
+        assert_eq!(
+            latest.undo_summary(),
+            format!(
+                "Renamed collection \"Users {}\" back to \"Users\"",
+                MAX_COMMANDS + 4
+            )
+        );
+
+        let mut remaining = 1;
+        let mut oldest = None;
+        while let Some(command) = stack.pop() {
+            remaining += 1;
+            oldest = Some(command);
+        }
+        assert_eq!(remaining, MAX_COMMANDS);
+        assert!(matches!(
+            oldest,
+            Some(WorkspaceCommand::RenameCollection { to, .. }) if to == "Users 5"
+        ));
+        assert!(stack.is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+/// Characters not allowed unencoded in a query, which some servers answer with a 400.
+/// Reserved characters such as `&`, `=` and `+` keep their meaning and are left alone.
+fn is_unsafe_in_query(c: char) -> bool {
+    !c.is_ascii() || c.is_ascii_control() || " \"<>`{}|\\^".contains(c)
+}
+
+fn is_escape(rest: &str) -> bool {
+    let mut hex = rest.chars().take(2);
+    matches!((hex.next(), hex.next()), (Some(a), Some(b)) if a.is_ascii_hexdigit() && b.is_ascii_hexdigit())
+}
+
+/// Splits a URL into the part before the query, the query and the fragment (with its `#`)
+fn split_query(url: &str) -> Option<(&str, &str, &str)> {
+    let (base, rest) = url.split_once('?')?;
+    let (query, fragment) = match rest.find('#') {
+        Some(index) => rest.split_at(index),
+        None => (rest, ""),
+    };
+    Some((base, query, fragment))
+}
+
+/// Percent-encodes what can't be sent as is in the query of `url`, as well as `%` signs
+/// that don't start an escape. Already encoded values are kept.
+pub fn encode_unsafe_query_chars(url: &str) -> String {
+    let Some((base, query, fragment)) = split_query(url) else {
+        return url.to_string();
+    };
+
+    let mut encoded = String::with_capacity(url.len());
+    for (index, c) in query.char_indices() {
+        if c == '%' && !is_escape(&query[index + 1..]) {
+            encoded.push_str("%25");
+        } else if is_unsafe_in_query(c) {
+            let mut buffer = [0; 4];
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            encoded.push(c);
+        }
+    }
+
+    format!("{}?{}{}", base, encoded, fragment)
+}
+
+/// Encodes every query value of `url` as a URL component, values already encoded stay the
+/// same. Values using `{{variables}}` are skipped so the variables keep working.
+pub fn encode_query_values(url: &str) -> String {
+    let Some((base, query, fragment)) = split_query(url) else {
+        return url.to_string();
+    };
+
+    let pairs: Vec<String> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) if !value.contains("{{") => {
+                let decoded = urlencoding::decode(value)
+                    .map(|value| value.into_owned())
+                    .unwrap_or_else(|_| value.to_string());
+                format!("{}={}", key, urlencoding::encode(&decoded))
+            }
+            _ => pair.to_string(),
+        })
+        .collect();
+
+    format!("{}?{}{}", base, pairs.join("&"), fragment)
+}
+
+/// Decodes percent escapes in the whole URL to make it readable, unchanged if the escapes
+/// aren't valid UTF-8
+pub fn decode_url(url: &str) -> String {
+    urlencoding::decode(url)
+        .map(|url| url.into_owned())
+        .unwrap_or_else(|_| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_only_unsafe_query_characters() {
+        assert_eq!(
+            encode_unsafe_query_chars("https://example.com/a b?q=a b|c&ok=100%&done=%20é#x y"),
+            "https://example.com/a b?q=a%20b%7Cc&ok=100%25&done=%20%C3%A9#x y"
+        );
+        assert_eq!(
+            encode_unsafe_query_chars("https://example.com/?a=1&b=x+y"),
+            "https://example.com/?a=1&b=x+y"
+        );
+    }
+
+    #[test]
+    fn encoding_query_values_is_idempotent() {
+        let url = "https://example.com/search?q=rust/iced é&page={{page}}&empty";
+        let encoded = encode_query_values(url);
+
+        assert_eq!(
+            encoded,
+            "https://example.com/search?q=rust%2Ficed%20%C3%A9&page={{page}}&empty"
+        );
+        assert_eq!(encode_query_values(&encoded), encoded);
+        assert_eq!(
+            decode_url(&encoded),
+            "https://example.com/search?q=rust/iced é&page={{page}}&empty"
+        );
+    }
+}
@@ -0,0 +1,408 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Requests with a larger head are rejected
+const MAX_HEAD_SIZE: usize = 64 * 1024;
+
+/// Bodies are cut off past this size
+const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// A sender that stops sending for this long gets whatever arrived so far
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often the listener checks whether it was stopped
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Connections read at the same time, each on its own thread. Others are turned away.
+const MAX_CONNECTIONS: usize = 16;
+
+const REPLY: &str = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 17\r\nConnection: close\r\n\r\n{\"received\":true}";
+
+const BUSY_REPLY: &[u8] =
+    b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+const BAD_CHUNK_REPLY: &[u8] =
+    b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+/// A request received by the inbox
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedRequest {
+    pub received_at: String, // RFC 3339
+    pub remote: String,
+    pub method: String,
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    pub is_binary: bool,
+    pub size: usize,
+    /// Time from accepting the connection to the end of the body
+    pub duration: Duration,
+    pub truncated: bool,
+}
+
+impl CapturedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A local HTTP listener answering every request with 200 and keeping it for display, to
+/// test the webhooks a service sends. Stops when dropped.
+#[derive(Debug)]
+pub struct WebhookInbox {
+    port: u16,
+    received: Arc<Mutex<Vec<CapturedRequest>>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl WebhookInbox {
+    /// Listens on `127.0.0.1:port`, a free port being picked for 0
+    pub fn start(port: u16) -> Result<WebhookInbox, String> {
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .map_err(|e| format!("Can't listen on port {}: {}", port, e))?;
+        let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (thread_received, thread_stopped) = (received.clone(), stopped.clone());
+        let connections = Arc::new(AtomicUsize::new(0));
+        std::thread::spawn(move || {
+            while !thread_stopped.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((mut stream, _)) => {
+                        let Some(slot) = ConnectionSlot::take(&connections) else {
+                            stream.write_all(BUSY_REPLY).ok();
+                            continue;
+                        };
+                        let received = thread_received.clone();
+                        std::thread::spawn(move || {
+                            let _slot = slot;
+                            if let Some(request) = handle_connection(stream)
+                                && let Ok(mut received) = received.lock()
+                            {
+                                received.push(request);
+                            }
+                        });
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                        std::thread::sleep(ACCEPT_INTERVAL)
+                    }
+                    Err(e) => {
+                        log::warn!("Webhook inbox stopped accepting: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(WebhookInbox {
+            port,
+            received,
+            stopped,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://localhost:{}/", self.port)
+    }
+
+    /// Requests received since the last call
+    pub fn take_received(&self) -> Vec<CapturedRequest> {
+        self.received
+            .lock()
+            .map(|mut received| std::mem::take(&mut *received))
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for WebhookInbox {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+/// One of the `MAX_CONNECTIONS` connections read at a time, given back when dropped
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    fn take(connections: &Arc<AtomicUsize>) -> Option<ConnectionSlot> {
+        connections
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < MAX_CONNECTIONS).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| ConnectionSlot(connections.clone()))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn handle_connection(mut stream: TcpStream) -> Option<CapturedRequest> {
+    let start = Instant::now();
+    let remote = stream
+        .peer_addr()
+        .map(|address| address.to_string())
+        .unwrap_or_default();
+    stream.set_nonblocking(false).ok()?;
+    stream.set_read_timeout(Some(READ_TIMEOUT)).ok()?;
+
+    // Read up to the blank line ending the head
+    let mut data = Vec::new();
+    let mut buffer = [0; 8192];
+    let head_end = loop {
+        if let Some(index) = data.windows(4).position(|window| window == b"\r\n\r\n") {
+            break index;
+        }
+        if data.len() > MAX_HEAD_SIZE {
+            stream
+                .write_all(b"HTTP/1.1 431 Request Header Fields Too Large\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .ok();
+            return None;
+        }
+        match stream.read(&mut buffer) {
+            Ok(0) | Err(_) => return None,
+            Ok(read) => data.extend_from_slice(&buffer[..read]),
+        }
+    };
+
+    let RequestHead {
+        method,
+        target,
+        headers,
+    } = parse_head(&String::from_utf8_lossy(&data[..head_end])).ok()?;
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim().to_ascii_lowercase())
+    };
+    let chunked = header("transfer-encoding").is_some_and(|value| value.contains("chunked"));
+    let content_length = header("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    if header("expect").is_some_and(|value| value == "100-continue") {
+        stream.write_all(b"HTTP/1.1 100 Continue\r\n\r\n").ok();
+    }
+
+    let mut body = data.split_off(head_end + 4);
+    let mut chunks = ChunkedBody::default();
+    let mut truncated = false;
+    loop {
+        let complete = if chunked {
+            match chunks.decode(&body) {
+                Ok(complete) => complete,
+                Err(e) => {
+                    log::warn!("Webhook inbox refused a chunked body: {}", e);
+                    stream.write_all(BAD_CHUNK_REPLY).ok();
+                    return None;
+                }
+            }
+        } else {
+            body.len() >= content_length
+        };
+        if complete {
+            break;
+        }
+        if body.len() >= MAX_BODY_SIZE {
+            truncated = true;
+            break;
+        }
+        match stream.read(&mut buffer) {
+            Ok(0) | Err(_) => {
+                truncated = true;
+                break;
+            }
+            Ok(read) => body.extend_from_slice(&buffer[..read]),
+        }
+    }
+    let duration = start.elapsed();
+    stream.write_all(REPLY.as_bytes()).ok();
+
+    let mut body = if chunked {
+        chunks.body
+    } else {
+        body.truncate(content_length.min(MAX_BODY_SIZE));
+        body
+    };
+    body.truncate(MAX_BODY_SIZE);
+    let size = body.len();
+    let (body, is_binary) = match String::from_utf8(body) {
+        Ok(text) => (text, false),
+        Err(e) => (format!("{} bytes of binary data", e.as_bytes().len()), true),
+    };
+
+    Some(CapturedRequest {
+        received_at: chrono::Local::now().to_rfc3339(),
+        remote,
+        method,
+        target,
+        headers,
+        body,
+        is_binary,
+        size,
+        duration,
+        truncated,
+    })
+}
+
+/// Request line and headers of a request
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestHead {
+    pub method: String,
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+}
+
+pub fn parse_head(head: &str) -> Result<RequestHead, String> {
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(format!("Invalid request line '{}'", request_line));
+    };
+    if !version.starts_with("HTTP/") {
+        return Err(format!("Invalid request line '{}'", request_line));
+    }
+
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    Ok(RequestHead {
+        method: method.to_string(),
+        target: target.to_string(),
+        headers,
+    })
+}
+
+/// A chunked body decoded as it arrives
+#[derive(Debug, Default)]
+struct ChunkedBody {
+    /// Chunks joined so far
+    body: Vec<u8>,
+    /// Bytes of the received data the chunks took up
+    consumed: usize,
+}
+
+impl ChunkedBody {
+    /// Decodes the chunks of `data` that complete since the last call, `data` being all
+    /// the body received so far. True once the last chunk arrived.
+    fn decode(&mut self, data: &[u8]) -> Result<bool, String> {
+        loop {
+            let rest = &data[self.consumed..];
+            let Some(line_end) = rest.windows(2).position(|window| window == b"\r\n") else {
+                return Ok(false);
+            };
+            let size_line = String::from_utf8_lossy(&rest[..line_end]);
+            let size_hex = size_line.split(';').next().unwrap_or_default().trim();
+            let size = usize::from_str_radix(size_hex, 16)
+                .map_err(|_| format!("Invalid chunk size '{}'", size_hex))?;
+
+            if size == 0 {
+                return Ok(true);
+            }
+            if size > MAX_BODY_SIZE - self.body.len() {
+                return Err(format!("Chunk of {} bytes is too large", size));
+            }
+            let chunk_end = line_end + 2 + size;
+            if rest.len() < chunk_end + 2 {
+                return Ok(false);
+            }
+            self.body.extend_from_slice(&rest[line_end + 2..chunk_end]);
+            self.consumed += chunk_end + 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_request_heads() {
+        let head = parse_head(
+            "POST /hooks/github?x=1 HTTP/1.1\r\nHost: localhost:9000\r\nX-GitHub-Event: push",
+        )
+        .unwrap();
+
+        assert_eq!(head.method, "POST");
+        assert_eq!(head.target, "/hooks/github?x=1");
+        assert_eq!(
+            head.headers[1],
+            ("X-GitHub-Event".to_string(), "push".to_string())
+        );
+        assert!(parse_head("GARBAGE").is_err());
+    }
+
+    #[test]
+    fn decodes_complete_chunked_bodies() {
+        let mut chunks = ChunkedBody::default();
+        assert_eq!(
+            chunks.decode(b"4\r\nWiki\r\n6;ext=1\r\npedia \r\n0\r\n\r\n"),
+            Ok(true)
+        );
+        assert_eq!(chunks.body, b"Wikipedia ".to_vec());
+        assert_eq!(
+            ChunkedBody::default().decode(b"4\r\nWiki\r\n6\r\nped"),
+            Ok(false)
+        );
+        assert!(
+            ChunkedBody::default()
+                .decode(b"ffffffffffffffff\r\nWiki\r\n0\r\n\r\n")
+                .is_err()
+        );
+
+        // Chunks arriving in pieces are each decoded once
+        let mut chunks = ChunkedBody::default();
+        let data = b"4\r\nWiki\r\n6\r\npedia \r\n0\r\n\r\n";
+        assert_eq!(chunks.decode(&data[..12]), Ok(false));
+        assert_eq!(chunks.consumed, 9);
+        assert_eq!(chunks.decode(data), Ok(true));
+        assert_eq!(chunks.body, b"Wikipedia ".to_vec());
+        assert!(ChunkedBody::default().decode(b"ffffffff\r\n").is_err());
+    }
+
+    #[test]
+    fn captures_posted_requests() {
+        let inbox = WebhookInbox::start(0).unwrap();
+        let mut stream = TcpStream::connect(("127.0.0.1", inbox.port())).unwrap();
+        stream
+            .write_all(b"POST /hook HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: 11\r\n\r\n{\"ok\":true}")
+            .unwrap();
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).unwrap();
+
+        assert!(reply.starts_with("HTTP/1.1 200 OK"));
+        // The request is stored once the connection closed
+        let mut received = Vec::new();
+        for _ in 0..50 {
+            received = inbox.take_received();
+            if !received.is_empty() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].target, "/hook");
+        assert_eq!(received[0].header("content-type"), Some("application/json"));
+        assert_eq!(received[0].body, "{\"ok\":true}");
+        assert!(inbox.take_received().is_empty());
+    }
+}